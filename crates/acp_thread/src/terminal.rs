@@ -20,10 +20,10 @@ use util::get_default_system_shell_preferring_bash;
 /// Request to run a terminal command inside an OS-level sandbox.
 ///
 /// Passed to [`super::AcpThread::create_terminal`]. The actual sandboxing
-/// mechanism is platform-specific (today: macOS Seatbelt; nothing on other
-/// platforms — the wrap is silently a no-op there), so callers describe the
-/// *intent* with plain data here rather than constructing platform-specific
-/// types directly.
+/// mechanism is platform-specific (today: macOS Seatbelt and Linux Landlock;
+/// nothing on other platforms — the wrap is silently a no-op there), so
+/// callers describe the *intent* with plain data here rather than
+/// constructing platform-specific types directly.
 ///
 /// All-zero defaults are the fully-sandboxed run. Setting `allow_network` /
 /// `allow_fs_write` requests a relaxation; the caller is responsible for
@@ -48,9 +48,9 @@ pub struct SandboxWrap {
 }
 
 /// Opaque RAII handle the sandbox implementation hands back to keep its
-/// per-command resources (e.g. an on-disk Seatbelt config file) alive for
-/// the duration of the spawned command. `Terminal` holds it in a field
-/// whose only job is to drop with the entity.
+/// per-command resources (e.g. an on-disk Seatbelt config or Landlock
+/// policy file) alive for the duration of the spawned command. `Terminal`
+/// holds it in a field whose only job is to drop with the entity.
 pub type SandboxConfigHandle = Box<dyn std::any::Any + Send>;
 
 /// Apply a [`SandboxWrap`] to a `(program, args)` pair, substituting the
@@ -59,9 +59,12 @@ pub type SandboxConfigHandle = Box<dyn std::any::Any + Send>;
 /// duration of the spawned command — dropping it deletes any on-disk
 /// config the launcher reads at startup.
 ///
-/// On non-macOS hosts this is a no-op: the inputs pass through unchanged
-/// and the returned handle is `None`. (We don't yet have a sandbox
-/// integration for other platforms.)
+/// On Linux the launcher is the running executable itself, which must handle
+/// [`sandbox::linux_landlock::LAUNCHER_FLAG`] before doing anything else.
+///
+/// On other hosts this is a no-op: the inputs pass through unchanged and the
+/// returned handle is `None`. (We don't yet have a sandbox integration for
+/// other platforms.)
 pub(crate) fn apply_sandbox_wrap(
     program: String,
     args: Vec<String>,
@@ -91,7 +94,35 @@ pub(crate) fn apply_sandbox_wrap(
             Some(Box::new(config_file) as SandboxConfigHandle),
         ))
     }
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "linux")]
+    {
+        use anyhow::Context as _;
+
+        let writable: Vec<&std::path::Path> = sandbox_wrap
+            .writable_paths
+            .iter()
+            .chain(sandbox_wrap.extra_write_paths.iter())
+            .map(|p| p.as_path())
+            .collect();
+        let permissions = sandbox::linux_landlock::SandboxPermissions {
+            allow_network: sandbox_wrap.allow_network,
+            allow_fs_write: sandbox_wrap.allow_fs_write,
+        };
+        let launcher = std::env::current_exe().context("failed to locate the sandbox launcher")?;
+        let (new_program, new_args, policy_file) = sandbox::linux_landlock::wrap_invocation(
+            &launcher,
+            &program,
+            &args,
+            &writable,
+            permissions,
+        )?;
+        Ok((
+            new_program,
+            new_args,
+            Some(Box::new(policy_file) as SandboxConfigHandle),
+        ))
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
         // No sandbox integration available; ignore the wrap request and
         // let the command run with the agent's ambient permissions.
@@ -5,12 +5,15 @@
 //! caller see the same answer (and so the `target_os` gate lives in one
 //! place instead of scattered across the agent crate).
 //!
-//! The current policy is: enabled iff we're on macOS *and* the user has the
-//! `sandboxing` feature flag turned on. There's deliberately no settings or
-//! env-var override yet — the flag is the only switch.
+//! The current policy is: enabled iff the host has a sandbox integration
+//! *and* the user has the `sandboxing` feature flag turned on. There's
+//! deliberately no settings or env-var override yet — the flag is the only
+//! switch.
 //!
-//! On non-macOS hosts we don't have a sandbox integration today, so this
-//! returns `false` regardless of the flag.
+//! macOS always has Seatbelt. On Linux we additionally require a kernel with
+//! Landlock support, so commands never silently run unconfined after the
+//! user was told they'd be sandboxed. Other hosts don't have a sandbox
+//! integration today, so this returns `false` regardless of the flag.
 //!
 //! Naming note: this module is about agent terminal sandboxing specifically.
 //! Other agent operations (e.g. file edits) are gated separately.
@@ -23,7 +26,23 @@ use std::path::PathBuf;
 /// Whether agent-run terminal commands should be wrapped in an OS-level
 /// sandbox for this process. See module docs for the policy.
 pub(crate) fn sandboxing_enabled(cx: &App) -> bool {
-    cfg!(target_os = "macos") && cx.has_flag::<SandboxingFeatureFlag>()
+    host_supports_sandboxing() && cx.has_flag::<SandboxingFeatureFlag>()
+}
+
+#[cfg(target_os = "macos")]
+fn host_supports_sandboxing() -> bool {
+    true
+}
+
+#[cfg(target_os = "linux")]
+fn host_supports_sandboxing() -> bool {
+    static SUPPORTED: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+    *SUPPORTED.get_or_init(sandbox::linux_landlock::is_supported)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn host_supports_sandboxing() -> bool {
+    false
 }
 
 /// A request for elevated sandbox permissions for a single terminal command.
@@ -9,6 +9,7 @@ mod completion_provider;
 mod config_options;
 mod context;
 mod context_server_configuration;
+mod context_server_requests;
 pub(crate) mod conversation_view;
 mod diagnostics;
 pub mod draft_prompt_store;
@@ -18,6 +19,7 @@ mod favorite_models;
 mod inline_assistant;
 mod inline_prompt_editor;
 mod language_model_selector;
+mod mcp_resource_picker;
 mod mention_set;
 mod message_editor;
 mod mode_selector;
@@ -563,6 +565,7 @@ pub fn init(
     }
     agent_panel::init(cx);
     context_server_configuration::init(language_registry.clone(), fs.clone(), cx);
+    context_server_requests::init(cx);
     thread_metadata_store::init(cx);
     terminal_thread_metadata_store::init(cx);
 
@@ -0,0 +1,972 @@
+//! Answers the sampling and elicitation requests that context servers send to Zed.
+//!
+//! Sampling requests are routed to the user's configured language model once the user has
+//! approved them in the agent panel, either once or for the rest of the session. Elicitation
+//! requests are shown as a small form in the agent panel. Requests from servers that no agent
+//! panel shows are rejected right away, since the user could never answer them.
+
+use std::rc::Rc;
+
+use anyhow::{Context as _, Result, anyhow, bail};
+use collections::{HashMap, HashSet};
+use context_server::{ContextServerClientDelegate, ContextServerId, types};
+use editor::Editor;
+use futures::{StreamExt as _, channel::oneshot};
+use gpui::{Entity, Global, Subscription, Task, WeakEntity};
+use language_model::{
+    LanguageModel, LanguageModelImage, LanguageModelRegistry, LanguageModelRequest,
+    LanguageModelRequestMessage, MessageContent, Role,
+};
+use project::context_server_store::ContextServerStore;
+use serde_json::Value;
+use ui::{Callout, Checkbox, prelude::*};
+use util::post_inc;
+
+pub(crate) fn init(cx: &mut App) {
+    let requests = ContextServerRequests::global(cx);
+    context_server::set_client_delegate(Rc::new(ContextServerRequestDelegate { requests }), cx);
+}
+
+struct ContextServerRequestDelegate {
+    requests: Entity<ContextServerRequests>,
+}
+
+impl ContextServerClientDelegate for ContextServerRequestDelegate {
+    fn create_message(
+        &self,
+        server_id: ContextServerId,
+        request: types::CreateMessageRequest,
+        cx: &mut App,
+    ) -> Task<Result<types::CreateMessageResult>> {
+        let Some(model) = select_model(request.model_preferences.as_ref(), cx) else {
+            return Task::ready(Err(anyhow!("No language model is configured in Zed")));
+        };
+        let preview = sampling_preview(&request);
+        let max_tokens = u64::from(request.max_tokens);
+        let model_request = match to_language_model_request(request) {
+            Ok(model_request) => model_request,
+            Err(error) => return Task::ready(Err(error)),
+        };
+        let approval = self.requests.update(cx, |requests, cx| {
+            requests.request_sampling_approval(server_id, model.name().0, preview, cx)
+        });
+        cx.spawn(async move |cx| {
+            approval.await?;
+            let mut response = model.stream_completion_text(model_request, cx).await?;
+            let mut text = String::new();
+            let mut stop_reason = "endTurn";
+            while let Some(chunk) = response.stream.next().await {
+                text.push_str(&chunk?);
+                let output_tokens = response.last_token_usage.lock().output_tokens;
+                if reached_max_tokens(&text, output_tokens, max_tokens) {
+                    // Dropping the stream cancels the rest of the response
+                    stop_reason = "maxTokens";
+                    break;
+                }
+            }
+            Ok(types::CreateMessageResult {
+                role: types::Role::Assistant,
+                content: types::MessageContent::Text {
+                    text,
+                    annotations: None,
+                },
+                model: model.id().0.to_string(),
+                stop_reason: Some(stop_reason.to_string()),
+            })
+        })
+    }
+
+    fn elicit(
+        &self,
+        server_id: ContextServerId,
+        request: types::ElicitRequest,
+        cx: &mut App,
+    ) -> Task<Result<types::ElicitResult>> {
+        let fields = match parse_requested_schema(&request.requested_schema) {
+            Ok(fields) => fields,
+            Err(error) => return Task::ready(Err(error)),
+        };
+        self.requests.update(cx, |requests, cx| {
+            requests.request_elicitation(server_id, request.message.into(), fields, cx)
+        })
+    }
+}
+
+/// Picks the first available model matching one of the server's hints, falling back to the
+/// user's default model.
+fn select_model(
+    preferences: Option<&types::ModelPreferences>,
+    cx: &App,
+) -> Option<std::sync::Arc<dyn LanguageModel>> {
+    let registry = LanguageModelRegistry::read_global(cx);
+    preferences
+        .and_then(|preferences| preferences.hints.as_ref())
+        .into_iter()
+        .flatten()
+        .filter_map(|hint| hint.name.as_deref())
+        .find_map(|hint| {
+            registry
+                .available_models(cx)
+                .find(|model| model.id().0.contains(hint) || model.name().0.contains(hint))
+        })
+        .or_else(|| registry.default_model().map(|default| default.model))
+}
+
+/// Whether a sampling response has used up the `maxTokens` the server asked for. Models that only
+/// report their usage at the end of a response are estimated from the length of the text.
+fn reached_max_tokens(text: &str, output_tokens: u64, max_tokens: u64) -> bool {
+    const BYTES_PER_TOKEN_GUESS: usize = 3;
+
+    let estimated_tokens = (text.len() / BYTES_PER_TOKEN_GUESS) as u64;
+    output_tokens.max(estimated_tokens) >= max_tokens
+}
+
+fn sampling_preview(request: &types::CreateMessageRequest) -> SharedString {
+    const MAX_PREVIEW_LEN: usize = 500;
+
+    let text = request
+        .messages
+        .iter()
+        .rev()
+        .find_map(|message| match &message.content {
+            types::MessageContent::Text { text, .. } if message.role == types::Role::User => {
+                Some(text.as_str())
+            }
+            _ => None,
+        })
+        .unwrap_or_default();
+    util::truncate_and_trailoff(text.trim(), MAX_PREVIEW_LEN).into()
+}
+
+fn to_language_model_request(request: types::CreateMessageRequest) -> Result<LanguageModelRequest> {
+    let mut messages = Vec::new();
+    if let Some(system_prompt) = request.system_prompt {
+        messages.push(LanguageModelRequestMessage {
+            role: Role::System,
+            content: vec![system_prompt.into()],
+            cache: false,
+            reasoning_details: None,
+        });
+    }
+    for message in request.messages {
+        let role = match message.role {
+            types::Role::User => Role::User,
+            types::Role::Assistant => Role::Assistant,
+        };
+        let content = match message.content {
+            types::MessageContent::Text { text, .. } => MessageContent::Text(text),
+            types::MessageContent::Image {
+                data, mime_type, ..
+            } if mime_type == "image/png" => MessageContent::Image(LanguageModelImage {
+                source: data.into(),
+            }),
+            types::MessageContent::Image { mime_type, .. }
+            | types::MessageContent::Audio { mime_type, .. } => {
+                bail!("Unsupported sampling content of type {mime_type}")
+            }
+            types::MessageContent::Resource { .. } => {
+                bail!("Unsupported sampling content: embedded resource")
+            }
+        };
+        messages.push(LanguageModelRequestMessage {
+            role,
+            content: vec![content],
+            cache: false,
+            reasoning_details: None,
+        });
+    }
+    Ok(LanguageModelRequest {
+        messages,
+        stop: request.stop_sequences.unwrap_or_default(),
+        temperature: request.temperature.map(|temperature| temperature as f32),
+        ..Default::default()
+    })
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct ElicitationField {
+    name: String,
+    title: SharedString,
+    description: Option<SharedString>,
+    kind: ElicitationFieldKind,
+    required: bool,
+    default: Option<Value>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum ElicitationFieldKind {
+    Text,
+    Number,
+    Integer,
+    Boolean,
+    Choice(Vec<String>),
+}
+
+/// Parses the restricted JSON schema of an elicitation request, which is a flat object of
+/// primitive properties.
+fn parse_requested_schema(schema: &Value) -> Result<Vec<ElicitationField>> {
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .context("The requested schema has no properties")?;
+    let required = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect::<HashSet<_>>();
+    properties
+        .iter()
+        .map(|(name, property)| {
+            let kind = match property.get("type").and_then(Value::as_str) {
+                Some("string") => match property.get("enum").and_then(Value::as_array) {
+                    Some(options) => ElicitationFieldKind::Choice(
+                        options
+                            .iter()
+                            .filter_map(|option| option.as_str().map(ToString::to_string))
+                            .collect(),
+                    ),
+                    None => ElicitationFieldKind::Text,
+                },
+                Some("number") => ElicitationFieldKind::Number,
+                Some("integer") => ElicitationFieldKind::Integer,
+                Some("boolean") => ElicitationFieldKind::Boolean,
+                other => bail!("Unsupported type {other:?} for field `{name}`"),
+            };
+            Ok(ElicitationField {
+                name: name.clone(),
+                title: property
+                    .get("title")
+                    .and_then(Value::as_str)
+                    .unwrap_or(name)
+                    .to_string()
+                    .into(),
+                description: property
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .map(|description| description.to_string().into()),
+                kind,
+                required: required.contains(name.as_str()),
+                default: property.get("default").cloned(),
+            })
+        })
+        .collect()
+}
+
+/// Converts the text the user entered for a field, returning `None` if the field was left empty.
+fn parse_field_text(kind: &ElicitationFieldKind, text: &str) -> Result<Option<Value>> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(match kind {
+        ElicitationFieldKind::Number => {
+            let number = text
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64);
+            Value::Number(number.with_context(|| format!("`{text}` is not a number"))?)
+        }
+        ElicitationFieldKind::Integer => Value::from(
+            text.parse::<i64>()
+                .with_context(|| format!("`{text}` is not an integer"))?,
+        ),
+        _ => Value::String(text.to_string()),
+    }))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SamplingApproval {
+    AllowOnce,
+    AlwaysAllow,
+    Deny,
+}
+
+struct PendingRequest {
+    id: usize,
+    server_id: ContextServerId,
+    kind: PendingRequestKind,
+}
+
+enum PendingRequestKind {
+    Sampling {
+        model_name: SharedString,
+        preview: SharedString,
+        tx: oneshot::Sender<SamplingApproval>,
+    },
+    Elicitation {
+        message: SharedString,
+        fields: Vec<ElicitationField>,
+        tx: oneshot::Sender<types::ElicitResult>,
+    },
+}
+
+/// The requests from context servers that are waiting for the user, shared by all agent panels.
+#[derive(Default)]
+pub(crate) struct ContextServerRequests {
+    pending: Vec<PendingRequest>,
+    /// Servers that the user has allowed to use their language model for the rest of the session.
+    always_allowed: HashSet<ContextServerId>,
+    /// The context servers of the projects whose agent panel shows the requests.
+    stores: Vec<WeakEntity<ContextServerStore>>,
+    next_id: usize,
+}
+
+struct GlobalContextServerRequests(Entity<ContextServerRequests>);
+
+impl Global for GlobalContextServerRequests {}
+
+impl ContextServerRequests {
+    fn global(cx: &mut App) -> Entity<Self> {
+        if let Some(requests) = cx.try_global::<GlobalContextServerRequests>() {
+            return requests.0.clone();
+        }
+        let requests = cx.new(|_| Self::default());
+        cx.set_global(GlobalContextServerRequests(requests.clone()));
+        requests
+    }
+
+    fn request_sampling_approval(
+        &mut self,
+        server_id: ContextServerId,
+        model_name: SharedString,
+        preview: SharedString,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if self.always_allowed.contains(&server_id) {
+            return Task::ready(Ok(()));
+        }
+        if !self.is_shown(&server_id, cx) {
+            return Task::ready(Err(anyhow!(
+                "No agent panel shows the requests of {server_id}"
+            )));
+        }
+        let (tx, rx) = oneshot::channel();
+        self.push(
+            server_id,
+            PendingRequestKind::Sampling {
+                model_name,
+                preview,
+                tx,
+            },
+            cx,
+        );
+        cx.background_spawn(async move {
+            match rx.await {
+                Ok(SamplingApproval::AllowOnce | SamplingApproval::AlwaysAllow) => Ok(()),
+                Ok(SamplingApproval::Deny) | Err(_) => {
+                    Err(anyhow!("User rejected sampling request"))
+                }
+            }
+        })
+    }
+
+    fn request_elicitation(
+        &mut self,
+        server_id: ContextServerId,
+        message: SharedString,
+        fields: Vec<ElicitationField>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<types::ElicitResult>> {
+        if !self.is_shown(&server_id, cx) {
+            return Task::ready(Err(anyhow!(
+                "No agent panel shows the requests of {server_id}"
+            )));
+        }
+        let (tx, rx) = oneshot::channel();
+        self.push(
+            server_id,
+            PendingRequestKind::Elicitation {
+                message,
+                fields,
+                tx,
+            },
+            cx,
+        );
+        cx.background_spawn(async move {
+            Ok(rx.await.unwrap_or(types::ElicitResult {
+                action: types::ElicitAction::Cancel,
+                content: None,
+            }))
+        })
+    }
+
+    /// Whether an agent panel shows the requests of the server, so that the user can answer them.
+    fn is_shown(&self, server_id: &ContextServerId, cx: &App) -> bool {
+        self.stores
+            .iter()
+            .filter_map(WeakEntity::upgrade)
+            .any(|store| store.read(cx).get_server(server_id).is_some())
+    }
+
+    fn push(
+        &mut self,
+        server_id: ContextServerId,
+        kind: PendingRequestKind,
+        cx: &mut Context<Self>,
+    ) {
+        self.pending.push(PendingRequest {
+            id: post_inc(&mut self.next_id),
+            server_id,
+            kind,
+        });
+        cx.notify();
+    }
+
+    fn take(&mut self, id: usize) -> Option<PendingRequest> {
+        let ix = self.pending.iter().position(|request| request.id == id)?;
+        Some(self.pending.remove(ix))
+    }
+
+    fn respond_to_sampling(
+        &mut self,
+        id: usize,
+        approval: SamplingApproval,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(PendingRequest {
+            server_id,
+            kind: PendingRequestKind::Sampling { tx, .. },
+            ..
+        }) = self.take(id)
+        else {
+            return;
+        };
+        tx.send(approval).ok();
+        if approval == SamplingApproval::AlwaysAllow {
+            // Approve the server's other waiting requests too
+            let (approved, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+                .into_iter()
+                .partition(|request| {
+                    request.server_id == server_id
+                        && matches!(request.kind, PendingRequestKind::Sampling { .. })
+                });
+            self.pending = pending;
+            for request in approved {
+                if let PendingRequestKind::Sampling { tx, .. } = request.kind {
+                    tx.send(approval).ok();
+                }
+            }
+            self.always_allowed.insert(server_id);
+        }
+        cx.notify();
+    }
+
+    fn respond_to_elicitation(
+        &mut self,
+        id: usize,
+        result: types::ElicitResult,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(PendingRequest {
+            kind: PendingRequestKind::Elicitation { tx, .. },
+            ..
+        }) = self.take(id)
+        {
+            tx.send(result).ok();
+        }
+        cx.notify();
+    }
+}
+
+enum FieldInput {
+    Text(Entity<Editor>),
+    Boolean(bool),
+    Choice(Option<usize>),
+}
+
+struct ElicitationForm {
+    fields: Vec<ElicitationField>,
+    inputs: Vec<FieldInput>,
+    error: Option<SharedString>,
+}
+
+impl ElicitationForm {
+    fn new(fields: Vec<ElicitationField>, window: &mut Window, cx: &mut App) -> Self {
+        let inputs =
+            fields
+                .iter()
+                .map(|field| match &field.kind {
+                    ElicitationFieldKind::Boolean => FieldInput::Boolean(
+                        field.default.as_ref().and_then(Value::as_bool) == Some(true),
+                    ),
+                    ElicitationFieldKind::Choice(options) => {
+                        FieldInput::Choice(field.default.as_ref().and_then(Value::as_str).and_then(
+                            |default| options.iter().position(|option| option == default),
+                        ))
+                    }
+                    ElicitationFieldKind::Text
+                    | ElicitationFieldKind::Number
+                    | ElicitationFieldKind::Integer => {
+                        let default = match &field.default {
+                            Some(Value::String(default)) => default.clone(),
+                            Some(Value::Number(default)) => default.to_string(),
+                            _ => String::new(),
+                        };
+                        FieldInput::Text(cx.new(|cx| {
+                            let mut editor = Editor::single_line(window, cx);
+                            if let Some(description) = &field.description {
+                                editor.set_placeholder_text(description, window, cx);
+                            }
+                            editor.set_text(default, window, cx);
+                            editor
+                        }))
+                    }
+                })
+                .collect();
+        Self {
+            fields,
+            inputs,
+            error: None,
+        }
+    }
+
+    fn content(&self, cx: &App) -> Result<HashMap<String, Value>> {
+        let mut content = HashMap::default();
+        for (field, input) in self.fields.iter().zip(&self.inputs) {
+            let value = match (input, &field.kind) {
+                (FieldInput::Text(editor), kind) => {
+                    parse_field_text(kind, &editor.read(cx).text(cx))
+                        .with_context(|| format!("Invalid value for {}", field.title))?
+                }
+                (FieldInput::Boolean(checked), _) => Some(Value::Bool(*checked)),
+                (FieldInput::Choice(selection), ElicitationFieldKind::Choice(options)) => {
+                    selection.map(|ix| Value::String(options[ix].clone()))
+                }
+                (FieldInput::Choice(_), _) => None,
+            };
+            match value {
+                Some(value) => {
+                    content.insert(field.name.clone(), value);
+                }
+                None if field.required => bail!("{} is required", field.title),
+                None => {}
+            }
+        }
+        Ok(content)
+    }
+}
+
+/// Shows the waiting requests of the context servers in one project, at the top of its agent
+/// panel.
+pub(crate) struct ContextServerRequestsView {
+    requests: Entity<ContextServerRequests>,
+    context_server_store: Entity<ContextServerStore>,
+    forms: HashMap<usize, ElicitationForm>,
+    _subscription: Subscription,
+}
+
+impl ContextServerRequestsView {
+    pub(crate) fn new(
+        context_server_store: Entity<ContextServerStore>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let requests = ContextServerRequests::global(cx);
+        requests.update(cx, |requests, _| {
+            requests.stores.retain(|store| store.upgrade().is_some());
+            requests.stores.push(context_server_store.downgrade());
+        });
+        let subscription = cx.observe_in(&requests, window, |this, _, window, cx| {
+            this.sync_forms(window, cx);
+            cx.notify();
+        });
+        let mut this = Self {
+            requests,
+            context_server_store,
+            forms: HashMap::default(),
+            _subscription: subscription,
+        };
+        this.sync_forms(window, cx);
+        this
+    }
+
+    fn sync_forms(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let requests = self.requests.read(cx);
+        self.forms
+            .retain(|id, _| requests.pending.iter().any(|request| request.id == *id));
+        let new_forms = requests
+            .pending
+            .iter()
+            .filter_map(|request| match &request.kind {
+                PendingRequestKind::Elicitation { fields, .. }
+                    if !self.forms.contains_key(&request.id) =>
+                {
+                    Some((request.id, fields.clone()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        for (id, fields) in new_forms {
+            self.forms
+                .insert(id, ElicitationForm::new(fields, window, cx));
+        }
+    }
+
+    fn submit_elicitation(&mut self, id: usize, cx: &mut Context<Self>) {
+        let Some(form) = self.forms.get_mut(&id) else {
+            return;
+        };
+        match form.content(cx) {
+            Ok(content) => self.requests.update(cx, |requests, cx| {
+                requests.respond_to_elicitation(
+                    id,
+                    types::ElicitResult {
+                        action: types::ElicitAction::Accept,
+                        content: Some(content),
+                    },
+                    cx,
+                )
+            }),
+            Err(error) => {
+                form.error = Some(format!("{error:#}").into());
+                cx.notify();
+            }
+        }
+    }
+
+    fn render_sampling_request(
+        &self,
+        id: usize,
+        server_id: &ContextServerId,
+        model_name: &SharedString,
+        preview: &SharedString,
+        cx: &mut Context<Self>,
+    ) -> AnyElement {
+        let respond = |label: &'static str, approval: SamplingApproval| {
+            Button::new(("respond-to-sampling", id * 3 + approval as usize), label)
+                .label_size(LabelSize::Small)
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    this.requests.update(cx, |requests, cx| {
+                        requests.respond_to_sampling(id, approval, cx)
+                    });
+                }))
+        };
+        Callout::new()
+            .icon(IconName::ToolHammer)
+            .severity(Severity::Info)
+            .title(format!(
+                "{server_id} wants to generate a response with {model_name}"
+            ))
+            .description(preview.clone())
+            .actions_slot(
+                h_flex()
+                    .gap_1()
+                    .child(respond("Deny", SamplingApproval::Deny))
+                    .child(respond("Always Allow", SamplingApproval::AlwaysAllow))
+                    .child(respond("Allow Once", SamplingApproval::AllowOnce)),
+            )
+            .into_any_element()
+    }
+
+    fn render_elicitation_request(
+        &self,
+        id: usize,
+        server_id: &ContextServerId,
+        message: &SharedString,
+        cx: &mut Context<Self>,
+    ) -> Option<AnyElement> {
+        let form = self.forms.get(&id)?;
+        let fields = form
+            .fields
+            .iter()
+            .zip(&form.inputs)
+            .enumerate()
+            .map(|(field_ix, (field, input))| {
+                let title = if field.required {
+                    format!("{} *", field.title)
+                } else {
+                    field.title.to_string()
+                };
+                let input = match input {
+                    FieldInput::Text(editor) => div()
+                        .px_2()
+                        .py_1()
+                        .border_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .rounded_sm()
+                        .child(editor.clone())
+                        .into_any_element(),
+                    FieldInput::Boolean(checked) => Checkbox::new(
+                        ("elicitation-checkbox", id * 1000 + field_ix),
+                        (*checked).into(),
+                    )
+                    .label(field.description.clone().unwrap_or_default())
+                    .on_click(cx.listener(move |this, state: &ToggleState, _, cx| {
+                        if let Some(FieldInput::Boolean(checked)) = this
+                            .forms
+                            .get_mut(&id)
+                            .and_then(|form| form.inputs.get_mut(field_ix))
+                        {
+                            *checked = state.selected();
+                            cx.notify();
+                        }
+                    }))
+                    .into_any_element(),
+                    FieldInput::Choice(selection) => {
+                        let ElicitationFieldKind::Choice(options) = &field.kind else {
+                            return None;
+                        };
+                        h_flex()
+                            .gap_1()
+                            .flex_wrap()
+                            .children(options.iter().enumerate().map(|(option_ix, option)| {
+                                Button::new(
+                                    SharedString::from(format!(
+                                        "elicitation-choice-{id}-{field_ix}-{option_ix}"
+                                    )),
+                                    option.clone(),
+                                )
+                                .label_size(LabelSize::Small)
+                                .toggle_state(*selection == Some(option_ix))
+                                .on_click(cx.listener(
+                                    move |this, _, _, cx| {
+                                        if let Some(FieldInput::Choice(selection)) = this
+                                            .forms
+                                            .get_mut(&id)
+                                            .and_then(|form| form.inputs.get_mut(field_ix))
+                                        {
+                                            *selection = Some(option_ix);
+                                            cx.notify();
+                                        }
+                                    },
+                                ))
+                            }))
+                            .into_any_element()
+                    }
+                };
+                Some(
+                    v_flex()
+                        .gap_0p5()
+                        .child(Label::new(title).size(LabelSize::Small))
+                        .child(input),
+                )
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(
+            v_flex()
+                .p_2()
+                .gap_2()
+                .border_b_1()
+                .border_color(cx.theme().colors().border)
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Icon::new(IconName::Info)
+                                .size(IconSize::Small)
+                                .color(Color::Muted),
+                        )
+                        .child(
+                            Label::new(format!("{server_id} asks"))
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                        ),
+                )
+                .child(Label::new(message.clone()))
+                .children(fields)
+                .children(
+                    form.error
+                        .clone()
+                        .map(|error| Label::new(error).size(LabelSize::Small).color(Color::Error)),
+                )
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .justify_end()
+                        .child(
+                            Button::new(("decline-elicitation", id), "Decline")
+                                .label_size(LabelSize::Small)
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.requests.update(cx, |requests, cx| {
+                                        requests.respond_to_elicitation(
+                                            id,
+                                            types::ElicitResult {
+                                                action: types::ElicitAction::Decline,
+                                                content: None,
+                                            },
+                                            cx,
+                                        )
+                                    });
+                                })),
+                        )
+                        .child(
+                            Button::new(("submit-elicitation", id), "Submit")
+                                .label_size(LabelSize::Small)
+                                .style(ButtonStyle::Filled)
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.submit_elicitation(id, cx);
+                                })),
+                        ),
+                )
+                .into_any_element(),
+        )
+    }
+}
+
+impl Render for ContextServerRequestsView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let context_server_store = self.context_server_store.read(cx);
+        let requests = self
+            .requests
+            .read(cx)
+            .pending
+            .iter()
+            .filter(|request| {
+                context_server_store
+                    .get_server(&request.server_id)
+                    .is_some()
+            })
+            .map(|request| {
+                let kind = match &request.kind {
+                    PendingRequestKind::Sampling {
+                        model_name,
+                        preview,
+                        ..
+                    } => Ok((model_name.clone(), preview.clone())),
+                    PendingRequestKind::Elicitation { message, .. } => Err(message.clone()),
+                };
+                (request.id, request.server_id.clone(), kind)
+            })
+            .collect::<Vec<_>>();
+
+        v_flex().children(
+            requests
+                .into_iter()
+                .filter_map(|(id, server_id, kind)| match kind {
+                    Ok((model_name, preview)) => Some(self.render_sampling_request(
+                        id,
+                        &server_id,
+                        &model_name,
+                        &preview,
+                        cx,
+                    )),
+                    Err(message) => self.render_elicitation_request(id, &server_id, &message, cx),
+                }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_requested_schema() {
+        let fields = parse_requested_schema(&json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "title": "Project name", "default": "zed" },
+                "count": { "type": "integer" },
+                "private": { "type": "boolean", "description": "Keep it private" },
+                "license": { "type": "string", "enum": ["MIT", "GPL"] }
+            },
+            "required": ["name"]
+        }))
+        .unwrap();
+        assert_eq!(
+            fields
+                .iter()
+                .map(|field| (
+                    field.name.as_str(),
+                    &*field.title,
+                    &field.kind,
+                    field.required
+                ))
+                .collect::<Vec<_>>(),
+            [
+                ("name", "Project name", &ElicitationFieldKind::Text, true),
+                ("count", "count", &ElicitationFieldKind::Integer, false),
+                ("private", "private", &ElicitationFieldKind::Boolean, false),
+                (
+                    "license",
+                    "license",
+                    &ElicitationFieldKind::Choice(vec!["MIT".into(), "GPL".into()]),
+                    false
+                ),
+            ]
+        );
+        assert_eq!(fields[0].default, Some(json!("zed")));
+
+        assert!(
+            parse_requested_schema(&json!({
+                "type": "object",
+                "properties": { "nested": { "type": "object" } }
+            }))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_field_text() {
+        assert_eq!(
+            parse_field_text(&ElicitationFieldKind::Text, " hello ").unwrap(),
+            Some(json!("hello"))
+        );
+        assert_eq!(
+            parse_field_text(&ElicitationFieldKind::Integer, "42").unwrap(),
+            Some(json!(42))
+        );
+        assert_eq!(
+            parse_field_text(&ElicitationFieldKind::Number, "1.5").unwrap(),
+            Some(json!(1.5))
+        );
+        assert_eq!(
+            parse_field_text(&ElicitationFieldKind::Number, "").unwrap(),
+            None
+        );
+        assert!(parse_field_text(&ElicitationFieldKind::Integer, "1.5").is_err());
+    }
+
+    #[test]
+    fn test_sampling_request_conversion() {
+        let request: types::CreateMessageRequest = serde_json::from_value(json!({
+            "messages": [
+                { "role": "user", "content": { "type": "text", "text": "Summarize" } },
+                { "role": "assistant", "content": { "type": "text", "text": "Sure" } }
+            ],
+            "systemPrompt": "Be brief",
+            "temperature": 0.5,
+            "maxTokens": 100,
+            "stopSequences": ["END"]
+        }))
+        .unwrap();
+        assert_eq!(&*sampling_preview(&request), "Summarize");
+
+        let model_request = to_language_model_request(request).unwrap();
+        assert_eq!(
+            model_request
+                .messages
+                .iter()
+                .map(|message| (message.role, message.string_contents()))
+                .collect::<Vec<_>>(),
+            [
+                (Role::System, "Be brief".to_string()),
+                (Role::User, "Summarize".to_string()),
+                (Role::Assistant, "Sure".to_string()),
+            ]
+        );
+        assert_eq!(model_request.temperature, Some(0.5));
+        assert_eq!(model_request.stop, ["END"]);
+
+        let audio: types::CreateMessageRequest = serde_json::from_value(json!({
+            "messages": [{
+                "role": "user",
+                "content": { "type": "audio", "data": "", "mimeType": "audio/wav" }
+            }],
+            "maxTokens": 100
+        }))
+        .unwrap();
+        assert!(to_language_model_request(audio).is_err());
+    }
+
+    #[test]
+    fn test_reached_max_tokens() {
+        assert!(!reached_max_tokens("Hello", 0, 100));
+        assert!(reached_max_tokens("Hello", 100, 100));
+        assert!(reached_max_tokens(&"word ".repeat(60), 0, 100));
+    }
+}
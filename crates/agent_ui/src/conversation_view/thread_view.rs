@@ -15,6 +15,7 @@ use editor::actions::OpenExcerpts;
 use feature_flags::AcpBetaFeatureFlag;
 
 use crate::completion_provider::AvailableSkill;
+use crate::mcp_resource_picker::McpResourcePicker;
 use crate::message_editor::SharedSessionCapabilities;
 
 use db::kvp::KeyValueStore;
@@ -4965,6 +4966,29 @@ impl ThreadView {
                             }
                         }),
                 )
+                .item(
+                    ContextMenuEntry::new("MCP Resources")
+                        .icon(IconName::ToolHammer)
+                        .icon_color(Color::Muted)
+                        .icon_size(IconSize::XSmall)
+                        .disabled(!supports_embedded_context)
+                        .handler({
+                            let message_editor = message_editor.clone();
+                            let workspace = workspace.clone();
+                            move |window, cx| {
+                                workspace
+                                    .update(cx, |workspace, cx| {
+                                        McpResourcePicker::toggle(
+                                            workspace,
+                                            message_editor.downgrade(),
+                                            window,
+                                            cx,
+                                        );
+                                    })
+                                    .ok();
+                            }
+                        }),
+                )
                 .item(
                     ContextMenuEntry::new("Branch Diff")
                         .icon(IconName::GitBranch)
@@ -10353,6 +10377,7 @@ pub(crate) fn open_link(
             MentionUri::TerminalSelection { .. } => {}
             MentionUri::GitDiff { .. } => {}
             MentionUri::MergeConflict { .. } => {}
+            MentionUri::McpResource { .. } => {}
             MentionUri::Rule { name, .. } => {
                 crate::ui::open_migrated_rule(workspace, &name, window, cx);
             }
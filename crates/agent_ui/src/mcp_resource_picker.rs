@@ -0,0 +1,440 @@
+use std::sync::Arc;
+
+use context_server::{ContextServerId, protocol::ServerCapability, types};
+use fuzzy::{StringMatch, StringMatchCandidate, match_strings};
+use gpui::{App, Context, DismissEvent, Entity, EventEmitter, Focusable, Task, WeakEntity, Window};
+use picker::{Picker, PickerDelegate};
+use project::context_server_store::ContextServerStore;
+use ui::{HighlightedLabel, ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+
+use crate::message_editor::MessageEditor;
+
+/// Lists the resources and resource templates of the running MCP servers, and attaches the
+/// selected one to the message being composed.
+pub struct McpResourcePicker {
+    picker: Entity<Picker<McpResourcePickerDelegate>>,
+}
+
+impl McpResourcePicker {
+    pub fn toggle(
+        workspace: &mut Workspace,
+        message_editor: WeakEntity<MessageEditor>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let context_server_store = workspace.project().read(cx).context_server_store();
+        workspace.toggle_modal(window, cx, |window, cx| {
+            let delegate = McpResourcePickerDelegate {
+                resource_picker: cx.entity().downgrade(),
+                message_editor,
+                entries: Vec::new(),
+                matches: Vec::new(),
+                attach_uri: None,
+                selected_index: 0,
+            };
+            let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+            let this = Self { picker };
+            this.load_entries(context_server_store, window, cx);
+            this
+        });
+    }
+
+    fn load_entries(
+        &self,
+        context_server_store: Entity<ContextServerStore>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let servers = context_server_store.read(cx).running_servers();
+        let picker = self.picker.downgrade();
+        cx.spawn_in(window, async move |_, cx| {
+            let mut entries = Vec::new();
+            for server in servers {
+                let Some(client) = server.client() else {
+                    continue;
+                };
+                if !client.capable(ServerCapability::Resources) {
+                    continue;
+                }
+                let server_id = server.id();
+                let mut cursor = None;
+                loop {
+                    let Some(response) = client
+                        .request::<types::requests::ResourcesList>(types::PaginatedParams {
+                            cursor: cursor.take(),
+                            meta: None,
+                        })
+                        .await
+                        .log_err()
+                    else {
+                        break;
+                    };
+                    entries.extend(response.resources.into_iter().map(|resource| {
+                        McpResourceEntry {
+                            server_id: server_id.clone(),
+                            name: resource.title.unwrap_or(resource.name),
+                            uri: resource.uri.to_string(),
+                            is_template: false,
+                        }
+                    }));
+                    cursor = response.next_cursor;
+                    if cursor.is_none() {
+                        break;
+                    }
+                }
+                loop {
+                    // Templates are optional, and servers without any may not implement the method
+                    let Ok(response) = client
+                        .request::<types::requests::ListResourceTemplates>(types::PaginatedParams {
+                            cursor: cursor.take(),
+                            meta: None,
+                        })
+                        .await
+                    else {
+                        break;
+                    };
+                    entries.extend(response.resource_templates.into_iter().map(|template| {
+                        McpResourceEntry {
+                            server_id: server_id.clone(),
+                            name: template.title.unwrap_or(template.name),
+                            uri: template.uri_template,
+                            is_template: true,
+                        }
+                    }));
+                    cursor = response.next_cursor;
+                    if cursor.is_none() {
+                        break;
+                    }
+                }
+            }
+
+            picker.update_in(cx, |picker, window, cx| {
+                picker.delegate.entries = entries;
+                picker.refresh(window, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+impl EventEmitter<DismissEvent> for McpResourcePicker {}
+impl ModalView for McpResourcePicker {}
+
+impl Focusable for McpResourcePicker {
+    fn focus_handle(&self, cx: &App) -> gpui::FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for McpResourcePicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+struct McpResourceEntry {
+    server_id: ContextServerId,
+    name: String,
+    /// The resource's URI, or the URI template for templates.
+    uri: String,
+    is_template: bool,
+}
+
+pub struct McpResourcePickerDelegate {
+    resource_picker: WeakEntity<McpResourcePicker>,
+    message_editor: WeakEntity<MessageEditor>,
+    entries: Vec<McpResourceEntry>,
+    matches: Vec<StringMatch>,
+    /// A URI typed into the query that fills in one of the templates, along with its server.
+    attach_uri: Option<(ContextServerId, String)>,
+    selected_index: usize,
+}
+
+impl McpResourcePickerDelegate {
+    fn attach(
+        &self,
+        server_id: ContextServerId,
+        uri: String,
+        name: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        self.message_editor
+            .update(cx, |message_editor, cx| {
+                message_editor.focus_handle(cx).focus(window, cx);
+                message_editor.insert_mcp_resource_crease(&server_id, uri, name, window, cx);
+            })
+            .log_err();
+        self.resource_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn selected_entry(&self) -> Option<&McpResourceEntry> {
+        let index = self
+            .selected_index
+            .checked_sub(self.attach_uri.is_some() as usize)?;
+        let candidate_id = self.matches.get(index)?.candidate_id;
+        self.entries.get(candidate_id)
+    }
+}
+
+impl PickerDelegate for McpResourcePickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search resources or enter a resource URI…".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No MCP resources found".into())
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len() + self.attach_uri.is_some() as usize
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let query = query.trim().to_string();
+        self.attach_uri = (query.contains("://") && !query.contains('{'))
+            .then(|| {
+                self.entries
+                    .iter()
+                    .find(|entry| entry.is_template && uri_matches_template(&query, &entry.uri))
+            })
+            .flatten()
+            .map(|template| (template.server_id.clone(), query.clone()));
+
+        let background = cx.background_executor().clone();
+        let candidates = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(id, entry)| {
+                StringMatchCandidate::new(id, &format!("{} {}", entry.name, entry.uri))
+            })
+            .collect::<Vec<_>>();
+
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    true,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(cx, |this, _| {
+                this.delegate.matches = matches;
+                this.delegate.selected_index = 0;
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm_update_query(
+        &mut self,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<String> {
+        // Templates need their variables filled in before they can be read
+        let entry = self.selected_entry()?;
+        entry.is_template.then(|| entry.uri.clone())
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        if self.selected_index == 0
+            && let Some((server_id, uri)) = self.attach_uri.clone()
+        {
+            self.attach(server_id, uri.clone(), uri, window, cx);
+            return;
+        }
+        let Some(entry) = self.selected_entry() else {
+            return;
+        };
+        if entry.is_template {
+            return;
+        }
+        self.attach(
+            entry.server_id.clone(),
+            entry.uri.clone(),
+            entry.name.clone(),
+            window,
+            cx,
+        );
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.resource_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let item = ListItem::new(ix)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected);
+
+        let attach_offset = self.attach_uri.is_some() as usize;
+        if let Some((server_id, uri)) = self.attach_uri.as_ref()
+            && ix == 0
+        {
+            return Some(
+                item.start_slot(Icon::new(IconName::Plus).color(Color::Muted))
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(Label::new(format!("Attach {uri}")).truncate())
+                            .child(
+                                Label::new(server_id.to_string())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                    ),
+            );
+        }
+
+        let string_match = self.matches.get(ix - attach_offset)?;
+        let entry = self.entries.get(string_match.candidate_id)?;
+        // Only highlight the positions that fall within the name
+        let name_positions = string_match
+            .positions
+            .iter()
+            .copied()
+            .filter(|position| *position < entry.name.len())
+            .collect::<Vec<_>>();
+        let icon = if entry.is_template {
+            IconName::Code
+        } else {
+            IconName::File
+        };
+
+        Some(
+            item.start_slot(Icon::new(icon).color(Color::Muted)).child(
+                v_flex()
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(HighlightedLabel::new(entry.name.clone(), name_positions))
+                            .child(
+                                Label::new(entry.server_id.to_string())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .child(
+                        Label::new(entry.uri.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .truncate(),
+                    ),
+            ),
+        )
+    }
+}
+
+/// Returns whether `uri` could have been produced by filling in the variables of the
+/// RFC 6570 `template`, by checking that the literal parts of the template appear in order.
+fn uri_matches_template(uri: &str, template: &str) -> bool {
+    let mut literals = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        literals.push(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            return false;
+        };
+        rest = &rest[start + end + 1..];
+    }
+    if literals.is_empty() {
+        // Templates without variables are plain resources
+        return false;
+    }
+    let trailing = rest;
+
+    let Some(mut remaining) = uri.strip_prefix(literals[0]) else {
+        return false;
+    };
+    // Each variable has to expand to at least one character
+    for literal in &literals[1..] {
+        let Some(index) = remaining.get(1..).and_then(|rest| rest.find(literal)) else {
+            return false;
+        };
+        remaining = &remaining[1 + index + literal.len()..];
+    }
+    remaining.len() > trailing.len() && remaining.ends_with(trailing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uri_matches_template() {
+        assert!(uri_matches_template(
+            "file:///logs/2026-10-18.log",
+            "file:///logs/{date}.log"
+        ));
+        assert!(uri_matches_template(
+            "github://zed-industries/zed/issues/42",
+            "github://{owner}/{repo}/issues/{number}"
+        ));
+        assert!(uri_matches_template("db://users", "db://{table}"));
+
+        assert!(!uri_matches_template(
+            "file:///logs/today.txt",
+            "file:///logs/{date}.log"
+        ));
+        assert!(!uri_matches_template(
+            "github://zed-industries/zed/pulls/42",
+            "github://{owner}/{repo}/issues/{number}"
+        ));
+        assert!(!uri_matches_template("db://", "db://{table}"));
+        assert!(!uri_matches_template("db://users", "db://users"));
+        assert!(!uri_matches_template(
+            "file:///logs/.log",
+            "file:///logs/{date}.log"
+        ));
+    }
+}
@@ -5,12 +5,18 @@ use agent_client_protocol::schema as acp;
 use agent_servers::{AgentServer, AgentServerDelegate};
 use anyhow::{Context as _, Result, anyhow};
 use collections::{HashMap, HashSet};
+use context_server::{
+    ContextServer, ContextServerId,
+    client::NotificationSubscription,
+    protocol::InitializedContextServerProtocol,
+    types::{self, Notification as _},
+};
 use editor::{
     Anchor, Editor, EditorSnapshot, FoldPlaceholder, ToOffset,
     display_map::{Crease, CreaseId, CreaseMetadata, FoldId},
     scroll::Autoscroll,
 };
-use futures::{AsyncReadExt as _, FutureExt as _, future::Shared};
+use futures::{AsyncReadExt as _, FutureExt as _, StreamExt as _, channel::mpsc, future::Shared};
 use gpui::{
     AppContext, ClipboardEntry, Context, Empty, Entity, EntityId, Image, ImageFormat, Img,
     SharedString, Task, WeakEntity,
@@ -62,6 +68,15 @@ pub struct MentionSet {
     thread_store: Option<Entity<ThreadStore>>,
     mentions: HashMap<CreaseId, (MentionUri, MentionTask)>,
     crease_entities: HashMap<CreaseId, Entity<LoadingContext>>,
+    mcp_resource_subscriptions: HashMap<ContextServerId, McpResourceSubscription>,
+}
+
+/// Keeps the mentioned resources of an MCP server up to date while they're attached.
+struct McpResourceSubscription {
+    client: Arc<InitializedContextServerProtocol>,
+    uris: HashSet<String>,
+    _notifications: NotificationSubscription,
+    _refresh: Task<()>,
 }
 
 impl MentionSet {
@@ -71,6 +86,7 @@ impl MentionSet {
             thread_store,
             mentions: HashMap::default(),
             crease_entities: HashMap::default(),
+            mcp_resource_subscriptions: HashMap::default(),
         }
     }
 
@@ -101,13 +117,14 @@ impl MentionSet {
         })
     }
 
-    pub fn remove_invalid(&mut self, snapshot: &EditorSnapshot) {
+    pub fn remove_invalid(&mut self, snapshot: &EditorSnapshot, cx: &mut App) {
         for (crease_id, crease) in snapshot.crease_snapshot.creases() {
             if !crease.range().start.is_valid(snapshot.buffer_snapshot()) {
                 self.mentions.remove(&crease_id);
                 self.crease_entities.remove(&crease_id);
             }
         }
+        self.prune_mcp_resource_subscriptions(cx);
     }
 
     pub fn insert_mention(
@@ -136,6 +153,9 @@ impl MentionSet {
     ) -> Task<Result<Mention>> {
         match mention_uri {
             MentionUri::Fetch { url } => self.confirm_mention_for_fetch(url, http_client, cx),
+            MentionUri::McpResource { server_id, uri, .. } => {
+                self.confirm_mention_for_mcp_resource(server_id, uri, cx)
+            }
             MentionUri::Directory { .. } => Task::ready(Ok(Mention::Link)),
             MentionUri::Thread { id, .. } => self.confirm_mention_for_thread(id, cx),
             MentionUri::File { abs_path } => {
@@ -176,6 +196,7 @@ impl MentionSet {
     pub fn remove_mention(&mut self, crease_id: &CreaseId, cx: &mut App) {
         self.mentions.remove(crease_id);
         self.crease_entities.remove(crease_id);
+        self.prune_mcp_resource_subscriptions(cx);
         self.recompute_disambiguation(cx);
     }
 
@@ -205,15 +226,29 @@ impl MentionSet {
         Some((uri.clone(), mention))
     }
 
-    pub fn set_mentions(&mut self, mentions: HashMap<CreaseId, (MentionUri, MentionTask)>) {
+    pub fn set_mentions(
+        &mut self,
+        mentions: HashMap<CreaseId, (MentionUri, MentionTask)>,
+        cx: &mut App,
+    ) {
         self.crease_entities
             .retain(|id, _| mentions.contains_key(id));
         self.mentions = mentions;
+        self.prune_mcp_resource_subscriptions(cx);
     }
 
-    pub fn clear(&mut self) -> impl Iterator<Item = (CreaseId, (MentionUri, MentionTask))> {
+    /// Removes all mentions, and unsubscribes from the MCP resources they mentioned.
+    pub fn clear(&mut self, cx: &mut App) -> HashMap<CreaseId, (MentionUri, MentionTask)> {
+        let mentions = self.take_mentions();
+        self.prune_mcp_resource_subscriptions(cx);
+        mentions
+    }
+
+    /// Removes all mentions so that they can be inserted again with [`Self::set_mentions`],
+    /// keeping the subscriptions to the MCP resources they mention.
+    pub fn take_mentions(&mut self) -> HashMap<CreaseId, (MentionUri, MentionTask)> {
         self.crease_entities.clear();
-        self.mentions.drain()
+        std::mem::take(&mut self.mentions)
     }
 
     fn recompute_disambiguation(&self, cx: &mut App) {
@@ -310,6 +345,9 @@ impl MentionSet {
             MentionUri::Fetch { url } => {
                 self.confirm_mention_for_fetch(url, workspace.read(cx).client().http_client(), cx)
             }
+            MentionUri::McpResource { server_id, uri, .. } => {
+                self.confirm_mention_for_mcp_resource(server_id, uri, cx)
+            }
             MentionUri::Directory { .. } => Task::ready(Ok(Mention::Link)),
             MentionUri::Thread { id, .. } => self.confirm_mention_for_thread(id, cx),
             MentionUri::File { abs_path } => {
@@ -375,6 +413,7 @@ impl MentionSet {
                     });
                     this.mentions.remove(&crease_id);
                     this.crease_entities.remove(&crease_id);
+                    this.prune_mcp_resource_subscriptions(cx);
                 })
                 .ok();
             }
@@ -452,6 +491,180 @@ impl MentionSet {
         })
     }
 
+    fn confirm_mention_for_mcp_resource(
+        &mut self,
+        server_id: String,
+        uri: String,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Mention>> {
+        let Some(project) = self.project.upgrade() else {
+            return Task::ready(Err(anyhow!("project not found")));
+        };
+        let server_id = ContextServerId(server_id.into());
+        let Some(server) = project
+            .read(cx)
+            .context_server_store()
+            .read(cx)
+            .get_running_server(&server_id)
+        else {
+            return Task::ready(Err(anyhow!("MCP server {server_id} is not running")));
+        };
+        self.subscribe_to_mcp_resource(&server, &uri, cx);
+        cx.background_spawn(read_mcp_resource(server, uri))
+    }
+
+    /// Subscribes to updates of `uri`, if the server supports it, so that mentions of the
+    /// resource are re-read when the server reports that it changed.
+    fn subscribe_to_mcp_resource(
+        &mut self,
+        server: &ContextServer,
+        uri: &str,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(client) = server.client() else {
+            return;
+        };
+        let supports_subscriptions = client
+            .initialize
+            .capabilities
+            .resources
+            .as_ref()
+            .and_then(|resources| resources.subscribe)
+            .unwrap_or(false);
+        if !supports_subscriptions {
+            return;
+        }
+
+        let server_id = server.id();
+        let is_stale = self
+            .mcp_resource_subscriptions
+            .get(&server_id)
+            .is_some_and(|subscription| !Arc::ptr_eq(&subscription.client, &client));
+        if is_stale {
+            // The server restarted, and its subscriptions went away with the old client
+            self.mcp_resource_subscriptions.remove(&server_id);
+        }
+
+        let subscription = self
+            .mcp_resource_subscriptions
+            .entry(server_id.clone())
+            .or_insert_with(|| {
+                let (updates_tx, mut updates_rx) = mpsc::unbounded();
+                let notifications = client.on_notification(
+                    types::notifications::ResourcesUpdated::METHOD,
+                    Box::new(move |params, _| {
+                        if let Some(params) =
+                            serde_json::from_value::<types::ResourcesUpdatedParams>(params)
+                                .log_err()
+                        {
+                            updates_tx.unbounded_send(params.uri).ok();
+                        }
+                    }),
+                );
+                let refresh = cx.spawn(async move |this, cx| {
+                    while let Some(uri) = updates_rx.next().await {
+                        let updated = this.update(cx, |this, cx| {
+                            this.refresh_mcp_resource(&server_id, &uri, cx);
+                        });
+                        if updated.is_err() {
+                            break;
+                        }
+                    }
+                });
+                McpResourceSubscription {
+                    client: client.clone(),
+                    uris: HashSet::default(),
+                    _notifications: notifications,
+                    _refresh: refresh,
+                }
+            });
+
+        if subscription.uris.insert(uri.to_string()) {
+            let uri = uri.to_string();
+            cx.background_spawn(async move {
+                client
+                    .request::<types::requests::ResourcesSubscribe>(
+                        types::ResourcesSubscribeParams {
+                            uri: url::Url::parse(&uri)?,
+                            meta: None,
+                        },
+                    )
+                    .await
+            })
+            .detach_and_log_err(cx);
+        }
+    }
+
+    /// Re-reads every mention of a resource that the server reported as updated.
+    fn refresh_mcp_resource(&mut self, server_id: &ContextServerId, uri: &str, cx: &mut App) {
+        let Some(project) = self.project.upgrade() else {
+            return;
+        };
+        let Some(server) = project
+            .read(cx)
+            .context_server_store()
+            .read(cx)
+            .get_running_server(server_id)
+        else {
+            return;
+        };
+
+        let mut refreshed_task = None;
+        for (mention_uri, task) in self.mentions.values_mut() {
+            if let MentionUri::McpResource {
+                server_id: mentioned_server_id,
+                uri: mentioned_uri,
+                ..
+            } = &*mention_uri
+                && mentioned_server_id.as_str() == &*server_id.0
+                && mentioned_uri == uri
+            {
+                *task = refreshed_task
+                    .get_or_insert_with(|| {
+                        let read = read_mcp_resource(server.clone(), uri.to_string());
+                        cx.background_spawn(async move { read.await.map_err(|e| e.to_string()) })
+                            .shared()
+                    })
+                    .clone();
+            }
+        }
+    }
+
+    /// Unsubscribes from the resources that are no longer mentioned.
+    fn prune_mcp_resource_subscriptions(&mut self, cx: &mut App) {
+        let mentioned = self
+            .mentions
+            .values()
+            .filter_map(|(uri, _)| match uri {
+                MentionUri::McpResource { server_id, uri, .. } => Some((server_id, uri)),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        self.mcp_resource_subscriptions
+            .retain(|server_id, subscription| {
+                subscription.uris.retain(|uri| {
+                    if mentioned.contains(&(&server_id.0.to_string(), uri)) {
+                        return true;
+                    }
+                    let client = subscription.client.clone();
+                    let uri = uri.clone();
+                    cx.background_spawn(async move {
+                        client
+                            .request::<types::requests::ResourcesUnsubscribe>(
+                                types::ResourcesUnsubscribeParams {
+                                    uri: url::Url::parse(&uri)?,
+                                    meta: None,
+                                },
+                            )
+                            .await
+                    })
+                    .detach_and_log_err(cx);
+                    false
+                });
+                !subscription.uris.is_empty()
+            });
+    }
+
     fn confirm_mention_for_symbol(
         &self,
         abs_path: PathBuf,
@@ -1376,6 +1589,41 @@ impl Render for ImageHover {
     }
 }
 
+async fn read_mcp_resource(server: Arc<ContextServer>, uri: String) -> Result<Mention> {
+    let client = server
+        .client()
+        .with_context(|| format!("MCP server {} is not running", server.id()))?;
+    let response = client
+        .request::<types::requests::ResourcesRead>(types::ResourcesReadParams {
+            uri: url::Url::parse(&uri)?,
+            meta: None,
+        })
+        .await?;
+
+    let mut content = String::new();
+    for contents in response.contents {
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        match contents {
+            types::ResourceContentsType::Text(text) => content.push_str(&text.text),
+            types::ResourceContentsType::Blob(blob) => {
+                write!(
+                    content,
+                    "[binary content of {} ({})]",
+                    blob.uri,
+                    blob.mime_type.as_deref().unwrap_or("unknown type")
+                )
+                .ok();
+            }
+        }
+    }
+    Ok(Mention::Text {
+        content,
+        tracked_buffers: Vec::new(),
+    })
+}
+
 async fn fetch_url_content(http_client: Arc<HttpClientWithUrl>, url: String) -> Result<String> {
     #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
     enum ContentType {
@@ -14,6 +14,7 @@ use agent::ThreadStore;
 use agent_client_protocol::schema as acp;
 use anyhow::{Result, anyhow};
 use base64::Engine as _;
+use context_server::ContextServerId;
 use editor::{
     Addon, AnchorRangeExt, ContextMenuOptions, Editor, EditorElement, EditorEvent, EditorMode,
     EditorStyle, Inlay, MultiBuffer, MultiBufferOffset, MultiBufferSnapshot, ToOffset,
@@ -560,8 +561,9 @@ impl MessageEditor {
                 {
                     editor.update(cx, |editor, cx| {
                         let snapshot = editor.snapshot(window, cx);
-                        this.mention_set
-                            .update(cx, |mention_set, _cx| mention_set.remove_invalid(&snapshot));
+                        this.mention_set.update(cx, |mention_set, cx| {
+                            mention_set.remove_invalid(&snapshot, cx)
+                        });
 
                         let new_hints = this
                             .command_hint(snapshot.buffer())
@@ -925,11 +927,8 @@ impl MessageEditor {
         self.editor.update(cx, |editor, cx| {
             editor.clear(window, cx);
             editor.remove_creases(
-                self.mention_set.update(cx, |mention_set, _cx| {
-                    mention_set
-                        .clear()
-                        .map(|(crease_id, _)| crease_id)
-                        .collect::<Vec<_>>()
+                self.mention_set.update(cx, |mention_set, cx| {
+                    mention_set.clear(cx).into_keys().collect::<Vec<_>>()
                 }),
                 cx,
             )
@@ -1520,15 +1519,39 @@ impl MessageEditor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let Some(workspace) = self.workspace.upgrade() else {
-            return;
-        };
-
         let mention_uri = MentionUri::Skill {
             name: skill.name.to_string(),
             source: skill.source.to_string(),
             skill_file_path: skill.skill_file_path.clone(),
         };
+        self.insert_mention_crease(mention_uri, window, cx);
+    }
+
+    pub fn insert_mcp_resource_crease(
+        &mut self,
+        server_id: &ContextServerId,
+        uri: String,
+        name: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let mention_uri = MentionUri::McpResource {
+            server_id: server_id.0.to_string(),
+            uri,
+            name,
+        };
+        self.insert_mention_crease(mention_uri, window, cx);
+    }
+
+    fn insert_mention_crease(
+        &mut self,
+        mention_uri: MentionUri,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
 
         let link_text = mention_uri.as_link().to_string();
         let content_len = link_text.len();
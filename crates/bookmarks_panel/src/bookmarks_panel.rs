@@ -0,0 +1,1324 @@
+mod bookmarks_panel_settings;
+
+use std::{
+    collections::HashMap,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Context as _;
+use bookmarks_panel_settings::{BookmarksPanelSettings, DockSide};
+use db::kvp::KeyValueStore;
+use editor::{Editor, EditorEvent, SelectionEffects, scroll::Autoscroll};
+use file_icons::FileIcons;
+use gpui::{
+    Action, App, AsyncWindowContext, DismissEvent, Entity, EntityId, EventEmitter, FocusHandle,
+    Focusable, KeyContext, MouseButton, MouseDownEvent, Pixels, Point, Render, ScrollStrategy,
+    Subscription, Task, UniformListScrollHandle, WeakEntity, Window, actions, anchored, deferred,
+    div, px, uniform_list,
+};
+use language::{Anchor, Buffer, BufferEvent, ToPoint as _};
+use menu::{Cancel, Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::{
+    Fs, Project,
+    bookmark_store::{BookmarkEntry, BookmarkStore, SharedBookmark},
+};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use theme_settings::ThemeSettings;
+use ui::{ContextMenu, ListItem, Tab, Tooltip, WithScrollbar, prelude::*};
+use util::{ResultExt, TryFutureExt};
+use workspace::{
+    Toast, Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+    notifications::{DetachAndPromptErr, NotificationId},
+};
+
+actions!(
+    bookmarks_panel,
+    [
+        /// Edits the label of the selected bookmark.
+        EditLabel,
+        /// Writes the project's bookmarks to `.zed/bookmarks.json`, so they can be shared.
+        ExportBookmarks,
+        /// Adds the bookmarks from the project's `.zed/bookmarks.json`.
+        ImportBookmarks,
+        /// Moves the file of the selected entry down in the panel.
+        MoveFileDown,
+        /// Moves the file of the selected entry up in the panel.
+        MoveFileUp,
+        /// Removes the selected bookmark, or all bookmarks of the selected file.
+        RemoveBookmark,
+        /// Toggles the bookmarks panel.
+        Toggle,
+        /// Toggles focus on the bookmarks panel.
+        ToggleFocus,
+    ]
+);
+
+const BOOKMARKS_PANEL_KEY: &str = "BookmarksPanel";
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<BookmarksPanel>(window, cx);
+        });
+        workspace.register_action(|workspace, _: &Toggle, window, cx| {
+            if !workspace.toggle_panel_focus::<BookmarksPanel>(window, cx) {
+                workspace.close_panel::<BookmarksPanel>(window, cx);
+            }
+        });
+    })
+    .detach();
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedBookmarksPanel {
+    active: Option<bool>,
+    #[serde(default)]
+    file_order: Vec<PathBuf>,
+}
+
+#[derive(Clone)]
+struct FileEntry {
+    abs_path: Arc<Path>,
+    buffer: Entity<Buffer>,
+}
+
+#[derive(Clone)]
+struct BookmarkItem {
+    abs_path: Arc<Path>,
+    buffer: Entity<Buffer>,
+    anchor: Anchor,
+    label: Option<Arc<str>>,
+}
+
+#[derive(Clone)]
+enum PanelEntry {
+    File(FileEntry),
+    Bookmark(BookmarkItem),
+}
+
+impl PanelEntry {
+    fn abs_path(&self) -> &Arc<Path> {
+        match self {
+            PanelEntry::File(file) => &file.abs_path,
+            PanelEntry::Bookmark(bookmark) => &bookmark.abs_path,
+        }
+    }
+}
+
+struct LabelEditor {
+    bookmark: BookmarkItem,
+    editor: Entity<Editor>,
+    _subscription: Subscription,
+}
+
+/// A file being dragged to a new position in the panel.
+#[derive(Clone)]
+struct DraggedBookmarkFile {
+    abs_path: Arc<Path>,
+    name: SharedString,
+    click_offset: Point<Pixels>,
+}
+
+impl Render for DraggedBookmarkFile {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let ui_font = ThemeSettings::get_global(cx).ui_font.clone();
+        h_flex()
+            .font(ui_font)
+            .pl(self.click_offset.x + px(12.))
+            .pt(self.click_offset.y + px(12.))
+            .child(
+                div()
+                    .py_1()
+                    .px_2()
+                    .rounded_lg()
+                    .bg(cx.theme().colors().background)
+                    .child(Label::new(self.name.clone())),
+            )
+    }
+}
+
+/// A panel listing the project's bookmarks, grouped by file.
+pub struct BookmarksPanel {
+    fs: Arc<dyn Fs>,
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    bookmark_store: Entity<BookmarkStore>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    active: bool,
+    /// The order in which files are listed. Files not in it come last, by path.
+    file_order: Vec<Arc<Path>>,
+    entries: Vec<PanelEntry>,
+    selected_index: Option<usize>,
+    label_editor: Option<LabelEditor>,
+    context_menu: Option<(Entity<ContextMenu>, Point<Pixels>, Subscription)>,
+    pending_serialization: Task<Option<()>>,
+    update_task: Task<()>,
+    buffer_subscriptions: HashMap<EntityId, Subscription>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl BookmarksPanel {
+    pub async fn load(
+        workspace: WeakEntity<Workspace>,
+        mut cx: AsyncWindowContext,
+    ) -> anyhow::Result<Entity<Self>> {
+        let serialized_panel = match workspace
+            .read_with(&cx, |workspace, _| {
+                BookmarksPanel::serialization_key(workspace)
+            })
+            .ok()
+            .flatten()
+        {
+            Some(serialization_key) => {
+                let kvp = cx.update(|_, cx| KeyValueStore::global(cx))?;
+                cx.background_spawn(async move { kvp.read_kvp(&serialization_key) })
+                    .await
+                    .context("loading bookmarks panel")
+                    .log_err()
+                    .flatten()
+                    .map(|panel| serde_json::from_str::<SerializedBookmarksPanel>(&panel))
+                    .transpose()
+                    .log_err()
+                    .flatten()
+            }
+            None => None,
+        };
+
+        workspace.update(&mut cx, |workspace, cx| {
+            let panel = Self::new(workspace, serialized_panel, cx);
+            panel.update(cx, |panel, cx| panel.update_entries(cx));
+            panel
+        })
+    }
+
+    fn new(
+        workspace: &mut Workspace,
+        serialized: Option<SerializedBookmarksPanel>,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let project = workspace.project().clone();
+        let bookmark_store = project.read(cx).bookmark_store();
+        let fs = workspace.app_state().fs.clone();
+        let workspace = cx.entity().downgrade();
+
+        cx.new(|cx| {
+            let focus_handle = cx.focus_handle();
+            let store_subscription = cx.observe(&bookmark_store, |this, _, cx| {
+                this.update_entries(cx);
+            });
+            let icons_subscription = cx.observe_global::<FileIcons>(|_, cx| cx.notify());
+            let (active, file_order) = serialized
+                .map(|serialized| {
+                    let file_order = serialized.file_order.into_iter().map(Arc::from).collect();
+                    (serialized.active.unwrap_or(false), file_order)
+                })
+                .unwrap_or_default();
+
+            Self {
+                fs,
+                project,
+                workspace,
+                bookmark_store,
+                focus_handle,
+                scroll_handle: UniformListScrollHandle::new(),
+                active,
+                file_order,
+                entries: Vec::new(),
+                selected_index: None,
+                label_editor: None,
+                context_menu: None,
+                pending_serialization: Task::ready(None),
+                update_task: Task::ready(()),
+                buffer_subscriptions: HashMap::new(),
+                _subscriptions: vec![store_subscription, icons_subscription],
+            }
+        })
+    }
+
+    fn serialization_key(workspace: &Workspace) -> Option<String> {
+        workspace
+            .database_id()
+            .map(|id| i64::from(id).to_string())
+            .or(workspace.session_id())
+            .map(|id| format!("{}-{:?}", BOOKMARKS_PANEL_KEY, id))
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let Some(serialization_key) = self
+            .workspace
+            .read_with(cx, |workspace, _| {
+                BookmarksPanel::serialization_key(workspace)
+            })
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+        let serialized = SerializedBookmarksPanel {
+            active: self.active.then_some(true),
+            file_order: self
+                .file_order
+                .iter()
+                .map(|abs_path| abs_path.to_path_buf())
+                .collect(),
+        };
+        let kvp = KeyValueStore::global(cx);
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                kvp.write_kvp(serialization_key, serde_json::to_string(&serialized)?)
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    /// Opens the buffers of bookmarked files that aren't open yet, so that
+    /// the panel can show their lines, then rebuilds the entries.
+    fn update_entries(&mut self, cx: &mut Context<Self>) {
+        if !self.active {
+            return;
+        }
+        let bookmark_store = self.bookmark_store.clone();
+        self.update_task = cx.spawn(async move |this, cx| {
+            BookmarkStore::resolve_all(&bookmark_store, cx)
+                .await
+                .log_err();
+            this.update(cx, |this, cx| this.rebuild_entries(cx)).ok();
+        });
+    }
+
+    fn rebuild_entries(&mut self, cx: &mut Context<Self>) {
+        let mut files = Vec::new();
+        for (abs_path, entry) in self.bookmark_store.read(cx).bookmark_entries() {
+            // Files that couldn't be opened stay unloaded, and have no lines to show.
+            let BookmarkEntry::Loaded(buffer_bookmarks) = entry else {
+                continue;
+            };
+            let buffer = buffer_bookmarks.buffer().clone();
+            let snapshot = buffer.read(cx).snapshot();
+            let mut bookmarks = buffer_bookmarks
+                .bookmarks()
+                .iter()
+                .filter(|bookmark| snapshot.can_resolve(&bookmark.anchor()))
+                .map(|bookmark| BookmarkItem {
+                    abs_path: abs_path.clone(),
+                    buffer: buffer.clone(),
+                    anchor: bookmark.anchor(),
+                    label: buffer_bookmarks.label(bookmark).cloned(),
+                })
+                .collect::<Vec<_>>();
+            if bookmarks.is_empty() {
+                continue;
+            }
+            bookmarks.sort_by(|a, b| a.anchor.cmp(&b.anchor, &snapshot));
+            files.push((
+                FileEntry {
+                    abs_path: abs_path.clone(),
+                    buffer,
+                },
+                bookmarks,
+            ));
+        }
+
+        let displayed_files = self
+            .entries
+            .iter()
+            .filter_map(|entry| match entry {
+                PanelEntry::File(file) => Some(file.abs_path.clone()),
+                PanelEntry::Bookmark(_) => None,
+            })
+            .collect::<Vec<_>>();
+        // Forget the position of files whose bookmarks were all removed, but keep
+        // the ones that haven't been shown yet, as their bookmarks may still load.
+        self.file_order.retain(|abs_path| {
+            !displayed_files.contains(abs_path)
+                || files.iter().any(|(file, _)| file.abs_path == *abs_path)
+        });
+        for (file, _) in &files {
+            if !self.file_order.contains(&file.abs_path) {
+                self.file_order.push(file.abs_path.clone());
+            }
+        }
+        files.sort_by_key(|(file, _)| {
+            self.file_order
+                .iter()
+                .position(|abs_path| *abs_path == file.abs_path)
+        });
+
+        let selected_entry = self.selected_entry().cloned();
+        self.entries.clear();
+        let mut buffer_subscriptions = HashMap::new();
+        for (file, bookmarks) in files {
+            let buffer_id = file.buffer.entity_id();
+            let subscription = self
+                .buffer_subscriptions
+                .remove(&buffer_id)
+                .unwrap_or_else(|| {
+                    cx.subscribe(&file.buffer, |_, _, event: &BufferEvent, cx| {
+                        if let BufferEvent::Edited { .. } = event {
+                            cx.notify();
+                        }
+                    })
+                });
+            buffer_subscriptions.insert(buffer_id, subscription);
+
+            self.entries.push(PanelEntry::File(file));
+            self.entries
+                .extend(bookmarks.into_iter().map(PanelEntry::Bookmark));
+        }
+        self.buffer_subscriptions = buffer_subscriptions;
+
+        self.selected_index = selected_entry
+            .and_then(|selected| {
+                self.entries
+                    .iter()
+                    .position(|entry| match (entry, &selected) {
+                        (PanelEntry::File(a), PanelEntry::File(b)) => a.abs_path == b.abs_path,
+                        (PanelEntry::Bookmark(a), PanelEntry::Bookmark(b)) => {
+                            a.abs_path == b.abs_path && a.anchor == b.anchor
+                        }
+                        _ => false,
+                    })
+            })
+            .or_else(|| {
+                self.selected_index
+                    .map(|ix| ix.min(self.entries.len().saturating_sub(1)))
+                    .filter(|_| !self.entries.is_empty())
+            });
+        cx.notify();
+    }
+
+    fn selected_entry(&self) -> Option<&PanelEntry> {
+        self.entries.get(self.selected_index?)
+    }
+
+    fn select_index(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_index = Some(ix);
+        self.scroll_handle
+            .scroll_to_item(ix, ScrollStrategy::Center);
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = match self.selected_index {
+            Some(ix) => (ix + 1).min(self.entries.len() - 1),
+            None => 0,
+        };
+        self.select_index(ix, cx);
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        if self.entries.is_empty() {
+            return;
+        }
+        let ix = match self.selected_index {
+            Some(ix) => ix.saturating_sub(1),
+            None => self.entries.len() - 1,
+        };
+        self.select_index(ix, cx);
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.entries.is_empty() {
+            self.select_index(0, cx);
+        }
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.entries.is_empty() {
+            self.select_index(self.entries.len() - 1, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if self.label_editor.is_some() {
+            self.confirm_label(window, cx);
+            return;
+        }
+        let bookmark = match self.selected_entry() {
+            Some(PanelEntry::Bookmark(bookmark)) => bookmark.clone(),
+            Some(PanelEntry::File(_)) => {
+                let Some(PanelEntry::Bookmark(bookmark)) = self
+                    .entries
+                    .get(self.selected_index.unwrap_or_default() + 1)
+                else {
+                    return;
+                };
+                bookmark.clone()
+            }
+            None => return,
+        };
+        self.open_bookmark(&bookmark, true, window, cx);
+    }
+
+    fn cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.label_editor.take().is_some() {
+            self.focus_handle.focus(window, cx);
+            cx.notify();
+        } else if self.context_menu.take().is_some() {
+            cx.notify();
+        } else {
+            cx.propagate();
+        }
+    }
+
+    fn open_bookmark(
+        &mut self,
+        bookmark: &BookmarkItem,
+        focus: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let row = bookmark
+            .anchor
+            .to_point(&bookmark.buffer.read(cx).snapshot())
+            .row;
+        workspace.update(cx, |workspace, cx| {
+            let pane = workspace.active_pane().clone();
+            let editor = workspace.open_project_item::<Editor>(
+                pane,
+                bookmark.buffer.clone(),
+                true,
+                focus,
+                true,
+                true,
+                window,
+                cx,
+            );
+            editor.update(cx, |editor, cx| {
+                let point = language::Point::new(row, 0);
+                editor.change_selections(
+                    SelectionEffects::scroll(Autoscroll::center()),
+                    window,
+                    cx,
+                    |selections| selections.select_ranges([point..point]),
+                );
+            });
+        });
+    }
+
+    fn edit_label(&mut self, _: &EditLabel, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(PanelEntry::Bookmark(bookmark)) = self.selected_entry().cloned() else {
+            return;
+        };
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Describe this bookmark…", window, cx);
+            editor.set_text(bookmark.label.clone().unwrap_or_default(), window, cx);
+            editor.select_all(&editor::actions::SelectAll, window, cx);
+            editor
+        });
+        let subscription = cx.subscribe_in(
+            &editor,
+            window,
+            |this, _, event: &EditorEvent, window, cx| {
+                if let EditorEvent::Blurred = event {
+                    this.confirm_label(window, cx);
+                }
+            },
+        );
+        window.focus(&editor.focus_handle(cx), cx);
+        self.label_editor = Some(LabelEditor {
+            bookmark,
+            editor,
+            _subscription: subscription,
+        });
+        cx.notify();
+    }
+
+    fn confirm_label(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(label_editor) = self.label_editor.take() else {
+            return;
+        };
+        let label = label_editor.editor.read(cx).text(cx);
+        let label = Some(Arc::from(label.trim()));
+        let bookmark = label_editor.bookmark;
+        self.bookmark_store.update(cx, |store, cx| {
+            store.set_bookmark_label(bookmark.buffer, bookmark.anchor, label, cx);
+        });
+        if label_editor.editor.focus_handle(cx).is_focused(window) {
+            self.focus_handle.focus(window, cx);
+        }
+        cx.notify();
+    }
+
+    fn remove_bookmark(&mut self, _: &RemoveBookmark, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(selected) = self.selected_entry() else {
+            return;
+        };
+        let bookmarks = match selected {
+            PanelEntry::Bookmark(bookmark) => vec![bookmark.clone()],
+            PanelEntry::File(file) => self
+                .entries
+                .iter()
+                .filter_map(|entry| match entry {
+                    PanelEntry::Bookmark(bookmark) if bookmark.abs_path == file.abs_path => {
+                        Some(bookmark.clone())
+                    }
+                    _ => None,
+                })
+                .collect(),
+        };
+        self.bookmark_store.update(cx, |store, cx| {
+            for bookmark in bookmarks {
+                store.toggle_bookmark(bookmark.buffer, bookmark.anchor, cx);
+            }
+        });
+    }
+
+    fn move_file_up(&mut self, _: &MoveFileUp, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_selected_file(-1, cx);
+    }
+
+    fn move_file_down(&mut self, _: &MoveFileDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_selected_file(1, cx);
+    }
+
+    fn move_selected_file(&mut self, offset: isize, cx: &mut Context<Self>) {
+        let Some(abs_path) = self.selected_entry().map(|entry| entry.abs_path().clone()) else {
+            return;
+        };
+        let files = self.displayed_files();
+        let Some(ix) = files.iter().position(|file| *file == abs_path) else {
+            return;
+        };
+        let Some(target) = ix
+            .checked_add_signed(offset)
+            .and_then(|target_ix| files.get(target_ix))
+        else {
+            return;
+        };
+        self.move_file(&abs_path, &target.clone(), cx);
+    }
+
+    fn displayed_files(&self) -> Vec<Arc<Path>> {
+        self.entries
+            .iter()
+            .filter_map(|entry| match entry {
+                PanelEntry::File(file) => Some(file.abs_path.clone()),
+                PanelEntry::Bookmark(_) => None,
+            })
+            .collect()
+    }
+
+    /// Moves a file to the position of `target`, shifting the files in between.
+    fn move_file(&mut self, abs_path: &Arc<Path>, target: &Arc<Path>, cx: &mut Context<Self>) {
+        if abs_path == target {
+            return;
+        }
+        let Some(ix) = self.file_order.iter().position(|file| file == abs_path) else {
+            return;
+        };
+        let Some(target_ix) = self.file_order.iter().position(|file| file == target) else {
+            return;
+        };
+        let file = self.file_order.remove(ix);
+        self.file_order.insert(target_ix, file);
+        self.serialize(cx);
+        self.rebuild_entries(cx);
+    }
+
+    fn shared_bookmarks_location(
+        &self,
+        cx: &App,
+    ) -> Option<(Entity<project::Worktree>, std::path::PathBuf)> {
+        let project = self.project.read(cx);
+        if !project.is_local() {
+            return None;
+        }
+        let worktree = project.visible_worktrees(cx).next()?;
+        let abs_path = worktree
+            .read(cx)
+            .absolutize(paths::local_bookmarks_file_relative_path());
+        Some((worktree, abs_path))
+    }
+
+    fn export_bookmarks(
+        &mut self,
+        _: &ExportBookmarks,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((worktree, abs_path)) = self.shared_bookmarks_location(cx) else {
+            return;
+        };
+        let bookmarks =
+            self.bookmark_store
+                .read(cx)
+                .shared_bookmarks(&worktree, &self.file_order, cx);
+        let fs = self.fs.clone();
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |_, cx| {
+            let count = bookmarks.len();
+            let contents = serde_json::to_string_pretty(&bookmarks)?;
+            if let Some(parent) = abs_path.parent() {
+                fs.create_dir(parent).await?;
+            }
+            fs.atomic_write(abs_path, contents).await?;
+            workspace.update(cx, |workspace, cx| {
+                struct ExportedBookmarks;
+                workspace.show_toast(
+                    Toast::new(
+                        NotificationId::unique::<ExportedBookmarks>(),
+                        format!(
+                            "Exported {count} bookmark{} to {}",
+                            if count == 1 { "" } else { "s" },
+                            paths::local_bookmarks_file_relative_path().as_unix_str()
+                        ),
+                    )
+                    .autohide(),
+                    cx,
+                );
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to export bookmarks", window, cx, |_, _, _| None);
+    }
+
+    fn import_bookmarks(
+        &mut self,
+        _: &ImportBookmarks,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((worktree, abs_path)) = self.shared_bookmarks_location(cx) else {
+            return;
+        };
+        let fs = self.fs.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            let contents = fs
+                .load(&abs_path)
+                .await
+                .with_context(|| format!("reading {}", abs_path.display()))?;
+            let bookmarks: Vec<SharedBookmark> = settings::parse_json_with_comments(&contents)
+                .with_context(|| format!("parsing {}", abs_path.display()))?;
+            this.update(cx, |this, cx| {
+                let imported_order = this.bookmark_store.update(cx, |store, cx| {
+                    store.add_shared_bookmarks(&worktree, bookmarks, cx)
+                });
+                // The files of the tour come first, in its order.
+                this.file_order
+                    .retain(|abs_path| !imported_order.contains(abs_path));
+                this.file_order.splice(0..0, imported_order);
+                this.serialize(cx);
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to import bookmarks", window, cx, |_, _, _| None);
+    }
+
+    fn deploy_context_menu(
+        &mut self,
+        position: Point<Pixels>,
+        ix: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.selected_index = Some(ix);
+        let is_bookmark = matches!(self.entries.get(ix), Some(PanelEntry::Bookmark(_)));
+        let can_share = self.shared_bookmarks_location(cx).is_some();
+        let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
+            menu.context(self.focus_handle.clone())
+                .when(is_bookmark, |menu| {
+                    menu.action("Edit Label", Box::new(EditLabel))
+                        .action("Remove Bookmark", Box::new(RemoveBookmark))
+                })
+                .when(!is_bookmark, |menu| {
+                    menu.action("Remove Bookmarks in File", Box::new(RemoveBookmark))
+                })
+                .separator()
+                .action("Move File Up", Box::new(MoveFileUp))
+                .action("Move File Down", Box::new(MoveFileDown))
+                .when(can_share, |menu| {
+                    menu.separator()
+                        .action("Export to .zed/bookmarks.json", Box::new(ExportBookmarks))
+                        .action("Import from .zed/bookmarks.json", Box::new(ImportBookmarks))
+                })
+        });
+        window.focus(&context_menu.focus_handle(cx), cx);
+        let subscription = cx.subscribe(&context_menu, |this, _, _: &DismissEvent, cx| {
+            this.context_menu.take();
+            cx.notify();
+        });
+        self.context_menu = Some((context_menu, position, subscription));
+        cx.notify();
+    }
+
+    fn dispatch_context(&self, window: &Window, cx: &App) -> KeyContext {
+        let mut dispatch_context = KeyContext::new_with_defaults();
+        dispatch_context.add("BookmarksPanel");
+        dispatch_context.add("menu");
+        let is_editing = self
+            .label_editor
+            .as_ref()
+            .is_some_and(|label_editor| label_editor.editor.focus_handle(cx).is_focused(window));
+        dispatch_context.add(if is_editing { "editing" } else { "not_editing" });
+        dispatch_context
+    }
+
+    fn render_entries(&mut self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<AnyElement> {
+        let path_style = self.project.read(cx).path_style(cx);
+        let show_file_icons = BookmarksPanelSettings::get_global(cx).file_icons;
+        range
+            .map(|ix| {
+                let is_selected = self.selected_index == Some(ix);
+                match &self.entries[ix] {
+                    PanelEntry::File(file) => self
+                        .render_file(ix, file, is_selected, show_file_icons, path_style, cx)
+                        .into_any_element(),
+                    PanelEntry::Bookmark(bookmark) => self
+                        .render_bookmark(ix, bookmark, is_selected, cx)
+                        .into_any_element(),
+                }
+            })
+            .collect()
+    }
+
+    fn render_file(
+        &self,
+        ix: usize,
+        file: &FileEntry,
+        is_selected: bool,
+        show_file_icons: bool,
+        path_style: util::paths::PathStyle,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let name: SharedString = file
+            .buffer
+            .read(cx)
+            .file()
+            .map(|buffer_file| buffer_file.path().display(path_style).to_string())
+            .unwrap_or_else(|| file.abs_path.to_string_lossy().to_string())
+            .into();
+        let icon = show_file_icons
+            .then(|| FileIcons::get_icon(&file.abs_path, cx))
+            .flatten()
+            .map(|icon| Icon::from_path(icon).color(Color::Muted));
+        let dragged = DraggedBookmarkFile {
+            abs_path: file.abs_path.clone(),
+            name: name.clone(),
+            click_offset: Point::default(),
+        };
+        let target = file.abs_path.clone();
+
+        div()
+            .id(("bookmarks-file", ix))
+            .on_drag(dragged, |dragged, click_offset, _, cx| {
+                cx.new(|_| DraggedBookmarkFile {
+                    click_offset,
+                    ..dragged.clone()
+                })
+            })
+            .drag_over::<DraggedBookmarkFile>(|style, _, _, cx| {
+                style.bg(cx.theme().colors().drop_target_background)
+            })
+            .on_drop(
+                cx.listener(move |this, dragged: &DraggedBookmarkFile, _, cx| {
+                    this.move_file(&dragged.abs_path, &target, cx);
+                }),
+            )
+            .child(
+                ListItem::new(("bookmarks-file-item", ix))
+                    .toggle_state(is_selected)
+                    .start_slot::<Icon>(icon)
+                    .on_click(cx.listener(move |this, _, _, cx| this.select_index(ix, cx)))
+                    .on_secondary_mouse_down(cx.listener(
+                        move |this, event: &MouseDownEvent, window, cx| {
+                            cx.stop_propagation();
+                            this.deploy_context_menu(event.position, ix, window, cx);
+                        },
+                    ))
+                    .child(Label::new(name).size(LabelSize::Small).truncate()),
+            )
+    }
+
+    fn render_bookmark(
+        &self,
+        ix: usize,
+        bookmark: &BookmarkItem,
+        is_selected: bool,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let snapshot = bookmark.buffer.read(cx).snapshot();
+        let row = bookmark.anchor.to_point(&snapshot).row;
+        let line_start = language::Point::new(row, 0);
+        let line_end = language::Point::new(row, snapshot.line_len(row));
+        let preview: String = snapshot.text_for_range(line_start..line_end).collect();
+        let preview = preview.trim().to_string();
+        let editing = self
+            .label_editor
+            .as_ref()
+            .filter(|label_editor| {
+                label_editor.bookmark.abs_path == bookmark.abs_path
+                    && label_editor.bookmark.anchor == bookmark.anchor
+            })
+            .map(|label_editor| label_editor.editor.clone());
+        let label = bookmark.label.clone();
+        let target = bookmark.abs_path.clone();
+        let bookmark = bookmark.clone();
+
+        div()
+            .id(("bookmark", ix))
+            .drag_over::<DraggedBookmarkFile>(|style, _, _, cx| {
+                style.bg(cx.theme().colors().drop_target_background)
+            })
+            .on_drop(
+                cx.listener(move |this, dragged: &DraggedBookmarkFile, _, cx| {
+                    this.move_file(&dragged.abs_path, &target, cx);
+                }),
+            )
+            .child(
+                ListItem::new(("bookmark-item", ix))
+                    .indent_level(1)
+                    .indent_step_size(px(12.))
+                    .toggle_state(is_selected)
+                    .start_slot(
+                        Icon::new(IconName::Bookmark)
+                            .size(IconSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.select_index(ix, cx);
+                        this.open_bookmark(&bookmark, false, window, cx);
+                    }))
+                    .on_secondary_mouse_down(cx.listener(
+                        move |this, event: &MouseDownEvent, window, cx| {
+                            cx.stop_propagation();
+                            this.deploy_context_menu(event.position, ix, window, cx);
+                        },
+                    ))
+                    .child(
+                        v_flex()
+                            .w_full()
+                            .overflow_hidden()
+                            .map(|this| match (editing, label) {
+                                (Some(editor), _) => this.child(editor),
+                                (None, Some(label)) => this.child(
+                                    Label::new(SharedString::from(label))
+                                        .size(LabelSize::Small)
+                                        .truncate(),
+                                ),
+                                (None, None) => this,
+                            })
+                            .child(
+                                h_flex()
+                                    .gap_1()
+                                    .child(
+                                        Label::new(format!("{}", row + 1))
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted),
+                                    )
+                                    .child(
+                                        Label::new(preview)
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted)
+                                            .buffer_font(cx)
+                                            .truncate(),
+                                    ),
+                            ),
+                    ),
+            )
+    }
+
+    fn render_empty_state(&self) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .gap_1()
+            .p_4()
+            .items_center()
+            .justify_center()
+            .child(Label::new("No bookmarks").color(Color::Muted))
+            .child(
+                Label::new("Toggle bookmarks in the editor's gutter to list them here.")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let can_share = self.shared_bookmarks_location(cx).is_some();
+        let focus_handle = self.focus_handle.clone();
+        h_flex()
+            .px_2()
+            .h(Tab::container_height(cx))
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Label::new("Bookmarks").size(LabelSize::Small))
+            .when(can_share, |this| {
+                this.child(
+                    h_flex()
+                        .gap_0p5()
+                        .child(
+                            IconButton::new("import-bookmarks", IconName::Download)
+                                .icon_size(IconSize::Small)
+                                .tooltip({
+                                    let focus_handle = focus_handle.clone();
+                                    move |_, cx| {
+                                        Tooltip::for_action_in(
+                                            "Import from .zed/bookmarks.json",
+                                            &ImportBookmarks,
+                                            &focus_handle,
+                                            cx,
+                                        )
+                                    }
+                                })
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.import_bookmarks(&ImportBookmarks, window, cx)
+                                })),
+                        )
+                        .child(
+                            IconButton::new("export-bookmarks", IconName::Share)
+                                .icon_size(IconSize::Small)
+                                .disabled(self.entries.is_empty())
+                                .tooltip(move |_, cx| {
+                                    Tooltip::for_action_in(
+                                        "Export to .zed/bookmarks.json",
+                                        &ExportBookmarks,
+                                        &focus_handle,
+                                        cx,
+                                    )
+                                })
+                                .on_click(cx.listener(|this, _, window, cx| {
+                                    this.export_bookmarks(&ExportBookmarks, window, cx)
+                                })),
+                        ),
+                )
+            })
+    }
+}
+
+impl Panel for BookmarksPanel {
+    fn persistent_name() -> &'static str {
+        "Bookmarks Panel"
+    }
+
+    fn panel_key() -> &'static str {
+        BOOKMARKS_PANEL_KEY
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        match BookmarksPanelSettings::get_global(cx).dock {
+            DockSide::Left => DockPosition::Left,
+            DockSide::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file(self.fs.clone(), cx, move |settings, _| {
+            let dock = match position {
+                DockPosition::Left | DockPosition::Bottom => DockSide::Left,
+                DockPosition::Right => DockSide::Right,
+            };
+            settings.bookmarks_panel.get_or_insert_default().dock = Some(dock);
+        });
+    }
+
+    fn default_size(&self, _: &Window, cx: &App) -> Pixels {
+        BookmarksPanelSettings::get_global(cx).default_width
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        BookmarksPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::Bookmark)
+    }
+
+    fn icon_tooltip(&self, _window: &Window, _: &App) -> Option<&'static str> {
+        Some("Bookmarks Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn starts_open(&self, _window: &Window, _: &App) -> bool {
+        self.active
+    }
+
+    fn set_active(&mut self, active: bool, _: &mut Window, cx: &mut Context<Self>) {
+        if self.active != active {
+            self.active = active;
+            self.serialize(cx);
+            self.update_entries(cx);
+        }
+    }
+
+    fn activation_priority(&self) -> u32 {
+        8
+    }
+
+    fn hide_button_setting(&self, _: &App) -> Option<workspace::HideStatusItem> {
+        Some(workspace::HideStatusItem::new(|settings| {
+            settings.bookmarks_panel.get_or_insert_default().button = Some(false);
+        }))
+    }
+}
+
+impl Focusable for BookmarksPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for BookmarksPanel {}
+
+impl Render for BookmarksPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .id("bookmarks-panel")
+            .size_full()
+            .overflow_hidden()
+            .key_context(self.dispatch_context(window, cx))
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::edit_label))
+            .on_action(cx.listener(Self::remove_bookmark))
+            .on_action(cx.listener(Self::move_file_up))
+            .on_action(cx.listener(Self::move_file_down))
+            .on_action(cx.listener(Self::export_bookmarks))
+            .on_action(cx.listener(Self::import_bookmarks))
+            .on_mouse_down(
+                MouseButton::Right,
+                cx.listener(|this, event: &MouseDownEvent, window, cx| {
+                    if let Some(ix) = this.selected_index {
+                        this.deploy_context_menu(event.position, ix, window, cx);
+                    }
+                }),
+            )
+            .child(self.render_toolbar(cx))
+            .map(|this| {
+                if self.entries.is_empty() {
+                    this.child(self.render_empty_state())
+                } else {
+                    this.child(
+                        div()
+                            .size_full()
+                            .child(
+                                uniform_list(
+                                    "bookmarks-panel-entries",
+                                    self.entries.len(),
+                                    cx.processor(|this, range: Range<usize>, _, cx| {
+                                        this.render_entries(range, cx)
+                                    }),
+                                )
+                                .size_full()
+                                .track_scroll(&self.scroll_handle),
+                            )
+                            .vertical_scrollbar_for(&self.scroll_handle, window, cx),
+                    )
+                }
+            })
+            .children(self.context_menu.as_ref().map(|(menu, position, _)| {
+                deferred(
+                    anchored()
+                        .position(*position)
+                        .anchor(gpui::Anchor::TopLeft)
+                        .child(menu.clone()),
+                )
+                .with_priority(1)
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{TestAppContext, VisualTestContext};
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    use super::*;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+
+            theme_settings::init(theme::LoadThemes::JustBase, cx);
+
+            editor::init(cx);
+            super::init(cx);
+        });
+    }
+
+    fn display_entries(panel: &BookmarksPanel, cx: &App) -> Vec<String> {
+        panel
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                PanelEntry::File(file) => file
+                    .abs_path
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string(),
+                PanelEntry::Bookmark(bookmark) => {
+                    let snapshot = bookmark.buffer.read(cx).snapshot();
+                    let row = bookmark.anchor.to_point(&snapshot).row;
+                    let line: String = snapshot
+                        .text_for_range(
+                            language::Point::new(row, 0)
+                                ..language::Point::new(row, snapshot.line_len(row)),
+                        )
+                        .collect();
+                    match &bookmark.label {
+                        Some(label) => format!("  {}: {line} ({label})", row + 1),
+                        None => format!("  {}: {line}", row + 1),
+                    }
+                }
+            })
+            .collect()
+    }
+
+    #[gpui::test]
+    async fn test_bookmarks_panel_entries(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/root"),
+            json!({
+                "a.rs": "fn a() {}\nfn b() {}\nfn c() {}\n",
+                "b.rs": "one\ntwo\nthree\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [path!("/root").as_ref()], cx).await;
+        let window =
+            cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = window
+            .read_with(cx, |multi_workspace, _| multi_workspace.workspace().clone())
+            .unwrap();
+        let cx = &mut VisualTestContext::from_window(window.into(), cx);
+        let panel = workspace.update_in(cx, |workspace, window, cx| {
+            let panel = BookmarksPanel::new(workspace, None, cx);
+            panel.update(cx, |panel, cx| panel.set_active(true, window, cx));
+            panel
+        });
+
+        let open_buffer = |path: &'static str, cx: &mut VisualTestContext| {
+            project.update(cx, |project, cx| project.open_local_buffer(path, cx))
+        };
+        let buffer_a = open_buffer(path!("/root/a.rs"), cx).await.unwrap();
+        let buffer_b = open_buffer(path!("/root/b.rs"), cx).await.unwrap();
+        let bookmark_store = project.read_with(cx, |project, _| project.bookmark_store());
+        let toggle_bookmark = |buffer: &Entity<Buffer>, row: u32, cx: &mut VisualTestContext| {
+            bookmark_store.update(cx, |store, cx| {
+                let anchor = buffer
+                    .read(cx)
+                    .snapshot()
+                    .anchor_after(language::Point::new(row, 0));
+                store.toggle_bookmark(buffer.clone(), anchor, cx);
+            });
+        };
+        toggle_bookmark(&buffer_b, 2, cx);
+        toggle_bookmark(&buffer_a, 2, cx);
+        toggle_bookmark(&buffer_a, 0, cx);
+        cx.run_until_parked();
+
+        panel.update(cx, |panel, cx| {
+            assert_eq!(
+                display_entries(panel, cx),
+                [
+                    "a.rs",
+                    "  1: fn a() {}",
+                    "  3: fn c() {}",
+                    "b.rs",
+                    "  3: three"
+                ]
+            );
+        });
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.select_index(2, cx);
+            panel.edit_label(&EditLabel, window, cx);
+            let editor = panel.label_editor.as_ref().unwrap().editor.clone();
+            editor.update(cx, |editor, cx| editor.set_text("Entry point", window, cx));
+            panel.confirm(&Confirm, window, cx);
+        });
+        cx.run_until_parked();
+        panel.update(cx, |panel, cx| {
+            assert!(panel.label_editor.is_none());
+            assert_eq!(
+                display_entries(panel, cx),
+                [
+                    "a.rs",
+                    "  1: fn a() {}",
+                    "  3: fn c() {} (Entry point)",
+                    "b.rs",
+                    "  3: three"
+                ]
+            );
+        });
+
+        // Previews follow edits to the buffer
+        buffer_a.update(cx, |buffer, cx| {
+            buffer.edit([(0..0, "// header\n")], None, cx);
+        });
+        cx.run_until_parked();
+        panel.update(cx, |panel, cx| {
+            assert_eq!(
+                display_entries(panel, cx),
+                [
+                    "a.rs",
+                    "  2: fn a() {}",
+                    "  4: fn c() {} (Entry point)",
+                    "b.rs",
+                    "  3: three"
+                ]
+            );
+        });
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.select_index(4, cx);
+            panel.move_file_up(&MoveFileUp, window, cx);
+        });
+        cx.run_until_parked();
+        panel.update(cx, |panel, cx| {
+            assert_eq!(
+                display_entries(panel, cx),
+                [
+                    "b.rs",
+                    "  3: three",
+                    "a.rs",
+                    "  2: fn a() {}",
+                    "  4: fn c() {} (Entry point)"
+                ]
+            );
+            assert_eq!(panel.selected_index, Some(1));
+        });
+
+        panel.update_in(cx, |panel, window, cx| {
+            panel.select_index(2, cx);
+            panel.remove_bookmark(&RemoveBookmark, window, cx);
+        });
+        cx.run_until_parked();
+        panel.update(cx, |panel, cx| {
+            assert_eq!(display_entries(panel, cx), ["b.rs", "  3: three"]);
+        });
+    }
+}
@@ -117,6 +117,9 @@ CREATE TABLE "project_repositories" (
     "linked_worktrees" VARCHAR,
     "repository_dir_abs_path" VARCHAR,
     "common_dir_abs_path" VARCHAR,
+    "rebase_progress" VARCHAR,
+    "bisect_status" VARCHAR,
+    "submodules" VARCHAR,
     PRIMARY KEY (project_id, id)
 );
 
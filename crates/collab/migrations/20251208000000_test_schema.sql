@@ -314,7 +314,10 @@ CREATE TABLE public.project_repositories (
     remote_origin_url character varying,
     linked_worktrees text,
     repository_dir_abs_path character varying,
-    common_dir_abs_path character varying
+    common_dir_abs_path character varying,
+    rebase_progress text,
+    bisect_status text,
+    submodules text
 );
 
 CREATE TABLE public.project_repository_statuses (
@@ -384,6 +384,21 @@ impl Database {
                 linked_worktrees: ActiveValue::Set(Some(
                     serde_json::to_string(&update.linked_worktrees).unwrap(),
                 )),
+                rebase_progress: ActiveValue::Set(
+                    update
+                        .rebase_progress
+                        .as_ref()
+                        .map(|progress| serde_json::to_string(progress).unwrap()),
+                ),
+                bisect_status: ActiveValue::Set(
+                    update
+                        .bisect_status
+                        .as_ref()
+                        .map(|status| serde_json::to_string(status).unwrap()),
+                ),
+                submodules: ActiveValue::Set(Some(
+                    serde_json::to_string(&update.submodules).unwrap(),
+                )),
             })
             .on_conflict(
                 OnConflict::columns([
@@ -401,6 +416,9 @@ impl Database {
                     project_repository::Column::RepositoryDirAbsPath,
                     project_repository::Column::CommonDirAbsPath,
                     project_repository::Column::LinkedWorktrees,
+                    project_repository::Column::RebaseProgress,
+                    project_repository::Column::BisectStatus,
+                    project_repository::Column::Submodules,
                 ])
                 .to_owned(),
             )
@@ -907,6 +925,19 @@ impl Database {
                             .as_deref()
                             .and_then(|s| serde_json::from_str(s).ok())
                             .unwrap_or_default(),
+                        rebase_progress: db_repository_entry
+                            .rebase_progress
+                            .as_deref()
+                            .and_then(|s| serde_json::from_str(s).ok()),
+                        bisect_status: db_repository_entry
+                            .bisect_status
+                            .as_deref()
+                            .and_then(|s| serde_json::from_str(s).ok()),
+                        submodules: db_repository_entry
+                            .submodules
+                            .as_deref()
+                            .and_then(|s| serde_json::from_str(s).ok())
+                            .unwrap_or_default(),
                     });
                 }
             }
@@ -808,6 +808,19 @@ impl Database {
                                 .as_deref()
                                 .and_then(|s| serde_json::from_str(s).ok())
                                 .unwrap_or_default(),
+                            rebase_progress: db_repository
+                                .rebase_progress
+                                .as_deref()
+                                .and_then(|s| serde_json::from_str(s).ok()),
+                            bisect_status: db_repository
+                                .bisect_status
+                                .as_deref()
+                                .and_then(|s| serde_json::from_str(s).ok()),
+                            submodules: db_repository
+                                .submodules
+                                .as_deref()
+                                .and_then(|s| serde_json::from_str(s).ok())
+                                .unwrap_or_default(),
                         });
                     }
                 }
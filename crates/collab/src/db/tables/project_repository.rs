@@ -28,6 +28,12 @@ pub struct Model {
     pub common_dir_abs_path: Option<String>,
     // JSON array of linked worktree objects
     pub linked_worktrees: Option<String>,
+    // A JSON object describing an in-progress rebase
+    pub rebase_progress: Option<String>,
+    // A JSON object describing an in-progress bisect
+    pub bisect_status: Option<String>,
+    // JSON array of submodule objects
+    pub submodules: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
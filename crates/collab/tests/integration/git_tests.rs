@@ -8,6 +8,8 @@ use client::RECEIVE_TIMEOUT;
 use collections::HashMap;
 use git::{
     Oid,
+    bisect::BisectStatus,
+    rebase::RebaseProgress,
     repository::{CommitData, InitialGraphCommitData, RepoPath, Worktree as GitWorktree},
     status::{DiffStat, FileStatus, StatusCode, TrackedStatus},
 };
@@ -1209,6 +1211,71 @@ async fn test_linked_worktrees_sync(
     );
 }
 
+#[gpui::test]
+async fn test_repository_operation_state_for_late_joiner(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree(
+            path!("/project"),
+            json!({ ".git": {}, "file.txt": "content" }),
+        )
+        .await;
+    let (project_a, _) = client_a.build_local_project(path!("/project"), cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    // The host stops partway through a rebase and starts a bisect before the guest joins,
+    // so the guest only learns about them from the state stored in the database.
+    let rebase = RebaseProgress {
+        done: 1,
+        total: 3,
+        stopped_at: Some("e".repeat(40).parse().unwrap()),
+        branch: Some("feature".into()),
+    };
+    let bisect = BisectStatus {
+        bad: Some("b".repeat(40).parse().unwrap()),
+        good: vec!["a".repeat(40).parse().unwrap()],
+        current: Some("c".repeat(40).parse().unwrap()),
+        remaining_steps: Some(2),
+        ..BisectStatus::default()
+    };
+    client_a
+        .fs()
+        .with_git_state(Path::new(path!("/project/.git")), true, |state| {
+            state.rebase_progress = Some(rebase.clone());
+            state.bisect = Some(bisect.clone());
+        })
+        .unwrap();
+    executor.run_until_parked();
+
+    let project_b = client_b.join_remote_project(project_id, cx_b).await;
+    executor.run_until_parked();
+
+    let (guest_rebase, guest_bisect) = project_b.read_with(cx_b, |project, cx| {
+        let repos = project.repositories(cx);
+        let repo = repos.values().next().unwrap();
+        let merge = &repo.read(cx).merge;
+        (merge.rebase.clone(), merge.bisect.clone())
+    });
+    assert_eq!(guest_rebase, Some(rebase));
+    assert_eq!(guest_bisect, Some(bisect));
+}
+
 #[gpui::test]
 async fn test_diff_stat_sync_between_host_and_downstream_client(
     cx_a: &mut TestAppContext,
@@ -37,7 +37,7 @@ pub const INTERNAL_ERROR: i32 = -32603;
 
 type ResponseHandler = Box<dyn Send + FnOnce(String)>;
 type NotificationHandler = Box<dyn Send + FnMut(Value, AsyncApp)>;
-type RequestHandler = Box<dyn Send + FnMut(RequestId, &RawValue, AsyncApp)>;
+type RequestHandler = Box<dyn Send + FnMut(&RawValue, AsyncApp) -> Task<Result<Value>>>;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -53,6 +53,7 @@ pub(crate) struct Client {
     name: Arc<str>,
     subscription_set: Arc<Mutex<NotificationSubscriptionSet>>,
     response_handlers: Arc<Mutex<Option<HashMap<RequestId, ResponseHandler>>>>,
+    request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
     #[allow(clippy::type_complexity)]
     #[allow(dead_code)]
     io_tasks: Mutex<Option<(Task<Option<()>>, Task<Option<()>>)>>,
@@ -109,7 +110,6 @@ struct AnyResponse<'a> {
 }
 
 #[derive(Serialize, Deserialize)]
-#[allow(dead_code)]
 pub(crate) struct Response<T> {
     pub jsonrpc: &'static str,
     pub id: RequestId,
@@ -122,7 +122,6 @@ pub(crate) struct Response<T> {
 pub(crate) enum CspResult<T> {
     #[serde(rename = "result")]
     Ok(Option<T>),
-    #[allow(dead_code)]
     Error(Option<Error>),
 }
 
@@ -204,12 +203,18 @@ impl Client {
         let subscription_set = Arc::new(Mutex::new(NotificationSubscriptionSet::default()));
         let response_handlers =
             Arc::new(Mutex::new(Some(HashMap::<_, ResponseHandler>::default())));
-        let request_handlers = Arc::new(Mutex::new(HashMap::<_, RequestHandler>::default()));
+        let mut request_handlers = HashMap::<_, RequestHandler>::default();
+        request_handlers.insert(
+            "ping",
+            Box::new(|_, _| Task::ready(Ok(Value::Object(Default::default())))),
+        );
+        let request_handlers = Arc::new(Mutex::new(request_handlers));
 
         let receive_input_task = cx.spawn({
             let subscription_set = subscription_set.clone();
             let response_handlers = response_handlers.clone();
             let request_handlers = request_handlers.clone();
+            let outbound_tx = outbound_tx.clone();
             let transport = transport.clone();
             async move |cx| {
                 Self::handle_input(
@@ -217,6 +222,7 @@ impl Client {
                     subscription_set,
                     request_handlers,
                     response_handlers,
+                    outbound_tx,
                     cx,
                 )
                 .log_err()
@@ -250,6 +256,7 @@ impl Client {
             server_id,
             subscription_set,
             response_handlers,
+            request_handlers,
             name: server_name,
             next_id: Default::default(),
             outbound_tx,
@@ -266,13 +273,15 @@ impl Client {
     ///
     /// This function continuously reads lines from the provided stdout stream,
     /// parses them as JSON-RPC responses or notifications, and dispatches them
-    /// to the appropriate handlers. It processes both responses (which are matched
-    /// to pending requests) and notifications (which trigger registered handlers).
+    /// to the appropriate handlers. It processes responses (which are matched
+    /// to pending requests), requests from the server (which are answered by
+    /// registered handlers) and notifications (which trigger registered handlers).
     async fn handle_input(
         transport: Arc<dyn Transport>,
         subscription_set: Arc<Mutex<NotificationSubscriptionSet>>,
         request_handlers: Arc<Mutex<HashMap<&'static str, RequestHandler>>>,
         response_handlers: Arc<Mutex<Option<HashMap<RequestId, ResponseHandler>>>>,
+        outbound_tx: async_channel::Sender<String>,
         cx: &mut AsyncApp,
     ) -> anyhow::Result<()> {
         let mut receiver = transport.receive();
@@ -280,14 +289,42 @@ impl Client {
         while let Some(message) = receiver.next().await {
             log::trace!("recv: {}", &message);
             if let Ok(request) = serde_json::from_str::<AnyRequest>(&message) {
-                let mut request_handlers = request_handlers.lock();
-                if let Some(handler) = request_handlers.get_mut(request.method) {
-                    handler(
-                        request.id,
-                        request.params.unwrap_or(RawValue::NULL),
-                        cx.clone(),
-                    );
-                }
+                let handler_task = request_handlers
+                    .lock()
+                    .get_mut(request.method)
+                    .map(|handler| handler(request.params.unwrap_or(RawValue::NULL), cx.clone()));
+                let method = request.method.to_string();
+                let id = request.id;
+                let outbound_tx = outbound_tx.clone();
+                cx.background_spawn(
+                    async move {
+                        let value = match handler_task {
+                            Some(task) => match task.await {
+                                Ok(result) => CspResult::Ok(Some(result)),
+                                Err(error) => {
+                                    log::debug!("failed to handle {method:?} request: {error:#}");
+                                    CspResult::Error(Some(Error {
+                                        message: error.to_string(),
+                                        code: INTERNAL_ERROR,
+                                    }))
+                                }
+                            },
+                            None => CspResult::Error(Some(Error {
+                                message: format!("Method not found: {method}"),
+                                code: METHOD_NOT_FOUND,
+                            })),
+                        };
+                        let response = serde_json::to_string(&Response {
+                            jsonrpc: JSON_RPC_VERSION,
+                            id,
+                            value,
+                        })?;
+                        outbound_tx.send(response).await?;
+                        anyhow::Ok(())
+                    }
+                    .log_err(),
+                )
+                .detach();
             } else if let Ok(response) = serde_json::from_str::<AnyResponse>(&message) {
                 if let Some(handlers) = response_handlers.lock().as_mut()
                     && let Some(handler) = handlers.remove(&response.id)
@@ -482,6 +519,16 @@ impl Client {
         self.transport.set_protocol_version(version);
     }
 
+    /// Registers a handler for requests that the server sends to the client. The value that the
+    /// returned task resolves to is sent back as the result, and errors are reported to the server.
+    pub fn on_request(
+        &self,
+        method: &'static str,
+        f: Box<dyn 'static + Send + FnMut(&RawValue, AsyncApp) -> Task<Result<Value>>>,
+    ) {
+        self.request_handlers.lock().insert(method, f);
+    }
+
     #[must_use]
     pub fn on_notification(
         &self,
@@ -592,3 +639,104 @@ impl Drop for NotificationSubscription {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{Stream, channel::mpsc};
+    use gpui::TestAppContext;
+
+    struct ChannelTransport {
+        incoming: Mutex<Option<mpsc::UnboundedReceiver<String>>>,
+        outgoing: mpsc::UnboundedSender<String>,
+    }
+
+    #[async_trait::async_trait]
+    impl Transport for ChannelTransport {
+        async fn send(&self, message: String) -> Result<()> {
+            self.outgoing.unbounded_send(message)?;
+            Ok(())
+        }
+
+        fn receive(&self) -> std::pin::Pin<Box<dyn Stream<Item = String> + Send>> {
+            Box::pin(self.incoming.lock().take().expect("receive called twice"))
+        }
+
+        fn receive_err(&self) -> std::pin::Pin<Box<dyn Stream<Item = String> + Send>> {
+            Box::pin(futures::stream::empty())
+        }
+    }
+
+    #[gpui::test]
+    async fn test_responding_to_server_requests(cx: &mut TestAppContext) {
+        let (server_tx, incoming) = mpsc::unbounded();
+        let (outgoing, mut server_rx) = mpsc::unbounded();
+        let transport = Arc::new(ChannelTransport {
+            incoming: Mutex::new(Some(incoming)),
+            outgoing,
+        });
+        let client = Client::new(
+            ContextServerId("test".into()),
+            "test".into(),
+            transport,
+            None,
+            cx.to_async(),
+        )
+        .unwrap();
+        client.on_request(
+            "sampling/createMessage",
+            Box::new(|params, _| {
+                let params: Value = serde_json::from_str(params.get()).unwrap();
+                Task::ready(Ok(serde_json::json!({ "echo": params["maxTokens"] })))
+            }),
+        );
+        client.on_request(
+            "elicitation/create",
+            Box::new(|_, _| Task::ready(Err(anyhow!("User declined")))),
+        );
+
+        let mut respond_to = async |request: Value| {
+            server_tx.unbounded_send(request.to_string()).unwrap();
+            cx.run_until_parked();
+            let response = server_rx.next().await.unwrap();
+            serde_json::from_str::<Value>(&response).unwrap()
+        };
+
+        assert_eq!(
+            respond_to(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "sampling/createMessage",
+                "params": { "messages": [], "maxTokens": 10 }
+            }))
+            .await,
+            serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": { "echo": 10 } })
+        );
+        assert_eq!(
+            respond_to(serde_json::json!({ "jsonrpc": "2.0", "id": "a", "method": "ping" })).await,
+            serde_json::json!({ "jsonrpc": "2.0", "id": "a", "result": {} })
+        );
+        assert_eq!(
+            respond_to(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "elicitation/create",
+                "params": {}
+            }))
+            .await,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "error": { "message": "User declined", "code": INTERNAL_ERROR }
+            })
+        );
+        assert_eq!(
+            respond_to(serde_json::json!({ "jsonrpc": "2.0", "id": 3, "method": "unknown" })).await,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 3,
+                "error": { "message": "Method not found: unknown", "code": METHOD_NOT_FOUND }
+            })
+        );
+    }
+}
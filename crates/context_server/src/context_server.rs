@@ -10,14 +10,15 @@ pub mod types;
 use collections::HashMap;
 use http_client::HttpClient;
 use std::path::Path;
+use std::rc::Rc;
 use std::sync::Arc;
 use std::time::Duration;
 use std::{fmt::Display, path::PathBuf};
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use client::Client;
-use gpui::AsyncApp;
-use parking_lot::RwLock;
+use gpui::{App, AsyncApp, Global, Task};
+use parking_lot::{Mutex, RwLock};
 pub use settings::ContextServerCommand;
 use url::Url;
 
@@ -32,6 +33,39 @@ impl Display for ContextServerId {
     }
 }
 
+/// Answers the requests that context servers make of Zed, such as asking the user's language
+/// model for a completion. Zed only advertises the sampling and elicitation capabilities to
+/// servers once a delegate has been set with [`set_client_delegate`].
+pub trait ContextServerClientDelegate: 'static {
+    fn create_message(
+        &self,
+        server_id: ContextServerId,
+        request: types::CreateMessageRequest,
+        cx: &mut App,
+    ) -> Task<Result<types::CreateMessageResult>>;
+
+    fn elicit(
+        &self,
+        server_id: ContextServerId,
+        request: types::ElicitRequest,
+        cx: &mut App,
+    ) -> Task<Result<types::ElicitResult>>;
+}
+
+struct GlobalContextServerClientDelegate(Rc<dyn ContextServerClientDelegate>);
+
+impl Global for GlobalContextServerClientDelegate {}
+
+pub fn set_client_delegate(delegate: Rc<dyn ContextServerClientDelegate>, cx: &mut App) {
+    cx.set_global(GlobalContextServerClientDelegate(delegate));
+}
+
+fn client_delegate(cx: &App) -> Result<Rc<dyn ContextServerClientDelegate>> {
+    cx.try_global::<GlobalContextServerClientDelegate>()
+        .map(|delegate| delegate.0.clone())
+        .ok_or_else(|| anyhow!("no context server client delegate"))
+}
+
 enum ContextServerTransport {
     Stdio(ContextServerCommand, Option<PathBuf>),
     Custom(Arc<dyn crate::transport::Transport>),
@@ -42,6 +76,7 @@ pub struct ContextServer {
     client: RwLock<Option<Arc<crate::protocol::InitializedContextServerProtocol>>>,
     configuration: ContextServerTransport,
     request_timeout: Option<Duration>,
+    roots: Arc<Mutex<Vec<types::Root>>>,
 }
 
 impl ContextServer {
@@ -58,6 +93,7 @@ impl ContextServer {
                 working_directory.map(|directory| directory.to_path_buf()),
             ),
             request_timeout: None,
+            roots: Default::default(),
         }
     }
 
@@ -95,6 +131,7 @@ impl ContextServer {
             client: RwLock::new(None),
             configuration: ContextServerTransport::Custom(transport),
             request_timeout,
+            roots: Default::default(),
         }
     }
 
@@ -106,8 +143,27 @@ impl ContextServer {
         self.client.read().clone()
     }
 
+    pub fn roots(&self) -> Vec<types::Root> {
+        self.roots.lock().clone()
+    }
+
+    /// Sets the roots that the server may operate in, notifying it if it's running and they changed.
+    pub fn set_roots(&self, roots: Vec<types::Root>) -> Result<()> {
+        {
+            let mut current_roots = self.roots.lock();
+            if *current_roots == roots {
+                return Ok(());
+            }
+            *current_roots = roots;
+        }
+        if let Some(client) = self.client() {
+            client.notify::<types::notifications::RootsListChanged>(())?;
+        }
+        Ok(())
+    }
+
     pub async fn start(&self, cx: &AsyncApp) -> Result<()> {
-        self.initialize(self.new_client(cx)?).await
+        self.initialize(self.new_client(cx)?, cx).await
     }
 
     fn new_client(&self, cx: &AsyncApp) -> Result<Client> {
@@ -133,7 +189,7 @@ impl ContextServer {
         })
     }
 
-    async fn initialize(&self, client: Client) -> Result<()> {
+    async fn initialize(&self, client: Client, cx: &AsyncApp) -> Result<()> {
         log::debug!("starting context server {}", self.id);
         let protocol = crate::protocol::ModelContextProtocol::new(client);
         let client_info = types::Implementation {
@@ -142,7 +198,41 @@ impl ContextServer {
             version: env!("CARGO_PKG_VERSION").to_string(),
             description: None,
         };
-        let initialized_protocol = protocol.initialize(client_info).await?;
+        let roots = self.roots.clone();
+        protocol.on_request::<types::requests::ListRoots>(move |_, _| {
+            Task::ready(Ok(types::ListRootsResponse {
+                roots: roots.lock().clone(),
+                meta: None,
+            }))
+        });
+        let has_delegate = cx.has_global::<GlobalContextServerClientDelegate>();
+        if has_delegate {
+            let server_id = self.id();
+            protocol.on_request::<types::requests::CreateMessage>(move |request, cx| {
+                let server_id = server_id.clone();
+                cx.update(|cx| match client_delegate(cx) {
+                    Ok(delegate) => delegate.create_message(server_id, request, cx),
+                    Err(error) => Task::ready(Err(error)),
+                })
+            });
+            let server_id = self.id();
+            protocol.on_request::<types::requests::Elicit>(move |request, cx| {
+                let server_id = server_id.clone();
+                cx.update(|cx| match client_delegate(cx) {
+                    Ok(delegate) => delegate.elicit(server_id, request, cx),
+                    Err(error) => Task::ready(Err(error)),
+                })
+            });
+        }
+        let capabilities = types::ClientCapabilities {
+            experimental: None,
+            sampling: has_delegate.then(|| serde_json::json!({})),
+            roots: Some(types::RootsCapabilities {
+                list_changed: Some(true),
+            }),
+            elicitation: has_delegate.then(|| serde_json::json!({})),
+        };
+        let initialized_protocol = protocol.initialize(client_info, capabilities).await?;
 
         log::debug!(
             "context server {} initialized: {:?}",
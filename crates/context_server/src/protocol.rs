@@ -9,7 +9,7 @@ use std::time::Duration;
 
 use anyhow::Result;
 use futures::channel::oneshot;
-use gpui::AsyncApp;
+use gpui::{AppContext as _, AsyncApp, Task};
 use serde_json::Value;
 
 use crate::client::{Client, NotificationSubscription};
@@ -33,17 +33,33 @@ impl ModelContextProtocol {
         ]
     }
 
+    /// Answers requests of type `T` that the server sends to the client. Handlers should be
+    /// registered before initializing, so that the server can use them as soon as it's ready.
+    pub fn on_request<T: Request>(
+        &self,
+        mut f: impl 'static + Send + FnMut(T::Params, AsyncApp) -> Task<Result<T::Response>>,
+    ) {
+        self.inner.on_request(
+            T::METHOD,
+            Box::new(move |params, cx| {
+                let params = match serde_json::from_str::<T::Params>(params.get()) {
+                    Ok(params) => params,
+                    Err(error) => return Task::ready(Err(error.into())),
+                };
+                let response = f(params, cx.clone());
+                cx.background_spawn(async move { Ok(serde_json::to_value(response.await?)?) })
+            }),
+        );
+    }
+
     pub async fn initialize(
         self,
         client_info: types::Implementation,
+        capabilities: types::ClientCapabilities,
     ) -> Result<InitializedContextServerProtocol> {
         let params = types::InitializeParams {
             protocol_version: types::ProtocolVersion(types::LATEST_PROTOCOL_VERSION.to_string()),
-            capabilities: types::ClientCapabilities {
-                experimental: None,
-                sampling: None,
-                roots: None,
-            },
+            capabilities,
             meta: None,
             client_info,
         };
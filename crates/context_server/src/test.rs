@@ -41,6 +41,7 @@ pub struct FakeTransport {
         &'static str,
         Arc<dyn Send + Sync + Fn(serde_json::Value) -> BoxFuture<'static, serde_json::Value>>,
     >,
+    notification_handlers: HashMap<&'static str, Arc<dyn Send + Sync + Fn(serde_json::Value)>>,
     tx: futures::channel::mpsc::UnboundedSender<String>,
     rx: Arc<Mutex<futures::channel::mpsc::UnboundedReceiver<String>>>,
     executor: BackgroundExecutor,
@@ -51,6 +52,7 @@ impl FakeTransport {
         let (tx, rx) = futures::channel::mpsc::unbounded();
         Self {
             request_handlers: Default::default(),
+            notification_handlers: Default::default(),
             tx,
             rx: Arc::new(Mutex::new(rx)),
             executor,
@@ -80,6 +82,23 @@ impl FakeTransport {
         );
         self
     }
+
+    pub fn on_notification<T: crate::types::Notification>(
+        mut self,
+        handler: impl 'static + Send + Sync + Fn(T::Params),
+    ) -> Self {
+        self.notification_handlers.insert(
+            T::METHOD,
+            Arc::new(move |value| {
+                let params = value
+                    .get("params")
+                    .cloned()
+                    .unwrap_or(serde_json::Value::Null);
+                handler(serde_json::from_value(params).expect("Invalid parameters received"));
+            }),
+        );
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -90,7 +109,11 @@ impl Transport for FakeTransport {
 
             if let Some(method) = msg.get("method") {
                 let method = method.as_str().expect("Invalid method received");
-                if let Some(handler) = self.request_handlers.get(method) {
+                if msg.get("id").is_none() {
+                    if let Some(handler) = self.notification_handlers.get(method) {
+                        handler(msg);
+                    }
+                } else if let Some(handler) = self.request_handlers.get(method) {
                     let payload = handler(msg).await;
                     let response = serde_json::json!({
                         "jsonrpc": "2.0",
@@ -1,7 +1,8 @@
 use editor::{Editor, EditorEvent};
 use feature_flags::{FeatureFlag, FeatureFlagAppExt as _, PresenceFlag, register_feature_flag};
 use gpui::{
-    AppContext, Entity, EventEmitter, FocusHandle, Focusable, ListAlignment, Task, actions,
+    AppContext, Entity, EventEmitter, FocusHandle, Focusable, ListAlignment, Subscription, Task,
+    actions,
 };
 use std::{
     collections::HashMap,
@@ -15,15 +16,49 @@ use ui::{
 };
 use workspace::{Item, SplitDirection, Workspace};
 
-use crate::{parser::EditorState, settings::CsvPreviewSettings, types::TableLikeContent};
+use crate::{
+    editing::CellEditor,
+    parser::EditorState,
+    settings::CsvPreviewSettings,
+    types::{AnyColumn, DisplayCellId, TableLikeContent},
+};
 
+mod editing;
 mod parser;
 mod renderer;
 mod settings;
 mod table_data_engine;
 mod types;
 
-actions!(csv, [OpenPreview, OpenPreviewToTheSide]);
+actions!(
+    csv,
+    [
+        OpenPreview,
+        OpenPreviewToTheSide,
+        /// Edits the value of the selected cell in place.
+        EditCell,
+        /// Inserts an empty row above the selected cell.
+        InsertRowAbove,
+        /// Inserts an empty row below the selected cell.
+        InsertRowBelow,
+        /// Deletes the row of the selected cell.
+        DeleteRow,
+        /// Moves the row of the selected cell up by one.
+        MoveRowUp,
+        /// Moves the row of the selected cell down by one.
+        MoveRowDown,
+        /// Inserts an empty column left of the selected cell.
+        InsertColumnLeft,
+        /// Inserts an empty column right of the selected cell.
+        InsertColumnRight,
+        /// Deletes the column of the selected cell.
+        DeleteColumn,
+        /// Moves the column of the selected cell left by one.
+        MoveColumnLeft,
+        /// Moves the column of the selected cell right by one.
+        MoveColumnRight,
+    ]
+);
 
 pub struct TabularDataPreviewFeatureFlag;
 
@@ -47,8 +82,22 @@ pub struct CsvPreviewView {
     pub(crate) list_state: gpui::ListState,
     /// Time when the last parsing operation ended, used for smart debouncing
     pub(crate) last_parse_end_time: Option<std::time::Instant>,
+    /// Cell targeted by row/column operations and keyboard editing
+    pub(crate) selected_cell: Option<DisplayCellId>,
+    /// In-place editor of the cell currently being edited, if any
+    pub(crate) cell_editor: Option<CellEditor>,
+    /// Input of the filter expression bar
+    pub(crate) filter_editor: Entity<Editor>,
+    _filter_editor_subscription: Subscription,
+    /// Background sorting and filtering of the rows
+    mapping_task: Option<Task<()>>,
+    /// Background summary of the column of the selected cell
+    column_stats_task: Option<(AnyColumn, Task<()>)>,
 }
 
+/// How long to wait for further typing before re-filtering the rows.
+const FILTER_DEBOUNCE: Duration = Duration::from_millis(150);
+
 pub fn init(cx: &mut App) {
     cx.observe_new(|workspace: &mut Workspace, _, _| {
         CsvPreviewView::register(workspace);
@@ -90,7 +139,7 @@ impl CsvPreviewView {
                         .and_then(|item| item.act_as::<Editor>(cx))
                         .filter(|editor| Self::is_csv_file(editor, cx))
                     {
-                        let csv_preview = Self::new(&editor, cx);
+                        let csv_preview = Self::new(&editor, window, cx);
                         workspace.active_pane().update(cx, |pane, cx| {
                             let existing = pane
                                 .items_of_type::<CsvPreviewView>()
@@ -111,7 +160,7 @@ impl CsvPreviewView {
                             .and_then(|item| item.act_as::<Editor>(cx))
                             .filter(|editor| Self::is_csv_file(editor, cx))
                         {
-                            let csv_preview = Self::new(&editor, cx);
+                            let csv_preview = Self::new(&editor, window, cx);
                             let pane = workspace
                                 .find_pane_in_direction(SplitDirection::Right, cx)
                                 .unwrap_or_else(|| {
@@ -148,7 +197,11 @@ impl CsvPreviewView {
         });
     }
 
-    fn new(editor: &Entity<Editor>, cx: &mut Context<Workspace>) -> Entity<Self> {
+    fn new(
+        editor: &Entity<Editor>,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
         let contents = TableLikeContent::default();
         let table_interaction_state = cx.new(|cx| {
             TableInteractionState::new(cx).with_custom_scrollbar(ui::Scrollbars::for_settings::<
@@ -169,6 +222,25 @@ impl CsvPreviewView {
                 },
             );
 
+            let filter_editor = cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text(
+                    "Filter rows, e.g. price > 10 and country = DE",
+                    window,
+                    cx,
+                );
+                editor
+            });
+            let filter_editor_subscription = cx.subscribe(
+                &filter_editor,
+                |this: &mut CsvPreviewView, filter_editor, event: &EditorEvent, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        let query = filter_editor.read(cx).text(cx);
+                        this.apply_filter(query, cx);
+                    }
+                },
+            );
+
             let mut view = CsvPreviewView {
                 focus_handle: cx.focus_handle(),
                 active_editor_state: EditorState {
@@ -184,6 +256,12 @@ impl CsvPreviewView {
                 settings: CsvPreviewSettings::default(),
                 last_parse_end_time: None,
                 engine: TableDataEngine::default(),
+                selected_cell: None,
+                cell_editor: None,
+                filter_editor,
+                _filter_editor_subscription: filter_editor_subscription,
+                mapping_task: None,
+                column_stats_task: None,
             };
 
             view.parse_csv_from_active_editor(false, cx);
@@ -194,18 +272,67 @@ impl CsvPreviewView {
     pub(crate) fn editor_state(&self) -> &EditorState {
         &self.active_editor_state
     }
-    pub(crate) fn apply_sort(&mut self) {
-        self.performance_metrics.record("Sort", || {
-            self.engine.apply_sort();
-        });
+    /// Update ordered indices when ordering or content changes
+    pub(crate) fn apply_filter_sort(&mut self, cx: &mut Context<Self>) {
+        self.update_mapping(None, cx);
     }
 
-    /// Update ordered indices when ordering or content changes
-    pub(crate) fn apply_filter_sort(&mut self) {
-        self.performance_metrics.record("Filter&sort", || {
-            self.engine.calculate_d2d_mapping();
+    /// Update displayed rows when the filter expression changes
+    pub(crate) fn apply_filter(&mut self, query: String, cx: &mut Context<Self>) {
+        self.engine.set_filter_query(query);
+        // Display rows shift when filtering, so the selection would point at another cell
+        self.selected_cell = None;
+        self.cell_editor = None;
+        self.update_mapping(Some(FILTER_DEBOUNCE), cx);
+        cx.notify();
+    }
+
+    /// Sorts and filters the rows in the background, replacing any computation still in flight.
+    fn update_mapping(&mut self, debounce: Option<Duration>, cx: &mut Context<Self>) {
+        let job = self.engine.mapping_job();
+        self.column_stats_task = None;
+        self.mapping_task = Some(cx.spawn(async move |this, cx| {
+            if let Some(debounce) = debounce {
+                cx.background_executor().timer(debounce).await;
+            }
+            let start = Instant::now();
+            let update = cx.background_spawn(async move { job() }).await;
+            let duration = start.elapsed();
+            this.update(cx, |this, cx| {
+                this.performance_metrics
+                    .timings
+                    .insert("Filter&sort", (duration, Instant::now()));
+                this.engine.apply_mapping(update);
+                this.reset_list_state();
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    /// Summarizes `col` in the background, unless it's already summarized or being summarized.
+    pub(crate) fn summarize_column(&mut self, col: AnyColumn, cx: &mut Context<Self>) {
+        if self.engine.column_stats(col).is_some()
+            || self
+                .column_stats_task
+                .as_ref()
+                .is_some_and(|(task_col, _)| *task_col == col)
+        {
+            return;
+        }
+        let job = self.engine.column_stats_job(col);
+        let task = cx.spawn(async move |this, cx| {
+            let stats = cx.background_spawn(async move { job() }).await;
+            this.update(cx, |this, cx| {
+                this.engine.set_column_stats(col, stats);
+                cx.notify();
+            })
+            .ok();
         });
+        self.column_stats_task = Some((col, task));
+    }
 
+    fn reset_list_state(&mut self) {
         // Update list state with filtered row count
         let visible_rows = self.engine.d2d_mapping().visible_row_count();
         self.list_state =
@@ -223,19 +350,21 @@ impl CsvPreviewView {
     }
 
     fn is_csv_file(editor: &Entity<Editor>, cx: &App) -> bool {
-        editor
-            .read(cx)
-            .buffer()
-            .read(cx)
-            .as_singleton()
-            .and_then(|buffer| {
-                buffer
-                    .read(cx)
-                    .file()
-                    .and_then(|file| file.path().extension())
-                    .map(|ext| ext.eq_ignore_ascii_case("csv"))
-            })
-            .unwrap_or(false)
+        Self::tabular_delimiter(editor, cx).is_some()
+    }
+
+    /// Field delimiter for the editor's file, derived from its extension.
+    /// `None` if the file isn't a supported tabular format.
+    pub(crate) fn tabular_delimiter(editor: &Entity<Editor>, cx: &App) -> Option<char> {
+        let buffer = editor.read(cx).buffer().read(cx).as_singleton()?;
+        let extension = buffer.read(cx).file()?.path().extension()?;
+        if extension.eq_ignore_ascii_case("csv") {
+            Some(',')
+        } else if extension.eq_ignore_ascii_case("tsv") {
+            Some('\t')
+        } else {
+            None
+        }
     }
 }
 
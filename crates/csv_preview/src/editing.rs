@@ -0,0 +1,357 @@
+//! Editing the source CSV through the table: in-cell editing and row/column operations.
+//!
+//! Every operation is written back into the previewed buffer as a single transaction, so it can
+//! be undone from either the preview or the source editor. The table itself is never mutated
+//! directly; it's re-parsed from the buffer after each edit.
+
+use editor::{
+    Editor, EditorEvent,
+    actions::{Redo, SelectAll, Undo},
+};
+use gpui::{Entity, Focusable, Subscription};
+use ui::prelude::*;
+
+use crate::{
+    CsvPreviewView, DeleteColumn, DeleteRow, EditCell, InsertColumnLeft, InsertColumnRight,
+    InsertRowAbove, InsertRowBelow, MoveColumnLeft, MoveColumnRight, MoveRowDown, MoveRowUp,
+    table_data_engine::editing::{SourceRow, TableEdit, buffer_edits},
+    types::{AnyColumn, DataRow, DisplayCellId},
+};
+
+/// Cell targeted by the in-place editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EditTarget {
+    /// Header cell, i.e. the column name
+    Header(AnyColumn),
+    Cell(DisplayCellId),
+}
+
+/// In-place editor for the value of a single cell.
+pub(crate) struct CellEditor {
+    pub target: EditTarget,
+    pub editor: Entity<Editor>,
+    _subscription: Subscription,
+}
+
+impl CsvPreviewView {
+    /// Write `edit` back into the source buffer as a single undoable transaction.
+    pub(crate) fn apply_table_edit(&mut self, edit: TableEdit, cx: &mut Context<Self>) {
+        let Some(buffer) = self
+            .editor_state()
+            .editor
+            .read(cx)
+            .buffer()
+            .read(cx)
+            .as_singleton()
+        else {
+            return;
+        };
+
+        let snapshot = buffer.read(cx).text_snapshot();
+        let edits = buffer_edits(&self.engine.contents, &edit, &snapshot);
+        if edits.is_empty() {
+            return;
+        }
+
+        buffer.update(cx, |buffer, cx| {
+            buffer.start_transaction();
+            buffer.edit(edits, None, cx);
+            buffer.end_transaction(cx);
+        });
+        self.parse_csv_from_active_editor(false, cx);
+    }
+
+    pub(crate) fn select_cell(&mut self, cell: DisplayCellId, cx: &mut Context<Self>) {
+        if self.selected_cell != Some(cell) {
+            self.selected_cell = Some(cell);
+            cx.notify();
+        }
+    }
+
+    /// Data row and column of the selected cell.
+    fn selected_data_cell(&self) -> Option<(DataRow, AnyColumn)> {
+        let cell = self.selected_cell?;
+        let data_row = self.engine.d2d_mapping().get_data_row(cell.row)?;
+        Some((data_row, cell.col))
+    }
+
+    /// Source row and current value of the edited cell.
+    fn resolve_edit_target(&self, target: EditTarget) -> Option<(SourceRow, AnyColumn, String)> {
+        let (row, col) = match target {
+            EditTarget::Header(col) => (SourceRow::Header, col),
+            EditTarget::Cell(cell) => (
+                SourceRow::Data(self.engine.d2d_mapping().get_data_row(cell.row)?),
+                cell.col,
+            ),
+        };
+        let cells = match row {
+            SourceRow::Header => Some(&self.engine.contents.headers),
+            SourceRow::Data(data_row) => self.engine.contents.get_row(data_row),
+        };
+        let value = cells
+            .and_then(|cells| cells.get(col))
+            .and_then(|cell| cell.display_value())
+            .map(|value| value.to_string())
+            .unwrap_or_default();
+        Some((row, col, value))
+    }
+
+    pub(crate) fn start_editing(
+        &mut self,
+        target: EditTarget,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((_, _, value)) = self.resolve_edit_target(target) else {
+            return;
+        };
+
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::auto_height(1, 8, window, cx);
+            editor.set_text(value, window, cx);
+            editor.select_all(&SelectAll, window, cx);
+            editor
+        });
+        let subscription = cx.subscribe(&editor, |this, _, event: &EditorEvent, cx| {
+            if let EditorEvent::Blurred = event {
+                this.commit_cell_edit(cx);
+            }
+        });
+        window.focus(&editor.focus_handle(cx), cx);
+
+        if let EditTarget::Cell(cell) = target {
+            self.selected_cell = Some(cell);
+        }
+        self.cell_editor = Some(CellEditor {
+            target,
+            editor,
+            _subscription: subscription,
+        });
+        cx.notify();
+    }
+
+    /// Write the value of the cell being edited back into the buffer.
+    fn commit_cell_edit(&mut self, cx: &mut Context<Self>) {
+        let Some(cell_editor) = self.cell_editor.take() else {
+            return;
+        };
+        if let Some((row, col, current_value)) = self.resolve_edit_target(cell_editor.target) {
+            let value = cell_editor.editor.read(cx).text(cx);
+            if value != current_value {
+                self.apply_table_edit(TableEdit::SetCell { row, col, value }, cx);
+            }
+        }
+        cx.notify();
+    }
+
+    pub(crate) fn confirm(
+        &mut self,
+        _: &menu::Confirm,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.cell_editor.is_some() {
+            self.commit_cell_edit(cx);
+            window.focus(&self.focus_handle, cx);
+        } else if self.filter_editor.focus_handle(cx).is_focused(window) {
+            window.focus(&self.focus_handle, cx);
+        } else if let Some(cell) = self.selected_cell {
+            self.start_editing(EditTarget::Cell(cell), window, cx);
+        }
+    }
+
+    pub(crate) fn cancel(&mut self, _: &menu::Cancel, window: &mut Window, cx: &mut Context<Self>) {
+        if self.cell_editor.take().is_some() {
+            window.focus(&self.focus_handle, cx);
+            cx.notify();
+        } else if self.filter_editor.focus_handle(cx).is_focused(window) {
+            window.focus(&self.focus_handle, cx);
+        } else if self.selected_cell.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    pub(crate) fn edit_cell(&mut self, _: &EditCell, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(cell) = self.selected_cell {
+            self.start_editing(EditTarget::Cell(cell), window, cx);
+        }
+    }
+
+    pub(crate) fn undo(&mut self, _: &Undo, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor_state()
+            .editor
+            .clone()
+            .update(cx, |editor, cx| editor.undo(&Undo, window, cx));
+    }
+
+    pub(crate) fn redo(&mut self, _: &Redo, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor_state()
+            .editor
+            .clone()
+            .update(cx, |editor, cx| editor.redo(&Redo, window, cx));
+    }
+
+    pub(crate) fn insert_row_above(
+        &mut self,
+        _: &InsertRowAbove,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some((row, _)) = self.selected_data_cell() {
+            self.apply_table_edit(TableEdit::InsertRow { at: row }, cx);
+        }
+    }
+
+    pub(crate) fn insert_row_below(
+        &mut self,
+        _: &InsertRowBelow,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some((row, _)) = self.selected_data_cell() {
+            self.apply_table_edit(
+                TableEdit::InsertRow {
+                    at: DataRow(*row + 1),
+                },
+                cx,
+            );
+        }
+    }
+
+    pub(crate) fn delete_row(&mut self, _: &DeleteRow, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some((row, _)) = self.selected_data_cell() {
+            self.apply_table_edit(TableEdit::DeleteRow { row }, cx);
+        }
+    }
+
+    /// Rows can only be reordered while the table shows all of them in source order,
+    /// otherwise "up" and "down" would be ambiguous.
+    pub(crate) fn can_move_rows(&self) -> bool {
+        self.engine.applied_sorting.is_none() && !self.engine.is_filtered()
+    }
+
+    pub(crate) fn move_row_up(&mut self, _: &MoveRowUp, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.can_move_rows() {
+            return;
+        }
+        if let Some((row, col)) = self.selected_data_cell()
+            && let Some(target) = row.checked_sub(1).map(DataRow)
+        {
+            self.apply_table_edit(
+                TableEdit::MoveRow {
+                    from: row,
+                    to: target,
+                },
+                cx,
+            );
+            self.selected_cell = Some(DisplayCellId::new(*target, col));
+        }
+    }
+
+    pub(crate) fn move_row_down(
+        &mut self,
+        _: &MoveRowDown,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.can_move_rows() {
+            return;
+        }
+        if let Some((row, col)) = self.selected_data_cell()
+            && *row + 1 < self.engine.contents.rows.len()
+        {
+            let target = DataRow(*row + 1);
+            self.apply_table_edit(
+                TableEdit::MoveRow {
+                    from: row,
+                    to: target,
+                },
+                cx,
+            );
+            self.selected_cell = Some(DisplayCellId::new(*target, col));
+        }
+    }
+
+    pub(crate) fn insert_column_left(
+        &mut self,
+        _: &InsertColumnLeft,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some((_, col)) = self.selected_data_cell() {
+            self.apply_table_edit(TableEdit::InsertColumn { at: col }, cx);
+        }
+    }
+
+    pub(crate) fn insert_column_right(
+        &mut self,
+        _: &InsertColumnRight,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some((_, col)) = self.selected_data_cell() {
+            self.apply_table_edit(
+                TableEdit::InsertColumn {
+                    at: AnyColumn(*col + 1),
+                },
+                cx,
+            );
+        }
+    }
+
+    pub(crate) fn delete_column(
+        &mut self,
+        _: &DeleteColumn,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some((_, col)) = self.selected_data_cell() {
+            self.apply_table_edit(TableEdit::DeleteColumn { col }, cx);
+        }
+    }
+
+    pub(crate) fn move_column_left(
+        &mut self,
+        _: &MoveColumnLeft,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some((_, col)) = self.selected_data_cell()
+            && let Some(target) = col.checked_sub(1).map(AnyColumn)
+        {
+            self.apply_table_edit(
+                TableEdit::MoveColumn {
+                    from: col,
+                    to: target,
+                },
+                cx,
+            );
+            self.selected_cell = self
+                .selected_cell
+                .map(|cell| DisplayCellId::new(cell.row, target));
+        }
+    }
+
+    pub(crate) fn move_column_right(
+        &mut self,
+        _: &MoveColumnRight,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some((_, col)) = self.selected_data_cell()
+            && *col + 1 < self.engine.contents.number_of_cols
+        {
+            let target = AnyColumn(*col + 1);
+            self.apply_table_edit(
+                TableEdit::MoveColumn {
+                    from: col,
+                    to: target,
+                },
+                cx,
+            );
+            self.selected_cell = self
+                .selected_cell
+                .map(|cell| DisplayCellId::new(cell.row, target));
+        }
+    }
+}
@@ -62,6 +62,9 @@ impl CsvPreviewView {
                     .as_singleton()
                     .map(|b| b.read(cx).text_snapshot())
             })?;
+            let delimiter = view.update(cx, |_, cx| {
+                CsvPreviewView::tabular_delimiter(&editor, cx).unwrap_or(',')
+            })?;
 
             let Some(buffer_snapshot) = buffer_snapshot else {
                 return Ok(());
@@ -69,7 +72,7 @@ impl CsvPreviewView {
 
             let instant = Instant::now();
             let parsed_csv = cx
-                .background_spawn(async move { from_buffer(&buffer_snapshot) })
+                .background_spawn(async move { from_buffer(&buffer_snapshot, delimiter) })
                 .await;
             let parse_duration = instant.elapsed();
             let parse_end_time: Instant = Instant::now();
@@ -80,33 +83,37 @@ impl CsvPreviewView {
                     .insert("Parsing", (parse_duration, Instant::now()));
 
                 log::debug!("Parsed {} rows", parsed_csv.rows.len());
-                view.engine.contents = parsed_csv;
+                view.engine.set_contents(parsed_csv);
                 view.sync_column_widths(cx);
                 view.last_parse_end_time = Some(parse_end_time);
 
-                view.apply_filter_sort();
+                view.apply_filter_sort(cx);
                 cx.notify();
             })
         })
     }
 }
 
-pub fn from_buffer(buffer_snapshot: &BufferSnapshot) -> TableLikeContent {
+pub fn from_buffer(buffer_snapshot: &BufferSnapshot, delimiter: char) -> TableLikeContent {
     let text = buffer_snapshot.text();
+    let empty = TableLikeContent {
+        delimiter,
+        ..TableLikeContent::default()
+    };
 
     if text.trim().is_empty() {
-        return TableLikeContent::default();
+        return empty;
     }
 
-    let (parsed_cells_with_positions, line_numbers) = parse_csv_with_positions(&text);
+    let (parsed_cells_with_positions, line_numbers) = parse_csv_with_positions(&text, delimiter);
     if parsed_cells_with_positions.is_empty() {
-        return TableLikeContent::default();
+        return empty;
     }
     let raw_headers = parsed_cells_with_positions[0].clone();
 
     // Calculating the longest row, as CSV might have less headers than max row width
     let Some(max_number_of_cols) = parsed_cells_with_positions.iter().map(|r| r.len()).max() else {
-        return TableLikeContent::default();
+        return empty;
     };
 
     // Convert to TableCell objects with buffer positions
@@ -125,12 +132,14 @@ pub fn from_buffer(buffer_snapshot: &BufferSnapshot) -> TableLikeContent {
         rows,
         line_numbers: row_line_numbers,
         number_of_cols: max_number_of_cols,
+        delimiter,
     }
 }
 
 /// Parse CSV and track byte positions for each cell
 fn parse_csv_with_positions(
     text: &str,
+    delimiter: char,
 ) -> (
     Vec<Vec<(SharedString, std::ops::Range<usize>)>>,
     Vec<LineNumber>,
@@ -170,12 +179,9 @@ fn parse_csv_with_positions(
                     }
                 }
             }
-            ',' if !in_quotes => {
+            ch if ch == delimiter && !in_quotes => {
                 // Field separator
                 let field_end_offset = current_offset;
-                if current_field.is_empty() && !in_quotes {
-                    field_start_offset = current_offset;
-                }
                 current_row.push((
                     current_field.clone().into(),
                     field_start_offset..field_end_offset,
@@ -188,19 +194,14 @@ fn parse_csv_with_positions(
                 if !in_quotes {
                     // Row separator (only when not inside quotes)
                     let field_end_offset = current_offset;
-                    if current_field.is_empty() && current_row.is_empty() {
-                        field_start_offset = 0;
-                    }
                     current_row.push((
                         current_field.clone().into(),
                         field_start_offset..field_end_offset,
                     ));
                     current_field.clear();
 
-                    // Only add non-empty rows
-                    if !current_row.is_empty()
-                        && !current_row.iter().all(|(field, _)| field.trim().is_empty())
-                    {
+                    // Only add non-blank rows
+                    if !is_blank_row(&current_row, text) {
                         rows.push(current_row);
                         // Add line number info for this row
                         let line_info = if row_start_line == current_line - 1 {
@@ -235,10 +236,8 @@ fn parse_csv_with_positions(
                         ));
                         current_field.clear();
 
-                        // Only add non-empty rows
-                        if !current_row.is_empty()
-                            && !current_row.iter().all(|(field, _)| field.trim().is_empty())
-                        {
+                        // Only add non-blank rows
+                        if !is_blank_row(&current_row, text) {
                             rows.push(current_row);
                             // Add line number info for this row
                             let line_info = if row_start_line == current_line - 1 {
@@ -268,15 +267,16 @@ fn parse_csv_with_positions(
         current_offset += char_byte_len;
     }
 
-    // Add the last field and row if not empty
-    if !current_field.is_empty() || !current_row.is_empty() {
+    // Add the last field and row if not empty. A field was started if its start isn't at the
+    // end of the text, which also covers a quoted empty field (`""`)
+    if field_start_offset < current_offset || !current_row.is_empty() {
         let field_end_offset = current_offset;
         current_row.push((
             current_field.clone().into(),
             field_start_offset..field_end_offset,
         ));
     }
-    if !current_row.is_empty() && !current_row.iter().all(|(field, _)| field.trim().is_empty()) {
+    if !is_blank_row(&current_row, text) {
         rows.push(current_row);
         // Add line number info for the last row
         let line_info = if row_start_line == current_line {
@@ -290,6 +290,15 @@ fn parse_csv_with_positions(
     (rows, line_numbers)
 }
 
+/// Whether a parsed row is blank and therefore not part of the table: every field is empty or
+/// whitespace, as on an empty line or a line of separators like `,,`.
+///
+/// A quoted empty field (`""`) still counts as content, which is how an empty record is written.
+fn is_blank_row(row: &[(SharedString, std::ops::Range<usize>)], text: &str) -> bool {
+    row.iter()
+        .all(|(field, range)| field.trim().is_empty() && !text[range.clone()].starts_with('"'))
+}
+
 fn create_table_row(
     buffer_snapshot: &BufferSnapshot,
     max_number_of_cols: usize,
@@ -418,10 +427,28 @@ Jane,"Simple name""#;
         assert!(parsed.rows.is_empty());
     }
 
+    #[test]
+    fn test_blank_rows_are_skipped() {
+        let csv_data = "a,b\n1,2\n\n,\n  ,\n\"\",\n3,4\n";
+        let (parsed_cells, _) = parse_csv_with_positions(csv_data, ',');
+        let fields = parsed_cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|(field, _)| field.to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            vec![vec!["a", "b"], vec!["1", "2"], vec!["", ""], vec!["3", "4"]]
+        );
+    }
+
     #[test]
     fn test_csv_parsing_quote_offset_handling() {
         let csv_data = r#"first,"se,cond",third"#;
-        let (parsed_cells, _) = parse_csv_with_positions(csv_data);
+        let (parsed_cells, _) = parse_csv_with_positions(csv_data, ',');
 
         assert_eq!(parsed_cells.len(), 1); // One row
         assert_eq!(parsed_cells[0].len(), 3); // Three cells
@@ -447,7 +474,7 @@ Jane,"Simple name""#;
         let csv_data = r#"id,"name with spaces","description, with commas",status
 1,"John Doe","A person with ""quotes"" and, commas",active
 2,"Jane Smith","Simple description",inactive"#;
-        let (parsed_cells, _) = parse_csv_with_positions(csv_data);
+        let (parsed_cells, _) = parse_csv_with_positions(csv_data, ',');
 
         assert_eq!(parsed_cells.len(), 3); // header + 2 rows
 
@@ -505,6 +532,6 @@ impl TableLikeContent {
         let buffer_id = BufferId::new(1).unwrap();
         let buffer = Buffer::new(ReplicaId::LOCAL, buffer_id, text);
         let snapshot = buffer.snapshot();
-        from_buffer(snapshot)
+        from_buffer(snapshot, ',')
     }
 }
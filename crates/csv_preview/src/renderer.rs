@@ -1,8 +1,10 @@
+mod filter_bar;
 #[cfg(feature = "dev-tools")]
 mod performance_metrics_overlay;
 mod preview_view;
 mod render_table;
 mod row_identifiers;
 mod settings;
+mod summary_footer;
 mod table_cell;
 mod table_header;
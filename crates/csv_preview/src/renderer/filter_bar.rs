@@ -0,0 +1,50 @@
+use ui::{Tooltip, prelude::*};
+
+use crate::CsvPreviewView;
+
+impl CsvPreviewView {
+    /// Render the input for the row filter expression, with the parse error next to it
+    pub(crate) fn render_filter_bar(&self, cx: &mut Context<Self>) -> AnyElement {
+        let error = self.engine.filter_error();
+        let border_color = if error.is_some() {
+            cx.theme().status().error_border
+        } else {
+            cx.theme().colors().border
+        };
+
+        h_flex()
+            .id("csv-filter-bar")
+            .flex_1()
+            .min_w_64()
+            .gap_2()
+            .tooltip(Tooltip::text(
+                "Filter by text in any cell, or by comparing columns: \
+                 price >= 10 and (country = DE or `unit price` < 5).\n\
+                 Operators: = != < <= > >= ~ (contains) !~ (doesn't contain)",
+            ))
+            .child(
+                Icon::new(IconName::Filter)
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .px_2()
+                    .py_0p5()
+                    .rounded_sm()
+                    .border_1()
+                    .border_color(border_color)
+                    .bg(cx.theme().colors().editor_background)
+                    .child(self.filter_editor.clone()),
+            )
+            .when_some(error, |this, error| {
+                this.child(
+                    Label::new(error.to_string())
+                        .size(LabelSize::Small)
+                        .color(Color::Error),
+                )
+            })
+            .into_any_element()
+    }
+}
@@ -6,14 +6,32 @@ use crate::CsvPreviewView;
 
 impl Render for CsvPreviewView {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let theme = cx.theme();
-
         let render_prep_start = Instant::now();
+        let summary_footer =
+            (self.engine.contents.number_of_cols > 0).then(|| self.render_summary_footer(cx));
+
+        let theme = cx.theme();
         let table_with_settings = v_flex()
             .size_full()
             .p_4()
             .bg(theme.colors().editor_background)
+            .key_context("CsvPreview")
             .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::confirm))
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::edit_cell))
+            .on_action(cx.listener(Self::undo))
+            .on_action(cx.listener(Self::redo))
+            .on_action(cx.listener(Self::insert_row_above))
+            .on_action(cx.listener(Self::insert_row_below))
+            .on_action(cx.listener(Self::delete_row))
+            .on_action(cx.listener(Self::move_row_up))
+            .on_action(cx.listener(Self::move_row_down))
+            .on_action(cx.listener(Self::insert_column_left))
+            .on_action(cx.listener(Self::insert_column_right))
+            .on_action(cx.listener(Self::delete_column))
+            .on_action(cx.listener(Self::move_column_left))
+            .on_action(cx.listener(Self::move_column_right))
             .child(self.render_settings_panel(window, cx))
             .child({
                 if self.engine.contents.number_of_cols == 0 {
@@ -30,7 +48,8 @@ impl Render for CsvPreviewView {
                 } else {
                     self.create_table(&self.column_widths.widths, cx)
                 }
-            });
+            })
+            .children(summary_footer);
 
         let render_prep_duration = render_prep_start.elapsed();
         self.performance_metrics.timings.insert(
@@ -5,6 +5,7 @@ use ui::{ColumnWidthConfig, ResizableColumnsState, Table, UncheckedTableRow, div
 
 use crate::{
     CsvPreviewView,
+    editing::EditTarget,
     settings::RowRenderMechanism,
     types::{AnyColumn, DisplayCellId, DisplayRow},
 };
@@ -17,7 +18,11 @@ impl CsvPreviewView {
         current_widths: &Entity<ResizableColumnsState>,
         cx: &mut Context<Self>,
     ) -> AnyElement {
-        self.create_table_inner(self.engine.contents.rows.len(), current_widths, cx)
+        self.create_table_inner(
+            self.engine.d2d_mapping().visible_row_count(),
+            current_widths,
+            cx,
+        )
     }
 
     fn create_table_inner(
@@ -129,14 +134,16 @@ impl CsvPreviewView {
 
             let display_cell_id = DisplayCellId::new(display_row, col);
 
-            let cell = div().size_full().whitespace_nowrap().text_ellipsis().child(
-                CsvPreviewView::create_selectable_cell(
-                    display_cell_id,
-                    cell_content,
-                    this.settings.vertical_alignment,
-                    cx,
-                ),
-            );
+            let cell = this
+                .render_cell_editor(EditTarget::Cell(display_cell_id))
+                .unwrap_or_else(|| {
+                    div()
+                        .size_full()
+                        .whitespace_nowrap()
+                        .text_ellipsis()
+                        .child(this.create_selectable_cell(display_cell_id, cell_content, cx))
+                        .into_any_element()
+                });
 
             elements.push(
                 div()
@@ -68,7 +68,8 @@ impl CsvPreviewView {
                             "Choose vertical text alignment within cells",
                         )),
                     ),
-            );
+            )
+            .child(self.render_filter_bar(cx));
 
         #[cfg(feature = "dev-tools")]
         let panel = panel.child(
@@ -0,0 +1,77 @@
+use ui::prelude::*;
+
+use crate::{CsvPreviewView, table_data_engine::typed_values::format_number};
+
+impl CsvPreviewView {
+    /// Render the footer with the number of shown rows and the summary of the selected column
+    pub(crate) fn render_summary_footer(&mut self, cx: &mut Context<Self>) -> AnyElement {
+        let total_rows = self.engine.contents.rows.len();
+        let visible_rows = self.engine.d2d_mapping().visible_row_count();
+        let rows_label = if visible_rows == total_rows {
+            format!("{total_rows} rows")
+        } else {
+            format!("{visible_rows} of {total_rows} rows")
+        };
+
+        let column_summary = match self.selected_cell {
+            Some(cell) => {
+                let column_name = self
+                    .engine
+                    .contents
+                    .headers
+                    .get(cell.col)
+                    .and_then(|header| header.display_value().cloned())
+                    .unwrap_or_else(|| format!("Col {}", *cell.col + 1).into());
+                self.summarize_column(cell.col, cx);
+                let entries = match self.engine.column_stats(cell.col) {
+                    Some(stats) => {
+                        let mut entries = vec![
+                            ("Type", stats.column_type.label().to_string()),
+                            ("Count", stats.count.to_string()),
+                            ("Distinct", stats.distinct.to_string()),
+                            ("Nulls", stats.nulls.to_string()),
+                        ];
+                        entries.extend(stats.min.clone().map(|min| ("Min", min)));
+                        entries.extend(stats.max.clone().map(|max| ("Max", max)));
+                        entries.extend(stats.mean.map(|mean| ("Mean", format_number(mean))));
+                        entries
+                    }
+                    None => vec![("Summarizing…", String::new())],
+                };
+
+                h_flex()
+                    .gap_3()
+                    .min_w_0()
+                    .overflow_x_hidden()
+                    .child(Label::new(column_name).size(LabelSize::Small))
+                    .children(entries.into_iter().map(|(name, value)| {
+                        h_flex()
+                            .gap_1()
+                            .flex_shrink_0()
+                            .child(Label::new(name).size(LabelSize::Small).color(Color::Muted))
+                            .child(Label::new(value).size(LabelSize::Small).truncate())
+                    }))
+                    .into_any_element()
+            }
+            None => Label::new("Select a cell to summarize its column")
+                .size(LabelSize::Small)
+                .color(Color::Muted)
+                .into_any_element(),
+        };
+
+        h_flex()
+            .gap_4()
+            .px_2()
+            .py_1()
+            .border_t_1()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().surface_background)
+            .child(
+                Label::new(rows_label)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(column_summary)
+            .into_any_element()
+    }
+}
@@ -1,29 +1,106 @@
 //! Table Cell Rendering
 
-use gpui::{AnyElement, ElementId};
-use ui::{SharedString, Tooltip, div, prelude::*};
+use gpui::{AnyElement, ClickEvent, ElementId, FocusHandle, MouseButton};
+use ui::{ContextMenu, SharedString, Tooltip, div, prelude::*, right_click_menu};
 
-use crate::{CsvPreviewView, settings::VerticalAlignment, types::DisplayCellId};
+use crate::{
+    CsvPreviewView, DeleteColumn, DeleteRow, EditCell, InsertColumnLeft, InsertColumnRight,
+    InsertRowAbove, InsertRowBelow, MoveColumnLeft, MoveColumnRight, MoveRowDown, MoveRowUp,
+    editing::EditTarget, settings::VerticalAlignment, types::DisplayCellId,
+};
 
 impl CsvPreviewView {
     /// Create selectable table cell with mouse event handlers.
+    ///
+    /// Clicking selects the cell, double-clicking edits it in place and right-clicking opens a
+    /// menu with row and column operations.
     pub fn create_selectable_cell(
+        &self,
         display_cell_id: DisplayCellId,
         cell_content: SharedString,
-        vertical_alignment: VerticalAlignment,
         cx: &Context<CsvPreviewView>,
     ) -> AnyElement {
-        create_table_cell(display_cell_id, cell_content, vertical_alignment, cx)
-            // Mouse events handlers will be here
-            .into_any_element()
+        let is_selected = self.selected_cell == Some(display_cell_id);
+        let cell = create_table_cell(
+            display_cell_id,
+            cell_content,
+            self.settings.vertical_alignment,
+            is_selected,
+            cx,
+        )
+        .on_mouse_down(
+            MouseButton::Right,
+            cx.listener(move |this, _, _window, cx| this.select_cell(display_cell_id, cx)),
+        )
+        .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+            if event.click_count() >= 2 {
+                this.start_editing(EditTarget::Cell(display_cell_id), window, cx);
+            } else {
+                this.select_cell(display_cell_id, cx);
+                window.focus(&this.focus_handle, cx);
+            }
+        }));
+
+        let focus_handle = self.focus_handle.clone();
+        let can_move_rows = self.can_move_rows();
+        right_click_menu(ElementId::Name(
+            format!(
+                "csv-cell-menu-{}-{}",
+                *display_cell_id.row, *display_cell_id.col
+            )
+            .into(),
+        ))
+        .trigger(move |_, _, _| cell)
+        .menu(move |window, cx| {
+            build_cell_context_menu(focus_handle.clone(), can_move_rows, window, cx)
+        })
+        .into_any_element()
+    }
+
+    /// Render the in-place editor for the cell currently being edited.
+    pub(crate) fn render_cell_editor(&self, target: EditTarget) -> Option<AnyElement> {
+        let cell_editor = self.cell_editor.as_ref()?;
+        (cell_editor.target == target).then(|| {
+            div()
+                .key_context("CsvCellEditor")
+                .size_full()
+                .px_1()
+                .child(cell_editor.editor.clone())
+                .into_any_element()
+        })
     }
 }
 
+fn build_cell_context_menu(
+    focus_handle: FocusHandle,
+    can_move_rows: bool,
+    window: &mut Window,
+    cx: &mut App,
+) -> Entity<ContextMenu> {
+    ContextMenu::build(window, cx, move |menu, _, _| {
+        menu.context(focus_handle)
+            .action("Edit Cell", Box::new(EditCell))
+            .separator()
+            .action("Insert Row Above", Box::new(InsertRowAbove))
+            .action("Insert Row Below", Box::new(InsertRowBelow))
+            .action_disabled_when(!can_move_rows, "Move Row Up", Box::new(MoveRowUp))
+            .action_disabled_when(!can_move_rows, "Move Row Down", Box::new(MoveRowDown))
+            .action("Delete Row", Box::new(DeleteRow))
+            .separator()
+            .action("Insert Column Left", Box::new(InsertColumnLeft))
+            .action("Insert Column Right", Box::new(InsertColumnRight))
+            .action("Move Column Left", Box::new(MoveColumnLeft))
+            .action("Move Column Right", Box::new(MoveColumnRight))
+            .action("Delete Column", Box::new(DeleteColumn))
+    })
+}
+
 /// Create styled table cell div element.
 fn create_table_cell(
     display_cell_id: DisplayCellId,
     cell_content: SharedString,
     vertical_alignment: VerticalAlignment,
+    is_selected: bool,
     cx: &Context<'_, CsvPreviewView>,
 ) -> gpui::Stateful<Div> {
     div()
@@ -38,7 +115,13 @@ fn create_table_cell(
         .flex()
         .h_full()
         .px_1()
-        .bg(cx.theme().colors().editor_background)
+        .map(|div| {
+            if is_selected {
+                div.bg(cx.theme().colors().element_selected)
+            } else {
+                div.bg(cx.theme().colors().editor_background)
+            }
+        })
         .border_b_1()
         .border_color(cx.theme().colors().border_variant)
         .map(|div| match vertical_alignment {
@@ -1,8 +1,9 @@
-use gpui::ElementId;
+use gpui::{ClickEvent, ElementId};
 use ui::{Tooltip, prelude::*};
 
 use crate::{
     CsvPreviewView,
+    editing::EditTarget,
     table_data_engine::sorting_by_column::{AppliedSorting, SortDirection},
     types::AnyColumn,
 };
@@ -21,7 +22,24 @@ impl CsvPreviewView {
             .items_center()
             .w_full()
             .font_buffer(cx)
-            .child(div().child(header_text))
+            .child(
+                self.render_cell_editor(EditTarget::Header(col_idx))
+                    .unwrap_or_else(|| {
+                        div()
+                            .id(ElementId::NamedInteger(
+                                "csv-header-text".into(),
+                                col_idx.get() as u64,
+                            ))
+                            .tooltip(Tooltip::text("Double-click to rename"))
+                            .on_click(cx.listener(move |this, event: &ClickEvent, window, cx| {
+                                if event.click_count() >= 2 {
+                                    this.start_editing(EditTarget::Header(col_idx), window, cx);
+                                }
+                            }))
+                            .child(header_text)
+                            .into_any_element()
+                    }),
+            )
             .child(h_flex().gap_1().child(self.create_sort_button(cx, col_idx)))
             .into_any_element()
     }
@@ -55,10 +73,10 @@ impl CsvPreviewView {
         )
         .tooltip(Tooltip::text(match self.engine.applied_sorting {
             Some(ordering) if ordering.col_idx == col_idx => match ordering.direction {
-                SortDirection::Asc => "Sorted A-Z. Click to sort Z-A",
-                SortDirection::Desc => "Sorted Z-A. Click to disable sorting",
+                SortDirection::Asc => "Sorted ascending. Click to sort descending",
+                SortDirection::Desc => "Sorted descending. Click to disable sorting",
             },
-            _ => "Not sorted. Click to sort A-Z",
+            _ => "Not sorted. Click to sort ascending",
         }))
         .on_click(cx.listener(move |this, _event, _window, cx| {
             let new_sorting = match this.engine.applied_sorting {
@@ -82,7 +100,7 @@ impl CsvPreviewView {
             };
 
             this.engine.applied_sorting = new_sorting;
-            this.apply_sort();
+            this.apply_filter_sort(cx);
             cx.notify();
         }));
         sort_btn
@@ -5,22 +5,52 @@
 //!
 //! It's designed to contain core logic of operations without relying on `CsvPreviewView`, context or window handles.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
-use ui::table_row::TableRow;
+use ui::{SharedString, table_row::TableRow};
 
 use crate::{
-    table_data_engine::sorting_by_column::{AppliedSorting, sort_data_rows},
-    types::{DataRow, DisplayRow, TableCell, TableLikeContent},
+    table_data_engine::{
+        column_stats::{ColumnStats, compute_column_stats},
+        filtering::{FilterCell, FilterParseError, RowFilter},
+        sorting_by_column::{AppliedSorting, sort_data_rows},
+    },
+    types::{AnyColumn, DataRow, DisplayRow, TableCell, TableLikeContent},
 };
 
+pub mod column_stats;
+pub mod editing;
+pub mod filtering;
 pub mod sorting_by_column;
+pub mod typed_values;
 
 #[derive(Default)]
 pub(crate) struct TableDataEngine {
     pub applied_sorting: Option<AppliedSorting>,
+    /// Filter expression as typed into the filter bar
+    filter_query: String,
+    /// Last valid filter parsed from `filter_query`
+    applied_filter: Option<RowFilter>,
+    /// Set while `filter_query` doesn't parse. The last valid filter stays applied meanwhile.
+    filter_error: Option<FilterParseError>,
     d2d_mapping: DisplayToDataMapping,
-    pub contents: TableLikeContent,
+    /// Lowercased cells for case-insensitive filtering.
+    /// Computed the first time a filter needs them and kept until the contents change.
+    lowercase_columns: Option<Arc<LowercaseColumns>>,
+    /// Statistics of the most recently summarized column, valid for the current mapping
+    column_stats: Option<(AnyColumn, ColumnStats)>,
+    pub contents: Arc<TableLikeContent>,
+}
+
+/// Result of [`TableDataEngine::mapping_job`], to be applied with
+/// [`TableDataEngine::apply_mapping`].
+pub(crate) struct MappingUpdate {
+    sorted_rows: Vec<DataRow>,
+    filtered_out_rows: HashSet<DataRow>,
+    lowercase_columns: Option<Arc<LowercaseColumns>>,
 }
 
 impl TableDataEngine {
@@ -28,28 +58,185 @@ impl TableDataEngine {
         &self.d2d_mapping
     }
 
-    pub(crate) fn apply_sort(&mut self) {
-        self.d2d_mapping
-            .apply_sorting(self.applied_sorting, &self.contents.rows);
-        self.d2d_mapping.merge_mappings();
+    /// Replaces the table contents. The mapping has to be recomputed afterwards.
+    pub(crate) fn set_contents(&mut self, contents: TableLikeContent) {
+        self.contents = Arc::new(contents);
+        self.lowercase_columns = None;
+        self.column_stats = None;
+        // Columns may have been renamed, added or removed
+        self.parse_filter_query();
+    }
+
+    pub(crate) fn filter_error(&self) -> Option<&FilterParseError> {
+        self.filter_error.as_ref()
+    }
+
+    pub(crate) fn is_filtered(&self) -> bool {
+        self.applied_filter.is_some()
+    }
+
+    /// Updates the filter expression. The mapping has to be recomputed afterwards.
+    pub(crate) fn set_filter_query(&mut self, query: String) {
+        self.filter_query = query;
+        self.parse_filter_query();
     }
 
-    /// Applies sorting and filtering to the data and produces display to data mapping
-    pub(crate) fn calculate_d2d_mapping(&mut self) {
-        self.d2d_mapping
-            .apply_sorting(self.applied_sorting, &self.contents.rows);
+    fn parse_filter_query(&mut self) {
+        let column_names = self
+            .contents
+            .headers
+            .as_slice()
+            .iter()
+            .map(|header| header.display_value().cloned().unwrap_or_default())
+            .collect::<Vec<SharedString>>();
+
+        match RowFilter::parse(&self.filter_query, &column_names) {
+            Ok(filter) => {
+                self.applied_filter = filter;
+                self.filter_error = None;
+            }
+            Err(error) => self.filter_error = Some(error),
+        }
+    }
+
+    /// Sorting and filtering of all rows, to be run on a background thread as it's linear in the
+    /// size of the table.
+    pub(crate) fn mapping_job(&self) -> impl FnOnce() -> MappingUpdate + Send + 'static {
+        let contents = self.contents.clone();
+        let sorting = self.applied_sorting;
+        let filter = self.applied_filter.clone();
+        let lowercase_columns = self.lowercase_columns.clone();
+        move || {
+            let sorted_rows = sort_rows(sorting, &contents.rows);
+            let Some(filter) = filter else {
+                return MappingUpdate {
+                    sorted_rows,
+                    filtered_out_rows: HashSet::default(),
+                    lowercase_columns,
+                };
+            };
+
+            let lowercase_columns =
+                lowercase_columns.unwrap_or_else(|| Arc::new(LowercaseColumns::new(&contents)));
+            let filtered_out_rows = contents
+                .rows
+                .iter()
+                .enumerate()
+                .map(|(data_row, row)| (DataRow(data_row), row))
+                .filter(|(data_row, row)| {
+                    !filter.matches(contents.number_of_cols, |col| FilterCell {
+                        text: cell_value(row, col),
+                        lowercase: lowercase_columns.cell(*data_row, col),
+                    })
+                })
+                .map(|(data_row, _)| data_row)
+                .collect();
+            MappingUpdate {
+                sorted_rows,
+                filtered_out_rows,
+                lowercase_columns: Some(lowercase_columns),
+            }
+        }
+    }
+
+    pub(crate) fn apply_mapping(&mut self, update: MappingUpdate) {
+        self.d2d_mapping.sorted_rows = update.sorted_rows;
+        self.d2d_mapping.filtered_out_rows = update.filtered_out_rows;
         self.d2d_mapping.merge_mappings();
+        if update.lowercase_columns.is_some() {
+            self.lowercase_columns = update.lowercase_columns;
+        }
+        self.column_stats = None;
+    }
+
+    /// Statistics of `col` over the rows passing the current filter, if already computed with
+    /// [`Self::column_stats_job`].
+    pub(crate) fn column_stats(&self, col: AnyColumn) -> Option<&ColumnStats> {
+        self.column_stats
+            .as_ref()
+            .filter(|(cached_col, _)| *cached_col == col)
+            .map(|(_, stats)| stats)
+    }
+
+    /// Computation of the statistics of `col` over the currently displayed rows, to be run on a
+    /// background thread and stored with [`Self::set_column_stats`].
+    pub(crate) fn column_stats_job(
+        &self,
+        col: AnyColumn,
+    ) -> impl FnOnce() -> ColumnStats + Send + 'static {
+        let contents = self.contents.clone();
+        let mapping = self.d2d_mapping.mapping.clone();
+        move || {
+            let values = (0..mapping.len()).filter_map(|display_row| {
+                let data_row = mapping.get(&DisplayRow(display_row))?;
+                Some(cell_value(contents.get_row(*data_row)?, col))
+            });
+            compute_column_stats(values)
+        }
+    }
+
+    pub(crate) fn set_column_stats(&mut self, col: AnyColumn, stats: ColumnStats) {
+        self.column_stats = Some((col, stats));
     }
 }
 
+/// Lowercased text of every cell, by column, so case-insensitive filters don't have to
+/// lowercase the whole table on every keystroke.
+pub(crate) struct LowercaseColumns {
+    columns: Vec<Vec<String>>,
+}
+
+impl LowercaseColumns {
+    fn new(contents: &TableLikeContent) -> Self {
+        let columns = (0..contents.number_of_cols)
+            .map(|col| {
+                contents
+                    .rows
+                    .iter()
+                    .map(|row| cell_value(row, AnyColumn(col)).to_lowercase())
+                    .collect()
+            })
+            .collect();
+        Self { columns }
+    }
+
+    fn cell(&self, row: DataRow, col: AnyColumn) -> &str {
+        self.columns
+            .get(*col)
+            .and_then(|column| column.get(*row))
+            .map_or("", String::as_str)
+    }
+}
+
+/// All rows in the order of `sorting`.
+fn sort_rows(sorting: Option<AppliedSorting>, rows: &[TableRow<TableCell>]) -> Vec<DataRow> {
+    let data_rows: Vec<DataRow> = (0..rows.len()).map(DataRow).collect();
+    if let Some(sorting) = sorting {
+        sort_data_rows(rows, data_rows, sorting)
+    } else {
+        data_rows
+    }
+}
+
+/// Text of the cell, with virtual cells of malformed rows being empty.
+fn cell_value(row: &TableRow<TableCell>, col: AnyColumn) -> &str {
+    row.get(col)
+        .and_then(|cell| cell.display_value())
+        .map(|value| value.as_str())
+        .unwrap_or("")
+}
+
 /// Relation of Display (rendered) rows to Data (src) rows with applied transformations
 /// Transformations applied:
 /// - sorting by column
+/// - filtering by the filter expression
 #[derive(Debug, Default)]
 pub struct DisplayToDataMapping {
     /// All rows sorted, regardless of applied filtering. Applied every time sorting changes
     pub sorted_rows: Vec<DataRow>,
-    /// Filtered and sorted rows. Computed cheaply from `sorted_mapping` and `filtered_out_rows`
+    /// Rows not passing the filter. Applied every time the filter or the data changes
+    pub filtered_out_rows: HashSet<DataRow>,
+    /// Filtered and sorted rows. Computed cheaply from `sorted_rows` and `filtered_out_rows`
     pub mapping: Arc<HashMap<DisplayRow, DataRow>>,
 }
 
@@ -64,27 +251,78 @@ impl DisplayToDataMapping {
         self.mapping.len()
     }
 
-    /// Computes sorting
-    fn apply_sorting(&mut self, sorting: Option<AppliedSorting>, rows: &[TableRow<TableCell>]) {
-        let data_rows: Vec<DataRow> = (0..rows.len()).map(DataRow).collect();
-
-        let sorted_rows = if let Some(sorting) = sorting {
-            sort_data_rows(&rows, data_rows, sorting)
-        } else {
-            data_rows
-        };
-
-        self.sorted_rows = sorted_rows;
-    }
-
     /// Take pre-computed sorting and filtering results, and apply them to the mapping
     fn merge_mappings(&mut self) {
         self.mapping = Arc::new(
             self.sorted_rows
                 .iter()
+                .filter(|data| !self.filtered_out_rows.contains(*data))
                 .enumerate()
                 .map(|(display, data)| (DisplayRow(display), *data))
                 .collect(),
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parser::from_buffer, table_data_engine::sorting_by_column::SortDirection};
+    use text::{Buffer, BufferId, ReplicaId};
+
+    fn engine(text: &str) -> TableDataEngine {
+        let buffer = Buffer::new(ReplicaId::LOCAL, BufferId::new(1).unwrap(), text);
+        let mut engine = TableDataEngine::default();
+        engine.set_contents(from_buffer(&buffer.snapshot(), ','));
+        engine
+    }
+
+    fn displayed_rows(engine: &TableDataEngine) -> Vec<usize> {
+        let mapping = engine.d2d_mapping();
+        (0..mapping.visible_row_count())
+            .map(|row| *mapping.get_data_row(DisplayRow(row)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_mapping_job_sorts_filters_and_caches_lowercase_cells() {
+        let mut engine = engine("name,price\nApple,3\nbanana,1\nAPRICOT,2\n");
+        engine.applied_sorting = Some(AppliedSorting {
+            col_idx: AnyColumn(1),
+            direction: SortDirection::Asc,
+        });
+        engine.set_filter_query("ap".into());
+        let update = engine.mapping_job()();
+        engine.apply_mapping(update);
+        assert_eq!(displayed_rows(&engine), vec![2, 0]);
+        let lowercase_columns = engine.lowercase_columns.clone().unwrap();
+
+        engine.set_filter_query("name ~ BAN".into());
+        let update = engine.mapping_job()();
+        engine.apply_mapping(update);
+        assert_eq!(displayed_rows(&engine), vec![1]);
+        assert!(Arc::ptr_eq(
+            engine.lowercase_columns.as_ref().unwrap(),
+            &lowercase_columns
+        ));
+
+        engine.set_contents(engine.contents.as_ref().clone());
+        assert!(engine.lowercase_columns.is_none());
+    }
+
+    #[test]
+    fn test_column_stats_cover_displayed_rows() {
+        let mut engine = engine("name,price\nApple,3\nbanana,1\nAPRICOT,2\n");
+        engine.set_filter_query("price >= 2".into());
+        let update = engine.mapping_job()();
+        engine.apply_mapping(update);
+        assert_eq!(engine.column_stats(AnyColumn(1)), None);
+
+        let stats = engine.column_stats_job(AnyColumn(1))();
+        engine.set_column_stats(AnyColumn(1), stats);
+        let stats = engine.column_stats(AnyColumn(1)).unwrap();
+        assert_eq!(stats.count, 2);
+        assert_eq!(stats.mean, Some(2.5));
+        assert_eq!(engine.column_stats(AnyColumn(0)), None);
+    }
+}
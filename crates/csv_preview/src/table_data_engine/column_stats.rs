@@ -0,0 +1,104 @@
+//! Summary statistics of a single column, computed over the rows passing the current filter.
+
+use std::collections::HashSet;
+
+use crate::table_data_engine::typed_values::{ColumnType, TypedValue, infer_column_type};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStats {
+    pub column_type: ColumnType,
+    /// Number of non-empty values
+    pub count: usize,
+    /// Number of distinct non-empty values
+    pub distinct: usize,
+    /// Number of empty cells
+    pub nulls: usize,
+    /// Smallest and largest values according to the column type, as written in the source
+    pub min: Option<String>,
+    pub max: Option<String>,
+    /// Only available for numeric columns
+    pub mean: Option<f64>,
+}
+
+/// Compute statistics of the given cell values of a column.
+pub fn compute_column_stats<'a>(values: impl Iterator<Item = &'a str> + Clone) -> ColumnStats {
+    let column_type = infer_column_type(values.clone());
+
+    let mut count = 0;
+    let mut nulls = 0;
+    let mut distinct = HashSet::new();
+    let mut min: Option<(TypedValue, &str)> = None;
+    let mut max: Option<(TypedValue, &str)> = None;
+    let mut sum = 0.0;
+
+    for value in values {
+        let typed = column_type.parse(value);
+        if typed.is_null() {
+            nulls += 1;
+            continue;
+        }
+
+        count += 1;
+        distinct.insert(value.trim());
+        if let TypedValue::Number(number) = typed {
+            sum += number;
+        }
+        if min.is_none_or(|(min, _)| typed.total_cmp(&min).is_lt()) {
+            min = Some((typed, value));
+        }
+        if max.is_none_or(|(max, _)| typed.total_cmp(&max).is_gt()) {
+            max = Some((typed, value));
+        }
+    }
+
+    ColumnStats {
+        column_type,
+        count,
+        distinct: distinct.len(),
+        nulls,
+        min: min.map(|(_, value)| value.trim().to_string()),
+        max: max.map(|(_, value)| value.trim().to_string()),
+        mean: (column_type == ColumnType::Number && count > 0).then(|| sum / count as f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_column_stats() {
+        let values = ["10", "9", "", "100", "9", " "];
+        assert_eq!(
+            compute_column_stats(values.into_iter()),
+            ColumnStats {
+                column_type: ColumnType::Number,
+                count: 4,
+                distinct: 3,
+                nulls: 2,
+                min: Some("9".to_string()),
+                max: Some("100".to_string()),
+                mean: Some(32.0),
+            }
+        );
+    }
+
+    #[test]
+    fn test_text_column_stats() {
+        let values = ["b", "a", "10", "c"];
+        let stats = compute_column_stats(values.into_iter());
+        assert_eq!(stats.column_type, ColumnType::Text);
+        assert_eq!(stats.min.as_deref(), Some("10"));
+        assert_eq!(stats.max.as_deref(), Some("c"));
+        assert_eq!(stats.mean, None);
+    }
+
+    #[test]
+    fn test_date_column_stats() {
+        let values = ["2024-03-01", "2023-12-31T23:59:59Z", "2024-01-15 08:00"];
+        let stats = compute_column_stats(values.into_iter());
+        assert_eq!(stats.column_type, ColumnType::Date);
+        assert_eq!(stats.min.as_deref(), Some("2023-12-31T23:59:59Z"));
+        assert_eq!(stats.max.as_deref(), Some("2024-03-01"));
+    }
+}
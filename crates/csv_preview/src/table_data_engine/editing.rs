@@ -0,0 +1,598 @@
+//! Translates table-level edits (cell changes, row/column insertion, deletion and reordering)
+//! into text edits on the source buffer.
+//!
+//! Edits are expressed in data coordinates and computed against the snapshot the table was parsed
+//! from, so they can be applied as a single buffer transaction. Untouched cells keep their original
+//! text byte for byte; edited cells are re-encoded with the table's delimiter, preserving quotes
+//! when the original cell was quoted.
+
+use std::ops::Range;
+
+use text::{BufferSnapshot, ToOffset as _};
+use ui::table_row::TableRow;
+
+use crate::types::{AnyColumn, DataRow, TableCell, TableLikeContent};
+
+/// Row of the source data, including the header row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceRow {
+    Header,
+    Data(DataRow),
+}
+
+/// A structural change to the table, in data coordinates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableEdit {
+    /// Replace the value of a single cell
+    SetCell {
+        row: SourceRow,
+        col: AnyColumn,
+        value: String,
+    },
+    /// Insert an empty row before `at`. `at == rows.len()` appends
+    InsertRow {
+        at: DataRow,
+    },
+    DeleteRow {
+        row: DataRow,
+    },
+    /// Move a row so it ends up at index `to`
+    MoveRow {
+        from: DataRow,
+        to: DataRow,
+    },
+    /// Insert an empty column before `at`. `at == number_of_cols` appends
+    InsertColumn {
+        at: AnyColumn,
+    },
+    DeleteColumn {
+        col: AnyColumn,
+    },
+    /// Move a column so it ends up at index `to`
+    MoveColumn {
+        from: AnyColumn,
+        to: AnyColumn,
+    },
+}
+
+/// Compute the buffer edits implementing `edit`, sorted by position and non-overlapping.
+///
+/// Returns no edits when `edit` refers to rows or columns outside of the table.
+pub fn buffer_edits(
+    contents: &TableLikeContent,
+    edit: &TableEdit,
+    snapshot: &BufferSnapshot,
+) -> Vec<(Range<usize>, String)> {
+    let table = SourceTable::new(contents, snapshot);
+    let mut edits = match edit {
+        TableEdit::SetCell { row, col, value } => table.set_cell(*row, *col, value),
+        TableEdit::InsertRow { at } => table.insert_row(*at),
+        TableEdit::DeleteRow { row } => table.delete_row(*row),
+        TableEdit::MoveRow { from, to } => table.move_row(*from, *to),
+        TableEdit::InsertColumn { at } => table.insert_column(*at),
+        TableEdit::DeleteColumn { col } => table.delete_column(*col),
+        TableEdit::MoveColumn { from, to } => table.move_column(*from, *to),
+    };
+    edits.sort_by_key(|(range, _)| range.start);
+    edits
+}
+
+/// Encode `value` as a single field. Values are quoted when required by their content, or when
+/// `quote` is set (to preserve the quoting style of the cell being replaced).
+pub fn encode_field(value: &str, delimiter: char, quote: bool) -> String {
+    let needs_quotes = quote
+        || value.contains(delimiter)
+        || value.contains('"')
+        || value.contains('\n')
+        || value.contains('\r');
+    if needs_quotes {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Resolved byte ranges of every parsed row, header first.
+struct SourceTable {
+    text: String,
+    delimiter: char,
+    number_of_cols: usize,
+    /// Byte ranges of the real (non-virtual) cells of each row, header first
+    rows: Vec<Vec<Range<usize>>>,
+}
+
+impl SourceTable {
+    fn new(contents: &TableLikeContent, snapshot: &BufferSnapshot) -> Self {
+        let resolve = |row: &TableRow<TableCell>| {
+            row.as_slice()
+                .iter()
+                .filter_map(|cell| match cell {
+                    TableCell::Real { position, .. } => {
+                        Some(position.start.to_offset(snapshot)..position.end.to_offset(snapshot))
+                    }
+                    TableCell::Virtual => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let rows = std::iter::once(&contents.headers)
+            .chain(contents.rows.iter())
+            .map(resolve)
+            .collect();
+
+        Self {
+            text: snapshot.text(),
+            delimiter: contents.delimiter,
+            number_of_cols: contents.number_of_cols,
+            rows,
+        }
+    }
+
+    fn data_row_count(&self) -> usize {
+        self.rows.len().saturating_sub(1)
+    }
+
+    fn cells(&self, row: SourceRow) -> Option<&[Range<usize>]> {
+        let index = match row {
+            SourceRow::Header => 0,
+            SourceRow::Data(row) => *row + 1,
+        };
+        self.rows.get(index).map(Vec::as_slice)
+    }
+
+    fn data_cells(&self, row: DataRow) -> Option<&[Range<usize>]> {
+        self.cells(SourceRow::Data(row))
+    }
+
+    fn row_range(cells: &[Range<usize>]) -> Range<usize> {
+        cells.first().map_or(0, |cell| cell.start)..cells.last().map_or(0, |cell| cell.end)
+    }
+
+    fn row_text(&self, cells: &[Range<usize>]) -> &str {
+        &self.text[Self::row_range(cells)]
+    }
+
+    /// Whether every cell of the row other than `col` is empty or whitespace, so that the row
+    /// would be parsed as a blank line without it.
+    fn is_blank_without(&self, cells: &[Range<usize>], col: AnyColumn) -> bool {
+        cells
+            .iter()
+            .enumerate()
+            .all(|(ix, cell)| ix == *col || self.text[cell.clone()].trim().is_empty())
+    }
+
+    /// An empty record. Its first field is quoted, since a row of empty unquoted fields would be
+    /// parsed as a blank line.
+    fn empty_row(&self) -> String {
+        format!(
+            "\"\"{}",
+            self.delimiter
+                .to_string()
+                .repeat(self.number_of_cols.saturating_sub(1))
+        )
+    }
+
+    fn set_cell(&self, row: SourceRow, col: AnyColumn, value: &str) -> Vec<(Range<usize>, String)> {
+        let Some(cells) = self.cells(row) else {
+            return Vec::new();
+        };
+        if let Some(cell) = cells.get(*col) {
+            let quoted = self.text[cell.clone()].starts_with('"')
+                || value.trim().is_empty() && self.is_blank_without(cells, col);
+            vec![(cell.clone(), encode_field(value, self.delimiter, quoted))]
+        } else {
+            // Pad a short row with empty fields up to the edited column
+            let row_end = Self::row_range(cells).end;
+            let padding = self.delimiter.to_string().repeat(*col - cells.len() + 1);
+            vec![(
+                row_end..row_end,
+                padding + &encode_field(value, self.delimiter, false),
+            )]
+        }
+    }
+
+    /// Line breaks are written as `\n` because buffers keep their text normalized and convert
+    /// it back to the file's own line ending (e.g. CRLF) when saving.
+    fn insert_row(&self, at: DataRow) -> Vec<(Range<usize>, String)> {
+        if let Some(cells) = self.data_cells(at) {
+            let row_start = Self::row_range(cells).start;
+            vec![(row_start..row_start, format!("{}\n", self.empty_row()))]
+        } else if *at == self.data_row_count()
+            && let Some(last) = self.rows.last()
+        {
+            let row_end = Self::row_range(last).end;
+            vec![(row_end..row_end, format!("\n{}", self.empty_row()))]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Range covering the row and one adjacent line break, so removing it leaves no blank line.
+    fn row_range_with_line_break(&self, row: DataRow) -> Option<Range<usize>> {
+        let cells = self.data_cells(row)?;
+        let range = Self::row_range(cells);
+        if let Some(next) = self.data_cells(DataRow(*row + 1)) {
+            Some(range.start..Self::row_range(next).start)
+        } else {
+            let previous = self.rows.get(*row)?;
+            Some(Self::row_range(previous).end..range.end)
+        }
+    }
+
+    fn delete_row(&self, row: DataRow) -> Vec<(Range<usize>, String)> {
+        self.row_range_with_line_break(row)
+            .map(|range| vec![(range, String::new())])
+            .unwrap_or_default()
+    }
+
+    fn move_row(&self, from: DataRow, to: DataRow) -> Vec<(Range<usize>, String)> {
+        if from == to || *to >= self.data_row_count() {
+            return Vec::new();
+        }
+        let (Some(removed), Some(moved_cells), Some(target_cells)) = (
+            self.row_range_with_line_break(from),
+            self.data_cells(from),
+            self.data_cells(to),
+        ) else {
+            return Vec::new();
+        };
+        let moved_text = self.row_text(moved_cells);
+        let target = Self::row_range(target_cells);
+
+        let insertion = if to < from {
+            (target.start..target.start, format!("{moved_text}\n"))
+        } else {
+            (target.end..target.end, format!("\n{moved_text}"))
+        };
+        vec![(removed, String::new()), insertion]
+    }
+
+    fn insert_column(&self, at: AnyColumn) -> Vec<(Range<usize>, String)> {
+        if *at > self.number_of_cols {
+            return Vec::new();
+        }
+        self.rows
+            .iter()
+            .filter_map(|cells| {
+                if let Some(cell) = cells.get(*at) {
+                    Some((cell.start..cell.start, self.delimiter.to_string()))
+                } else if *at == cells.len() {
+                    let row_end = Self::row_range(cells).end;
+                    Some((row_end..row_end, self.delimiter.to_string()))
+                } else {
+                    // Short rows don't have this column yet; it stays virtual
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn delete_column(&self, col: AnyColumn) -> Vec<(Range<usize>, String)> {
+        self.rows
+            .iter()
+            .filter_map(|cells| {
+                let cell = cells.get(*col)?;
+                if self.is_blank_without(cells, col) {
+                    // Write the remaining fields as an empty record, so the row isn't lost
+                    let remaining = self
+                        .delimiter
+                        .to_string()
+                        .repeat(cells.len().saturating_sub(2));
+                    return Some((Self::row_range(cells), format!("\"\"{remaining}")));
+                }
+                let range = if let Some(next) = cells.get(*col + 1) {
+                    cell.start..next.start
+                } else if let Some(previous) = col.checked_sub(1).and_then(|ix| cells.get(ix)) {
+                    previous.end..cell.end
+                } else {
+                    cell.clone()
+                };
+                Some((range, String::new()))
+            })
+            .collect()
+    }
+
+    fn move_column(&self, from: AnyColumn, to: AnyColumn) -> Vec<(Range<usize>, String)> {
+        if from == to || *from >= self.number_of_cols || *to >= self.number_of_cols {
+            return Vec::new();
+        }
+        self.rows
+            .iter()
+            .filter(|cells| *from < cells.len() || *to < cells.len())
+            .map(|cells| {
+                let mut fields = cells
+                    .iter()
+                    .map(|cell| &self.text[cell.clone()])
+                    .collect::<Vec<_>>();
+                let width = fields.len().max(*from.max(to) + 1);
+                fields.resize(width, "");
+                let field = fields.remove(*from);
+                fields.insert(*to, field);
+                (
+                    Self::row_range(cells),
+                    fields.join(&self.delimiter.to_string()),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::from_buffer;
+    use text::{Buffer, BufferId, ReplicaId};
+
+    fn apply(text: &str, delimiter: char, edit: TableEdit) -> String {
+        let mut buffer = Buffer::new(ReplicaId::LOCAL, BufferId::new(1).unwrap(), text);
+        let contents = from_buffer(&buffer.snapshot(), delimiter);
+        let edits = buffer_edits(&contents, &edit, &buffer.snapshot());
+        buffer.edit(edits);
+        buffer.text()
+    }
+
+    #[test]
+    fn test_set_cell_preserves_other_cells_and_quoting() {
+        let csv = "name,notes\n\"Doe, John\",plain\nJane,\"quoted\"\n";
+        assert_eq!(
+            apply(
+                csv,
+                ',',
+                TableEdit::SetCell {
+                    row: SourceRow::Data(DataRow(1)),
+                    col: AnyColumn(1),
+                    value: "new".into(),
+                }
+            ),
+            "name,notes\n\"Doe, John\",plain\nJane,\"new\"\n"
+        );
+        assert_eq!(
+            apply(
+                csv,
+                ',',
+                TableEdit::SetCell {
+                    row: SourceRow::Data(DataRow(0)),
+                    col: AnyColumn(1),
+                    value: "has \"quotes\", and commas".into(),
+                }
+            ),
+            "name,notes\n\"Doe, John\",\"has \"\"quotes\"\", and commas\"\nJane,\"quoted\"\n"
+        );
+        assert_eq!(
+            apply(
+                csv,
+                ',',
+                TableEdit::SetCell {
+                    row: SourceRow::Header,
+                    col: AnyColumn(0),
+                    value: "full name".into(),
+                }
+            ),
+            "full name,notes\n\"Doe, John\",plain\nJane,\"quoted\"\n"
+        );
+    }
+
+    #[test]
+    fn test_set_cell_pads_short_rows_and_fills_empty_cells() {
+        assert_eq!(
+            apply(
+                "a,b,c\n1\n",
+                ',',
+                TableEdit::SetCell {
+                    row: SourceRow::Data(DataRow(0)),
+                    col: AnyColumn(2),
+                    value: "3".into(),
+                }
+            ),
+            "a,b,c\n1,,3\n"
+        );
+        assert_eq!(
+            apply(
+                "a,b,c\n1,,3\n",
+                ',',
+                TableEdit::SetCell {
+                    row: SourceRow::Data(DataRow(0)),
+                    col: AnyColumn(1),
+                    value: "2".into(),
+                }
+            ),
+            "a,b,c\n1,2,3\n"
+        );
+    }
+
+    #[test]
+    fn test_set_cell_uses_table_delimiter() {
+        assert_eq!(
+            apply(
+                "a\tb\n1\t2",
+                '\t',
+                TableEdit::SetCell {
+                    row: SourceRow::Data(DataRow(0)),
+                    col: AnyColumn(0),
+                    value: "x\ty".into(),
+                }
+            ),
+            "a\tb\n\"x\ty\"\t2"
+        );
+    }
+
+    #[test]
+    fn test_insert_and_delete_rows() {
+        let csv = "a,b\n1,2\n3,4";
+        assert_eq!(
+            apply(csv, ',', TableEdit::InsertRow { at: DataRow(1) }),
+            "a,b\n1,2\n\"\",\n3,4"
+        );
+        assert_eq!(
+            apply(csv, ',', TableEdit::InsertRow { at: DataRow(2) }),
+            "a,b\n1,2\n3,4\n\"\","
+        );
+        assert_eq!(
+            apply(csv, ',', TableEdit::DeleteRow { row: DataRow(0) }),
+            "a,b\n3,4"
+        );
+        assert_eq!(
+            apply(csv, ',', TableEdit::DeleteRow { row: DataRow(1) }),
+            "a,b\n1,2"
+        );
+        assert_eq!(
+            apply("a\n1", ',', TableEdit::InsertRow { at: DataRow(0) }),
+            "a\n\"\"\n1"
+        );
+    }
+
+    #[test]
+    fn test_inserted_rows_use_the_file_line_ending() {
+        let mut buffer = Buffer::new(
+            ReplicaId::LOCAL,
+            BufferId::new(1).unwrap(),
+            "a,b\r\n1,2\r\n3,4",
+        );
+        for at in [DataRow(1), DataRow(3)] {
+            let contents = from_buffer(&buffer.snapshot(), ',');
+            let edits = buffer_edits(&contents, &TableEdit::InsertRow { at }, &buffer.snapshot());
+            buffer.edit(edits);
+        }
+
+        let saved: String =
+            text::chunks_with_line_ending(buffer.as_rope(), buffer.line_ending()).collect();
+        assert_eq!(saved, "a,b\r\n1,2\r\n\"\",\r\n3,4\r\n\"\",");
+    }
+
+    #[test]
+    fn test_inserted_empty_rows_are_parsed_as_rows() {
+        let text = apply("a,b\n1,2", ',', TableEdit::InsertRow { at: DataRow(0) });
+        let contents = TableLikeContent::from_str(text);
+        assert_eq!(contents.rows.len(), 2);
+    }
+
+    #[test]
+    fn test_move_rows() {
+        let csv = "h\nA\nB\nC";
+        assert_eq!(
+            apply(
+                csv,
+                ',',
+                TableEdit::MoveRow {
+                    from: DataRow(0),
+                    to: DataRow(2)
+                }
+            ),
+            "h\nB\nC\nA"
+        );
+        assert_eq!(
+            apply(
+                csv,
+                ',',
+                TableEdit::MoveRow {
+                    from: DataRow(2),
+                    to: DataRow(0)
+                }
+            ),
+            "h\nC\nA\nB"
+        );
+        assert_eq!(
+            apply(
+                csv,
+                ',',
+                TableEdit::MoveRow {
+                    from: DataRow(2),
+                    to: DataRow(1)
+                }
+            ),
+            "h\nA\nC\nB"
+        );
+    }
+
+    #[test]
+    fn test_insert_and_delete_columns() {
+        let csv = "a,b\n1,2\n3";
+        assert_eq!(
+            apply(csv, ',', TableEdit::InsertColumn { at: AnyColumn(0) }),
+            ",a,b\n,1,2\n,3"
+        );
+        assert_eq!(
+            apply(csv, ',', TableEdit::InsertColumn { at: AnyColumn(2) }),
+            "a,b,\n1,2,\n3"
+        );
+        assert_eq!(
+            apply(csv, ',', TableEdit::DeleteColumn { col: AnyColumn(0) }),
+            "b\n2\n\"\""
+        );
+        assert_eq!(
+            apply(csv, ',', TableEdit::DeleteColumn { col: AnyColumn(1) }),
+            "a\n1\n3"
+        );
+    }
+
+    #[test]
+    fn test_deleting_a_column_keeps_every_row() {
+        for (csv, col) in [
+            ("a,b\n1,2\n3", AnyColumn(0)),
+            ("a,b\n1,\n,2", AnyColumn(1)),
+            ("a\n1\n2", AnyColumn(0)),
+        ] {
+            let rows = TableLikeContent::from_str(csv.to_string()).rows.len();
+            let text = apply(csv, ',', TableEdit::DeleteColumn { col });
+            assert_eq!(
+                TableLikeContent::from_str(text.clone()).rows.len(),
+                rows,
+                "{text:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_setting_the_last_filled_cell_to_empty_keeps_the_row() {
+        let text = apply(
+            "a,b\n1,\n2,3",
+            ',',
+            TableEdit::SetCell {
+                row: SourceRow::Data(DataRow(0)),
+                col: AnyColumn(0),
+                value: String::new(),
+            },
+        );
+        assert_eq!(text, "a,b\n\"\",\n2,3");
+        assert_eq!(TableLikeContent::from_str(text).rows.len(), 2);
+    }
+
+    #[test]
+    fn test_move_columns() {
+        let csv = "a,\"b,c\",d\n1,2,3\n4";
+        assert_eq!(
+            apply(
+                csv,
+                ',',
+                TableEdit::MoveColumn {
+                    from: AnyColumn(0),
+                    to: AnyColumn(2)
+                }
+            ),
+            "\"b,c\",d,a\n2,3,1\n,,4"
+        );
+        assert_eq!(
+            apply(
+                csv,
+                ',',
+                TableEdit::MoveColumn {
+                    from: AnyColumn(2),
+                    to: AnyColumn(1)
+                }
+            ),
+            "a,d,\"b,c\"\n1,3,2\n4"
+        );
+    }
+
+    #[test]
+    fn test_out_of_range_edits_are_ignored() {
+        let csv = "a,b\n1,2";
+        assert_eq!(
+            apply(csv, ',', TableEdit::DeleteRow { row: DataRow(5) }),
+            csv
+        );
+        assert_eq!(
+            apply(csv, ',', TableEdit::InsertColumn { at: AnyColumn(7) }),
+            csv
+        );
+    }
+}
@@ -0,0 +1,510 @@
+//! Row filtering by an expression typed into the filter bar.
+//!
+//! Supported syntax:
+//! - `error` keeps rows where any cell contains "error" (case-insensitive)
+//! - `price > 10 and country = DE` compares cells of the named columns
+//! - `` `unit price` >= 9.99 or not (status ~ "cancel") `` for column names and values with spaces
+//!
+//! Comparison operators are `=`, `!=`, `<`, `<=`, `>`, `>=`, `~` (contains) and `!~` (doesn't
+//! contain). Comparisons are typed: when both sides parse as numbers or dates they're compared as
+//! such, otherwise as case-insensitive text. Conditions are combined with `and`/`&&`, `or`/`||`,
+//! `not`/`!` and parentheses; conditions separated by whitespace only are and-ed.
+
+use std::{cmp::Ordering, fmt};
+
+use chrono::NaiveDateTime;
+
+use crate::{
+    table_data_engine::typed_values::{is_null, parse_date, parse_number},
+    types::AnyColumn,
+};
+
+/// Parsed filter expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowFilter {
+    expr: FilterExpr,
+}
+
+/// A cell as seen by the filter. The lowercase text is passed in, rather than derived on every
+/// match, so callers can compute it once per table instead of once per keystroke.
+#[derive(Debug, Clone, Copy)]
+pub struct FilterCell<'a> {
+    pub text: &'a str,
+    pub lowercase: &'a str,
+}
+
+/// Error in a filter expression, pointing at the offending part of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterParseError {
+    pub message: String,
+    /// Char offset into the filter input
+    pub offset: usize,
+}
+
+impl fmt::Display for FilterParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at character {})", self.message, self.offset + 1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FilterExpr {
+    /// Any cell of the row contains the (lowercase) text
+    AnyCellContains(String),
+    Compare {
+        col: AnyColumn,
+        op: CompareOp,
+        literal: Literal,
+    },
+    Not(Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    Contains,
+    NotContains,
+}
+
+/// Right-hand side of a comparison, parsed upfront into every type it might be compared as.
+#[derive(Debug, Clone, PartialEq)]
+struct Literal {
+    lowercase: String,
+    number: Option<f64>,
+    date: Option<NaiveDateTime>,
+}
+
+impl Literal {
+    fn new(text: &str) -> Self {
+        Self {
+            lowercase: text.to_lowercase(),
+            number: parse_number(text),
+            date: parse_date(text),
+        }
+    }
+}
+
+impl RowFilter {
+    /// Parse a filter expression, resolving column references against `column_names`.
+    /// Returns `None` for a blank input, which doesn't filter anything.
+    pub fn parse(
+        input: &str,
+        column_names: &[impl AsRef<str>],
+    ) -> Result<Option<Self>, FilterParseError> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Ok(None);
+        }
+
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+            input_len: input.chars().count(),
+            column_names,
+        };
+        let expr = parser.parse_or()?;
+        if let Some(token) = parser.tokens.get(parser.position) {
+            return Err(FilterParseError {
+                message: format!("Unexpected {}", token.kind.describe()),
+                offset: token.offset,
+            });
+        }
+        Ok(Some(Self { expr }))
+    }
+
+    /// Whether a row with `cols` columns and cells provided by `cell` passes the filter.
+    pub fn matches<'a>(&self, cols: usize, cell: impl Fn(AnyColumn) -> FilterCell<'a>) -> bool {
+        self.expr.matches(cols, &cell)
+    }
+}
+
+impl FilterExpr {
+    fn matches<'a>(&self, cols: usize, cell: &dyn Fn(AnyColumn) -> FilterCell<'a>) -> bool {
+        match self {
+            FilterExpr::AnyCellContains(text) => (0..cols)
+                .map(AnyColumn)
+                .any(|col| cell(col).lowercase.contains(text.as_str())),
+            FilterExpr::Compare { col, op, literal } => compare(cell(*col), *op, literal),
+            FilterExpr::Not(expr) => !expr.matches(cols, cell),
+            FilterExpr::And(lhs, rhs) => lhs.matches(cols, cell) && rhs.matches(cols, cell),
+            FilterExpr::Or(lhs, rhs) => lhs.matches(cols, cell) || rhs.matches(cols, cell),
+        }
+    }
+}
+
+fn compare(cell: FilterCell, op: CompareOp, literal: &Literal) -> bool {
+    match op {
+        CompareOp::Contains => cell.lowercase.contains(&literal.lowercase),
+        CompareOp::NotContains => !cell.lowercase.contains(&literal.lowercase),
+        CompareOp::Eq => order(cell, literal) == Ordering::Equal,
+        CompareOp::NotEq => order(cell, literal) != Ordering::Equal,
+        // Empty cells have no order, so they never pass range comparisons
+        _ if is_null(cell.text) => false,
+        CompareOp::Lt => order(cell, literal) == Ordering::Less,
+        CompareOp::LtEq => order(cell, literal) != Ordering::Greater,
+        CompareOp::Gt => order(cell, literal) == Ordering::Greater,
+        CompareOp::GtEq => order(cell, literal) != Ordering::Less,
+    }
+}
+
+fn order(cell: FilterCell, literal: &Literal) -> Ordering {
+    if let Some(literal_number) = literal.number
+        && let Some(number) = parse_number(cell.text)
+    {
+        return number.total_cmp(&literal_number);
+    }
+    if let Some(literal_date) = literal.date
+        && let Some(date) = parse_date(cell.text)
+    {
+        return date.cmp(&literal_date);
+    }
+    cell.lowercase.trim().cmp(literal.lowercase.as_str())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    /// Unquoted word: a column name or a value
+    Word(String),
+    /// `"double"` or `'single'` quoted text: a column name or a value
+    Quoted(String),
+    /// `` `backticked` `` column name
+    Column(String),
+    Op(CompareOp),
+    And,
+    Or,
+    Not,
+    OpenParen,
+    CloseParen,
+}
+
+impl TokenKind {
+    fn describe(&self) -> String {
+        match self {
+            TokenKind::Word(word) => format!("`{word}`"),
+            TokenKind::Quoted(text) => format!("\"{text}\""),
+            TokenKind::Column(column) => format!("column `{column}`"),
+            TokenKind::Op(_) => "operator".to_string(),
+            TokenKind::And => "`and`".to_string(),
+            TokenKind::Or => "`or`".to_string(),
+            TokenKind::Not => "`not`".to_string(),
+            TokenKind::OpenParen => "`(`".to_string(),
+            TokenKind::CloseParen => "`)`".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    /// Char offset of the token start
+    offset: usize,
+}
+
+fn is_word_char(c: char) -> bool {
+    !c.is_whitespace() && !"()<>=!~&|\"'`".contains(c)
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, FilterParseError> {
+    let chars = input.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let offset = i;
+        let next = chars.get(i + 1).copied();
+        let (kind, len) = match (c, next) {
+            _ if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            ('(', _) => (TokenKind::OpenParen, 1),
+            (')', _) => (TokenKind::CloseParen, 1),
+            ('&', Some('&')) => (TokenKind::And, 2),
+            ('|', Some('|')) => (TokenKind::Or, 2),
+            ('=', Some('=')) => (TokenKind::Op(CompareOp::Eq), 2),
+            ('=', _) => (TokenKind::Op(CompareOp::Eq), 1),
+            ('!', Some('=')) => (TokenKind::Op(CompareOp::NotEq), 2),
+            ('!', Some('~')) => (TokenKind::Op(CompareOp::NotContains), 2),
+            ('!', _) => (TokenKind::Not, 1),
+            ('<', Some('>')) => (TokenKind::Op(CompareOp::NotEq), 2),
+            ('<', Some('=')) => (TokenKind::Op(CompareOp::LtEq), 2),
+            ('<', _) => (TokenKind::Op(CompareOp::Lt), 1),
+            ('>', Some('=')) => (TokenKind::Op(CompareOp::GtEq), 2),
+            ('>', _) => (TokenKind::Op(CompareOp::Gt), 1),
+            ('~', _) => (TokenKind::Op(CompareOp::Contains), 1),
+            ('"' | '\'' | '`', _) => {
+                let Some(len) = chars[i + 1..].iter().position(|&ch| ch == c) else {
+                    return Err(FilterParseError {
+                        message: format!("Unclosed {c}"),
+                        offset,
+                    });
+                };
+                let text = chars[i + 1..i + 1 + len].iter().collect::<String>();
+                let kind = if c == '`' {
+                    TokenKind::Column(text)
+                } else {
+                    TokenKind::Quoted(text)
+                };
+                (kind, len + 2)
+            }
+            ('&' | '|', _) => {
+                return Err(FilterParseError {
+                    message: format!("Unexpected `{c}`, did you mean `{c}{c}`?"),
+                    offset,
+                });
+            }
+            _ => {
+                let len = chars[i..]
+                    .iter()
+                    .position(|&ch| !is_word_char(ch))
+                    .unwrap_or(chars.len() - i);
+                let word = chars[i..i + len].iter().collect::<String>();
+                let kind = match word.to_lowercase().as_str() {
+                    "and" => TokenKind::And,
+                    "or" => TokenKind::Or,
+                    "not" => TokenKind::Not,
+                    _ => TokenKind::Word(word),
+                };
+                (kind, len)
+            }
+        };
+        tokens.push(Token { kind, offset });
+        i += len;
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a, S> {
+    tokens: Vec<Token>,
+    position: usize,
+    input_len: usize,
+    column_names: &'a [S],
+}
+
+impl<S: AsRef<str>> Parser<'_, S> {
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.position).map(|token| &token.kind)
+    }
+
+    fn next_offset(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.input_len, |token| token.offset)
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpr, FilterParseError> {
+        let mut expr = self.parse_and()?;
+        while self.peek() == Some(&TokenKind::Or) {
+            self.position += 1;
+            expr = FilterExpr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr, FilterParseError> {
+        let mut expr = self.parse_unary()?;
+        loop {
+            match self.peek() {
+                Some(TokenKind::And) => self.position += 1,
+                // Implicit `and` between adjacent conditions
+                Some(
+                    TokenKind::Word(_)
+                    | TokenKind::Quoted(_)
+                    | TokenKind::Column(_)
+                    | TokenKind::Not
+                    | TokenKind::OpenParen,
+                ) => {}
+                _ => return Ok(expr),
+            }
+            expr = FilterExpr::And(Box::new(expr), Box::new(self.parse_unary()?));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterExpr, FilterParseError> {
+        let offset = self.next_offset();
+        let Some(kind) = self.peek().cloned() else {
+            return Err(FilterParseError {
+                message: "Expected a condition".to_string(),
+                offset,
+            });
+        };
+        self.position += 1;
+
+        let is_column = matches!(kind, TokenKind::Column(_));
+        match kind {
+            TokenKind::Not => Ok(FilterExpr::Not(Box::new(self.parse_unary()?))),
+            TokenKind::OpenParen => {
+                let expr = self.parse_or()?;
+                if self.peek() != Some(&TokenKind::CloseParen) {
+                    return Err(FilterParseError {
+                        message: "Expected `)`".to_string(),
+                        offset: self.next_offset(),
+                    });
+                }
+                self.position += 1;
+                Ok(expr)
+            }
+            TokenKind::Word(text) | TokenKind::Quoted(text) | TokenKind::Column(text) => {
+                if let Some(TokenKind::Op(op)) = self.peek().cloned() {
+                    self.position += 1;
+                    let col = self.resolve_column(&text, offset)?;
+                    let literal = self.parse_literal()?;
+                    Ok(FilterExpr::Compare { col, op, literal })
+                } else if is_column {
+                    Err(FilterParseError {
+                        message: format!("Expected an operator after column `{text}`"),
+                        offset: self.next_offset(),
+                    })
+                } else {
+                    Ok(FilterExpr::AnyCellContains(text.to_lowercase()))
+                }
+            }
+            TokenKind::Op(_) | TokenKind::And | TokenKind::Or | TokenKind::CloseParen => {
+                Err(FilterParseError {
+                    message: format!("Unexpected {}", kind.describe()),
+                    offset,
+                })
+            }
+        }
+    }
+
+    fn parse_literal(&mut self) -> Result<Literal, FilterParseError> {
+        match self.peek().cloned() {
+            Some(TokenKind::Word(text) | TokenKind::Quoted(text)) => {
+                self.position += 1;
+                Ok(Literal::new(&text))
+            }
+            _ => Err(FilterParseError {
+                message: "Expected a value to compare with".to_string(),
+                offset: self.next_offset(),
+            }),
+        }
+    }
+
+    /// Find a column by its header name, preferring an exact match over a case-insensitive one.
+    fn resolve_column(&self, name: &str, offset: usize) -> Result<AnyColumn, FilterParseError> {
+        let names = self.column_names.iter().map(|name| name.as_ref().trim());
+        names
+            .clone()
+            .position(|column_name| column_name == name)
+            .or_else(|| {
+                names
+                    .clone()
+                    .position(|column_name| column_name.eq_ignore_ascii_case(name))
+            })
+            .map(AnyColumn)
+            .ok_or_else(|| FilterParseError {
+                message: format!("Unknown column `{name}`"),
+                offset,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMNS: &[&str] = &["name", "price", "created", "unit price"];
+
+    fn filter(input: &str) -> RowFilter {
+        RowFilter::parse(input, COLUMNS)
+            .unwrap()
+            .expect("non-empty filter")
+    }
+
+    fn filter_rows<'a>(input: &str, rows: &[[&'a str; 4]]) -> Vec<[&'a str; 4]> {
+        let filter = filter(input);
+        rows.iter()
+            .filter(|row| {
+                let lowercase = row.map(str::to_lowercase);
+                filter.matches(row.len(), |col| FilterCell {
+                    text: row[*col],
+                    lowercase: &lowercase[*col],
+                })
+            })
+            .copied()
+            .collect()
+    }
+
+    const ROWS: &[[&str; 4]] = &[
+        ["Apple", "9", "2024-01-15", "0.5"],
+        ["Banana", "10", "2023-12-01", ""],
+        ["Cherry", "100", "2024-02-01", "2"],
+        ["apple pie", "", "", "12.5"],
+    ];
+
+    #[test]
+    fn test_blank_filter() {
+        assert_eq!(RowFilter::parse("   ", COLUMNS), Ok(None));
+    }
+
+    #[test]
+    fn test_numeric_comparison() {
+        // Textual comparison would put "9" after "10" and "100"
+        assert_eq!(filter_rows("price < 10", ROWS), vec![ROWS[0]]);
+        assert_eq!(filter_rows("price >= 10", ROWS), vec![ROWS[1], ROWS[2]]);
+        assert_eq!(
+            filter_rows("`unit price` > 1", ROWS),
+            vec![ROWS[2], ROWS[3]]
+        );
+    }
+
+    #[test]
+    fn test_date_comparison() {
+        assert_eq!(
+            filter_rows("created >= 2024-01-01", ROWS),
+            vec![ROWS[0], ROWS[2]]
+        );
+    }
+
+    #[test]
+    fn test_text_matching() {
+        assert_eq!(filter_rows("name = apple", ROWS), vec![ROWS[0]]);
+        assert_eq!(filter_rows("name ~ APPLE", ROWS), vec![ROWS[0], ROWS[3]]);
+        assert_eq!(filter_rows("name !~ apple", ROWS), vec![ROWS[1], ROWS[2]]);
+        assert_eq!(filter_rows("pie", ROWS), vec![ROWS[3]]);
+        assert_eq!(filter_rows("price = ''", ROWS), vec![ROWS[3]]);
+    }
+
+    #[test]
+    fn test_boolean_operators() {
+        assert_eq!(filter_rows("name ~ a and price > 9", ROWS), vec![ROWS[1]]);
+        assert_eq!(filter_rows("apple price > 5", ROWS), vec![ROWS[0]]);
+        assert_eq!(
+            filter_rows("price = 9 || name = cherry", ROWS),
+            vec![ROWS[0], ROWS[2]]
+        );
+        assert_eq!(
+            filter_rows("not (name ~ apple or price > 50)", ROWS),
+            vec![ROWS[1]]
+        );
+        assert_eq!(filter_rows("!pie && 'Unit Price' < 1", ROWS), vec![ROWS[0]]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input: &str| RowFilter::parse(input, COLUMNS).unwrap_err();
+
+        assert_eq!(
+            error("weight > 5"),
+            FilterParseError {
+                message: "Unknown column `weight`".to_string(),
+                offset: 0,
+            }
+        );
+        assert_eq!(error("price >").offset, 7);
+        assert_eq!(error("(price > 5").message, "Expected `)`");
+        assert_eq!(error("name = 'apple").offset, 7);
+        assert_eq!(error("price > 5 & name = a").offset, 10);
+        assert_eq!(error("price > 5 or").message, "Expected a condition");
+    }
+}
@@ -1,6 +1,11 @@
+use std::cmp::Ordering;
+
 use ui::table_row::TableRow;
 
-use crate::types::{AnyColumn, DataRow, TableCell};
+use crate::{
+    table_data_engine::typed_values::infer_column_type,
+    types::{AnyColumn, DataRow, TableCell},
+};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum SortDirection {
@@ -17,33 +22,42 @@ pub struct AppliedSorting {
     pub direction: SortDirection,
 }
 
+/// Sort rows by the values of the sorted column, compared according to the column's inferred type.
+/// Empty cells always go last, regardless of the direction.
 pub fn sort_data_rows(
     content_rows: &[TableRow<TableCell>],
-    mut data_row_ids: Vec<DataRow>,
+    data_row_ids: Vec<DataRow>,
     sorting: AppliedSorting,
 ) -> Vec<DataRow> {
-    data_row_ids.sort_by(|&a, &b| {
-        let row_a = &content_rows[*a];
-        let row_b = &content_rows[*b];
-
-        // TODO: Decide how to handle nulls (on top or on bottom)
-        let val_a = row_a
-            .get(sorting.col_idx)
-            .and_then(|tc| tc.display_value())
-            .map(|tc| tc.as_str())
-            .unwrap_or("");
-        let val_b = row_b
+    let cell_value = |data_row: DataRow| {
+        content_rows[*data_row]
             .get(sorting.col_idx)
             .and_then(|tc| tc.display_value())
             .map(|tc| tc.as_str())
-            .unwrap_or("");
+            .unwrap_or("")
+    };
+
+    let column_type = infer_column_type(data_row_ids.iter().map(|&row| cell_value(row)));
+    // Parse each value once upfront instead of on every comparison
+    let mut keyed_rows = data_row_ids
+        .into_iter()
+        .map(|row| (column_type.parse(cell_value(row)), row))
+        .collect::<Vec<_>>();
 
-        let cmp = val_a.cmp(val_b);
-        match sorting.direction {
-            SortDirection::Asc => cmp,
-            SortDirection::Desc => cmp.reverse(),
-        }
-    });
+    keyed_rows.sort_by(
+        |(val_a, _), (val_b, _)| match (val_a.is_null(), val_b.is_null()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => {
+                let cmp = val_a.total_cmp(val_b);
+                match sorting.direction {
+                    SortDirection::Asc => cmp,
+                    SortDirection::Desc => cmp.reverse(),
+                }
+            }
+        },
+    );
 
-    data_row_ids
+    keyed_rows.into_iter().map(|(_, row)| row).collect()
 }
@@ -0,0 +1,163 @@
+//! Typed interpretation of cell values, shared by sorting, filtering and column statistics.
+//!
+//! CSV has no types, so a column's type is inferred from its values: a column is numeric if all
+//! of its non-empty cells parse as numbers, a date column if they all parse as dates, and text
+//! otherwise. Empty cells are treated as nulls.
+
+use std::cmp::Ordering;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+
+const DATE_TIME_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+    "%Y/%m/%d %H:%M:%S",
+];
+
+const DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%Y/%m/%d", "%d.%m.%Y"];
+
+/// Type of the values in a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+    Number,
+    Date,
+    Text,
+}
+
+impl ColumnType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ColumnType::Number => "Number",
+            ColumnType::Date => "Date",
+            ColumnType::Text => "Text",
+        }
+    }
+
+    /// Interpret `value` as a value of this type.
+    /// Falls back to text for values that don't parse, so no cell is ever lost.
+    pub fn parse(self, value: &str) -> TypedValue<'_> {
+        if is_null(value) {
+            return TypedValue::Null;
+        }
+        let typed = match self {
+            ColumnType::Number => parse_number(value).map(TypedValue::Number),
+            ColumnType::Date => parse_date(value).map(TypedValue::Date),
+            ColumnType::Text => None,
+        };
+        typed.unwrap_or(TypedValue::Text(value))
+    }
+}
+
+/// Cell value interpreted according to its column type.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TypedValue<'a> {
+    Null,
+    Number(f64),
+    Date(NaiveDateTime),
+    Text(&'a str),
+}
+
+impl TypedValue<'_> {
+    pub fn is_null(&self) -> bool {
+        matches!(self, TypedValue::Null)
+    }
+
+    /// Total order over values. Values of different kinds are ordered as
+    /// numbers < dates < text < null.
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (TypedValue::Number(a), TypedValue::Number(b)) => a.total_cmp(b),
+            (TypedValue::Date(a), TypedValue::Date(b)) => a.cmp(b),
+            (TypedValue::Text(a), TypedValue::Text(b)) => a.cmp(b),
+            _ => self.kind_rank().cmp(&other.kind_rank()),
+        }
+    }
+
+    fn kind_rank(&self) -> u8 {
+        match self {
+            TypedValue::Number(_) => 0,
+            TypedValue::Date(_) => 1,
+            TypedValue::Text(_) => 2,
+            TypedValue::Null => 3,
+        }
+    }
+}
+
+pub fn is_null(value: &str) -> bool {
+    value.trim().is_empty()
+}
+
+pub fn parse_number(value: &str) -> Option<f64> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+}
+
+/// Parses RFC 3339 timestamps and common ISO-like date and date-time formats.
+pub fn parse_date(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    // Cheap rejection, as this runs for every cell of a column during type inference
+    if value.len() < 8 || !value.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    if let Ok(date_time) = DateTime::parse_from_rfc3339(value) {
+        return Some(date_time.naive_utc());
+    }
+    DATE_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            DATE_FORMATS
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+                .map(|date| date.and_time(NaiveTime::MIN))
+        })
+}
+
+/// Infer the type of a column from its values, ignoring nulls.
+/// Columns without any non-null values are text.
+pub fn infer_column_type<'a>(values: impl IntoIterator<Item = &'a str>) -> ColumnType {
+    let mut could_be_number = true;
+    let mut could_be_date = true;
+    let mut has_values = false;
+
+    for value in values {
+        if is_null(value) {
+            continue;
+        }
+        has_values = true;
+        // Numbers and dates are disjoint, so a number never needs to be parsed as a date
+        let is_number = parse_number(value).is_some();
+        could_be_number = could_be_number && is_number;
+        could_be_date = could_be_date && !is_number && parse_date(value).is_some();
+        if !could_be_number && !could_be_date {
+            return ColumnType::Text;
+        }
+    }
+
+    if !has_values {
+        ColumnType::Text
+    } else if could_be_number {
+        ColumnType::Number
+    } else {
+        ColumnType::Date
+    }
+}
+
+/// Formats a number for display without trailing zeros, e.g. `3` or `2.5`.
+pub fn format_number(number: f64) -> String {
+    if number.fract() == 0.0 && number.abs() < 1e15 {
+        format!("{number:.0}")
+    } else {
+        let formatted = format!("{number:.4}");
+        formatted
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+}
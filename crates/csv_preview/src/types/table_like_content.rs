@@ -12,6 +12,8 @@ pub struct TableLikeContent {
     pub rows: Vec<TableRow<TableCell>>,
     /// Follows the same indices as `rows`
     pub line_numbers: Vec<LineNumber>,
+    /// Field separator the content was parsed with, reused when writing edits back
+    pub delimiter: char,
 }
 
 impl Default for TableLikeContent {
@@ -21,6 +23,7 @@ impl Default for TableLikeContent {
             headers: TableRow::<TableCell>::from_vec(vec![], 0),
             rows: vec![],
             line_numbers: vec![],
+            delimiter: ',',
         }
     }
 }
@@ -384,6 +384,11 @@ pub trait DebugAdapter: 'static + Send + Sync {
 
     fn dap_schema(&self) -> serde_json::Value;
 
+    /// Whether this adapter can open a core dump given as `coreFile` in its configuration.
+    fn supports_core_dumps(&self) -> bool {
+        false
+    }
+
     fn label_for_child_session(&self, _args: &StartDebuggingRequestArguments) -> Option<String> {
         None
     }
@@ -27,6 +27,15 @@ pub struct DebuggerSettings {
     ///
     /// Default: true
     pub format_dap_log_messages: bool,
+    /// Whether inline values evaluate member access chains, such as `config.port` or `ptr->len`,
+    /// in addition to plain variables.
+    ///
+    /// Default: true
+    pub inline_value_expressions: bool,
+    /// The length at which a single inline value is truncated.
+    ///
+    /// Default: 100
+    pub inline_value_max_length: usize,
     /// The dock position of the debug panel
     ///
     /// Default: Bottom
@@ -45,6 +54,8 @@ impl Settings for DebuggerSettings {
             timeout: content.timeout.unwrap(),
             log_dap_communications: content.log_dap_communications.unwrap(),
             format_dap_log_messages: content.format_dap_log_messages.unwrap(),
+            inline_value_expressions: content.inline_value_expressions.unwrap(),
+            inline_value_max_length: content.inline_value_max_length.unwrap(),
             dock: content.dock.unwrap(),
         }
     }
@@ -45,6 +45,21 @@ impl CodeLldbDebugAdapter {
         })
     }
 
+    /// CodeLLDB has no core dump option; it loads one by replacing how the target and
+    /// process are created.
+    fn open_core_file(config: &mut serde_json::Map<String, Value>) {
+        let Some(core_file) = config.get("coreFile").and_then(Value::as_str) else {
+            return;
+        };
+        let mut command = format!("target create --core {core_file:?}");
+        if let Some(program) = config.get("program").and_then(Value::as_str) {
+            command.push_str(&format!(" {program:?}"));
+        }
+        config.insert("request".into(), "launch".into());
+        config.insert("targetCreateCommands".into(), json!([command]));
+        config.insert("processCreateCommands".into(), json!([]));
+    }
+
     async fn fetch_latest_adapter_version(
         &self,
         delegate: &Arc<dyn DapDelegate>,
@@ -133,6 +148,10 @@ impl DebugAdapter for CodeLldbDebugAdapter {
         })
     }
 
+    fn supports_core_dumps(&self) -> bool {
+        true
+    }
+
     fn dap_schema(&self) -> serde_json::Value {
         json!({
             "properties": {
@@ -278,6 +297,10 @@ impl DebugAdapter for CodeLldbDebugAdapter {
                     "type": "boolean",
                     "description": "Wait for the process to launch (MacOS only)",
                     "default": false
+                },
+                "coreFile": {
+                    "type": "string",
+                    "description": "Path to a core dump to inspect instead of running the program"
                 }
             },
             "required": ["request"],
@@ -382,6 +405,9 @@ impl DebugAdapter for CodeLldbDebugAdapter {
             command = Some(path);
         };
         let mut json_config = config.config.clone();
+        if let Some(config_obj) = json_config.as_object_mut() {
+            Self::open_core_file(config_obj);
+        }
 
         // Auto-detect Rust projects and add sourceLanguages if not present.
         // This enables panic breakpoints to work correctly with CodeLLDB.
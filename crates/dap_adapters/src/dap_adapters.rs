@@ -3,7 +3,9 @@ mod dart;
 mod gdb;
 mod go;
 mod javascript;
+mod lldb_dap;
 mod python;
+mod rr;
 
 #[cfg(test)]
 use std::path::PathBuf;
@@ -24,7 +26,9 @@ use gdb::GdbDebugAdapter;
 use go::GoDebugAdapter;
 use gpui::{App, BorrowAppContext};
 use javascript::JsDebugAdapter;
+use lldb_dap::LldbDapDebugAdapter;
 use python::PythonDebugAdapter;
+use rr::RrDebugAdapter;
 use serde_json::json;
 use task::{DebugScenario, ZedDebugConfig};
 
@@ -36,6 +40,8 @@ pub fn init(cx: &mut App) {
         registry.add_adapter(Arc::from(DartDebugAdapter));
         registry.add_adapter(Arc::from(GoDebugAdapter::default()));
         registry.add_adapter(Arc::from(GdbDebugAdapter));
+        registry.add_adapter(Arc::from(LldbDapDebugAdapter));
+        registry.add_adapter(Arc::from(RrDebugAdapter));
 
         #[cfg(any(test, feature = "test-support"))]
         {
@@ -50,12 +56,19 @@ mod test_mocks {
 
     pub(crate) struct MockDelegate {
         worktree_root: PathBuf,
+        /// Paths that `which` finds, matched by file name
+        binaries: Vec<PathBuf>,
     }
 
     impl MockDelegate {
         pub(crate) fn new() -> Arc<dyn adapters::DapDelegate> {
+            Self::with_binaries(&[])
+        }
+
+        pub(crate) fn with_binaries(binaries: &[&str]) -> Arc<dyn adapters::DapDelegate> {
             Arc::new(Self {
                 worktree_root: PathBuf::from("/tmp/test"),
+                binaries: binaries.iter().map(PathBuf::from).collect(),
             })
         }
     }
@@ -88,8 +101,11 @@ mod test_mocks {
 
         fn output_to_console(&self, _msg: String) {}
 
-        async fn which(&self, _command: &std::ffi::OsStr) -> Option<PathBuf> {
-            None
+        async fn which(&self, command: &std::ffi::OsStr) -> Option<PathBuf> {
+            self.binaries
+                .iter()
+                .find(|path| path.file_name() == Some(command))
+                .cloned()
         }
 
         async fn read_text_file(&self, _path: &util::rel_path::RelPath) -> Result<String> {
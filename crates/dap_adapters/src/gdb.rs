@@ -3,6 +3,7 @@ use async_trait::async_trait;
 use collections::HashMap;
 use dap::{StartDebuggingRequestArguments, adapters::DebugTaskDefinition};
 use gpui::AsyncApp;
+use serde_json::Value;
 use std::ffi::OsStr;
 use task::{DebugScenario, ZedDebugConfig};
 
@@ -15,6 +16,21 @@ impl GdbDebugAdapter {
     const ADAPTER_NAME: &'static str = "GDB";
 }
 
+/// GDB's debug adapter interface has no core dump option, so GDB opens the program and its core
+/// dump when it starts. The launch request then leaves the program out, since naming it there
+/// would load it again as a fresh process.
+fn open_core_file(config: &mut serde_json::Map<String, Value>, gdb_args: &mut Vec<String>) {
+    let Some(core_file) = config.get("coreFile").and_then(Value::as_str) else {
+        return;
+    };
+    let core_file = format!("--core={core_file}");
+    if let Some(Value::String(program)) = config.remove("program") {
+        gdb_args.push(format!("--se={program}"));
+    }
+    gdb_args.push(core_file);
+    config.insert("request".into(), "launch".into());
+}
+
 /// Ensures that "-i=dap" is present in the GDB argument list.
 fn ensure_dap_interface(mut gdb_args: Vec<String>) -> Vec<String> {
     if !gdb_args.iter().any(|arg| arg.trim() == "-i=dap") {
@@ -71,6 +87,10 @@ impl DebugAdapter for GdbDebugAdapter {
         })
     }
 
+    fn supports_core_dumps(&self) -> bool {
+        true
+    }
+
     fn dap_schema(&self) -> serde_json::Value {
         json!({
             "oneOf": [
@@ -131,6 +151,10 @@ impl DebugAdapter for GdbDebugAdapter {
                                     "type": "boolean",
                                     "description": "When true, GDB will set a temporary breakpoint at the program's first instruction, like the 'starti' command.",
                                     "default": false
+                                },
+                                "coreFile": {
+                                    "type": "string",
+                                    "description": "Path to a core dump to inspect instead of running the program. GDB opens it at startup, like the '--core' option."
                                 }
                             },
                             "required": ["program"]
@@ -212,7 +236,7 @@ impl DebugAdapter for GdbDebugAdapter {
         };
 
         // Arguments: use gdb_args from config if present, else user_args, else default
-        let gdb_args = {
+        let mut gdb_args = {
             let args = config
                 .config
                 .get("gdb_args")
@@ -232,6 +256,7 @@ impl DebugAdapter for GdbDebugAdapter {
             configuration
                 .entry("cwd")
                 .or_insert_with(|| delegate.worktree_root_path().to_string_lossy().into());
+            open_core_file(configuration, &mut gdb_args);
         }
 
         let mut base_env = delegate.shell_env().await;
@@ -257,9 +282,59 @@ impl DebugAdapter for GdbDebugAdapter {
             cwd: Some(delegate.worktree_root_path().to_path_buf()),
             connection: None,
             request_args: StartDebuggingRequestArguments {
-                request: self.request_kind(&config.config).await?,
+                request: self.request_kind(&configuration).await?,
                 configuration,
             },
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_gdb_opens_core_dumps_at_startup(cx: &mut gpui::TestAppContext) {
+        let adapter = GdbDebugAdapter;
+        let delegate = test_mocks::MockDelegate::new();
+        let task_def = DebugTaskDefinition {
+            label: "core dump".into(),
+            config: json!({
+                "request": "launch",
+                "program": "/tmp/test/build/service",
+                "coreFile": "/tmp/test/core.1234",
+                "cwd": "/tmp/test",
+                "gdb_path": "/usr/bin/gdb",
+            }),
+            tcp_connection: None,
+            adapter: GdbDebugAdapter::ADAPTER_NAME.into(),
+        };
+
+        let binary = adapter
+            .get_binary(&delegate, &task_def, None, None, None, &mut cx.to_async())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            binary.arguments,
+            [
+                "-i=dap",
+                "--se=/tmp/test/build/service",
+                "--core=/tmp/test/core.1234"
+            ]
+        );
+        assert!(matches!(
+            binary.request_args.request,
+            dap::StartDebuggingRequestArgumentsRequest::Launch
+        ));
+        assert_eq!(
+            binary.request_args.configuration,
+            json!({
+                "request": "launch",
+                "coreFile": "/tmp/test/core.1234",
+                "cwd": "/tmp/test",
+                "gdb_path": "/usr/bin/gdb",
+            })
+        );
+    }
+}
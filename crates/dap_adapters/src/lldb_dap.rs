@@ -0,0 +1,453 @@
+use std::{ffi::OsStr, ops::RangeInclusive, path::PathBuf};
+
+use anyhow::{Context as _, Result};
+use async_trait::async_trait;
+use collections::HashMap;
+use dap::adapters::DebugTaskDefinition;
+use gpui::AsyncApp;
+use serde_json::Value;
+use task::{DebugRequest, DebugScenario, ZedDebugConfig};
+
+use crate::*;
+
+/// LLVM's own debug adapter, which ships with LLDB rather than being downloaded.
+#[derive(Default)]
+pub(crate) struct LldbDapDebugAdapter;
+
+impl LldbDapDebugAdapter {
+    const ADAPTER_NAME: &'static str = "lldb-dap";
+    /// The adapter's name before LLVM 18.
+    const LEGACY_BINARY_NAME: &'static str = "lldb-vscode";
+    /// The LLVM major versions whose versioned binaries (e.g. `lldb-dap-18`, as installed by
+    /// distribution packages) are looked up, newest first.
+    const LLVM_VERSIONS: RangeInclusive<u32> = 11..=30;
+    /// The first LLVM major version that ships the adapter as `lldb-dap`.
+    const FIRST_LLDB_DAP_VERSION: u32 = 18;
+
+    fn binary_names() -> impl Iterator<Item = String> {
+        let versioned = Self::LLVM_VERSIONS.rev().map(|version| {
+            let name = if version >= Self::FIRST_LLDB_DAP_VERSION {
+                Self::ADAPTER_NAME
+            } else {
+                Self::LEGACY_BINARY_NAME
+            };
+            format!("{name}-{version}")
+        });
+        [Self::ADAPTER_NAME, Self::LEGACY_BINARY_NAME]
+            .into_iter()
+            .map(str::to_string)
+            .chain(versioned)
+    }
+
+    /// Looks for the adapter in the binary directory of the LLVM installation reported by
+    /// `llvm-config`, which isn't always on the PATH.
+    async fn find_in_llvm_bindir(delegate: &Arc<dyn DapDelegate>) -> Option<PathBuf> {
+        let llvm_config = delegate.which(OsStr::new("llvm-config")).await?;
+        let output = util::command::new_command(&llvm_config)
+            .arg("--bindir")
+            .output()
+            .await
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let bindir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+        for name in [Self::ADAPTER_NAME, Self::LEGACY_BINARY_NAME] {
+            let path = bindir.join(name);
+            if delegate.fs().is_file(&path).await {
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    async fn find_binary(
+        delegate: &Arc<dyn DapDelegate>,
+        config: &Value,
+        user_installed_path: Option<PathBuf>,
+    ) -> Result<String> {
+        if let Some(path) = config.get("lldb_dap_path").and_then(Value::as_str) {
+            return Ok(path.to_string());
+        }
+        if let Some(path) = user_installed_path {
+            return Ok(path.to_string_lossy().into_owned());
+        }
+        for name in Self::binary_names() {
+            if let Some(path) = delegate.which(OsStr::new(&name)).await {
+                return Ok(path.to_string_lossy().into_owned());
+            }
+        }
+        if let Some(path) = Self::find_in_llvm_bindir(delegate).await {
+            return Ok(path.to_string_lossy().into_owned());
+        }
+
+        // On macOS, lldb-dap is part of the Xcode toolchain and isn't on the PATH.
+        if cfg!(target_os = "macos") && !delegate.is_headless() {
+            let output = util::command::new_command("xcrun")
+                .args(["--find", Self::ADAPTER_NAME])
+                .output()
+                .await?;
+            if output.status.success() {
+                let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                if !path.is_empty() {
+                    return Ok(path);
+                }
+            }
+        }
+
+        anyhow::bail!(
+            "Could not find {} in path. Install LLDB, or set `lldb_dap_path` in your debug configuration",
+            Self::ADAPTER_NAME
+        )
+    }
+
+    /// Rewrites the fields that other adapters (and older lldb-dap releases) spell differently
+    /// into the forms that every lldb-dap release accepts.
+    fn normalize_config(config: &mut serde_json::Map<String, Value>) -> Result<()> {
+        // CodeLLDB's `{ "from": "to" }` source map is a list of pairs for lldb-dap.
+        if let Some(Value::Object(source_map)) = config.get("sourceMap") {
+            let pairs = source_map
+                .iter()
+                .filter_map(|(from, to)| Some(json!([from, to.as_str()?])))
+                .collect::<Vec<_>>();
+            config.insert("sourceMap".into(), Value::Array(pairs));
+        }
+
+        // lldb-dap expects the environment as `NAME=value` strings.
+        if let Some(Value::Object(env)) = config.get("env") {
+            let env = env
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some(Value::from(format!("{name}={}", value.as_str()?)))
+                })
+                .collect::<Vec<_>>();
+            config.insert("env".into(), Value::Array(env));
+        }
+
+        // lldb-dap opens core dumps through `attach`.
+        if config.get("coreFile").is_some_and(Value::is_string) {
+            config.insert("request".into(), "attach".into());
+        }
+
+        // CodeLLDB accepts arguments as a single string, split like a shell command line.
+        if let Some(Value::String(args)) = config.get("args") {
+            let args = shlex::split(args)
+                .with_context(|| format!("Failed to split `args` into arguments: {args}"))?;
+            config.insert("args".into(), args.into());
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl DebugAdapter for LldbDapDebugAdapter {
+    fn name(&self) -> DebugAdapterName {
+        DebugAdapterName(Self::ADAPTER_NAME.into())
+    }
+
+    async fn config_from_zed_format(&self, zed_scenario: ZedDebugConfig) -> Result<DebugScenario> {
+        let mut configuration = json!({
+            "request": match zed_scenario.request {
+                DebugRequest::Launch(_) => "launch",
+                DebugRequest::Attach(_) => "attach",
+            },
+        });
+        let map = configuration.as_object_mut().unwrap();
+        match &zed_scenario.request {
+            DebugRequest::Attach(attach) => {
+                map.insert("pid".into(), attach.process_id.into());
+            }
+            DebugRequest::Launch(launch) => {
+                map.insert("program".into(), launch.program.clone().into());
+
+                if !launch.args.is_empty() {
+                    map.insert("args".into(), launch.args.clone().into());
+                }
+                if !launch.env.is_empty() {
+                    map.insert("env".into(), launch.env_json());
+                }
+                if let Some(stop_on_entry) = zed_scenario.stop_on_entry {
+                    map.insert("stopOnEntry".into(), stop_on_entry.into());
+                }
+                if let Some(cwd) = launch.cwd.as_ref() {
+                    map.insert("cwd".into(), cwd.to_string_lossy().into_owned().into());
+                }
+            }
+        }
+
+        Ok(DebugScenario {
+            adapter: zed_scenario.adapter,
+            label: zed_scenario.label,
+            config: configuration,
+            build: None,
+            tcp_connection: None,
+        })
+    }
+
+    fn supports_core_dumps(&self) -> bool {
+        true
+    }
+
+    fn dap_schema(&self) -> serde_json::Value {
+        json!({
+            "properties": {
+                "request": {
+                    "type": "string",
+                    "enum": ["attach", "launch"],
+                    "description": "Debug adapter request type"
+                },
+                "program": {
+                    "type": "string",
+                    "description": "Path to the program to debug or attach to"
+                },
+                "args": {
+                    "type": ["array", "string"],
+                    "description": "Program arguments, as an array or as a string split like a shell command line"
+                },
+                "cwd": {
+                    "type": "string",
+                    "description": "Program working directory"
+                },
+                "env": {
+                    "type": ["object", "array"],
+                    "description": "Additional environment variables, as an object or as `NAME=value` strings"
+                },
+                "stopOnEntry": {
+                    "type": "boolean",
+                    "description": "Automatically stop debuggee after launch",
+                    "default": false
+                },
+                "pid": {
+                    "type": ["integer", "string"],
+                    "description": "Process id to attach to"
+                },
+                "waitFor": {
+                    "type": "boolean",
+                    "description": "Wait for a process named after `program` to launch, then attach to it",
+                    "default": false
+                },
+                "coreFile": {
+                    "type": "string",
+                    "description": "Path to a core dump to debug. Use with the `attach` request and `program`"
+                },
+                "sourceMap": {
+                    "type": ["object", "array"],
+                    "description": "Source path remapping between the build machine and the local machine, as an object or a list of [from, to] pairs"
+                },
+                "sourcePath": {
+                    "type": "string",
+                    "description": "Directory to resolve relative source paths against"
+                },
+                "debuggerRoot": {
+                    "type": "string",
+                    "description": "Working directory for lldb-dap itself, used for relative paths in debug information"
+                },
+                "initCommands": {
+                    "type": "array",
+                    "description": "LLDB commands executed upon debugger startup",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "preRunCommands": {
+                    "type": "array",
+                    "description": "LLDB commands executed just before the program is launched or attached to",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "launchCommands": {
+                    "type": "array",
+                    "description": "LLDB commands that launch the program, replacing the default launch",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "attachCommands": {
+                    "type": "array",
+                    "description": "LLDB commands that attach to the program, replacing the default attach",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "stopCommands": {
+                    "type": "array",
+                    "description": "LLDB commands executed each time the program stops",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "exitCommands": {
+                    "type": "array",
+                    "description": "LLDB commands executed when the program exits",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "terminateCommands": {
+                    "type": "array",
+                    "description": "LLDB commands executed when the debugging session ends",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "lldb_dap_path": {
+                    "type": "string",
+                    "description": "Alternative path to the lldb-dap executable, if the one in standard path is not desirable"
+                }
+            },
+            "required": ["request"],
+            "allOf": [
+                {
+                    "if": {
+                        "properties": {
+                            "request": {
+                                "enum": ["launch"]
+                            }
+                        }
+                    },
+                    "then": {
+                        "oneOf": [
+                            {
+                                "required": ["program"]
+                            },
+                            {
+                                "required": ["launchCommands"]
+                            }
+                        ]
+                    }
+                },
+                {
+                    "if": {
+                        "properties": {
+                            "request": {
+                                "enum": ["attach"]
+                            }
+                        }
+                    },
+                    "then": {
+                        "oneOf": [
+                            {
+                                "required": ["pid"]
+                            },
+                            {
+                                "required": ["program"]
+                            },
+                            {
+                                "required": ["attachCommands"]
+                            }
+                        ]
+                    }
+                }
+            ]
+        })
+    }
+
+    async fn get_binary(
+        &self,
+        delegate: &Arc<dyn DapDelegate>,
+        config: &DebugTaskDefinition,
+        user_installed_path: Option<PathBuf>,
+        user_args: Option<Vec<String>>,
+        user_env: Option<HashMap<String, String>>,
+        _: &mut AsyncApp,
+    ) -> Result<DebugAdapterBinary> {
+        let command = Self::find_binary(delegate, &config.config, user_installed_path).await?;
+
+        let mut configuration = config.config.clone();
+        let obj = configuration
+            .as_object_mut()
+            .context("lldb-dap configuration is not a valid json object")?;
+        obj.remove("lldb_dap_path");
+        obj.entry("name")
+            .or_insert(Value::String(config.label.to_string()));
+        obj.entry("cwd")
+            .or_insert(delegate.worktree_root_path().to_string_lossy().into());
+        Self::normalize_config(obj)?;
+
+        let request = self.request_kind(&configuration).await?;
+
+        Ok(DebugAdapterBinary {
+            command: Some(command),
+            arguments: user_args.unwrap_or_default(),
+            envs: user_env.unwrap_or_default(),
+            cwd: Some(delegate.worktree_root_path().to_path_buf()),
+            connection: None,
+            request_args: dap::StartDebuggingRequestArguments {
+                request,
+                configuration,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_lldb_dap_translates_codelldb_style_config(cx: &mut gpui::TestAppContext) {
+        let adapter = LldbDapDebugAdapter;
+        let delegate = test_mocks::MockDelegate::new();
+        let task_def = DebugTaskDefinition {
+            label: "core dump".into(),
+            config: json!({
+                "request": "attach",
+                "program": "/tmp/test/build/service",
+                "coreFile": "/tmp/test/core.1234",
+                "args": "--port 8080 --name 'core service'",
+                "env": { "RUST_LOG": "debug" },
+                "sourceMap": { "/build/src": "/tmp/test/src" },
+                "lldb_dap_path": "/usr/bin/lldb-dap",
+            }),
+            tcp_connection: None,
+            adapter: LldbDapDebugAdapter::ADAPTER_NAME.into(),
+        };
+
+        let binary = adapter
+            .get_binary(&delegate, &task_def, None, None, None, &mut cx.to_async())
+            .await
+            .unwrap();
+
+        assert_eq!(binary.command.as_deref(), Some("/usr/bin/lldb-dap"));
+        assert!(matches!(
+            binary.request_args.request,
+            dap::StartDebuggingRequestArgumentsRequest::Attach
+        ));
+        assert_eq!(
+            binary.request_args.configuration,
+            json!({
+                "request": "attach",
+                "name": "core dump",
+                "cwd": "/tmp/test",
+                "program": "/tmp/test/build/service",
+                "coreFile": "/tmp/test/core.1234",
+                "args": ["--port", "8080", "--name", "core service"],
+                "env": ["RUST_LOG=debug"],
+                "sourceMap": [["/build/src", "/tmp/test/src"]],
+            })
+        );
+    }
+
+    #[gpui::test]
+    async fn test_lldb_dap_finds_versioned_binary(cx: &mut gpui::TestAppContext) {
+        let adapter = LldbDapDebugAdapter;
+        let delegate = test_mocks::MockDelegate::with_binaries(&["/usr/bin/lldb-dap-19"]);
+        let task_def = DebugTaskDefinition {
+            label: "launch".into(),
+            config: json!({
+                "request": "launch",
+                "program": "/tmp/test/build/service",
+            }),
+            tcp_connection: None,
+            adapter: LldbDapDebugAdapter::ADAPTER_NAME.into(),
+        };
+
+        let binary = adapter
+            .get_binary(&delegate, &task_def, None, None, None, &mut cx.to_async())
+            .await
+            .unwrap();
+
+        assert_eq!(binary.command.as_deref(), Some("/usr/bin/lldb-dap-19"));
+    }
+}
@@ -0,0 +1,303 @@
+use anyhow::{Context as _, Result, bail};
+use async_trait::async_trait;
+use collections::HashMap;
+use dap::{
+    StartDebuggingRequestArguments, StartDebuggingRequestArgumentsRequest,
+    adapters::DebugTaskDefinition,
+};
+use gpui::AsyncApp;
+use std::ffi::OsStr;
+use task::{DebugScenario, TcpArgumentsTemplate, ZedDebugConfig};
+
+use crate::*;
+
+/// Replays a trace recorded with `rr record` under GDB's DAP interface.
+///
+/// `rr replay` serves the recording over the GDB remote protocol, and GDB attaches to it as
+/// a remote target. Since a replay can run backwards, GDB supports `stepBack` and
+/// `reverseContinue` for these sessions.
+#[derive(Default)]
+pub(crate) struct RrDebugAdapter;
+
+impl RrDebugAdapter {
+    const ADAPTER_NAME: &'static str = "rr";
+
+    /// Starts `rr replay` in the background and waits until it serves the trace, then runs GDB
+    /// with its stdio carrying the DAP traffic. rr is killed when GDB exits or the script is
+    /// killed, so that no replay outlives its session. rr's own output goes to stderr.
+    const LAUNCH_SCRIPT: &'static str = r#"rr="$1"; port="$2"; trace="$3"; shift 3
+log=$(mktemp) || exit 1
+if [ -n "$trace" ]; then
+  "$rr" replay -s "$port" "$trace" </dev/null >"$log" &
+else
+  "$rr" replay -s "$port" </dev/null >"$log" &
+fi
+rr_pid=$!
+trap 'kill "$rr_pid" 2>/dev/null; rm -f "$log"' EXIT
+trap 'exit 129' HUP
+trap 'exit 130' INT
+trap 'exit 143' TERM
+# Kills rr even if this script is killed without running its traps
+shell_pid=$$
+(while kill -0 "$shell_pid" 2>/dev/null; do sleep 1; done; kill "$rr_pid" 2>/dev/null) </dev/null >/dev/null 2>&1 &
+# rr prints how to connect to it once it listens on the port
+until grep -q ":$port" "$log"; do
+  if ! kill -0 "$rr_pid" 2>/dev/null; then
+    cat "$log" >&2
+    echo "rr replay exited before serving the trace" >&2
+    exit 1
+  fi
+  sleep 0.1
+done
+cat "$log" >&2
+"$@""#;
+
+    async fn find_binary(
+        delegate: &Arc<dyn DapDelegate>,
+        config: &serde_json::Value,
+        key: &str,
+        name: &str,
+        user_installed_path: Option<std::path::PathBuf>,
+    ) -> Result<String> {
+        if let Some(path) = config.get(key).and_then(|path| path.as_str()) {
+            return Ok(path.to_string());
+        }
+        if let Some(path) = user_installed_path
+            .filter(|path| path.exists())
+            .and_then(|path| path.to_str().map(|path| path.to_string()))
+        {
+            return Ok(path);
+        }
+        delegate
+            .which(OsStr::new(name))
+            .await
+            .and_then(|path| path.to_str().map(|path| path.to_string()))
+            .with_context(|| format!("Could not find {name} in path"))
+    }
+}
+
+#[async_trait(?Send)]
+impl DebugAdapter for RrDebugAdapter {
+    fn name(&self) -> DebugAdapterName {
+        DebugAdapterName(Self::ADAPTER_NAME.into())
+    }
+
+    async fn config_from_zed_format(&self, zed_scenario: ZedDebugConfig) -> Result<DebugScenario> {
+        let mut obj = serde_json::Map::default();
+
+        match &zed_scenario.request {
+            dap::DebugRequest::Attach(_) => {
+                bail!("rr can only replay recorded traces, not attach to running processes");
+            }
+            dap::DebugRequest::Launch(launch) => {
+                obj.insert("request".into(), "launch".into());
+                obj.insert("program".into(), launch.program.clone().into());
+                if let Some(cwd) = launch.cwd.as_ref() {
+                    obj.insert("cwd".into(), cwd.to_string_lossy().into_owned().into());
+                }
+            }
+        }
+
+        Ok(DebugScenario {
+            adapter: zed_scenario.adapter,
+            label: zed_scenario.label,
+            build: None,
+            config: serde_json::Value::Object(obj),
+            tcp_connection: None,
+        })
+    }
+
+    fn dap_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "required": ["request"],
+            "properties": {
+                "request": {
+                    "type": "string",
+                    "enum": ["launch"],
+                    "description": "Replay a recorded trace"
+                },
+                "program": {
+                    "type": "string",
+                    "description": "The recorded program, used by GDB to load symbols. Defaults to the executable stored in the trace."
+                },
+                "trace": {
+                    "type": "string",
+                    "description": "The trace directory to replay. Defaults to the latest trace recorded with `rr record`."
+                },
+                "port": {
+                    "type": "number",
+                    "description": "The port `rr replay` serves the trace on. Defaults to an unused port."
+                },
+                "cwd": {
+                    "type": "string",
+                    "description": "Working directory for GDB."
+                },
+                "rr_path": {
+                    "type": "string",
+                    "description": "Alternative path to the rr executable, if the one in standard path is not desirable"
+                },
+                "gdb_path": {
+                    "type": "string",
+                    "description": "Alternative path to the GDB executable, if the one in standard path is not desirable"
+                },
+                "gdb_args": {
+                    "type": "array",
+                    "items": {
+                        "type": "string"
+                    },
+                    "description": "additional arguments given to GDB at startup",
+                    "default": []
+                }
+            }
+        })
+    }
+
+    async fn get_binary(
+        &self,
+        delegate: &Arc<dyn DapDelegate>,
+        config: &DebugTaskDefinition,
+        user_installed_path: Option<std::path::PathBuf>,
+        user_args: Option<Vec<String>>,
+        user_env: Option<HashMap<String, String>>,
+        _: &mut AsyncApp,
+    ) -> Result<DebugAdapterBinary> {
+        let rr_path = Self::find_binary(
+            delegate,
+            &config.config,
+            "rr_path",
+            "rr",
+            user_installed_path,
+        )
+        .await?;
+        let gdb_path = Self::find_binary(delegate, &config.config, "gdb_path", "gdb", None).await?;
+
+        let gdb_args = config
+            .config
+            .get("gdb_args")
+            .and_then(|args| args.as_array())
+            .map(|args| {
+                args.iter()
+                    .filter_map(|arg| arg.as_str().map(|arg| arg.to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .or(user_args)
+            .unwrap_or_default();
+
+        let trace = config
+            .config
+            .get("trace")
+            .and_then(|trace| trace.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let port = config
+            .config
+            .get("port")
+            .and_then(|port| port.as_u64())
+            .and_then(|port| u16::try_from(port).ok());
+        let (host, port, _) = dap::configure_tcp_connection(TcpArgumentsTemplate {
+            host: None,
+            port,
+            timeout: None,
+        })
+        .await?;
+
+        let mut arguments = vec![
+            "-c".to_string(),
+            Self::LAUNCH_SCRIPT.to_string(),
+            "sh".to_string(),
+            rr_path,
+            port.to_string(),
+            trace,
+            gdb_path,
+        ];
+        arguments.extend(gdb_args);
+        if !arguments.iter().any(|arg| arg.trim() == "-i=dap") {
+            arguments.push("-i=dap".to_string());
+        }
+
+        // GDB's DAP interface only replays through `attach`, connecting to rr as a remote target.
+        let mut configuration = serde_json::Map::default();
+        configuration.insert("request".into(), "attach".into());
+        configuration.insert("target".into(), format!("{host}:{port}").into());
+        if let Some(program) = config.config.get("program") {
+            configuration.insert("program".into(), program.clone());
+        }
+
+        let mut envs = delegate.shell_env().await;
+        envs.extend(user_env.unwrap_or_default());
+
+        let cwd = config
+            .config
+            .get("cwd")
+            .and_then(|cwd| cwd.as_str())
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(|| delegate.worktree_root_path().to_path_buf());
+
+        Ok(DebugAdapterBinary {
+            command: Some("sh".into()),
+            arguments,
+            envs,
+            cwd: Some(cwd),
+            connection: None,
+            request_args: StartDebuggingRequestArguments {
+                request: StartDebuggingRequestArgumentsRequest::Attach,
+                configuration: serde_json::Value::Object(configuration),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_rr_replays_through_gdb_attach(cx: &mut gpui::TestAppContext) {
+        let adapter = RrDebugAdapter;
+        let delegate = test_mocks::MockDelegate::new();
+        let task_def = DebugTaskDefinition {
+            label: "replay".into(),
+            config: json!({
+                "request": "launch",
+                "program": "/tmp/test/build/service",
+                "trace": "/tmp/rr/service-0",
+                "port": 50505,
+                "rr_path": "/usr/bin/rr",
+                "gdb_path": "/usr/bin/gdb",
+            }),
+            tcp_connection: None,
+            adapter: RrDebugAdapter::ADAPTER_NAME.into(),
+        };
+
+        let binary = adapter
+            .get_binary(&delegate, &task_def, None, None, None, &mut cx.to_async())
+            .await
+            .unwrap();
+
+        assert_eq!(binary.command.as_deref(), Some("sh"));
+        assert_eq!(
+            &binary.arguments[2..],
+            [
+                "sh",
+                "/usr/bin/rr",
+                "50505",
+                "/tmp/rr/service-0",
+                "/usr/bin/gdb",
+                "-i=dap"
+            ]
+        );
+        assert!(matches!(
+            binary.request_args.request,
+            StartDebuggingRequestArgumentsRequest::Attach
+        ));
+        assert_eq!(
+            binary.request_args.configuration,
+            json!({
+                "request": "attach",
+                "target": "127.0.0.1:50505",
+                "program": "/tmp/test/build/service",
+            })
+        );
+    }
+}
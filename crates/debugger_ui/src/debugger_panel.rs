@@ -6,8 +6,9 @@ use crate::session::running::breakpoint_list::BreakpointList;
 use crate::{
     ClearAllBreakpoints, Continue, CopyDebugAdapterArguments, Detach, FocusBreakpointList,
     FocusConsole, FocusFrames, FocusLoadedSources, FocusModules, FocusTerminal, FocusVariables,
-    NewProcessModal, NewProcessMode, Pause, RerunSession, StepInto, StepOut, StepOver, Stop,
-    ToggleExpandItem, ToggleSessionPicker, ToggleThreadPicker, persistence, spawn_task_or_modal,
+    NewProcessModal, NewProcessMode, Pause, RerunSession, ReverseContinue, StepBack, StepInto,
+    StepOut, StepOver, Stop, ToggleExpandItem, ToggleSessionPicker, ToggleThreadPicker,
+    persistence, spawn_task_or_modal,
 };
 use anyhow::{Context as _, Result, anyhow};
 use collections::IndexMap;
@@ -697,6 +698,13 @@ impl DebugPanel {
                                     let capabilities = running_state.read(cx).capabilities(cx);
                                     let supports_detach =
                                         running_state.read(cx).session().read(cx).is_attached();
+                                    // Core dumps can be inspected, but not resumed.
+                                    let can_step = thread_status == ThreadStatus::Stopped
+                                        && !running_state
+                                            .read(cx)
+                                            .session()
+                                            .read(cx)
+                                            .is_post_mortem();
 
                                     this.map(|this| {
                                         if thread_status == ThreadStatus::Running {
@@ -735,7 +743,7 @@ impl DebugPanel {
                                                     running_state,
                                                     |this, _, _window, cx| this.continue_thread(cx),
                                                 ))
-                                                .disabled(thread_status != ThreadStatus::Stopped)
+                                                .disabled(!can_step)
                                                 .tooltip({
                                                     let focus_handle = focus_handle.clone();
                                                     move |_window, cx| {
@@ -759,7 +767,7 @@ impl DebugPanel {
                                                     this.step_over(cx);
                                                 },
                                             ))
-                                            .disabled(thread_status != ThreadStatus::Stopped)
+                                            .disabled(!can_step)
                                             .tooltip({
                                                 let focus_handle = focus_handle.clone();
                                                 move |_window, cx| {
@@ -781,7 +789,7 @@ impl DebugPanel {
                                                     this.step_in(cx);
                                                 },
                                             ))
-                                            .disabled(thread_status != ThreadStatus::Stopped)
+                                            .disabled(!can_step)
                                             .tooltip({
                                                 let focus_handle = focus_handle.clone();
                                                 move |_window, cx| {
@@ -803,7 +811,7 @@ impl DebugPanel {
                                                     this.step_out(cx);
                                                 },
                                             ))
-                                            .disabled(thread_status != ThreadStatus::Stopped)
+                                            .disabled(!can_step)
                                             .tooltip({
                                                 let focus_handle = focus_handle.clone();
                                                 move |_window, cx| {
@@ -816,6 +824,61 @@ impl DebugPanel {
                                                 }
                                             }),
                                     )
+                                    .when(
+                                        capabilities.supports_step_back.unwrap_or_default(),
+                                        |this| {
+                                            this.child(
+                                                IconButton::new(
+                                                    "step-back",
+                                                    IconName::DebugStepBack,
+                                                )
+                                                .icon_size(IconSize::Small)
+                                                .on_click(window.listener_for(
+                                                    running_state,
+                                                    |this, _, _window, cx| {
+                                                        this.step_back(cx);
+                                                    },
+                                                ))
+                                                .disabled(!can_step)
+                                                .tooltip({
+                                                    let focus_handle = focus_handle.clone();
+                                                    move |_window, cx| {
+                                                        Tooltip::for_action_in(
+                                                            "Step Back",
+                                                            &StepBack,
+                                                            &focus_handle,
+                                                            cx,
+                                                        )
+                                                    }
+                                                }),
+                                            )
+                                            .child(
+                                                IconButton::new(
+                                                    "debug-reverse-continue",
+                                                    IconName::DebugReverseContinue,
+                                                )
+                                                .icon_size(IconSize::Small)
+                                                .on_click(window.listener_for(
+                                                    running_state,
+                                                    |this, _, _window, cx| {
+                                                        this.reverse_continue_thread(cx);
+                                                    },
+                                                ))
+                                                .disabled(!can_step)
+                                                .tooltip({
+                                                    let focus_handle = focus_handle.clone();
+                                                    move |_window, cx| {
+                                                        Tooltip::for_action_in(
+                                                            "Reverse Continue",
+                                                            &ReverseContinue,
+                                                            &focus_handle,
+                                                            cx,
+                                                        )
+                                                    }
+                                                }),
+                                            )
+                                        },
+                                    )
                                     .child(Divider::vertical())
                                     .child(
                                         IconButton::new("debug-restart", IconName::RotateCcw)
@@ -49,6 +49,8 @@ actions!(
         StepOut,
         /// Steps back to the previous statement.
         StepBack,
+        /// Runs the program backwards until the previous breakpoint.
+        ReverseContinue,
         /// Stops the debugging session.
         Stop,
         /// Toggles whether to ignore all breakpoints.
@@ -172,6 +174,7 @@ pub fn init(cx: &mut App) {
                 let caps = running_state.capabilities(cx);
                 let supports_step_back = caps.supports_step_back.unwrap_or_default();
                 let supports_detach = running_state.session().read(cx).is_attached();
+                let is_post_mortem = running_state.session().read(cx).is_post_mortem();
                 let status = running_state.thread_status(cx);
 
                 let active_item = active_item.downgrade();
@@ -183,40 +186,53 @@ pub fn init(cx: &mut App) {
                             .ok();
                     })
                 })
-                .when(status == Some(ThreadStatus::Stopped), |div| {
-                    div.on_action({
-                        let active_item = active_item.clone();
-                        move |_: &StepInto, _, cx| {
-                            active_item.update(cx, |item, cx| item.step_in(cx)).ok();
-                        }
-                    })
-                    .on_action({
-                        let active_item = active_item.clone();
-                        move |_: &StepOver, _, cx| {
-                            active_item.update(cx, |item, cx| item.step_over(cx)).ok();
-                        }
-                    })
-                    .on_action({
-                        let active_item = active_item.clone();
-                        move |_: &StepOut, _, cx| {
-                            active_item.update(cx, |item, cx| item.step_out(cx)).ok();
-                        }
-                    })
-                    .when(supports_step_back, |div| {
-                        let active_item = active_item.clone();
-                        div.on_action(move |_: &StepBack, _, cx| {
-                            active_item.update(cx, |item, cx| item.step_back(cx)).ok();
+                .when(
+                    status == Some(ThreadStatus::Stopped) && !is_post_mortem,
+                    |div| {
+                        div.on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepInto, _, cx| {
+                                active_item.update(cx, |item, cx| item.step_in(cx)).ok();
+                            }
                         })
-                    })
-                    .on_action({
-                        let active_item = active_item.clone();
-                        move |_: &Continue, _, cx| {
-                            active_item
-                                .update(cx, |item, cx| item.continue_thread(cx))
-                                .ok();
-                        }
-                    })
-                })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepOver, _, cx| {
+                                active_item.update(cx, |item, cx| item.step_over(cx)).ok();
+                            }
+                        })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepOut, _, cx| {
+                                active_item.update(cx, |item, cx| item.step_out(cx)).ok();
+                            }
+                        })
+                        .when(supports_step_back, |div| {
+                            div.on_action({
+                                let active_item = active_item.clone();
+                                move |_: &StepBack, _, cx| {
+                                    active_item.update(cx, |item, cx| item.step_back(cx)).ok();
+                                }
+                            })
+                            .on_action({
+                                let active_item = active_item.clone();
+                                move |_: &ReverseContinue, _, cx| {
+                                    active_item
+                                        .update(cx, |item, cx| item.reverse_continue_thread(cx))
+                                        .ok();
+                                }
+                            })
+                        })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &Continue, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.continue_thread(cx))
+                                    .ok();
+                            }
+                        })
+                    },
+                )
                 .when(supports_detach, |div| {
                     let active_item = active_item.clone();
                     div.on_action(move |_: &Detach, _, cx| {
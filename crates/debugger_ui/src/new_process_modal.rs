@@ -293,9 +293,18 @@ impl NewProcessModal {
             NewProcessMode::Attach => self.attach_mode.update(cx, |this, cx| {
                 this.clone().render(window, cx).into_any_element()
             }),
-            NewProcessMode::Launch => self.configure_mode.update(cx, |this, cx| {
-                this.clone().render(dap_menu, window, cx).into_any_element()
-            }),
+            NewProcessMode::Launch => {
+                let supports_core_dumps = self
+                    .debugger
+                    .as_ref()
+                    .and_then(|debugger| cx.global::<DapRegistry>().adapter(debugger))
+                    .is_none_or(|adapter| adapter.supports_core_dumps());
+                self.configure_mode.update(cx, |this, cx| {
+                    this.clone()
+                        .render(dap_menu, supports_core_dumps, window, cx)
+                        .into_any_element()
+                })
+            }
             NewProcessMode::Debug => v_flex()
                 .w(rems(34.))
                 .child(self.debug_picker.clone())
@@ -322,9 +331,20 @@ impl NewProcessModal {
             }
             _ => return Task::ready(None),
         };
-        let label = suggested_label(&request, debugger);
+        let mut label = suggested_label(&request, debugger);
+
+        let core_file = if let NewProcessMode::Launch = &self.mode {
+            self.configure_mode.read(cx).core_file(cx)
+        } else {
+            None
+        };
+        if core_file.is_some() {
+            label = format!("{label} core dump").into();
+        }
 
-        let stop_on_entry = if let NewProcessMode::Launch = &self.mode {
+        let stop_on_entry = if let NewProcessMode::Launch = &self.mode
+            && core_file.is_none()
+        {
             Some(self.configure_mode.read(cx).stop_on_entry.selected())
         } else {
             None
@@ -341,7 +361,18 @@ impl NewProcessModal {
             .global::<DapRegistry>()
             .adapter(&session_scenario.adapter);
 
-        cx.spawn(async move |_| adapter?.config_from_zed_format(session_scenario).await.ok())
+        cx.spawn(async move |_| {
+            let mut scenario = adapter?
+                .config_from_zed_format(session_scenario)
+                .await
+                .ok()?;
+            if let Some(core_file) = core_file
+                && let Some(config) = scenario.config.as_object_mut()
+            {
+                config.insert("coreFile".into(), core_file.into());
+            }
+            Some(scenario)
+        })
     }
 
     fn start_new_session(&mut self, window: &mut Window, cx: &mut Context<Self>) {
@@ -821,6 +852,7 @@ impl RenderOnce for AttachMode {
 pub(super) struct ConfigureMode {
     program: Entity<InputField>,
     cwd: Entity<InputField>,
+    core_file: Entity<InputField>,
     stop_on_entry: ToggleState,
     save_to_debug_json: ToggleState,
 }
@@ -841,9 +873,17 @@ impl ConfigureMode {
                 .tab_index(2)
         });
 
+        let core_file = cx.new(|cx| {
+            InputField::new(window, cx, "Ex: $ZED_WORKTREE_ROOT/core")
+                .label("Core Dump (optional)")
+                .tab_stop(true)
+                .tab_index(3)
+        });
+
         cx.new(|_| Self {
             program,
             cwd,
+            core_file,
             stop_on_entry: ToggleState::Unselected,
             save_to_debug_json: ToggleState::Unselected,
         })
@@ -857,6 +897,13 @@ impl ConfigureMode {
         });
     }
 
+    /// The core dump to open instead of launching the program, if one was given.
+    pub(super) fn core_file(&self, cx: &App) -> Option<String> {
+        let core_file = self.core_file.read(cx).text(cx);
+        let core_file = core_file.trim();
+        (!core_file.is_empty()).then(|| core_file.to_string())
+    }
+
     pub(super) fn debug_request(&self, cx: &App) -> task::LaunchRequest {
         let cwd_text = self.cwd.read(cx).text(cx);
         let cwd = if cwd_text.is_empty() {
@@ -919,9 +966,11 @@ impl ConfigureMode {
     fn render(
         &mut self,
         adapter_menu: DropdownMenu,
+        supports_core_dumps: bool,
         _: &mut Window,
         cx: &mut ui::Context<Self>,
     ) -> impl IntoElement {
+        let has_core_file = self.core_file(cx).is_some();
         v_flex()
             .tab_group()
             .track_focus(&self.program.focus_handle(cx))
@@ -938,9 +987,20 @@ impl ConfigureMode {
             )
             .child(self.program.clone())
             .child(self.cwd.clone())
+            .child(self.core_file.clone())
+            .when(has_core_file && !supports_core_dumps, |this| {
+                this.child(
+                    Label::new(
+                        "This debugger can't open core dumps. Use CodeLLDB, lldb-dap or GDB.",
+                    )
+                    .size(LabelSize::Small)
+                    .color(Color::Warning),
+                )
+            })
             .child(
                 Switch::new("debugger-stop-on-entry", self.stop_on_entry)
-                    .tab_index(3_isize)
+                    .tab_index(4_isize)
+                    .disabled(has_core_file)
                     .label("Stop on Entry")
                     .label_position(SwitchLabelPosition::Start)
                     .label_size(LabelSize::Default)
@@ -3,11 +3,14 @@ use collections::HashMap;
 use dap::{Capabilities, adapters::DebugAdapterName};
 use db::kvp::KeyValueStore;
 use gpui::{Axis, Context, Entity, EntityId, Focusable, Subscription, WeakEntity, Window};
-use project::Project;
+use project::{
+    Project,
+    debugger::{dap_command::DataBreakpointContext, session::DataBreakpointState},
+};
 use serde::{Deserialize, Serialize};
 use ui::{App, SharedString};
 use util::ResultExt;
-use workspace::{Member, Pane, PaneAxis, Workspace};
+use workspace::{Member, Pane, PaneAxis, Workspace, WorkspaceId};
 
 use crate::session::running::{
     self, DebugTerminal, RunningState, SubView, breakpoint_list::BreakpointList, console::Console,
@@ -199,6 +202,102 @@ pub(crate) fn get_serialized_layout(
         .and_then(|value| serde_json::from_str::<SerializedLayout>(&value).ok())
 }
 
+const DATA_BREAKPOINTS_PREFIX: &str = "debugger_data_breakpoints_";
+
+/// What a persisted data breakpoint watches. Variable references don't outlive a session,
+/// so variables are saved as the expression that evaluates to them.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) enum SerializedDataBreakpointTarget {
+    Expression(String),
+    Address { address: String, bytes: Option<u64> },
+}
+
+/// A data breakpoint saved between sessions, re-armed by resolving its target again with
+/// `dataBreakpointInfo` once the next session stops.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub(crate) struct SerializedDataBreakpoint {
+    pub target: SerializedDataBreakpointTarget,
+    pub access_type: Option<dap::DataBreakpointAccessType>,
+    pub condition: Option<String>,
+    pub hit_condition: Option<String>,
+    pub is_enabled: bool,
+}
+
+impl SerializedDataBreakpoint {
+    /// Returns `None` for variables the adapter gave no `evaluateName` for, since their name
+    /// alone may not evaluate to the same variable in the next session.
+    pub(crate) fn from_state(state: &DataBreakpointState) -> Option<Self> {
+        let target = match &*state.context {
+            DataBreakpointContext::Variable { evaluate_name, .. } => {
+                SerializedDataBreakpointTarget::Expression(evaluate_name.clone()?)
+            }
+            DataBreakpointContext::Expression { expression, .. } => {
+                SerializedDataBreakpointTarget::Expression(expression.clone())
+            }
+            DataBreakpointContext::Address { address, bytes } => {
+                SerializedDataBreakpointTarget::Address {
+                    address: address.clone(),
+                    bytes: *bytes,
+                }
+            }
+        };
+
+        Some(Self {
+            target,
+            access_type: state.dap.access_type,
+            condition: state.dap.condition.clone(),
+            hit_condition: state.dap.hit_condition.clone(),
+            is_enabled: state.is_enabled,
+        })
+    }
+
+    /// The context to resolve the breakpoint's target in, when stopped in `frame_id`.
+    pub(crate) fn context(&self, frame_id: Option<u64>) -> DataBreakpointContext {
+        match &self.target {
+            SerializedDataBreakpointTarget::Expression(expression) => {
+                DataBreakpointContext::Expression {
+                    expression: expression.clone(),
+                    frame_id,
+                }
+            }
+            SerializedDataBreakpointTarget::Address { address, bytes } => {
+                DataBreakpointContext::Address {
+                    address: address.clone(),
+                    bytes: *bytes,
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn data_breakpoints_key(adapter_name: &str, workspace_id: WorkspaceId) -> String {
+    format!(
+        "{DATA_BREAKPOINTS_PREFIX}{adapter_name}_{}",
+        i64::from(workspace_id)
+    )
+}
+
+pub(crate) async fn serialize_data_breakpoints(
+    key: String,
+    breakpoints: Vec<SerializedDataBreakpoint>,
+    kvp: KeyValueStore,
+) -> anyhow::Result<()> {
+    let serialized_breakpoints = serde_json::to_string(&breakpoints)
+        .context("Serializing data breakpoints with serde_json as a string")?;
+    kvp.write_kvp(key, serialized_breakpoints).await
+}
+
+pub(crate) fn get_serialized_data_breakpoints(
+    key: &str,
+    kvp: &KeyValueStore,
+) -> Vec<SerializedDataBreakpoint> {
+    kvp.read_kvp(key)
+        .log_err()
+        .flatten()
+        .and_then(|value| serde_json::from_str(&value).log_err())
+        .unwrap_or_default()
+}
+
 pub(crate) fn deserialize_pane_layout(
     serialized: SerializedPaneLayout,
     should_invert: bool,
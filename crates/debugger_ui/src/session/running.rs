@@ -45,7 +45,7 @@ use project::{
 use rpc::proto::ViewId;
 use serde_json::Value;
 use settings::Settings;
-use stack_frame_list::StackFrameList;
+use stack_frame_list::{StackFrameList, StackFrameListEvent};
 use task::{
     BuildTaskDefinition, DebugScenario, SharedTaskContext, Shell, ShellBuilder, SpawnInTerminal,
     TaskContext, ZedDebugConfig, substitute_variables_in_str,
@@ -835,6 +835,15 @@ impl RunningState {
                 }
             }),
             cx.observe(&module_list, |_, _, cx| cx.notify()),
+            cx.subscribe(&stack_frame_list, {
+                let breakpoint_list = breakpoint_list.clone();
+                move |_, _, event, cx| {
+                    if let StackFrameListEvent::SelectedStackFrameChanged(frame_id) = event {
+                        breakpoint_list
+                            .update(cx, |list, cx| list.rearm_data_breakpoints(*frame_id, cx));
+                    }
+                }
+            }),
             cx.subscribe_in(&session, window, |this, _, event, window, cx| {
                 match event {
                     SessionEvent::Stopped(thread_id) => {
@@ -1753,6 +1762,16 @@ impl RunningState {
         });
     }
 
+    pub(crate) fn reverse_continue_thread(&mut self, cx: &mut Context<Self>) {
+        let Some(thread_id) = self.thread_id else {
+            return;
+        };
+
+        self.session().update(cx, |state, cx| {
+            state.reverse_continue_thread(thread_id, cx);
+        });
+    }
+
     pub fn rerun_session(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         if let Some((scenario, context)) = self.scenario.take().zip(self.scenario_context.take())
             && scenario.build.is_some()
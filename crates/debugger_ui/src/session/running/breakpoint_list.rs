@@ -5,12 +5,12 @@ use std::{
     time::Duration,
 };
 
-use dap::{Capabilities, ExceptionBreakpointsFilter, adapters::DebugAdapterName};
+use dap::{Capabilities, ExceptionBreakpointsFilter, StackFrameId, adapters::DebugAdapterName};
 use db::kvp::KeyValueStore;
 use editor::Editor;
 use gpui::{
     Action, AppContext, ClickEvent, Entity, FocusHandle, Focusable, MouseButton, ScrollStrategy,
-    Task, UniformListScrollHandle, WeakEntity, actions, uniform_list,
+    Subscription, Task, UniformListScrollHandle, WeakEntity, actions, uniform_list,
 };
 use itertools::Itertools;
 use language::Point;
@@ -19,7 +19,7 @@ use project::{
     debugger::{
         breakpoint_store::{BreakpointEditAction, BreakpointStore, SourceBreakpoint},
         dap_store::{DapStore, PersistedAdapterOptions},
-        session::Session,
+        session::{DataBreakpointState, Session, SessionEvent},
     },
     worktree_store::WorktreeStore,
 };
@@ -27,10 +27,12 @@ use ui::{
     Divider, DividerColor, FluentBuilder as _, Indicator, IntoElement, ListItem, Render,
     ScrollAxes, StatefulInteractiveElement, Tooltip, WithScrollbar, prelude::*,
 };
-use util::rel_path::RelPath;
+use util::{ResultExt as _, rel_path::RelPath};
 use workspace::Workspace;
 use zed_actions::{ToggleEnableBreakpoint, UnsetBreakpoint};
 
+use crate::persistence::{self, SerializedDataBreakpoint};
+
 actions!(
     debugger,
     [
@@ -60,6 +62,12 @@ pub(crate) struct BreakpointList {
     input: Entity<Editor>,
     strip_mode: Option<ActiveBreakpointStripMode>,
     serialize_exception_breakpoints_task: Option<Task<anyhow::Result<()>>>,
+    /// Data breakpoints saved by an earlier session that this session hasn't resolved yet.
+    unresolved_data_breakpoints: Vec<SerializedDataBreakpoint>,
+    data_breakpoints_key: Option<String>,
+    serialize_data_breakpoints_task: Option<Task<()>>,
+    rearm_data_breakpoints_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl Focusable for BreakpointList {
@@ -92,6 +100,18 @@ impl BreakpointList {
 
         let adapter_name = session.as_ref().map(|session| session.read(cx).adapter());
         cx.new(|cx| {
+            let mut subscriptions = Vec::new();
+            if let Some(session) = &session {
+                subscriptions.push(cx.subscribe(session, |this, _, event: &SessionEvent, cx| {
+                    if let SessionEvent::DataBreakpoints = event {
+                        this.serialize_data_breakpoints(cx);
+                    }
+                }));
+                // The workspace may be mid-update while the list is created, so read its
+                // database id once that's done.
+                cx.defer_in(window, |this, _, cx| this.load_data_breakpoints(cx));
+            }
+
             let this = Self {
                 breakpoint_store,
                 dap_store,
@@ -106,6 +126,11 @@ impl BreakpointList {
                 input: cx.new(|cx| Editor::single_line(window, cx)),
                 strip_mode: None,
                 serialize_exception_breakpoints_task: None,
+                unresolved_data_breakpoints: Vec::new(),
+                data_breakpoints_key: None,
+                serialize_data_breakpoints_task: None,
+                rearm_data_breakpoints_task: None,
+                _subscriptions: subscriptions,
             };
             if let Some(name) = adapter_name {
                 _ = this.deserialize_exception_breakpoints(name, cx);
@@ -183,7 +208,7 @@ impl BreakpointList {
                     (SelectedBreakpointKind::Exception, bp.is_enabled)
                 }
                 BreakpointEntryKind::DataBreakpoint(bp) => {
-                    (SelectedBreakpointKind::Data, bp.0.is_enabled)
+                    (SelectedBreakpointKind::Data, bp.is_enabled())
                 }
             })
         })
@@ -201,11 +226,11 @@ impl BreakpointList {
             ActiveBreakpointStripMode::Condition => "Set Condition",
             ActiveBreakpointStripMode::HitCondition => "Set Hit Condition",
         };
-        let mut is_exception_breakpoint = true;
+        let mut is_read_only = true;
         let active_value = self.selected_ix.and_then(|ix| {
-            self.breakpoints.get(ix).and_then(|bp| {
-                if let BreakpointEntryKind::LineBreakpoint(bp) = &bp.kind {
-                    is_exception_breakpoint = false;
+            self.breakpoints.get(ix).and_then(|bp| match &bp.kind {
+                BreakpointEntryKind::LineBreakpoint(bp) => {
+                    is_read_only = false;
                     match prop {
                         ActiveBreakpointStripMode::Log => bp.breakpoint.message.clone(),
                         ActiveBreakpointStripMode::Condition => bp.breakpoint.condition.clone(),
@@ -213,15 +238,25 @@ impl BreakpointList {
                             bp.breakpoint.hit_condition.clone()
                         }
                     }
-                } else {
-                    None
                 }
+                BreakpointEntryKind::DataBreakpoint(bp) => match prop {
+                    ActiveBreakpointStripMode::Log => None,
+                    ActiveBreakpointStripMode::Condition => {
+                        is_read_only = false;
+                        bp.condition().map(Arc::from)
+                    }
+                    ActiveBreakpointStripMode::HitCondition => {
+                        is_read_only = false;
+                        bp.hit_condition().map(Arc::from)
+                    }
+                },
+                BreakpointEntryKind::ExceptionBreakpoint(_) => None,
             })
         });
 
         self.input.update(cx, |this, cx| {
             this.set_placeholder_text(placeholder, window, cx);
-            this.set_read_only(is_exception_breakpoint);
+            this.set_read_only(is_read_only);
             this.set_text(active_value.as_deref().unwrap_or(""), window, cx);
         });
     }
@@ -341,8 +376,8 @@ impl BreakpointList {
                             );
                         }
                     }
-                    ActiveBreakpointStripMode::Condition => {
-                        if let BreakpointEntryKind::LineBreakpoint(line_breakpoint) = &entry.kind {
+                    ActiveBreakpointStripMode::Condition => match &entry.kind {
+                        BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                             Self::edit_line_breakpoint_inner(
                                 &self.breakpoint_store,
                                 line_breakpoint.breakpoint.path.clone(),
@@ -351,9 +386,18 @@ impl BreakpointList {
                                 cx,
                             );
                         }
-                    }
-                    ActiveBreakpointStripMode::HitCondition => {
-                        if let BreakpointEntryKind::LineBreakpoint(line_breakpoint) = &entry.kind {
+                        BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                            let data_breakpoint = data_breakpoint.clone();
+                            self.edit_data_breakpoint(
+                                &data_breakpoint,
+                                DataBreakpointEdit::Condition(text),
+                                cx,
+                            );
+                        }
+                        BreakpointEntryKind::ExceptionBreakpoint(_) => {}
+                    },
+                    ActiveBreakpointStripMode::HitCondition => match &entry.kind {
+                        BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                             Self::edit_line_breakpoint_inner(
                                 &self.breakpoint_store,
                                 line_breakpoint.breakpoint.path.clone(),
@@ -362,7 +406,16 @@ impl BreakpointList {
                                 cx,
                             );
                         }
-                    }
+                        BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                            let data_breakpoint = data_breakpoint.clone();
+                            self.edit_data_breakpoint(
+                                &data_breakpoint,
+                                DataBreakpointEdit::HitCondition(text),
+                                cx,
+                            );
+                        }
+                        BreakpointEntryKind::ExceptionBreakpoint(_) => {}
+                    },
                 }
                 self.focus_handle.focus(window, cx);
             } else {
@@ -407,8 +460,8 @@ impl BreakpointList {
                 self.toggle_exception_breakpoint(&id, cx);
             }
             BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
-                let id = data_breakpoint.0.dap.data_id.clone();
-                self.toggle_data_breakpoint(&id, cx);
+                let data_breakpoint = data_breakpoint.clone();
+                self.edit_data_breakpoint(&data_breakpoint, DataBreakpointEdit::ToggleEnabled, cx);
             }
         }
         cx.notify();
@@ -424,10 +477,17 @@ impl BreakpointList {
             return;
         };
 
-        if let BreakpointEntryKind::LineBreakpoint(line_breakpoint) = &mut entry.kind {
-            let path = line_breakpoint.breakpoint.path.clone();
-            let row = line_breakpoint.breakpoint.row;
-            self.edit_line_breakpoint(path, row, BreakpointEditAction::Toggle, cx);
+        match &mut entry.kind {
+            BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
+                let path = line_breakpoint.breakpoint.path.clone();
+                let row = line_breakpoint.breakpoint.row;
+                self.edit_line_breakpoint(path, row, BreakpointEditAction::Toggle, cx);
+            }
+            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                let data_breakpoint = data_breakpoint.clone();
+                self.edit_data_breakpoint(&data_breakpoint, DataBreakpointEdit::Remove, cx);
+            }
+            BreakpointEntryKind::ExceptionBreakpoint(_) => {}
         }
         cx.notify();
     }
@@ -476,12 +536,176 @@ impl BreakpointList {
         cx.notify();
     }
 
-    fn toggle_data_breakpoint(&mut self, id: &str, cx: &mut Context<Self>) {
-        if let Some(session) = &self.session {
-            session.update(cx, |this, cx| {
-                this.toggle_data_breakpoint(id, cx);
-            });
+    fn edit_data_breakpoint(
+        &mut self,
+        data_breakpoint: &DataBreakpoint,
+        edit: DataBreakpointEdit,
+        cx: &mut Context<Self>,
+    ) {
+        match data_breakpoint {
+            DataBreakpoint::Armed(state) => {
+                let Some(session) = &self.session else {
+                    return;
+                };
+                let id = state.dap.data_id.as_str();
+                session.update(cx, |session, cx| match edit {
+                    DataBreakpointEdit::ToggleEnabled => session.toggle_data_breakpoint(id, cx),
+                    DataBreakpointEdit::Condition(condition) => {
+                        session.set_data_breakpoint_condition(id, Some(condition), cx)
+                    }
+                    DataBreakpointEdit::HitCondition(hit_condition) => {
+                        session.set_data_breakpoint_hit_condition(id, Some(hit_condition), cx)
+                    }
+                    DataBreakpointEdit::Remove => session.remove_data_breakpoint(id, cx),
+                });
+            }
+            DataBreakpoint::Unresolved { index, .. } => {
+                let index = *index;
+                if index >= self.unresolved_data_breakpoints.len() {
+                    return;
+                }
+                let breakpoint = &mut self.unresolved_data_breakpoints[index];
+                match edit {
+                    DataBreakpointEdit::ToggleEnabled => {
+                        breakpoint.is_enabled = !breakpoint.is_enabled
+                    }
+                    DataBreakpointEdit::Condition(condition) => {
+                        breakpoint.condition = Some(condition).filter(|c| !c.is_empty())
+                    }
+                    DataBreakpointEdit::HitCondition(hit_condition) => {
+                        breakpoint.hit_condition = Some(hit_condition).filter(|c| !c.is_empty())
+                    }
+                    DataBreakpointEdit::Remove => {
+                        self.unresolved_data_breakpoints.remove(index);
+                    }
+                }
+                self.serialize_data_breakpoints(cx);
+            }
+        }
+        cx.notify();
+    }
+
+    fn load_data_breakpoints(&mut self, cx: &mut Context<Self>) {
+        let Some(session) = &self.session else {
+            return;
+        };
+        let Some(workspace_id) = self
+            .workspace
+            .read_with(cx, |workspace, _| workspace.database_id())
+            .ok()
+            .flatten()
+        else {
+            return;
+        };
+
+        let key = persistence::data_breakpoints_key(&session.read(cx).adapter(), workspace_id);
+        self.unresolved_data_breakpoints =
+            persistence::get_serialized_data_breakpoints(&key, &KeyValueStore::global(cx));
+        self.data_breakpoints_key = Some(key);
+        cx.notify();
+    }
+
+    fn serialize_data_breakpoints(&mut self, cx: &mut Context<Self>) {
+        let (Some(session), Some(key)) = (&self.session, self.data_breakpoints_key.clone()) else {
+            return;
+        };
+
+        let mut breakpoints = session
+            .read(cx)
+            .data_breakpoints()
+            .filter_map(SerializedDataBreakpoint::from_state)
+            .collect::<Vec<_>>();
+        for unresolved in &self.unresolved_data_breakpoints {
+            if !breakpoints
+                .iter()
+                .any(|breakpoint| breakpoint.target == unresolved.target)
+            {
+                breakpoints.push(unresolved.clone());
+            }
+        }
+
+        let kvp = KeyValueStore::global(cx);
+        self.serialize_data_breakpoints_task = Some(cx.background_spawn(async move {
+            persistence::serialize_data_breakpoints(key, breakpoints, kvp)
+                .await
+                .log_err();
+        }));
+    }
+
+    /// Resolves the data breakpoints saved by an earlier session in the newly selected
+    /// stack frame, and arms the ones that resolve. The rest are retried on the next stop.
+    pub(crate) fn rearm_data_breakpoints(
+        &mut self,
+        frame_id: StackFrameId,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(session) = self.session.clone() else {
+            return;
+        };
+        if self.unresolved_data_breakpoints.is_empty()
+            || self.rearm_data_breakpoints_task.is_some()
+            || !session
+                .read(cx)
+                .capabilities()
+                .supports_data_breakpoints
+                .unwrap_or_default()
+        {
+            return;
         }
+
+        let requests = self
+            .unresolved_data_breakpoints
+            .iter()
+            .map(|breakpoint| {
+                let context = Arc::new(breakpoint.context(Some(frame_id)));
+                let info = session.update(cx, |session, cx| {
+                    session.data_breakpoint_info(context.clone(), None, cx)
+                });
+                (breakpoint.clone(), context, info)
+            })
+            .collect::<Vec<_>>();
+
+        self.rearm_data_breakpoints_task = Some(cx.spawn(async move |this, cx| {
+            let mut resolved = Vec::new();
+            let mut restored = Vec::new();
+            for (breakpoint, context, info) in requests {
+                let Some(info) = info.await else {
+                    continue;
+                };
+                let Some(data_id) = info.data_id else {
+                    continue;
+                };
+                let access_type = breakpoint.access_type.filter(|access_type| {
+                    info.access_types
+                        .as_ref()
+                        .is_some_and(|access_types| access_types.contains(access_type))
+                });
+                restored.push(DataBreakpointState {
+                    dap: dap::DataBreakpoint {
+                        data_id,
+                        access_type,
+                        condition: breakpoint.condition.clone(),
+                        hit_condition: breakpoint.hit_condition.clone(),
+                    },
+                    is_enabled: breakpoint.is_enabled,
+                    context,
+                });
+                resolved.push(breakpoint);
+            }
+
+            this.update(cx, |this, cx| {
+                this.rearm_data_breakpoints_task = None;
+                this.unresolved_data_breakpoints
+                    .retain(|breakpoint| !resolved.contains(breakpoint));
+                if !restored.is_empty() {
+                    session.update(cx, |session, cx| {
+                        session.restore_data_breakpoints(restored, cx)
+                    });
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
     }
 
     fn toggle_exception_breakpoint(&mut self, id: &str, cx: &mut Context<Self>) {
@@ -650,9 +874,10 @@ impl BreakpointList {
                             }
                         })
                     })
-                    .disabled(
-                        selection_kind.map(|kind| kind.0) != Some(SelectedBreakpointKind::Source),
-                    )
+                    .disabled(matches!(
+                        selection_kind.map(|kind| kind.0),
+                        None | Some(SelectedBreakpointKind::Exception)
+                    ))
                     .on_click({
                         move |_, window, cx| {
                             focus_handle.focus(window, cx);
@@ -723,15 +948,27 @@ impl Render for BreakpointList {
                     weak: weak.clone(),
                 })
         });
-        let data_breakpoints = self.session.as_ref().into_iter().flat_map(|session| {
-            session
-                .read(cx)
-                .data_breakpoints()
-                .map(|state| BreakpointEntry {
-                    kind: BreakpointEntryKind::DataBreakpoint(DataBreakpoint(state.clone())),
-                    weak: weak.clone(),
-                })
-        });
+        let data_breakpoints = self
+            .session
+            .as_ref()
+            .into_iter()
+            .flat_map(|session| {
+                session
+                    .read(cx)
+                    .data_breakpoints()
+                    .map(|state| DataBreakpoint::Armed(state.clone()))
+            })
+            .chain(self.unresolved_data_breakpoints.iter().enumerate().map(
+                |(index, breakpoint)| DataBreakpoint::Unresolved {
+                    index,
+                    breakpoint: breakpoint.clone(),
+                },
+            ))
+            .map(|data_breakpoint| BreakpointEntry {
+                kind: BreakpointEntryKind::DataBreakpoint(data_breakpoint),
+                weak: weak.clone(),
+            })
+            .collect::<Vec<_>>();
         self.breakpoints.extend(
             breakpoints
                 .chain(data_breakpoints)
@@ -753,7 +990,7 @@ impl Render for BreakpointList {
                     exc_bp.data.label.len() as f32 * text_pixels
                 }
                 BreakpointEntryKind::DataBreakpoint(data_bp) => {
-                    data_bp.0.context.human_readable_label().len() as f32 * text_pixels
+                    data_bp.label().len() as f32 * text_pixels
                 }
             })
             .position_max_by(|left, right| left.total_cmp(right));
@@ -971,9 +1208,60 @@ struct ExceptionBreakpoint {
 }
 
 #[derive(Clone, Debug)]
-struct DataBreakpoint(project::debugger::session::DataBreakpointState);
+enum DataBreakpoint {
+    Armed(DataBreakpointState),
+    /// Saved by an earlier session and not yet resolved in this one.
+    Unresolved {
+        index: usize,
+        breakpoint: SerializedDataBreakpoint,
+    },
+}
+
+enum DataBreakpointEdit {
+    ToggleEnabled,
+    Condition(String),
+    HitCondition(String),
+    Remove,
+}
 
 impl DataBreakpoint {
+    fn id(&self) -> SharedString {
+        match self {
+            Self::Armed(state) => state.dap.data_id.clone().into(),
+            Self::Unresolved { index, .. } => format!("unresolved-{index}").into(),
+        }
+    }
+
+    fn label(&self) -> SharedString {
+        match self {
+            Self::Armed(state) => state.context.human_readable_label().into(),
+            Self::Unresolved { breakpoint, .. } => {
+                breakpoint.context(None).human_readable_label().into()
+            }
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        match self {
+            Self::Armed(state) => state.is_enabled,
+            Self::Unresolved { breakpoint, .. } => breakpoint.is_enabled,
+        }
+    }
+
+    fn condition(&self) -> Option<&str> {
+        match self {
+            Self::Armed(state) => state.dap.condition.as_deref(),
+            Self::Unresolved { breakpoint, .. } => breakpoint.condition.as_deref(),
+        }
+    }
+
+    fn hit_condition(&self) -> Option<&str> {
+        match self {
+            Self::Armed(state) => state.dap.hit_condition.as_deref(),
+            Self::Unresolved { breakpoint, .. } => breakpoint.hit_condition.as_deref(),
+        }
+    }
+
     fn render(
         &self,
         props: SupportedBreakpointProperties,
@@ -983,25 +1271,24 @@ impl DataBreakpoint {
         focus_handle: FocusHandle,
         list: WeakEntity<BreakpointList>,
     ) -> ListItem {
-        let color = if self.0.is_enabled {
+        let is_enabled = self.is_enabled();
+        let is_unresolved = matches!(self, Self::Unresolved { .. });
+        let color = if is_enabled && !is_unresolved {
             Color::Debugger
         } else {
             Color::Muted
         };
-        let is_enabled = self.0.is_enabled;
-        let id = self.0.dap.data_id.clone();
+        let id = self.id();
 
         ListItem::new(SharedString::from(format!(
-            "data-breakpoint-ui-item-{}",
-            self.0.dap.data_id
+            "data-breakpoint-ui-item-{id}"
         )))
         .toggle_state(is_selected)
         .inset(true)
         .start_slot(
             div()
                 .id(SharedString::from(format!(
-                    "data-breakpoint-ui-item-{}-click-handler",
-                    self.0.dap.data_id
+                    "data-breakpoint-ui-item-{id}-click-handler"
                 )))
                 .child(
                     Icon::new(IconName::Binary)
@@ -1025,9 +1312,14 @@ impl DataBreakpoint {
                 })
                 .on_click({
                     let list = list.clone();
+                    let data_breakpoint = self.clone();
                     move |_, _, cx| {
                         list.update(cx, |this, cx| {
-                            this.toggle_data_breakpoint(&id, cx);
+                            this.edit_data_breakpoint(
+                                &data_breakpoint,
+                                DataBreakpointEdit::ToggleEnabled,
+                                cx,
+                            );
                         })
                         .ok();
                     }
@@ -1046,10 +1338,15 @@ impl DataBreakpoint {
                         .justify_center()
                         .id(("data-breakpoint-label", ix))
                         .child(
-                            Label::new(self.0.context.human_readable_label())
+                            Label::new(self.label())
                                 .size(LabelSize::Small)
                                 .line_height_style(ui::LineHeightStyle::UiLabel),
-                        ),
+                        )
+                        .when(is_unresolved, |this| {
+                            this.tooltip(Tooltip::text(
+                                "Saved from an earlier session. It will be set again when the program stops in a frame where it can be resolved.",
+                            ))
+                        }),
                 )
                 .child(BreakpointOptionsStrip {
                     props,
@@ -1234,11 +1531,9 @@ impl BreakpointEntry {
                 exception_breakpoint.id
             )
             .into(),
-            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => format!(
-                "data-breakpoint-control-strip--{}",
-                data_breakpoint.0.dap.data_id
-            )
-            .into(),
+            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                format!("data-breakpoint-control-strip--{}", data_breakpoint.id()).into()
+            }
         }
     }
 
@@ -1256,8 +1551,11 @@ impl BreakpointEntry {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                 line_breakpoint.breakpoint.condition.is_some()
             }
-            // We don't support conditions on exception/data breakpoints
-            _ => false,
+            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                data_breakpoint.condition().is_some()
+            }
+            // We don't support conditions on exception breakpoints
+            BreakpointEntryKind::ExceptionBreakpoint(_) => false,
         }
     }
 
@@ -1266,7 +1564,10 @@ impl BreakpointEntry {
             BreakpointEntryKind::LineBreakpoint(line_breakpoint) => {
                 line_breakpoint.breakpoint.hit_condition.is_some()
             }
-            _ => false,
+            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                data_breakpoint.hit_condition().is_some()
+            }
+            BreakpointEntryKind::ExceptionBreakpoint(_) => false,
         }
     }
 }
@@ -1311,8 +1612,8 @@ impl SupportedBreakpointProperties {
         Self::empty()
     }
     fn for_data_breakpoints(self) -> Self {
-        // TODO: we don't yet support conditions for data breakpoints at the data layer, hence all props are disabled here.
-        Self::empty()
+        // Data breakpoints carry conditions and hit conditions, but not log messages.
+        self & (Self::CONDITION | Self::HIT_CONDITION)
     }
 }
 #[derive(IntoElement)]
@@ -467,20 +467,30 @@ impl MemoryView {
                         .supports_write_memory_request
                         .unwrap_or_default()
                     {
-                        let adapter_name = session.adapter();
+                        let message = if session.is_post_mortem() {
+                            "The memory of a core dump can't be modified".to_string()
+                        } else {
+                            let adapter_name = session.adapter();
+                            format!(
+                                "Debug Adapter `{adapter_name}` does not support writing to memory"
+                            )
+                        };
                         // We cannot write memory with this adapter.
                         _ = self.workspace.update(cx, |this, cx| {
                             this.toggle_status_toast(
-                                StatusToast::new(format!(
-                                    "Debug Adapter `{adapter_name}` does not support writing to memory"
-                                ), cx, |this, cx| {
+                                StatusToast::new(message, cx, |this, cx| {
                                     cx.spawn(async move |this, cx| {
-                                        cx.background_executor().timer(Duration::from_secs(2)).await;
-                                        _ = this.update(cx, |_, cx| {
-                                            cx.emit(DismissEvent)
-                                        });
-                                    }).detach();
-                                    this.icon(Icon::new(IconName::XCircle).size(IconSize::Small).color(Color::Error))
+                                        cx.background_executor()
+                                            .timer(Duration::from_secs(2))
+                                            .await;
+                                        _ = this.update(cx, |_, cx| cx.emit(DismissEvent));
+                                    })
+                                    .detach();
+                                    this.icon(
+                                        Icon::new(IconName::XCircle)
+                                            .size(IconSize::Small)
+                                            .color(Color::Error),
+                                    )
                                 }),
                                 cx,
                             );
@@ -687,6 +687,7 @@ impl VariableList {
                         Arc::new(DataBreakpointContext::Variable {
                             variables_reference,
                             name: variable.name.clone(),
+                            evaluate_name: variable.evaluate_name.clone(),
                             bytes: None,
                         }),
                         None,
@@ -793,7 +794,7 @@ impl VariableList {
             return;
         };
 
-        let Some((name, var_ref)) = entry.as_variable().map(|var| &var.name).zip(
+        let Some((variable, var_ref)) = entry.as_variable().zip(
             self.entry_states
                 .get(&entry.path)
                 .map(|state| state.parent_reference),
@@ -803,7 +804,8 @@ impl VariableList {
 
         let context = Arc::new(DataBreakpointContext::Variable {
             variables_reference: var_ref,
-            name: name.clone(),
+            name: variable.name.clone(),
+            evaluate_name: variable.evaluate_name.clone(),
             bytes: None,
         });
         let data_breakpoint = self.session.update(cx, |session, cx| {
@@ -1862,10 +1862,30 @@ fn go_lang() -> Arc<Language> {
     )
 }
 
+fn c_lang() -> Arc<Language> {
+    let debug_variables_query = include_str!("../../../grammars/src/c/debugger.scm");
+    Arc::new(
+        Language::new(
+            LanguageConfig {
+                name: "C".into(),
+                matcher: LanguageMatcher {
+                    path_suffixes: vec!["c".to_string()],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            Some(tree_sitter_c::LANGUAGE.into()),
+        )
+        .with_debug_variables_query(debug_variables_query)
+        .unwrap(),
+    )
+}
+
 /// Test utility function for inline values testing
 ///
 /// # Arguments
 /// * `variables` - List of tuples containing (variable_name, variable_value)
+/// * `expressions` - List of tuples containing (expression, result) answered by `evaluate`
 /// * `before` - Source code before inline values are applied
 /// * `after` - Expected source code after inline values are applied
 /// * `language` - Language configuration to use for parsing
@@ -1874,6 +1894,7 @@ fn go_lang() -> Arc<Language> {
 async fn test_inline_values_util(
     local_variables: &[(&str, &str)],
     global_variables: &[(&str, &str)],
+    expressions: &[(&str, &str)],
     before: &str,
     after: &str,
     active_debug_line: Option<usize>,
@@ -2020,9 +2041,10 @@ async fn test_inline_values_util(
         })
     });
 
-    if !global_variables.is_empty() {
+    if !global_variables.is_empty() || !expressions.is_empty() {
         let global_evaluate_map: std::collections::HashMap<String, String> = global_variables
             .iter()
+            .chain(expressions)
             .map(|(name, value)| (name.to_string(), value.to_string()))
             .collect();
 
@@ -2131,6 +2153,7 @@ fn main() {
     test_inline_values_util(
         &variables,
         &[],
+        &[],
         &before,
         &after,
         None,
@@ -2176,6 +2199,7 @@ fn main() {
     test_inline_values_util(
         &variables,
         &[("GLOBAL_COUNTER", "42")],
+        &[],
         &before,
         &after,
         None,
@@ -2221,6 +2245,7 @@ func main() {
     test_inline_values_util(
         &variables,
         &[("globalCounter", "100")],
+        &[],
         &before,
         &after,
         None,
@@ -2231,6 +2256,126 @@ func main() {
     .await;
 }
 
+#[gpui::test]
+async fn test_c_inline_values(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    let variables = [("cfg", "0x7ffd1000"), ("port", "0"), ("fallback", "0")];
+
+    let before = r#"
+struct config {
+    int port;
+};
+
+int serve(struct config *cfg) {
+    int port = cfg->port;
+    int fallback = get_config().port;
+    return port;
+}
+"#
+    .unindent();
+
+    let after = r#"
+struct config {
+    int port;
+};
+
+int serve(struct config *cfg: 0x7ffd1000) {
+    int port: 0 = cfg->port: 8080;
+    int fallback: 0 = get_config().port;
+    return port;
+}
+"#
+    .unindent();
+
+    test_inline_values_util(
+        &variables,
+        &[],
+        &[("cfg->port", "8080")],
+        &before,
+        &after,
+        Some(7),
+        c_lang(),
+        executor,
+        cx,
+    )
+    .await;
+}
+
+#[gpui::test]
+async fn test_rust_member_chain_inline_values(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    let variables = [("cfg", "0x1"), ("port", "8080"), ("fallback", "0")];
+
+    let before = r#"
+fn serve(cfg: &Config) -> u16 {
+    let port = cfg.server.port;
+    let fallback = load().port;
+    port + fallback
+}
+"#
+    .unindent();
+
+    let after = r#"
+fn serve(cfg: 0x1: &Config) -> u16 {
+    let port: 8080 = cfg: 0x1.server.port: 8080;
+    let fallback: 0 = load().port;
+    port: 8080 + fallback: 0
+}
+"#
+    .unindent();
+
+    test_inline_values_util(
+        &variables,
+        &[],
+        &[("cfg.server.port", "8080")],
+        &before,
+        &after,
+        None,
+        rust_lang(),
+        executor,
+        cx,
+    )
+    .await;
+}
+
+#[gpui::test]
+async fn test_python_member_chain_inline_values(
+    executor: BackgroundExecutor,
+    cx: &mut TestAppContext,
+) {
+    let variables = [("cfg", "Config"), ("port", "8080"), ("fallback", "0")];
+
+    let before = r#"
+def serve(cfg):
+    port = cfg.server.port
+    fallback = load().port
+    return port + fallback
+"#
+    .unindent();
+
+    let after = r#"
+def serve(cfg):
+    port: 8080 = cfg: Config.server.port: 8080
+    fallback: 0 = load().port
+    return port: 8080 + fallback: 0
+"#
+    .unindent();
+
+    test_inline_values_util(
+        &variables,
+        &[],
+        &[("cfg.server.port", "8080")],
+        &before,
+        &after,
+        Some(4),
+        Arc::new(python_lang()),
+        executor,
+        cx,
+    )
+    .await;
+}
+
 #[gpui::test]
 async fn test_trim_multi_line_inline_value(executor: BackgroundExecutor, cx: &mut TestAppContext) {
     let variables = [("y", "hello\n world")];
@@ -2252,6 +2397,7 @@ fn main() {
     test_inline_values_util(
         &variables,
         &[],
+        &[],
         &before,
         &after,
         None,
@@ -2353,6 +2499,7 @@ function calculate() {
     test_inline_values_util(
         &variables,
         &[],
+        &[],
         &before,
         &after,
         None,
@@ -2397,6 +2544,7 @@ function processData(count: number, name: string): number {
     test_inline_values_util(
         &variables,
         &[],
+        &[],
         &before,
         &after,
         None,
@@ -2442,6 +2590,7 @@ const Counter = () => {
     test_inline_values_util(
         &variables,
         &[],
+        &[],
         &before,
         &after,
         None,
@@ -2475,6 +2624,7 @@ const double = (x) => {
     test_inline_values_util(
         &variables,
         &[],
+        &[],
         &before,
         &after,
         None,
@@ -2512,6 +2662,7 @@ function iterate() {
     test_inline_values_util(
         &variables,
         &[],
+        &[],
         &before,
         &after,
         None,
@@ -3,12 +3,15 @@ use std::iter::zip;
 
 use crate::{
     debugger_panel::DebugPanel,
-    persistence::SerializedPaneLayout,
+    persistence::{SerializedDataBreakpoint, SerializedDataBreakpointTarget, SerializedPaneLayout},
     tests::{init_test, init_test_workspace, start_debug_session},
 };
 use dap::{StoppedEvent, StoppedEventReason, messages::Events};
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
-use project::{FakeFs, Project};
+use project::{
+    FakeFs, Project,
+    debugger::{dap_command::DataBreakpointContext, session::DataBreakpointState},
+};
 use serde_json::json;
 use util::path;
 use workspace::{Panel, dock::DockPosition};
@@ -130,3 +133,67 @@ async fn test_invert_axis_on_panel_position_change(
         }
     }
 }
+
+#[test]
+fn test_data_breakpoint_is_saved_as_its_evaluate_name() {
+    let state = DataBreakpointState {
+        dap: dap::DataBreakpoint {
+            data_id: "0x1000".into(),
+            access_type: Some(dap::DataBreakpointAccessType::Write),
+            condition: Some("counter > 3".into()),
+            hit_condition: Some("2".into()),
+        },
+        is_enabled: false,
+        context: std::sync::Arc::new(DataBreakpointContext::Variable {
+            variables_reference: 7,
+            name: "counter".into(),
+            evaluate_name: Some("state.counter".into()),
+            bytes: None,
+        }),
+    };
+
+    let serialized = SerializedDataBreakpoint::from_state(&state).unwrap();
+    assert_eq!(
+        serialized.target,
+        SerializedDataBreakpointTarget::Expression("state.counter".into())
+    );
+    assert_eq!(serialized.condition.as_deref(), Some("counter > 3"));
+    assert_eq!(serialized.hit_condition.as_deref(), Some("2"));
+    assert!(!serialized.is_enabled);
+
+    let round_tripped: SerializedDataBreakpoint =
+        serde_json::from_str(&serde_json::to_string(&serialized).unwrap()).unwrap();
+    assert_eq!(round_tripped, serialized);
+
+    match round_tripped.context(Some(42)) {
+        DataBreakpointContext::Expression {
+            expression,
+            frame_id,
+        } => {
+            assert_eq!(expression, "state.counter");
+            assert_eq!(frame_id, Some(42));
+        }
+        context => panic!("expected an expression context, got {context:?}"),
+    }
+}
+
+#[test]
+fn test_data_breakpoint_without_evaluate_name_is_not_saved() {
+    let state = DataBreakpointState {
+        dap: dap::DataBreakpoint {
+            data_id: "0x1000".into(),
+            access_type: None,
+            condition: None,
+            hit_condition: None,
+        },
+        is_enabled: true,
+        context: std::sync::Arc::new(DataBreakpointContext::Variable {
+            variables_reference: 7,
+            name: "counter".into(),
+            evaluate_name: None,
+            bytes: None,
+        }),
+    };
+
+    assert_eq!(SerializedDataBreakpoint::from_state(&state), None);
+}
@@ -164,8 +164,8 @@ impl FeatureFlag for AutoWatchFeatureFlag {
 register_feature_flag!(AutoWatchFeatureFlag);
 
 /// Wraps agent-run terminal commands in an OS-level sandbox where supported
-/// (currently macOS Seatbelt only). When off, terminal commands run with the
-/// agent's full ambient permissions, as they always have.
+/// (macOS Seatbelt and Linux Landlock). When off, terminal commands run with
+/// the agent's full ambient permissions, as they always have.
 pub struct SandboxingFeatureFlag;
 
 impl FeatureFlag for SandboxingFeatureFlag {
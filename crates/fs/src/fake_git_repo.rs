@@ -9,18 +9,21 @@ use futures::future::{self, BoxFuture, join_all};
 use git::repository::GitCommitTemplate;
 use git::{
     Oid, RunHook,
+    bisect::{BisectStatus, BisectTerm},
     blame::Blame,
+    rebase::{RebaseControl, RebaseProgress, RebaseTodoEntry},
     repository::{
         AskPassDelegate, Branch, CommitData, CommitDataReader, CommitDetails, CommitOptions,
-        CreateWorktreeTarget, FetchOptions, FileHistoryChangedFileSets, GRAPH_CHUNK_SIZE,
-        GitRepository, GitRepositoryCheckpoint, InitialGraphCommitData, LogOrder, LogSource,
-        PushOptions, RefEdit, Remote, RepoPath, ResetMode, SearchCommitArgs, Worktree,
+        ConflictStages, CreateWorktreeTarget, FetchOptions, FileHistoryChangedFileSets,
+        GRAPH_CHUNK_SIZE, GitRepository, GitRepositoryCheckpoint, InitialGraphCommitData, LogOrder,
+        LogSource, PushOptions, RefEdit, Remote, RepoPath, ResetMode, SearchCommitArgs, Worktree,
     },
     stash::GitStash,
     status::{
         DiffTreeType, FileStatus, GitStatus, StatusCode, TrackedStatus, TreeDiff, TreeDiffStatus,
         UnmergedStatus,
     },
+    submodule::{Submodule, SubmoduleCommand, SubmoduleLog, SubmoduleState},
 };
 use gpui::{AsyncApp, BackgroundExecutor, SharedString, Task};
 use ignore::gitignore::GitignoreBuilder;
@@ -78,6 +81,10 @@ pub struct FakeGitRepositoryState {
     pub graph_commits: Vec<Arc<InitialGraphCommitData>>,
     pub commit_data: HashMap<Oid, FakeCommitDataEntry>,
     pub stash_entries: GitStash,
+    pub rebase_progress: Option<RebaseProgress>,
+    pub bisect: Option<BisectStatus>,
+    pub submodules: Vec<Submodule>,
+    pub conflict_stages: HashMap<RepoPath, ConflictStages>,
 }
 
 impl FakeGitRepositoryState {
@@ -103,6 +110,10 @@ impl FakeGitRepositoryState {
             commit_data: Default::default(),
             commit_history: Vec::new(),
             stash_entries: Default::default(),
+            rebase_progress: None,
+            bisect: None,
+            submodules: Vec::new(),
+            conflict_stages: Default::default(),
         }
     }
 }
@@ -187,6 +198,16 @@ impl GitRepository for FakeGitRepository {
         async { Ok(None) }.boxed()
     }
 
+    fn load_conflict_stages(&self, path: RepoPath) -> BoxFuture<'_, Result<ConflictStages>> {
+        self.with_state_async(false, move |state| {
+            Ok(state
+                .conflict_stages
+                .get(&path)
+                .cloned()
+                .unwrap_or_default())
+        })
+    }
+
     fn load_blob_content(&self, oid: git::Oid) -> BoxFuture<'_, Result<String>> {
         self.with_state_async(false, move |state| {
             state.oids.get(&oid).cloned().context("oid does not exist")
@@ -320,7 +341,7 @@ impl GitRepository for FakeGitRepository {
                 ResetMode::Soft => {
                     state.head_contents = snapshot.head_contents;
                 }
-                ResetMode::Mixed => {
+                ResetMode::Mixed | ResetMode::Hard => {
                     state.head_contents = snapshot.head_contents;
                     state.index_contents = state.head_contents.clone();
                 }
@@ -331,6 +352,48 @@ impl GitRepository for FakeGitRepository {
         })
     }
 
+    fn cherry_pick(
+        &self,
+        _commits: Vec<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn revert(
+        &self,
+        _commit: String,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        commit: String,
+        _message: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let ref_name = format!("refs/tags/{name}");
+            if state.refs.contains_key(&ref_name) {
+                bail!("tag '{name}' already exists");
+            }
+            state.refs.insert(ref_name, commit);
+            Ok(())
+        })
+    }
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if state.refs.remove(&format!("refs/tags/{name}")).is_none() {
+                bail!("tag '{name}' not found");
+            }
+            Ok(())
+        })
+    }
+
     fn checkout_files(
         &self,
         _commit: String,
@@ -340,6 +403,123 @@ impl GitRepository for FakeGitRepository {
         unimplemented!()
     }
 
+    fn rebase_todo(&self, _base: Option<String>) -> BoxFuture<'_, Result<Vec<RebaseTodoEntry>>> {
+        unimplemented!()
+    }
+
+    fn rebase_interactive(
+        &self,
+        _base: Option<String>,
+        _entries: Vec<RebaseTodoEntry>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        unimplemented!()
+    }
+
+    fn rebase_control(
+        &self,
+        control: RebaseControl,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let Some(progress) = state.rebase_progress.as_mut() else {
+                bail!("No rebase in progress");
+            };
+            match control {
+                RebaseControl::Abort => state.rebase_progress = None,
+                RebaseControl::Continue | RebaseControl::Skip => {
+                    if progress.done >= progress.total {
+                        state.rebase_progress = None;
+                    } else {
+                        progress.done += 1;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>> {
+        let state = self.with_state_async(false, |state| Ok(state.rebase_progress.clone()));
+        async move { state.await.ok().flatten() }.boxed()
+    }
+
+    fn bisect_start(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, |state| {
+            state.bisect.get_or_insert_default();
+            Ok(())
+        })
+    }
+
+    fn bisect_mark(
+        &self,
+        term: BisectTerm,
+        commit: Option<String>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            let Some(bisect) = state.bisect.as_mut() else {
+                bail!("No bisect in progress");
+            };
+            let commit = match commit {
+                Some(commit) => commit.parse()?,
+                None => bisect
+                    .current
+                    .context("No commit checked out for bisecting")?,
+            };
+            match term {
+                BisectTerm::Good => bisect.good.push(commit),
+                BisectTerm::Bad => bisect.bad = Some(commit),
+                BisectTerm::Skip => bisect.skipped.push(commit),
+            }
+            Ok(())
+        })
+    }
+
+    fn bisect_reset(&self, _env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, |state| {
+            state.bisect = None;
+            Ok(())
+        })
+    }
+
+    fn bisect_status(&self) -> BoxFuture<'_, Result<Option<BisectStatus>>> {
+        self.with_state_async(false, |state| Ok(state.bisect.clone()))
+    }
+
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        self.with_state_async(false, |state| Ok(state.submodules.clone()))
+    }
+
+    fn submodule_command(
+        &self,
+        command: SubmoduleCommand,
+        paths: Vec<RepoPath>,
+        _env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        self.with_state_async(true, move |state| {
+            if command != SubmoduleCommand::Update {
+                return Ok(());
+            }
+            for submodule in &mut state.submodules {
+                if paths.is_empty() || paths.contains(&submodule.path) {
+                    submodule.checked_out_sha = submodule.index_sha;
+                    submodule.state = SubmoduleState::UpToDate;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn submodule_log(
+        &self,
+        _path: RepoPath,
+        _old_sha: Oid,
+        _new_sha: Oid,
+    ) -> BoxFuture<'_, Result<SubmoduleLog>> {
+        unimplemented!()
+    }
+
     fn path(&self) -> PathBuf {
         self.repository_dir_path.clone()
     }
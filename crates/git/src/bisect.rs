@@ -0,0 +1,150 @@
+use crate::Oid;
+use std::str::FromStr;
+
+/// A verdict on a commit tested during a bisect.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BisectTerm {
+    Good,
+    Bad,
+    Skip,
+}
+
+impl BisectTerm {
+    /// The `git bisect` subcommand that records this verdict.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Good => "good",
+            Self::Bad => "bad",
+            Self::Skip => "skip",
+        }
+    }
+}
+
+/// The state of a bisect in progress.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BisectStatus {
+    /// The earliest commit known to be bad.
+    pub bad: Option<Oid>,
+    pub good: Vec<Oid>,
+    pub skipped: Vec<Oid>,
+    /// The commit checked out for testing.
+    pub current: Option<Oid>,
+    /// Roughly how many more commits need to be tested, once a good and a bad commit are known.
+    pub remaining_steps: Option<usize>,
+    /// The first bad commit, once the bisect has found it.
+    pub first_bad: Option<Oid>,
+}
+
+impl BisectStatus {
+    /// Whether the bisect is still waiting for a good or a bad commit to narrow down.
+    pub fn needs_bounds(&self) -> bool {
+        self.bad.is_none() || self.good.is_empty()
+    }
+}
+
+/// Reads the commits marked so far from the output of
+/// `git for-each-ref --format='%(refname) %(objectname)' refs/bisect`.
+pub fn parse_bisect_refs(output: &str, status: &mut BisectStatus) {
+    for line in output.lines() {
+        let Some((ref_name, sha)) = line.trim().split_once(' ') else {
+            continue;
+        };
+        let Ok(sha) = Oid::from_str(sha) else {
+            continue;
+        };
+        if ref_name == "refs/bisect/bad" {
+            status.bad = Some(sha);
+        } else if ref_name.starts_with("refs/bisect/good-") {
+            status.good.push(sha);
+        } else if ref_name.starts_with("refs/bisect/skip-") {
+            status.skipped.push(sha);
+        }
+    }
+}
+
+/// Reads the estimated number of remaining steps from the output of `git rev-list --bisect-vars`.
+pub fn parse_bisect_steps(output: &str) -> Option<usize> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("bisect_steps="))
+        .and_then(|steps| steps.trim_matches('\'').parse().ok())
+}
+
+/// Finds the commit the bisect settled on in the contents of `BISECT_LOG`.
+pub fn parse_first_bad_commit(log: &str) -> Option<Oid> {
+    log.lines().rev().find_map(|line| {
+        let sha = line
+            .strip_prefix("# first bad commit: [")?
+            .split(']')
+            .next()?;
+        Oid::from_str(sha).ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sha(digit: char) -> String {
+        digit.to_string().repeat(40)
+    }
+
+    fn oid(digit: char) -> Oid {
+        Oid::from_str(&sha(digit)).unwrap()
+    }
+
+    #[test]
+    fn test_parse_bisect_refs() {
+        let output = format!(
+            "refs/bisect/bad {a}\n\
+             refs/bisect/good-{b} {b}\n\
+             refs/bisect/good-{c} {c}\n\
+             refs/bisect/skip-{d} {d}\n",
+            a = sha('a'),
+            b = sha('b'),
+            c = sha('c'),
+            d = sha('d'),
+        );
+        let mut status = BisectStatus::default();
+        parse_bisect_refs(&output, &mut status);
+        assert_eq!(
+            status,
+            BisectStatus {
+                bad: Some(oid('a')),
+                good: vec![oid('b'), oid('c')],
+                skipped: vec![oid('d')],
+                ..BisectStatus::default()
+            }
+        );
+        assert!(!status.needs_bounds());
+    }
+
+    #[test]
+    fn test_parse_bisect_steps() {
+        let output = "bisect_rev='a9d6876e67ff33056f0d456331901c8b62b67d8a'\n\
+                      bisect_nr=3\n\
+                      bisect_good=3\n\
+                      bisect_bad=2\n\
+                      bisect_all=7\n\
+                      bisect_steps=2\n";
+        assert_eq!(parse_bisect_steps(output), Some(2));
+        assert_eq!(parse_bisect_steps(""), None);
+    }
+
+    #[test]
+    fn test_parse_first_bad_commit() {
+        let log = format!(
+            "git bisect start\n\
+             # bad: [{a}] Break everything\n\
+             git bisect bad {a}\n\
+             # good: [{b}] Fix a typo\n\
+             git bisect good {b}\n",
+            a = sha('a'),
+            b = sha('b'),
+        );
+        assert_eq!(parse_first_bad_commit(&log), None);
+
+        let log = format!("{log}# first bad commit: [{}] Break everything\n", sha('a'));
+        assert_eq!(parse_first_bad_commit(&log), Some(oid('a')));
+    }
+}
@@ -1,10 +1,13 @@
+pub mod bisect;
 pub mod blame;
 pub mod commit;
 mod hosting_provider;
+pub mod rebase;
 mod remote;
 pub mod repository;
 pub mod stash;
 pub mod status;
+pub mod submodule;
 
 pub use crate::hosting_provider::*;
 pub use crate::remote::*;
@@ -79,6 +82,33 @@ actions!(
         Pull,
         /// Pulls changes from the remote repository with rebase.
         PullRebase,
+        /// Opens the interactive rebase editor for the commits on the current
+        /// branch that aren't on its upstream.
+        InteractiveRebase,
+        /// Continues a rebase that stopped at a conflict or at a commit marked for editing.
+        RebaseContinue,
+        /// Skips the commit a rebase stopped at and continues with the next one.
+        RebaseSkip,
+        /// Aborts the rebase in progress, restoring the branch to where it started.
+        RebaseAbort,
+        /// Marks the commit being tested as good, starting a bisect if none is in progress.
+        BisectGood,
+        /// Marks the commit being tested as bad, starting a bisect if none is in progress.
+        BisectBad,
+        /// Skips the commit being tested in the bisect in progress.
+        BisectSkip,
+        /// Runs a task at each step of the bisect in progress, using its exit code to mark commits.
+        BisectRun,
+        /// Ends the bisect in progress and checks out the commit it started from.
+        BisectReset,
+        /// Checks out the commits recorded for the submodules, initializing them if needed.
+        UpdateSubmodules,
+        /// Registers the submodules listed in `.gitmodules` in the repository's config.
+        InitSubmodules,
+        /// Copies the submodules' URLs from `.gitmodules` into the repository's config.
+        SyncSubmodules,
+        /// Opens the conflicted file in a three-way merge editor.
+        OpenMergeEditor,
         /// Fetches changes from the remote repository.
         Fetch,
         /// Fetches changes from a specific remote.
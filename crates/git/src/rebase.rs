@@ -0,0 +1,318 @@
+use crate::Oid;
+use anyhow::{Context as _, Result, anyhow};
+use gpui::SharedString;
+use std::{path::Path, str::FromStr};
+use util::shell::ShellKind;
+
+/// What an interactive rebase does with a commit from its todo list.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum RebaseAction {
+    #[default]
+    Pick,
+    Reword,
+    Edit,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl RebaseAction {
+    pub const ALL: [Self; 6] = [
+        Self::Pick,
+        Self::Reword,
+        Self::Edit,
+        Self::Squash,
+        Self::Fixup,
+        Self::Drop,
+    ];
+
+    /// The todo list command for this action.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pick => "pick",
+            Self::Reword => "reword",
+            Self::Edit => "edit",
+            Self::Squash => "squash",
+            Self::Fixup => "fixup",
+            Self::Drop => "drop",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Pick => "Pick",
+            Self::Reword => "Reword",
+            Self::Edit => "Edit",
+            Self::Squash => "Squash",
+            Self::Fixup => "Fixup",
+            Self::Drop => "Drop",
+        }
+    }
+
+    /// Whether the commit is folded into the commit before it.
+    pub fn melds_into_previous(self) -> bool {
+        matches!(self, Self::Squash | Self::Fixup)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RebaseTodoEntry {
+    pub action: RebaseAction,
+    pub sha: Oid,
+    pub subject: SharedString,
+    /// The new message of a reworded commit. Without one, the commit keeps its message.
+    pub message: Option<String>,
+}
+
+/// Parses the output of `git log --format=%H%x00%s` into a todo list that picks every commit.
+pub fn parse_rebase_todo(output: &str) -> Result<Vec<RebaseTodoEntry>> {
+    output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (sha, subject) = line
+                .split_once('\0')
+                .with_context(|| format!("malformed rebase todo line: {line:?}"))?;
+            Ok(RebaseTodoEntry {
+                action: RebaseAction::Pick,
+                sha: Oid::from_str(sha)?,
+                subject: subject.to_string().into(),
+                message: None,
+            })
+        })
+        .collect()
+}
+
+/// Renders a todo list in the format `git rebase -i` reads from its sequence editor.
+///
+/// Git would open an editor to reword a commit, so a new message is applied by an `exec` line
+/// that amends the commit once any squashes and fixups into it have been applied.
+pub fn rebase_todo_script(entries: &[RebaseTodoEntry]) -> Result<String> {
+    let mut script = String::new();
+    let mut pending_message: Option<&str> = None;
+    let mut has_kept_commit = false;
+
+    for entry in entries {
+        if entry.action == RebaseAction::Drop {
+            script.push_str(&format!("drop {}\n", entry.sha));
+            continue;
+        }
+
+        if entry.action.melds_into_previous() {
+            if !has_kept_commit {
+                return Err(anyhow!(
+                    "Can't {} {} into a previous commit because it is the first commit in the rebase",
+                    entry.action.as_str(),
+                    entry.sha.display_short()
+                ));
+            }
+        } else if let Some(message) = pending_message.take() {
+            push_amend_message(&mut script, message)?;
+        }
+        has_kept_commit = true;
+
+        let command = if entry.action == RebaseAction::Reword {
+            pending_message = entry.message.as_deref();
+            RebaseAction::Pick.as_str()
+        } else {
+            entry.action.as_str()
+        };
+        script.push_str(&format!("{command} {} {}\n", entry.sha, entry.subject));
+    }
+
+    if let Some(message) = pending_message {
+        push_amend_message(&mut script, message)?;
+    }
+
+    Ok(script)
+}
+
+fn push_amend_message(script: &mut String, message: &str) -> Result<()> {
+    script.push_str("exec printf '%s\\n'");
+    for line in message.trim_end().lines() {
+        let line = ShellKind::Posix
+            .try_quote(line)
+            .with_context(|| format!("can't quote commit message line {line:?}"))?;
+        script.push(' ');
+        script.push_str(&line);
+    }
+    script.push_str(" | git commit --amend --only --no-verify --file=-\n");
+    Ok(())
+}
+
+/// How to resume a rebase that stopped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RebaseControl {
+    Continue,
+    Skip,
+    Abort,
+}
+
+impl RebaseControl {
+    pub fn as_arg(self) -> &'static str {
+        match self {
+            Self::Continue => "--continue",
+            Self::Skip => "--skip",
+            Self::Abort => "--abort",
+        }
+    }
+}
+
+/// The state of a rebase that stopped partway, either at a conflict or at a commit marked
+/// for editing.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RebaseProgress {
+    /// The number of todo steps applied so far, including the one the rebase stopped at.
+    pub done: usize,
+    pub total: usize,
+    /// The commit the rebase stopped at.
+    pub stopped_at: Option<Oid>,
+    /// The branch being rebased, if the rebase didn't start from a detached HEAD.
+    pub branch: Option<SharedString>,
+}
+
+impl RebaseProgress {
+    /// Reads the progress of an in-progress rebase from the repository's Git directory.
+    pub fn load(git_dir: &Path) -> Option<Self> {
+        let read = |path: &Path| {
+            std::fs::read_to_string(path)
+                .ok()
+                .map(|contents| contents.trim().to_string())
+        };
+
+        // Interactive and merge-based rebases keep their state in `rebase-merge`, while
+        // `git am`-based rebases use `rebase-apply`.
+        let (state_dir, done_file, total_file, stopped_file) =
+            if git_dir.join("rebase-merge").is_dir() {
+                (git_dir.join("rebase-merge"), "msgnum", "end", "stopped-sha")
+            } else if git_dir.join("rebase-apply").join("rebasing").exists() {
+                (
+                    git_dir.join("rebase-apply"),
+                    "next",
+                    "last",
+                    "original-commit",
+                )
+            } else {
+                return None;
+            };
+
+        let done = read(&state_dir.join(done_file))
+            .and_then(|done| done.parse().ok())
+            .unwrap_or_default();
+        let total = read(&state_dir.join(total_file))
+            .and_then(|total| total.parse().ok())
+            .unwrap_or_default();
+        let stopped_at = read(&state_dir.join(stopped_file)).and_then(|sha| sha.parse().ok());
+        let branch = read(&state_dir.join("head-name")).and_then(|head| {
+            head.strip_prefix("refs/heads/")
+                .map(|branch| SharedString::from(branch.to_string()))
+        });
+
+        Some(Self {
+            done,
+            total,
+            stopped_at,
+            branch,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sha(digit: char) -> String {
+        digit.to_string().repeat(40)
+    }
+
+    fn entry(action: RebaseAction, digit: char, subject: &str) -> RebaseTodoEntry {
+        RebaseTodoEntry {
+            action,
+            sha: Oid::from_str(&sha(digit)).unwrap(),
+            subject: subject.to_string().into(),
+            message: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_rebase_todo() {
+        let output = format!(
+            "{}\0First commit\n{}\0Second: with a colon\n",
+            sha('a'),
+            sha('b')
+        );
+        let entries = parse_rebase_todo(&output).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                entry(RebaseAction::Pick, 'a', "First commit"),
+                entry(RebaseAction::Pick, 'b', "Second: with a colon"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rebase_todo_script() {
+        let mut reword = entry(RebaseAction::Reword, 'b', "Second");
+        reword.message = Some("Second, reworded\n\nWith a body\n".into());
+        let entries = [
+            entry(RebaseAction::Pick, 'a', "First"),
+            reword,
+            entry(RebaseAction::Fixup, 'c', "Fix second"),
+            entry(RebaseAction::Drop, 'd', "Unwanted"),
+            entry(RebaseAction::Edit, 'e', "Fifth"),
+            entry(RebaseAction::Squash, 'f', "Sixth"),
+        ];
+
+        pretty_assertions::assert_eq!(
+            rebase_todo_script(&entries).unwrap(),
+            format!(
+                "pick {} First\n\
+                 pick {} Second\n\
+                 fixup {} Fix second\n\
+                 exec printf '%s\\n' 'Second, reworded' '' 'With a body' | git commit --amend --only --no-verify --file=-\n\
+                 drop {}\n\
+                 edit {} Fifth\n\
+                 squash {} Sixth\n",
+                sha('a'),
+                sha('b'),
+                sha('c'),
+                sha('d'),
+                sha('e'),
+                sha('f'),
+            )
+        );
+    }
+
+    #[test]
+    fn test_rebase_todo_script_rejects_leading_squash() {
+        let entries = [
+            entry(RebaseAction::Drop, 'a', "First"),
+            entry(RebaseAction::Squash, 'b', "Second"),
+        ];
+        assert!(rebase_todo_script(&entries).is_err());
+    }
+
+    #[test]
+    fn test_rebase_progress_load() {
+        let git_dir = tempfile::tempdir().unwrap();
+        assert_eq!(RebaseProgress::load(git_dir.path()), None);
+
+        let state_dir = git_dir.path().join("rebase-merge");
+        std::fs::create_dir(&state_dir).unwrap();
+        std::fs::write(state_dir.join("msgnum"), "2\n").unwrap();
+        std::fs::write(state_dir.join("end"), "5\n").unwrap();
+        std::fs::write(state_dir.join("stopped-sha"), format!("{}\n", sha('e'))).unwrap();
+        std::fs::write(state_dir.join("head-name"), "refs/heads/feature\n").unwrap();
+
+        assert_eq!(
+            RebaseProgress::load(git_dir.path()),
+            Some(RebaseProgress {
+                done: 2,
+                total: 5,
+                stopped_at: Some(Oid::from_str(&sha('e')).unwrap()),
+                branch: Some("feature".into()),
+            })
+        );
+    }
+}
@@ -1,6 +1,16 @@
+use crate::bisect::{
+    BisectStatus, BisectTerm, parse_bisect_refs, parse_bisect_steps, parse_first_bad_commit,
+};
 use crate::commit::parse_git_diff_name_status;
+use crate::rebase::{
+    RebaseControl, RebaseProgress, RebaseTodoEntry, parse_rebase_todo, rebase_todo_script,
+};
 use crate::stash::GitStash;
 use crate::status::{DiffTreeType, GitStatus, StatusCode, TreeDiff};
+use crate::submodule::{
+    Submodule, SubmoduleCommand, SubmoduleLog, SubmoduleState, parse_gitlinks,
+    parse_index_gitlinks, parse_submodule_log,
+};
 use crate::{Oid, RunHook, SHORT_SHA_LENGTH};
 use anyhow::{Context as _, Result, anyhow, bail};
 use async_channel::Sender;
@@ -34,6 +44,7 @@ use thiserror::Error;
 use util::command::{Stdio, new_command};
 use util::paths::PathStyle;
 use util::rel_path::RelPath;
+use util::shell::ShellKind;
 use util::{ResultExt, paths};
 use uuid::Uuid;
 
@@ -516,6 +527,19 @@ pub struct CommitDiff {
     pub files: Vec<CommitFile>,
 }
 
+/// The versions of a conflicted file recorded in the index during a merge.
+///
+/// A side is `None` when it has no entry for the file, such as when one side deleted it.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ConflictStages {
+    /// The version in the merge base (stage 1).
+    pub base: Option<String>,
+    /// The version on the current branch (stage 2).
+    pub ours: Option<String>,
+    /// The version being merged in (stage 3).
+    pub theirs: Option<String>,
+}
+
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct FileHistoryChangedFileSets {
     pub file_sets: Vec<Vec<RepoPath>>,
@@ -564,6 +588,7 @@ pub struct Remote {
     pub name: SharedString,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ResetMode {
     /// Reset the branch pointer, leave index and worktree unchanged (this will make it look like things that were
     /// committed are now staged).
@@ -571,6 +596,8 @@ pub enum ResetMode {
     /// Reset the branch pointer and index, leave worktree unchanged (this makes it look as though things that were
     /// committed are now unstaged).
     Mixed,
+    /// Reset the branch pointer, index and worktree, discarding any uncommitted changes.
+    Hard,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -787,6 +814,9 @@ pub trait GitRepository: Send + Sync {
     ///
     /// Also returns `None` for symlinks.
     fn load_committed_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>>;
+
+    /// Returns the base, ours and theirs versions of a conflicted file from the index.
+    fn load_conflict_stages(&self, path: RepoPath) -> BoxFuture<'_, Result<ConflictStages>>;
     fn load_blob_content(&self, oid: Oid) -> BoxFuture<'_, Result<String>>;
 
     fn set_index_text(
@@ -862,6 +892,32 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Applies the changes introduced by each of `commits` on top of HEAD. A commit may also
+    /// be a range such as `a^..b`.
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Creates a commit that undoes the changes introduced by `commit`.
+    fn revert(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Creates a tag pointing at `commit`, annotated when a message is given.
+    fn create_tag(
+        &self,
+        name: String,
+        commit: String,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>>;
+
     fn checkout_files(
         &self,
         commit: String,
@@ -869,6 +925,67 @@ pub trait GitRepository: Send + Sync {
         env: Arc<HashMap<String, String>>,
     ) -> BoxFuture<'_, Result<()>>;
 
+    /// Lists the commits after `base` up to `HEAD`, oldest first, as a todo list that picks
+    /// each of them. Without a base, every commit reachable from `HEAD` is listed.
+    fn rebase_todo(&self, base: Option<String>) -> BoxFuture<'_, Result<Vec<RebaseTodoEntry>>>;
+
+    /// Runs `git rebase --interactive` onto `base` with the given todo list, or onto the root
+    /// commit without a base.
+    ///
+    /// Stopping at a conflict or at a commit marked for editing isn't an error; it is reported
+    /// by [`GitRepository::rebase_progress`] instead.
+    fn rebase_interactive(
+        &self,
+        base: Option<String>,
+        entries: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    fn rebase_control(
+        &self,
+        control: RebaseControl,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the progress of the rebase in progress, if any.
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>>;
+
+    fn bisect_start(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    /// Marks `commit`, or the commit being tested without one, as good, bad or skipped.
+    fn bisect_mark(
+        &self,
+        term: BisectTerm,
+        commit: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Ends the bisect, checking out the branch it started from.
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the state of the bisect in progress, if any.
+    fn bisect_status(&self) -> BoxFuture<'_, Result<Option<BisectStatus>>>;
+
+    /// Returns the submodules declared in `.gitmodules`, with the commits recorded for them.
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>>;
+
+    /// Runs `git submodule init`, `update` or `sync` on the given submodules, or on all of them
+    /// when `paths` is empty.
+    fn submodule_command(
+        &self,
+        command: SubmoduleCommand,
+        paths: Vec<RepoPath>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>>;
+
+    /// Returns the commits between two revisions of the submodule at `path`.
+    fn submodule_log(
+        &self,
+        path: RepoPath,
+        old_sha: Oid,
+        new_sha: Oid,
+    ) -> BoxFuture<'_, Result<SubmoduleLog>>;
+
     fn show(&self, commit: String) -> BoxFuture<'_, Result<CommitDetails>>;
 
     fn load_commit(&self, commit: String, cx: AsyncApp) -> BoxFuture<'_, Result<CommitDiff>>;
@@ -1490,6 +1607,7 @@ impl GitRepository for RealGitRepository {
             let mode_flag = match mode {
                 ResetMode::Mixed => "--mixed",
                 ResetMode::Soft => "--soft",
+                ResetMode::Hard => "--hard",
             };
 
             let output = git
@@ -1507,6 +1625,83 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
+    fn cherry_pick(
+        &self,
+        commits: Vec<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git = self.git_binary_in_worktree();
+        async move {
+            let mut args = vec!["cherry-pick"];
+            args.extend(commits.iter().map(String::as_str));
+            let output = git?.build_command(&args).envs(env.iter()).output().await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to cherry-pick:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn revert(
+        &self,
+        commit: String,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git = self.git_binary_in_worktree();
+        async move {
+            let output = git?
+                .build_command(&["revert", "--no-edit", &commit])
+                .envs(env.iter())
+                .output()
+                .await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to revert:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn create_tag(
+        &self,
+        name: String,
+        commit: String,
+        message: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git = self.git_binary_in_worktree();
+        async move {
+            let mut args = vec!["tag"];
+            if let Some(message) = message.as_deref() {
+                args.extend(["--annotate", "--message", message]);
+            }
+            args.extend(["--", name.as_str(), commit.as_str()]);
+            let output = git?.build_command(&args).envs(env.iter()).output().await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to create tag:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn delete_tag(&self, name: String) -> BoxFuture<'_, Result<()>> {
+        let git_binary = self.git_binary_in_worktree();
+        self.executor
+            .spawn(async move {
+                git_binary?.run(&["tag", "--delete", "--", &name]).await?;
+                anyhow::Ok(())
+            })
+            .boxed()
+    }
+
     fn checkout_files(
         &self,
         commit: String,
@@ -1536,6 +1731,354 @@ impl GitRepository for RealGitRepository {
         .boxed()
     }
 
+    fn rebase_todo(&self, base: Option<String>) -> BoxFuture<'_, Result<Vec<RebaseTodoEntry>>> {
+        let git = self.git_binary_in_worktree();
+        async move {
+            let git = git?;
+            let range = match base {
+                Some(base) => format!("{base}..HEAD"),
+                None => "HEAD".to_string(),
+            };
+            let output = git
+                .run(&[
+                    "log",
+                    "--reverse",
+                    "--topo-order",
+                    "--no-merges",
+                    "--format=%H%x00%s",
+                    &range,
+                ])
+                .await?;
+            parse_rebase_todo(&output)
+        }
+        .boxed()
+    }
+
+    fn rebase_interactive(
+        &self,
+        base: Option<String>,
+        entries: Vec<RebaseTodoEntry>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git = self.git_binary_in_worktree();
+        let git_dir = self.path();
+        async move {
+            let git = git?;
+            let script = rebase_todo_script(&entries)?;
+
+            // Git opens the todo list in `GIT_SEQUENCE_EDITOR`, which we point at a command
+            // that overwrites it with ours.
+            let todo_path = git_dir.join(format!("rebase-todo-{}.tmp", Uuid::new_v4()));
+            smol::fs::write(&todo_path, script).await?;
+            let sequence_editor = ShellKind::Posix
+                .try_quote(&todo_path.to_string_lossy())
+                .map(|todo_path| format!("cp {todo_path}"))
+                .context("can't quote the rebase todo path")?;
+
+            let mut args = vec!["rebase", "--interactive"];
+            match base.as_deref() {
+                Some(base) => args.push(base),
+                None => args.push("--root"),
+            }
+            let output = git
+                .build_command(&args)
+                .envs(env.iter())
+                .env("GIT_SEQUENCE_EDITOR", sequence_editor)
+                // Squashes keep their combined message rather than opening an editor.
+                .env("GIT_EDITOR", ":")
+                .output()
+                .await;
+            smol::fs::remove_file(&todo_path).await.log_err();
+
+            let output = output?;
+            if !output.status.success() && RebaseProgress::load(&git_dir).is_none() {
+                bail!(
+                    "Failed to rebase:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                );
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn rebase_control(
+        &self,
+        control: RebaseControl,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git = self.git_binary_in_worktree();
+        let git_dir = self.path();
+        async move {
+            let git = git?;
+            let progress_before = RebaseProgress::load(&git_dir);
+            let output = git
+                .build_command(&["rebase", control.as_arg()])
+                .envs(env.iter())
+                .env("GIT_EDITOR", ":")
+                .output()
+                .await?;
+
+            // Stopping again at a later commit isn't an error, but failing to move past the
+            // current one is.
+            if !output.status.success() {
+                let progress_after = RebaseProgress::load(&git_dir);
+                if progress_after.is_none() || progress_after == progress_before {
+                    bail!(
+                        "Failed to {} the rebase:\n{}",
+                        control.as_arg().trim_start_matches('-'),
+                        String::from_utf8_lossy(&output.stderr)
+                    );
+                }
+            }
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn rebase_progress(&self) -> BoxFuture<'_, Option<RebaseProgress>> {
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move { RebaseProgress::load(&git_dir) })
+            .boxed()
+    }
+
+    fn bisect_start(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        let git = self.git_binary_in_worktree();
+        async move {
+            let output = git?
+                .build_command(&["bisect", "start"])
+                .envs(env.iter())
+                .output()
+                .await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to start bisecting:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn bisect_mark(
+        &self,
+        term: BisectTerm,
+        commit: Option<String>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git = self.git_binary_in_worktree();
+        async move {
+            let mut args = vec!["bisect", term.as_str()];
+            args.extend(commit.as_deref());
+            let output = git?.build_command(&args).envs(env.iter()).output().await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to mark the commit as {}:\n{}",
+                term.as_str(),
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn bisect_reset(&self, env: Arc<HashMap<String, String>>) -> BoxFuture<'_, Result<()>> {
+        let git = self.git_binary_in_worktree();
+        async move {
+            let output = git?
+                .build_command(&["bisect", "reset"])
+                .envs(env.iter())
+                .output()
+                .await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to reset the bisect:\n{}",
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn bisect_status(&self) -> BoxFuture<'_, Result<Option<BisectStatus>>> {
+        let git = self.git_binary_in_worktree();
+        let git_dir = self.path();
+        self.executor
+            .spawn(async move {
+                if !git_dir.join("BISECT_START").exists() {
+                    return Ok(None);
+                }
+                let git = git?;
+
+                let mut status = BisectStatus::default();
+                let refs = git
+                    .run(&[
+                        "for-each-ref",
+                        "--format=%(refname) %(objectname)",
+                        "refs/bisect",
+                    ])
+                    .await?;
+                parse_bisect_refs(&refs, &mut status);
+                status.current = git
+                    .run(&["rev-parse", "HEAD"])
+                    .await
+                    .ok()
+                    .and_then(|sha| sha.parse().ok());
+                status.first_bad = std::fs::read_to_string(git_dir.join("BISECT_LOG"))
+                    .ok()
+                    .and_then(|log| parse_first_bad_commit(&log));
+
+                if status.first_bad.is_none()
+                    && let Some(bad) = status.bad.filter(|_| !status.good.is_empty())
+                {
+                    let mut args = vec!["rev-list".to_string(), "--bisect-vars".to_string()];
+                    args.push(bad.to_string());
+                    args.push("--not".to_string());
+                    args.extend(status.good.iter().map(|sha| sha.to_string()));
+                    status.remaining_steps = parse_bisect_steps(&git.run(&args).await?);
+                }
+
+                Ok(Some(status))
+            })
+            .boxed()
+    }
+
+    fn submodules(&self) -> BoxFuture<'_, Result<Vec<Submodule>>> {
+        let git = self.git_binary_in_worktree();
+        let working_directory = self.working_directory.clone();
+        self.executor
+            .spawn(async move {
+                let Some(working_directory) = working_directory else {
+                    return Ok(Vec::new());
+                };
+                if !working_directory.join(".gitmodules").exists() {
+                    return Ok(Vec::new());
+                }
+                let git = git?;
+
+                // Conflicted submodules have an entry for each side of the merge.
+                let mut index_gitlinks = Vec::<(RepoPath, Option<Oid>)>::new();
+                for (path, sha, stage) in
+                    parse_index_gitlinks(&git.run(&["ls-files", "--stage", "-z"]).await?)
+                {
+                    match index_gitlinks.last_mut() {
+                        Some((last_path, index_sha)) if *last_path == path => *index_sha = None,
+                        _ => index_gitlinks.push((path, (stage == 0).then_some(sha))),
+                    }
+                }
+                if index_gitlinks.is_empty() {
+                    return Ok(Vec::new());
+                }
+
+                let mut args = vec!["ls-tree", "-z", "HEAD", "--"];
+                args.extend(index_gitlinks.iter().map(|(path, _)| path.as_unix_str()));
+                // An unborn HEAD has no recorded submodule commits.
+                let head_shas = git
+                    .run(&args)
+                    .await
+                    .map(|output| {
+                        parse_gitlinks(&output)
+                            .into_iter()
+                            .collect::<HashMap<_, _>>()
+                    })
+                    .unwrap_or_default();
+
+                let mut submodules = Vec::with_capacity(index_gitlinks.len());
+                for (path, index_sha) in index_gitlinks {
+                    // Without a `.git` entry the submodule isn't initialized, and running git in
+                    // its empty directory would read the superproject's HEAD instead.
+                    let checked_out_sha = if working_directory
+                        .join(path.as_std_path())
+                        .join(".git")
+                        .exists()
+                    {
+                        git.run(&["-C", path.as_unix_str(), "rev-parse", "--verify", "HEAD"])
+                            .await
+                            .ok()
+                            .and_then(|sha| Oid::from_str(&sha).ok())
+                    } else {
+                        None
+                    };
+                    let state = match (index_sha, checked_out_sha) {
+                        (None, _) => SubmoduleState::Conflicted,
+                        (Some(_), None) => SubmoduleState::Uninitialized,
+                        (Some(index_sha), Some(checked_out_sha))
+                            if index_sha == checked_out_sha =>
+                        {
+                            SubmoduleState::UpToDate
+                        }
+                        (Some(_), Some(_)) => SubmoduleState::Modified,
+                    };
+                    submodules.push(Submodule {
+                        head_sha: head_shas.get(&path).copied(),
+                        index_sha,
+                        checked_out_sha,
+                        path,
+                        state,
+                    });
+                }
+                Ok(submodules)
+            })
+            .boxed()
+    }
+
+    fn submodule_command(
+        &self,
+        command: SubmoduleCommand,
+        paths: Vec<RepoPath>,
+        env: Arc<HashMap<String, String>>,
+    ) -> BoxFuture<'_, Result<()>> {
+        let git = self.git_binary_in_worktree();
+        async move {
+            let mut args = command.args().to_vec();
+            args.extend(paths.iter().map(|path| path.as_unix_str()));
+            let output = git?.build_command(&args).envs(env.iter()).output().await?;
+            anyhow::ensure!(
+                output.status.success(),
+                "Failed to {} submodules:\n{}",
+                command.as_str(),
+                String::from_utf8_lossy(&output.stderr),
+            );
+            Ok(())
+        }
+        .boxed()
+    }
+
+    fn submodule_log(
+        &self,
+        path: RepoPath,
+        old_sha: Oid,
+        new_sha: Oid,
+    ) -> BoxFuture<'_, Result<SubmoduleLog>> {
+        let git = self.git_binary_in_worktree();
+        self.executor
+            .spawn(async move {
+                let git = git?;
+                let log = async |range: String| {
+                    let output = git
+                        .run(&[
+                            "-C",
+                            path.as_unix_str(),
+                            "log",
+                            "--format=%H%x00%s",
+                            &range,
+                            "--",
+                        ])
+                        .await
+                        .with_context(|| {
+                            format!("Failed to read the log of submodule {}", path.as_unix_str())
+                        })?;
+                    anyhow::Ok(parse_submodule_log(&output))
+                };
+                Ok(SubmoduleLog {
+                    added: log(format!("{old_sha}..{new_sha}")).await?,
+                    removed: log(format!("{new_sha}..{old_sha}")).await?,
+                })
+            })
+            .boxed()
+    }
+
     fn load_index_text(&self, path: RepoPath) -> BoxFuture<'_, Option<String>> {
         let git_binary = self.git_binary();
         let path_str = format!(":{}", path.as_unix_str());
@@ -1577,6 +2120,37 @@ impl GitRepository for RealGitRepository {
             .boxed()
     }
 
+    fn load_conflict_stages(&self, path: RepoPath) -> BoxFuture<'_, Result<ConflictStages>> {
+        let git = self.git_binary();
+        self.executor
+            .spawn(async move {
+                let output = git
+                    .build_command(&["ls-files", "--unmerged", "-z", "--", path.as_unix_str()])
+                    .output()
+                    .await?;
+                anyhow::ensure!(
+                    output.status.success(),
+                    "Failed to list the conflict stages of {}:\n{}",
+                    path.as_unix_str(),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+
+                let mut stages = ConflictStages::default();
+                let output = String::from_utf8_lossy(&output.stdout);
+                for (stage, sha) in parse_unmerged_stages(&output) {
+                    let text = git.run_raw(&["cat-file", "blob", &sha.to_string()]).await?;
+                    match stage {
+                        1 => stages.base = Some(text),
+                        2 => stages.ours = Some(text),
+                        3 => stages.theirs = Some(text),
+                        _ => {}
+                    }
+                }
+                Ok(stages)
+            })
+            .boxed()
+    }
+
     fn load_blob_content(&self, oid: Oid) -> BoxFuture<'_, Result<String>> {
         let git_binary = self.git_binary();
         let oid_str = oid.to_string();
@@ -3306,6 +3880,20 @@ fn parse_file_history_changed_files_output(
     histories
 }
 
+/// Parses the output of `git ls-files --unmerged -z`, where each entry is
+/// `<mode> <sha> <stage>\t<path>`, into the stage numbers and blob shas.
+fn parse_unmerged_stages(output: &str) -> Vec<(u8, Oid)> {
+    output
+        .split('\0')
+        .filter_map(|entry| {
+            let (info, _path) = entry.split_once('\t')?;
+            let mut fields = info.split(' ');
+            let (_mode, sha, stage) = (fields.next()?, fields.next()?, fields.next()?);
+            Some((stage.parse().ok()?, Oid::from_str(sha).ok()?))
+        })
+        .collect()
+}
+
 fn parse_initial_graph_output<'a>(
     lines: impl Iterator<Item = &'a str>,
 ) -> Vec<Arc<InitialGraphCommitData>> {
@@ -3813,6 +4401,7 @@ mod tests {
     };
 
     use super::*;
+    use crate::rebase::RebaseAction;
     use gpui::TestAppContext;
 
     fn disable_git_global_config() {
@@ -5086,6 +5675,373 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_rebase_interactive(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git_init_repo(repo_dir.path());
+        for name in ["one", "two", "three", "four"] {
+            fs::write(repo_dir.path().join(name), name).unwrap();
+            git_command(repo_dir.path(), ["add", name]);
+            git_command(repo_dir.path(), ["commit", "-m", &format!("Add {name}")]);
+        }
+
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(test_commit_envs());
+
+        let base = repo.revparse_batch(vec!["HEAD~3".into()]).await.unwrap()[0].clone();
+        let mut entries = repo.rebase_todo(base.clone()).await.unwrap();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.subject.as_ref())
+                .collect::<Vec<_>>(),
+            ["Add two", "Add three", "Add four"]
+        );
+
+        entries[0].action = RebaseAction::Reword;
+        entries[0].message = Some("Add two and three".into());
+        entries[1].action = RebaseAction::Fixup;
+        entries[2].action = RebaseAction::Edit;
+        repo.rebase_interactive(base, entries, env.clone())
+            .await
+            .unwrap();
+
+        let progress = repo.rebase_progress().await.unwrap();
+        assert_eq!((progress.done, progress.total), (4, 4));
+        assert_eq!(progress.branch.as_deref(), Some("main"));
+
+        repo.rebase_control(RebaseControl::Continue, env)
+            .await
+            .unwrap();
+        assert_eq!(repo.rebase_progress().await, None);
+
+        let log = repo
+            .git_binary()
+            .run(&["log", "--format=%s"])
+            .await
+            .unwrap();
+        assert_eq!(log, "Add four\nAdd two and three\nAdd one");
+    }
+
+    #[gpui::test]
+    async fn test_commit_operations(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git_init_repo(repo_dir.path());
+        fs::write(repo_dir.path().join("a"), "1").unwrap();
+        git_command(repo_dir.path(), ["add", "a"]);
+        git_command(repo_dir.path(), ["commit", "-m", "Add a"]);
+        git_command(repo_dir.path(), ["switch", "-c", "feature"]);
+        for (file, message) in [("b", "Add b"), ("c", "Add c"), ("d", "Add d")] {
+            fs::write(repo_dir.path().join(file), file).unwrap();
+            git_command(repo_dir.path(), ["add", file]);
+            git_command(repo_dir.path(), ["commit", "-m", message]);
+        }
+        git_command(repo_dir.path(), ["switch", "main"]);
+
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(test_commit_envs());
+        let git = repo.git_binary();
+        let subjects = async || git.run(&["log", "--format=%s"]).await.unwrap();
+
+        repo.cherry_pick(vec!["feature~1^..feature".into()], env.clone())
+            .await
+            .unwrap();
+        assert_eq!(subjects().await, "Add d\nAdd c\nAdd a");
+        repo.cherry_pick(vec!["feature~2".into()], env.clone())
+            .await
+            .unwrap();
+        assert_eq!(subjects().await, "Add b\nAdd d\nAdd c\nAdd a");
+
+        repo.revert("HEAD".into(), env.clone()).await.unwrap();
+        assert_eq!(
+            subjects().await,
+            "Revert \"Add b\"\nAdd b\nAdd d\nAdd c\nAdd a"
+        );
+        assert!(!repo_dir.path().join("b").exists());
+
+        repo.create_tag("v1".into(), "HEAD~1".into(), None, env.clone())
+            .await
+            .unwrap();
+        repo.create_tag(
+            "v2".into(),
+            "HEAD".into(),
+            Some("Second release".into()),
+            env.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            git.run(&["tag", "--list", "-n1"]).await.unwrap(),
+            "v1              Add b\nv2              Second release"
+        );
+        repo.delete_tag("v1".into()).await.unwrap();
+        assert_eq!(git.run(&["tag", "--list"]).await.unwrap(), "v2");
+
+        fs::write(repo_dir.path().join("a"), "changed").unwrap();
+        repo.reset("HEAD~2".into(), ResetMode::Hard, env.clone())
+            .await
+            .unwrap();
+        assert_eq!(subjects().await, "Add d\nAdd c\nAdd a");
+        assert_eq!(fs::read_to_string(repo_dir.path().join("a")).unwrap(), "1");
+    }
+
+    #[gpui::test]
+    async fn test_load_conflict_stages(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git_init_repo(repo_dir.path());
+        fs::write(repo_dir.path().join("a"), "base\n").unwrap();
+        fs::write(repo_dir.path().join("b"), "base\n").unwrap();
+        git_command(repo_dir.path(), ["add", "a", "b"]);
+        git_command(repo_dir.path(), ["commit", "-m", "Base"]);
+
+        git_command(repo_dir.path(), ["checkout", "-b", "other"]);
+        fs::write(repo_dir.path().join("a"), "theirs\n").unwrap();
+        git_command(repo_dir.path(), ["rm", "b"]);
+        git_command(repo_dir.path(), ["commit", "-am", "Theirs"]);
+
+        git_command(repo_dir.path(), ["checkout", "main"]);
+        fs::write(repo_dir.path().join("a"), "ours\n").unwrap();
+        fs::write(repo_dir.path().join("b"), "ours\n").unwrap();
+        git_command(repo_dir.path(), ["commit", "-am", "Ours"]);
+
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        repo.git_binary()
+            .run(&["merge", "other"])
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            repo.load_conflict_stages(repo_path("a")).await.unwrap(),
+            ConflictStages {
+                base: Some("base\n".into()),
+                ours: Some("ours\n".into()),
+                theirs: Some("theirs\n".into()),
+            }
+        );
+        assert_eq!(
+            repo.load_conflict_stages(repo_path("b")).await.unwrap(),
+            ConflictStages {
+                base: Some("base\n".into()),
+                ours: Some("ours\n".into()),
+                theirs: None,
+            }
+        );
+    }
+
+    #[gpui::test]
+    async fn test_submodules(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let lib_dir = tempfile::tempdir().unwrap();
+        git_init_repo(lib_dir.path());
+        for ix in 1..=3 {
+            fs::write(lib_dir.path().join("lib"), ix.to_string()).unwrap();
+            git_command(lib_dir.path(), ["add", "lib"]);
+            git_command(lib_dir.path(), ["commit", "-m", &format!("Lib {ix}")]);
+        }
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git_init_repo(repo_dir.path());
+        let lib_url = lib_dir.path().to_str().unwrap();
+        for path in ["libs/my lib", "other"] {
+            git_command(
+                repo_dir.path(),
+                [
+                    "-c",
+                    "protocol.file.allow=always",
+                    "submodule",
+                    "add",
+                    lib_url,
+                    path,
+                ],
+            );
+        }
+        git_command(repo_dir.path(), ["commit", "-m", "Add submodules"]);
+        git_command(repo_dir.path(), ["submodule", "deinit", "other"]);
+        git_command(&repo_dir.path().join("libs/my lib"), ["checkout", "HEAD~2"]);
+
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let git = repo.git_binary();
+        let rev_parse = async |args: &[&str]| Oid::from_str(&git.run(args).await.unwrap()).unwrap();
+        let lib_head = rev_parse(&["rev-parse", "HEAD:libs/my lib"]).await;
+        let lib_first = rev_parse(&["-C", "libs/my lib", "rev-parse", "HEAD"]).await;
+
+        assert_eq!(
+            repo.submodules().await.unwrap(),
+            vec![
+                Submodule {
+                    path: repo_path("libs/my lib"),
+                    head_sha: Some(lib_head),
+                    index_sha: Some(lib_head),
+                    checked_out_sha: Some(lib_first),
+                    state: SubmoduleState::Modified,
+                },
+                Submodule {
+                    path: repo_path("other"),
+                    head_sha: Some(lib_head),
+                    index_sha: Some(lib_head),
+                    checked_out_sha: None,
+                    state: SubmoduleState::Uninitialized,
+                },
+            ]
+        );
+
+        let log = repo
+            .submodule_log(repo_path("libs/my lib"), lib_head, lib_first)
+            .await
+            .unwrap();
+        assert!(log.added.is_empty());
+        assert_eq!(
+            log.removed
+                .iter()
+                .map(|entry| entry.subject.as_ref())
+                .collect::<Vec<_>>(),
+            ["Lib 3", "Lib 2"]
+        );
+
+        // Staging a submodule records its checked-out commit.
+        let env = Arc::new(HashMap::from_iter([
+            ("GIT_CONFIG_COUNT".to_string(), "1".to_string()),
+            (
+                "GIT_CONFIG_KEY_0".to_string(),
+                "protocol.file.allow".to_string(),
+            ),
+            ("GIT_CONFIG_VALUE_0".to_string(), "always".to_string()),
+        ]));
+        repo.stage_paths(vec![repo_path("libs/my lib")], env.clone())
+            .await
+            .unwrap();
+        let submodules = repo.submodules().await.unwrap();
+        assert_eq!(submodules[0].head_sha, Some(lib_head));
+        assert_eq!(submodules[0].index_sha, Some(lib_first));
+        assert_eq!(submodules[0].state, SubmoduleState::UpToDate);
+
+        repo.unstage_paths(vec![repo_path("libs/my lib")], env.clone())
+            .await
+            .unwrap();
+        repo.submodule_command(SubmoduleCommand::Update, Vec::new(), env.clone())
+            .await
+            .unwrap();
+        let submodules = repo.submodules().await.unwrap();
+        assert!(
+            submodules
+                .iter()
+                .all(|submodule| submodule.state == SubmoduleState::UpToDate
+                    && submodule.checked_out_sha == Some(lib_head)),
+            "{submodules:?}"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_bisect(cx: &mut TestAppContext) {
+        disable_git_global_config();
+        cx.executor().allow_parking();
+
+        let repo_dir = tempfile::tempdir().unwrap();
+        git_init_repo(repo_dir.path());
+        for ix in 1..=8 {
+            fs::write(repo_dir.path().join("file"), ix.to_string()).unwrap();
+            git_command(repo_dir.path(), ["add", "file"]);
+            git_command(repo_dir.path(), ["commit", "-m", &format!("Commit {ix}")]);
+        }
+
+        let repo = RealGitRepository::new(
+            &repo_dir.path().join(".git"),
+            None,
+            Some("git".into()),
+            cx.executor(),
+        )
+        .unwrap();
+        let env = Arc::new(test_commit_envs());
+        let git = repo.git_binary();
+        let sha = async |rev: &str| -> Oid {
+            git.run(&["rev-parse", rev]).await.unwrap().parse().unwrap()
+        };
+        assert_eq!(repo.bisect_status().await.unwrap(), None);
+
+        // Commit 3 introduced the bug.
+        let culprit = sha("HEAD~5").await;
+        repo.bisect_start(env.clone()).await.unwrap();
+        repo.bisect_mark(BisectTerm::Bad, None, env.clone())
+            .await
+            .unwrap();
+        repo.bisect_mark(BisectTerm::Good, Some("HEAD~7".into()), env.clone())
+            .await
+            .unwrap();
+
+        let status = repo.bisect_status().await.unwrap().unwrap();
+        assert_eq!(status.good, [sha("main~7").await]);
+        assert_eq!(status.bad, Some(sha("main").await));
+        assert_eq!(status.remaining_steps, Some(2));
+        assert_eq!(status.first_bad, None);
+
+        let mut steps = 0;
+        let first_bad = loop {
+            let status = repo.bisect_status().await.unwrap().unwrap();
+            if let Some(found) = status.first_bad {
+                break found;
+            }
+            let current = status.current.unwrap().to_string();
+            let culprit = culprit.to_string();
+            let is_bad = git
+                .run(&[
+                    "merge-base",
+                    "--is-ancestor",
+                    culprit.as_str(),
+                    current.as_str(),
+                ])
+                .await
+                .is_ok();
+            let term = if is_bad {
+                BisectTerm::Bad
+            } else {
+                BisectTerm::Good
+            };
+            repo.bisect_mark(term, None, env.clone()).await.unwrap();
+            steps += 1;
+            assert!(steps < 8, "bisect didn't converge");
+        };
+        assert_eq!(first_bad, culprit);
+
+        repo.bisect_reset(env).await.unwrap();
+        assert_eq!(repo.bisect_status().await.unwrap(), None);
+        assert_eq!(sha("HEAD").await, sha("main").await);
+    }
+
     #[gpui::test]
     async fn test_initial_graph_data_ref_set(cx: &mut TestAppContext) {
         disable_git_global_config();
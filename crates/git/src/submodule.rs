@@ -0,0 +1,199 @@
+use crate::{Oid, repository::RepoPath};
+use gpui::SharedString;
+use std::str::FromStr;
+
+/// How a submodule's checkout relates to the commit recorded for it in the superproject.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SubmoduleState {
+    /// The submodule has not been initialized, so nothing is checked out.
+    Uninitialized,
+    /// The checked-out commit matches the one recorded in the index.
+    UpToDate,
+    /// A different commit is checked out than the one recorded in the index.
+    Modified,
+    /// The recorded commit has merge conflicts.
+    Conflicted,
+}
+
+/// A submodule of a repository, along with the commits recorded for it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Submodule {
+    pub path: RepoPath,
+    /// The commit recorded for the submodule in `HEAD`.
+    pub head_sha: Option<Oid>,
+    /// The commit recorded for the submodule in the index.
+    pub index_sha: Option<Oid>,
+    /// The commit checked out in the submodule, if it has been initialized.
+    pub checked_out_sha: Option<Oid>,
+    pub state: SubmoduleState,
+}
+
+/// A `git submodule` subcommand that acts on a set of submodules.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SubmoduleCommand {
+    /// Registers the submodules' URLs in the repository's config.
+    Init,
+    /// Initializes the submodules if needed and checks out their recorded commits.
+    Update,
+    /// Copies the submodules' URLs from `.gitmodules` into the repository's config.
+    Sync,
+}
+
+impl SubmoduleCommand {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Init => "init",
+            Self::Update => "update",
+            Self::Sync => "sync",
+        }
+    }
+
+    /// The arguments to `git`, before the submodule paths.
+    pub fn args(self) -> &'static [&'static str] {
+        match self {
+            Self::Init => &["submodule", "init", "--"],
+            Self::Update => &["submodule", "update", "--init", "--recursive", "--"],
+            Self::Sync => &["submodule", "sync", "--recursive", "--"],
+        }
+    }
+}
+
+/// The commits between two revisions of a submodule.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SubmoduleLog {
+    /// Commits reachable from the new revision but not the old one, newest first.
+    pub added: Vec<SubmoduleLogEntry>,
+    /// Commits reachable from the old revision but not the new one, newest first.
+    pub removed: Vec<SubmoduleLogEntry>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SubmoduleLogEntry {
+    pub sha: Oid,
+    pub subject: SharedString,
+}
+
+/// Parses the gitlink entries from the output of `git ls-files --stage -z`, which prints the
+/// mode, commit, merge stage and path of each index entry.
+///
+/// Returns the submodule paths in index order along with their recorded commits and stages. A
+/// conflicted submodule has one entry per stage.
+pub fn parse_index_gitlinks(output: &str) -> Vec<(RepoPath, Oid, u8)> {
+    output
+        .split('\0')
+        .filter_map(|entry| {
+            let (info, path) = entry.split_once('\t')?;
+            let mut fields = info.split(' ');
+            let (mode, sha, stage) = (fields.next()?, fields.next()?, fields.next()?);
+            if mode != "160000" {
+                return None;
+            }
+            Some((
+                RepoPath::new(path).ok()?,
+                Oid::from_str(sha).ok()?,
+                stage.parse().ok()?,
+            ))
+        })
+        .collect()
+}
+
+/// Parses the gitlink entries from the output of `git ls-tree -z <tree> -- <paths>`.
+pub fn parse_gitlinks(output: &str) -> Vec<(RepoPath, Oid)> {
+    output
+        .split('\0')
+        .filter_map(|entry| {
+            let (info, path) = entry.split_once('\t')?;
+            let mut fields = info.split(' ');
+            let (_mode, kind, sha) = (fields.next()?, fields.next()?, fields.next()?);
+            if kind != "commit" {
+                return None;
+            }
+            Some((RepoPath::new(path).ok()?, Oid::from_str(sha).ok()?))
+        })
+        .collect()
+}
+
+/// Parses the output of `git log --format=%H%x00%s`.
+pub fn parse_submodule_log(output: &str) -> Vec<SubmoduleLogEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (sha, subject) = line.split_once('\0')?;
+            Some(SubmoduleLogEntry {
+                sha: Oid::from_str(sha).ok()?,
+                subject: subject.to_string().into(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::repo_path;
+
+    fn sha(digit: char) -> String {
+        digit.to_string().repeat(40)
+    }
+
+    fn oid(digit: char) -> Oid {
+        Oid::from_str(&sha(digit)).unwrap()
+    }
+
+    #[test]
+    fn test_parse_index_gitlinks() {
+        let output = format!(
+            "100644 {a} 0\tREADME.md\0\
+             160000 {b} 1\tconflicted\0\
+             160000 {c} 2\tconflicted\0\
+             160000 {d} 0\tlibs/my lib\0",
+            a = sha('a'),
+            b = sha('b'),
+            c = sha('c'),
+            d = sha('d'),
+        );
+        assert_eq!(
+            parse_index_gitlinks(&output),
+            vec![
+                (repo_path("conflicted"), oid('b'), 1),
+                (repo_path("conflicted"), oid('c'), 2),
+                (repo_path("libs/my lib"), oid('d'), 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_gitlinks() {
+        let output = format!(
+            "160000 commit {a}\tlibs/my lib\0100644 blob {b}\tREADME.md\0",
+            a = sha('a'),
+            b = sha('b'),
+        );
+        assert_eq!(
+            parse_gitlinks(&output),
+            vec![(repo_path("libs/my lib"), oid('a'))]
+        );
+    }
+
+    #[test]
+    fn test_parse_submodule_log() {
+        let output = format!(
+            "{a}\0Fix the thing\n{b}\0Add a thing",
+            a = sha('a'),
+            b = sha('b')
+        );
+        assert_eq!(
+            parse_submodule_log(&output),
+            vec![
+                SubmoduleLogEntry {
+                    sha: oid('a'),
+                    subject: "Fix the thing".into(),
+                },
+                SubmoduleLogEntry {
+                    sha: oid('b'),
+                    subject: "Add a thing".into(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,376 @@
+use crate::{commit_view::CommitView, git_panel::show_error_toast, picker_prompt};
+use anyhow::Context as _;
+use git::{Oid, bisect::BisectTerm};
+use gpui::{Action as _, App, Empty, Entity, Subscription, WeakEntity, Window};
+use project::git_store::{GitStore, GitStoreEvent, Repository, RepositoryEvent};
+use task::{ResolvedTask, TaskContext, TaskVariables, VariableName};
+use ui::{ContextMenu, PopoverMenu, Tooltip, prelude::*};
+use util::shell::ShellKind;
+use workspace::{StatusItemView, Workspace, item::ItemHandle, notifications::DetachAndPromptErr};
+
+/// Records a verdict on `commit`, or on the commit being tested without one, starting a bisect
+/// first if none is in progress.
+pub fn mark(
+    repository: Entity<Repository>,
+    term: BisectTerm,
+    commit: Option<Oid>,
+    workspace: WeakEntity<Workspace>,
+    cx: &mut App,
+) {
+    let needs_start = repository.read(cx).merge.bisect.is_none();
+    if needs_start && term == BisectTerm::Skip {
+        return;
+    }
+
+    cx.spawn(async move |cx| {
+        let result: anyhow::Result<()> = async {
+            if needs_start {
+                repository
+                    .update(cx, |repository, _| repository.bisect_start())
+                    .await??;
+            }
+            repository
+                .update(cx, |repository, _| {
+                    repository.bisect_mark(term, commit.map(|sha| sha.to_string()))
+                })
+                .await?
+        }
+        .await;
+
+        if let Err(error) = result
+            && let Some(workspace) = workspace.upgrade()
+        {
+            cx.update(|cx| {
+                show_error_toast(workspace, format!("bisect {}", term.as_str()), error, cx)
+            });
+        }
+    })
+    .detach();
+}
+
+/// Ends the bisect in progress and checks out the commit it started from.
+pub fn reset(repository: Entity<Repository>, workspace: WeakEntity<Workspace>, cx: &mut App) {
+    cx.spawn(async move |cx| {
+        let result = repository
+            .update(cx, |repository, _| repository.bisect_reset())
+            .await;
+        if let Ok(Err(error)) = result
+            && let Some(workspace) = workspace.upgrade()
+        {
+            cx.update(|cx| show_error_toast(workspace, "bisect reset", error, cx));
+        }
+    })
+    .detach();
+}
+
+/// Lets the user pick a task and runs it as the `git bisect run` script, so that its exit code
+/// marks each commit as good, bad or skipped until the first bad commit is found.
+pub fn run_task(
+    repository: Entity<Repository>,
+    workspace: &Workspace,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let project = workspace.project().clone();
+    let Some(task_inventory) = project
+        .read(cx)
+        .task_store()
+        .read(cx)
+        .task_inventory()
+        .cloned()
+    else {
+        return;
+    };
+    let repository_path = repository.read(cx).work_directory_abs_path.to_path_buf();
+    let worktree_id = project
+        .read(cx)
+        .find_worktree(&repository_path, cx)
+        .map(|(worktree, _)| worktree.read(cx).id());
+    let list_tasks = task_inventory
+        .read(cx)
+        .list_tasks(None, None, worktree_id, cx);
+    let weak_workspace = workspace.weak_handle();
+
+    window
+        .spawn(cx, async move |cx| {
+            let templates = list_tasks.await;
+            if templates.is_empty() {
+                anyhow::bail!("There are no tasks to run as the bisect script");
+            }
+
+            let mut task_variables = TaskVariables::from_iter([(
+                VariableName::GitRepositoryPath,
+                repository_path.to_string_lossy().into_owned(),
+            )]);
+            if let Some(repository_name) =
+                repository_path.file_name().and_then(|name| name.to_str())
+            {
+                task_variables.insert(VariableName::GitRepositoryName, repository_name.to_string());
+            }
+            let task_context = TaskContext {
+                cwd: Some(repository_path),
+                task_variables,
+                ..TaskContext::default()
+            };
+            let tasks = templates
+                .into_iter()
+                .filter_map(|(kind, template)| {
+                    let id_base = kind.to_id_base();
+                    let task = template.resolve_task(&id_base, &task_context)?;
+                    Some((kind, task))
+                })
+                .collect::<Vec<_>>();
+            let labels = tasks
+                .iter()
+                .map(|(_, task)| task.display_label().to_string().into())
+                .collect();
+
+            let Some(ix) = cx
+                .update(|window, cx| {
+                    picker_prompt::prompt(
+                        "Run a task at each bisect step",
+                        labels,
+                        weak_workspace.clone(),
+                        window,
+                        cx,
+                    )
+                })?
+                .await
+            else {
+                return Ok(());
+            };
+            let (kind, task) = tasks.into_iter().nth(ix).context("no task selected")?;
+            let task = bisect_run_task(task)?;
+
+            weak_workspace.update_in(cx, |workspace, window, cx| {
+                workspace.schedule_resolved_task(kind, task, false, window, cx);
+            })
+        })
+        .detach_and_prompt_err("Failed to start git bisect run", window, cx, |_, _, _| None);
+}
+
+/// Wraps a task so that it runs as the script of `git bisect run`.
+fn bisect_run_task(mut task: ResolvedTask) -> anyhow::Result<ResolvedTask> {
+    let spawn = &mut task.resolved;
+    let mut script = spawn.command.clone().context("the task has no command")?;
+    for arg in &spawn.args {
+        let arg = ShellKind::Posix
+            .try_quote(arg)
+            .with_context(|| format!("can't quote task argument {arg:?}"))?;
+        script.push(' ');
+        script.push_str(&arg);
+    }
+
+    spawn.command = Some("git".into());
+    spawn.args = ["bisect", "run", "sh", "-c"]
+        .into_iter()
+        .map(String::from)
+        .chain([script])
+        .collect();
+    spawn.label = format!("git bisect run: {}", spawn.label);
+    spawn.full_label = format!("git bisect run: {}", spawn.full_label);
+    spawn.command_label = format!("git bisect run {}", spawn.command_label);
+    Ok(task)
+}
+
+/// Shows the progress of a bisect in the status bar, along with the actions to drive it.
+pub struct BisectIndicator {
+    workspace: WeakEntity<Workspace>,
+    git_store: Entity<GitStore>,
+    last_first_bad: Option<Oid>,
+    _subscription: Subscription,
+}
+
+impl BisectIndicator {
+    pub fn new(workspace: &Workspace, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let git_store = workspace.project().read(cx).git_store().clone();
+        let subscription = cx.subscribe_in(&git_store, window, Self::on_git_store_event);
+        let mut this = Self {
+            workspace: workspace.weak_handle(),
+            git_store,
+            last_first_bad: None,
+            _subscription: subscription,
+        };
+        this.last_first_bad = this
+            .active_repository(cx)
+            .and_then(|repository| repository.read(cx).merge.bisect.as_ref()?.first_bad);
+        this
+    }
+
+    fn active_repository(&self, cx: &App) -> Option<Entity<Repository>> {
+        self.git_store.read(cx).active_repository()
+    }
+
+    fn on_git_store_event(
+        &mut self,
+        _: &Entity<GitStore>,
+        event: &GitStoreEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !matches!(
+            event,
+            GitStoreEvent::ActiveRepositoryChanged(_)
+                | GitStoreEvent::RepositoryUpdated(_, RepositoryEvent::StatusesChanged, true)
+        ) {
+            return;
+        }
+
+        let repository = self.active_repository(cx);
+        let first_bad = repository
+            .as_ref()
+            .and_then(|repository| repository.read(cx).merge.bisect.as_ref()?.first_bad);
+        if first_bad != self.last_first_bad {
+            self.last_first_bad = first_bad;
+            if let (Some(sha), Some(repository)) = (first_bad, repository)
+                && matches!(event, GitStoreEvent::RepositoryUpdated(..))
+            {
+                CommitView::open(
+                    sha.to_string(),
+                    repository.downgrade(),
+                    self.workspace.clone(),
+                    None,
+                    None,
+                    window,
+                    cx,
+                );
+            }
+        }
+        cx.notify();
+    }
+
+    fn build_menu(
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Entity<ContextMenu> {
+        let first_bad = repository
+            .read(cx)
+            .merge
+            .bisect
+            .as_ref()
+            .and_then(|bisect| bisect.first_bad);
+        ContextMenu::build(window, cx, move |menu, _, _| {
+            let menu = if let Some(first_bad) = first_bad {
+                let repository = repository.clone();
+                let workspace = workspace.clone();
+                menu.entry("View First Bad Commit", None, move |window, cx| {
+                    CommitView::open(
+                        first_bad.to_string(),
+                        repository.downgrade(),
+                        workspace.clone(),
+                        None,
+                        None,
+                        window,
+                        cx,
+                    );
+                })
+            } else {
+                menu.action("Mark as Good", git::BisectGood.boxed_clone())
+                    .action("Mark as Bad", git::BisectBad.boxed_clone())
+                    .action("Skip", git::BisectSkip.boxed_clone())
+                    .separator()
+                    .action("Run Task…", git::BisectRun.boxed_clone())
+            };
+            menu.separator()
+                .action("Reset Bisect", git::BisectReset.boxed_clone())
+        })
+    }
+}
+
+impl Render for BisectIndicator {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let Some(repository) = self.active_repository(cx) else {
+            return Empty.into_any_element();
+        };
+        let Some(bisect) = repository.read(cx).merge.bisect.clone() else {
+            return Empty.into_any_element();
+        };
+
+        let label: SharedString = if let Some(first_bad) = bisect.first_bad {
+            format!("First Bad Commit: {}", first_bad.display_short()).into()
+        } else if bisect.needs_bounds() {
+            match (bisect.bad, bisect.good.is_empty()) {
+                (None, true) => "Bisecting: Mark a Good and a Bad Commit".into(),
+                (None, false) => "Bisecting: Mark a Bad Commit".into(),
+                _ => "Bisecting: Mark a Good Commit".into(),
+            }
+        } else {
+            match bisect.remaining_steps {
+                Some(1) => "Bisecting: ~1 Step Left".into(),
+                Some(steps) => format!("Bisecting: ~{steps} Steps Left").into(),
+                None => "Bisecting".into(),
+            }
+        };
+        let tooltip: SharedString = match bisect.current {
+            Some(current) => format!("Testing {}", current.display_short()).into(),
+            None => "Git Bisect".into(),
+        };
+        let workspace = self.workspace.clone();
+
+        PopoverMenu::new("bisect-indicator")
+            .trigger(
+                Button::new("bisect-indicator-trigger", label)
+                    .label_size(LabelSize::Small)
+                    .start_icon(
+                        Icon::new(IconName::MagnifyingGlass)
+                            .size(IconSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .tooltip(Tooltip::text(tooltip)),
+            )
+            .anchor(gpui::Anchor::BottomLeft)
+            .menu(move |window, cx| {
+                Some(Self::build_menu(
+                    repository.clone(),
+                    workspace.clone(),
+                    window,
+                    cx,
+                ))
+            })
+            .into_any_element()
+    }
+}
+
+impl StatusItemView for BisectIndicator {
+    fn set_active_pane_item(
+        &mut self,
+        _: Option<&dyn ItemHandle>,
+        _window: &mut Window,
+        _: &mut Context<Self>,
+    ) {
+    }
+
+    fn hide_setting(&self, _: &App) -> Option<workspace::HideStatusItem> {
+        // The bisect indicator only shows while a bisect is in progress.
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use task::TaskTemplate;
+
+    #[test]
+    fn test_bisect_run_task() {
+        let template = TaskTemplate {
+            label: "cargo test".into(),
+            command: "cargo".into(),
+            args: vec!["test".into(), "--".into(), "it's broken".into()],
+            ..TaskTemplate::default()
+        };
+        let task = template
+            .resolve_task("test", &TaskContext::default())
+            .unwrap();
+        let task = bisect_run_task(task).unwrap();
+
+        assert_eq!(task.resolved.command.as_deref(), Some("git"));
+        assert_eq!(
+            task.resolved.args,
+            vec!["bisect", "run", "sh", "-c", "cargo test -- \"it's broken\""]
+        );
+        assert_eq!(task.resolved.label, "git bisect run: cargo test");
+    }
+}
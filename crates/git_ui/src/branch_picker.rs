@@ -35,7 +35,9 @@ actions!(
         /// Force deletes the selected git branch or remote.
         ForceDeleteBranch,
         /// Filter the list of remotes
-        FilterRemotes
+        FilterRemotes,
+        /// Interactively rebases the current branch onto the selected branch.
+        RebaseOntoBranch,
     ]
 );
 
@@ -417,6 +419,35 @@ impl BranchList {
         })
     }
 
+    pub fn handle_rebase(
+        &mut self,
+        _: &branch_picker::RebaseOntoBranch,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let target = self.picker.update(cx, |picker, _| {
+            if picker.delegate.is_select_only() {
+                return None;
+            }
+            let branch = picker
+                .delegate
+                .matches
+                .get(picker.delegate.selected_index)?
+                .as_branch()
+                .filter(|branch| !branch.is_head)?;
+            Some((
+                picker.delegate.repo.clone()?,
+                picker.delegate.workspace.clone(),
+                branch.ref_name.to_string(),
+            ))
+        });
+        let Some((repository, workspace, base)) = target else {
+            return;
+        };
+        cx.emit(DismissEvent);
+        crate::rebase_editor::open(repository, Some(base), workspace, window, cx);
+    }
+
     pub fn handle_filter(
         &mut self,
         _: &branch_picker::FilterRemotes,
@@ -449,6 +480,7 @@ impl Render for BranchList {
             .on_action(cx.listener(Self::handle_delete))
             .on_action(cx.listener(Self::handle_force_delete))
             .on_action(cx.listener(Self::handle_filter))
+            .on_action(cx.listener(Self::handle_rebase))
             .child(self.picker.clone())
             .when(!self.embedded, |this| {
                 this.on_mouse_down_out({
@@ -1765,6 +1797,23 @@ impl PickerDelegate for BranchListDelegate {
                             .is_some_and(|branch| branch.is_head),
                         |this| {
                             this.child(
+                                Button::new("rebase-onto-branch", "Rebase Onto")
+                                    .key_binding(
+                                        KeyBinding::for_action_in(
+                                            &branch_picker::RebaseOntoBranch,
+                                            &focus_handle,
+                                            cx,
+                                        )
+                                        .map(|kb| kb.size(rems_from_px(12.))),
+                                    )
+                                    .on_click(|_, window, cx| {
+                                        window.dispatch_action(
+                                            branch_picker::RebaseOntoBranch.boxed_clone(),
+                                            cx,
+                                        );
+                                    }),
+                            )
+                            .child(
                                 Button::new("delete-branch", "Delete")
                                     .key_binding(
                                         KeyBinding::for_action_in(
@@ -47,6 +47,7 @@ use workspace::{
 
 use crate::commit_tooltip::CommitAvatar;
 use crate::git_panel::GitPanel;
+use crate::notebook_diff::{self, NotebookText};
 
 actions!(
     git,
@@ -316,6 +317,15 @@ impl CommitView {
                     raw_new_text
                 };
                 let old_text = if is_binary { None } else { raw_old_text };
+                // Notebooks are diffed by their cells, unless either side can't be read as one
+                let notebook = if !is_binary && notebook_diff::is_notebook_path(&file.path) {
+                    NotebookText::parse(&new_text).zip(match &old_text {
+                        Some(old_text) => NotebookText::parse(old_text).map(Some),
+                        None => Some(None),
+                    })
+                } else {
+                    None
+                };
                 let worktree_id = repository_clone
                     .update(cx, |repository, cx| {
                         repository
@@ -340,7 +350,27 @@ impl CommitView {
                     display_name,
                 }) as Arc<dyn language::File>;
 
-                let buffer = build_buffer(new_text, file, &language_registry, cx).await?;
+                let (buffer, old_text) = match notebook {
+                    Some((notebook, base)) => {
+                        let language_name = notebook
+                            .language
+                            .as_deref()
+                            .or_else(|| base.as_ref()?.language.as_deref());
+                        let language =
+                            notebook_diff::load_language(language_name, &language_registry).await;
+                        let buffer = notebook_diff::build_notebook_buffer(
+                            notebook.text,
+                            Some(file),
+                            language,
+                            cx,
+                        );
+                        (buffer, base.map(|base| base.text))
+                    }
+                    None => (
+                        build_buffer(new_text, file, &language_registry, cx).await?,
+                        old_text,
+                    ),
+                };
                 let buffer_id = cx.update(|_, cx| buffer.read(cx).remote_id())?;
 
                 let status_code = if is_created {
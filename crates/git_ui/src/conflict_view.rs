@@ -1,4 +1,9 @@
+use crate::{
+    file_diff_view::{FileDiffView, build_buffer_diff},
+    notebook_diff::{self, NotebookFile, NotebookText},
+};
 use agent_settings::AgentSettings;
+use anyhow::{Context as _, Result};
 use collections::{HashMap, HashSet};
 use editor::{
     ConflictsOurs, ConflictsOursMarker, ConflictsOuter, ConflictsTheirs, ConflictsTheirsMarker,
@@ -9,7 +14,7 @@ use gpui::{
     App, ClickEvent, Context, Empty, Entity, InteractiveElement as _, ParentElement as _,
     Subscription, Task, WeakEntity,
 };
-use language::{Anchor, Buffer, BufferId};
+use language::{Anchor, Buffer, BufferId, OffsetRangeExt as _};
 use project::{
     ConflictRegion, ConflictSet, ConflictSetUpdate, Project,
     git_store::{GitStore, GitStoreEvent, RepositoryEvent},
@@ -176,6 +181,12 @@ fn conflicts_updated(
     let conflict_set = conflict_set.read(cx).snapshot();
     let multibuffer = editor.buffer().read(cx);
     let snapshot = multibuffer.snapshot(cx);
+    let is_notebook = multibuffer.buffer(buffer_id).is_some_and(|buffer| {
+        buffer
+            .read(cx)
+            .file()
+            .is_some_and(|file| notebook_diff::is_notebook_path(file.path()))
+    });
     let old_range = maybe!({
         let conflict_addon = editor.addon_mut::<ConflictAddon>().unwrap();
         let buffer_conflicts = conflict_addon.buffers.get(&buffer_id)?;
@@ -249,7 +260,7 @@ fn conflicts_updated(
             style: BlockStyle::Sticky,
             render: Arc::new({
                 let conflict = conflict.clone();
-                move |cx| render_conflict_buttons(&conflict, editor_handle.clone(), cx)
+                move |cx| render_conflict_buttons(&conflict, is_notebook, editor_handle.clone(), cx)
             }),
             priority: 0,
         })
@@ -319,6 +330,7 @@ fn update_conflict_highlighting(
 
 fn render_conflict_buttons(
     conflict: &ConflictRegion,
+    is_notebook: bool,
     editor: WeakEntity<Editor>,
     cx: &mut BlockContext,
 ) -> AnyElement {
@@ -388,6 +400,23 @@ fn render_conflict_buttons(
                     }
                 }),
         )
+        .when(is_notebook, |this| {
+            this.child(
+                Button::new("compare-cells", "Compare Cells")
+                    .label_size(LabelSize::Small)
+                    .tooltip(Tooltip::text(
+                        "Compare the cells of both sides of the notebook's conflicts",
+                    ))
+                    .on_click({
+                        let editor = editor.clone();
+                        let buffer_id = conflict.ours.end.buffer_id;
+                        move |_, window, cx| {
+                            compare_notebook_cells(editor.clone(), buffer_id, window, cx)
+                                .detach_and_log_err(cx)
+                        }
+                    }),
+            )
+        })
         .when(is_ai_enabled, |this| {
             this.child(Divider::vertical()).child(
                 Button::new("resolve-with-agent", "Resolve with Agent")
@@ -439,6 +468,89 @@ fn render_conflict_buttons(
         .into_any()
 }
 
+/// Opens a diff between the cells of each side of a conflicted notebook, whose JSON
+/// is hard to compare by its conflict markers alone.
+fn compare_notebook_cells(
+    editor: WeakEntity<Editor>,
+    buffer_id: BufferId,
+    window: &mut Window,
+    cx: &mut App,
+) -> Task<Result<()>> {
+    window.spawn(cx, async move |cx| {
+        let (ours, theirs, file, project, workspace) = editor.update(cx, |editor, cx| {
+            let buffer = editor
+                .buffer()
+                .read(cx)
+                .buffer(buffer_id)
+                .context("notebook is no longer open")?;
+            let conflict_set = editor
+                .addon::<ConflictAddon>()
+                .and_then(|addon| addon.buffers.get(&buffer_id))
+                .context("notebook has no conflicts")?
+                .conflict_set
+                .read(cx)
+                .snapshot();
+            let buffer = buffer.read(cx);
+            let snapshot = buffer.snapshot();
+            let text = snapshot.text();
+            let resolve = |side: fn(&ConflictRegion) -> &Range<Anchor>| {
+                let conflicts = conflict_set
+                    .conflicts
+                    .iter()
+                    .map(|conflict| {
+                        (
+                            conflict.range.to_offset(&snapshot),
+                            side(conflict).to_offset(&snapshot),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                notebook_diff::resolve_conflicts(&text, &conflicts)
+            };
+            anyhow::Ok((
+                resolve(|conflict| &conflict.ours),
+                resolve(|conflict| &conflict.theirs),
+                buffer.file().cloned().context("notebook has no file")?,
+                editor.project().cloned().context("editor has no project")?,
+                editor.workspace().context("editor has no workspace")?,
+            ))
+        })??;
+        let ours = NotebookText::parse(&ours).context("reading our side of the notebook")?;
+        let theirs = NotebookText::parse(&theirs).context("reading their side of the notebook")?;
+
+        let languages = project.read_with(cx, |project, _| project.languages().clone());
+        let language_name = ours.language.as_deref().or(theirs.language.as_deref());
+        let language = notebook_diff::load_language(language_name, &languages).await;
+        let file: Arc<dyn language::File> = Arc::new(NotebookFile::new(file));
+        let ours_buffer = notebook_diff::build_notebook_buffer(
+            ours.text,
+            Some(file.clone()),
+            language.clone(),
+            cx,
+        );
+        let theirs_buffer =
+            notebook_diff::build_notebook_buffer(theirs.text, Some(file), language, cx);
+        let diff = build_buffer_diff(&ours_buffer, &theirs_buffer, languages, cx).await?;
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            let workspace_entity = cx.entity();
+            let diff_view = cx.new(|cx| {
+                FileDiffView::new(
+                    ours_buffer,
+                    theirs_buffer,
+                    diff,
+                    project,
+                    workspace_entity,
+                    window,
+                    cx,
+                )
+            });
+            workspace.active_pane().update(cx, |pane, cx| {
+                pane.add_item(Box::new(diff_view), true, true, None, window, cx);
+            });
+        })
+    })
+}
+
 fn collect_conflicted_file_paths(project: &Project, cx: &App) -> Vec<String> {
     let git_store = project.git_store().read(cx);
     let mut paths = Vec::new();
@@ -3,15 +3,17 @@ use crate::{
     commit_view::CommitView,
     git_status_icon,
 };
-use collections::{BTreeMap, HashMap, IndexSet};
+use collections::{BTreeMap, HashMap, HashSet, IndexSet};
 use editor::Editor;
 use file_icons::FileIcons;
+use futures::channel::oneshot;
 use git::{
     BuildCommitPermalinkParams, GitHostingProviderRegistry, GitRemote, Oid, ParsedGitRemote,
+    bisect::BisectTerm,
     commit::ParsedCommitMessage,
     parse_git_remote_url,
     repository::{
-        CommitDiff, CommitFile, InitialGraphCommitData, LogOrder, LogSource, RepoPath,
+        CommitDiff, CommitFile, InitialGraphCommitData, LogOrder, LogSource, RepoPath, ResetMode,
         SearchCommitArgs,
     },
     status::{FileStatus, StatusCode, TrackedStatus},
@@ -19,13 +21,13 @@ use git::{
 use gpui::{
     Action, Anchor, AnyElement, App, Bounds, ClickEvent, ClipboardItem, DefiniteLength,
     DismissEvent, DragMoveEvent, ElementId, Empty, Entity, EventEmitter, FocusHandle, Focusable,
-    Hsla, MouseButton, MouseDownEvent, PathBuilder, Pixels, Point, ScrollStrategy,
+    Hsla, MouseButton, MouseDownEvent, PathBuilder, Pixels, Point, PromptLevel, ScrollStrategy,
     ScrollWheelEvent, SharedString, Subscription, Task, TextStyleRefinement,
     UniformListScrollHandle, WeakEntity, Window, actions, anchored, deferred, point, prelude::*,
     px, uniform_list,
 };
 use language::line_diff;
-use menu::{Cancel, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use menu::{Cancel, Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
 use picker::{Picker, PickerDelegate};
 use project::{
     GIT_COMMAND_TASK_TAG, ProjectPath, TaskSourceKind,
@@ -199,6 +201,162 @@ impl PickerDelegate for CommitTagPickerDelegate {
     }
 }
 
+#[derive(Clone, Copy, PartialEq)]
+enum CommitRefKind {
+    Branch,
+    Tag,
+}
+
+/// Asks for the name of a branch or tag to create at a commit.
+struct CommitRefNameModal {
+    kind: CommitRefKind,
+    sha: Oid,
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    name_editor: Entity<Editor>,
+    message_editor: Option<Entity<Editor>>,
+}
+
+impl CommitRefNameModal {
+    fn new(
+        kind: CommitRefKind,
+        sha: Oid,
+        repository: Entity<Repository>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let name_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            let placeholder = match kind {
+                CommitRefKind::Branch => "Branch name…",
+                CommitRefKind::Tag => "Tag name…",
+            };
+            editor.set_placeholder_text(placeholder, window, cx);
+            editor
+        });
+        let message_editor = (kind == CommitRefKind::Tag).then(|| {
+            cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text(
+                    "Message (optional, creates an annotated tag)…",
+                    window,
+                    cx,
+                );
+                editor
+            })
+        });
+
+        Self {
+            kind,
+            sha,
+            repository,
+            workspace,
+            name_editor,
+            message_editor,
+        }
+    }
+
+    fn cancel(&mut self, _: &Cancel, _window: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn confirm(&mut self, _: &Confirm, _window: &mut Window, cx: &mut Context<Self>) {
+        let name = self.name_editor.read(cx).text(cx).trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        let sha = self.sha.to_string();
+
+        let (operation, job) = match self.kind {
+            CommitRefKind::Branch => (
+                "switch -c",
+                self.repository.update(cx, |repository, _| {
+                    repository.create_branch(name, Some(sha))
+                }),
+            ),
+            CommitRefKind::Tag => {
+                let message = self
+                    .message_editor
+                    .as_ref()
+                    .map(|editor| editor.read(cx).text(cx).trim().to_string())
+                    .filter(|message| !message.is_empty());
+                (
+                    "tag",
+                    self.repository.update(cx, |repository, cx| {
+                        repository.create_tag(name, sha, message, cx)
+                    }),
+                )
+            }
+        };
+        show_error_toast_on_failure(job, operation, self.workspace.clone(), cx);
+        cx.emit(DismissEvent);
+    }
+}
+
+impl EventEmitter<DismissEvent> for CommitRefNameModal {}
+impl ModalView for CommitRefNameModal {}
+
+impl Focusable for CommitRefNameModal {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.name_editor.focus_handle(cx)
+    }
+}
+
+impl Render for CommitRefNameModal {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let (icon, title) = match self.kind {
+            CommitRefKind::Branch => (IconName::GitBranch, "Create Branch"),
+            CommitRefKind::Tag => (IconName::Hash, "Create Tag"),
+        };
+
+        v_flex()
+            .key_context("CommitRefNameModal")
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::confirm))
+            .elevation_2(cx)
+            .w(rems(34.))
+            .pb_3()
+            .gap_1()
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(icon).size(IconSize::XSmall))
+                    .child(Headline::new(title).size(HeadlineSize::XSmall))
+                    .child(
+                        Label::new(format!("at {}", self.sha.display_short()))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    ),
+            )
+            .child(div().px_3().w_full().child(self.name_editor.clone()))
+            .when_some(self.message_editor.clone(), |this, editor| {
+                this.child(div().px_3().w_full().child(editor))
+            })
+    }
+}
+
+/// Shows an error toast if a repository operation fails.
+fn show_error_toast_on_failure(
+    job: oneshot::Receiver<anyhow::Result<()>>,
+    operation: &'static str,
+    workspace: WeakEntity<Workspace>,
+    cx: &mut App,
+) {
+    cx.spawn(async move |cx| {
+        if let Ok(Err(error)) = job.await
+            && let Some(workspace) = workspace.upgrade()
+        {
+            cx.update(|cx| crate::git_panel::show_error_toast(workspace, operation, error, cx));
+        }
+    })
+    .detach();
+}
+
 #[derive(Clone)]
 struct ChangedFileEntry {
     status: FileStatus,
@@ -955,6 +1113,49 @@ impl GraphData {
         })
     }
 
+    /// Whether the commit at index `ancestor` is reachable from the one at `descendant` by
+    /// following parents. Commits are listed after all of their children, so only the commits
+    /// between the two need to be walked.
+    fn is_ancestor(&self, ancestor: usize, descendant: usize) -> bool {
+        let Some(target) = self.commits.get(ancestor) else {
+            return false;
+        };
+        if descendant > ancestor {
+            return false;
+        }
+        let mut reachable = HashSet::default();
+        reachable.insert(self.commits[descendant].data.sha);
+        for commit in &self.commits[descendant..ancestor] {
+            if reachable.contains(&commit.data.sha) {
+                reachable.extend(commit.data.parents.iter().copied());
+            }
+        }
+        reachable.contains(&target.data.sha)
+    }
+
+    /// Returns the commits from `oldest` up to `newest`, oldest first, when `oldest` is an
+    /// ancestor of `newest` and none of the commits in between is a merge.
+    fn linear_range(&self, oldest: usize, newest: usize) -> Option<Vec<Oid>> {
+        if !self.is_ancestor(oldest, newest) {
+            return None;
+        }
+        let mut reachable = HashSet::default();
+        reachable.insert(self.commits[newest].data.sha);
+        let mut range = Vec::new();
+        for commit in &self.commits[newest..=oldest] {
+            if !reachable.contains(&commit.data.sha) {
+                continue;
+            }
+            if commit.data.parents.len() > 1 {
+                return None;
+            }
+            reachable.extend(commit.data.parents.iter().copied());
+            range.push(commit.data.sha);
+        }
+        range.reverse();
+        Some(range)
+    }
+
     fn add_commits(&mut self, commits: &[Arc<InitialGraphCommitData>]) {
         self.commits.reserve(commits.len());
         self.lines.reserve(commits.len() / 2);
@@ -1339,6 +1540,8 @@ pub struct GitGraph {
     changed_files_view_mode: ChangedFilesViewMode,
     changed_files_expanded_dirs: HashMap<RepoPath, bool>,
     pending_select_sha: Option<Oid>,
+    /// The oldest or newest commit of a range to cherry-pick, picked before the other end.
+    cherry_pick_range_start: Option<Oid>,
 }
 
 impl GitGraph {
@@ -1561,6 +1764,7 @@ impl GitGraph {
             changed_files_view_mode: ChangedFilesViewMode::default(),
             changed_files_expanded_dirs: HashMap::default(),
             pending_select_sha: None,
+            cherry_pick_range_start: None,
         };
 
         this.fetch_initial_graph_data(cx);
@@ -1647,6 +1851,9 @@ impl GitGraph {
                     self.invalidate_state(cx);
                 }
             }
+            RepositoryEvent::TagsChanged => {
+                self.invalidate_state(cx);
+            }
             RepositoryEvent::GraphEvent(_, _) => {}
             _ => {}
         }
@@ -2258,6 +2465,186 @@ impl GitGraph {
         cx.write_to_clipboard(ClipboardItem::new_string(commit.data.sha.to_string()));
     }
 
+    /// The index of the checked-out commit in the graph, if it has been loaded.
+    fn head_index(&self, cx: &App) -> Option<usize> {
+        let repository = self.get_repository(cx)?;
+        let head_sha = repository.read(cx).head_commit.as_ref()?.sha.clone();
+        self.graph_data
+            .commits
+            .iter()
+            .position(|commit| commit.data.sha.to_string() == head_sha.as_ref())
+    }
+
+    /// Whether the commit at `entry_index` is HEAD or one of its ancestors, which is what an
+    /// interactive rebase can rewrite.
+    fn is_ancestor_of_head(&self, entry_index: usize, cx: &App) -> bool {
+        self.head_index(cx)
+            .is_some_and(|head_index| self.graph_data.is_ancestor(entry_index, head_index))
+    }
+
+    /// Opens the rebase editor with the commits from this one up to HEAD.
+    fn rebase_from_commit(
+        &mut self,
+        entry_index: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.is_ancestor_of_head(entry_index, cx) {
+            return;
+        }
+        let Some(commit) = self.graph_data.commits.get(entry_index) else {
+            return;
+        };
+        let Some(repository) = self.get_repository(cx) else {
+            return;
+        };
+        let base = commit.data.parents.first().map(|parent| parent.to_string());
+        crate::rebase_editor::open(repository, base, self.workspace.clone(), window, cx);
+    }
+
+    fn bisect_commit(&mut self, entry_index: usize, term: BisectTerm, cx: &mut Context<Self>) {
+        let Some(commit) = self.graph_data.commits.get(entry_index) else {
+            return;
+        };
+        let Some(repository) = self.get_repository(cx) else {
+            return;
+        };
+        crate::bisect::mark(
+            repository,
+            term,
+            Some(commit.data.sha),
+            self.workspace.clone(),
+            cx,
+        );
+    }
+
+    fn cherry_pick(&mut self, commits: Vec<String>, cx: &mut Context<Self>) {
+        let Some(repository) = self.get_repository(cx) else {
+            return;
+        };
+        let job = repository.update(cx, |repository, _| repository.cherry_pick(commits));
+        show_error_toast_on_failure(job, "cherry-pick", self.workspace.clone(), cx);
+    }
+
+    /// Returns the commits between the marked range start and this commit, oldest first, when
+    /// they form a single line of history without merges.
+    fn cherry_pick_range_commits(&self, entry_index: usize) -> Option<Vec<Oid>> {
+        let start = self.cherry_pick_range_start?;
+        let start_index = self
+            .graph_data
+            .commits
+            .iter()
+            .position(|commit| commit.data.sha == start)?;
+        // The graph lists newer commits first.
+        let (oldest, newest) = if start_index > entry_index {
+            (start_index, entry_index)
+        } else {
+            (entry_index, start_index)
+        };
+        self.graph_data.linear_range(oldest, newest)
+    }
+
+    /// Cherry-picks the commits between the marked range start and this commit, inclusive.
+    ///
+    /// The commits are passed one by one rather than as `oldest^..newest`, which git can't
+    /// resolve when the oldest commit is a root commit.
+    fn cherry_pick_range(&mut self, entry_index: usize, cx: &mut Context<Self>) {
+        let commits = self.cherry_pick_range_commits(entry_index);
+        self.cherry_pick_range_start = None;
+        let Some(commits) = commits else {
+            return;
+        };
+        self.cherry_pick(commits.iter().map(Oid::to_string).collect(), cx);
+    }
+
+    fn revert_commit(&mut self, entry_index: usize, cx: &mut Context<Self>) {
+        let Some(commit) = self.graph_data.commits.get(entry_index) else {
+            return;
+        };
+        let Some(repository) = self.get_repository(cx) else {
+            return;
+        };
+        let sha = commit.data.sha.to_string();
+        let job = repository.update(cx, |repository, _| repository.revert(sha));
+        show_error_toast_on_failure(job, "revert", self.workspace.clone(), cx);
+    }
+
+    fn reset_to_commit(
+        &mut self,
+        entry_index: usize,
+        mode: ResetMode,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(commit) = self.graph_data.commits.get(entry_index) else {
+            return;
+        };
+        let Some(repository) = self.get_repository(cx) else {
+            return;
+        };
+        let sha = commit.data.sha;
+        let workspace = self.workspace.clone();
+
+        let confirmation = (mode == ResetMode::Hard).then(|| {
+            window.prompt(
+                PromptLevel::Warning,
+                &format!(
+                    "Reset the current branch to {} and discard all uncommitted changes?",
+                    sha.display_short()
+                ),
+                None,
+                &["Reset", "Cancel"],
+                cx,
+            )
+        });
+        cx.spawn(async move |_, cx| {
+            if let Some(confirmation) = confirmation
+                && confirmation.await != Ok(0)
+            {
+                return;
+            }
+            let job = repository.update(cx, |repository, cx| {
+                repository.reset(sha.to_string(), mode, cx)
+            });
+            cx.update(|cx| show_error_toast_on_failure(job, "reset", workspace, cx));
+        })
+        .detach();
+    }
+
+    fn create_ref_at_commit(
+        &mut self,
+        entry_index: usize,
+        kind: CommitRefKind,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(commit) = self.graph_data.commits.get(entry_index) else {
+            return;
+        };
+        let Some(repository) = self.get_repository(cx) else {
+            return;
+        };
+        let sha = commit.data.sha;
+        let weak_workspace = self.workspace.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(window, cx, |window, cx| {
+                    CommitRefNameModal::new(kind, sha, repository, weak_workspace, window, cx)
+                });
+            })
+            .ok();
+    }
+
+    fn delete_tag(&mut self, tag_name: SharedString, cx: &mut Context<Self>) {
+        let Some(repository) = self.get_repository(cx) else {
+            return;
+        };
+        let job = repository.update(cx, |repository, cx| {
+            repository.delete_tag(tag_name.to_string(), cx)
+        });
+        show_error_toast_on_failure(job, "tag --delete", self.workspace.clone(), cx);
+    }
+
     fn copy_selected_commit_sha(
         &mut self,
         _: &CopyCommitSha,
@@ -2402,6 +2789,24 @@ impl GitGraph {
             .map(|task_context| self.git_context_menu_tasks(&task_context, cx))
             .unwrap_or_default();
 
+        let bisecting = self
+            .get_repository(cx)
+            .is_some_and(|repository| repository.read(cx).merge.bisect.is_some());
+        let can_rebase = self.is_ancestor_of_head(index, cx);
+        let range_start = self
+            .cherry_pick_range_start
+            .filter(|range_start| *range_start != sha);
+        // Cherry-picking a merge needs a mainline parent, which the menu doesn't ask for.
+        let is_merge = commit.data.parents.len() > 1;
+        let can_cherry_pick_range =
+            range_start.is_some() && self.cherry_pick_range_commits(index).is_some();
+        let tag_names = commit
+            .data
+            .tag_names()
+            .into_iter()
+            .map(|tag_name| SharedString::from(tag_name.to_string()))
+            .collect::<Vec<_>>();
+
         let focus_handle = self.focus_handle.clone();
         let git_graph = cx.entity();
         let context_menu = ContextMenu::build(window, cx, |context_menu, window, _| {
@@ -2422,6 +2827,153 @@ impl GitGraph {
                         this.copy_commit_sha(index, cx);
                     }),
                 )
+                .when(can_rebase, |menu| {
+                    menu.entry(
+                        "Interactive Rebase from Here",
+                        None,
+                        window.handler_for(&git_graph, move |this, window, cx| {
+                            this.rebase_from_commit(index, window, cx);
+                        }),
+                    )
+                })
+                .submenu("Bisect", {
+                    let git_graph = git_graph.clone();
+                    move |menu, window, _| {
+                        let menu = menu
+                            .entry(
+                                "Mark as Bad",
+                                None,
+                                window.handler_for(&git_graph, move |this, _, cx| {
+                                    this.bisect_commit(index, BisectTerm::Bad, cx);
+                                }),
+                            )
+                            .entry(
+                                "Mark as Good",
+                                None,
+                                window.handler_for(&git_graph, move |this, _, cx| {
+                                    this.bisect_commit(index, BisectTerm::Good, cx);
+                                }),
+                            );
+                        if !bisecting {
+                            return menu;
+                        }
+                        menu.entry(
+                            "Skip",
+                            None,
+                            window.handler_for(&git_graph, move |this, _, cx| {
+                                this.bisect_commit(index, BisectTerm::Skip, cx);
+                            }),
+                        )
+                        .separator()
+                        .action("Reset Bisect", git::BisectReset.boxed_clone())
+                    }
+                })
+                .separator()
+                .item(
+                    ContextMenuEntry::new("Cherry-Pick")
+                        .handler(window.handler_for(&git_graph, move |this, _, cx| {
+                            this.cherry_pick(vec![sha.to_string()], cx);
+                        }))
+                        .disabled(is_merge),
+                )
+                .map(|menu| match range_start {
+                    Some(range_start) => menu
+                        .item(
+                            ContextMenuEntry::new(format!(
+                                "Cherry-Pick Range {}…{sha_short}",
+                                range_start.display_short()
+                            ))
+                            .handler(window.handler_for(&git_graph, move |this, _, cx| {
+                                this.cherry_pick_range(index, cx);
+                            }))
+                            .disabled(!can_cherry_pick_range),
+                        )
+                        .entry(
+                            "Clear Cherry-Pick Range Start",
+                            None,
+                            window.handler_for(&git_graph, |this, _, _| {
+                                this.cherry_pick_range_start = None;
+                            }),
+                        ),
+                    None => menu.entry(
+                        "Start Cherry-Pick Range Here",
+                        None,
+                        window.handler_for(&git_graph, move |this, _, _| {
+                            this.cherry_pick_range_start = Some(sha);
+                        }),
+                    ),
+                })
+                .entry(
+                    "Revert Commit",
+                    None,
+                    window.handler_for(&git_graph, move |this, _, cx| {
+                        this.revert_commit(index, cx);
+                    }),
+                )
+                .submenu("Reset Current Branch to Here", {
+                    let git_graph = git_graph.clone();
+                    move |menu, window, _| {
+                        [
+                            ("Soft", ResetMode::Soft),
+                            ("Mixed", ResetMode::Mixed),
+                            ("Hard…", ResetMode::Hard),
+                        ]
+                        .into_iter()
+                        .fold(menu, |menu, (label, mode)| {
+                            menu.entry(
+                                label,
+                                None,
+                                window.handler_for(&git_graph, move |this, window, cx| {
+                                    this.reset_to_commit(index, mode, window, cx);
+                                }),
+                            )
+                        })
+                    }
+                })
+                .separator()
+                .entry(
+                    "Create Branch Here…",
+                    None,
+                    window.handler_for(&git_graph, move |this, window, cx| {
+                        this.create_ref_at_commit(index, CommitRefKind::Branch, window, cx);
+                    }),
+                )
+                .entry(
+                    "Create Tag Here…",
+                    None,
+                    window.handler_for(&git_graph, move |this, window, cx| {
+                        this.create_ref_at_commit(index, CommitRefKind::Tag, window, cx);
+                    }),
+                )
+                .map(|menu| match tag_names.as_slice() {
+                    [] => menu,
+                    [tag_name] => {
+                        let tag_name = tag_name.clone();
+                        menu.entry(
+                            format!("Delete Tag: {tag_name}"),
+                            None,
+                            window.handler_for(&git_graph, move |this, _, cx| {
+                                this.delete_tag(tag_name.clone(), cx);
+                            }),
+                        )
+                    }
+                    _ => menu.submenu("Delete Tag", {
+                        let git_graph = git_graph.clone();
+                        let tag_names = tag_names.clone();
+                        move |mut menu, window, _| {
+                            for tag_name in tag_names.clone() {
+                                menu = menu.entry(
+                                    tag_name.clone(),
+                                    None,
+                                    window.handler_for(&git_graph, move |this, _, cx| {
+                                        this.delete_tag(tag_name.clone(), cx);
+                                    }),
+                                );
+                            }
+                            menu
+                        }
+                    }),
+                })
                 .map(|menu| {
                     let tag_names = commit
                         .data
@@ -4996,6 +5548,100 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_git_graph_is_ancestor() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let oid1 = Oid::random(&mut rng);
+        let oid2 = Oid::random(&mut rng);
+        let oid3 = Oid::random(&mut rng);
+        let oid4 = Oid::random(&mut rng);
+
+        // oid3 is on a side branch that oid2 doesn't contain.
+        let commits = vec![
+            Arc::new(InitialGraphCommitData {
+                sha: oid1,
+                parents: smallvec![oid2],
+                ref_names: vec!["HEAD".into()],
+            }),
+            Arc::new(InitialGraphCommitData {
+                sha: oid3,
+                parents: smallvec![oid4],
+                ref_names: vec!["feature".into()],
+            }),
+            Arc::new(InitialGraphCommitData {
+                sha: oid2,
+                parents: smallvec![oid4],
+                ref_names: vec![],
+            }),
+            Arc::new(InitialGraphCommitData {
+                sha: oid4,
+                parents: smallvec![],
+                ref_names: vec![],
+            }),
+        ];
+
+        let mut graph_data = GraphData::new(8);
+        graph_data.add_commits(&commits);
+
+        assert!(graph_data.is_ancestor(0, 0));
+        assert!(graph_data.is_ancestor(2, 0));
+        assert!(graph_data.is_ancestor(3, 0));
+        assert!(graph_data.is_ancestor(3, 1));
+        assert!(!graph_data.is_ancestor(1, 0));
+        assert!(!graph_data.is_ancestor(0, 2));
+        assert!(!graph_data.is_ancestor(2, 1));
+    }
+
+    #[test]
+    fn test_git_graph_linear_range() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let oid1 = Oid::random(&mut rng);
+        let oid2 = Oid::random(&mut rng);
+        let oid3 = Oid::random(&mut rng);
+        let oid4 = Oid::random(&mut rng);
+        let oid5 = Oid::random(&mut rng);
+
+        // oid1 merges the side branch oid3 into oid2, which starts from the root commit oid5.
+        let commits = vec![
+            Arc::new(InitialGraphCommitData {
+                sha: oid1,
+                parents: smallvec![oid2, oid3],
+                ref_names: vec!["HEAD".into()],
+            }),
+            Arc::new(InitialGraphCommitData {
+                sha: oid3,
+                parents: smallvec![oid5],
+                ref_names: vec!["feature".into()],
+            }),
+            Arc::new(InitialGraphCommitData {
+                sha: oid2,
+                parents: smallvec![oid4],
+                ref_names: vec![],
+            }),
+            Arc::new(InitialGraphCommitData {
+                sha: oid4,
+                parents: smallvec![oid5],
+                ref_names: vec![],
+            }),
+            Arc::new(InitialGraphCommitData {
+                sha: oid5,
+                parents: smallvec![],
+                ref_names: vec![],
+            }),
+        ];
+
+        let mut graph_data = GraphData::new(8);
+        graph_data.add_commits(&commits);
+
+        assert_eq!(graph_data.linear_range(4, 2), Some(vec![oid5, oid4, oid2]));
+        assert_eq!(graph_data.linear_range(4, 1), Some(vec![oid5, oid3]));
+        assert_eq!(graph_data.linear_range(3, 3), Some(vec![oid4]));
+        assert_eq!(graph_data.linear_range(2, 0), None);
+        assert_eq!(graph_data.linear_range(3, 1), None);
+    }
+
     #[test]
     fn test_git_graph_linear_commits() {
         let mut rng = StdRng::seed_from_u64(42);
@@ -6,7 +6,8 @@ use crate::git_panel_settings::GitPanelScrollbarAccessor;
 use crate::project_diff::{self, BranchDiff, Diff, ProjectDiff};
 use crate::remote_output::{self, RemoteAction, SuccessMessage};
 use crate::solo_diff_view::SoloDiffView;
-use crate::{branch_picker, picker_prompt, render_remote_button};
+use crate::submodule_diff_view::{SubmoduleDiffView, submodule_change};
+use crate::{branch_picker, merge_editor, picker_prompt, render_remote_button};
 use crate::{
     git_panel_settings::GitPanelSettings, git_status_icon, repository_selector::RepositorySelector,
 };
@@ -22,6 +23,7 @@ use futures::StreamExt as _;
 use futures::channel::oneshot::Canceled;
 use git::Oid;
 use git::commit::ParsedCommitMessage;
+use git::rebase::RebaseControl;
 use git::repository::{
     Branch, CommitData, CommitDetails, CommitOptions, CommitSummary, DiffType, FetchOptions,
     GitCommitTemplate, GitCommitter, LogOrder, LogSource, PushOptions, Remote, RemoteCommandOutput,
@@ -29,6 +31,7 @@ use git::repository::{
 };
 use git::stash::GitStash;
 use git::status::{DiffStat, StageStatus};
+use git::submodule::{Submodule, SubmoduleCommand, SubmoduleState};
 use git::{Amend, Commit, Signoff, ToggleStaged, repository::RepoPath, status::FileStatus};
 use git::{
     ExpandCommitEditor, GitHostingProviderRegistry, GitRemote, RestoreTrackedFiles, StageAll,
@@ -72,9 +75,9 @@ use strum::{IntoEnumIterator, VariantNames};
 use theme_settings::ThemeSettings;
 use time::OffsetDateTime;
 use ui::{
-    ButtonLike, Checkbox, ContextMenu, Divider, ElevationIndex, IndentGuideColors, KeyBinding,
-    PopoverMenu, ProjectEmptyState, RenderedIndentGuide, ScrollAxes, Scrollbars, SplitButton, Tab,
-    TintColor, Tooltip, WithScrollbar, prelude::*,
+    ButtonLike, Checkbox, ContextMenu, Disclosure, Divider, ElevationIndex, IndentGuideColors,
+    KeyBinding, PopoverMenu, ProjectEmptyState, RenderedIndentGuide, ScrollAxes, Scrollbars,
+    SplitButton, Tab, TintColor, Tooltip, WithScrollbar, prelude::*,
 };
 use util::paths::PathStyle;
 use util::{ResultExt, TryFutureExt, markdown::MarkdownInlineCode, maybe, rel_path::RelPath};
@@ -228,6 +231,65 @@ fn git_panel_context_menu(
     })
 }
 
+/// Appends the actions that act on a single submodule to a context menu.
+fn submodule_menu_entries(
+    menu: ContextMenu,
+    git_panel: WeakEntity<GitPanel>,
+    submodule: &Submodule,
+) -> ContextMenu {
+    let command_entry = |menu: ContextMenu, label: &'static str, command: SubmoduleCommand| {
+        let git_panel = git_panel.clone();
+        let path = submodule.path.clone();
+        menu.entry(label, None, move |_, cx| {
+            git_panel
+                .update(cx, |git_panel, cx| {
+                    git_panel.submodule_command(command, vec![path.clone()], cx)
+                })
+                .ok();
+        })
+    };
+
+    let menu = if submodule.state == SubmoduleState::Uninitialized {
+        command_entry(menu, "Initialize Submodule", SubmoduleCommand::Init)
+    } else {
+        menu
+    };
+    let menu = command_entry(menu, "Update Submodule", SubmoduleCommand::Update);
+    let menu = command_entry(menu, "Sync Submodule URL", SubmoduleCommand::Sync);
+    if submodule_change(submodule).is_some() {
+        let path = submodule.path.clone();
+        menu.entry("View Submodule Commits", None, move |window, cx| {
+            git_panel
+                .update(cx, |git_panel, cx| {
+                    git_panel.open_submodule_diff(path.clone(), window, cx)
+                })
+                .ok();
+        })
+    } else {
+        menu
+    }
+}
+
+/// Describes the commits recorded, staged and checked out for a submodule.
+fn submodule_tooltip(submodule: &Submodule) -> String {
+    let short = |sha: Option<Oid>| {
+        sha.map(|sha| sha.display_short())
+            .unwrap_or_else(|| "none".into())
+    };
+    let mut lines = vec![format!("Recorded: {}", short(submodule.head_sha))];
+    if submodule.index_sha != submodule.head_sha {
+        lines.push(format!("Staged: {}", short(submodule.index_sha)));
+    }
+    lines.push(match submodule.state {
+        SubmoduleState::Uninitialized => "Not initialized".into(),
+        SubmoduleState::Conflicted => "Conflicted".into(),
+        SubmoduleState::UpToDate | SubmoduleState::Modified => {
+            format!("Checked out: {}", short(submodule.checked_out_sha))
+        }
+    });
+    lines.join("\n")
+}
+
 const GIT_PANEL_KEY: &str = "GitPanel";
 
 const UPDATE_DEBOUNCE: Duration = Duration::from_millis(50);
@@ -685,6 +747,7 @@ pub struct GitPanel {
     commit_history_shas: Option<Vec<Oid>>,
     focused_history_entry: Option<usize>,
     history_keyboard_nav: bool,
+    submodules_expanded: bool,
     _repo_subscriptions: Vec<Subscription>,
 
     _settings_subscription: Subscription,
@@ -883,6 +946,7 @@ impl GitPanel {
                 commit_history_shas: None,
                 focused_history_entry: None,
                 history_keyboard_nav: false,
+                submodules_expanded: false,
                 _repo_subscriptions: Vec::new(),
                 _settings_subscription,
                 git_access: GitAccess::Yes,
@@ -1360,17 +1424,70 @@ impl GitPanel {
                 return None;
             };
 
-            self.workspace
-                .update(cx, |workspace, cx| {
-                    ProjectDiff::deploy_at(workspace, Some(entry.clone()), window, cx);
-                })
-                .ok();
+            // Submodule changes are shown as the commits between the old and new revisions.
+            let opened_submodule_diff = git_repo
+                .read(cx)
+                .submodule_for_path(&entry.repo_path)
+                .is_some()
+                && workspace.update(cx, |workspace, cx| {
+                    SubmoduleDiffView::deploy(
+                        git_repo.clone(),
+                        entry.repo_path.clone(),
+                        workspace,
+                        window,
+                        cx,
+                    )
+                });
+            if !opened_submodule_diff {
+                self.workspace
+                    .update(cx, |workspace, cx| {
+                        ProjectDiff::deploy_at(workspace, Some(entry.clone()), window, cx);
+                    })
+                    .ok();
+            }
             self.focus_handle.focus(window, cx);
 
             Some(())
         });
     }
 
+    fn open_submodule_diff(&mut self, path: RepoPath, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(repository), Some(workspace)) =
+            (self.active_repository.clone(), self.workspace.upgrade())
+        else {
+            return;
+        };
+        workspace.update(cx, |workspace, cx| {
+            SubmoduleDiffView::deploy(repository, path, workspace, window, cx)
+        });
+    }
+
+    fn open_merge_editor(
+        &mut self,
+        _: &git::OpenMergeEditor,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        maybe!({
+            let entry = self.entries.get(self.selected_entry?)?.status_entry()?;
+            let repository = self.active_repository.clone()?;
+            merge_editor::open(
+                repository,
+                entry.repo_path.clone(),
+                self.workspace.clone(),
+                window,
+                cx,
+            )
+            .detach_and_prompt_err(
+                "Failed to open the merge editor",
+                window,
+                cx,
+                |_, _, _| None,
+            );
+            Some(())
+        });
+    }
+
     fn open_solo_diff(
         &mut self,
         _: &menu::SecondaryConfirm,
@@ -2013,6 +2130,51 @@ impl GitPanel {
         .detach();
     }
 
+    pub(crate) fn rebase_control(&mut self, control: RebaseControl, cx: &mut Context<Self>) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+
+        cx.spawn(async move |this, cx| {
+            let rebase_task = active_repository
+                .update(cx, |repo, _| repo.rebase_control(control))
+                .await;
+            this.update(cx, |this, cx| {
+                if let Ok(Err(e)) = rebase_task {
+                    this.show_error_toast(format!("rebase {}", control.as_arg()), e, cx);
+                }
+                cx.notify();
+            })
+        })
+        .detach();
+    }
+
+    /// Runs `git submodule init`, `update` or `sync` on the given submodules, or on all of them
+    /// when `paths` is empty.
+    pub(crate) fn submodule_command(
+        &mut self,
+        command: SubmoduleCommand,
+        paths: Vec<RepoPath>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(active_repository) = self.active_repository.clone() else {
+            return;
+        };
+
+        cx.spawn(async move |this, cx| {
+            let submodule_task = active_repository
+                .update(cx, |repo, _| repo.submodule_command(command, paths))
+                .await;
+            this.update(cx, |this, cx| {
+                if let Ok(Err(e)) = submodule_task {
+                    this.show_error_toast(format!("submodule {}", command.as_str()), e, cx);
+                }
+                cx.notify();
+            })
+        })
+        .detach();
+    }
+
     pub fn stash_apply(&mut self, _: &StashApply, _window: &mut Window, cx: &mut Context<Self>) {
         let Some(active_repository) = self.active_repository.clone() else {
             return;
@@ -4676,6 +4838,8 @@ impl GitPanel {
                 head_commit,
                 Some(git_panel),
             ))
+            .children(self.render_rebase_progress(cx))
+            .children(self.render_submodules(cx))
             .when(title_exceeds_limit, |this| {
                 this.child(
                     h_flex()
@@ -4822,6 +4986,267 @@ impl GitPanel {
         Some(footer)
     }
 
+    fn render_rebase_progress(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let progress = self
+            .active_repository
+            .as_ref()?
+            .read(cx)
+            .merge
+            .rebase
+            .clone()?;
+        let has_conflicts = self.conflicted_count > 0;
+        let can_continue = !self.has_unstaged_conflicts();
+
+        let title = match &progress.branch {
+            Some(branch) => format!("Rebasing {branch} ({}/{})", progress.done, progress.total),
+            None => format!("Rebasing ({}/{})", progress.done, progress.total),
+        };
+        let stopped_at = progress
+            .stopped_at
+            .map(|sha| sha.display_short())
+            .unwrap_or_else(|| "the current commit".to_string());
+        let detail = if has_conflicts {
+            format!("Resolve and stage the conflicts from {stopped_at}, then continue.")
+        } else {
+            format!("Stopped at {stopped_at}. Amend it or add commits, then continue.")
+        };
+
+        Some(
+            v_flex()
+                .px_2()
+                .py_1p5()
+                .gap_1()
+                .border_t_1()
+                .border_color(if has_conflicts {
+                    cx.theme().status().warning_border
+                } else {
+                    cx.theme().status().info_border
+                })
+                .bg(if has_conflicts {
+                    cx.theme().status().warning_background.opacity(0.5)
+                } else {
+                    cx.theme().status().info_background.opacity(0.5)
+                })
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(
+                            Icon::new(if has_conflicts {
+                                IconName::Warning
+                            } else {
+                                IconName::GitBranch
+                            })
+                            .size(IconSize::XSmall)
+                            .color(if has_conflicts {
+                                Color::Warning
+                            } else {
+                                Color::Info
+                            }),
+                        )
+                        .child(Label::new(title).size(LabelSize::Small)),
+                )
+                .child(
+                    Label::new(detail)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .justify_end()
+                        .child(
+                            Button::new("rebase-abort", "Abort")
+                                .label_size(LabelSize::Small)
+                                .tooltip(Tooltip::text("git rebase --abort"))
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(git::RebaseAbort.boxed_clone(), cx);
+                                }),
+                        )
+                        .child(
+                            Button::new("rebase-skip", "Skip")
+                                .label_size(LabelSize::Small)
+                                .tooltip(Tooltip::text("git rebase --skip"))
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(git::RebaseSkip.boxed_clone(), cx);
+                                }),
+                        )
+                        .child(
+                            Button::new("rebase-continue", "Continue")
+                                .label_size(LabelSize::Small)
+                                .style(ButtonStyle::Filled)
+                                .disabled(!can_continue)
+                                .tooltip(Tooltip::text(if can_continue {
+                                    "git rebase --continue"
+                                } else {
+                                    "You must stage the resolved conflicts before continuing"
+                                }))
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(git::RebaseContinue.boxed_clone(), cx);
+                                }),
+                        ),
+                ),
+        )
+    }
+
+    fn render_submodules(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let submodules = self.active_repository.as_ref()?.read(cx).submodules.clone();
+        if submodules.is_empty() {
+            return None;
+        }
+        let out_of_date = submodules
+            .iter()
+            .filter(|submodule| submodule.state != SubmoduleState::UpToDate)
+            .count();
+        let title = if out_of_date == 0 {
+            format!("Submodules ({})", submodules.len())
+        } else {
+            format!(
+                "Submodules ({}, {out_of_date} out of date)",
+                submodules.len()
+            )
+        };
+        let expanded = self.submodules_expanded;
+
+        Some(
+            v_flex()
+                .px_2()
+                .py_1()
+                .gap_0p5()
+                .border_t_1()
+                .border_color(cx.theme().colors().border_variant)
+                .child(
+                    h_flex()
+                        .gap_1()
+                        .child(Disclosure::new("submodules-disclosure", expanded).on_click(
+                            cx.listener(|this, _, _, cx| {
+                                this.submodules_expanded = !this.submodules_expanded;
+                                cx.notify();
+                            }),
+                        ))
+                        .child(
+                            Label::new(title)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        )
+                        .child(div().flex_1())
+                        .child(
+                            Button::new("submodules-sync", "Sync")
+                                .label_size(LabelSize::Small)
+                                .tooltip(|_window, cx| {
+                                    Tooltip::for_action(
+                                        "git submodule sync",
+                                        &git::SyncSubmodules,
+                                        cx,
+                                    )
+                                })
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(git::SyncSubmodules.boxed_clone(), cx);
+                                }),
+                        )
+                        .child(
+                            Button::new("submodules-update", "Update All")
+                                .label_size(LabelSize::Small)
+                                .tooltip(|_window, cx| {
+                                    Tooltip::for_action(
+                                        "git submodule update --init",
+                                        &git::UpdateSubmodules,
+                                        cx,
+                                    )
+                                })
+                                .on_click(|_, window, cx| {
+                                    window.dispatch_action(git::UpdateSubmodules.boxed_clone(), cx);
+                                }),
+                        ),
+                )
+                .when(expanded, |this| {
+                    this.child(
+                        v_flex()
+                            .id("submodules")
+                            .max_h(px(200.))
+                            .overflow_y_scroll()
+                            .children(
+                                submodules.iter().enumerate().map(|(ix, submodule)| {
+                                    self.render_submodule(ix, submodule, cx)
+                                }),
+                            ),
+                    )
+                }),
+        )
+    }
+
+    fn render_submodule(
+        &self,
+        ix: usize,
+        submodule: &Submodule,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let change = submodule_change(submodule);
+        let (shas, color) = match (submodule.state, change) {
+            (SubmoduleState::Uninitialized, _) => ("not initialized".to_string(), Color::Muted),
+            (SubmoduleState::Conflicted, _) => {
+                ("conflicted".to_string(), Color::VersionControlConflict)
+            }
+            (_, Some((old_sha, new_sha))) => (
+                format!("{} → {}", old_sha.display_short(), new_sha.display_short()),
+                Color::VersionControlModified,
+            ),
+            (_, None) => (
+                submodule
+                    .checked_out_sha
+                    .map(|sha| sha.display_short())
+                    .unwrap_or_default(),
+                Color::Muted,
+            ),
+        };
+        let path = submodule.path.clone();
+        let git_panel = cx.weak_entity();
+        let menu_submodule = submodule.clone();
+
+        h_flex()
+            .id(("submodule", ix))
+            .pl_5()
+            .gap_1()
+            .rounded_sm()
+            .hover(|style| style.bg(cx.theme().colors().ghost_element_hover))
+            .tooltip(Tooltip::text(submodule_tooltip(submodule)))
+            .when(change.is_some(), |row| {
+                row.cursor_pointer()
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.open_submodule_diff(path.clone(), window, cx);
+                    }))
+            })
+            .child(Icon::new(IconName::Box).size(IconSize::XSmall).color(color))
+            .child(
+                h_flex().flex_1().min_w_0().child(
+                    Label::new(submodule.path.as_unix_str().to_string())
+                        .size(LabelSize::Small)
+                        .truncate(),
+                ),
+            )
+            .child(
+                Label::new(shas)
+                    .buffer_font(cx)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(
+                PopoverMenu::new(("submodule-menu", ix))
+                    .trigger(
+                        IconButton::new(("submodule-menu-trigger", ix), IconName::Ellipsis)
+                            .icon_size(IconSize::XSmall),
+                    )
+                    .menu(move |window, cx| {
+                        let git_panel = git_panel.clone();
+                        let submodule = menu_submodule.clone();
+                        Some(ContextMenu::build(window, cx, move |menu, _, _| {
+                            submodule_menu_entries(menu, git_panel, &submodule)
+                        }))
+                    })
+                    .anchor(Anchor::TopRight),
+            )
+    }
+
     fn render_commit_button(&self, cx: &mut Context<Self>) -> impl IntoElement {
         let (can_commit, tooltip) = self.configure_commit_button(cx);
         let title = self.commit_button_title();
@@ -5941,18 +6366,25 @@ impl GitPanel {
         let Some(entry) = self.entries.get(ix).and_then(|e| e.status_entry()) else {
             return;
         };
-        let stage_title = if entry.status.staging().is_fully_staged() {
-            "Unstage File"
-        } else {
-            "Stage File"
+        let submodule = self
+            .active_repository
+            .as_ref()
+            .and_then(|repo| repo.read(cx).submodule_for_path(&entry.repo_path).cloned());
+        let stage_title = match (entry.status.staging().is_fully_staged(), &submodule) {
+            (true, Some(_)) => "Unstage Submodule",
+            (true, None) => "Unstage File",
+            (false, Some(_)) => "Stage Submodule",
+            (false, None) => "Stage File",
         };
         let restore_title = if entry.status.is_created() {
             "Trash File"
         } else {
             "Discard Changes"
         };
+        let git_panel = cx.weak_entity();
         let context_menu = ContextMenu::build(window, cx, |context_menu, _, _| {
             let is_created = entry.status.is_created();
+            let is_conflicted = entry.status.is_conflicted();
             context_menu
                 .context(self.focus_handle.clone())
                 .action(stage_title, ToggleStaged.boxed_clone())
@@ -5965,11 +6397,17 @@ impl GitPanel {
                 .separator()
                 .action("Open Diff", menu::Confirm.boxed_clone())
                 .action("Open Diff (File)", menu::SecondaryConfirm.boxed_clone())
+                .when(is_conflicted, |context_menu| {
+                    context_menu.action("Open Merge Editor", git::OpenMergeEditor.boxed_clone())
+                })
                 .when(!is_created, |context_menu| {
                     context_menu
                         .separator()
                         .action("View File History", Box::new(git::FileHistory))
                 })
+                .when_some(submodule, |context_menu, submodule| {
+                    submodule_menu_entries(context_menu.separator(), git_panel, &submodule)
+                })
         });
         self.selected_entry = Some(ix);
         self.set_context_menu(context_menu, position, window, cx);
@@ -6042,7 +6480,8 @@ impl GitPanel {
         let marked = self.marked_entries.contains(&ix);
         let status_style = settings.status_style;
         let status = entry.status;
-        let file_icon = if settings.file_icons {
+        let submodule = repo.submodule_for_path(&entry.repo_path);
+        let file_icon = if settings.file_icons && submodule.is_none() {
             FileIcons::get_icon(entry.repo_path.as_std_path(), cx)
         } else {
             None
@@ -6081,6 +6520,8 @@ impl GitPanel {
             ElementId::Name(format!("entry_{}_{}_checkbox_wrapper", display_name, ix).into());
         let checkbox_id: ElementId =
             ElementId::Name(format!("entry_{}_{}_checkbox", display_name, ix).into());
+        let submodule_id: ElementId =
+            ElementId::Name(format!("entry_{}_{}_submodule", display_name, ix).into());
 
         let stage_status = GitPanel::stage_status_for_entry(entry, &repo);
         let mut is_staged: ToggleState = match stage_status {
@@ -6132,9 +6573,13 @@ impl GitPanel {
                                 .color(Color::Muted)
                         })
                         .unwrap_or_else(|| {
-                            Icon::new(IconName::File)
-                                .size(IconSize::Small)
-                                .color(Color::Muted)
+                            Icon::new(if submodule.is_some() {
+                                IconName::Box
+                            } else {
+                                IconName::File
+                            })
+                            .size(IconSize::Small)
+                            .color(Color::Muted)
                         }),
                 )
             })
@@ -6159,7 +6604,31 @@ impl GitPanel {
                         status.is_deleted(),
                     ))
                 }
-            });
+            })
+            .when_some(
+                submodule.and_then(|submodule| {
+                    let (old_sha, new_sha) = submodule_change(submodule)?;
+                    Some((old_sha, new_sha, submodule_tooltip(submodule)))
+                }),
+                |this, (old_sha, new_sha, tooltip)| {
+                    this.child(
+                        div()
+                            .id(submodule_id)
+                            .flex_none()
+                            .child(
+                                Label::new(format!(
+                                    "{} → {}",
+                                    old_sha.display_short(),
+                                    new_sha.display_short()
+                                ))
+                                .buffer_font(cx)
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                            )
+                            .tooltip(Tooltip::text(tooltip)),
+                    )
+                },
+            );
 
         let id_for_diff_stat = id.clone();
 
@@ -6699,6 +7168,7 @@ impl Render for GitPanel {
             .on_action(cx.listener(Self::close_panel))
             .on_action(cx.listener(Self::open_diff))
             .on_action(cx.listener(Self::open_solo_diff))
+            .on_action(cx.listener(Self::open_merge_editor))
             .on_action(cx.listener(Self::focus_changes_list))
             .on_action(cx.listener(Self::focus_editor))
             .on_action(cx.listener(Self::expand_commit_editor))
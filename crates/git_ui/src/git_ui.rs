@@ -12,8 +12,11 @@ mod blame_ui;
 pub mod clone;
 
 use git::{
+    bisect::BisectTerm,
+    rebase::RebaseControl,
     repository::{Branch, CommitDetails, Upstream, UpstreamTracking, UpstreamTrackingStatus},
     status::{FileStatus, StatusCode, UnmergedStatus, UnmergedStatusCode},
+    submodule::SubmoduleCommand,
 };
 use gpui::{
     App, ClipboardItem, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
@@ -30,6 +33,7 @@ use zed_actions;
 use crate::{commit_view::CommitView, git_panel::GitPanel, text_diff_view::TextDiffView};
 
 mod askpass_modal;
+pub mod bisect;
 pub mod branch_picker;
 mod commit_modal;
 pub mod commit_tooltip;
@@ -41,18 +45,23 @@ pub mod git_panel;
 mod git_panel_settings;
 pub mod git_picker;
 mod git_runtime_diagnostics;
+pub mod merge_editor;
 pub mod multi_diff_view;
+mod notebook_diff;
 pub mod picker_prompt;
 pub mod project_diff;
+pub mod rebase_editor;
 pub(crate) mod remote_output;
 pub mod repository_selector;
 pub mod solo_diff_view;
 pub mod stash_picker;
+pub mod submodule_diff_view;
 pub mod text_diff_view;
 pub mod worktree_names;
 pub mod worktree_picker;
 pub mod worktree_service;
 
+pub use bisect::BisectIndicator;
 pub use conflict_view::MergeConflictIndicator;
 
 pub fn get_provider_icon(name: &str) -> IconName {
@@ -262,6 +271,66 @@ pub fn init(cx: &mut App) {
                 panel.uncommit(window, cx);
             })
         });
+        workspace.register_action(|workspace, _: &git::InteractiveRebase, window, cx| {
+            interactive_rebase(workspace, window, cx);
+        });
+        workspace.register_action(|workspace, _: &git::RebaseContinue, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.rebase_control(RebaseControl::Continue, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::RebaseSkip, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.rebase_control(RebaseControl::Skip, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::RebaseAbort, _, cx| {
+            let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
+                return;
+            };
+            panel.update(cx, |panel, cx| {
+                panel.rebase_control(RebaseControl::Abort, cx);
+            });
+        });
+        workspace.register_action(|workspace, _: &git::BisectGood, _, cx| {
+            bisect_mark(workspace, BisectTerm::Good, cx);
+        });
+        workspace.register_action(|workspace, _: &git::BisectBad, _, cx| {
+            bisect_mark(workspace, BisectTerm::Bad, cx);
+        });
+        workspace.register_action(|workspace, _: &git::BisectSkip, _, cx| {
+            bisect_mark(workspace, BisectTerm::Skip, cx);
+        });
+        workspace.register_action(|workspace, _: &git::BisectRun, window, cx| {
+            let Some(repo) = workspace.project().read(cx).active_repository(cx) else {
+                return;
+            };
+            bisect::run_task(repo, workspace, window, cx);
+        });
+        workspace.register_action(|workspace, _: &git::BisectReset, _, cx| {
+            let Some(repo) = workspace.project().read(cx).active_repository(cx) else {
+                return;
+            };
+            bisect::reset(repo, workspace.weak_handle(), cx);
+        });
+        workspace.register_action(|workspace, _: &git::UpdateSubmodules, _, cx| {
+            submodule_command(workspace, SubmoduleCommand::Update, cx);
+        });
+        workspace.register_action(|workspace, _: &git::InitSubmodules, _, cx| {
+            submodule_command(workspace, SubmoduleCommand::Init, cx);
+        });
+        workspace.register_action(|workspace, _: &git::SyncSubmodules, _, cx| {
+            submodule_command(workspace, SubmoduleCommand::Sync, cx);
+        });
+        workspace.register_action(|workspace, _: &git::OpenMergeEditor, window, cx| {
+            open_merge_editor(workspace, window, cx);
+        });
         workspace.register_action(|workspace, _action: &git::Init, window, cx| {
             let Some(panel) = workspace.panel::<git_panel::GitPanel>(cx) else {
                 return;
@@ -448,6 +517,76 @@ fn rename_current_branch(
     });
 }
 
+fn interactive_rebase(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+    let Some(repo) = workspace.project().read(cx).active_repository(cx) else {
+        return;
+    };
+    let Some(upstream) = repo
+        .read(cx)
+        .branch
+        .as_ref()
+        .and_then(|branch| branch.upstream.as_ref())
+        .map(|upstream| upstream.ref_name.to_string())
+    else {
+        struct NoUpstreamToast;
+        workspace.show_toast(
+            Toast::new(
+                NotificationId::unique::<NoUpstreamToast>(),
+                "The current branch has no upstream. Start the rebase from the branch picker or the Git Graph instead.",
+            )
+            .autohide(),
+            cx,
+        );
+        return;
+    };
+
+    rebase_editor::open(repo, Some(upstream), workspace.weak_handle(), window, cx);
+}
+
+fn bisect_mark(workspace: &mut Workspace, term: BisectTerm, cx: &mut Context<Workspace>) {
+    let Some(repo) = workspace.project().read(cx).active_repository(cx) else {
+        return;
+    };
+    bisect::mark(repo, term, None, workspace.weak_handle(), cx);
+}
+
+fn submodule_command(
+    workspace: &mut Workspace,
+    command: SubmoduleCommand,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(panel) = workspace.panel::<GitPanel>(cx) else {
+        return;
+    };
+    panel.update(cx, |panel, cx| {
+        panel.submodule_command(command, Vec::new(), cx);
+    });
+}
+
+/// Opens the file in the active editor in a merge editor, if it has conflicts.
+fn open_merge_editor(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+        return;
+    };
+    let buffer_id = buffer.read(cx).remote_id();
+    let Some((repository, repo_path)) = workspace
+        .project()
+        .read(cx)
+        .git_store()
+        .read(cx)
+        .repository_and_path_for_buffer_id(buffer_id, cx)
+    else {
+        return;
+    };
+    merge_editor::open(repository, repo_path, workspace.weak_handle(), window, cx)
+        .detach_and_prompt_err("Failed to open the merge editor", window, cx, |_, _, _| {
+            None
+        });
+}
+
 fn copy_branch_name(workspace: &mut Workspace, cx: &mut Context<Workspace>) {
     let Some(panel) = workspace.panel::<GitPanel>(cx) else {
         return;
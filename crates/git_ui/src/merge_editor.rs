@@ -0,0 +1,1017 @@
+//! MergeEditor resolves the conflicts in a file with its three versions side by side: the base,
+//! ours and theirs versions from the index are shown above the result, which is the file itself.
+
+use anyhow::{Context as _, Result};
+use buffer_diff::BufferDiff;
+use editor::{Editor, EditorEvent, MultiBuffer, SelectionEffects, scroll::Autoscroll};
+use git::repository::RepoPath;
+use gpui::{
+    Action, AnyElement, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, Subscription, Task, WeakEntity, Window, actions,
+};
+use language::{
+    Anchor, Buffer, Capability, Language, LineEnding, OffsetRangeExt as _, ReplicaId, Rope,
+    TextBuffer, ToOffset as _,
+};
+use project::{
+    ConflictRegion, ConflictSet, ConflictSetUpdate, Project, ProjectPath, git_store::Repository,
+};
+use std::{ops::Range, sync::Arc};
+use ui::{Tooltip, prelude::*};
+use util::paths::PathStyle;
+use workspace::{
+    Item, ItemHandle as _, Workspace,
+    item::{ItemEvent, SaveOptions, TabContentParams},
+};
+
+use crate::{
+    conflict_view::resolve_conflict, file_diff_view::build_buffer_diff, git_panel::show_error_toast,
+};
+
+actions!(
+    merge_editor,
+    [
+        /// Resolves the conflict at the cursor by keeping our side.
+        AcceptOurs,
+        /// Resolves the conflict at the cursor by keeping their side.
+        AcceptTheirs,
+        /// Resolves the conflict at the cursor by keeping our side followed by theirs.
+        AcceptBoth,
+        /// Moves to the next unresolved conflict.
+        NextConflict,
+        /// Moves to the previous unresolved conflict.
+        PreviousConflict,
+    ]
+);
+
+/// Which versions of a conflict to keep when resolving it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Resolution {
+    Ours,
+    Theirs,
+    Both,
+}
+
+impl Resolution {
+    fn kept_ranges(self, conflict: &ConflictRegion) -> Vec<Range<Anchor>> {
+        match self {
+            Self::Ours => vec![conflict.ours.clone()],
+            Self::Theirs => vec![conflict.theirs.clone()],
+            Self::Both => vec![conflict.ours.clone(), conflict.theirs.clone()],
+        }
+    }
+}
+
+/// Opens the conflicted file at `repo_path` in a [`MergeEditor`], reusing the one already open
+/// for it.
+pub fn open(
+    repository: Entity<Repository>,
+    repo_path: RepoPath,
+    workspace: WeakEntity<Workspace>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Task<Result<Entity<MergeEditor>>> {
+    let Some(workspace_entity) = workspace.upgrade() else {
+        return Task::ready(Err(anyhow::anyhow!("workspace was dropped")));
+    };
+
+    let existing = workspace_entity
+        .read(cx)
+        .items_of_type::<MergeEditor>(cx)
+        .find(|item| {
+            let item = item.read(cx);
+            item.repository == repository && item.repo_path == repo_path
+        });
+    if let Some(existing) = existing {
+        workspace_entity.update(cx, |workspace, cx| {
+            workspace.activate_item(&existing, true, true, window, cx);
+        });
+        return Task::ready(Ok(existing));
+    }
+
+    let is_conflicted = repository
+        .read(cx)
+        .status_for_path(&repo_path)
+        .is_some_and(|entry| entry.status.is_conflicted());
+    if !is_conflicted {
+        return Task::ready(Err(anyhow::anyhow!(
+            "{} has no merge conflicts",
+            repo_path.as_ref().display(PathStyle::local())
+        )));
+    }
+    let Some(project_path) = repository
+        .read(cx)
+        .repo_path_to_project_path(&repo_path, cx)
+    else {
+        return Task::ready(Err(anyhow::anyhow!(
+            "could not resolve repository path {repo_path:?}"
+        )));
+    };
+
+    let project = workspace_entity.read(cx).project().clone();
+    window.spawn(cx, async move |cx| {
+        let buffer = project
+            .update(cx, |project, cx| project.open_buffer(project_path, cx))
+            .await?;
+        let stages = repository
+            .update(cx, |repository, _| {
+                repository.load_conflict_stages(repo_path.clone())
+            })
+            .await??;
+        let git_store = project.read_with(cx, |project, _| project.git_store().clone());
+        let conflict_set = git_store
+            .update(cx, |git_store, cx| {
+                git_store.open_conflict_set(buffer.clone(), cx)
+            })
+            .await;
+
+        let language = buffer.read_with(cx, |buffer, _| buffer.language().cloned());
+        let languages = project.read_with(cx, |project, _| project.languages().clone());
+        let base_buffer = build_stage_buffer(stages.base, language.clone(), cx);
+        let ours_buffer = build_stage_buffer(stages.ours, language.clone(), cx);
+        let theirs_buffer = build_stage_buffer(stages.theirs, language, cx);
+        let ours_diff =
+            build_buffer_diff(&base_buffer, &ours_buffer, languages.clone(), cx).await?;
+        let theirs_diff = build_buffer_diff(&base_buffer, &theirs_buffer, languages, cx).await?;
+
+        workspace_entity.update_in(cx, |workspace, window, cx| {
+            let weak_workspace = workspace.weak_handle();
+            let merge_editor = cx.new(|cx| {
+                MergeEditor::new(
+                    repository,
+                    repo_path,
+                    project,
+                    weak_workspace,
+                    buffer,
+                    conflict_set,
+                    StageBuffers {
+                        base: base_buffer,
+                        ours: (ours_buffer, ours_diff),
+                        theirs: (theirs_buffer, theirs_diff),
+                    },
+                    window,
+                    cx,
+                )
+            });
+            workspace.add_item_to_active_pane(
+                Box::new(merge_editor.clone()),
+                None,
+                true,
+                window,
+                cx,
+            );
+            merge_editor
+        })
+    })
+}
+
+/// Builds a read-only buffer holding one version of the conflicted file.
+fn build_stage_buffer(
+    text: Option<String>,
+    language: Option<Arc<Language>>,
+    cx: &mut impl AppContext,
+) -> Entity<Buffer> {
+    let mut text = text.unwrap_or_default();
+    let line_ending = LineEnding::detect(&text);
+    LineEnding::normalize(&mut text);
+    cx.new(|cx| {
+        let buffer = TextBuffer::new_normalized(
+            ReplicaId::LOCAL,
+            cx.entity_id().as_non_zero_u64().into(),
+            line_ending,
+            Rope::from(text),
+        );
+        let mut buffer = Buffer::build(buffer, None, Capability::ReadOnly);
+        buffer.set_language_async(language, cx);
+        buffer
+    })
+}
+
+/// The buffers showing the base, ours and theirs versions of the file, with ours and theirs
+/// diffed against the base.
+struct StageBuffers {
+    base: Entity<Buffer>,
+    ours: (Entity<Buffer>, Entity<BufferDiff>),
+    theirs: (Entity<Buffer>, Entity<BufferDiff>),
+}
+
+pub struct MergeEditor {
+    repository: Entity<Repository>,
+    repo_path: RepoPath,
+    project: Entity<Project>,
+    workspace: WeakEntity<Workspace>,
+    result_buffer: Entity<Buffer>,
+    conflict_set: Entity<ConflictSet>,
+    base_editor: Entity<Editor>,
+    ours_editor: Entity<Editor>,
+    theirs_editor: Entity<Editor>,
+    result_editor: Entity<Editor>,
+    ours_branch_name: SharedString,
+    theirs_branch_name: SharedString,
+    /// Whether the result has been saved and staged since its conflicts last changed.
+    staged: bool,
+    /// Whether the result had conflict markers when its conflicts last changed.
+    has_conflict_markers: bool,
+    _stage_task: Task<()>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl MergeEditor {
+    fn new(
+        repository: Entity<Repository>,
+        repo_path: RepoPath,
+        project: Entity<Project>,
+        workspace: WeakEntity<Workspace>,
+        result_buffer: Entity<Buffer>,
+        conflict_set: Entity<ConflictSet>,
+        stages: StageBuffers,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let base_editor = Self::build_stage_editor(stages.base, None, &project, window, cx);
+        let (ours_buffer, ours_diff) = stages.ours;
+        let ours_editor =
+            Self::build_stage_editor(ours_buffer, Some(ours_diff), &project, window, cx);
+        let (theirs_buffer, theirs_diff) = stages.theirs;
+        let theirs_editor =
+            Self::build_stage_editor(theirs_buffer, Some(theirs_diff), &project, window, cx);
+        let result_editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_buffer(result_buffer.clone(), Some(project.clone()), window, cx);
+            editor.set_should_serialize(false, cx);
+            editor
+        });
+
+        let (ours_branch_name, theirs_branch_name) = conflict_set
+            .read(cx)
+            .snapshot
+            .conflicts
+            .first()
+            .map(|conflict| {
+                (
+                    conflict.ours_branch_name.clone(),
+                    conflict.theirs_branch_name.clone(),
+                )
+            })
+            .unwrap_or_else(|| ("Ours".into(), "Theirs".into()));
+
+        let subscriptions = vec![
+            cx.subscribe_in(&conflict_set, window, Self::conflicts_updated),
+            cx.subscribe(&result_editor, |_, _, event: &EditorEvent, cx| {
+                cx.emit(event.clone());
+            }),
+        ];
+
+        let mut this = Self {
+            repository,
+            repo_path,
+            project,
+            workspace,
+            result_buffer,
+            conflict_set,
+            base_editor,
+            ours_editor,
+            theirs_editor,
+            result_editor,
+            ours_branch_name,
+            theirs_branch_name,
+            staged: false,
+            has_conflict_markers: true,
+            _stage_task: Task::ready(()),
+            _subscriptions: subscriptions,
+        };
+        this.go_to_conflict(0, window, cx);
+        this
+    }
+
+    fn build_stage_editor(
+        buffer: Entity<Buffer>,
+        diff: Option<Entity<BufferDiff>>,
+        project: &Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<Editor> {
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::singleton(buffer, cx);
+            if let Some(diff) = diff {
+                multibuffer.add_diff(diff, cx);
+                multibuffer.set_all_diff_hunks_expanded(cx);
+            }
+            multibuffer
+        });
+        cx.new(|cx| {
+            let mut editor =
+                Editor::for_multibuffer(multibuffer, Some(project.clone()), window, cx);
+            editor.set_read_only(true);
+            editor.set_should_serialize(false, cx);
+            editor.start_temporary_diff_override();
+            editor.disable_diagnostics(cx);
+            editor.set_render_diff_hunk_controls(
+                Arc::new(|_, _, _, _, _, _, _, _| gpui::Empty.into_any_element()),
+                cx,
+            );
+            editor
+        })
+    }
+
+    fn conflicts(&self, cx: &App) -> Arc<[ConflictRegion]> {
+        self.conflict_set.read(cx).snapshot.conflicts.clone()
+    }
+
+    fn conflicts_updated(
+        &mut self,
+        _: &Entity<ConflictSet>,
+        _: &ConflictSetUpdate,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        cx.notify();
+        // Any change to the conflicts leaves the staged version behind, unless git no longer
+        // reports the file as conflicted because it was staged elsewhere.
+        self.staged = !self.conflict_set.read(cx).has_conflict;
+        // Removing a single marker by hand also leaves no parseable conflicts, so only treat the
+        // file as resolved once every marker is gone.
+        let had_conflict_markers = self.has_conflict_markers;
+        self.has_conflict_markers = !self.conflicts(cx).is_empty()
+            || has_conflict_markers(&self.result_buffer.read(cx).text());
+        // Only stage the result when its last conflict is resolved, not on every later edit.
+        if had_conflict_markers && !self.has_conflict_markers && !self.staged {
+            self.save_and_stage(window, cx);
+        }
+    }
+
+    /// The offset of the cursor in the result.
+    fn cursor_offset(&self, cx: &App) -> Option<usize> {
+        let editor = self.result_editor.read(cx);
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let (anchor, buffer) =
+            snapshot.anchor_to_buffer_anchor(editor.selections.newest_anchor().head())?;
+        Some(anchor.to_offset(buffer))
+    }
+
+    /// The index of the conflict containing the cursor, or else the nearest one after it.
+    fn current_conflict_ix(&self, cx: &App) -> Option<usize> {
+        let conflicts = self.conflicts(cx);
+        let cursor = self.cursor_offset(cx)?;
+        let buffer = self.result_buffer.read(cx).snapshot();
+        conflicts
+            .iter()
+            .position(|conflict| conflict.range.to_offset(&buffer).end >= cursor)
+            .or(conflicts.len().checked_sub(1))
+    }
+
+    fn go_to_conflict(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(conflict) = self.conflicts(cx).get(ix).cloned() else {
+            return;
+        };
+        let buffer = self.result_buffer.read(cx).snapshot();
+        let text_for =
+            |range: &Range<Anchor>| buffer.text_for_range(range.clone()).collect::<String>();
+        let ours = text_for(&conflict.ours);
+        let theirs = text_for(&conflict.theirs);
+        let base = conflict.base.as_ref().map(text_for);
+
+        reveal_range(&self.result_editor, conflict.range.start, window, cx);
+        // The conflict markers hold each side's text verbatim, so it can be found in that side's
+        // version of the file.
+        for (editor, text) in [
+            (&self.ours_editor, Some(ours)),
+            (&self.theirs_editor, Some(theirs)),
+            (&self.base_editor, base),
+        ] {
+            if let Some(text) = text {
+                reveal_text(editor, &text, window, cx);
+            }
+        }
+        cx.notify();
+    }
+
+    fn next_conflict(&mut self, _: &NextConflict, window: &mut Window, cx: &mut Context<Self>) {
+        let conflicts = self.conflicts(cx);
+        let Some(cursor) = self.cursor_offset(cx) else {
+            return;
+        };
+        let buffer = self.result_buffer.read(cx).snapshot();
+        let ix = conflicts
+            .iter()
+            .position(|conflict| conflict.range.start.to_offset(&buffer) > cursor)
+            .unwrap_or(0);
+        self.go_to_conflict(ix, window, cx);
+    }
+
+    fn previous_conflict(
+        &mut self,
+        _: &PreviousConflict,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let conflicts = self.conflicts(cx);
+        let Some(cursor) = self.cursor_offset(cx) else {
+            return;
+        };
+        let buffer = self.result_buffer.read(cx).snapshot();
+        let Some(ix) = conflicts
+            .iter()
+            .rposition(|conflict| conflict.range.start.to_offset(&buffer) < cursor)
+            .or(conflicts.len().checked_sub(1))
+        else {
+            return;
+        };
+        self.go_to_conflict(ix, window, cx);
+    }
+
+    fn accept_ours(&mut self, _: &AcceptOurs, window: &mut Window, cx: &mut Context<Self>) {
+        self.resolve_current(Resolution::Ours, window, cx);
+    }
+
+    fn accept_theirs(&mut self, _: &AcceptTheirs, window: &mut Window, cx: &mut Context<Self>) {
+        self.resolve_current(Resolution::Theirs, window, cx);
+    }
+
+    fn accept_both(&mut self, _: &AcceptBoth, window: &mut Window, cx: &mut Context<Self>) {
+        self.resolve_current(Resolution::Both, window, cx);
+    }
+
+    /// Resolves the conflict at the cursor and moves on to the next one.
+    fn resolve_current(
+        &mut self,
+        resolution: Resolution,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let conflicts = self.conflicts(cx);
+        let Some(ix) = self.current_conflict_ix(cx) else {
+            return;
+        };
+        let conflict = conflicts[ix].clone();
+        resolve_conflict(
+            self.result_editor.downgrade(),
+            conflict.clone(),
+            resolution.kept_ranges(&conflict),
+            window,
+            cx,
+        )
+        .detach();
+
+        // The conflict is resolved in a later task, so the conflicts are still indexed as before.
+        if let Some(next) = (ix + 1 < conflicts.len())
+            .then_some(ix + 1)
+            .or((ix > 0).then_some(0))
+        {
+            self.go_to_conflict(next, window, cx);
+        }
+    }
+
+    fn save_and_stage(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let save = self.project.update(cx, |project, cx| {
+            project.save_buffer(self.result_buffer.clone(), cx)
+        });
+        let repository = self.repository.clone();
+        let repo_path = self.repo_path.clone();
+        let workspace = self.workspace.clone();
+        self._stage_task = cx.spawn_in(window, async move |this, cx| {
+            let result = async {
+                save.await.context("saving the merge result")?;
+                repository
+                    .update(cx, |repository, cx| {
+                        repository.stage_entries(vec![repo_path], cx)
+                    })
+                    .await
+            }
+            .await;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(()) => this.staged = true,
+                    Err(error) => {
+                        if let Some(workspace) = workspace.upgrade() {
+                            show_error_toast(workspace, "add", error, cx);
+                        }
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn render_status(&self, conflict_count: usize) -> impl IntoElement {
+        let (label, color) = match (conflict_count, self.staged) {
+            (0, true) => ("All Conflicts Resolved and Staged".into(), Color::Created),
+            (0, false) => ("All Conflicts Resolved".into(), Color::Default),
+            (1, _) => ("1 Conflict Remaining".into(), Color::Warning),
+            (count, _) => (
+                SharedString::from(format!("{count} Conflicts Remaining")),
+                Color::Warning,
+            ),
+        };
+        h_flex()
+            .gap_1()
+            .child(
+                Icon::new(IconName::GitMergeConflict)
+                    .size(IconSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(Label::new(
+                self.repo_path
+                    .as_ref()
+                    .display(PathStyle::local())
+                    .into_owned(),
+            ))
+            .child(Label::new(label).size(LabelSize::Small).color(color))
+    }
+
+    fn render_controls(&self, conflict_count: usize, cx: &Context<Self>) -> impl IntoElement {
+        let has_conflicts = conflict_count > 0;
+        let accept_button = |id: &'static str, label: &'static str, resolution: Resolution| {
+            let action: Box<dyn Action> = match resolution {
+                Resolution::Ours => Box::new(AcceptOurs),
+                Resolution::Theirs => Box::new(AcceptTheirs),
+                Resolution::Both => Box::new(AcceptBoth),
+            };
+            Button::new(id, label)
+                .label_size(LabelSize::Small)
+                .disabled(!has_conflicts)
+                .tooltip(Tooltip::for_action_title(label, action.as_ref()))
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.resolve_current(resolution, window, cx)
+                }))
+        };
+        h_flex()
+            .gap_1()
+            .child(
+                IconButton::new("previous-conflict", IconName::ArrowUp)
+                    .icon_size(IconSize::Small)
+                    .disabled(!has_conflicts)
+                    .tooltip(Tooltip::for_action_title(
+                        "Previous Conflict",
+                        &PreviousConflict,
+                    ))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.previous_conflict(&PreviousConflict, window, cx)
+                    })),
+            )
+            .child(
+                IconButton::new("next-conflict", IconName::ArrowDown)
+                    .icon_size(IconSize::Small)
+                    .disabled(!has_conflicts)
+                    .tooltip(Tooltip::for_action_title("Next Conflict", &NextConflict))
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.next_conflict(&NextConflict, window, cx)
+                    })),
+            )
+            .child(accept_button(
+                "accept-ours",
+                "Accept Ours",
+                Resolution::Ours,
+            ))
+            .child(accept_button(
+                "accept-theirs",
+                "Accept Theirs",
+                Resolution::Theirs,
+            ))
+            .child(accept_button(
+                "accept-both",
+                "Accept Both",
+                Resolution::Both,
+            ))
+            .when(!has_conflicts && !self.staged, |this| {
+                this.child(
+                    Button::new("save-and-stage", "Save and Stage")
+                        .label_size(LabelSize::Small)
+                        .style(ButtonStyle::Filled)
+                        .on_click(
+                            cx.listener(|this, _, window, cx| this.save_and_stage(window, cx)),
+                        ),
+                )
+            })
+    }
+
+    fn render_pane(
+        &self,
+        title: SharedString,
+        editor: &Entity<Editor>,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        v_flex()
+            .flex_1()
+            .min_w_0()
+            .min_h_0()
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(Label::new(title).size(LabelSize::Small).color(Color::Muted)),
+            )
+            .child(div().flex_1().min_h_0().child(editor.clone()))
+    }
+}
+
+/// Whether any line of `text` is a conflict marker.
+///
+/// A `=======` line on its own doesn't count, since it also underlines Markdown and
+/// reStructuredText headings.
+fn has_conflict_markers(text: &str) -> bool {
+    text.lines().any(|line| {
+        ["<<<<<<<", "|||||||", ">>>>>>>"].iter().any(|marker| {
+            line.strip_prefix(marker)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+        })
+    })
+}
+
+/// Moves the cursor of a singleton editor to a position in its buffer.
+fn reveal_range(editor: &Entity<Editor>, position: Anchor, window: &mut Window, cx: &mut App) {
+    editor.update(cx, |editor, cx| {
+        let snapshot = editor.buffer().read(cx).snapshot(cx);
+        let Some(range) = snapshot.buffer_anchor_range_to_anchor_range(position..position) else {
+            return;
+        };
+        editor.change_selections(
+            SelectionEffects::scroll(Autoscroll::center()),
+            window,
+            cx,
+            |selections| selections.select_anchor_ranges([range]),
+        );
+    });
+}
+
+/// Moves the cursor of a singleton editor to the first occurrence of `text` in its buffer.
+fn reveal_text(editor: &Entity<Editor>, text: &str, window: &mut Window, cx: &mut App) {
+    if text.is_empty() {
+        return;
+    }
+    let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+        return;
+    };
+    let buffer = buffer.read(cx).snapshot();
+    let Some(offset) = buffer.text().find(text) else {
+        return;
+    };
+    reveal_range(editor, buffer.anchor_after(offset), window, cx);
+}
+
+impl EventEmitter<EditorEvent> for MergeEditor {}
+
+impl Focusable for MergeEditor {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.result_editor.focus_handle(cx)
+    }
+}
+
+impl Item for MergeEditor {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitMergeConflict).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        let name = self
+            .repo_path
+            .file_name()
+            .unwrap_or(self.repo_path.as_unix_str());
+        format!("{name} (Merge)").into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(
+            format!(
+                "Merging {} into {}",
+                self.theirs_branch_name, self.ours_branch_name
+            )
+            .into(),
+        )
+    }
+
+    fn to_item_events(event: &EditorEvent, f: &mut dyn FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Merge Editor Opened")
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.result_editor.for_each_project_item(cx, f)
+    }
+
+    fn active_project_path(&self, cx: &App) -> Option<ProjectPath> {
+        self.repository
+            .read(cx)
+            .repo_path_to_project_path(&self.repo_path, cx)
+    }
+
+    fn is_dirty(&self, cx: &App) -> bool {
+        self.result_buffer.read(cx).is_dirty()
+    }
+
+    fn has_conflict(&self, cx: &App) -> bool {
+        self.result_buffer.read(cx).has_conflict()
+    }
+
+    fn can_save(&self, _: &App) -> bool {
+        true
+    }
+
+    fn save(
+        &mut self,
+        options: SaveOptions,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.result_editor.save(options, project, window, cx)
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.result_editor.deactivated(window, cx);
+    }
+}
+
+impl Render for MergeEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let conflict_count = self.conflicts(cx).len();
+        let border_color = cx.theme().colors().border;
+
+        v_flex()
+            .key_context("MergeEditor")
+            .on_action(cx.listener(Self::accept_ours))
+            .on_action(cx.listener(Self::accept_theirs))
+            .on_action(cx.listener(Self::accept_both))
+            .on_action(cx.listener(Self::next_conflict))
+            .on_action(cx.listener(Self::previous_conflict))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .px_2()
+                    .py_1()
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(border_color)
+                    .child(self.render_status(conflict_count))
+                    .child(self.render_controls(conflict_count, cx)),
+            )
+            .child(
+                h_flex()
+                    .flex_1()
+                    .min_h_0()
+                    .border_b_1()
+                    .border_color(border_color)
+                    .child(self.render_pane("Base".into(), &self.base_editor, cx))
+                    .child(div().h_full().w_px().bg(border_color))
+                    .child(self.render_pane(
+                        format!("Ours ({})", self.ours_branch_name).into(),
+                        &self.ours_editor,
+                        cx,
+                    ))
+                    .child(div().h_full().w_px().bg(border_color))
+                    .child(self.render_pane(
+                        format!("Theirs ({})", self.theirs_branch_name).into(),
+                        &self.theirs_editor,
+                        cx,
+                    )),
+            )
+            .child(self.render_pane("Result".into(), &self.result_editor, cx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git::{
+        repository::{ConflictStages, repo_path},
+        status::{FileStatus, UnmergedStatus, UnmergedStatusCode},
+    };
+    use gpui::{TestAppContext, VisualTestContext};
+    use indoc::indoc;
+    use project::{FakeFs, Fs};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+    use util::path;
+    use workspace::MultiWorkspace;
+
+    const CONFLICTED_TEXT: &str = indoc! {"
+        <<<<<<< HEAD
+        one ours
+        =======
+        one theirs
+        >>>>>>> other
+        middle
+        <<<<<<< HEAD
+        two ours
+        =======
+        two theirs
+        >>>>>>> other
+        end
+        <<<<<<< HEAD
+        three ours
+        =======
+        three theirs
+        >>>>>>> other
+    "};
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme_settings::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            crate::init(cx);
+        });
+    }
+
+    fn current_conflict_ix(
+        merge_editor: &Entity<MergeEditor>,
+        cx: &mut VisualTestContext,
+    ) -> Option<usize> {
+        merge_editor.read_with(cx, |merge_editor, cx| merge_editor.current_conflict_ix(cx))
+    }
+
+    #[test]
+    fn test_has_conflict_markers() {
+        assert!(has_conflict_markers("a\n<<<<<<< HEAD\nb\n"));
+        assert!(has_conflict_markers("a\n||||||| base\nb\n=======\n"));
+        assert!(has_conflict_markers(">>>>>>> other\n"));
+        assert!(has_conflict_markers("a\n<<<<<<<\n"));
+        assert!(!has_conflict_markers("Title\n=======\n\nText\n"));
+        assert!(!has_conflict_markers("a\n<<<<<<<< eight\n"));
+        assert!(!has_conflict_markers("a\n  <<<<<<< indented\nb == c\n"));
+    }
+
+    #[gpui::test]
+    async fn test_resolve_conflicts_and_stage(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "file.txt": CONFLICTED_TEXT,
+            }),
+        )
+        .await;
+        let dot_git = Path::new(path!("/project/.git"));
+        fs.set_status_for_repo(
+            dot_git,
+            &[(
+                "file.txt",
+                FileStatus::Unmerged(UnmergedStatus {
+                    first_head: UnmergedStatusCode::Updated,
+                    second_head: UnmergedStatusCode::Updated,
+                }),
+            )],
+        );
+        fs.with_git_state(dot_git, true, |state| {
+            state.conflict_stages.insert(
+                repo_path("file.txt"),
+                ConflictStages {
+                    base: Some("one\nmiddle\ntwo\nend\nthree\n".into()),
+                    ours: Some("one ours\nmiddle\ntwo ours\nend\nthree ours\n".into()),
+                    theirs: Some("one theirs\nmiddle\ntwo theirs\nend\nthree theirs\n".into()),
+                },
+            );
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        let (multi_workspace, cx) =
+            cx.add_window_view(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+        let workspace = multi_workspace.read_with(cx, |mw, _| mw.workspace().clone());
+        cx.run_until_parked();
+
+        let repository =
+            project.read_with(cx, |project, cx| project.active_repository(cx).unwrap());
+        let merge_editor = cx
+            .update(|window, cx| {
+                open(
+                    repository,
+                    repo_path("file.txt"),
+                    workspace.downgrade(),
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        let conflict_count = |cx: &mut VisualTestContext| {
+            merge_editor.read_with(cx, |merge_editor, cx| merge_editor.conflicts(cx).len())
+        };
+        let result_text = |cx: &mut VisualTestContext| {
+            merge_editor.read_with(cx, |merge_editor, cx| {
+                merge_editor.result_buffer.read(cx).text()
+            })
+        };
+        assert_eq!(conflict_count(cx), 3);
+        assert_eq!(current_conflict_ix(&merge_editor, cx), Some(0));
+
+        // Navigation wraps around at either end.
+        cx.update(|window, cx| {
+            merge_editor.update(cx, |merge_editor, cx| {
+                merge_editor.next_conflict(&NextConflict, window, cx);
+                merge_editor.next_conflict(&NextConflict, window, cx);
+            })
+        });
+        assert_eq!(current_conflict_ix(&merge_editor, cx), Some(2));
+        cx.update(|window, cx| {
+            merge_editor.update(cx, |merge_editor, cx| {
+                merge_editor.next_conflict(&NextConflict, window, cx)
+            })
+        });
+        assert_eq!(current_conflict_ix(&merge_editor, cx), Some(0));
+        cx.update(|window, cx| {
+            merge_editor.update(cx, |merge_editor, cx| {
+                merge_editor.previous_conflict(&PreviousConflict, window, cx)
+            })
+        });
+        assert_eq!(current_conflict_ix(&merge_editor, cx), Some(2));
+        cx.update(|window, cx| {
+            merge_editor.update(cx, |merge_editor, cx| {
+                merge_editor.previous_conflict(&PreviousConflict, window, cx);
+                merge_editor.previous_conflict(&PreviousConflict, window, cx);
+            })
+        });
+        assert_eq!(current_conflict_ix(&merge_editor, cx), Some(0));
+
+        // Each resolution moves on to the next conflict.
+        cx.update(|window, cx| {
+            merge_editor.update(cx, |merge_editor, cx| {
+                merge_editor.accept_ours(&AcceptOurs, window, cx)
+            })
+        });
+        cx.run_until_parked();
+        assert_eq!(conflict_count(cx), 2);
+        assert!(result_text(cx).starts_with("one ours\nmiddle\n<<<<<<< HEAD\ntwo ours\n"));
+        assert!(!merge_editor.read_with(cx, |merge_editor, _| merge_editor.staged));
+
+        cx.update(|window, cx| {
+            merge_editor.update(cx, |merge_editor, cx| {
+                merge_editor.accept_theirs(&AcceptTheirs, window, cx)
+            })
+        });
+        cx.run_until_parked();
+        assert_eq!(conflict_count(cx), 1);
+
+        cx.update(|window, cx| {
+            merge_editor.update(cx, |merge_editor, cx| {
+                merge_editor.accept_both(&AcceptBoth, window, cx)
+            })
+        });
+        cx.run_until_parked();
+
+        // Resolving the last conflict saves and stages the result.
+        let resolved_text = "one ours\nmiddle\ntwo theirs\nend\nthree ours\nthree theirs\n";
+        assert_eq!(conflict_count(cx), 0);
+        assert_eq!(result_text(cx), resolved_text);
+        assert!(merge_editor.read_with(cx, |merge_editor, _| merge_editor.staged));
+        assert_eq!(
+            fs.load(path!("/project/file.txt").as_ref()).await.unwrap(),
+            resolved_text
+        );
+        assert_eq!(
+            fs.with_git_state(dot_git, false, |state| {
+                state.index_contents.get(&repo_path("file.txt")).cloned()
+            })
+            .unwrap()
+            .as_deref(),
+            Some(resolved_text)
+        );
+
+        // A new conflict in the file is no longer staged.
+        merge_editor.update(cx, |merge_editor, cx| {
+            merge_editor.result_buffer.update(cx, |buffer, cx| {
+                buffer.edit(
+                    [(
+                        0..0,
+                        "<<<<<<< HEAD\nfour ours\n=======\nfour theirs\n>>>>>>> other\n",
+                    )],
+                    None,
+                    cx,
+                )
+            })
+        });
+        cx.run_until_parked();
+        assert_eq!(conflict_count(cx), 1);
+        assert!(!merge_editor.read_with(cx, |merge_editor, _| merge_editor.staged));
+    }
+}
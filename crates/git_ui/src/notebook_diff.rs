@@ -0,0 +1,422 @@
+//! Renders Jupyter notebooks cell by cell, so that diffs of `.ipynb` files show the changes to
+//! each cell's source instead of the notebook's JSON. Outputs, execution counts and metadata
+//! are left out, since they change on every run.
+
+use std::{ops::Range, path::PathBuf, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use buffer_diff::{BufferDiff, BufferDiffEvent};
+use gpui::{AppContext, AsyncApp, Context, Entity, Subscription};
+use language::{
+    Buffer, Capability, DiskState, File, Language, LanguageRegistry, LineEnding, ReplicaId, Rope,
+    TextBuffer,
+};
+use project::WorktreeId;
+use serde::Deserialize;
+use util::{ResultExt as _, paths::PathStyle, rel_path::RelPath};
+
+pub(crate) fn is_notebook_path(path: &RelPath) -> bool {
+    path.extension() == Some("ipynb")
+}
+
+/// The cells of a notebook, rendered as text in the percent format used by Jupytext.
+#[derive(Debug, PartialEq)]
+pub(crate) struct NotebookText {
+    pub text: String,
+    /// The name of the kernel's language, used to highlight the cells.
+    pub language: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Notebook {
+    cells: Vec<Cell>,
+    #[serde(default)]
+    metadata: NotebookMetadata,
+}
+
+#[derive(Default, Deserialize)]
+struct NotebookMetadata {
+    kernelspec: Option<KernelSpec>,
+    language_info: Option<LanguageInfo>,
+}
+
+#[derive(Deserialize)]
+struct KernelSpec {
+    language: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LanguageInfo {
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Cell {
+    cell_type: String,
+    #[serde(default)]
+    source: CellSource,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CellSource {
+    Lines(Vec<String>),
+    Text(String),
+}
+
+impl Default for CellSource {
+    fn default() -> Self {
+        Self::Text(String::new())
+    }
+}
+
+impl NotebookText {
+    /// Returns `None` if `json` isn't a notebook in the v4 format. Empty files, such as a
+    /// notebook that was deleted, have no cells.
+    pub(crate) fn parse(json: &str) -> Option<Self> {
+        if json.trim().is_empty() {
+            return Some(Self {
+                text: String::new(),
+                language: None,
+            });
+        }
+        let notebook = serde_json::from_str::<Notebook>(json).ok()?;
+
+        let mut text = String::new();
+        for (index, cell) in notebook.cells.iter().enumerate() {
+            if index > 0 {
+                text.push('\n');
+            }
+            match cell.cell_type.as_str() {
+                "code" => text.push_str("# %%\n"),
+                cell_type => {
+                    text.push_str("# %% [");
+                    text.push_str(cell_type);
+                    text.push_str("]\n");
+                }
+            }
+            match &cell.source {
+                CellSource::Lines(lines) => lines.iter().for_each(|line| text.push_str(line)),
+                CellSource::Text(source) => text.push_str(source),
+            }
+            if !text.ends_with('\n') {
+                text.push('\n');
+            }
+        }
+
+        let metadata = notebook.metadata;
+        let language = metadata
+            .kernelspec
+            .and_then(|kernelspec| kernelspec.language)
+            .or_else(|| metadata.language_info.and_then(|info| info.name));
+        Some(Self { text, language })
+    }
+}
+
+/// Replaces each conflict in `text` with one of its sides, given the byte ranges of each conflict
+/// and of the side to keep.
+pub(crate) fn resolve_conflicts(text: &str, conflicts: &[(Range<usize>, Range<usize>)]) -> String {
+    let mut resolved = String::with_capacity(text.len());
+    let mut offset = 0;
+    for (conflict_range, kept_range) in conflicts {
+        resolved.push_str(&text[offset..conflict_range.start]);
+        resolved.push_str(&text[kept_range.clone()]);
+        offset = conflict_range.end;
+    }
+    resolved.push_str(&text[offset..]);
+    resolved
+}
+
+pub(crate) async fn load_language(
+    name: Option<&str>,
+    language_registry: &Arc<LanguageRegistry>,
+) -> Option<Arc<Language>> {
+    language_registry
+        .language_for_name_or_extension(name?)
+        .await
+        .ok()
+}
+
+/// Stands in for the notebook file in buffers that hold its rendered cells, so that they're
+/// displayed and opened as the notebook, while never being saved over it.
+pub(crate) struct NotebookFile {
+    source: Arc<dyn File>,
+}
+
+impl NotebookFile {
+    pub(crate) fn new(source: Arc<dyn File>) -> Self {
+        Self { source }
+    }
+}
+
+impl File for NotebookFile {
+    fn as_local(&self) -> Option<&dyn language::LocalFile> {
+        None
+    }
+
+    fn disk_state(&self) -> DiskState {
+        self.source.disk_state()
+    }
+
+    fn path_style(&self, cx: &gpui::App) -> PathStyle {
+        self.source.path_style(cx)
+    }
+
+    fn path(&self) -> &Arc<RelPath> {
+        self.source.path()
+    }
+
+    fn full_path(&self, cx: &gpui::App) -> PathBuf {
+        self.source.full_path(cx)
+    }
+
+    fn file_name<'a>(&'a self, cx: &'a gpui::App) -> &'a str {
+        self.source.file_name(cx)
+    }
+
+    fn worktree_id(&self, cx: &gpui::App) -> WorktreeId {
+        self.source.worktree_id(cx)
+    }
+
+    fn to_proto(&self, cx: &gpui::App) -> language::proto::File {
+        self.source.to_proto(cx)
+    }
+
+    fn is_private(&self) -> bool {
+        self.source.is_private()
+    }
+
+    fn can_open(&self) -> bool {
+        self.source.can_open()
+    }
+}
+
+pub(crate) fn build_notebook_buffer(
+    mut text: String,
+    file: Option<Arc<dyn File>>,
+    language: Option<Arc<Language>>,
+    cx: &mut impl AppContext,
+) -> Entity<Buffer> {
+    let line_ending = LineEnding::detect(&text);
+    LineEnding::normalize(&mut text);
+    cx.new(|cx| {
+        let buffer = TextBuffer::new_normalized(
+            ReplicaId::LOCAL,
+            cx.entity_id().as_non_zero_u64().into(),
+            line_ending,
+            Rope::from(text),
+        );
+        let mut buffer = Buffer::build(buffer, file, Capability::ReadOnly);
+        buffer.set_language_async(language, cx);
+        buffer
+    })
+}
+
+/// The rendered cells of a notebook with uncommitted changes, diffed against the rendered cells
+/// of its base text and kept up to date as the notebook's own diff changes.
+pub(crate) struct NotebookDiff {
+    buffer: Entity<Buffer>,
+    diff: Entity<BufferDiff>,
+    source_buffer: Entity<Buffer>,
+    source_diff: Entity<BufferDiff>,
+    _source_diff_subscription: Subscription,
+}
+
+impl NotebookDiff {
+    /// Returns `None` if either side of the diff isn't a notebook that can be read, in which case
+    /// the notebook's JSON should be diffed instead.
+    pub(crate) async fn load(
+        source_buffer: Entity<Buffer>,
+        source_diff: Entity<BufferDiff>,
+        language_registry: Arc<LanguageRegistry>,
+        cx: &mut AsyncApp,
+    ) -> Result<Option<Entity<Self>>> {
+        let (json, base_json, file) = cx.update(|cx| {
+            let source_buffer = source_buffer.read(cx);
+            (
+                source_buffer.text(),
+                source_diff.read(cx).base_text_string(cx),
+                source_buffer.file().cloned(),
+            )
+        });
+        let file = file.context("notebook buffer has no file")?;
+        let Some(notebook) = NotebookText::parse(&json) else {
+            return Ok(None);
+        };
+        let base = match base_json {
+            Some(base_json) => match NotebookText::parse(&base_json) {
+                Some(base) => Some(base),
+                None => return Ok(None),
+            },
+            None => None,
+        };
+
+        let language_name = notebook
+            .language
+            .as_deref()
+            .or_else(|| base.as_ref()?.language.as_deref());
+        let language = load_language(language_name, &language_registry).await;
+        let buffer = build_notebook_buffer(
+            notebook.text,
+            Some(Arc::new(NotebookFile::new(file))),
+            language.clone(),
+            cx,
+        );
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.text_snapshot());
+        let diff = cx.new(|cx| BufferDiff::new(&snapshot, cx));
+        diff.update(cx, |diff, cx| {
+            diff.language_changed(language.clone(), Some(language_registry), cx);
+            diff.set_base_text(base.map(|base| base.text.into()), language, snapshot, cx)
+        })
+        .await
+        .log_err();
+
+        Ok(Some(cx.new(|cx| {
+            let subscription = cx.subscribe(&source_diff, |this, _, event, cx| match event {
+                BufferDiffEvent::DiffChanged(_) | BufferDiffEvent::BaseTextChanged => {
+                    this.source_changed(cx)
+                }
+                BufferDiffEvent::LanguageChanged | BufferDiffEvent::HunksStagedOrUnstaged(_) => {}
+            });
+            Self {
+                buffer,
+                diff,
+                source_buffer,
+                source_diff,
+                _source_diff_subscription: subscription,
+            }
+        })))
+    }
+
+    pub(crate) fn buffer(&self) -> &Entity<Buffer> {
+        &self.buffer
+    }
+
+    pub(crate) fn diff(&self) -> &Entity<BufferDiff> {
+        &self.diff
+    }
+
+    pub(crate) fn source_buffer(&self) -> &Entity<Buffer> {
+        &self.source_buffer
+    }
+
+    fn source_changed(&mut self, cx: &mut Context<Self>) {
+        // Keep showing the last rendering while the notebook can't be read, e.g. mid-merge
+        let Some(notebook) = NotebookText::parse(&self.source_buffer.read(cx).text()) else {
+            return;
+        };
+        let base = match self.source_diff.read(cx).base_text_string(cx) {
+            Some(base_json) => match NotebookText::parse(&base_json) {
+                Some(base) => Some(base.text),
+                None => return,
+            },
+            None => None,
+        };
+
+        self.buffer.update(cx, |buffer, cx| {
+            let mut text = notebook.text;
+            LineEnding::normalize(&mut text);
+            if buffer.text() != text {
+                buffer.set_text(text, cx);
+            }
+        });
+        let buffer = self.buffer.read(cx);
+        let snapshot = buffer.text_snapshot();
+        let language = buffer.language().cloned();
+        self.diff.update(cx, |diff, cx| {
+            diff.set_base_text(base.map(Into::into), language, snapshot, cx);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_notebook_text() {
+        let notebook = NotebookText::parse(indoc! {r##"
+            {
+              "cells": [
+                {
+                  "cell_type": "markdown",
+                  "metadata": {},
+                  "source": ["# Analysis\n", "Loads the data."]
+                },
+                {
+                  "cell_type": "code",
+                  "execution_count": 3,
+                  "metadata": {},
+                  "outputs": [{"output_type": "stream", "name": "stdout", "text": ["42\n"]}],
+                  "source": "import pandas as pd\nprint(42)"
+                },
+                {
+                  "cell_type": "raw",
+                  "metadata": {},
+                  "source": []
+                }
+              ],
+              "metadata": {
+                "kernelspec": {"display_name": "Python 3", "language": "python", "name": "python3"}
+              },
+              "nbformat": 4,
+              "nbformat_minor": 5
+            }
+        "##})
+        .unwrap();
+
+        assert_eq!(
+            notebook.text,
+            indoc! {"
+                # %% [markdown]
+                # Analysis
+                Loads the data.
+
+                # %%
+                import pandas as pd
+                print(42)
+
+                # %% [raw]
+            "}
+        );
+        assert_eq!(notebook.language.as_deref(), Some("python"));
+    }
+
+    #[test]
+    fn test_notebook_text_ignores_outputs() {
+        let notebook = |outputs: &str, execution_count: &str| {
+            NotebookText::parse(&format!(
+                r#"{{"cells": [{{"cell_type": "code", "execution_count": {execution_count}, "metadata": {{}}, "outputs": {outputs}, "source": "x = 1"}}], "metadata": {{"language_info": {{"name": "julia"}}}}}}"#
+            ))
+            .unwrap()
+        };
+
+        assert_eq!(
+            notebook("[]", "null"),
+            notebook(r#"[{"output_type": "execute_result", "data": {}}]"#, "7")
+        );
+        assert_eq!(notebook("[]", "null").language.as_deref(), Some("julia"));
+    }
+
+    #[test]
+    fn test_notebook_text_rejects_other_json() {
+        assert_eq!(NotebookText::parse("{\"a\": 1}"), None);
+        assert_eq!(NotebookText::parse("<<<<<<< HEAD\n{}\n"), None);
+        assert_eq!(NotebookText::parse("\n").unwrap().text, "");
+    }
+
+    #[test]
+    fn test_resolve_conflicts() {
+        let text = "a\n<<<<<<< ours\nb\n=======\nc\n>>>>>>> theirs\nd\n";
+        let conflict = 2..text.len() - 2;
+        let ours = text.find("b\n").unwrap()..text.find("=").unwrap();
+        let theirs = text.find("c\n").unwrap()..text.find(">").unwrap();
+
+        assert_eq!(
+            resolve_conflicts(text, &[(conflict.clone(), ours)]),
+            "a\nb\nd\n"
+        );
+        assert_eq!(resolve_conflicts(text, &[(conflict, theirs)]), "a\nc\nd\n");
+    }
+}
@@ -2,13 +2,15 @@ use crate::{
     branch_picker, conflict_view,
     git_panel::{GitPanel, GitPanelAddon, GitStatusEntry},
     git_panel_settings::GitPanelSettings,
+    notebook_diff::{self, NotebookDiff},
 };
 use agent_settings::AgentSettings;
 use anyhow::{Context as _, Result, anyhow};
 use buffer_diff::{BufferDiff, DiffHunkSecondaryStatus};
 use collections::HashMap;
 use editor::{
-    Addon, Editor, EditorEvent, EditorSettings, SelectionEffects, SplittableEditor,
+    Addon, Editor, EditorEvent, EditorSettings, RenderDiffHunkControlsFn, SelectionEffects,
+    SplittableEditor,
     actions::{GoToHunk, GoToPreviousHunk, SendReviewToAgent},
     multibuffer_context_lines,
     scroll::Autoscroll,
@@ -84,6 +86,7 @@ pub struct ProjectDiff {
     branch_diff: Entity<branch_diff::BranchDiff>,
     editor: Entity<SplittableEditor>,
     buffer_subscriptions: HashMap<Arc<RelPath>, BufferSubscriptions>,
+    notebook_diffs: HashMap<Arc<RelPath>, Entity<NotebookDiff>>,
     workspace: WeakEntity<Workspace>,
     focus_handle: FocusHandle,
     pending_scroll: Option<PathKey>,
@@ -507,6 +510,7 @@ impl ProjectDiff {
         cx: &mut Context<Self>,
     ) -> Self {
         let focus_handle = cx.focus_handle();
+        let this = cx.weak_entity();
         let multibuffer = cx.new(|cx| {
             let mut multibuffer = MultiBuffer::new(Capability::ReadWrite);
             multibuffer.set_all_diff_hunks_expanded(cx);
@@ -523,7 +527,12 @@ impl ProjectDiff {
                 cx,
             );
             match branch_diff.read(cx).diff_base() {
-                DiffBase::Head => {}
+                DiffBase::Head => {
+                    let controls = render_diff_hunk_controls(this);
+                    diff_display_editor.update_editors(cx, |editor, cx| {
+                        editor.set_render_diff_hunk_controls(controls.clone(), cx)
+                    });
+                }
                 DiffBase::Merge { .. } => diff_display_editor.disable_diff_hunk_controls(cx),
             }
             diff_display_editor.rhs_editor().update(cx, |editor, cx| {
@@ -610,6 +619,7 @@ impl ProjectDiff {
             editor,
             multibuffer,
             buffer_subscriptions: Default::default(),
+            notebook_diffs: Default::default(),
             pending_scroll: None,
             review_comment_count: 0,
             _task: task,
@@ -734,7 +744,10 @@ impl ProjectDiff {
         }
         let mut has_staged_hunks = false;
         let mut has_unstaged_hunks = false;
-        for hunk in editor.diff_hunks_in_ranges(&ranges, &snapshot) {
+        for hunk in editor
+            .diff_hunks_in_ranges(&ranges, &snapshot)
+            .filter(|hunk| !self.is_notebook_buffer(hunk.buffer_id, cx))
+        {
             match hunk.status.secondary {
                 DiffHunkSecondaryStatus::HasSecondaryHunk
                 | DiffHunkSecondaryStatus::SecondaryHunkAdditionPending => {
@@ -1011,6 +1024,7 @@ impl ProjectDiff {
             this.editor.update(cx, |editor, cx| {
                 for (path, buffer_id) in previous_paths {
                     this.buffer_subscriptions.remove(&path.path);
+                    this.notebook_diffs.remove(&path.path);
                     editor.rhs_editor().update(cx, |editor, cx| {
                         conflict_view::buffers_removed(editor, &[buffer_id], cx);
                     });
@@ -1030,6 +1044,13 @@ impl ProjectDiff {
                 // We might be lagging behind enough that all future entry.load futures are no longer pending.
                 // If that is the case, this task will never yield, starving the foreground thread of execution time.
                 yield_now().await;
+                let (buffer, diff, conflict_set) =
+                    if notebook_diff::is_notebook_path(&path_key.path) {
+                        Self::load_notebook_diff(&this, &path_key, buffer, diff, conflict_set, cx)
+                            .await?
+                    } else {
+                        (buffer, diff, conflict_set)
+                    };
                 cx.update(|window, cx| {
                     this.update(cx, |this, cx| {
                         if let Some(buffer_id) = this.register_buffer(
@@ -1063,6 +1084,62 @@ impl ProjectDiff {
         Ok(())
     }
 
+    /// Whether the buffer holds the rendered cells of a notebook, whose hunks can't be staged or
+    /// restored on their own since the buffer isn't the notebook's.
+    fn is_notebook_buffer(&self, buffer_id: BufferId, cx: &App) -> bool {
+        self.notebook_diffs
+            .values()
+            .any(|notebook_diff| notebook_diff.read(cx).buffer().read(cx).remote_id() == buffer_id)
+    }
+
+    /// Shows a notebook as its rendered cells rather than its JSON, falling back to the JSON
+    /// when the notebook can't be parsed or has conflicts.
+    async fn load_notebook_diff(
+        this: &WeakEntity<Self>,
+        path_key: &PathKey,
+        buffer: Entity<Buffer>,
+        diff: Entity<BufferDiff>,
+        conflict_set: Entity<ConflictSet>,
+        cx: &mut AsyncWindowContext,
+    ) -> Result<(Entity<Buffer>, Entity<BufferDiff>, Entity<ConflictSet>)> {
+        let (has_conflict, existing, languages) = this.read_with(cx, |this, cx| {
+            let existing = this
+                .notebook_diffs
+                .get(&path_key.path)
+                .filter(|notebook_diff| notebook_diff.read(cx).source_buffer() == &buffer)
+                .cloned();
+            (
+                conflict_set.read(cx).has_conflict,
+                existing,
+                this.project.read(cx).languages().clone(),
+            )
+        })?;
+        let notebook_diff = if has_conflict {
+            None
+        } else if existing.is_some() {
+            existing
+        } else {
+            NotebookDiff::load(buffer.clone(), diff.clone(), languages, cx)
+                .await
+                .log_err()
+                .flatten()
+        };
+
+        this.update(cx, |this, cx| {
+            let Some(notebook_diff) = notebook_diff else {
+                this.notebook_diffs.remove(&path_key.path);
+                return (buffer, diff, conflict_set);
+            };
+            this.notebook_diffs
+                .insert(path_key.path.clone(), notebook_diff.clone());
+            let notebook_diff = notebook_diff.read(cx);
+            let buffer = notebook_diff.buffer().clone();
+            let buffer_id = buffer.read(cx).remote_id();
+            let conflict_set = cx.new(|cx| ConflictSet::new(buffer_id, false, cx));
+            (buffer, notebook_diff.diff().clone(), conflict_set)
+        })
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub fn excerpt_paths(&self, cx: &App) -> Vec<std::sync::Arc<util::rel_path::RelPath>> {
         let snapshot = self
@@ -1100,6 +1177,56 @@ fn sort_prefix(repo: &Repository, repo_path: &RepoPath, status: FileStatus, cx:
     }
 }
 
+/// Renders the default hunk controls, except for notebooks, whose rendered cells aren't the
+/// notebook's own buffer. Their hunks can't be staged or restored, so the controls point to the
+/// file's stage checkbox instead.
+fn render_diff_hunk_controls(project_diff: WeakEntity<ProjectDiff>) -> RenderDiffHunkControlsFn {
+    Arc::new(
+        move |row, status, hunk_range, is_created_file, line_height, editor, window, cx| {
+            let is_notebook = project_diff.upgrade().is_some_and(|project_diff| {
+                let snapshot = editor.read(cx).buffer().read(cx).snapshot(cx);
+                snapshot
+                    .anchor_to_buffer_anchor(hunk_range.start)
+                    .is_some_and(|(anchor, _)| {
+                        project_diff
+                            .read(cx)
+                            .is_notebook_buffer(anchor.buffer_id, cx)
+                    })
+            });
+            if !is_notebook {
+                return editor::render_diff_hunk_controls(
+                    row,
+                    status,
+                    hunk_range,
+                    is_created_file,
+                    line_height,
+                    editor,
+                    window,
+                    cx,
+                );
+            }
+            h_flex()
+                .h(line_height)
+                .mr_1()
+                .px_1()
+                .pb_1()
+                .border_x_1()
+                .border_b_1()
+                .border_color(cx.theme().colors().border_variant)
+                .rounded_b_lg()
+                .bg(cx.theme().colors().editor_background)
+                .block_mouse_except_scroll()
+                .shadow_md()
+                .child(
+                    Label::new("Notebooks can only be staged or restored as a whole file")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                )
+                .into_any_element()
+        },
+    )
+}
+
 impl EventEmitter<EditorEvent> for ProjectDiff {}
 
 impl Focusable for ProjectDiff {
@@ -0,0 +1,609 @@
+use editor::Editor;
+use git::{
+    Oid,
+    rebase::{RebaseAction, RebaseTodoEntry, rebase_todo_script},
+};
+use gpui::{
+    App, ClickEvent, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Pixels,
+    Point, ScrollHandle, SharedString, Subscription, Task, TaskExt, WeakEntity, Window, actions,
+};
+use project::git_store::{Repository, RepositoryEvent};
+use settings::Settings as _;
+use theme_settings::ThemeSettings;
+use ui::{ContextMenu, DropdownMenu, DropdownStyle, KeyBinding, Tooltip, prelude::*};
+use workspace::{ModalView, Toast, Workspace, notifications::NotificationId};
+
+use crate::git_panel::show_error_toast;
+
+actions!(
+    rebase_editor,
+    [
+        /// Moves the selected commit one step earlier in the rebase.
+        MoveEntryUp,
+        /// Moves the selected commit one step later in the rebase.
+        MoveEntryDown,
+        /// Switches the selected commit to the next rebase action.
+        CycleAction,
+        /// Starts the rebase with the edited todo list.
+        StartRebase,
+    ]
+);
+
+/// Loads the commits a rebase onto `base` would replay and opens them in a [`RebaseEditor`].
+///
+/// Without a base, the rebase replays every commit reachable from HEAD.
+pub fn open(
+    repository: Entity<Repository>,
+    base: Option<String>,
+    workspace: WeakEntity<Workspace>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let todo = repository.update(cx, |repository, _| repository.rebase_todo(base.clone()));
+    window
+        .spawn(cx, async move |cx| {
+            let entries = match todo.await {
+                Ok(Ok(entries)) => entries,
+                Ok(Err(error)) => {
+                    if let Some(workspace) = workspace.upgrade() {
+                        cx.update(|_, cx| show_error_toast(workspace, "rebase", error, cx))
+                            .ok();
+                    }
+                    return;
+                }
+                Err(_) => return,
+            };
+
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    if entries.is_empty() {
+                        struct NothingToRebase;
+                        workspace.show_toast(
+                            Toast::new(
+                                NotificationId::unique::<NothingToRebase>(),
+                                "There are no commits to rebase",
+                            )
+                            .autohide(),
+                            cx,
+                        );
+                        return;
+                    }
+
+                    let weak_workspace = workspace.weak_handle();
+                    workspace.toggle_modal(window, cx, |window, cx| {
+                        RebaseEditor::new(repository, base, entries, weak_workspace, window, cx)
+                    });
+                })
+                .ok();
+        })
+        .detach();
+}
+
+struct RewordEditor {
+    sha: Oid,
+    editor: Entity<Editor>,
+    _load_message: Option<Task<()>>,
+}
+
+#[derive(Clone)]
+struct DraggedRebaseEntry {
+    index: usize,
+    subject: SharedString,
+    click_offset: Point<Pixels>,
+}
+
+impl Render for DraggedRebaseEntry {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let ui_font = ThemeSettings::get_global(cx).ui_font.clone();
+        h_flex()
+            .font(ui_font)
+            .pl(self.click_offset.x + px(12.))
+            .pt(self.click_offset.y + px(12.))
+            .child(
+                div()
+                    .py_1()
+                    .px_2()
+                    .rounded_lg()
+                    .bg(cx.theme().colors().background)
+                    .child(Label::new(self.subject.clone())),
+            )
+    }
+}
+
+/// A modal for editing the todo list of an interactive rebase before running it.
+pub struct RebaseEditor {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    base: Option<String>,
+    entries: Vec<RebaseTodoEntry>,
+    selected_index: usize,
+    reword_editor: Option<RewordEditor>,
+    focus_handle: FocusHandle,
+    scroll_handle: ScrollHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl RebaseEditor {
+    fn new(
+        repository: Entity<Repository>,
+        base: Option<String>,
+        entries: Vec<RebaseTodoEntry>,
+        workspace: WeakEntity<Workspace>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        // Once another rebase is underway the todo list no longer applies.
+        let subscription = cx.subscribe(&repository, |this, repository, event, cx| {
+            if matches!(event, RepositoryEvent::StatusesChanged)
+                && repository.read(cx).merge.rebase.is_some()
+            {
+                this.reword_editor.take();
+                cx.emit(DismissEvent);
+            }
+        });
+
+        Self {
+            repository,
+            workspace,
+            base,
+            entries,
+            selected_index: 0,
+            reword_editor: None,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: ScrollHandle::new(),
+            _subscriptions: vec![subscription],
+        }
+    }
+
+    fn select(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if index >= self.entries.len() {
+            return;
+        }
+        self.selected_index = index;
+        self.scroll_handle.scroll_to_item(index);
+        if self.entries[index].action == RebaseAction::Reword {
+            self.edit_message(index, window, cx);
+        }
+        cx.notify();
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, window: &mut Window, cx: &mut Context<Self>) {
+        self.select(self.selected_index + 1, window, cx);
+    }
+
+    fn select_previous(
+        &mut self,
+        _: &menu::SelectPrevious,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(index) = self.selected_index.checked_sub(1) {
+            self.select(index, window, cx);
+        }
+    }
+
+    fn move_entry(&mut self, from: usize, to: usize, cx: &mut Context<Self>) {
+        if from == to || from >= self.entries.len() || to >= self.entries.len() {
+            return;
+        }
+        let entry = self.entries.remove(from);
+        self.entries.insert(to, entry);
+        self.selected_index = to;
+        self.scroll_handle.scroll_to_item(to);
+        cx.notify();
+    }
+
+    fn move_entry_up(&mut self, _: &MoveEntryUp, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(to) = self.selected_index.checked_sub(1) {
+            self.move_entry(self.selected_index, to, cx);
+        }
+    }
+
+    fn move_entry_down(&mut self, _: &MoveEntryDown, _: &mut Window, cx: &mut Context<Self>) {
+        self.move_entry(self.selected_index, self.selected_index + 1, cx);
+    }
+
+    fn cycle_action(&mut self, _: &CycleAction, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(self.selected_index) else {
+            return;
+        };
+        let next = RebaseAction::ALL
+            .iter()
+            .position(|action| *action == entry.action)
+            .map_or(RebaseAction::Pick, |position| {
+                RebaseAction::ALL[(position + 1) % RebaseAction::ALL.len()]
+            });
+        self.set_action(self.selected_index, next, window, cx);
+    }
+
+    fn set_action(
+        &mut self,
+        index: usize,
+        action: RebaseAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry) = self.entries.get_mut(index) else {
+            return;
+        };
+        entry.action = action;
+        let sha = entry.sha;
+        self.selected_index = index;
+
+        if action == RebaseAction::Reword {
+            self.edit_message(index, window, cx);
+        } else if self
+            .reword_editor
+            .as_ref()
+            .is_some_and(|reword_editor| reword_editor.sha == sha)
+        {
+            self.reword_editor.take();
+            window.focus(&self.focus_handle, cx);
+        }
+        cx.notify();
+    }
+
+    /// Stores the message being edited back into its entry.
+    fn save_message(&mut self, cx: &App) {
+        let Some(reword_editor) = self.reword_editor.as_ref() else {
+            return;
+        };
+        let message = reword_editor.editor.read(cx).text(cx);
+        if let Some(entry) = self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.sha == reword_editor.sha)
+        {
+            entry.message = Some(message).filter(|message| !message.trim().is_empty());
+        }
+    }
+
+    fn edit_message(&mut self, index: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(index) else {
+            return;
+        };
+        if self
+            .reword_editor
+            .as_ref()
+            .is_some_and(|reword_editor| reword_editor.sha == entry.sha)
+        {
+            return;
+        }
+        self.save_message(cx);
+
+        let sha = entry.sha;
+        let message = entry.message.clone();
+        let editor = cx.new(|cx| {
+            let mut editor = Editor::auto_height(3, 10, window, cx);
+            editor.set_placeholder_text("Commit message", window, cx);
+            if let Some(message) = &message {
+                editor.set_text(message.as_str(), window, cx);
+            }
+            editor
+        });
+
+        // Start from the commit's full message, since the todo list only has its subject.
+        let load_message = message.is_none().then(|| {
+            let show = self
+                .repository
+                .update(cx, |repository, _| repository.show(sha.to_string()));
+            let editor = editor.downgrade();
+            cx.spawn_in(window, async move |_, cx| {
+                let Ok(Ok(details)) = show.await else {
+                    return;
+                };
+                editor
+                    .update_in(cx, |editor, window, cx| {
+                        if editor.text(cx).is_empty() {
+                            editor.set_text(details.message.trim_end(), window, cx);
+                        }
+                    })
+                    .ok();
+            })
+        });
+
+        window.focus(&editor.focus_handle(cx), cx);
+        self.reword_editor = Some(RewordEditor {
+            sha,
+            editor,
+            _load_message: load_message,
+        });
+    }
+
+    fn validation_error(&self) -> Option<SharedString> {
+        if self
+            .entries
+            .iter()
+            .all(|entry| entry.action == RebaseAction::Drop)
+        {
+            return Some("The rebase would drop every commit".into());
+        }
+        rebase_todo_script(&self.entries)
+            .err()
+            .map(|error| error.to_string().into())
+    }
+
+    fn cancel(&mut self, _: &menu::Cancel, _: &mut Window, cx: &mut Context<Self>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn start_rebase(&mut self, _: &StartRebase, window: &mut Window, cx: &mut Context<Self>) {
+        self.save_message(cx);
+        if self.validation_error().is_some() {
+            return;
+        }
+
+        let rebase = self.repository.update(cx, |repository, _| {
+            repository.rebase_interactive(self.base.clone(), self.entries.clone())
+        });
+        let workspace = self.workspace.clone();
+        window
+            .spawn(cx, async move |cx| {
+                if let Ok(Err(error)) = rebase.await
+                    && let Some(workspace) = workspace.upgrade()
+                {
+                    cx.update(|_, cx| show_error_toast(workspace, "rebase", error, cx))
+                        .ok();
+                }
+            })
+            .detach();
+        cx.emit(DismissEvent);
+    }
+
+    fn render_action_dropdown(
+        &self,
+        index: usize,
+        action: RebaseAction,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let rebase_editor = cx.entity();
+        let menu = ContextMenu::build(window, cx, move |mut menu, window, _| {
+            for option in RebaseAction::ALL {
+                menu = menu.toggleable_entry(
+                    option.label(),
+                    option == action,
+                    IconPosition::Start,
+                    None,
+                    window.handler_for(&rebase_editor, move |this, window, cx| {
+                        this.set_action(index, option, window, cx);
+                    }),
+                );
+            }
+            menu
+        });
+
+        div().w(rems(6.)).flex_none().child(
+            DropdownMenu::new(("rebase-action", index), action.label(), menu)
+                .trigger_size(ButtonSize::Compact)
+                .style(DropdownStyle::Subtle)
+                .full_width(true),
+        )
+    }
+
+    fn render_entry(
+        &self,
+        index: usize,
+        entry: &RebaseTodoEntry,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let is_selected = index == self.selected_index;
+        let is_dropped = entry.action == RebaseAction::Drop;
+        let melds = entry.action.melds_into_previous();
+        let subject = entry
+            .message
+            .as_deref()
+            .filter(|_| entry.action == RebaseAction::Reword)
+            .and_then(|message| message.lines().next())
+            .map(|subject| SharedString::from(subject.to_string()))
+            .unwrap_or_else(|| entry.subject.clone());
+        let dragged = DraggedRebaseEntry {
+            index,
+            subject: entry.subject.clone(),
+            click_offset: Point::default(),
+        };
+
+        h_flex()
+            .id(("rebase-entry", index))
+            .w_full()
+            .px_2()
+            .py_0p5()
+            .gap_2()
+            .rounded_sm()
+            .when(is_selected, |this| {
+                this.bg(cx.theme().colors().ghost_element_selected)
+            })
+            .hover(|style| style.bg(cx.theme().colors().ghost_element_hover))
+            .on_click(cx.listener(move |this, _: &ClickEvent, window, cx| {
+                this.select(index, window, cx);
+            }))
+            .on_drag(dragged, |dragged, click_offset, _, cx| {
+                cx.new(|_| DraggedRebaseEntry {
+                    click_offset,
+                    ..dragged.clone()
+                })
+            })
+            .drag_over::<DraggedRebaseEntry>(|style, _, _, cx| {
+                style.bg(cx.theme().colors().drop_target_background)
+            })
+            .on_drop(
+                cx.listener(move |this, dragged: &DraggedRebaseEntry, _, cx| {
+                    this.move_entry(dragged.index, index, cx);
+                }),
+            )
+            .child(self.render_action_dropdown(index, entry.action, window, cx))
+            .child(
+                Label::new(entry.sha.display_short())
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+                    .buffer_font(cx),
+            )
+            .when(melds, |this| {
+                this.child(
+                    Icon::new(IconName::ArrowUp)
+                        .size(IconSize::XSmall)
+                        .color(Color::Muted),
+                )
+            })
+            .child(
+                Label::new(subject)
+                    .size(LabelSize::Small)
+                    .truncate()
+                    .when(is_dropped, |label| {
+                        label.strikethrough().color(Color::Muted)
+                    }),
+            )
+    }
+}
+
+impl EventEmitter<DismissEvent> for RebaseEditor {}
+impl ModalView for RebaseEditor {}
+impl Focusable for RebaseEditor {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for RebaseEditor {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+        let validation_error = self.validation_error();
+        let title: SharedString = match &self.base {
+            Some(base) => {
+                let base = base
+                    .parse::<Oid>()
+                    .map_or_else(|_| base.clone(), |sha| sha.display_short());
+                format!("Rebase {} commits onto {base}", self.entries.len()).into()
+            }
+            None => format!("Rebase {} commits from the root", self.entries.len()).into(),
+        };
+        let rows = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                self.render_entry(index, entry, window, cx)
+                    .into_any_element()
+            })
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .key_context("RebaseEditor")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::cancel))
+            .on_action(cx.listener(Self::start_rebase))
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::move_entry_up))
+            .on_action(cx.listener(Self::move_entry_down))
+            .on_action(cx.listener(Self::cycle_action))
+            .elevation_2(cx)
+            .w(rems(40.))
+            .child(
+                h_flex()
+                    .px_3()
+                    .pt_2()
+                    .pb_1()
+                    .w_full()
+                    .gap_1p5()
+                    .child(Icon::new(IconName::GitBranch).size(IconSize::XSmall))
+                    .child(Headline::new(title).size(HeadlineSize::XSmall)),
+            )
+            .child(
+                div().px_3().pb_1().child(
+                    Label::new("Oldest commit first. Drag commits to reorder them.")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                ),
+            )
+            .child(
+                v_flex()
+                    .id("rebase-entries")
+                    .px_1()
+                    .max_h(rems(24.))
+                    .overflow_y_scroll()
+                    .track_scroll(&self.scroll_handle)
+                    .children(rows),
+            )
+            .when_some(self.reword_editor.as_ref(), |this, reword_editor| {
+                this.child(
+                    v_flex()
+                        .mx_3()
+                        .mt_2()
+                        .gap_1()
+                        .child(
+                            Label::new(format!(
+                                "New message for {}",
+                                reword_editor.sha.display_short()
+                            ))
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                        )
+                        .child(
+                            div()
+                                .p_1()
+                                .rounded_sm()
+                                .border_1()
+                                .border_color(cx.theme().colors().border_variant)
+                                .bg(cx.theme().colors().editor_background)
+                                .child(reword_editor.editor.clone()),
+                        ),
+                )
+            })
+            .child(
+                h_flex()
+                    .w_full()
+                    .mt_2()
+                    .p_1p5()
+                    .gap_1()
+                    .justify_between()
+                    .border_t_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(h_flex().min_w_0().when_some(
+                        validation_error.clone(),
+                        |this, validation_error| {
+                            this.gap_1()
+                                .child(
+                                    Icon::new(IconName::Warning)
+                                        .size(IconSize::XSmall)
+                                        .color(Color::Warning),
+                                )
+                                .child(
+                                    Label::new(validation_error)
+                                        .size(LabelSize::Small)
+                                        .color(Color::Muted)
+                                        .truncate(),
+                                )
+                        },
+                    ))
+                    .child(
+                        h_flex()
+                            .gap_1()
+                            .flex_none()
+                            .child(
+                                Button::new("cancel-rebase", "Cancel")
+                                    .key_binding(
+                                        KeyBinding::for_action_in(&menu::Cancel, &focus_handle, cx)
+                                            .map(|kb| kb.size(rems_from_px(12.))),
+                                    )
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.cancel(&menu::Cancel, window, cx);
+                                    })),
+                            )
+                            .child(
+                                Button::new("start-rebase", "Start Rebase")
+                                    .disabled(validation_error.is_some())
+                                    .key_binding(
+                                        KeyBinding::for_action_in(&StartRebase, &focus_handle, cx)
+                                            .map(|kb| kb.size(rems_from_px(12.))),
+                                    )
+                                    .tooltip(Tooltip::text("git rebase --interactive"))
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.start_rebase(&StartRebase, window, cx);
+                                    })),
+                            ),
+                    ),
+            )
+    }
+}
@@ -0,0 +1,337 @@
+//! SubmoduleDiffView shows how a submodule changed as the commits between its old and new
+//! revisions, rather than as a single changed commit hash.
+
+use git::{
+    Oid,
+    repository::RepoPath,
+    submodule::{Submodule, SubmoduleLog, SubmoduleLogEntry},
+};
+use gpui::{
+    AnyElement, App, Context, Empty, Entity, EventEmitter, FocusHandle, Focusable, ScrollHandle,
+    Subscription, Task, WeakEntity, Window,
+};
+use project::git_store::{Repository, RepositoryEvent};
+use ui::prelude::*;
+use workspace::{
+    Item, Workspace,
+    item::{ItemEvent, TabContentParams},
+};
+
+use crate::commit_view::CommitView;
+
+/// The revisions of a submodule to compare: the one recorded in `HEAD`, and the one checked out
+/// or staged.
+pub fn submodule_change(submodule: &Submodule) -> Option<(Oid, Oid)> {
+    let old_sha = submodule.head_sha?;
+    let new_sha = submodule.checked_out_sha.or(submodule.index_sha)?;
+    (old_sha != new_sha).then_some((old_sha, new_sha))
+}
+
+pub struct SubmoduleDiffView {
+    repository: Entity<Repository>,
+    workspace: WeakEntity<Workspace>,
+    path: RepoPath,
+    old_sha: Oid,
+    new_sha: Oid,
+    log: Option<Result<SubmoduleLog, SharedString>>,
+    focus_handle: FocusHandle,
+    scroll_handle: ScrollHandle,
+    _load_log: Task<()>,
+    _subscription: Subscription,
+}
+
+impl SubmoduleDiffView {
+    /// Opens the commits between the recorded and checked-out revisions of a submodule,
+    /// reusing the view already open for it.
+    ///
+    /// Returns `false` when the submodule has no change to show.
+    pub fn deploy(
+        repository: Entity<Repository>,
+        path: RepoPath,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> bool {
+        let Some((old_sha, new_sha)) = repository
+            .read(cx)
+            .submodule_for_path(&path)
+            .and_then(submodule_change)
+        else {
+            return false;
+        };
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .filter_map(|item| item.downcast::<Self>())
+            .find(|view| {
+                let view = view.read(cx);
+                view.repository == repository && view.path == path
+            });
+        if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+            return true;
+        }
+
+        let workspace_handle = workspace.weak_handle();
+        let view = cx.new(|cx| Self::new(repository, path, old_sha, new_sha, workspace_handle, cx));
+        workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+        true
+    }
+
+    fn new(
+        repository: Entity<Repository>,
+        path: RepoPath,
+        old_sha: Oid,
+        new_sha: Oid,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let subscription = cx.subscribe(&repository, |this, repository, event, cx| {
+            if !matches!(event, RepositoryEvent::StatusesChanged) {
+                return;
+            }
+            let change = repository
+                .read(cx)
+                .submodule_for_path(&this.path)
+                .and_then(submodule_change);
+            if let Some((old_sha, new_sha)) = change
+                && (old_sha, new_sha) != (this.old_sha, this.new_sha)
+            {
+                this.old_sha = old_sha;
+                this.new_sha = new_sha;
+                this.load_log(cx);
+            }
+        });
+
+        let mut this = Self {
+            repository,
+            workspace,
+            path,
+            old_sha,
+            new_sha,
+            log: None,
+            focus_handle: cx.focus_handle(),
+            scroll_handle: ScrollHandle::new(),
+            _load_log: Task::ready(()),
+            _subscription: subscription,
+        };
+        this.load_log(cx);
+        this
+    }
+
+    fn load_log(&mut self, cx: &mut Context<Self>) {
+        let log = self.repository.update(cx, |repository, _| {
+            repository.submodule_log(self.path.clone(), self.old_sha, self.new_sha)
+        });
+        self._load_log = cx.spawn(async move |this, cx| {
+            let log = match log.await {
+                Ok(Ok(log)) => Ok(log),
+                Ok(Err(error)) => Err(error.to_string().into()),
+                Err(_) => return,
+            };
+            this.update(cx, |this, cx| {
+                this.log = Some(log);
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    /// The repository checked out at the submodule's path, if it has been initialized.
+    fn submodule_repository(&self, cx: &App) -> Option<Entity<Repository>> {
+        let repository = self.repository.read(cx);
+        let work_directory = repository
+            .work_directory_abs_path
+            .join(self.path.as_std_path());
+        let git_store = repository.git_store()?;
+        git_store
+            .read(cx)
+            .repositories()
+            .values()
+            .find(|repository| *repository.read(cx).work_directory_abs_path == *work_directory)
+            .cloned()
+    }
+
+    fn render_section(
+        &self,
+        title: SharedString,
+        entries: &[SubmoduleLogEntry],
+        icon: IconName,
+        color: Color,
+        submodule_repository: Option<&Entity<Repository>>,
+        cx: &Context<Self>,
+    ) -> AnyElement {
+        if entries.is_empty() {
+            return Empty.into_any_element();
+        }
+        v_flex()
+            .gap_0p5()
+            .child(Label::new(title).size(LabelSize::Small).color(Color::Muted))
+            .children(entries.iter().map(|entry| {
+                let sha = entry.sha;
+                h_flex()
+                    .id(SharedString::from(format!("submodule-commit-{sha}")))
+                    .px_1()
+                    .gap_2()
+                    .rounded_sm()
+                    .child(Icon::new(icon).size(IconSize::Small).color(color))
+                    .child(
+                        Label::new(sha.display_short())
+                            .buffer_font(cx)
+                            .color(Color::Muted),
+                    )
+                    .child(Label::new(entry.subject.clone()).truncate())
+                    .when_some(submodule_repository.cloned(), |row, repository| {
+                        let workspace = self.workspace.clone();
+                        row.cursor_pointer()
+                            .hover(|style| style.bg(cx.theme().colors().ghost_element_hover))
+                            .on_click(move |_, window, cx| {
+                                CommitView::open(
+                                    sha.to_string(),
+                                    repository.downgrade(),
+                                    workspace.clone(),
+                                    None,
+                                    None,
+                                    window,
+                                    cx,
+                                );
+                            })
+                    })
+            }))
+            .into_any_element()
+    }
+}
+
+impl EventEmitter<ItemEvent> for SubmoduleDiffView {}
+
+impl Focusable for SubmoduleDiffView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for SubmoduleDiffView {
+    type Event = ItemEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Box).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        let name = self.path.file_name().unwrap_or(self.path.as_unix_str());
+        format!("{name} (Submodule)").into()
+    }
+
+    fn tab_tooltip_text(&self, _cx: &App) -> Option<SharedString> {
+        Some(
+            format!(
+                "{}: {} → {}",
+                self.path.as_unix_str(),
+                self.old_sha.display_short(),
+                self.new_sha.display_short()
+            )
+            .into(),
+        )
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Submodule Diff View Opened")
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn to_item_events(event: &Self::Event, f: &mut dyn FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
+
+impl Render for SubmoduleDiffView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let submodule_repository = self.submodule_repository(cx);
+        let header = v_flex()
+            .gap_0p5()
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(Icon::new(IconName::Box).color(Color::Muted))
+                    .child(Label::new(self.path.as_unix_str().to_string())),
+            )
+            .child(
+                Label::new(format!(
+                    "{} → {}",
+                    self.old_sha.display_short(),
+                    self.new_sha.display_short()
+                ))
+                .buffer_font(cx)
+                .size(LabelSize::Small)
+                .color(Color::Muted),
+            );
+
+        let body = match &self.log {
+            None => Label::new("Loading commits…")
+                .color(Color::Muted)
+                .into_any_element(),
+            Some(Err(error)) => Label::new(error.clone())
+                .color(Color::Error)
+                .into_any_element(),
+            Some(Ok(log)) if log.added.is_empty() && log.removed.is_empty() => {
+                Label::new("No commits between these revisions")
+                    .color(Color::Muted)
+                    .into_any_element()
+            }
+            Some(Ok(log)) => v_flex()
+                .gap_3()
+                .child(self.render_section(
+                    commit_count_title(log.added.len(), "Added"),
+                    &log.added,
+                    IconName::Plus,
+                    Color::Created,
+                    submodule_repository.as_ref(),
+                    cx,
+                ))
+                .child(self.render_section(
+                    commit_count_title(log.removed.len(), "Removed"),
+                    &log.removed,
+                    IconName::Dash,
+                    Color::Deleted,
+                    submodule_repository.as_ref(),
+                    cx,
+                ))
+                .into_any_element(),
+        };
+
+        v_flex()
+            .id("submodule-diff-view")
+            .key_context("SubmoduleDiffView")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .p_4()
+            .gap_4()
+            .bg(cx.theme().colors().editor_background)
+            .overflow_y_scroll()
+            .track_scroll(&self.scroll_handle)
+            .child(header)
+            .child(body)
+    }
+}
+
+fn commit_count_title(count: usize, change: &str) -> SharedString {
+    if count == 1 {
+        format!("1 Commit {change}").into()
+    } else {
+        format!("{count} Commits {change}").into()
+    }
+}
@@ -0,0 +1,18 @@
+(variable_assignment
+  name: (variable_name) @debug-variable)
+
+(simple_expansion
+  (variable_name) @debug-variable)
+
+(expansion
+  (variable_name) @debug-variable)
+
+(for_statement
+  variable: (variable_name) @debug-variable)
+
+(declaration_command
+  (variable_name) @debug-variable)
+
+(compound_statement) @debug-scope
+
+(function_definition) @debug-scope
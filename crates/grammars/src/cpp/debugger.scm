@@ -0,0 +1,115 @@
+(this) @debug-variable
+
+(parameter_declaration
+  declarator: (identifier) @debug-variable)
+
+(parameter_declaration
+  declarator: (pointer_declarator
+    declarator: (identifier) @debug-variable))
+
+(parameter_declaration
+  declarator: (reference_declarator
+    (identifier) @debug-variable))
+
+(optional_parameter_declaration
+  declarator: (identifier) @debug-variable)
+
+(declaration
+  declarator: (identifier) @debug-variable)
+
+(init_declarator
+  declarator: (identifier) @debug-variable)
+
+(init_declarator
+  declarator: (pointer_declarator
+    declarator: (identifier) @debug-variable))
+
+(init_declarator
+  declarator: (reference_declarator
+    (identifier) @debug-variable))
+
+(init_declarator
+  value: (field_expression) @debug-expression
+  (#match? @debug-expression "^[A-Za-z_][A-Za-z0-9_]*(([.]|->)[A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(for_range_loop
+  declarator: (identifier) @debug-variable)
+
+(for_range_loop
+  declarator: (reference_declarator
+    (identifier) @debug-variable))
+
+(assignment_expression
+  left: (identifier) @debug-variable)
+
+(assignment_expression
+  left: (field_expression) @debug-expression
+  (#match? @debug-expression "^[A-Za-z_][A-Za-z0-9_]*(([.]|->)[A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(assignment_expression
+  right: (identifier) @debug-variable
+  (#not-match? @debug-variable "^[A-Z]"))
+
+(assignment_expression
+  right: (field_expression) @debug-expression
+  (#match? @debug-expression "^[A-Za-z_][A-Za-z0-9_]*(([.]|->)[A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(binary_expression
+  (identifier) @debug-variable
+  (#not-match? @debug-variable "^[A-Z]"))
+
+(binary_expression
+  (field_expression) @debug-expression
+  (#match? @debug-expression "^[A-Za-z_][A-Za-z0-9_]*(([.]|->)[A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(unary_expression
+  (identifier) @debug-variable
+  (#not-match? @debug-variable "^[A-Z]"))
+
+(update_expression
+  (identifier) @debug-variable)
+
+(pointer_expression
+  (identifier) @debug-variable)
+
+(subscript_expression
+  (identifier) @debug-variable
+  (#not-match? @debug-variable "^[A-Z]"))
+
+(parenthesized_expression
+  (identifier) @debug-variable
+  (#not-match? @debug-variable "^[A-Z]"))
+
+(parenthesized_expression
+  (field_expression) @debug-expression
+  (#match? @debug-expression "^[A-Za-z_][A-Za-z0-9_]*(([.]|->)[A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(condition_clause
+  (identifier) @debug-variable
+  (#not-match? @debug-variable "^[A-Z]"))
+
+(condition_clause
+  (field_expression) @debug-expression
+  (#match? @debug-expression "^[A-Za-z_][A-Za-z0-9_]*(([.]|->)[A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(argument_list
+  (identifier) @debug-variable
+  (#not-match? @debug-variable "^[A-Z]"))
+
+(argument_list
+  (field_expression) @debug-expression
+  (#match? @debug-expression "^[A-Za-z_][A-Za-z0-9_]*(([.]|->)[A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(return_statement
+  (identifier) @debug-variable
+  (#not-match? @debug-variable "^[A-Z]"))
+
+(return_statement
+  (field_expression) @debug-expression
+  (#match? @debug-expression "^[A-Za-z_][A-Za-z0-9_]*(([.]|->)[A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(compound_statement) @debug-scope
+
+(function_definition) @debug-scope
+
+(lambda_expression) @debug-scope
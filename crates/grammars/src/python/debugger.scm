@@ -92,6 +92,51 @@
   condition: (identifier) @debug-variable
   (#not-match? @debug-variable "^[A-Z]"))
 
+; Member chains such as `self.config.port`, but not ones through calls or indexing
+(assignment
+  left: (attribute) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(assignment
+  right: (attribute) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(augmented_assignment
+  right: (attribute) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(binary_operator
+  left: (attribute) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(binary_operator
+  right: (attribute) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(comparison_operator
+  (attribute) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(argument_list
+  (attribute) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(return_statement
+  (attribute) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(parenthesized_expression
+  (attribute) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(if_statement
+  condition: (attribute) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z_][A-Za-z0-9_]*)+$"))
+
+(while_statement
+  condition: (attribute) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z_][A-Za-z0-9_]*)+$"))
+
 (block) @debug-scope
 
 (module) @debug-scope
@@ -82,4 +82,49 @@
     (identifier) @debug-variable
     (#not-match? @debug-variable "^[A-Z]")))
 
+; Member chains such as `self.config.port`, but not ones through calls or indexing
+(let_declaration
+  value: (field_expression) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z0-9_]+)+$"))
+
+(assignment_expression
+  right: (field_expression) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z0-9_]+)+$"))
+
+(compound_assignment_expr
+  right: (field_expression) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z0-9_]+)+$"))
+
+(binary_expression
+  (field_expression) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z0-9_]+)+$"))
+
+(reference_expression
+  (field_expression) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z0-9_]+)+$"))
+
+(unary_expression
+  (field_expression) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z0-9_]+)+$"))
+
+(arguments
+  (field_expression) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z0-9_]+)+$"))
+
+(return_expression
+  (field_expression) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z0-9_]+)+$"))
+
+(parenthesized_expression
+  (field_expression) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z0-9_]+)+$"))
+
+(if_expression
+  condition: (field_expression) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z0-9_]+)+$"))
+
+(while_expression
+  condition: (field_expression) @debug-expression
+  (#match? @debug-expression "^[a-z_][A-Za-z0-9_]*([.][A-Za-z0-9_]+)+$"))
+
 (block) @debug-scope
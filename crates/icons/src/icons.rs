@@ -93,6 +93,8 @@ pub enum IconName {
     DebugIgnoreBreakpoints,
     DebugLogBreakpoint,
     DebugPause,
+    DebugReverseContinue,
+    DebugStepBack,
     DebugStepInto,
     DebugStepOut,
     DebugStepOver,
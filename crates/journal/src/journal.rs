@@ -1,11 +1,16 @@
-use chrono::{Datelike, Local, NaiveTime, Timelike};
+mod journal_picker;
+
+use chrono::{Datelike, Days, Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
 use editor::scroll::Autoscroll;
 use editor::{Editor, SelectionEffects};
-use gpui::{App, AppContext as _, Context, TaskExt, Window, actions};
+use gpui::{App, AppContext as _, Context, Entity, Subscription, Task, TaskExt, Window, actions};
+pub use journal_picker::JournalPicker;
+use language::{BufferEvent, DiskState};
+use project::Project;
 pub use settings::HourFormat;
 use settings::{RegisterSetting, Settings};
 use std::{
-    fs::OpenOptions,
+    ops::Range,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -15,7 +20,13 @@ actions!(
     journal,
     [
         /// Creates a new journal entry for today.
-        NewJournalEntry
+        NewJournalEntry,
+        /// Opens the journal page for the day before the active one, or before today.
+        OpenYesterday,
+        /// Opens the journal page for the day after the active one, or after today.
+        OpenTomorrow,
+        /// Lists past journal pages by date, searching their contents.
+        OpenJournalPage,
     ]
 );
 
@@ -30,6 +41,18 @@ pub struct JournalSettings {
     ///
     /// Default: hour12
     pub hour_format: HourFormat,
+    /// The text inserted for each new journal entry.
+    ///
+    /// Default: `# {time}`
+    pub entry_template: String,
+    /// The text a new day's page starts with.
+    ///
+    /// Default: ""
+    pub day_template: String,
+    /// Whether to link each day's page to the previous and next pages.
+    ///
+    /// Default: true
+    pub link_days: bool,
 }
 
 impl settings::Settings for JournalSettings {
@@ -39,6 +62,9 @@ impl settings::Settings for JournalSettings {
         Self {
             path: journal.path.unwrap(),
             hour_format: journal.hour_format.unwrap(),
+            entry_template: journal.entry_template.unwrap(),
+            day_template: journal.day_template.unwrap(),
+            link_days: journal.link_days.unwrap(),
         }
     }
 }
@@ -46,54 +72,106 @@ impl settings::Settings for JournalSettings {
 pub fn init(_: Arc<AppState>, cx: &mut App) {
     cx.observe_new(
         |workspace: &mut Workspace, _window, _cx: &mut Context<Workspace>| {
-            workspace.register_action(|workspace, _: &NewJournalEntry, window, cx| {
-                new_journal_entry(workspace, window, cx);
-            });
+            workspace
+                .register_action(|workspace, _: &NewJournalEntry, window, cx| {
+                    new_journal_entry(workspace, window, cx);
+                })
+                .register_action(|workspace, _: &OpenYesterday, window, cx| {
+                    open_adjacent_day(workspace, Direction::Previous, window, cx);
+                })
+                .register_action(|workspace, _: &OpenTomorrow, window, cx| {
+                    open_adjacent_day(workspace, Direction::Next, window, cx);
+                })
+                .register_action(|workspace, _: &OpenJournalPage, window, cx| {
+                    JournalPicker::toggle(workspace, window, cx);
+                });
         },
     )
     .detach();
 }
 
 pub fn new_journal_entry(workspace: &Workspace, window: &mut Window, cx: &mut App) {
-    let settings = JournalSettings::get_global(cx);
-    let journal_dir = match journal_dir(&settings.path) {
-        Some(journal_dir) => journal_dir,
-        None => {
-            log::error!("Can't determine journal directory");
-            return;
-        }
+    let settings = JournalSettings::get_global(cx).clone();
+    let Some(journal_dir) = journal_dir(&settings.path) else {
+        log::error!("Can't determine journal directory");
+        return;
+    };
+
+    let now = Local::now().naive_local();
+    let entry = render_template(&settings.entry_template, now, &settings.hour_format);
+    let path = page_path(&journal_dir, now.date());
+    open_journal_page(workspace, journal_dir, path, Some(entry), window, cx);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Direction {
+    Previous,
+    Next,
+}
+
+/// Opens the page for the day before or after the journal page in the active
+/// editor. Outside of the journal, today is used.
+fn open_adjacent_day(
+    workspace: &Workspace,
+    direction: Direction,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let settings = JournalSettings::get_global(cx).clone();
+    let Some(journal_dir) = journal_dir(&settings.path) else {
+        log::error!("Can't determine journal directory");
+        return;
     };
-    let journal_dir_clone = journal_dir.clone();
-
-    let now = Local::now();
-    let month_dir = journal_dir
-        .join(format!("{:02}", now.year()))
-        .join(format!("{:02}", now.month()));
-    let entry_path = month_dir.join(format!("{:02}.md", now.day()));
-    let now = now.time();
-    let entry_heading = heading_entry(now, &settings.hour_format);
-
-    let create_entry = cx.background_spawn(async move {
-        std::fs::create_dir_all(month_dir)?;
-        OpenOptions::new()
-            .create(true)
-            .truncate(false)
-            .write(true)
-            .open(&entry_path)?;
-        Ok::<_, std::io::Error>((journal_dir, entry_path))
-    });
 
+    let now = Local::now().naive_local();
+    let active_date = workspace
+        .active_item(cx)
+        .and_then(|item| item.project_path(cx))
+        .and_then(|project_path| {
+            workspace
+                .project()
+                .read(cx)
+                .absolute_path(&project_path, cx)
+        })
+        .and_then(|path| page_date(&journal_dir, &path));
+    let date = active_date.unwrap_or(now.date());
+    let date = match direction {
+        Direction::Previous => date.checked_sub_days(Days::new(1)),
+        Direction::Next => date.checked_add_days(Days::new(1)),
+    };
+    let Some(date) = date else {
+        return;
+    };
+
+    let path = page_path(&journal_dir, date);
+    open_journal_page(workspace, journal_dir, path, None, window, cx);
+}
+
+/// Opens a journal page, in a new window when the journal isn't part of the
+/// workspace. `entry` is appended at the end of the page.
+///
+/// A page that doesn't exist yet is opened as an unsaved buffer starting with
+/// the day template, so that it is only created when the user saves it. Its
+/// neighbors are linked to it then.
+fn open_journal_page(
+    workspace: &Workspace,
+    journal_dir: PathBuf,
+    path: PathBuf,
+    entry: Option<String>,
+    window: &mut Window,
+    cx: &mut App,
+) {
     let worktrees = workspace.visible_worktrees(cx).collect::<Vec<_>>();
     let mut open_new_workspace = true;
     'outer: for worktree in worktrees.iter() {
         let worktree_root = worktree.read(cx).abs_path();
-        if *worktree_root == journal_dir_clone {
+        if *worktree_root == journal_dir {
             open_new_workspace = false;
             break;
         }
         for directory in worktree.read(cx).directories(true, 1) {
             let full_directory_path = worktree_root.join(directory.path.as_std_path());
-            if full_directory_path.ends_with(&journal_dir_clone) {
+            if full_directory_path.ends_with(&journal_dir) {
                 open_new_workspace = false;
                 break 'outer;
             }
@@ -102,18 +180,41 @@ pub fn new_journal_entry(workspace: &Workspace, window: &mut Window, cx: &mut Ap
 
     let app_state = workspace.app_state().clone();
     let view_snapshot = workspace.weak_handle();
+    let settings = JournalSettings::get_global(cx).clone();
+    let now = Local::now().naive_local();
 
     window
         .spawn(cx, async move |cx| {
-            let (journal_dir, entry_path) = create_entry.await?;
+            let date = page_date(&journal_dir, &path);
+            let pages = if settings.link_days && date.is_some() {
+                let journal_dir = journal_dir.clone();
+                cx.background_spawn(async move { journal_pages(&journal_dir) })
+                    .await
+            } else {
+                Vec::new()
+            };
+            let new_page_content = date.map(|date| {
+                let (previous, next) = neighbors(&pages, date);
+                day_page_content(
+                    date,
+                    now,
+                    previous.map(|(date, _)| date),
+                    next.map(|(date, _)| date),
+                    &settings,
+                )
+            });
+
             let opened = if open_new_workspace {
+                // The journal is opened as a worktree, so its directory has to
+                // exist. Pages are only created when they are saved.
+                app_state.fs.create_dir(&journal_dir).await?;
                 let OpenResult {
                     window: new_workspace,
                     ..
                 } = cx
                     .update(|_window, cx| {
                         workspace::open_paths(
-                            &[journal_dir],
+                            &[journal_dir.clone()],
                             app_state,
                             workspace::OpenOptions::default(),
                             cx,
@@ -125,7 +226,7 @@ pub fn new_journal_entry(workspace: &Workspace, window: &mut Window, cx: &mut Ap
                         let workspace = multi_workspace.workspace().clone();
                         workspace.update(cx, |workspace, cx| {
                             workspace.open_paths(
-                                vec![entry_path],
+                                vec![path],
                                 workspace::OpenOptions {
                                     visible: Some(OpenVisible::All),
                                     ..Default::default()
@@ -141,7 +242,7 @@ pub fn new_journal_entry(workspace: &Workspace, window: &mut Window, cx: &mut Ap
                 view_snapshot
                     .update_in(cx, |workspace, window, cx| {
                         workspace.open_paths(
-                            vec![entry_path],
+                            vec![path],
                             workspace::OpenOptions {
                                 visible: Some(OpenVisible::All),
                                 ..Default::default()
@@ -158,6 +259,22 @@ pub fn new_journal_entry(workspace: &Workspace, window: &mut Window, cx: &mut Ap
                 && let Some(editor) = item.downcast::<Editor>().map(|editor| editor.downgrade())
             {
                 editor.update_in(cx, |editor, window, cx| {
+                    let buffer = editor.buffer().read(cx).as_singleton();
+                    if let Some(buffer) = buffer
+                        && let Some(date) = date
+                        && let Some(content) = new_page_content
+                        && buffer.read(cx).is_empty()
+                        && buffer
+                            .read(cx)
+                            .file()
+                            .is_some_and(|file| file.disk_state() == DiskState::New)
+                    {
+                        buffer.update(cx, |buffer, cx| buffer.edit([(0..0, content)], None, cx));
+                        if settings.link_days {
+                            link_neighbors_on_save(editor, &buffer, journal_dir, date, cx);
+                        }
+                    }
+
                     let len = editor.buffer().read(cx).len(cx);
                     editor.change_selections(
                         SelectionEffects::scroll(Autoscroll::center()),
@@ -165,11 +282,22 @@ pub fn new_journal_entry(workspace: &Workspace, window: &mut Window, cx: &mut Ap
                         cx,
                         |s| s.select_ranges([len..len]),
                     );
-                    if len.0 > 0 {
+                    if let Some(entry) = entry {
+                        // Separate the entry from the page's content by a blank line.
+                        let trailing_newlines = editor
+                            .buffer()
+                            .read(cx)
+                            .snapshot(cx)
+                            .reversed_chars_at(len)
+                            .take(2)
+                            .take_while(|c| *c == '\n')
+                            .count();
+                        if len.0 > 0 {
+                            editor.insert(&"\n".repeat(2 - trailing_newlines), window, cx);
+                        }
+                        editor.insert(&entry, window, cx);
                         editor.insert("\n\n", window, cx);
                     }
-                    editor.insert(&entry_heading, window, cx);
-                    editor.insert("\n\n", window, cx);
                 })?;
             }
 
@@ -190,16 +318,225 @@ fn journal_dir(path: &str) -> Option<PathBuf> {
     Some(absolute_path.join("journal"))
 }
 
-fn heading_entry(now: NaiveTime, hour_format: &HourFormat) -> String {
+/// The path of the page for `date`, as `<year>/<month>/<day>.md`.
+fn page_path(journal_dir: &Path, date: NaiveDate) -> PathBuf {
+    journal_dir.join(page_relative_path(date))
+}
+
+fn page_relative_path(date: NaiveDate) -> String {
+    format!(
+        "{:02}/{:02}/{:02}.md",
+        date.year(),
+        date.month(),
+        date.day()
+    )
+}
+
+/// The date of the journal page at `path`, if it is one.
+fn page_date(journal_dir: &Path, path: &Path) -> Option<NaiveDate> {
+    let relative = path.strip_prefix(journal_dir).ok()?;
+    let mut components = relative.iter().map(|component| component.to_str());
+    let year = components.next()??.parse().ok()?;
+    let month = components.next()??.parse().ok()?;
+    let day = components.next()??.strip_suffix(".md")?.parse().ok()?;
+    if components.next().is_some() {
+        return None;
+    }
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+/// Lists the journal's pages, oldest first.
+fn journal_pages(journal_dir: &Path) -> Vec<(NaiveDate, PathBuf)> {
+    fn entries(dir: &Path) -> impl Iterator<Item = PathBuf> {
+        std::fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| Some(entry.ok()?.path()))
+    }
+
+    let mut pages = entries(journal_dir)
+        .flat_map(|year| entries(&year).collect::<Vec<_>>())
+        .flat_map(|month| entries(&month).collect::<Vec<_>>())
+        .filter_map(|path| Some((page_date(journal_dir, &path)?, path)))
+        .collect::<Vec<_>>();
+    pages.sort();
+    pages
+}
+
+/// The pages right before and after `date` in `pages`, which are sorted by date.
+fn neighbors(
+    pages: &[(NaiveDate, PathBuf)],
+    date: NaiveDate,
+) -> (Option<(NaiveDate, PathBuf)>, Option<(NaiveDate, PathBuf)>) {
+    (
+        pages.iter().rev().find(|(page, _)| *page < date).cloned(),
+        pages.iter().find(|(page, _)| *page > date).cloned(),
+    )
+}
+
+/// The text a new page for `date` starts with: the day template, between links
+/// to the `previous` and `next` pages.
+fn day_page_content(
+    date: NaiveDate,
+    now: NaiveDateTime,
+    previous: Option<NaiveDate>,
+    next: Option<NaiveDate>,
+    settings: &JournalSettings,
+) -> String {
+    let day_time = NaiveDateTime::new(date, now.time());
+    let mut content = render_template(&settings.day_template, day_time, &settings.hour_format);
+    if let Some(previous) = previous {
+        content.insert_str(
+            0,
+            &format!("{}\n\n", day_link(previous, Direction::Previous)),
+        );
+    }
+    if let Some(next) = next {
+        if !content.is_empty() {
+            content.truncate(content.trim_end_matches('\n').len());
+            content.push_str("\n\n");
+        }
+        content.push_str(&day_link(next, Direction::Next));
+        content.push('\n');
+    }
+    content
+}
+
+/// Keeps the neighbors of the journal page open in an editor linked to it, for
+/// as long as the editor is open.
+struct JournalPageAddon {
+    _link_neighbors_on_save: Subscription,
+}
+
+impl editor::Addon for JournalPageAddon {
+    fn to_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Links the pages before and after `date` to the page in `buffer` each time
+/// it is saved from `editor`, which is how new pages are created.
+fn link_neighbors_on_save(
+    editor: &mut Editor,
+    buffer: &Entity<language::Buffer>,
+    journal_dir: PathBuf,
+    date: NaiveDate,
+    cx: &mut Context<Editor>,
+) {
+    let subscription = cx.subscribe(buffer, move |editor, _, event, cx| {
+        if let BufferEvent::Saved = event
+            && let Some(project) = editor.project().cloned()
+        {
+            link_neighbors(project, journal_dir.clone(), date, cx).detach_and_log_err(cx);
+        }
+    });
+    editor.register_addon(JournalPageAddon {
+        _link_neighbors_on_save: subscription,
+    });
+}
+
+/// Adds links to the page for `date` to the pages before and after it, through
+/// the project's buffers. When the page was inserted between two pages, their
+/// links to each other are replaced.
+fn link_neighbors(
+    project: Entity<Project>,
+    journal_dir: PathBuf,
+    date: NaiveDate,
+    cx: &mut App,
+) -> Task<anyhow::Result<()>> {
+    cx.spawn(async move |cx| {
+        let pages = cx
+            .background_spawn(async move { journal_pages(&journal_dir) })
+            .await;
+        let (previous, next) = neighbors(&pages, date);
+        let previous_date = previous.as_ref().map(|(date, _)| *date);
+        let next_date = next.as_ref().map(|(date, _)| *date);
+        for (neighbor, direction, replaced) in [
+            (previous, Direction::Next, next_date),
+            (next, Direction::Previous, previous_date),
+        ] {
+            let Some((_, path)) = neighbor else {
+                continue;
+            };
+            let buffer = project
+                .update(cx, |project, cx| project.open_local_buffer(path, cx))
+                .await?;
+            let save = buffer.update(cx, |buffer, cx| {
+                let (range, text) = relink(&buffer.text(), date, direction, replaced)?;
+                // Changes the user hasn't saved yet are left for them to save.
+                let was_dirty = buffer.is_dirty();
+                buffer.edit([(range, text)], None, cx);
+                Some(!was_dirty)
+            });
+            if save == Some(true) {
+                project
+                    .update(cx, |project, cx| project.save_buffer(buffer, cx))
+                    .await?;
+            }
+        }
+        anyhow::Ok(())
+    })
+}
+
+/// A Markdown link to the page for `date`, relative to another page.
+fn day_link(date: NaiveDate, direction: Direction) -> String {
+    let target = format!("../../{}", page_relative_path(date));
+    match direction {
+        Direction::Previous => format!("[← {date}]({target})"),
+        Direction::Next => format!("[{date} →]({target})"),
+    }
+}
+
+/// The edit that makes a page with `content` link to the page for `date` in
+/// `direction`, replacing its link to `replaced` if it has one, and appending
+/// the link otherwise. Returns `None` if the page already links to `date`.
+fn relink(
+    content: &str,
+    date: NaiveDate,
+    direction: Direction,
+    replaced: Option<NaiveDate>,
+) -> Option<(Range<usize>, String)> {
+    let link = day_link(date, direction);
+    if content.contains(&link) {
+        return None;
+    }
+    if let Some(replaced) = replaced {
+        let replaced_link = day_link(replaced, direction);
+        if let Some(start) = content.find(&replaced_link) {
+            return Some((start..start + replaced_link.len(), link));
+        }
+    }
+
+    let mut text = String::new();
+    if !content.is_empty() && !content.ends_with('\n') {
+        text.push('\n');
+    }
+    if !content.is_empty() {
+        text.push('\n');
+    }
+    text.push_str(&link);
+    text.push('\n');
+    Some((content.len()..content.len(), text))
+}
+
+/// Replaces the `{date}`, `{time}` and `{weekday}` placeholders of a template.
+fn render_template(template: &str, now: NaiveDateTime, hour_format: &HourFormat) -> String {
+    template
+        .replace("{date}", &now.date().to_string())
+        .replace("{time}", &format_time(now.time(), hour_format))
+        .replace("{weekday}", &now.format("%A").to_string())
+}
+
+fn format_time(now: NaiveTime, hour_format: &HourFormat) -> String {
     match hour_format {
         HourFormat::Hour24 => {
             let hour = now.hour();
-            format!("# {}:{:02}", hour, now.minute())
+            format!("{}:{:02}", hour, now.minute())
         }
         HourFormat::Hour12 => {
             let (pm, hour) = now.hour12();
             let am_or_pm = if pm { "PM" } else { "AM" };
-            format!("# {}:{:02} {}", hour, now.minute(), am_or_pm)
+            format!("{}:{:02} {}", hour, now.minute(), am_or_pm)
         }
     }
 }
@@ -209,6 +546,11 @@ mod tests {
     mod heading_entry_tests {
         use super::super::*;
 
+        fn heading_entry(time: NaiveTime, hour_format: &HourFormat) -> String {
+            let date = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+            render_template("# {time}", date.and_time(time), hour_format)
+        }
+
         #[test]
         fn test_heading_entry_defaults_to_hour_12() {
             let naive_time = NaiveTime::from_hms_milli_opt(15, 0, 0, 0).unwrap();
@@ -237,6 +579,226 @@ mod tests {
         }
     }
 
+    mod template_tests {
+        use super::super::*;
+
+        #[test]
+        fn test_render_template() {
+            let now = NaiveDate::from_ymd_opt(2026, 10, 18)
+                .unwrap()
+                .and_hms_opt(9, 5, 0)
+                .unwrap();
+            assert_eq!(
+                render_template(
+                    "## {weekday}, {date}\n\n- {time} standup",
+                    now,
+                    &HourFormat::Hour24
+                ),
+                "## Sunday, 2026-10-18\n\n- 9:05 standup"
+            );
+            assert_eq!(
+                render_template("{time} {time}", now, &HourFormat::Hour12),
+                "9:05 AM 9:05 AM"
+            );
+            assert_eq!(render_template("", now, &HourFormat::Hour12), "");
+        }
+    }
+
+    mod page_tests {
+        use super::super::*;
+
+        fn settings(day_template: &str, link_days: bool) -> JournalSettings {
+            JournalSettings {
+                path: String::new(),
+                hour_format: HourFormat::Hour24,
+                entry_template: "# {time}".to_string(),
+                day_template: day_template.to_string(),
+                link_days,
+            }
+        }
+
+        fn date(day: u32) -> NaiveDate {
+            NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+        }
+
+        #[test]
+        fn test_page_date() {
+            let journal_dir = Path::new("/notes/journal");
+            assert_eq!(
+                page_date(journal_dir, &page_path(journal_dir, date(7))),
+                Some(date(7))
+            );
+            assert_eq!(
+                page_date(journal_dir, Path::new("/notes/journal/2026/02/30.md")),
+                None
+            );
+            assert_eq!(
+                page_date(journal_dir, Path::new("/notes/journal/2026/10/07.txt")),
+                None
+            );
+            assert_eq!(
+                page_date(journal_dir, Path::new("/notes/other/2026/10/07.md")),
+                None
+            );
+        }
+
+        #[test]
+        fn test_day_page_content_links_neighbors() {
+            let now = date(20).and_hms_opt(10, 0, 0).unwrap();
+            let without_template = settings("", true);
+            let settings = settings("# {weekday}\n", true);
+
+            assert_eq!(
+                day_page_content(date(16), now, None, None, &settings),
+                "# Friday\n"
+            );
+            assert_eq!(
+                day_page_content(date(17), now, Some(date(16)), Some(date(19)), &settings),
+                "[← 2026-10-16](../../2026/10/16.md)\n\n# Saturday\n\n[2026-10-19 →](../../2026/10/19.md)\n"
+            );
+            assert_eq!(
+                day_page_content(date(17), now, None, Some(date(19)), &without_template),
+                "[2026-10-19 →](../../2026/10/19.md)\n"
+            );
+        }
+
+        #[test]
+        fn test_neighbors() {
+            let pages = [16, 19, 21]
+                .map(|day| (date(day), PathBuf::from(format!("{day}.md"))))
+                .to_vec();
+            let dates = |date: NaiveDate| {
+                let (previous, next) = neighbors(&pages, date);
+                (previous.map(|(date, _)| date), next.map(|(date, _)| date))
+            };
+            assert_eq!(dates(date(15)), (None, Some(date(16))));
+            assert_eq!(dates(date(17)), (Some(date(16)), Some(date(19))));
+            assert_eq!(dates(date(19)), (Some(date(16)), Some(date(21))));
+            assert_eq!(dates(date(22)), (Some(date(21)), None));
+        }
+
+        #[test]
+        fn test_relink() {
+            fn apply(content: &str, edit: Option<(Range<usize>, String)>) -> String {
+                let mut content = content.to_string();
+                if let Some((range, text)) = edit {
+                    content.replace_range(range, &text);
+                }
+                content
+            }
+
+            // A page without links gets one appended after a blank line.
+            assert_eq!(
+                apply(
+                    "# Friday",
+                    relink("# Friday", date(19), Direction::Next, None)
+                ),
+                "# Friday\n\n[2026-10-19 →](../../2026/10/19.md)\n"
+            );
+            assert_eq!(
+                apply("", relink("", date(19), Direction::Next, None)),
+                "[2026-10-19 →](../../2026/10/19.md)\n"
+            );
+
+            // A page inserted between two pages replaces their links to each other.
+            let first = "# Friday\n\n[2026-10-19 →](../../2026/10/19.md)\n";
+            assert_eq!(
+                apply(
+                    first,
+                    relink(first, date(17), Direction::Next, Some(date(19)))
+                ),
+                "# Friday\n\n[2026-10-17 →](../../2026/10/17.md)\n"
+            );
+            let last = "[← 2026-10-16](../../2026/10/16.md)\n\n# Monday\n";
+            assert_eq!(
+                apply(
+                    last,
+                    relink(last, date(17), Direction::Previous, Some(date(16)))
+                ),
+                "[← 2026-10-17](../../2026/10/17.md)\n\n# Monday\n"
+            );
+
+            // Pages that already link to the new page are left alone.
+            assert_eq!(
+                relink(first, date(19), Direction::Next, Some(date(21))),
+                None
+            );
+        }
+    }
+
+    mod open_page_tests {
+        use super::super::*;
+        use gpui::{TestAppContext, VisualTestContext};
+        use project::{FakeFs, Fs as _};
+        use serde_json::json;
+        use settings::SettingsStore;
+        use util::path;
+        use workspace::{MultiWorkspace, SaveIntent};
+
+        fn init_test(cx: &mut TestAppContext) {
+            cx.update(|cx| {
+                let settings = SettingsStore::test(cx);
+                cx.set_global(settings);
+                theme_settings::init(theme::LoadThemes::JustBase, cx);
+                editor::init(cx);
+                SettingsStore::update_global(cx, |store, cx| {
+                    store.update_user_settings(cx, |settings| {
+                        settings.journal = Some(settings::JournalSettingsContent {
+                            path: Some(path!("/notes").into()),
+                            hour_format: None,
+                            entry_template: None,
+                            day_template: Some("# Today\n".into()),
+                            link_days: None,
+                        });
+                    });
+                });
+            });
+        }
+
+        #[gpui::test]
+        async fn test_new_page_is_only_created_when_saved(cx: &mut TestAppContext) {
+            init_test(cx);
+
+            let fs = FakeFs::new(cx.background_executor.clone());
+            fs.insert_tree(path!("/notes"), json!({ "journal": {} }))
+                .await;
+            let project = Project::test(fs.clone(), [Path::new(path!("/notes/journal"))], cx).await;
+            let window =
+                cx.add_window(|window, cx| MultiWorkspace::test_new(project.clone(), window, cx));
+            let workspace = window
+                .read_with(cx, |multi_workspace, _| multi_workspace.workspace().clone())
+                .unwrap();
+            let cx = &mut VisualTestContext::from_window(window.into(), cx);
+
+            let journal_dir = PathBuf::from(path!("/notes/journal"));
+            let page = page_path(&journal_dir, NaiveDate::from_ymd_opt(2026, 10, 18).unwrap());
+            workspace.update_in(cx, |workspace, window, cx| {
+                open_journal_page(workspace, journal_dir, page.clone(), None, window, cx);
+            });
+            cx.run_until_parked();
+
+            // Opening a page that doesn't exist yet only fills an unsaved buffer with the
+            // day template, so that browsing days doesn't leave empty pages behind.
+            let editor = workspace
+                .read_with(cx, |workspace, cx| workspace.active_item_as::<Editor>(cx))
+                .unwrap();
+            assert_eq!(
+                editor.read_with(cx, |editor, cx| editor.text(cx)),
+                "# Today\n"
+            );
+            assert!(!fs.is_file(&page).await);
+
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.save_active_item(SaveIntent::Save, window, cx)
+                })
+                .await
+                .unwrap();
+            assert!(fs.is_file(&page).await);
+            assert_eq!(fs.load(&page).await.unwrap(), "# Today\n");
+        }
+    }
+
     mod journal_dir_tests {
         use super::super::*;
 
@@ -0,0 +1,393 @@
+use crate::{JournalSettings, journal_dir, journal_pages, open_journal_page};
+use chrono::NaiveDate;
+use gpui::{
+    App, AppContext as _, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    Render, SharedString, Task, TaskExt, WeakEntity, Window, rems,
+};
+use picker::{Picker, PickerDelegate};
+use settings::Settings as _;
+use std::{path::PathBuf, rc::Rc, sync::Arc};
+use ui::{
+    DocumentationAside, DocumentationSide, HighlightedLabel, ListItem, ListItemSpacing, prelude::*,
+};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace};
+
+/// How many lines of the selected page are shown next to the picker.
+const PREVIEW_LINES: usize = 40;
+
+/// A modal listing the journal's pages, newest first, and searching their text.
+pub struct JournalPicker {
+    picker: Entity<Picker<JournalPickerDelegate>>,
+}
+
+impl JournalPicker {
+    pub fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let Some(journal_dir) = journal_dir(&JournalSettings::get_global(cx).path) else {
+            log::error!("Can't determine journal directory");
+            return;
+        };
+        let weak_workspace = cx.weak_entity();
+        workspace.toggle_modal(window, cx, move |window, cx| {
+            JournalPicker::new(weak_workspace, journal_dir, window, cx)
+        });
+    }
+
+    fn new(
+        workspace: WeakEntity<Workspace>,
+        journal_dir: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = JournalPickerDelegate {
+            journal_picker: cx.entity().downgrade(),
+            workspace,
+            journal_dir: journal_dir.clone(),
+            pages: Arc::default(),
+            matches: Vec::new(),
+            selected_index: 0,
+        };
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+
+        let load_pages = cx.background_spawn(async move { load_pages(journal_dir) });
+        cx.spawn_in(window, {
+            let picker = picker.downgrade();
+            async move |_, cx| {
+                let pages = load_pages.await;
+                picker.update_in(cx, |picker, window, cx| {
+                    picker.delegate.pages = Arc::new(pages);
+                    picker.refresh(window, cx);
+                })
+            }
+        })
+        .detach_and_log_err(cx);
+
+        Self { picker }
+    }
+}
+
+impl Render for JournalPicker {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("JournalPicker")
+            .w(rems(34.))
+            .child(self.picker.clone())
+    }
+}
+
+impl Focusable for JournalPicker {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for JournalPicker {}
+impl ModalView for JournalPicker {}
+
+struct JournalPage {
+    path: PathBuf,
+    label: SharedString,
+    text: String,
+}
+
+struct PageMatch {
+    page_ix: usize,
+    label_positions: Vec<usize>,
+    snippet: SharedString,
+}
+
+pub struct JournalPickerDelegate {
+    journal_picker: WeakEntity<JournalPicker>,
+    workspace: WeakEntity<Workspace>,
+    journal_dir: PathBuf,
+    pages: Arc<Vec<JournalPage>>,
+    matches: Vec<PageMatch>,
+    selected_index: usize,
+}
+
+/// Reads the journal's pages, newest first.
+fn load_pages(journal_dir: PathBuf) -> Vec<JournalPage> {
+    journal_pages(&journal_dir)
+        .into_iter()
+        .rev()
+        .filter_map(|(date, path)| {
+            let text = std::fs::read_to_string(&path).log_err()?;
+            Some(JournalPage {
+                label: page_label(date).into(),
+                path,
+                text,
+            })
+        })
+        .collect()
+}
+
+fn page_label(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d %A").to_string()
+}
+
+/// Matches pages whose label or text contains every word of the query,
+/// ignoring case.
+fn match_pages(pages: &[JournalPage], query: &str) -> Vec<PageMatch> {
+    let words = query
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>();
+
+    pages
+        .iter()
+        .enumerate()
+        .filter_map(|(page_ix, page)| {
+            let label = page.label.to_lowercase();
+            let mut label_positions = Vec::new();
+            let mut snippet = None;
+            for word in &words {
+                if let Some(start) = label.find(word.as_str()) {
+                    label_positions.extend(start..start + word.len());
+                } else {
+                    let line = page
+                        .text
+                        .lines()
+                        .find(|line| line.to_lowercase().contains(word.as_str()))?;
+                    snippet.get_or_insert(line);
+                }
+            }
+            label_positions.sort_unstable();
+            label_positions.dedup();
+
+            let snippet = snippet
+                .or_else(|| page.text.lines().find(|line| is_content_line(line)))
+                .unwrap_or_default()
+                .trim();
+            Some(PageMatch {
+                page_ix,
+                label_positions,
+                snippet: snippet.to_string().into(),
+            })
+        })
+        .collect()
+}
+
+/// Whether a line has text of its own, as opposed to a blank line or a link to
+/// the previous or next day.
+fn is_content_line(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && !line.starts_with("[← ") && !line.contains(" →](")
+}
+
+impl PickerDelegate for JournalPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Search journal pages...".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        let text = if self.pages.is_empty() {
+            "No journal pages yet"
+        } else {
+            "No matching journal pages"
+        };
+        Some(text.into())
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let pages = self.pages.clone();
+        let matches = cx.background_spawn(async move { match_pages(&pages, &query) });
+
+        cx.spawn_in(window, async move |this, cx| {
+            let matches = matches.await;
+            this.update(cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(page) = self
+            .matches
+            .get(self.selected_index)
+            .and_then(|page_match| self.pages.get(page_match.page_ix))
+        else {
+            return;
+        };
+
+        let path = page.path.clone();
+        let journal_dir = self.journal_dir.clone();
+        self.workspace
+            .update(cx, |workspace, cx| {
+                open_journal_page(workspace, journal_dir, path, None, window, cx);
+            })
+            .log_err();
+        self.dismissed(window, cx);
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        self.journal_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let page_match = self.matches.get(ix)?;
+        let page = self.pages.get(page_match.page_ix)?;
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .child(
+                    v_flex()
+                        .child(HighlightedLabel::new(
+                            page.label.clone(),
+                            page_match.label_positions.clone(),
+                        ))
+                        .child(
+                            Label::new(page_match.snippet.clone())
+                                .size(LabelSize::Small)
+                                .color(Color::Muted)
+                                .truncate(),
+                        ),
+                ),
+        )
+    }
+
+    fn documentation_aside(
+        &self,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<DocumentationAside> {
+        let page_match = self.matches.get(self.selected_index)?;
+        let page = self.pages.get(page_match.page_ix)?;
+        let preview: SharedString = page
+            .text
+            .lines()
+            .take(PREVIEW_LINES)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .into();
+        if preview.trim().is_empty() {
+            return None;
+        }
+
+        Some(DocumentationAside::new(
+            DocumentationSide::Right,
+            Rc::new(move |_| {
+                v_flex()
+                    .max_w(rems(28.))
+                    .child(Label::new(preview.clone()).size(LabelSize::Small))
+                    .into_any_element()
+            }),
+        ))
+    }
+
+    fn documentation_aside_index(&self) -> Option<usize> {
+        Some(self.selected_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(date: NaiveDate, text: &str) -> JournalPage {
+        JournalPage {
+            path: PathBuf::from(format!("/journal/{date}.md")),
+            label: page_label(date).into(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_match_pages() {
+        let pages = [
+            page(
+                NaiveDate::from_ymd_opt(2026, 10, 19).unwrap(),
+                "[← 2026-10-16](../../2026/10/16.md)\n\n# 9:00 AM\n\nFixed the Flaky test",
+            ),
+            page(
+                NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(),
+                "# 4:30 PM\n\nRelease notes\n\n[2026-10-19 →](../../2026/10/19.md)",
+            ),
+        ];
+        let matched = |query: &str| {
+            match_pages(&pages, query)
+                .into_iter()
+                .map(|page_match| {
+                    (
+                        pages[page_match.page_ix].label.to_string(),
+                        page_match.label_positions,
+                        page_match.snippet.to_string(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            matched(""),
+            vec![
+                (
+                    "2026-10-19 Monday".to_string(),
+                    vec![],
+                    "# 9:00 AM".to_string()
+                ),
+                (
+                    "2026-10-16 Friday".to_string(),
+                    vec![],
+                    "# 4:30 PM".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            matched("flaky"),
+            vec![(
+                "2026-10-19 Monday".to_string(),
+                vec![],
+                "Fixed the Flaky test".to_string()
+            )]
+        );
+        assert_eq!(
+            matched("fri release"),
+            vec![(
+                "2026-10-16 Friday".to_string(),
+                vec![11, 12, 13],
+                "Release notes".to_string()
+            )]
+        );
+        assert_eq!(matched("2026-10 nothing"), Vec::new());
+    }
+}
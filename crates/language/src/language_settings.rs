@@ -24,7 +24,7 @@ pub use settings::{
 use settings::{RegisterSetting, Settings, SettingsLocation, SettingsStore, merge_from::MergeFrom};
 use shellexpand;
 use std::{borrow::Cow, num::NonZeroU32, path::Path, sync::Arc};
-use text::ToOffset;
+use text::{LineEnding, ToOffset};
 
 /// Returns the settings for all languages from the provided file.
 pub fn all_language_settings<'a>(
@@ -632,15 +632,36 @@ fn merge_with_modeline(settings: &mut LanguageSettings, modeline: &ModelineSetti
         }
     });
 
-    settings
-        .tab_size
-        .merge_from_option(modeline.tab_size.as_ref());
+    // Zed indents by one tab size, so the indent width wins when indenting
+    // with spaces, and the tab width wins when indenting with tabs.
+    let tab_size = if modeline.hard_tabs == Some(true) {
+        modeline.tab_size.or(modeline.indent_size)
+    } else {
+        modeline.indent_size.or(modeline.tab_size)
+    };
+    let soft_wrap = modeline.soft_wrap.map(|enabled| {
+        if !enabled {
+            SoftWrap::None
+        } else if modeline.preferred_line_length.is_some() {
+            SoftWrap::Bounded
+        } else {
+            SoftWrap::EditorWidth
+        }
+    });
+    let line_ending = modeline.line_ending.map(|line_ending| match line_ending {
+        LineEnding::Unix => LineEndingSetting::EnforceLf,
+        LineEnding::Windows => LineEndingSetting::EnforceCrlf,
+    });
+
+    settings.tab_size.merge_from_option(tab_size.as_ref());
     settings
         .hard_tabs
         .merge_from_option(modeline.hard_tabs.as_ref());
     settings
         .preferred_line_length
         .merge_from_option(modeline.preferred_line_length.map(u32::from).as_ref());
+    settings.soft_wrap.merge_from_option(soft_wrap.as_ref());
+    settings.line_ending.merge_from_option(line_ending.as_ref());
     let auto_indent_mode = modeline.auto_indent.map(|enabled| {
         if enabled {
             AutoIndentMode::SyntaxAware
@@ -1069,6 +1090,55 @@ mod tests {
         assert!(!settings.enabled_for_file(&home_file, &cx));
     }
 
+    #[gpui::test]
+    fn test_merge_with_modeline(cx: &mut App) {
+        cx.set_global(SettingsStore::test(cx));
+        let defaults = AllLanguageSettings::get_global(cx)
+            .language(None, None, cx)
+            .into_owned();
+        let merged = |modeline: ModelineSettings| {
+            let mut settings = defaults.clone();
+            merge_with_modeline(&mut settings, &modeline);
+            settings
+        };
+
+        // `vim: set sw=2 ts=8 et tw=100 wrap ff=dos:`
+        let settings = merged(ModelineSettings {
+            indent_size: NonZeroU32::new(2),
+            tab_size: NonZeroU32::new(8),
+            hard_tabs: Some(false),
+            preferred_line_length: NonZeroU32::new(100),
+            soft_wrap: Some(true),
+            line_ending: Some(LineEnding::Windows),
+            ..Default::default()
+        });
+        assert_eq!(settings.tab_size, NonZeroU32::new(2).unwrap());
+        assert!(!settings.hard_tabs);
+        assert_eq!(settings.preferred_line_length, 100);
+        assert_eq!(settings.soft_wrap, SoftWrap::Bounded);
+        assert_eq!(settings.line_ending, LineEndingSetting::EnforceCrlf);
+
+        // `vim: set sw=4 ts=8 noet nowrap:`
+        let settings = merged(ModelineSettings {
+            indent_size: NonZeroU32::new(4),
+            tab_size: NonZeroU32::new(8),
+            hard_tabs: Some(true),
+            soft_wrap: Some(false),
+            ..Default::default()
+        });
+        assert_eq!(settings.tab_size, NonZeroU32::new(8).unwrap());
+        assert!(settings.hard_tabs);
+        assert_eq!(settings.soft_wrap, SoftWrap::None);
+        assert_eq!(settings.line_ending, defaults.line_ending);
+
+        let settings = merged(ModelineSettings {
+            soft_wrap: Some(true),
+            ..Default::default()
+        });
+        assert_eq!(settings.soft_wrap, SoftWrap::EditorWidth);
+        assert_eq!(settings.tab_size, defaults.tab_size);
+    }
+
     #[test]
     fn test_resolve_language_servers() {
         fn language_server_names(names: &[&str]) -> Vec<LanguageServerName> {
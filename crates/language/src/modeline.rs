@@ -1,5 +1,7 @@
+use encoding_rs::Encoding;
 use regex::Regex;
 use std::{num::NonZeroU32, sync::LazyLock};
+use text::LineEnding;
 
 /// The settings extracted from an emacs/vim modelines.
 ///
@@ -27,6 +29,12 @@ pub struct ModelineSettings {
     pub ensure_final_newline: Option<bool>,
     /// Whether to show trailing whitespace on the editor.
     pub show_trailing_whitespace: Option<bool>,
+    /// Whether to soft-wrap long lines.
+    pub soft_wrap: Option<bool>,
+    /// The character encoding of the file.
+    pub encoding: Option<&'static Encoding>,
+    /// The line ending to use when saving the file.
+    pub line_ending: Option<LineEnding>,
 
     /// Emacs modeline variables that were parsed but not mapped to Zed settings.
     /// Stored as (variable-name, value) pairs.
@@ -162,7 +170,13 @@ fn parse_emacs_key_value(part: &str, settings: &mut ModelineSettings, bare: bool
             "mode" => {
                 settings.mode = Some(value.to_string());
             }
-            "c-basic-offset" | "python-indent-offset" => {
+            "c-basic-offset"
+            | "python-indent-offset"
+            | "js-indent-level"
+            | "sh-basic-offset"
+            | "css-indent-offset"
+            | "rust-indent-offset"
+            | "standard-indent" => {
                 if let Ok(size) = value.parse::<NonZeroU32>() {
                     settings.indent_size = Some(size);
                 }
@@ -189,6 +203,12 @@ fn parse_emacs_key_value(part: &str, settings: &mut ModelineSettings, bare: bool
             "show-trailing-whitespace" => {
                 settings.show_trailing_whitespace = Some(value != "nil");
             }
+            "truncate-lines" => {
+                settings.soft_wrap = Some(value == "nil");
+            }
+            "coding" | "buffer-file-coding-system" => {
+                parse_emacs_coding_system(value, settings);
+            }
             key => settings
                 .emacs_extra_variables
                 .push((key.to_string(), value.to_string())),
@@ -199,6 +219,36 @@ fn parse_emacs_key_value(part: &str, settings: &mut ModelineSettings, bare: bool
     }
 }
 
+/// Parse an Emacs coding system, like `utf-8`, `latin-1` or `utf-8-unix`.
+///
+/// The optional suffix selects the line ending, and `undecided` leaves the
+/// encoding to be detected.
+fn parse_emacs_coding_system(value: &str, settings: &mut ModelineSettings) {
+    let value = value.to_lowercase();
+    let mut name = value.as_str();
+    for (suffix, line_ending) in [
+        ("-unix", Some(LineEnding::Unix)),
+        ("-dos", Some(LineEnding::Windows)),
+        ("-mac", None),
+    ] {
+        if let Some(stripped) = name.strip_suffix(suffix) {
+            name = stripped;
+            settings.line_ending = line_ending;
+            break;
+        }
+    }
+    if name != "undecided" {
+        settings.encoding = encoding_for_label(name);
+    }
+}
+
+/// Looks up an encoding by a name used in Vim or Emacs. Emacs names like
+/// `latin-1` are looked up with their dashes removed when they aren't known.
+fn encoding_for_label(name: &str) -> Option<&'static Encoding> {
+    Encoding::for_label(name.as_bytes())
+        .or_else(|| Encoding::for_label(name.replace('-', "").as_bytes()))
+}
+
 fn parse_vim_modelines(modelines: &[&str], settings: &mut ModelineSettings) {
     for line in modelines {
         parse_vim_modeline(line, settings);
@@ -286,6 +336,15 @@ fn parse_vim_settings(content: &str, settings: &mut ModelineSettings) {
                             settings.preferred_line_length = Some(size);
                         }
                     }
+                    "fenc" | "fileencoding" if encoding_for_label(value).is_some() => {
+                        settings.encoding = encoding_for_label(value);
+                    }
+                    "ff" | "fileformat" if value == "unix" => {
+                        settings.line_ending = Some(LineEnding::Unix);
+                    }
+                    "ff" | "fileformat" if value == "dos" => {
+                        settings.line_ending = Some(LineEnding::Windows);
+                    }
                     _ => {
                         settings
                             .vim_extra_variables
@@ -306,12 +365,18 @@ fn parse_vim_settings(content: &str, settings: &mut ModelineSettings) {
                     "noet" | "noexpandtab" => {
                         settings.hard_tabs = Some(true);
                     }
-                    "eol" | "endofline" => {
+                    "eol" | "endofline" | "fixeol" | "fixendofline" => {
                         settings.ensure_final_newline = Some(true);
                     }
-                    "noeol" | "noendofline" => {
+                    "noeol" | "noendofline" | "nofixeol" | "nofixendofline" => {
                         settings.ensure_final_newline = Some(false);
                     }
+                    "wrap" => {
+                        settings.soft_wrap = Some(true);
+                    }
+                    "nowrap" => {
+                        settings.soft_wrap = Some(false);
+                    }
                     "set" => {
                         // Ignore the "set" keyword itself
                     }
@@ -566,14 +631,15 @@ mod tests {
         );
 
         // Test mixed boolean flags
-        let content = "vim: set wrap noet ts=8:";
+        let content = "vim: set wrap noet ts=8 cindent:";
         let settings = parse_modeline(&[content], &[]).unwrap();
         assert_eq!(
             settings,
             ModelineSettings {
                 tab_size: Some(NonZeroU32::new(8).unwrap()),
                 hard_tabs: Some(true),
-                vim_extra_variables: vec![("wrap".to_string(), None)],
+                soft_wrap: Some(true),
+                vim_extra_variables: vec![("cindent".to_string(), None)],
                 ..Default::default()
             }
         );
@@ -756,6 +822,83 @@ mod tests {
         assert_eq!(settings.hard_tabs, Some(false));
     }
 
+    #[test]
+    fn test_vim_modeline_file_options() {
+        let content = "# vim: set ft=python tw=100 et sw=2 fenc=latin1 ff=dos nowrap:";
+        let settings = parse_modeline(&[content], &[]).unwrap();
+        assert_eq!(
+            settings,
+            ModelineSettings {
+                mode: Some("python".to_string()),
+                preferred_line_length: Some(NonZeroU32::new(100).unwrap()),
+                hard_tabs: Some(false),
+                indent_size: Some(NonZeroU32::new(2).unwrap()),
+                encoding: Some(encoding_rs::WINDOWS_1252),
+                line_ending: Some(LineEnding::Windows),
+                soft_wrap: Some(false),
+                ..Default::default()
+            }
+        );
+
+        // Unknown encodings and file formats are kept as extra variables
+        let content = "vim: set fenc=klingon ff=mac:";
+        let settings = parse_modeline(&[content], &[]).unwrap();
+        assert_eq!(
+            settings.vim_extra_variables,
+            vec![
+                ("fenc".to_string(), Some("klingon".to_string())),
+                ("ff".to_string(), Some("mac".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_emacs_file_variables() {
+        let content =
+            "// -*- mode: c++; fill-column: 80; coding: utf-8-unix; truncate-lines: t -*-";
+        let settings = parse_modeline(&[content], &[]).unwrap();
+        assert_eq!(
+            settings,
+            ModelineSettings {
+                mode: Some("c++".to_string()),
+                preferred_line_length: Some(NonZeroU32::new(80).unwrap()),
+                encoding: Some(encoding_rs::UTF_8),
+                line_ending: Some(LineEnding::Unix),
+                soft_wrap: Some(false),
+                ..Default::default()
+            }
+        );
+
+        let content = indoc! {"
+            ;; Local Variables:
+            ;; coding: latin-1-dos
+            ;; js-indent-level: 2
+            ;; End:
+        "}
+        .lines()
+        .collect::<Vec<_>>();
+        let settings = parse_modeline(&[], &content).unwrap();
+        assert_eq!(
+            settings,
+            ModelineSettings {
+                indent_size: Some(NonZeroU32::new(2).unwrap()),
+                encoding: Some(encoding_rs::WINDOWS_1252),
+                line_ending: Some(LineEnding::Windows),
+                ..Default::default()
+            }
+        );
+
+        let content = "# -*- coding: undecided-dos -*-";
+        let settings = parse_modeline(&[content], &[]).unwrap();
+        assert_eq!(
+            settings,
+            ModelineSettings {
+                line_ending: Some(LineEnding::Windows),
+                ..Default::default()
+            }
+        );
+    }
+
     #[test]
     fn test_combined_emacs_vim_detection() {
         // Test that both emacs and vim modelines can be detected in the same file
@@ -72,6 +72,8 @@ pub struct OutlineConfig {
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DebuggerTextObject {
     Variable,
+    /// A member access chain, such as `config.port` or `ptr->len`, that is evaluated as a whole.
+    Expression,
     Scope,
 }
 
@@ -79,6 +81,7 @@ impl DebuggerTextObject {
     pub fn from_capture_name(name: &str) -> Option<DebuggerTextObject> {
         match name {
             "debug-variable" => Some(DebuggerTextObject::Variable),
+            "debug-expression" => Some(DebuggerTextObject::Expression),
             "debug-scope" => Some(DebuggerTextObject::Scope),
             _ => None,
         }
@@ -228,38 +228,6 @@ fn parse_mermaid_info(info: &str) -> Option<u32> {
     )
 }
 
-/// We deliberately block rendering of some diagram types, even though `merman`
-/// supports them, because we have not yet written custom CSS to ensure text is
-/// readable.
-fn is_supported_diagram_type(source: &str) -> bool {
-    /// If updating this list, also update the system prompt!
-    const SUPPORTED_PREFIXES: &[&str] = &[
-        "flowchart",
-        "graph",
-        "sequenceDiagram",
-        "classDiagram",
-        "stateDiagram",
-        "stateDiagram-v2",
-        "erDiagram",
-        "gantt",
-        "pie",
-        "gitGraph",
-        "mindmap",
-        "timeline",
-        "quadrantChart",
-        "xychart-beta",
-        "journey",
-    ];
-    let first_token = source
-        .trim_start()
-        .split(|c: char| c.is_whitespace() || c == '\n')
-        .next()
-        .unwrap_or("");
-    SUPPORTED_PREFIXES
-        .iter()
-        .any(|prefix| first_token.eq_ignore_ascii_case(prefix))
-}
-
 pub(crate) fn extract_mermaid_diagrams(
     source: &str,
     events: &[(Range<usize>, MarkdownEvent)],
@@ -292,9 +260,6 @@ pub(crate) fn extract_mermaid_diagrams(
             .strip_suffix('\n')
             .unwrap_or(&source[metadata.content_range.clone()])
             .to_string();
-        if !is_supported_diagram_type(&contents) {
-            continue;
-        }
         mermaid_diagrams.insert(
             source_range.start,
             ParsedMarkdownMermaidDiagram {
@@ -363,9 +328,10 @@ pub(crate) fn render_mermaid_diagram(
                 })
                 .into_any_element()
         }
-        Some(Err(_)) => {
-            // Render failed — show the source code without tabs
+        Some(Err(error)) => {
+            // Render failed — show where the source went wrong, then the code without tabs
             container
+                .child(render_mermaid_error(error))
                 .child(render_mermaid_code_view(&parsed.contents.contents))
                 .when(show_interactive, |container| {
                     container.child(render_mermaid_copy_button(
@@ -519,6 +485,28 @@ fn render_mermaid_copy_button(
     )
 }
 
+fn render_mermaid_error(error: &anyhow::Error) -> impl IntoElement {
+    let message = match error.downcast_ref::<mermaid_render::MermaidError>() {
+        Some(error) => error.to_string(),
+        None => format!("Failed to render mermaid diagram: {error}"),
+    };
+
+    h_flex()
+        .items_start()
+        .gap_1p5()
+        .mb_2()
+        .child(
+            Icon::new(IconName::Warning)
+                .size(IconSize::Small)
+                .color(Color::Error),
+        )
+        .child(
+            Label::new(message)
+                .size(LabelSize::Small)
+                .color(Color::Error),
+        )
+}
+
 fn render_mermaid_code_view(contents: &SharedString) -> AnyElement {
     div()
         .w_full()
@@ -674,7 +662,9 @@ mod tests {
     }
 
     #[test]
-    fn test_unsupported_diagram_types_are_skipped() {
+    fn test_unsupported_diagram_types_are_extracted() {
+        // Unsupported diagrams are still extracted, so that the preview can
+        // explain why they aren't rendered instead of showing nothing.
         let markdown = concat!(
             "```mermaid\nsankey-beta\n```\n\n",
             "```mermaid\nblock-beta\n```\n\n",
@@ -683,15 +673,13 @@ mod tests {
         let events =
             crate::parser::parse_markdown_with_options(markdown, false, false, false).events;
         let diagrams = extract_mermaid_diagrams(markdown, &events);
+        let contents = diagrams
+            .values()
+            .map(|diagram| diagram.contents.contents.as_ref())
+            .collect::<Vec<_>>();
         assert_eq!(
-            diagrams.len(),
-            1,
-            "Only the flowchart should be extracted; sankey and block should be skipped"
-        );
-        let diagram = diagrams.values().next().unwrap();
-        assert!(
-            diagram.contents.contents.contains("flowchart"),
-            "The extracted diagram should be the flowchart"
+            contents,
+            ["sankey-beta", "block-beta", "flowchart TD\n    A --> B"]
         );
     }
 
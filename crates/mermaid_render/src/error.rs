@@ -0,0 +1,131 @@
+//! Structured errors for diagrams that can't be rendered, so that previews can
+//! point at the offending part of the source instead of showing nothing.
+
+use std::fmt;
+
+/// 1-based position in the diagram source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourcePosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MermaidErrorKind {
+    /// The diagram type is unknown, or deliberately not rendered by Zed.
+    UnsupportedDiagram,
+    /// The diagram source is malformed.
+    Syntax,
+    /// The diagram parsed, but rendering it failed.
+    Render,
+}
+
+/// Error returned (wrapped in [`anyhow::Error`]) by [`render_to_svg`](crate::render_to_svg).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MermaidError {
+    pub kind: MermaidErrorKind,
+    pub message: String,
+    pub position: Option<SourcePosition>,
+}
+
+impl MermaidError {
+    pub(crate) fn syntax(message: impl Into<String>, line: usize, column: usize) -> Self {
+        Self {
+            kind: MermaidErrorKind::Syntax,
+            message: message.into(),
+            position: Some(SourcePosition { line, column }),
+        }
+    }
+
+    pub(crate) fn unsupported(diagram_type: &str, line: usize, column: usize) -> Self {
+        Self {
+            kind: MermaidErrorKind::UnsupportedDiagram,
+            message: format!("Unsupported diagram type `{diagram_type}`"),
+            position: Some(SourcePosition { line, column }),
+        }
+    }
+
+    /// Converts an error from merman, recovering the position from its message
+    /// where possible. Merman mirrors mermaid's parser errors, which look like
+    /// `Parse error on line 3: ...`.
+    pub(crate) fn from_render_error(source: &str, error: &anyhow::Error) -> Self {
+        let message = format!("{error:#}");
+        let position = find_number_after(&message, "line ").map(|line| {
+            let column = find_number_after(&message, "column ").unwrap_or_else(|| {
+                // Point at the first non-blank character of the line
+                source
+                    .lines()
+                    .nth(line.saturating_sub(1))
+                    .and_then(|text| text.chars().position(|c| !c.is_whitespace()))
+                    .map_or(1, |index| index + 1)
+            });
+            SourcePosition { line, column }
+        });
+
+        Self {
+            kind: if position.is_some() {
+                MermaidErrorKind::Syntax
+            } else {
+                MermaidErrorKind::Render
+            },
+            message,
+            position,
+        }
+    }
+}
+
+/// Finds the first number directly following `prefix` (case-insensitive) in `text`.
+fn find_number_after(text: &str, prefix: &str) -> Option<usize> {
+    let lowercase = text.to_ascii_lowercase();
+    lowercase.match_indices(prefix).find_map(|(index, _)| {
+        let rest = &lowercase[index + prefix.len()..];
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(rest, |end| &rest[..end]);
+        digits.parse().ok().filter(|&number| number > 0)
+    })
+}
+
+impl fmt::Display for MermaidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some(SourcePosition { line, column }) => {
+                write!(f, "Line {line}, column {column}: {}", self.message)
+            }
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for MermaidError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_error_position_is_recovered_from_message() {
+        let source = "sequenceDiagram\n    Alice->>Bob: Hello\n    Alice-Bob";
+        let error = anyhow::anyhow!(
+            "Parse error on line 3:\n...Hello    Alice-Bob\n---------------^\nExpecting 'SOLID_ARROW'"
+        );
+        let error = MermaidError::from_render_error(source, &error);
+        assert_eq!(error.kind, MermaidErrorKind::Syntax);
+        assert_eq!(error.position, Some(SourcePosition { line: 3, column: 5 }));
+
+        let error = anyhow::anyhow!("unexpected token at line 2, column 7");
+        let error = MermaidError::from_render_error(source, &error);
+        assert_eq!(error.position, Some(SourcePosition { line: 2, column: 7 }));
+
+        let error = anyhow::anyhow!("containment cycle detected");
+        let error = MermaidError::from_render_error(source, &error);
+        assert_eq!(error.kind, MermaidErrorKind::Render);
+        assert_eq!(error.position, None);
+    }
+
+    #[test]
+    fn display_includes_position() {
+        let error = MermaidError::syntax("Unexpected `end`", 4, 5);
+        assert_eq!(error.to_string(), "Line 4, column 5: Unexpected `end`");
+    }
+}
@@ -26,7 +26,9 @@
 //! editor-specific theme and accent color rules in this crate. The [`gpui`]
 //! dependency is only needed for the [`Hsla`] and [`Rgba`] color types.
 //!
-//! The [`render_to_svg`] function operates in two stages:
+//! The [`render_to_svg`] function operates in three stages:
+//! - [`validate`] the mermaid text, so malformed diagrams produce a
+//!   [`MermaidError`] pointing at the offending line and column.
 //! - [`render`] the mermaid text to raster-safe SVG using [`merman`].
 //! - [`postprocess`] the SVG to add Zed theme and accent styling.
 //!
@@ -63,12 +65,16 @@
 //! 3. `postprocess::inject_css` injects CSS rules for the classes applied by
 //!    `accent_colors`
 
+mod error;
 mod postprocess;
 mod render;
+mod validate;
 
 use anyhow::Result;
 use gpui::{Hsla, Rgba};
 
+pub use error::{MermaidError, MermaidErrorKind, SourcePosition};
+
 #[derive(Debug, Clone, Copy)]
 pub struct AccentColor {
     pub foreground: Hsla,
@@ -175,8 +181,13 @@ pub(crate) fn css_color(color: Hsla) -> String {
 pub use postprocess::util::text_color_for_background;
 
 /// See the [module-level docs][crate] for more info.
+///
+/// Errors caused by the diagram source are returned as a [`MermaidError`],
+/// which can be recovered with [`anyhow::Error::downcast_ref`].
 pub fn render_to_svg(source: &str, theme: &MermaidTheme) -> Result<String> {
-    let svg = render::render_mermaid(source, theme)?;
+    validate::validate(source)?;
+    let svg = render::render_mermaid(source, theme)
+        .map_err(|error| MermaidError::from_render_error(source, &error))?;
     let svg = postprocess::postprocess(&svg, theme)?;
     Ok(svg)
 }
@@ -200,4 +211,17 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn malformed_diagrams_report_their_position() {
+        let source = "sequenceDiagram\n    loop Every minute\n        Alice->>Bob: Ping\n";
+        let error = render_to_svg(source, &MermaidTheme::default()).unwrap_err();
+        let error = error.downcast_ref::<MermaidError>().unwrap();
+        assert_eq!(error.kind, MermaidErrorKind::Syntax);
+        assert_eq!(error.position, Some(SourcePosition { line: 2, column: 5 }));
+
+        let error = render_to_svg("sankey-beta\n  a,b,1", &MermaidTheme::default()).unwrap_err();
+        let error = error.downcast_ref::<MermaidError>().unwrap();
+        assert_eq!(error.kind, MermaidErrorKind::UnsupportedDiagram);
+    }
 }
@@ -337,6 +337,7 @@ fn build_injected_css(theme: &MermaidTheme, svg_id: &str) -> String {
     let text = crate::css_color(theme.text_color);
     let line = crate::css_color(theme.line_color);
     let primary = crate::css_color(theme.primary_color);
+    let primary_text = crate::css_color(theme.primary_text_color);
     let border = crate::css_color(theme.primary_border_color);
     let secondary = crate::css_color(theme.secondary_color);
     let tertiary = crate::css_color(theme.tertiary_color);
@@ -447,6 +448,14 @@ fn build_injected_css(theme: &MermaidTheme, svg_id: &str) -> String {
         .node .row-rect-even path {{ fill: {er_even} !important; }}
         .edge-thickness-normal {{ stroke-width: 1px; }}
         .relation {{ stroke: {line}; stroke-width: 1; fill: none; }}
+        g.classGroup rect {{ fill: {primary} !important; stroke: {border} !important; }}
+        g.classGroup text {{ fill: {primary_text} !important; }}
+        g.classGroup line, .divider {{ stroke: {border} !important; }}
+        .classTitle {{ fill: {primary_text} !important; }}
+        .classLabel .box {{ fill: {edge_label_bg} !important; stroke: none !important; }}
+        .classLabel .label {{ fill: {text} !important; }}
+        .dashed-line {{ stroke: {line} !important; stroke-dasharray: 3; }}
+        .cardinality text {{ fill: {text} !important; }}
         .edgePaths path {{ fill: none; }}
         .marker {{ fill: {line} !important; stroke: {line} !important; }}
         .marker.er {{ fill: none !important; stroke: {line} !important; }}
@@ -472,6 +481,8 @@ fn build_injected_css(theme: &MermaidTheme, svg_id: &str) -> String {
         .titleText {{ fill: {text} !important; font-family: {font} !important; }}
         .grid .tick text {{ fill: {text} !important; font-family: {font} !important; }}
         .grid .tick {{ stroke: {border} !important; }}
+        .today {{ stroke: {warning} !important; fill: none !important; }}
+        .exclude-range {{ fill: {secondary} !important; }}
         {git_branch_css}
         .commit-merge {{ stroke: {primary}; fill: {primary}; }}
         .commit-reverse {{ stroke: {primary}; fill: {primary}; stroke-width: 3; }}
@@ -506,4 +517,17 @@ mod tests {
         let result = scope_css(input, "my-svg");
         assert!(result.contains("#my-svg .foo"), "got: {result}");
     }
+
+    #[test]
+    fn class_diagrams_are_themed() {
+        let theme = MermaidTheme::default();
+        let css = build_injected_css(&theme, "my-svg");
+        let primary = crate::css_color(theme.primary_color);
+        assert!(
+            css.contains(&format!(
+                "#my-svg g.classGroup rect{{ fill: {primary} !important;"
+            )),
+            "got: {css}"
+        );
+    }
 }
@@ -52,6 +52,8 @@ fn to_merman_config(theme: &MermaidTheme) -> merman::MermaidConfig {
     let er_even = css_color(theme.er_attr_bg_even);
     let git: [String; 8] = theme.git_branch_colors.map(css_color);
     let git_lbl: [String; 8] = theme.git_branch_label_colors.map(css_color);
+    let error = css_color(theme.error_color);
+    let warning = css_color(theme.warning_color);
 
     let mut theme_vars = serde_json::json!({
         "primaryColor": primary,
@@ -84,7 +86,6 @@ fn to_merman_config(theme: &MermaidTheme) -> merman::MermaidConfig {
         "signalTextColor": text,
         "activationBkgColor": activation_bg,
         "activationBorderColor": activation_border,
-        "classText": text,
         "labelColor": primary_text,
         "attributeBackgroundColorOdd": er_odd,
         "attributeBackgroundColorEven": er_even,
@@ -108,6 +109,44 @@ fn to_merman_config(theme: &MermaidTheme) -> merman::MermaidConfig {
         "quadrantYAxisTextFill": text,
         "quadrantExternalBorderStrokeFill": primary_border,
         "quadrantInternalBorderStrokeFill": primary_border,
+        // Sequence diagrams
+        "sequenceNumberColor": background,
+        "labelBoxBkgColor": actor_bg,
+        "labelBoxBorderColor": actor_border,
+        // State diagrams
+        "stateBkg": primary,
+        "stateLabelColor": primary_text,
+        "labelBackgroundColor": edge_label_bg,
+        "altBackground": secondary,
+        "compositeBackground": cluster_bg,
+        "compositeTitleBackground": cluster_bg,
+        "compositeBorder": cluster_border,
+        "transitionColor": line,
+        "transitionLabelColor": text,
+        "specialStateColor": line,
+        "innerEndBackground": primary_border,
+        // Class diagrams. Class boxes and relations otherwise use `mainBkg`, `nodeBorder` and
+        // `lineColor`, with namespaces drawn as clusters.
+        "classText": text,
+        // Gantt charts
+        "sectionBkgColor": cluster_bg,
+        "altSectionBkgColor": background,
+        "sectionBkgColor2": secondary,
+        "gridColor": cluster_border,
+        "taskBkgColor": primary,
+        "taskBorderColor": primary_border,
+        "taskTextColor": primary_text,
+        "taskTextLightColor": primary_text,
+        "taskTextOutsideColor": text,
+        "taskTextDarkColor": primary_text,
+        "activeTaskBkgColor": secondary,
+        "activeTaskBorderColor": primary_border,
+        "doneTaskBkgColor": secondary,
+        "doneTaskBorderColor": primary_border,
+        "critBkgColor": error,
+        "critBorderColor": error,
+        "todayLineColor": warning,
+        "excludeBkgColor": secondary,
     });
 
     if let Some(map) = theme_vars.as_object_mut() {
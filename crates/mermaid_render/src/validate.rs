@@ -0,0 +1,196 @@
+//! Checks diagram sources before handing them to [`merman`].
+//!
+//! This detects the diagram type, rejecting the ones Zed doesn't render, and
+//! catches common structural mistakes in the block-based diagram families
+//! (unclosed `loop`/`alt` blocks in sequence diagrams, unbalanced braces in
+//! state and class diagrams, gantt tasks without a `:`). Merman's own errors
+//! often lack a usable position for these, so we report them with the exact
+//! line and column.
+//!
+//! The checks are intentionally conservative: anything they don't understand
+//! is left for merman to accept or reject.
+
+mod class_diagram;
+mod gantt;
+mod sequence_diagram;
+mod state_diagram;
+
+use crate::error::MermaidError;
+
+/// Diagram types that Zed renders.
+///
+/// We deliberately block rendering of some diagram types, even though `merman`
+/// supports them, because we have not yet written custom CSS to ensure text is
+/// readable.
+///
+/// If updating this list, also update the system prompt!
+pub(crate) const SUPPORTED_DIAGRAM_TYPES: &[&str] = &[
+    "flowchart",
+    "graph",
+    "sequenceDiagram",
+    "classDiagram",
+    "stateDiagram",
+    "stateDiagram-v2",
+    "erDiagram",
+    "gantt",
+    "pie",
+    "gitGraph",
+    "mindmap",
+    "timeline",
+    "quadrantChart",
+    "xychart-beta",
+    "journey",
+];
+
+/// A significant line of the diagram source.
+pub(crate) struct SourceLine<'a> {
+    /// 1-based line number
+    pub number: usize,
+    /// 1-based column of the first non-blank character
+    pub indent: usize,
+    /// Line content without surrounding whitespace and trailing `;`
+    pub text: &'a str,
+}
+
+impl SourceLine<'_> {
+    /// First whitespace-separated word of the line.
+    pub fn keyword(&self) -> &str {
+        self.text.split_whitespace().next().unwrap_or("")
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> MermaidError {
+        MermaidError::syntax(message, self.number, self.indent)
+    }
+
+    /// Error pointing at the end of the line.
+    pub fn error_at_end(&self, message: impl Into<String>) -> MermaidError {
+        MermaidError::syntax(
+            message,
+            self.number,
+            self.indent + self.text.chars().count(),
+        )
+    }
+}
+
+/// Lines carrying diagram statements: front matter, `%%` comments, directives
+/// and multi-line accessibility descriptions are skipped.
+fn significant_lines(source: &str) -> Vec<SourceLine<'_>> {
+    let mut lines = Vec::new();
+    let mut in_front_matter = false;
+    let mut in_acc_descr = false;
+
+    for (index, line) in source.lines().enumerate() {
+        let trimmed = line.trim();
+        if index == 0 && trimmed == "---" {
+            in_front_matter = true;
+            continue;
+        }
+        if in_front_matter {
+            in_front_matter = trimmed != "---";
+            continue;
+        }
+        if in_acc_descr {
+            in_acc_descr = !trimmed.contains('}');
+            continue;
+        }
+        if trimmed.is_empty() || trimmed.starts_with("%%") {
+            continue;
+        }
+        if trimmed.starts_with("accDescr") && trimmed.contains('{') && !trimmed.contains('}') {
+            in_acc_descr = true;
+            continue;
+        }
+
+        let indent = line.chars().take_while(|c| c.is_whitespace()).count() + 1;
+        lines.push(SourceLine {
+            number: index + 1,
+            indent,
+            text: trimmed.trim_end_matches(';').trim_end(),
+        });
+    }
+    lines
+}
+
+pub(crate) fn validate(source: &str) -> Result<(), MermaidError> {
+    let lines = significant_lines(source);
+    let Some((header, body)) = lines.split_first() else {
+        return Err(MermaidError::syntax("Diagram is empty", 1, 1));
+    };
+
+    let diagram_type = header.keyword();
+    let Some(&diagram_type) = SUPPORTED_DIAGRAM_TYPES
+        .iter()
+        .find(|supported| supported.eq_ignore_ascii_case(diagram_type))
+    else {
+        return Err(MermaidError::unsupported(
+            diagram_type,
+            header.number,
+            header.indent,
+        ));
+    };
+
+    match diagram_type {
+        "sequenceDiagram" => sequence_diagram::validate(body),
+        "stateDiagram" | "stateDiagram-v2" => state_diagram::validate(body),
+        "classDiagram" => class_diagram::validate(body),
+        "gantt" => gantt::validate(body),
+        _ => Ok(()),
+    }
+}
+
+/// Checks that blocks opened by lines ending with `{` are closed by `}` lines.
+/// Shared by the diagram types using braces for nesting.
+pub(crate) fn validate_braces<'a, 'b>(
+    lines: &'a [SourceLine<'b>],
+    mut skip_line: impl FnMut(&'a SourceLine<'b>) -> Result<bool, MermaidError>,
+) -> Result<(), MermaidError> {
+    let mut open_blocks = Vec::new();
+    for line in lines {
+        if skip_line(line)? {
+            continue;
+        }
+        if line.text.ends_with('{') {
+            open_blocks.push(line);
+        } else if line.text == "}" && open_blocks.pop().is_none() {
+            return Err(line.error("Unexpected `}` without a matching `{`"));
+        }
+    }
+
+    match open_blocks.pop() {
+        Some(line) => Err(line.error("`{` is never closed with `}`")),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{MermaidErrorKind, SourcePosition};
+
+    pub(super) fn error_position(source: &str) -> Option<(usize, usize)> {
+        validate(source)
+            .err()
+            .and_then(|error| error.position)
+            .map(|SourcePosition { line, column }| (line, column))
+    }
+
+    #[test]
+    fn unsupported_diagram_type_is_reported() {
+        let error = validate("%% comment\n  sankey-beta\n  a,b,1").unwrap_err();
+        assert_eq!(error.kind, MermaidErrorKind::UnsupportedDiagram);
+        assert_eq!(error.message, "Unsupported diagram type `sankey-beta`");
+        assert_eq!(error.position, Some(SourcePosition { line: 2, column: 3 }));
+    }
+
+    #[test]
+    fn front_matter_and_directives_are_skipped() {
+        let source =
+            "---\ntitle: Example\n---\n%%{init: {\"theme\": \"dark\"}}%%\ngraph TD\n  A --> B";
+        assert_eq!(validate(source), Ok(()));
+    }
+
+    #[test]
+    fn empty_diagram_is_reported() {
+        assert_eq!(error_position("  \n%% nothing\n"), Some((1, 1)));
+    }
+}
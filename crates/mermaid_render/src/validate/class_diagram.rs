@@ -0,0 +1,43 @@
+//! Class diagrams declare members in `class X { ... }` bodies, which may be
+//! nested in `namespace X { ... }` blocks.
+
+use crate::{
+    error::MermaidError,
+    validate::{SourceLine, validate_braces},
+};
+
+pub(super) fn validate(lines: &[SourceLine<'_>]) -> Result<(), MermaidError> {
+    validate_braces(lines, |_| Ok(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validate::{tests::error_position, validate};
+
+    #[test]
+    fn valid_class_diagram() {
+        let source = "classDiagram
+    namespace Shapes {
+        class Shape {
+            <<interface>>
+            +area() double
+        }
+        class Square
+    }
+    class Animal { +String name }
+    class Shelter {
+        -List~Animal~ animals
+    }
+    Shape <|-- Square
+    Shelter o-- Animal";
+        assert_eq!(validate(source), Ok(()));
+    }
+
+    #[test]
+    fn unclosed_class_body() {
+        assert_eq!(
+            error_position("classDiagram\n  class Foo {\n    +bar() void\n  class Baz {\n  }"),
+            Some((2, 3))
+        );
+    }
+}
@@ -0,0 +1,68 @@
+//! Gantt charts consist of settings, sections and tasks. Every task needs a `:`
+//! separating its name from its metadata.
+
+use crate::{error::MermaidError, validate::SourceLine};
+
+const KEYWORDS: &[&str] = &[
+    "title",
+    "dateFormat",
+    "axisFormat",
+    "tickInterval",
+    "excludes",
+    "includes",
+    "todayMarker",
+    "weekday",
+    "weekend",
+    "inclusiveEndDates",
+    "topAxis",
+    "displayMode",
+    "section",
+    "click",
+    "accTitle",
+    "accDescr",
+];
+
+pub(super) fn validate(lines: &[SourceLine<'_>]) -> Result<(), MermaidError> {
+    for line in lines {
+        let keyword = line.keyword().trim_end_matches(':');
+        if KEYWORDS.contains(&keyword) {
+            continue;
+        }
+        if !line.text.contains(':') {
+            return Err(line.error_at_end(format!(
+                "Expected `:` followed by the task data after `{}`",
+                line.text
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validate::{tests::error_position, validate};
+
+    #[test]
+    fn valid_gantt_chart() {
+        let source = "gantt
+    title A Gantt Diagram
+    dateFormat YYYY-MM-DD
+    excludes weekends
+    accTitle: Plan
+    section Section
+        A task          :a1, 2014-01-01, 30d
+        Another task    :after a1, 20d
+    section Another
+        Task in Another :2014-01-12, 12d
+        Milestone       :milestone, m1, 2014-01-20, 0d";
+        assert_eq!(validate(source), Ok(()));
+    }
+
+    #[test]
+    fn task_without_data() {
+        assert_eq!(
+            error_position("gantt\n    section S\n        Write docs\n"),
+            Some((3, 19))
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! Sequence diagrams nest `loop`, `alt`, `par` etc. blocks, each closed by `end`.
+
+use crate::{error::MermaidError, validate::SourceLine};
+
+const BLOCK_KEYWORDS: &[&str] = &[
+    "loop", "alt", "opt", "par", "par_over", "critical", "break", "rect", "box",
+];
+
+pub(super) fn validate(lines: &[SourceLine<'_>]) -> Result<(), MermaidError> {
+    let mut open_blocks: Vec<(&str, &SourceLine<'_>)> = Vec::new();
+
+    for line in lines {
+        let keyword = line.keyword();
+        if let Some(&block) = BLOCK_KEYWORDS.iter().find(|block| **block == keyword) {
+            open_blocks.push((block, line));
+            continue;
+        }
+
+        let current_block = open_blocks.last().map(|(block, _)| *block);
+        match keyword {
+            "end" => {
+                let Some(_) = open_blocks.pop() else {
+                    return Err(line.error("Unexpected `end` without an open block"));
+                };
+            }
+            "else" if current_block != Some("alt") => {
+                return Err(line.error("`else` is only allowed inside an `alt` block"));
+            }
+            "and" if !matches!(current_block, Some("par" | "par_over")) => {
+                return Err(line.error("`and` is only allowed inside a `par` block"));
+            }
+            "option" if current_block != Some("critical") => {
+                return Err(line.error("`option` is only allowed inside a `critical` block"));
+            }
+            _ => {}
+        }
+    }
+
+    match open_blocks.pop() {
+        Some((block, line)) => {
+            Err(line.error(format!("`{block}` block is never closed with `end`")))
+        }
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validate::{tests::error_position, validate};
+
+    #[test]
+    fn valid_sequence_diagram() {
+        let source = "sequenceDiagram
+    autonumber
+    participant Alice
+    box Purple Backend
+        participant Bob
+    end
+    loop Every minute
+        Alice->>Bob: Ping
+        alt is sick
+            Bob-->>Alice: Not so good
+        else is well
+            Bob-->>Alice: Fine
+        end
+    end
+    par Alice to Bob
+        Alice->>Bob: Hello
+    and Alice to John
+        Alice->>John: Hello
+    end
+    critical Connect
+        Alice->>DB: connect
+    option Timeout
+        Alice->>Alice: Log
+    end
+    Note over Alice,Bob: The end";
+        assert_eq!(validate(source), Ok(()));
+    }
+
+    #[test]
+    fn unbalanced_blocks() {
+        assert_eq!(
+            error_position("sequenceDiagram\n    Alice->>Bob: Hi\n    end"),
+            Some((3, 5))
+        );
+        assert_eq!(
+            error_position("sequenceDiagram\n  loop Forever\n    A->>B: Hi\n  opt Maybe\n  end"),
+            Some((2, 3))
+        );
+        assert_eq!(
+            error_position("sequenceDiagram\n  opt Maybe\n    A->>B: Hi\n  else\n  end"),
+            Some((4, 3))
+        );
+    }
+}
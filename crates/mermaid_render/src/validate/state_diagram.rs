@@ -0,0 +1,75 @@
+//! State diagrams nest composite states in `{ ... }` and have multi-line notes
+//! terminated by `end note`.
+
+use crate::{
+    error::MermaidError,
+    validate::{SourceLine, validate_braces},
+};
+
+pub(super) fn validate(lines: &[SourceLine<'_>]) -> Result<(), MermaidError> {
+    let mut open_note: Option<&SourceLine<'_>> = None;
+    validate_braces(lines, |line| {
+        if open_note.is_some() {
+            if line.text == "end note" {
+                open_note = None;
+            }
+            return Ok(true);
+        }
+        // `note right of X : text` is a single-line note, without `:` the note
+        // continues until `end note`
+        if line.keyword() == "note" && !line.text.contains(':') {
+            open_note = Some(line);
+            return Ok(true);
+        }
+        if line.text == "end note" {
+            return Err(line.error("Unexpected `end note` without an open note"));
+        }
+        Ok(false)
+    })?;
+
+    match open_note {
+        Some(line) => Err(line.error("Note is never closed with `end note`")),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::validate::{tests::error_position, validate};
+
+    #[test]
+    fn valid_state_diagram() {
+        let source = "stateDiagram-v2
+    [*] --> Active
+    state Active {
+        [*] --> Idle
+        Idle --> Busy : work
+        state Busy {
+            [*] --> Working
+        }
+    }
+    note right of Active
+        Multi-line
+        note
+    end note
+    note left of Active : Single line
+    Active --> [*]";
+        assert_eq!(validate(source), Ok(()));
+    }
+
+    #[test]
+    fn unbalanced_composite_states() {
+        assert_eq!(
+            error_position("stateDiagram-v2\n    state Active {\n        [*] --> Idle\n"),
+            Some((2, 5))
+        );
+        assert_eq!(
+            error_position("stateDiagram\n    A --> B\n    }"),
+            Some((3, 5))
+        );
+        assert_eq!(
+            error_position("stateDiagram\n    note left of A\n        text"),
+            Some((2, 5))
+        );
+    }
+}
@@ -5,8 +5,11 @@ use futures::{StreamExt, TryFutureExt, TryStreamExt, stream::FuturesUnordered};
 use gpui::{App, AppContext, Context, Entity, Subscription, Task};
 use itertools::Itertools;
 use language::{Buffer, BufferEvent};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use text::{BufferSnapshot, Point};
+use util::rel_path::RelPath;
+use worktree::Worktree;
 
 use crate::{ProjectPath, buffer_store::BufferStore, worktree_store::WorktreeStore};
 
@@ -19,13 +22,35 @@ impl BookmarkAnchor {
     }
 }
 
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
-pub struct SerializedBookmark(pub u32);
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SerializedBookmark {
+    pub row: u32,
+    pub label: Option<Arc<str>>,
+}
+
+impl SerializedBookmark {
+    pub fn new(row: u32) -> Self {
+        Self { row, label: None }
+    }
+}
+
+/// A bookmark as written to a project's `.zed/bookmarks.json`, which can be
+/// committed to share a tour of the code with others.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SharedBookmark {
+    /// The path of the file, relative to the worktree root.
+    pub path: String,
+    /// The one-based line number.
+    pub line: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
 
 #[derive(Debug)]
 pub struct BufferBookmarks {
     buffer: Entity<Buffer>,
     bookmarks: Vec<BookmarkAnchor>,
+    labels: HashMap<BookmarkAnchor, Arc<str>>,
     _subscription: Subscription,
 }
 
@@ -44,6 +69,7 @@ impl BufferBookmarks {
         Self {
             buffer,
             bookmarks: Vec::new(),
+            labels: HashMap::new(),
             _subscription: subscription,
         }
     }
@@ -55,6 +81,16 @@ impl BufferBookmarks {
     pub fn bookmarks(&self) -> &[BookmarkAnchor] {
         &self.bookmarks
     }
+
+    pub fn label(&self, bookmark: &BookmarkAnchor) -> Option<&Arc<str>> {
+        self.labels.get(bookmark)
+    }
+
+    fn bookmark_at_row(&self, row: u32, snapshot: &text::BufferSnapshot) -> Option<usize> {
+        self.bookmarks
+            .iter()
+            .position(|existing| existing.0.summary::<Point>(snapshot).row == row)
+    }
 }
 
 #[derive(Debug)]
@@ -129,23 +165,27 @@ impl BookmarkStore {
         let snapshot = buffer.read(cx).snapshot();
         let max_point = snapshot.max_point();
 
+        let mut labels = HashMap::new();
         let anchors: Vec<BookmarkAnchor> = rows
             .iter()
             .filter_map(|bookmark_row| {
-                let point = Point::new(bookmark_row.0, 0);
+                let point = Point::new(bookmark_row.row, 0);
 
                 if point > max_point {
                     log::warn!(
                         "Skipping out-of-range bookmark: {} row {} (file has {} rows)",
                         abs_path.display(),
-                        bookmark_row.0,
+                        bookmark_row.row,
                         max_point.row
                     );
                     return None;
                 }
 
-                let anchor = snapshot.anchor_after(point);
-                Some(BookmarkAnchor(anchor))
+                let anchor = BookmarkAnchor(snapshot.anchor_after(point));
+                if let Some(label) = &bookmark_row.label {
+                    labels.insert(anchor, label.clone());
+                }
+                Some(anchor)
             })
             .collect();
 
@@ -154,6 +194,7 @@ impl BookmarkStore {
         } else {
             let mut buffer_bookmarks = BufferBookmarks::new(buffer.clone(), cx);
             buffer_bookmarks.bookmarks = anchors;
+            buffer_bookmarks.labels = labels;
             self.bookmarks
                 .insert(abs_path.clone(), BookmarkEntry::Loaded(buffer_bookmarks));
         }
@@ -191,12 +232,12 @@ impl BookmarkStore {
 
         let snapshot = buffer.read(cx).text_snapshot();
 
-        let existing_index = buffer_bookmarks.bookmarks.iter().position(|existing| {
-            existing.0.summary::<Point>(&snapshot).row == anchor.summary::<Point>(&snapshot).row
-        });
+        let existing_index =
+            buffer_bookmarks.bookmark_at_row(anchor.summary::<Point>(&snapshot).row, &snapshot);
 
         if let Some(index) = existing_index {
-            buffer_bookmarks.bookmarks.remove(index);
+            let removed = buffer_bookmarks.bookmarks.remove(index);
+            buffer_bookmarks.labels.remove(&removed);
             if buffer_bookmarks.bookmarks.is_empty() {
                 self.bookmarks.remove(&abs_path);
             }
@@ -207,6 +248,42 @@ impl BookmarkStore {
         cx.notify();
     }
 
+    /// Sets the label of the bookmark on the row of `anchor`, or removes it
+    /// when `label` is empty. Does nothing if there is no bookmark on that row.
+    pub fn set_bookmark_label(
+        &mut self,
+        buffer: Entity<Buffer>,
+        anchor: text::Anchor,
+        label: Option<Arc<str>>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(abs_path) = Self::abs_path_from_buffer(&buffer, cx) else {
+            return;
+        };
+
+        self.resolve_anchors_if_needed(&abs_path, &buffer, cx);
+
+        let Some(BookmarkEntry::Loaded(buffer_bookmarks)) = self.bookmarks.get_mut(&abs_path)
+        else {
+            return;
+        };
+
+        let snapshot = buffer.read(cx).text_snapshot();
+        let Some(index) =
+            buffer_bookmarks.bookmark_at_row(anchor.summary::<Point>(&snapshot).row, &snapshot)
+        else {
+            return;
+        };
+
+        let bookmark = buffer_bookmarks.bookmarks[index];
+        match label.filter(|label| !label.trim().is_empty()) {
+            Some(label) => buffer_bookmarks.labels.insert(bookmark, label),
+            None => buffer_bookmarks.labels.remove(&bookmark),
+        };
+
+        cx.notify();
+    }
+
     /// Returns the bookmarks for a given buffer within an optional range.
     /// Only returns bookmarks that have been resolved to anchors (loaded).
     /// Unloaded bookmarks for the given buffer will be resolved first.
@@ -315,14 +392,17 @@ impl BookmarkStore {
                                 }
                                 let row =
                                     snapshot.summary_for_anchor::<Point>(&bookmark.anchor()).row;
-                                Some(SerializedBookmark(row))
+                                Some(SerializedBookmark {
+                                    row,
+                                    label: buffer_bookmarks.label(bookmark).cloned(),
+                                })
                             })
                             .collect()
                     }
                 };
 
                 rows.sort();
-                rows.dedup();
+                rows.dedup_by_key(|bookmark| bookmark.row);
 
                 if rows.is_empty() {
                     None
@@ -362,8 +442,126 @@ impl BookmarkStore {
         })
     }
 
+    /// Returns the bookmarks of every file, by absolute path.
+    pub fn bookmark_entries(&self) -> impl Iterator<Item = (&Arc<Path>, &BookmarkEntry)> {
+        self.bookmarks.iter()
+    }
+
+    /// Returns the bookmarks in `worktree` as they're written to a shared
+    /// bookmarks file. Files in `file_order` come first, then the rest by path.
+    pub fn shared_bookmarks(
+        &self,
+        worktree: &Entity<Worktree>,
+        file_order: &[Arc<Path>],
+        cx: &App,
+    ) -> Vec<SharedBookmark> {
+        let worktree = worktree.read(cx);
+        let root = worktree.abs_path();
+        let path_style = worktree.path_style();
+
+        self.all_serialized_bookmarks(cx)
+            .into_iter()
+            .filter_map(|(abs_path, bookmarks)| {
+                let relative_path = abs_path.strip_prefix(&root).ok()?;
+                let relative_path = RelPath::new(relative_path, path_style).ok()?;
+                let path = relative_path.as_unix_str().to_string();
+                let order = file_order
+                    .iter()
+                    .position(|ordered| *ordered == abs_path)
+                    .unwrap_or(file_order.len());
+                Some((order, path, bookmarks))
+            })
+            .sorted_by(|(order_a, path_a, _), (order_b, path_b, _)| {
+                order_a.cmp(order_b).then_with(|| path_a.cmp(path_b))
+            })
+            .flat_map(|(_, path, bookmarks)| {
+                bookmarks.into_iter().map(move |bookmark| SharedBookmark {
+                    path: path.clone(),
+                    line: bookmark.row + 1,
+                    label: bookmark.label.map(|label| label.to_string()),
+                })
+            })
+            .collect()
+    }
+
+    /// Adds bookmarks read from a shared bookmarks file in `worktree`, keeping
+    /// the existing ones. Labels in the file replace existing labels.
+    ///
+    /// Returns the absolute paths of the files, in the order they first appear.
+    pub fn add_shared_bookmarks(
+        &mut self,
+        worktree: &Entity<Worktree>,
+        bookmarks: Vec<SharedBookmark>,
+        cx: &mut Context<Self>,
+    ) -> Vec<Arc<Path>> {
+        let mut file_order = Vec::new();
+
+        for shared in bookmarks {
+            let Ok(relative_path) = RelPath::unix(&shared.path) else {
+                log::warn!(
+                    "Skipping shared bookmark with invalid path {:?}",
+                    shared.path
+                );
+                continue;
+            };
+            let abs_path: Arc<Path> = worktree.read(cx).absolutize(relative_path).into();
+            let row = shared.line.saturating_sub(1);
+            let label = shared
+                .label
+                .filter(|label| !label.trim().is_empty())
+                .map(Arc::<str>::from);
+
+            match self.bookmarks.get_mut(&abs_path) {
+                Some(BookmarkEntry::Loaded(buffer_bookmarks)) => {
+                    let snapshot = buffer_bookmarks.buffer.read(cx).text_snapshot();
+                    if row > snapshot.max_point().row {
+                        continue;
+                    }
+                    let bookmark = match buffer_bookmarks.bookmark_at_row(row, &snapshot) {
+                        Some(index) => buffer_bookmarks.bookmarks[index],
+                        None => {
+                            let bookmark =
+                                BookmarkAnchor(snapshot.anchor_after(Point::new(row, 0)));
+                            buffer_bookmarks.bookmarks.push(bookmark);
+                            bookmark
+                        }
+                    };
+                    if let Some(label) = label {
+                        buffer_bookmarks.labels.insert(bookmark, label);
+                    }
+                }
+                Some(BookmarkEntry::Unloaded(rows)) => {
+                    match rows.iter_mut().find(|bookmark| bookmark.row == row) {
+                        Some(bookmark) => {
+                            if label.is_some() {
+                                bookmark.label = label;
+                            }
+                        }
+                        None => rows.push(SerializedBookmark { row, label }),
+                    }
+                }
+                None => {
+                    self.bookmarks.insert(
+                        abs_path.clone(),
+                        BookmarkEntry::Unloaded(vec![SerializedBookmark { row, label }]),
+                    );
+                }
+            }
+
+            if !file_order.contains(&abs_path) {
+                file_order.push(abs_path);
+            }
+        }
+
+        cx.notify();
+        file_order
+    }
+
     /// Opens buffers for all unloaded bookmark entries and resolves them to anchors. This is used to show all bookmarks in a large multi-buffer.
-    async fn resolve_all(this: &Entity<Self>, cx: &mut (impl AppContext + Clone)) -> Result<()> {
+    pub async fn resolve_all(
+        this: &Entity<Self>,
+        cx: &mut (impl AppContext + Clone),
+    ) -> Result<()> {
         let unloaded_paths: Vec<Arc<Path>> = cx.read_entity(&this, |this, _| {
             this.bookmarks
                 .iter()
@@ -9,7 +9,7 @@ use anyhow::{Context as _, Result};
 use collections::{HashMap, HashSet};
 use context_server::oauth::{self, McpOAuthTokenProvider, OAuthDiscovery, OAuthSession};
 use context_server::transport::{HttpTransport, TransportError};
-use context_server::{ContextServer, ContextServerCommand, ContextServerId};
+use context_server::{ContextServer, ContextServerCommand, ContextServerId, types};
 use credentials_provider::CredentialsProvider;
 use futures::future::Either;
 use futures::{FutureExt as _, StreamExt as _, future::join_all};
@@ -471,6 +471,17 @@ impl ContextServerStore {
             }
         })];
 
+        subscriptions.push(cx.subscribe(&worktree_store, |this, _store, event, cx| {
+            if matches!(
+                event,
+                WorktreeStoreEvent::WorktreeAdded(_)
+                    | WorktreeStoreEvent::WorktreeRemoved(_, _)
+                    | WorktreeStoreEvent::WorktreeOrderChanged
+            ) {
+                this.update_roots(cx);
+            }
+        }));
+
         if maintain_server_loop {
             subscriptions.push(cx.observe(&registry, |this, _registry, cx| {
                 if !DisableAiSettings::get_global(cx).disable_ai {
@@ -512,6 +523,28 @@ impl ContextServerStore {
         this
     }
 
+    /// The visible worktrees of the project, which are exposed to servers as their roots.
+    fn roots(&self, cx: &App) -> Vec<types::Root> {
+        self.worktree_store
+            .read(cx)
+            .visible_worktrees(cx)
+            .filter_map(|worktree| {
+                let worktree = worktree.read(cx);
+                Some(types::Root {
+                    uri: url::Url::from_directory_path(worktree.abs_path()).ok()?,
+                    name: Some(worktree.root_name_str().to_string()),
+                })
+            })
+            .collect()
+    }
+
+    fn update_roots(&self, cx: &App) {
+        let roots = self.roots(cx);
+        for state in self.servers.values() {
+            state.server().set_roots(roots.clone()).log_err();
+        }
+    }
+
     pub fn get_server(&self, id: &ContextServerId) -> Option<Arc<ContextServer>> {
         self.servers.get(id).map(|state| state.server())
     }
@@ -672,6 +705,7 @@ impl ContextServerStore {
         ) {
             self.stop_server(&id, cx).log_err();
         }
+        server.set_roots(self.roots(cx)).log_err();
         let task = cx.spawn({
             let id = server.id();
             let server = server.clone();
@@ -21,6 +21,7 @@ use dap::{
         DapDelegate, DebugAdapterBinary, DebugAdapterName, DebugTaskDefinition, TcpArguments,
     },
     client::SessionId,
+    debugger_settings::DebuggerSettings,
     inline_value::VariableLookupKind,
     messages::Message,
 };
@@ -635,16 +636,16 @@ impl DapStore {
                 .read(cx)
                 .variables_by_stack_frame_id(stack_frame_id, true, false);
 
-        fn format_value(mut value: String) -> String {
-            const LIMIT: usize = 100;
+        let max_length = DebuggerSettings::get_global(cx).inline_value_max_length;
 
+        fn format_value(mut value: String, max_length: usize) -> String {
             if let Some(index) = value.find("\n") {
                 value.truncate(index);
                 value.push_str("…");
             }
 
-            if value.len() > LIMIT {
-                let mut index = LIMIT;
+            if value.len() > max_length {
+                let mut index = max_length;
                 // If index isn't a char boundary truncate will cause a panic
                 while !value.is_char_boundary(index) {
                     index -= 1;
@@ -686,7 +687,10 @@ impl DapStore {
 
                         inlay_hints.push(InlayHint {
                             position,
-                            label: InlayHintLabel::String(format_value(variable.value.clone())),
+                            label: InlayHintLabel::String(format_value(
+                                variable.value.clone(),
+                                max_length,
+                            )),
                             kind: Some(InlayHintKind::Type),
                             padding_left: false,
                             padding_right: false,
@@ -707,7 +711,10 @@ impl DapStore {
                         if let Some(response) = eval_task.await.log_err() {
                             inlay_hints.push(InlayHint {
                                 position,
-                                label: InlayHintLabel::String(format_value(response.result)),
+                                label: InlayHintLabel::String(format_value(
+                                    response.result,
+                                    max_length,
+                                )),
                                 kind: Some(InlayHintKind::Type),
                                 padding_left: false,
                                 padding_right: false,
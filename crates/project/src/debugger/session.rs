@@ -5,9 +5,9 @@ use super::dap_command::{
     self, Attach, ConfigurationDone, ContinueCommand, DataBreakpointInfoCommand, DisconnectCommand,
     EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand,
     ModulesCommand, NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand,
-    ScopesCommand, SetDataBreakpointsCommand, SetExceptionBreakpoints, SetVariableValueCommand,
-    StackTraceCommand, StepBackCommand, StepCommand, StepInCommand, StepOutCommand,
-    TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    ReverseContinueCommand, ScopesCommand, SetDataBreakpointsCommand, SetExceptionBreakpoints,
+    SetVariableValueCommand, StackTraceCommand, StepBackCommand, StepCommand, StepInCommand,
+    StepOutCommand, TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
 };
 use super::dap_store::DapStore;
 use crate::debugger::breakpoint_store::BreakpointSessionState;
@@ -806,6 +806,8 @@ pub enum SessionEvent {
         sender: mpsc::Sender<Result<u32>>,
     },
     DataBreakpointInfo,
+    /// Data breakpoints were added, removed or edited.
+    DataBreakpoints,
     ConsoleOutput,
     HistoricSnapshotSelected,
 }
@@ -1308,6 +1310,7 @@ impl Session {
                     Ok(capabilities) => {
                         this.update(cx, |session, cx| {
                             session.capabilities = capabilities;
+                            session.restrict_capabilities_for_post_mortem();
 
                             cx.emit(SessionEvent::CapabilitiesLoaded);
                         })?;
@@ -1614,6 +1617,7 @@ impl Session {
             }
             Events::Capabilities(event) => {
                 self.capabilities = self.capabilities.merge(event.capabilities);
+                self.restrict_capabilities_for_post_mortem();
 
                 // The adapter might've enabled new exception breakpoints (or disabled existing ones).
                 let recent_filters = self
@@ -2054,11 +2058,56 @@ impl Session {
     pub fn toggle_data_breakpoint(&mut self, id: &str, cx: &mut Context<'_, Session>) {
         if let Some(state) = self.data_breakpoints.get_mut(id) {
             state.is_enabled = !state.is_enabled;
-            self.send_exception_breakpoints(cx);
+            self.send_data_breakpoints(cx);
+        }
+    }
+
+    pub fn set_data_breakpoint_condition(
+        &mut self,
+        id: &str,
+        condition: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(state) = self.data_breakpoints.get_mut(id) {
+            state.dap.condition = condition.filter(|condition| !condition.is_empty());
+            self.send_data_breakpoints(cx);
+        }
+    }
+
+    pub fn set_data_breakpoint_hit_condition(
+        &mut self,
+        id: &str,
+        hit_condition: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(state) = self.data_breakpoints.get_mut(id) {
+            state.dap.hit_condition = hit_condition.filter(|condition| !condition.is_empty());
+            self.send_data_breakpoints(cx);
+        }
+    }
+
+    /// Adds data breakpoints restored from an earlier session, keeping any that already exist.
+    pub fn restore_data_breakpoints(
+        &mut self,
+        breakpoints: impl IntoIterator<Item = DataBreakpointState>,
+        cx: &mut Context<Self>,
+    ) {
+        for state in breakpoints {
+            self.data_breakpoints
+                .entry(state.dap.data_id.clone())
+                .or_insert(state);
+        }
+        self.send_data_breakpoints(cx);
+    }
+
+    pub fn remove_data_breakpoint(&mut self, id: &str, cx: &mut Context<Self>) {
+        if self.data_breakpoints.remove(id).is_some() {
+            self.send_data_breakpoints(cx);
         }
     }
 
     fn send_data_breakpoints(&mut self, cx: &mut Context<Self>) {
+        cx.emit(SessionEvent::DataBreakpoints);
         if let Some(mode) = self.as_running() {
             let breakpoints = self
                 .data_breakpoints
@@ -2285,6 +2334,9 @@ impl Session {
     }
 
     pub fn continue_thread(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        if self.is_post_mortem() {
+            return;
+        }
         self.select_historic_snapshot(None, cx);
 
         let supports_single_thread_execution_requests =
@@ -2305,6 +2357,28 @@ impl Session {
         .detach();
     }
 
+    pub fn reverse_continue_thread(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        if self.is_post_mortem() {
+            return;
+        }
+        self.select_historic_snapshot(None, cx);
+
+        let supports_single_thread_execution_requests =
+            self.capabilities.supports_single_thread_execution_requests;
+        self.active_snapshot
+            .thread_states
+            .continue_thread(thread_id);
+        self.request(
+            ReverseContinueCommand {
+                thread_id: thread_id.0,
+                single_thread: supports_single_thread_execution_requests,
+            },
+            Self::on_step_response::<ReverseContinueCommand>(thread_id),
+            cx,
+        )
+        .detach();
+    }
+
     pub fn adapter_client(&self) -> Option<Arc<DebugAdapterClient>> {
         match self.state {
             SessionState::Running(ref local) => Some(local.client.clone()),
@@ -2322,6 +2396,9 @@ impl Session {
         granularity: SteppingGranularity,
         cx: &mut Context<Self>,
     ) {
+        if self.is_post_mortem() {
+            return;
+        }
         self.select_historic_snapshot(None, cx);
 
         let supports_single_thread_execution_requests =
@@ -2354,6 +2431,9 @@ impl Session {
         granularity: SteppingGranularity,
         cx: &mut Context<Self>,
     ) {
+        if self.is_post_mortem() {
+            return;
+        }
         self.select_historic_snapshot(None, cx);
 
         let supports_single_thread_execution_requests =
@@ -2386,6 +2466,9 @@ impl Session {
         granularity: SteppingGranularity,
         cx: &mut Context<Self>,
     ) {
+        if self.is_post_mortem() {
+            return;
+        }
         self.select_historic_snapshot(None, cx);
 
         let supports_single_thread_execution_requests =
@@ -2418,6 +2501,9 @@ impl Session {
         granularity: SteppingGranularity,
         cx: &mut Context<Self>,
     ) {
+        if self.is_post_mortem() {
+            return;
+        }
         self.select_historic_snapshot(None, cx);
 
         let supports_single_thread_execution_requests =
@@ -2834,6 +2920,39 @@ impl Session {
         self.session_state().locations.get(&reference).cloned()
     }
 
+    /// Whether this session inspects a core dump rather than a live process. Such sessions
+    /// can show threads, frames, variables and memory, but can't resume or modify the program,
+    /// so requests to continue or step are ignored.
+    pub fn is_post_mortem(&self) -> bool {
+        let SessionState::Running(local_mode) = &self.state else {
+            return false;
+        };
+        local_mode
+            .binary
+            .request_args
+            .configuration
+            .get("coreFile")
+            .is_some_and(|core_file| !core_file.is_null())
+    }
+
+    fn restrict_capabilities_for_post_mortem(&mut self) {
+        if !self.is_post_mortem() {
+            return;
+        }
+        for capability in [
+            &mut self.capabilities.supports_set_variable,
+            &mut self.capabilities.supports_set_expression,
+            &mut self.capabilities.supports_write_memory_request,
+            &mut self.capabilities.supports_step_back,
+            &mut self.capabilities.supports_restart_frame,
+            &mut self.capabilities.supports_data_breakpoints,
+            &mut self.capabilities.supports_goto_targets_request,
+            &mut self.capabilities.supports_step_in_targets_request,
+        ] {
+            *capability = Some(false);
+        }
+    }
+
     pub fn is_attached(&self) -> bool {
         let SessionState::Running(local_mode) = &self.state else {
             return false;
@@ -31,20 +31,24 @@ use futures::{
 };
 use git::{
     BuildPermalinkParams, GitHostingProviderRegistry, Oid, RunHook,
+    bisect::{BisectStatus, BisectTerm},
     blame::Blame,
     parse_git_remote_url,
+    rebase::{RebaseAction, RebaseControl, RebaseProgress, RebaseTodoEntry},
     repository::{
         Branch, BranchesScanResult, CommitData, CommitDetails, CommitDiff, CommitFile,
-        CommitOptions, CreateWorktreeTarget, DiffType, FetchOptions, FileHistoryChangedFileSets,
-        GitCommitTemplate, GitRepository, GitRepositoryCheckpoint, InitialGraphCommitData,
-        LogOrder, LogSource, PushOptions, Remote, RemoteCommandOutput, RepoPath, ResetMode,
-        SearchCommitArgs, UpstreamTrackingStatus, Worktree as GitWorktree, delete_branch_flag,
+        CommitOptions, ConflictStages, CreateWorktreeTarget, DiffType, FetchOptions,
+        FileHistoryChangedFileSets, GitCommitTemplate, GitRepository, GitRepositoryCheckpoint,
+        InitialGraphCommitData, LogOrder, LogSource, PushOptions, Remote, RemoteCommandOutput,
+        RepoPath, ResetMode, SearchCommitArgs, UpstreamTrackingStatus, Worktree as GitWorktree,
+        delete_branch_flag,
     },
     stash::{GitStash, StashEntry},
     status::{
         self, DiffStat, DiffTreeType, FileStatus, GitSummary, StatusCode, TrackedStatus, TreeDiff,
         TreeDiffStatus, UnmergedStatus, UnmergedStatusCode,
     },
+    submodule::{Submodule, SubmoduleCommand, SubmoduleLog, SubmoduleLogEntry, SubmoduleState},
 };
 use gpui::{
     App, AppContext, AsyncApp, BackgroundExecutor, Context, Entity, EventEmitter, SharedString,
@@ -60,7 +64,10 @@ use pending_op::{PendingOp, PendingOpId, PendingOps, PendingOpsSummary};
 use postage::stream::Stream as _;
 use rpc::{
     AnyProtoClient, TypedEnvelope,
-    proto::{self, git_reset, split_repository_update},
+    proto::{
+        self, git_bisect_mark, git_rebase_control, git_rebase_todo_entry, git_reset,
+        git_submodule_command, split_repository_update,
+    },
 };
 use serde::Deserialize;
 use settings::{Settings, WorktreeId};
@@ -289,6 +296,10 @@ pub struct RepositoryId(pub u64);
 pub struct MergeDetails {
     pub merge_heads_by_conflicted_path: TreeMap<RepoPath, Vec<Option<SharedString>>>,
     pub message: Option<SharedString>,
+    /// Set while an interactive or non-interactive rebase is stopped partway.
+    pub rebase: Option<RebaseProgress>,
+    /// Set while a bisect is in progress, including after it has found the first bad commit.
+    pub bisect: Option<BisectStatus>,
 }
 
 #[derive(Clone)]
@@ -326,6 +337,7 @@ pub struct RepositorySnapshot {
     pub remote_upstream_url: Option<String>,
     pub stash_entries: GitStash,
     pub linked_worktrees: Arc<[GitWorktree]>,
+    pub submodules: Arc<[Submodule]>,
 }
 
 type JobId = u64;
@@ -479,7 +491,11 @@ pub enum RepositoryEvent {
     BranchListChanged,
     StashEntriesChanged,
     GitWorktreeListChanged,
-    PendingOpsChanged { pending_ops: SumTree<PendingOps> },
+    /// A tag was created or deleted through this repository.
+    TagsChanged,
+    PendingOpsChanged {
+        pending_ops: SumTree<PendingOps>,
+    },
     GraphEvent((LogSource, LogOrder), GitGraphEvent),
 }
 
@@ -678,6 +694,19 @@ impl GitStore {
         client.add_entity_request_handler(Self::handle_diff_checkpoints);
         client.add_entity_request_handler(Self::handle_load_commit_diff);
         client.add_entity_request_handler(Self::handle_checkout_files);
+        client.add_entity_request_handler(Self::handle_rebase_todo);
+        client.add_entity_request_handler(Self::handle_rebase_interactive);
+        client.add_entity_request_handler(Self::handle_rebase_control);
+        client.add_entity_request_handler(Self::handle_bisect_start);
+        client.add_entity_request_handler(Self::handle_bisect_mark);
+        client.add_entity_request_handler(Self::handle_bisect_reset);
+        client.add_entity_request_handler(Self::handle_submodule_command);
+        client.add_entity_request_handler(Self::handle_submodule_log);
+        client.add_entity_request_handler(Self::handle_load_conflict_stages);
+        client.add_entity_request_handler(Self::handle_cherry_pick);
+        client.add_entity_request_handler(Self::handle_revert);
+        client.add_entity_request_handler(Self::handle_create_tag);
+        client.add_entity_request_handler(Self::handle_delete_tag);
         client.add_entity_request_handler(Self::handle_open_commit_message_buffer);
         client.add_entity_request_handler(Self::handle_set_index_text);
         client.add_entity_request_handler(Self::handle_askpass);
@@ -3276,6 +3305,7 @@ impl GitStore {
         let mode = match envelope.payload.mode() {
             git_reset::ResetMode::Soft => ResetMode::Soft,
             git_reset::ResetMode::Mixed => ResetMode::Mixed,
+            git_reset::ResetMode::Hard => ResetMode::Hard,
         };
 
         repository_handle
@@ -3286,6 +3316,75 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_cherry_pick(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCherryPick>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.cherry_pick(envelope.payload.commits)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_revert(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRevert>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.revert(envelope.payload.commit)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_create_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitCreateTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.create_tag(
+                    envelope.payload.tag_name,
+                    envelope.payload.commit,
+                    envelope.payload.message,
+                    cx,
+                )
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_delete_tag(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitDeleteTag>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, cx| {
+                repository_handle.delete_tag(envelope.payload.tag_name, cx)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
     async fn handle_checkout_files(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::GitCheckoutFiles>,
@@ -3308,6 +3407,196 @@ impl GitStore {
         Ok(proto::Ack {})
     }
 
+    async fn handle_rebase_todo(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseTodo>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitRebaseTodoResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let entries = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_todo(envelope.payload.base)
+            })
+            .await??;
+        Ok(proto::GitRebaseTodoResponse {
+            entries: entries.iter().map(rebase_todo_entry_to_proto).collect(),
+        })
+    }
+
+    async fn handle_rebase_interactive(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseInteractive>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let entries = envelope
+            .payload
+            .entries
+            .iter()
+            .map(proto_to_rebase_todo_entry)
+            .collect::<Result<Vec<_>>>()?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_interactive(envelope.payload.base, entries)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_rebase_control(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitRebaseControl>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let control = match envelope.payload.control() {
+            git_rebase_control::Control::Continue => RebaseControl::Continue,
+            git_rebase_control::Control::Skip => RebaseControl::Skip,
+            git_rebase_control::Control::Abort => RebaseControl::Abort,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.rebase_control(control)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bisect_start(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectStart>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_start()
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bisect_mark(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectMark>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        let term = match envelope.payload.term() {
+            git_bisect_mark::Term::Good => BisectTerm::Good,
+            git_bisect_mark::Term::Bad => BisectTerm::Bad,
+            git_bisect_mark::Term::Skip => BisectTerm::Skip,
+        };
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_mark(term, envelope.payload.commit)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_bisect_reset(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitBisectReset>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.bisect_reset()
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_submodule_command(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSubmoduleCommand>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let command = match envelope.payload.command() {
+            git_submodule_command::Command::Init => SubmoduleCommand::Init,
+            git_submodule_command::Command::Update => SubmoduleCommand::Update,
+            git_submodule_command::Command::Sync => SubmoduleCommand::Sync,
+        };
+        let paths = envelope
+            .payload
+            .paths
+            .iter()
+            .map(|path| RepoPath::from_proto(path))
+            .collect::<Result<Vec<_>>>()?;
+
+        repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.submodule_command(command, paths)
+            })
+            .await??;
+        Ok(proto::Ack {})
+    }
+
+    async fn handle_submodule_log(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitSubmoduleLog>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitSubmoduleLogResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = RepoPath::from_proto(&envelope.payload.path)?;
+        let old_sha = Oid::from_str(&envelope.payload.old_sha)?;
+        let new_sha = Oid::from_str(&envelope.payload.new_sha)?;
+
+        let log = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.submodule_log(path, old_sha, new_sha)
+            })
+            .await??;
+        Ok(proto::GitSubmoduleLogResponse {
+            added: log.added.iter().map(submodule_log_entry_to_proto).collect(),
+            removed: log
+                .removed
+                .iter()
+                .map(submodule_log_entry_to_proto)
+                .collect(),
+        })
+    }
+
+    async fn handle_load_conflict_stages(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::GitLoadConflictStages>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::GitLoadConflictStagesResponse> {
+        let repository_id = RepositoryId::from_proto(envelope.payload.repository_id);
+        let repository_handle = Self::repository_for_request(&this, repository_id, &mut cx)?;
+        let path = RepoPath::from_proto(&envelope.payload.path)?;
+
+        let stages = repository_handle
+            .update(&mut cx, |repository_handle, _| {
+                repository_handle.load_conflict_stages(path)
+            })
+            .await??;
+        Ok(proto::GitLoadConflictStagesResponse {
+            base: stages.base,
+            ours: stages.ours,
+            theirs: stages.theirs,
+        })
+    }
+
     async fn handle_open_commit_message_buffer(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::OpenCommitMessageBuffer>,
@@ -4222,6 +4511,7 @@ impl RepositorySnapshot {
             remote_upstream_url: None,
             stash_entries: Default::default(),
             linked_worktrees: Arc::from([]),
+            submodules: Arc::from([]),
             path_style,
         }
     }
@@ -4248,6 +4538,8 @@ impl RepositorySnapshot {
                 .map(|(repo_path, _)| repo_path.to_proto())
                 .collect(),
             merge_message: self.merge.message.as_ref().map(|msg| msg.to_string()),
+            rebase_progress: self.merge.rebase.as_ref().map(rebase_progress_to_proto),
+            bisect_status: self.merge.bisect.as_ref().map(bisect_status_to_proto),
             project_id,
             id: self.id.to_proto(),
             abs_path: self.work_directory_abs_path.to_string_lossy().into_owned(),
@@ -4271,6 +4563,7 @@ impl RepositorySnapshot {
                 .iter()
                 .map(worktree_to_proto)
                 .collect(),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
         }
     }
 
@@ -4335,6 +4628,8 @@ impl RepositorySnapshot {
                 .map(|(path, _)| path.to_proto())
                 .collect(),
             merge_message: self.merge.message.as_ref().map(|msg| msg.to_string()),
+            rebase_progress: self.merge.rebase.as_ref().map(rebase_progress_to_proto),
+            bisect_status: self.merge.bisect.as_ref().map(bisect_status_to_proto),
             project_id,
             id: self.id.to_proto(),
             abs_path: self.work_directory_abs_path.to_string_lossy().into_owned(),
@@ -4358,6 +4653,7 @@ impl RepositorySnapshot {
                 .iter()
                 .map(worktree_to_proto)
                 .collect(),
+            submodules: self.submodules.iter().map(submodule_to_proto).collect(),
         }
     }
 
@@ -4401,6 +4697,12 @@ impl RepositorySnapshot {
         &self.linked_worktrees
     }
 
+    pub fn submodule_for_path(&self, path: &RepoPath) -> Option<&Submodule> {
+        self.submodules
+            .iter()
+            .find(|submodule| &submodule.path == path)
+    }
+
     pub fn status(&self) -> impl Iterator<Item = StatusEntry> + '_ {
         self.statuses_by_path.iter().cloned()
     }
@@ -4500,6 +4802,12 @@ impl MergeDetails {
     ) -> bool {
         log::debug!("load merge details");
         self.message = backend.merge_message().await.map(SharedString::from);
+        let rebase = backend.rebase_progress().await;
+        let rebase_changed = self.rebase != rebase;
+        self.rebase = rebase;
+        let bisect = backend.bisect_status().await.log_err().flatten();
+        let bisect_changed = self.bisect != bisect;
+        self.bisect = bisect;
         let heads = backend
             .revparse_batch(vec![
                 "MERGE_HEAD".into(),
@@ -4538,7 +4846,7 @@ impl MergeDetails {
                 keep
             });
 
-        conflicts_changed
+        conflicts_changed || rebase_changed || bisect_changed
     }
 }
 
@@ -4734,6 +5042,9 @@ impl Repository {
                         .retain(|(log_source, _), _| *log_source != LogSource::All);
                 }
             }
+            RepositoryEvent::TagsChanged => {
+                self.initial_graph_data.clear();
+            }
             _ => {}
         }
     }
@@ -5198,6 +5509,7 @@ impl Repository {
                             mode: match reset_mode {
                                 ResetMode::Soft => git_reset::ResetMode::Soft.into(),
                                 ResetMode::Mixed => git_reset::ResetMode::Mixed.into(),
+                                ResetMode::Hard => git_reset::ResetMode::Hard.into(),
                             },
                         })
                         .await?;
@@ -5208,50 +5520,490 @@ impl Repository {
         })
     }
 
-    pub fn show(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDetails>> {
+    /// Applies each of `commits`, which may include ranges such as `a^..b`, on top of HEAD.
+    pub fn cherry_pick(&mut self, commits: Vec<String>) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
-        self.send_job("show", None, move |git_repo, _cx| async move {
-            match git_repo {
-                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                    backend.show(commit).await
-                }
-                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
-                    let resp = client
-                        .request(proto::GitShow {
-                            project_id: project_id.0,
-                            repository_id: id.to_proto(),
-                            commit,
-                        })
-                        .await?;
+        self.send_job(
+            "cherry_pick",
+            Some(format!("git cherry-pick {}", commits.join(" ")).into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.cherry_pick(commits, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitCherryPick {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commits,
+                            })
+                            .await?;
 
-                    Ok(CommitDetails {
-                        sha: resp.sha.into(),
-                        message: resp.message.into(),
-                        commit_timestamp: resp.commit_timestamp,
-                        author_email: resp.author_email.into(),
-                        author_name: resp.author_name.into(),
-                    })
+                        Ok(())
+                    }
                 }
-            }
-        })
+            },
+        )
     }
 
-    pub fn load_commit_diff(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDiff>> {
+    pub fn revert(&mut self, commit: String) -> oneshot::Receiver<Result<()>> {
         let id = self.id;
-        self.send_job("load_commit_diff", None, move |git_repo, cx| async move {
-            match git_repo {
-                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
-                    backend.load_commit(commit, cx).await
+        self.send_job(
+            "revert",
+            Some(format!("git revert {commit}").into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.revert(commit, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRevert {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                commit,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
                 }
-                RepositoryState::Remote(RemoteRepositoryState {
-                    client, project_id, ..
-                }) => {
-                    let response = client
-                        .request(proto::LoadCommitDiff {
-                            project_id: project_id.0,
-                            repository_id: id.to_proto(),
-                            commit,
-                        })
+            },
+        )
+    }
+
+    pub fn create_tag(
+        &mut self,
+        tag_name: String,
+        commit: String,
+        message: Option<String>,
+        cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let this = cx.weak_entity();
+        self.send_job(
+            "create_tag",
+            Some(format!("git tag {tag_name} {commit}").into()),
+            move |git_repo, mut cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => {
+                        backend
+                            .create_tag(tag_name, commit, message, environment)
+                            .await?
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitCreateTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                tag_name,
+                                commit,
+                                message,
+                            })
+                            .await?;
+                    }
+                }
+                this.update(&mut cx, |_, cx| cx.emit(RepositoryEvent::TagsChanged))
+            },
+        )
+    }
+
+    pub fn delete_tag(
+        &mut self,
+        tag_name: String,
+        cx: &mut Context<Self>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        let this = cx.weak_entity();
+        self.send_job(
+            "delete_tag",
+            Some(format!("git tag --delete {tag_name}").into()),
+            move |git_repo, mut cx| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                        backend.delete_tag(tag_name).await?
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitDeleteTag {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                tag_name,
+                            })
+                            .await?;
+                    }
+                }
+                this.update(&mut cx, |_, cx| cx.emit(RepositoryEvent::TagsChanged))
+            },
+        )
+    }
+
+    /// Lists the commits an interactive rebase onto `base` would replay, oldest first.
+    ///
+    /// Without a base, every commit reachable from HEAD is listed.
+    pub fn rebase_todo(
+        &mut self,
+        base: Option<String>,
+    ) -> oneshot::Receiver<Result<Vec<RebaseTodoEntry>>> {
+        let id = self.id;
+        self.send_job("rebase_todo", None, move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.rebase_todo(base).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitRebaseTodo {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            base,
+                        })
+                        .await?;
+
+                    response
+                        .entries
+                        .iter()
+                        .map(proto_to_rebase_todo_entry)
+                        .collect()
+                }
+            }
+        })
+    }
+
+    pub fn rebase_interactive(
+        &mut self,
+        base: Option<String>,
+        entries: Vec<RebaseTodoEntry>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            "rebase_interactive",
+            Some("git rebase --interactive".into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.rebase_interactive(base, entries, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRebaseInteractive {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                base,
+                                entries: entries.iter().map(rebase_todo_entry_to_proto).collect(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn rebase_control(&mut self, control: RebaseControl) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            "rebase_control",
+            Some(format!("git rebase {}", control.as_arg()).into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.rebase_control(control, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitRebaseControl {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                control: match control {
+                                    RebaseControl::Continue => {
+                                        git_rebase_control::Control::Continue.into()
+                                    }
+                                    RebaseControl::Skip => git_rebase_control::Control::Skip.into(),
+                                    RebaseControl::Abort => {
+                                        git_rebase_control::Control::Abort.into()
+                                    }
+                                },
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect_start(&mut self) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            "bisect_start",
+            Some("git bisect start".into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_start(environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitBisectStart {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    /// Marks `commit`, or the commit being tested without one, as good, bad or skipped.
+    pub fn bisect_mark(
+        &mut self,
+        term: BisectTerm,
+        commit: Option<String>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            "bisect_mark",
+            Some(format!("git bisect {}", term.as_str()).into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_mark(term, commit, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitBisectMark {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                term: match term {
+                                    BisectTerm::Good => git_bisect_mark::Term::Good.into(),
+                                    BisectTerm::Bad => git_bisect_mark::Term::Bad.into(),
+                                    BisectTerm::Skip => git_bisect_mark::Term::Skip.into(),
+                                },
+                                commit,
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    pub fn bisect_reset(&mut self) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            "bisect_reset",
+            Some("git bisect reset".into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.bisect_reset(environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitBisectReset {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    /// Runs `git submodule init`, `update` or `sync` on the given submodules, or on all of them
+    /// when `paths` is empty.
+    pub fn submodule_command(
+        &mut self,
+        command: SubmoduleCommand,
+        paths: Vec<RepoPath>,
+    ) -> oneshot::Receiver<Result<()>> {
+        let id = self.id;
+        self.send_job(
+            "submodule_command",
+            Some(format!("git submodule {}", command.as_str()).into()),
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState {
+                        backend,
+                        environment,
+                        ..
+                    }) => backend.submodule_command(command, paths, environment).await,
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        client
+                            .request(proto::GitSubmoduleCommand {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                command: match command {
+                                    SubmoduleCommand::Init => {
+                                        git_submodule_command::Command::Init.into()
+                                    }
+                                    SubmoduleCommand::Update => {
+                                        git_submodule_command::Command::Update.into()
+                                    }
+                                    SubmoduleCommand::Sync => {
+                                        git_submodule_command::Command::Sync.into()
+                                    }
+                                },
+                                paths: paths.iter().map(|path| path.to_proto()).collect(),
+                            })
+                            .await?;
+
+                        Ok(())
+                    }
+                }
+            },
+        )
+    }
+
+    /// Returns the base, ours and theirs versions of a conflicted file.
+    pub fn load_conflict_stages(
+        &mut self,
+        path: RepoPath,
+    ) -> oneshot::Receiver<Result<ConflictStages>> {
+        let id = self.id;
+        self.send_job(
+            "load_conflict_stages",
+            None,
+            move |git_repo, _| async move {
+                match git_repo {
+                    RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                        backend.load_conflict_stages(path).await
+                    }
+                    RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                        let response = client
+                            .request(proto::GitLoadConflictStages {
+                                project_id: project_id.0,
+                                repository_id: id.to_proto(),
+                                path: path.to_proto(),
+                            })
+                            .await?;
+
+                        Ok(ConflictStages {
+                            base: response.base,
+                            ours: response.ours,
+                            theirs: response.theirs,
+                        })
+                    }
+                }
+            },
+        )
+    }
+
+    /// Returns the commits between two revisions of the submodule at `path`.
+    pub fn submodule_log(
+        &mut self,
+        path: RepoPath,
+        old_sha: Oid,
+        new_sha: Oid,
+    ) -> oneshot::Receiver<Result<SubmoduleLog>> {
+        let id = self.id;
+        self.send_job("submodule_log", None, move |git_repo, _| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.submodule_log(path, old_sha, new_sha).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let response = client
+                        .request(proto::GitSubmoduleLog {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            path: path.to_proto(),
+                            old_sha: old_sha.to_string(),
+                            new_sha: new_sha.to_string(),
+                        })
+                        .await?;
+
+                    Ok(SubmoduleLog {
+                        added: response
+                            .added
+                            .iter()
+                            .filter_map(proto_to_submodule_log_entry)
+                            .collect(),
+                        removed: response
+                            .removed
+                            .iter()
+                            .filter_map(proto_to_submodule_log_entry)
+                            .collect(),
+                    })
+                }
+            }
+        })
+    }
+
+    pub fn show(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDetails>> {
+        let id = self.id;
+        self.send_job("show", None, move |git_repo, _cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.show(commit).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState { project_id, client }) => {
+                    let resp = client
+                        .request(proto::GitShow {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            commit,
+                        })
+                        .await?;
+
+                    Ok(CommitDetails {
+                        sha: resp.sha.into(),
+                        message: resp.message.into(),
+                        commit_timestamp: resp.commit_timestamp,
+                        author_email: resp.author_email.into(),
+                        author_name: resp.author_name.into(),
+                    })
+                }
+            }
+        })
+    }
+
+    pub fn load_commit_diff(&mut self, commit: String) -> oneshot::Receiver<Result<CommitDiff>> {
+        let id = self.id;
+        self.send_job("load_commit_diff", None, move |git_repo, cx| async move {
+            match git_repo {
+                RepositoryState::Local(LocalRepositoryState { backend, .. }) => {
+                    backend.load_commit(commit, cx).await
+                }
+                RepositoryState::Remote(RemoteRepositoryState {
+                    client, project_id, ..
+                }) => {
+                    let response = client
+                        .request(proto::LoadCommitDiff {
+                            project_id: project_id.0,
+                            repository_id: id.to_proto(),
+                            commit,
+                        })
                         .await?;
                     Ok(CommitDiff {
                         files: response
@@ -7794,6 +8546,15 @@ impl Repository {
             self.snapshot.merge.merge_heads_by_conflicted_path != new_merge_heads;
         self.snapshot.merge.merge_heads_by_conflicted_path = new_merge_heads;
         self.snapshot.merge.message = update.merge_message.map(SharedString::from);
+        let new_rebase = update
+            .rebase_progress
+            .as_ref()
+            .map(proto_to_rebase_progress);
+        let rebase_changed = self.snapshot.merge.rebase != new_rebase;
+        self.snapshot.merge.rebase = new_rebase;
+        let new_bisect = update.bisect_status.as_ref().map(proto_to_bisect_status);
+        let bisect_changed = self.snapshot.merge.bisect != new_bisect;
+        self.snapshot.merge.bisect = new_bisect;
         let new_stash_entries = GitStash {
             entries: update
                 .stash_entries
@@ -7814,6 +8575,13 @@ impl Repository {
             cx.emit(RepositoryEvent::GitWorktreeListChanged);
         }
         self.snapshot.linked_worktrees = new_linked_worktrees;
+        let new_submodules: Arc<[Submodule]> = update
+            .submodules
+            .iter()
+            .filter_map(proto_to_submodule)
+            .collect();
+        let submodules_changed = *self.snapshot.submodules != *new_submodules;
+        self.snapshot.submodules = new_submodules;
         self.snapshot.remote_upstream_url = update.remote_upstream_url;
         self.snapshot.remote_origin_url = update.remote_origin_url;
 
@@ -7834,7 +8602,12 @@ impl Repository {
                     }),
             )
             .collect::<Vec<_>>();
-        if conflicts_changed || !edits.is_empty() {
+        if conflicts_changed
+            || rebase_changed
+            || bisect_changed
+            || submodules_changed
+            || !edits.is_empty()
+        {
             cx.emit(RepositoryEvent::StatusesChanged);
         }
         self.snapshot.statuses_by_path.edit(edits, ());
@@ -8203,6 +8976,15 @@ impl Repository {
                 let has_head = prev_snapshot.head_commit.is_some();
 
                 let stash_entries = backend.stash_entries().await?;
+                let submodules_changed = changed_paths.iter().flatten().any(|path| {
+                    path.as_unix_str() == ".gitmodules"
+                        || prev_snapshot.submodule_for_path(path).is_some()
+                });
+                let submodules = if submodules_changed {
+                    Some(backend.submodules().await.log_err().unwrap_or_default())
+                } else {
+                    None
+                };
                 let changed_path_statuses = cx
                     .background_spawn(async move {
                         let mut changed_paths =
@@ -8264,6 +9046,13 @@ impl Repository {
                         this.snapshot.stash_entries = stash_entries;
                     }
 
+                    if let Some(submodules) = submodules
+                        && *this.snapshot.submodules != *submodules
+                    {
+                        cx.emit(RepositoryEvent::StatusesChanged);
+                        this.snapshot.submodules = submodules.into();
+                    }
+
                     if !changed_path_statuses.is_empty() {
                         cx.emit(RepositoryEvent::StatusesChanged);
                         this.snapshot
@@ -8922,6 +9711,130 @@ fn proto_to_branch(proto: &proto::Branch) -> git::repository::Branch {
     }
 }
 
+fn rebase_todo_entry_to_proto(entry: &RebaseTodoEntry) -> proto::GitRebaseTodoEntry {
+    let action = match entry.action {
+        RebaseAction::Pick => git_rebase_todo_entry::RebaseAction::Pick,
+        RebaseAction::Reword => git_rebase_todo_entry::RebaseAction::Reword,
+        RebaseAction::Edit => git_rebase_todo_entry::RebaseAction::Edit,
+        RebaseAction::Squash => git_rebase_todo_entry::RebaseAction::Squash,
+        RebaseAction::Fixup => git_rebase_todo_entry::RebaseAction::Fixup,
+        RebaseAction::Drop => git_rebase_todo_entry::RebaseAction::Drop,
+    };
+    proto::GitRebaseTodoEntry {
+        action: action.into(),
+        sha: entry.sha.to_string(),
+        subject: entry.subject.to_string(),
+        message: entry.message.clone(),
+    }
+}
+
+fn proto_to_rebase_todo_entry(proto: &proto::GitRebaseTodoEntry) -> Result<RebaseTodoEntry> {
+    let action = match proto.action() {
+        git_rebase_todo_entry::RebaseAction::Pick => RebaseAction::Pick,
+        git_rebase_todo_entry::RebaseAction::Reword => RebaseAction::Reword,
+        git_rebase_todo_entry::RebaseAction::Edit => RebaseAction::Edit,
+        git_rebase_todo_entry::RebaseAction::Squash => RebaseAction::Squash,
+        git_rebase_todo_entry::RebaseAction::Fixup => RebaseAction::Fixup,
+        git_rebase_todo_entry::RebaseAction::Drop => RebaseAction::Drop,
+    };
+    Ok(RebaseTodoEntry {
+        action,
+        sha: Oid::from_str(&proto.sha)?,
+        subject: proto.subject.clone().into(),
+        message: proto.message.clone(),
+    })
+}
+
+fn rebase_progress_to_proto(progress: &RebaseProgress) -> proto::RebaseProgress {
+    proto::RebaseProgress {
+        done: progress.done as u64,
+        total: progress.total as u64,
+        stopped_at: progress.stopped_at.map(|sha| sha.to_string()),
+        branch: progress.branch.as_ref().map(|branch| branch.to_string()),
+    }
+}
+
+fn proto_to_rebase_progress(proto: &proto::RebaseProgress) -> RebaseProgress {
+    RebaseProgress {
+        done: proto.done as usize,
+        total: proto.total as usize,
+        stopped_at: proto
+            .stopped_at
+            .as_deref()
+            .and_then(|sha| Oid::from_str(sha).ok()),
+        branch: proto.branch.clone().map(SharedString::from),
+    }
+}
+
+fn bisect_status_to_proto(status: &BisectStatus) -> proto::BisectStatus {
+    proto::BisectStatus {
+        bad: status.bad.map(|sha| sha.to_string()),
+        good: status.good.iter().map(|sha| sha.to_string()).collect(),
+        skipped: status.skipped.iter().map(|sha| sha.to_string()).collect(),
+        current: status.current.map(|sha| sha.to_string()),
+        remaining_steps: status.remaining_steps.map(|steps| steps as u64),
+        first_bad: status.first_bad.map(|sha| sha.to_string()),
+    }
+}
+
+fn proto_to_bisect_status(proto: &proto::BisectStatus) -> BisectStatus {
+    let parse = |sha: &String| Oid::from_str(sha).ok();
+    BisectStatus {
+        bad: proto.bad.as_ref().and_then(parse),
+        good: proto.good.iter().filter_map(parse).collect(),
+        skipped: proto.skipped.iter().filter_map(parse).collect(),
+        current: proto.current.as_ref().and_then(parse),
+        remaining_steps: proto.remaining_steps.map(|steps| steps as usize),
+        first_bad: proto.first_bad.as_ref().and_then(parse),
+    }
+}
+
+fn submodule_to_proto(submodule: &Submodule) -> proto::Submodule {
+    proto::Submodule {
+        path: submodule.path.to_proto(),
+        head_sha: submodule.head_sha.map(|sha| sha.to_string()),
+        index_sha: submodule.index_sha.map(|sha| sha.to_string()),
+        checked_out_sha: submodule.checked_out_sha.map(|sha| sha.to_string()),
+        state: match submodule.state {
+            SubmoduleState::Uninitialized => proto::submodule::State::Uninitialized,
+            SubmoduleState::UpToDate => proto::submodule::State::UpToDate,
+            SubmoduleState::Modified => proto::submodule::State::Modified,
+            SubmoduleState::Conflicted => proto::submodule::State::Conflicted,
+        }
+        .into(),
+    }
+}
+
+fn proto_to_submodule(proto: &proto::Submodule) -> Option<Submodule> {
+    let parse = |sha: &String| Oid::from_str(sha).ok();
+    Some(Submodule {
+        path: RepoPath::from_proto(&proto.path).log_err()?,
+        head_sha: proto.head_sha.as_ref().and_then(parse),
+        index_sha: proto.index_sha.as_ref().and_then(parse),
+        checked_out_sha: proto.checked_out_sha.as_ref().and_then(parse),
+        state: match proto.state() {
+            proto::submodule::State::Uninitialized => SubmoduleState::Uninitialized,
+            proto::submodule::State::UpToDate => SubmoduleState::UpToDate,
+            proto::submodule::State::Modified => SubmoduleState::Modified,
+            proto::submodule::State::Conflicted => SubmoduleState::Conflicted,
+        },
+    })
+}
+
+fn submodule_log_entry_to_proto(entry: &SubmoduleLogEntry) -> proto::SubmoduleLogEntry {
+    proto::SubmoduleLogEntry {
+        sha: entry.sha.to_string(),
+        subject: entry.subject.to_string(),
+    }
+}
+
+fn proto_to_submodule_log_entry(proto: &proto::SubmoduleLogEntry) -> Option<SubmoduleLogEntry> {
+    Some(SubmoduleLogEntry {
+        sha: Oid::from_str(&proto.sha).log_err()?,
+        subject: proto.subject.clone().into(),
+    })
+}
+
 fn commit_details_to_proto(commit: &CommitDetails) -> proto::GitCommitDetails {
     proto::GitCommitDetails {
         sha: commit.sha.to_string(),
@@ -9466,9 +10379,19 @@ async fn compute_snapshot(
         async move { backend.stash_entries().await.log_err().unwrap_or_default() }
     };
 
-    let (statuses, diff_stats, stash_entries) =
-        futures::future::join3(statuses_future, diff_stat_future, stash_entries_future).await;
-    log::debug!("fetched statuses, diff stats, stash entries");
+    let submodules_future = {
+        let backend = backend.clone();
+        async move { backend.submodules().await.log_err().unwrap_or_default() }
+    };
+
+    let (statuses, diff_stats, stash_entries, submodules) = futures::future::join4(
+        statuses_future,
+        diff_stat_future,
+        stash_entries_future,
+        submodules_future,
+    )
+    .await;
+    log::debug!("fetched statuses, diff stats, stash entries, submodules");
 
     let diff_stat_map: HashMap<&RepoPath, DiffStat> =
         diff_stats.entries.iter().map(|(p, s)| (p, *s)).collect();
@@ -9500,7 +10423,10 @@ async fn compute_snapshot(
     log::debug!("new merge details: {merge_details:?}");
 
     this.update(cx, |this, cx| {
-        if conflicts_changed || statuses_by_path != this.snapshot.statuses_by_path {
+        if conflicts_changed
+            || statuses_by_path != this.snapshot.statuses_by_path
+            || *submodules != *this.snapshot.submodules
+        {
             cx.emit(RepositoryEvent::StatusesChanged);
         }
         if stash_entries != this.snapshot.stash_entries {
@@ -9511,6 +10437,7 @@ async fn compute_snapshot(
         this.snapshot.merge = merge_details;
         this.snapshot.statuses_by_path = statuses_by_path;
         this.snapshot.stash_entries = stash_entries;
+        this.snapshot.submodules = submodules.into();
 
         this.snapshot.clone()
     })
@@ -49,7 +49,9 @@ use crate::{
     prettier_store::{self, PrettierStore, PrettierStoreEvent},
     project_settings::{BinarySettings, LspSettings, ProjectSettings},
     toolchain_store::{LocalToolchainStore, ToolchainStoreEvent},
-    trusted_worktrees::{PathTrust, TrustedWorktrees, TrustedWorktreesEvent},
+    trusted_worktrees::{
+        PathTrust, TrustedWorktrees, TrustedWorktreesEvent, TrustedWorktreesStore,
+    },
     worktree_store::{WorktreeStore, WorktreeStoreEvent},
     yarn::YarnPathStore,
 };
@@ -76,9 +78,9 @@ use language::{
     Bias, BinaryStatus, Buffer, BufferRow, BufferSnapshot, CachedLspAdapter, Capability, CodeLabel,
     CodeLabelExt, Diagnostic, DiagnosticEntry, DiagnosticSet, DiagnosticSourceKind, Diff,
     File as _, Language, LanguageAwareStyling, LanguageName, LanguageRegistry, LocalFile,
-    LspAdapter, LspAdapterDelegate, LspInstaller, ManifestDelegate, ManifestName, ModelineSettings,
-    OffsetUtf16, Patch, PointUtf16, TextBufferSnapshot, ToOffset, ToOffsetUtf16, ToPointUtf16,
-    Toolchain, Transaction, Unclipped,
+    LspAdapter, LspAdapterDelegate, LspInstaller, ManifestDelegate, ManifestName, OffsetUtf16,
+    Patch, PointUtf16, TextBufferSnapshot, ToOffset, ToOffsetUtf16, ToPointUtf16, Toolchain,
+    Transaction, Unclipped,
     language_settings::{
         AllLanguageSettings, FormatOnSave, Formatter, LanguageSettings, LineEndingSetting,
         all_language_settings,
@@ -4325,6 +4327,10 @@ impl LspStore {
             .detach();
         cx.observe_global::<SettingsStore>(Self::on_settings_changed)
             .detach();
+        if let Some(trusted_worktrees) = TrustedWorktrees::try_get_global(cx) {
+            cx.subscribe(&trusted_worktrees, Self::on_trusted_worktrees_event)
+                .detach();
+        }
         subscribe_to_binary_statuses(&languages, cx).detach();
 
         let _maintain_workspace_config = {
@@ -4428,6 +4434,10 @@ impl LspStore {
             .detach();
         cx.subscribe(&worktree_store, Self::on_worktree_store_event)
             .detach();
+        if let Some(trusted_worktrees) = TrustedWorktrees::try_get_global(cx) {
+            cx.subscribe(&trusted_worktrees, Self::on_trusted_worktrees_event)
+                .detach();
+        }
         subscribe_to_binary_statuses(&languages, cx).detach();
         let _maintain_workspace_config = {
             let (sender, receiver) = watch::channel();
@@ -4458,6 +4468,29 @@ impl LspStore {
         }
     }
 
+    /// Modelines are ignored in untrusted worktrees, so apply them once a
+    /// worktree becomes trusted.
+    fn on_trusted_worktrees_event(
+        &mut self,
+        _: Entity<TrustedWorktreesStore>,
+        event: &TrustedWorktreesEvent,
+        cx: &mut Context<Self>,
+    ) {
+        let TrustedWorktreesEvent::Trusted(worktree_store, _) = event else {
+            return;
+        };
+        if worktree_store != &self.worktree_store.downgrade() {
+            return;
+        }
+
+        let buffers = self.buffer_store.read(cx).buffers().collect::<Vec<_>>();
+        for buffer in buffers {
+            if self.parse_modeline(&buffer, cx) {
+                self.detect_language_for_buffer(&buffer, cx);
+            }
+        }
+    }
+
     fn on_buffer_store_event(
         &mut self,
         _: Entity<BufferStore>,
@@ -4911,7 +4944,33 @@ impl LspStore {
         })
     }
 
+    /// Whether modelines of the buffer may be applied. Modelines can change
+    /// the language and how the file is saved, so untrusted worktrees can't
+    /// use them.
+    fn can_apply_modeline(&self, buffer: &Buffer, cx: &mut Context<Self>) -> bool {
+        let Some(worktree_id) = buffer.file().map(|file| file.worktree_id(cx)) else {
+            return true;
+        };
+        // Collab hosts apply their own trust rules.
+        if self
+            .upstream_client()
+            .is_some_and(|(client, _)| client.is_via_collab())
+        {
+            return true;
+        }
+        let Some(trusted_worktrees) = TrustedWorktrees::try_get_global(cx) else {
+            return true;
+        };
+        trusted_worktrees.update(cx, |trusted_worktrees, cx| {
+            trusted_worktrees.can_trust(&self.worktree_store, worktree_id, cx)
+        })
+    }
+
     fn parse_modeline(&mut self, buffer_handle: &Entity<Buffer>, cx: &mut Context<Self>) -> bool {
+        if !self.can_apply_modeline(buffer_handle.read(cx), cx) {
+            return buffer_handle.update(cx, |buffer, _cx| buffer.set_modeline(None));
+        }
+
         let buffer = buffer_handle.read(cx);
         let content = buffer.as_rope();
 
@@ -4958,7 +5017,25 @@ impl LspStore {
 
         log::debug!("Parsed modeline settings: {:?}", modeline_settings);
 
-        buffer_handle.update(cx, |buffer, _cx| buffer.set_modeline(modeline_settings))
+        let encoding = modeline_settings
+            .as_ref()
+            .and_then(|modeline| modeline.encoding);
+        let is_local = self.as_local().is_some();
+        buffer_handle.update(cx, |buffer, cx| {
+            let changed = buffer.set_modeline(modeline_settings);
+            // Only reload when the modeline changes, so that picking another
+            // encoding by hand isn't undone on the next reload.
+            if changed
+                && is_local
+                && let Some(encoding) = encoding
+                && encoding != buffer.encoding()
+                && !buffer.is_dirty()
+                && buffer.file().is_some_and(|file| file.disk_state().exists())
+            {
+                let _ = buffer.reload_with_encoding(encoding, cx);
+            }
+            changed
+        })
     }
 
     fn detect_language_for_buffer(
@@ -4972,16 +5049,13 @@ impl LspStore {
         let content = buffer.as_rope();
         let modeline_settings = buffer.modeline().map(Arc::as_ref);
 
-        let available_language = if let Some(ModelineSettings {
-            mode: Some(mode_name),
-            ..
-        }) = modeline_settings
-        {
-            self.languages
-                .available_language_for_modeline_name(mode_name)
-        } else {
-            self.languages.language_for_file(file, Some(content), cx)
-        };
+        let available_language = modeline_settings
+            .and_then(|modeline| modeline.mode.as_deref())
+            .and_then(|mode_name| {
+                self.languages
+                    .available_language_for_modeline_name(mode_name)
+            })
+            .or_else(|| self.languages.language_for_file(file, Some(content), cx));
         if let Some(available_language) = &available_language {
             if let Some(Ok(Ok(new_language))) = self
                 .languages
@@ -33,7 +33,10 @@ use git_store::{Repository, RepositoryId};
 pub mod search_history;
 pub mod yarn;
 
-use dap::inline_value::{InlineValueLocation, VariableLookupKind, VariableScope};
+use dap::{
+    debugger_settings::DebuggerSettings,
+    inline_value::{InlineValueLocation, VariableLookupKind, VariableScope},
+};
 use itertools::{Either, Itertools};
 
 use crate::{
@@ -4514,7 +4517,9 @@ impl Project {
             .summary_for_anchor::<text::PointUtf16>(&range.end)
             .row as usize;
 
-        let inline_value_locations = provide_inline_values(captures, &snapshot, row);
+        let evaluate_expressions = DebuggerSettings::get_global(cx).inline_value_expressions;
+        let inline_value_locations =
+            provide_inline_values(captures, &snapshot, row, evaluate_expressions);
 
         let stack_frame_id = active_stack_frame.stack_frame_id;
         cx.spawn(async move |this, cx| {
@@ -6645,6 +6650,7 @@ fn provide_inline_values(
     captures: impl Iterator<Item = (Range<usize>, language::DebuggerTextObject)>,
     snapshot: &language::BufferSnapshot,
     max_row: usize,
+    evaluate_expressions: bool,
 ) -> Vec<InlineValueLocation> {
     let mut variables = Vec::new();
     let mut variable_position = HashSet::default();
@@ -6654,7 +6660,15 @@ fn provide_inline_values(
 
     for (capture_range, capture_kind) in captures {
         match capture_kind {
-            language::DebuggerTextObject::Variable => {
+            language::DebuggerTextObject::Variable | language::DebuggerTextObject::Expression => {
+                let lookup = if capture_kind == language::DebuggerTextObject::Expression {
+                    if !evaluate_expressions {
+                        continue;
+                    }
+                    VariableLookupKind::Expression
+                } else {
+                    VariableLookupKind::Variable
+                };
                 let variable_name = snapshot
                     .text_for_range(capture_range.clone())
                     .collect::<String>();
@@ -6684,7 +6698,7 @@ fn provide_inline_values(
                     variables.push(InlineValueLocation {
                         variable_name,
                         scope,
-                        lookup: VariableLookupKind::Variable,
+                        lookup,
                         row: point.row as usize,
                         column: point.column as usize,
                     });
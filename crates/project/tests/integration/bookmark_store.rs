@@ -3,7 +3,10 @@ use std::{path::Path, sync::Arc};
 use collections::BTreeMap;
 use gpui::{Entity, TestAppContext};
 use language::Buffer;
-use project::{Project, bookmark_store::SerializedBookmark};
+use project::{
+    Project,
+    bookmark_store::{SerializedBookmark, SharedBookmark},
+};
 use serde_json::json;
 use util::path;
 
@@ -75,7 +78,9 @@ mod integration {
             let path = project_path(path_str);
             map.insert(
                 path.clone(),
-                rows.iter().map(|&row| SerializedBookmark(row)).collect(),
+                rows.iter()
+                    .map(|&row| SerializedBookmark::new(row))
+                    .collect(),
             );
         }
         map
@@ -113,7 +118,7 @@ mod integration {
         let file_bookmarks = bookmarks
             .get(&path)
             .unwrap_or_else(|| panic!("Expected bookmarks for {}", path.display()));
-        let rows: Vec<u32> = file_bookmarks.iter().map(|b| b.0).collect();
+        let rows: Vec<u32> = file_bookmarks.iter().map(|b| b.row).collect();
         assert_eq!(rows, expected_rows, "Bookmark rows for {}", path.display());
     }
 
@@ -293,7 +298,7 @@ mod integration {
             .get(&project_path(path!("/project/file1.rs")))
             .unwrap()
             .iter()
-            .map(|b| b.0)
+            .map(|b| b.row)
             .collect();
         let mut deduped = rows.clone();
         deduped.dedup();
@@ -682,4 +687,139 @@ mod integration {
         assert_bookmark_rows(&bookmarks, path!("/project/renamed.rs"), &[1]);
         assert_bookmark_rows(&bookmarks, path!("/project/untouched.rs"), &[0, 2]);
     }
+
+    #[gpui::test]
+    async fn test_bookmark_labels(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.executor().allow_parking();
+
+        let fs = fs::FakeFs::new(cx.executor());
+        fs.insert_tree(path!("/project"), json!({"file1.rs": "aaa\nbbb\nccc\n"}))
+            .await;
+
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let buffer = open_buffer(&project, path!("/project/file1.rs"), cx).await;
+        add_bookmarks(&project, &buffer, &[0, 2], cx);
+
+        let set_label = |row: u32, label: Option<&str>, cx: &mut TestAppContext| {
+            project.update(cx, |project, cx| {
+                let anchor = buffer
+                    .read(cx)
+                    .snapshot()
+                    .anchor_after(text::Point::new(row, 0));
+                project.bookmark_store().update(cx, |store, cx| {
+                    store.set_bookmark_label(buffer.clone(), anchor, label.map(Arc::from), cx);
+                });
+            });
+        };
+        let labels = |cx: &mut TestAppContext| {
+            get_all_bookmarks(&project, cx)[&project_path(path!("/project/file1.rs"))]
+                .iter()
+                .map(|bookmark| bookmark.label.as_deref().map(str::to_string))
+                .collect::<Vec<_>>()
+        };
+
+        set_label(2, Some("Entry point"), cx);
+        set_label(1, Some("No bookmark here"), cx);
+        assert_eq!(labels(cx), [None, Some("Entry point".to_string())]);
+
+        set_label(2, Some("  "), cx);
+        assert_eq!(labels(cx), [None, None]);
+
+        set_label(0, Some("Start"), cx);
+        let serialized = get_all_bookmarks(&project, cx);
+        clear_bookmarks(&project, cx);
+        restore_bookmarks(&project, serialized, cx).await;
+        add_bookmarks(&project, &buffer, &[1], cx);
+        assert_eq!(labels(cx), [Some("Start".to_string()), None, None]);
+
+        // Removing a bookmark removes its label
+        add_bookmarks(&project, &buffer, &[0, 0], cx);
+        assert_eq!(labels(cx), [None, None, None]);
+    }
+
+    #[gpui::test]
+    async fn test_shared_bookmarks(cx: &mut TestAppContext) {
+        init_test(cx);
+        cx.executor().allow_parking();
+
+        let fs = fs::FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "a.rs": "aaa\nbbb\nccc\n",
+                "src": {"main.rs": "fn main() {}\n"}
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+        let worktree = project.read_with(cx, |project, cx| project.worktrees(cx).next().unwrap());
+        let buffer = open_buffer(&project, path!("/project/a.rs"), cx).await;
+        add_bookmarks(&project, &buffer, &[1], cx);
+
+        let file_order = project.update(cx, |project, cx| {
+            project.bookmark_store().update(cx, |store, cx| {
+                store.add_shared_bookmarks(
+                    &worktree,
+                    vec![
+                        SharedBookmark {
+                            path: "src/main.rs".into(),
+                            line: 1,
+                            label: Some("Start here".into()),
+                        },
+                        SharedBookmark {
+                            path: "a.rs".into(),
+                            line: 2,
+                            label: Some("Then here".into()),
+                        },
+                        SharedBookmark {
+                            path: "a.rs".into(),
+                            line: 3,
+                            label: None,
+                        },
+                    ],
+                    cx,
+                )
+            })
+        });
+        assert_eq!(
+            file_order,
+            [
+                project_path(path!("/project/src/main.rs")),
+                project_path(path!("/project/a.rs")),
+            ]
+        );
+
+        let bookmarks = get_all_bookmarks(&project, cx);
+        assert_bookmark_rows(&bookmarks, path!("/project/a.rs"), &[1, 2]);
+        assert_bookmark_rows(&bookmarks, path!("/project/src/main.rs"), &[0]);
+
+        let shared = project.read_with(cx, |project, cx| {
+            project
+                .bookmark_store()
+                .read(cx)
+                .shared_bookmarks(&worktree, &file_order, cx)
+        });
+        assert_eq!(
+            shared,
+            [
+                SharedBookmark {
+                    path: "src/main.rs".into(),
+                    line: 1,
+                    label: Some("Start here".into()),
+                },
+                SharedBookmark {
+                    path: "a.rs".into(),
+                    line: 2,
+                    label: Some("Then here".into()),
+                },
+                SharedBookmark {
+                    path: "a.rs".into(),
+                    line: 3,
+                    label: None,
+                },
+            ]
+        );
+    }
 }
@@ -14,7 +14,10 @@ use project::{
 use serde_json::json;
 use settings::settings_content::SaturatingBool;
 use settings::{ContextServerCommand, Settings, SettingsStore};
-use std::sync::Arc;
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering::SeqCst},
+};
 use std::{cell::RefCell, path::PathBuf, rc::Rc};
 use util::path;
 
@@ -751,6 +754,61 @@ async fn test_context_server_refreshed_when_worktree_added(cx: &mut TestAppConte
     });
 }
 
+#[gpui::test]
+async fn test_context_server_roots_follow_worktrees(cx: &mut TestAppContext) {
+    const SERVER_ID: &str = "mcp-1";
+
+    let (fs, project) = setup_context_server_test(cx, json!({"code.rs": ""}), vec![]).await;
+    fs.insert_tree(path!("/second"), json!({"other.rs": ""}))
+        .await;
+
+    let registry = cx.new(|_| ContextServerDescriptorRegistry::new());
+    let store = cx.new(|cx| {
+        ContextServerStore::test(
+            registry.clone(),
+            project.read(cx).worktree_store(),
+            Some(project.downgrade()),
+            cx,
+        )
+    });
+
+    let roots_changed_count = Arc::new(AtomicUsize::new(0));
+    let transport = create_fake_transport(SERVER_ID, cx.executor())
+        .on_notification::<context_server::types::notifications::RootsListChanged>({
+        let roots_changed_count = roots_changed_count.clone();
+        move |_| {
+            roots_changed_count.fetch_add(1, SeqCst);
+        }
+    });
+    let server = Arc::new(ContextServer::new(
+        ContextServerId(SERVER_ID.into()),
+        Arc::new(transport),
+    ));
+    store.update(cx, |store, cx| store.test_start_server(server.clone(), cx));
+    cx.run_until_parked();
+
+    let root_names = |server: &ContextServer| {
+        server
+            .roots()
+            .into_iter()
+            .map(|root| root.name.unwrap())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(root_names(&server), ["test"]);
+    assert_eq!(roots_changed_count.load(SeqCst), 0);
+
+    project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree(path!("/second"), true, cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    assert_eq!(root_names(&server), ["test", "second"]);
+    assert_eq!(roots_changed_count.load(SeqCst), 1);
+}
+
 #[gpui::test]
 async fn test_server_ids_includes_disabled_servers(cx: &mut TestAppContext) {
     const ENABLED_SERVER_ID: &str = "enabled-server";
@@ -1,12 +1,16 @@
 mod conflict_set_tests {
-    use std::sync::mpsc;
+    use std::{str::FromStr as _, sync::mpsc};
 
     use crate::Project;
 
     use fs::FakeFs;
     use git::{
-        repository::{RepoPath, repo_path},
+        Oid,
+        bisect::BisectTerm,
+        rebase::{RebaseControl, RebaseProgress},
+        repository::{ConflictStages, RepoPath, repo_path},
         status::{UnmergedStatus, UnmergedStatusCode},
+        submodule::{Submodule, SubmoduleCommand, SubmoduleState},
     };
     use gpui::{BackgroundExecutor, TestAppContext};
     use project::git_store::*;
@@ -629,6 +633,362 @@ mod conflict_set_tests {
             assert_eq!(conflict_set.snapshot.conflicts.len(), 0);
         });
     }
+
+    #[gpui::test]
+    async fn test_rebase_progress_updates(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+        zlog::init_test();
+        cx.update(|cx| {
+            settings::init(cx);
+        });
+
+        let fs = FakeFs::new(executor);
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "a.txt": "a",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let repository = project.read_with(cx, |project, cx| {
+            project.active_repository(cx).expect("repository")
+        });
+        repository.read_with(cx, |repository, _| {
+            assert_eq!(repository.merge.rebase, None);
+        });
+
+        let stopped_at = Oid::from_str(&"e".repeat(40)).unwrap();
+        fs.with_git_state(path!("/project/.git").as_ref(), true, |state| {
+            state.rebase_progress = Some(RebaseProgress {
+                done: 1,
+                total: 2,
+                stopped_at: Some(stopped_at),
+                branch: Some("feature".into()),
+            });
+        })
+        .unwrap();
+        cx.run_until_parked();
+        repository.read_with(cx, |repository, _| {
+            assert_eq!(
+                repository.merge.rebase,
+                Some(RebaseProgress {
+                    done: 1,
+                    total: 2,
+                    stopped_at: Some(stopped_at),
+                    branch: Some("feature".into()),
+                })
+            );
+        });
+
+        repository
+            .update(cx, |repository, _| {
+                repository.rebase_control(RebaseControl::Continue)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        cx.run_until_parked();
+        repository.read_with(cx, |repository, _| {
+            assert_eq!(
+                repository
+                    .merge
+                    .rebase
+                    .as_ref()
+                    .map(|progress| (progress.done, progress.total)),
+                Some((2, 2))
+            );
+        });
+
+        repository
+            .update(cx, |repository, _| {
+                repository.rebase_control(RebaseControl::Abort)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        cx.run_until_parked();
+        repository.read_with(cx, |repository, _| {
+            assert_eq!(repository.merge.rebase, None);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_bisect_status_updates(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+        zlog::init_test();
+        cx.update(|cx| {
+            settings::init(cx);
+        });
+
+        let fs = FakeFs::new(executor);
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "a.txt": "a",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let repository = project.read_with(cx, |project, cx| {
+            project.active_repository(cx).expect("repository")
+        });
+        repository.read_with(cx, |repository, _| {
+            assert_eq!(repository.merge.bisect, None);
+        });
+
+        let bad = Oid::from_str(&"b".repeat(40)).unwrap();
+        let good = Oid::from_str(&"a".repeat(40)).unwrap();
+        repository
+            .update(cx, |repository, _| repository.bisect_start())
+            .await
+            .unwrap()
+            .unwrap();
+        repository
+            .update(cx, |repository, _| {
+                repository.bisect_mark(BisectTerm::Bad, Some(bad.to_string()))
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        cx.run_until_parked();
+        repository.read_with(cx, |repository, _| {
+            let bisect = repository
+                .merge
+                .bisect
+                .as_ref()
+                .expect("bisect in progress");
+            assert_eq!(bisect.bad, Some(bad));
+            assert!(bisect.needs_bounds());
+        });
+
+        repository
+            .update(cx, |repository, _| {
+                repository.bisect_mark(BisectTerm::Good, Some(good.to_string()))
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        cx.run_until_parked();
+        repository.read_with(cx, |repository, _| {
+            let bisect = repository
+                .merge
+                .bisect
+                .as_ref()
+                .expect("bisect in progress");
+            assert_eq!(bisect.good, vec![good]);
+            assert!(!bisect.needs_bounds());
+        });
+
+        repository
+            .update(cx, |repository, _| repository.bisect_reset())
+            .await
+            .unwrap()
+            .unwrap();
+        cx.run_until_parked();
+        repository.read_with(cx, |repository, _| {
+            assert_eq!(repository.merge.bisect, None);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_create_and_delete_tag(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+        zlog::init_test();
+        cx.update(|cx| {
+            settings::init(cx);
+        });
+
+        let fs = FakeFs::new(executor);
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "a.txt": "a",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let repository = project.read_with(cx, |project, cx| {
+            project.active_repository(cx).expect("repository")
+        });
+        let (events_tx, events_rx) = mpsc::channel::<()>();
+        let _repository_subscription = cx.update(|cx| {
+            cx.subscribe(&repository, move |_, event, _| {
+                if matches!(event, RepositoryEvent::TagsChanged) {
+                    events_tx.send(()).ok();
+                }
+            })
+        });
+
+        let sha = "a".repeat(40);
+        repository
+            .update(cx, |repository, cx| {
+                repository.create_tag("v1".into(), sha.clone(), None, cx)
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(events_rx.try_recv(), Ok(()));
+        let tag = fs
+            .with_git_state(path!("/project/.git").as_ref(), false, |state| {
+                state.refs.get("refs/tags/v1").cloned()
+            })
+            .unwrap();
+        assert_eq!(tag, Some(sha.clone()));
+
+        let result = repository
+            .update(cx, |repository, cx| {
+                repository.create_tag("v1".into(), sha, None, cx)
+            })
+            .await
+            .unwrap();
+        assert!(result.is_err());
+        cx.run_until_parked();
+        assert!(events_rx.try_recv().is_err());
+
+        repository
+            .update(cx, |repository, cx| repository.delete_tag("v1".into(), cx))
+            .await
+            .unwrap()
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(events_rx.try_recv(), Ok(()));
+        let tag = fs
+            .with_git_state(path!("/project/.git").as_ref(), false, |state| {
+                state.refs.get("refs/tags/v1").cloned()
+            })
+            .unwrap();
+        assert_eq!(tag, None);
+    }
+
+    #[gpui::test]
+    async fn test_submodule_updates(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+        zlog::init_test();
+        cx.update(|cx| {
+            settings::init(cx);
+        });
+
+        let fs = FakeFs::new(executor);
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                ".gitmodules": "",
+                "a.txt": "a",
+            }),
+        )
+        .await;
+
+        let recorded = Oid::from_str(&"a".repeat(40)).unwrap();
+        let checked_out = Oid::from_str(&"b".repeat(40)).unwrap();
+        fs.with_git_state(path!("/project/.git").as_ref(), true, |state| {
+            state.submodules = vec![
+                Submodule {
+                    path: repo_path("libs/first"),
+                    head_sha: Some(recorded),
+                    index_sha: Some(recorded),
+                    checked_out_sha: Some(checked_out),
+                    state: SubmoduleState::Modified,
+                },
+                Submodule {
+                    path: repo_path("libs/second"),
+                    head_sha: Some(recorded),
+                    index_sha: Some(recorded),
+                    checked_out_sha: None,
+                    state: SubmoduleState::Uninitialized,
+                },
+            ];
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let repository = project.read_with(cx, |project, cx| {
+            project.active_repository(cx).expect("repository")
+        });
+        repository.read_with(cx, |repository, _| {
+            let first = repository
+                .submodule_for_path(&repo_path("libs/first"))
+                .expect("submodule");
+            assert_eq!(first.checked_out_sha, Some(checked_out));
+            assert_eq!(first.state, SubmoduleState::Modified);
+            assert_eq!(repository.submodules.len(), 2);
+        });
+
+        repository
+            .update(cx, |repository, _| {
+                repository
+                    .submodule_command(SubmoduleCommand::Update, vec![repo_path("libs/first")])
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        cx.run_until_parked();
+        repository.read_with(cx, |repository, _| {
+            assert_eq!(
+                repository
+                    .submodules
+                    .iter()
+                    .map(|submodule| (submodule.checked_out_sha, submodule.state))
+                    .collect::<Vec<_>>(),
+                [
+                    (Some(recorded), SubmoduleState::UpToDate),
+                    (None, SubmoduleState::Uninitialized),
+                ]
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_load_conflict_stages(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+        zlog::init_test();
+        cx.update(|cx| {
+            settings::init(cx);
+        });
+
+        let fs = FakeFs::new(executor);
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                ".git": {},
+                "a.txt": "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> other\n",
+            }),
+        )
+        .await;
+
+        let stages = ConflictStages {
+            base: Some("base\n".into()),
+            ours: Some("ours\n".into()),
+            theirs: None,
+        };
+        fs.with_git_state(path!("/project/.git").as_ref(), true, |state| {
+            state
+                .conflict_stages
+                .insert(repo_path("a.txt"), stages.clone());
+        })
+        .unwrap();
+
+        let project = Project::test(fs.clone(), [path!("/project").as_ref()], cx).await;
+        cx.run_until_parked();
+        let repository = project.read_with(cx, |project, cx| {
+            project.active_repository(cx).expect("repository")
+        });
+        let loaded = repository
+            .update(cx, |repository, _| {
+                repository.load_conflict_stages(repo_path("a.txt"))
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded, stages);
+    }
 }
 
 mod git_traversal {
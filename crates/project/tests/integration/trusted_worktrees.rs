@@ -1,4 +1,4 @@
-use std::{cell::RefCell, path::PathBuf, rc::Rc};
+use std::{cell::RefCell, num::NonZeroU32, path::PathBuf, rc::Rc};
 
 use collections::HashSet;
 use gpui::{Entity, TestAppContext};
@@ -955,3 +955,54 @@ async fn test_invisible_worktree_stores_do_not_affect_trust(cx: &mut TestAppCont
         "only visible worktrees should be restricted"
     );
 }
+
+#[gpui::test]
+async fn test_modelines_require_trust(cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/root"),
+        json!({ "notes.txt": "# vim: set tw=80 nowrap:\nhello\n" }),
+    )
+    .await;
+
+    cx.update(|cx| init(DbTrustedPaths::default(), cx));
+    let project = Project::test_with_worktree_trust(fs, [path!("/root").as_ref()], cx).await;
+    let worktree_store = project.read_with(cx, |project, _| project.worktree_store());
+    let worktree_id = worktree_store.read_with(cx, |store, cx| {
+        store.worktrees().next().unwrap().read(cx).id()
+    });
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/root/notes.txt"), cx)
+        })
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    buffer.read_with(cx, |buffer, _| {
+        assert_eq!(
+            buffer.modeline(),
+            None,
+            "modelines should be ignored in untrusted worktrees"
+        );
+    });
+
+    let trusted_worktrees = cx.update(|cx| TrustedWorktrees::try_get_global(cx).unwrap());
+    trusted_worktrees.update(cx, |store, cx| {
+        store.trust(
+            &worktree_store,
+            HashSet::from_iter([PathTrust::Worktree(worktree_id)]),
+            cx,
+        );
+    });
+    cx.executor().run_until_parked();
+    buffer.read_with(cx, |buffer, _| {
+        let modeline = buffer
+            .modeline()
+            .expect("modeline should apply once trusted");
+        assert_eq!(modeline.preferred_line_length, NonZeroU32::new(80));
+        assert_eq!(modeline.soft_wrap, Some(false));
+    });
+}
@@ -292,6 +292,22 @@ messages!(
     (RemoveRepository, Foreground),
     (UsersResponse, Foreground),
     (GitReset, Background),
+    (GitRebaseTodo, Background),
+    (GitRebaseTodoResponse, Background),
+    (GitRebaseInteractive, Background),
+    (GitRebaseControl, Background),
+    (GitBisectStart, Background),
+    (GitBisectMark, Background),
+    (GitBisectReset, Background),
+    (GitCherryPick, Background),
+    (GitRevert, Background),
+    (GitCreateTag, Background),
+    (GitDeleteTag, Background),
+    (GitSubmoduleCommand, Background),
+    (GitSubmoduleLog, Background),
+    (GitSubmoduleLogResponse, Background),
+    (GitLoadConflictStages, Background),
+    (GitLoadConflictStagesResponse, Background),
     (GitDeleteBranch, Background),
     (GitCheckoutFiles, Background),
     (GitShow, Background),
@@ -547,6 +563,19 @@ request_messages!(
     (GitCompareCheckpoints, GitCompareCheckpointsResponse),
     (GitDiffCheckpoints, GitDiffCheckpointsResponse),
     (GitReset, Ack),
+    (GitRebaseTodo, GitRebaseTodoResponse),
+    (GitRebaseInteractive, Ack),
+    (GitRebaseControl, Ack),
+    (GitBisectStart, Ack),
+    (GitBisectMark, Ack),
+    (GitBisectReset, Ack),
+    (GitCherryPick, Ack),
+    (GitRevert, Ack),
+    (GitCreateTag, Ack),
+    (GitDeleteTag, Ack),
+    (GitSubmoduleCommand, Ack),
+    (GitSubmoduleLog, GitSubmoduleLogResponse),
+    (GitLoadConflictStages, GitLoadConflictStagesResponse),
     (GitDeleteBranch, Ack),
     (GitCheckoutFiles, Ack),
     (SetIndexText, Ack),
@@ -741,6 +770,19 @@ entity_messages!(
     GitCompareCheckpoints,
     GitDiffCheckpoints,
     GitReset,
+    GitRebaseTodo,
+    GitRebaseInteractive,
+    GitRebaseControl,
+    GitBisectStart,
+    GitBisectMark,
+    GitBisectReset,
+    GitCherryPick,
+    GitRevert,
+    GitCreateTag,
+    GitDeleteTag,
+    GitSubmoduleCommand,
+    GitSubmoduleLog,
+    GitLoadConflictStages,
     GitDeleteBranch,
     GitCheckoutFiles,
     SetIndexText,
@@ -1,4 +1,6 @@
 mod cell;
 mod notebook_ui;
+mod text_notebook;
 pub use cell::*;
 pub use notebook_ui::*;
+pub use text_notebook::*;
@@ -1,10 +1,15 @@
 #![allow(unused, dead_code)]
+use std::any::TypeId;
 use std::future::Future;
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use anyhow::{Context as _, Result};
 use client::proto::ViewId;
 use collections::HashMap;
+use command_palette_hooks::CommandPaletteFilter;
 use editor::DisplayPoint;
 use feature_flags::{FeatureFlagAppExt as _, NotebookFeatureFlag};
 use futures::FutureExt;
@@ -20,16 +25,21 @@ use project::{Project, ProjectEntryId, ProjectPath};
 use settings::Settings as _;
 use ui::{CommonAnimationExt, Tooltip, prelude::*};
 use workspace::item::{ItemEvent, SaveOptions, TabContentParams};
+use workspace::notifications::DetachAndPromptErr;
 use workspace::searchable::SearchableItemHandle;
-use workspace::{Item, ItemHandle, Pane, ProjectItem, ToolbarItemLocation};
+use workspace::{Item, ItemHandle, Pane, ProjectItem, ToolbarItemLocation, Workspace};
 
-use super::{Cell, CellEvent, CellPosition, MarkdownCellEvent, RenderableCell};
+use super::{
+    Cell, CellEvent, CellPosition, MarkdownCellEvent, RenderableCell, TextCell, TextCellKind,
+    TextNotebook, TextNotebookFormat,
+};
 
 use nbformat::v4::CellId;
 use nbformat::v4::Metadata as NotebookMetadata;
 use serde_json;
 use uuid::Uuid;
 
+use crate::JupyterSettings;
 use crate::components::{KernelPickerDelegate, KernelSelector};
 use crate::kernels::{
     Kernel, KernelSession, KernelSpecification, KernelStatus, LocalKernelSpecification,
@@ -44,8 +54,8 @@ use ui::PopoverMenuHandle;
 use zed_actions::editor::{MoveDown, MoveUp};
 use zed_actions::notebook::{
     AddCodeBlock, AddMarkdownBlock, ClearOutputs, EnterCommandMode, EnterEditMode, InterruptKernel,
-    MoveCellDown, MoveCellUp, NotebookMoveDown, NotebookMoveUp, OpenNotebook, RestartKernel, Run,
-    RunAll, RunAndAdvance,
+    MoveCellDown, MoveCellUp, NotebookMoveDown, NotebookMoveUp, OpenAsNotebook, OpenNotebook,
+    RestartKernel, Run, RunAll, RunAndAdvance,
 };
 
 /// Whether the notebook is in command mode (navigating cells) or edit mode (editing a cell).
@@ -70,14 +80,22 @@ pub(crate) const CODE_BLOCK_INSET: f32 = MEDIUM_SPACING_SIZE;
 pub(crate) const CONTROL_SIZE: f32 = 20.0;
 
 pub fn init(cx: &mut App) {
+    CommandPaletteFilter::update_global(cx, |filter, _| {
+        filter.hide_action_types(&[TypeId::of::<OpenAsNotebook>()]);
+    });
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(NotebookEditor::open_as_notebook);
+    })
+    .detach();
+
     if cx.has_flag::<NotebookFeatureFlag>() || std::env::var("LOCAL_NOTEBOOK_DEV").is_ok() {
-        workspace::register_project_item::<NotebookEditor>(cx);
+        enable_notebooks(cx);
     }
 
     cx.observe_flag::<NotebookFeatureFlag, _>({
         move |flag, cx| {
             if *flag {
-                workspace::register_project_item::<NotebookEditor>(cx);
+                enable_notebooks(cx);
             } else {
                 // todo: there is no way to unregister a project item, so if the feature flag
                 // gets turned off they need to restart Zed.
@@ -87,6 +105,13 @@ pub fn init(cx: &mut App) {
     .detach();
 }
 
+fn enable_notebooks(cx: &mut App) {
+    workspace::register_project_item::<NotebookEditor>(cx);
+    CommandPaletteFilter::update_global(cx, |filter, _| {
+        filter.show_action_types(&[TypeId::of::<OpenAsNotebook>()]);
+    });
+}
+
 pub struct NotebookEditor {
     languages: Arc<LanguageRegistry>,
     project: Entity<Project>,
@@ -227,6 +252,51 @@ impl NotebookEditor {
         editor
     }
 
+    fn open_as_notebook(
+        workspace: &mut Workspace,
+        _: &OpenAsNotebook,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let Some(project_path) = workspace
+            .active_item(cx)
+            .and_then(|item| item.project_path(cx))
+        else {
+            return;
+        };
+        let Some(format) = project_path
+            .path
+            .extension()
+            .and_then(TextNotebookFormat::for_extension)
+        else {
+            workspace.show_error(
+                &anyhow::anyhow!(
+                    "Only percent-format scripts and Markdown files can be opened as notebooks"
+                ),
+                cx,
+            );
+            return;
+        };
+
+        let project = workspace.project().clone();
+        let notebook_item = NotebookItem::open(&project, project_path, Some(format), cx);
+        cx.spawn_in(window, async move |workspace, cx| {
+            let notebook_item = notebook_item.await?;
+            workspace.update_in(cx, |workspace, window, cx| {
+                let notebook_editor =
+                    cx.new(|cx| NotebookEditor::new(project, notebook_item, window, cx));
+                workspace.add_item_to_active_pane(
+                    Box::new(notebook_editor),
+                    None,
+                    true,
+                    window,
+                    cx,
+                );
+            })
+        })
+        .detach_and_prompt_err("Failed to open notebook", window, cx, |_, _, _| None);
+    }
+
     fn refresh_kernelspecs(&mut self, cx: &mut Context<Self>) {
         let store = ReplStore::global(cx);
         let project = self.project.clone();
@@ -1470,6 +1540,10 @@ pub struct NotebookItem {
     languages: Arc<LanguageRegistry>,
     // Raw notebook data
     notebook: nbformat::v4::Notebook,
+    /// How the notebook is stored, when it's a text notebook rather than a `.ipynb` file.
+    format: Option<TextNotebookFormat>,
+    /// The front matter of a text notebook, written back as is.
+    text_header: String,
     // Store our version of the notebook in memory (cell_order, cell_map)
     id: ProjectEntryId,
 }
@@ -1480,80 +1554,8 @@ impl project::ProjectItem for NotebookItem {
         path: &ProjectPath,
         cx: &mut App,
     ) -> Option<Task<anyhow::Result<Entity<Self>>>> {
-        let path = path.clone();
-        let project = project.clone();
-        let fs = project.read(cx).fs().clone();
-        let languages = project.read(cx).languages().clone();
-
         if path.path.extension().unwrap_or_default() == "ipynb" {
-            Some(cx.spawn(async move |cx| {
-                let abs_path = project
-                    .read_with(cx, |project, cx| project.absolute_path(&path, cx))
-                    .with_context(|| format!("finding the absolute path of {path:?}"))?;
-
-                // todo: watch for changes to the file
-                let buffer = project
-                    .update(cx, |project, cx| project.open_buffer(path.clone(), cx))
-                    .await?;
-                let file_content = buffer.read_with(cx, |buffer, _| buffer.text());
-
-                let notebook = if file_content.trim().is_empty() {
-                    nbformat::v4::Notebook {
-                        nbformat: 4,
-                        nbformat_minor: 5,
-                        cells: vec![],
-                        metadata: serde_json::from_str("{}").unwrap(),
-                    }
-                } else {
-                    let notebook = match nbformat::parse_notebook(&file_content) {
-                        Ok(nb) => nb,
-                        Err(_) => {
-                            // Pre-process to ensure IDs exist
-                            let mut json: serde_json::Value = serde_json::from_str(&file_content)?;
-                            if let Some(cells) =
-                                json.get_mut("cells").and_then(|c| c.as_array_mut())
-                            {
-                                for cell in cells {
-                                    if cell.get("id").is_none() {
-                                        cell["id"] =
-                                            serde_json::Value::String(Uuid::new_v4().to_string());
-                                    }
-                                }
-                            }
-                            let file_content = serde_json::to_string(&json)?;
-                            nbformat::parse_notebook(&file_content)?
-                        }
-                    };
-
-                    match notebook {
-                        nbformat::Notebook::V4(notebook) => notebook,
-                        // 4.1 - 4.4 are converted to 4.5
-                        nbformat::Notebook::Legacy(legacy_notebook) => {
-                            // TODO: Decide if we want to mutate the notebook by including Cell IDs
-                            // and any other conversions
-
-                            nbformat::upgrade_legacy_notebook(legacy_notebook)?
-                        }
-                        nbformat::Notebook::V3(v3_notebook) => {
-                            nbformat::upgrade_v3_notebook(v3_notebook)?
-                        }
-                    }
-                };
-
-                let id = project
-                    .update(cx, |project, cx| {
-                        project.entry_for_path(&path, cx).map(|entry| entry.id)
-                    })
-                    .context("Entry not found")?;
-
-                Ok(cx.new(|_| NotebookItem {
-                    path: abs_path,
-                    project_path: path,
-                    languages,
-                    notebook,
-                    id,
-                }))
-            }))
+            Some(Self::open(project, path.clone(), None, cx))
         } else {
             None
         }
@@ -1574,18 +1576,44 @@ impl project::ProjectItem for NotebookItem {
 }
 
 impl NotebookItem {
+    /// Opens the notebook at `path`, which is a text notebook in the given format or, without
+    /// one, a `.ipynb` file.
+    pub fn open(
+        project: &Entity<Project>,
+        path: ProjectPath,
+        format: Option<TextNotebookFormat>,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let project = project.clone();
+        let languages = project.read(cx).languages().clone();
+
+        cx.spawn(async move |cx| {
+            let abs_path = project
+                .read_with(cx, |project, cx| project.absolute_path(&path, cx))
+                .with_context(|| format!("finding the absolute path of {path:?}"))?;
+            let (notebook, text_header) =
+                load_notebook(&project, &path, &abs_path, format, cx).await?;
+
+            let id = project
+                .update(cx, |project, cx| {
+                    project.entry_for_path(&path, cx).map(|entry| entry.id)
+                })
+                .context("Entry not found")?;
+
+            Ok(cx.new(|_| NotebookItem {
+                path: abs_path,
+                project_path: path,
+                languages,
+                notebook,
+                format,
+                text_header,
+                id,
+            }))
+        })
+    }
+
     pub fn language_name(&self) -> Option<String> {
-        self.notebook
-            .metadata
-            .language_info
-            .as_ref()
-            .map(|l| l.name.clone())
-            .or(self
-                .notebook
-                .metadata
-                .kernelspec
-                .as_ref()
-                .and_then(|spec| spec.language.clone()))
+        notebook_language_name(&self.notebook)
     }
 
     pub fn notebook_language(&self) -> impl Future<Output = Option<Arc<Language>>> + use<> {
@@ -1602,6 +1630,368 @@ impl NotebookItem {
     }
 }
 
+fn notebook_language_name(notebook: &nbformat::v4::Notebook) -> Option<String> {
+    notebook
+        .metadata
+        .language_info
+        .as_ref()
+        .map(|l| l.name.clone())
+        .or(notebook
+            .metadata
+            .kernelspec
+            .as_ref()
+            .and_then(|spec| spec.language.clone()))
+}
+
+/// Reads the notebook at `path`, along with the front matter of text notebooks. When pairing is
+/// enabled and a text notebook declares it's paired with a `.ipynb` file, it takes its metadata
+/// and the outputs of its unchanged code cells from the `.ipynb` file of the same name.
+async fn load_notebook(
+    project: &Entity<Project>,
+    path: &ProjectPath,
+    abs_path: &Path,
+    format: Option<TextNotebookFormat>,
+    cx: &mut gpui::AsyncApp,
+) -> Result<(nbformat::v4::Notebook, String)> {
+    // todo: watch for changes to the file
+    let buffer = project
+        .update(cx, |project, cx| project.open_buffer(path.clone(), cx))
+        .await?;
+    let file_content = buffer.read_with(cx, |buffer, _| buffer.text());
+    let Some(format) = format else {
+        return Ok((parse_ipynb(&file_content)?, String::new()));
+    };
+
+    let text_notebook = TextNotebook::parse(&file_content, format);
+    let language = text_notebook
+        .language
+        .as_deref()
+        .or_else(|| super::language_for_extension(path.path.extension().unwrap_or_default()));
+    let mut notebook = notebook_from_text(&text_notebook, language)?;
+
+    let (fs, pair_notebooks) = cx.update(|cx| {
+        (
+            project.read(cx).fs().clone(),
+            JupyterSettings::get_global(cx).pair_notebooks,
+        )
+    });
+    let paired_path = abs_path.with_extension("ipynb");
+    let pairs_ipynb =
+        super::header_value(&text_notebook.header, "formats").is_some_and(|formats| {
+            super::paired_formats(formats)
+                .iter()
+                .any(|(_, format)| format.is_none())
+        });
+    if pair_notebooks && pairs_ipynb && fs.is_file(&paired_path).await {
+        let paired = fs.load(&paired_path).await?;
+        let paired = parse_ipynb(&paired).with_context(|| format!("parsing {paired_path:?}"))?;
+        merge_paired_notebook(&mut notebook, paired);
+    }
+    Ok((notebook, text_notebook.header))
+}
+
+fn parse_ipynb(file_content: &str) -> Result<nbformat::v4::Notebook> {
+    if file_content.trim().is_empty() {
+        return Ok(nbformat::v4::Notebook {
+            nbformat: 4,
+            nbformat_minor: 5,
+            cells: vec![],
+            metadata: serde_json::from_str("{}").unwrap(),
+        });
+    }
+
+    let notebook = match nbformat::parse_notebook(file_content) {
+        Ok(nb) => nb,
+        Err(_) => {
+            // Pre-process to ensure IDs exist
+            let mut json: serde_json::Value = serde_json::from_str(file_content)?;
+            if let Some(cells) = json.get_mut("cells").and_then(|c| c.as_array_mut()) {
+                for cell in cells {
+                    if cell.get("id").is_none() {
+                        cell["id"] = serde_json::Value::String(Uuid::new_v4().to_string());
+                    }
+                }
+            }
+            let file_content = serde_json::to_string(&json)?;
+            nbformat::parse_notebook(&file_content)?
+        }
+    };
+
+    match notebook {
+        nbformat::Notebook::V4(notebook) => Ok(notebook),
+        // 4.1 - 4.4 are converted to 4.5
+        nbformat::Notebook::Legacy(legacy_notebook) => {
+            // TODO: Decide if we want to mutate the notebook by including Cell IDs
+            // and any other conversions
+
+            Ok(nbformat::upgrade_legacy_notebook(legacy_notebook)?)
+        }
+        nbformat::Notebook::V3(v3_notebook) => Ok(nbformat::upgrade_v3_notebook(v3_notebook)?),
+    }
+}
+
+fn notebook_from_text(
+    text_notebook: &TextNotebook,
+    language: Option<&str>,
+) -> Result<nbformat::v4::Notebook> {
+    let cells = text_notebook
+        .cells
+        .iter()
+        .map(|cell| {
+            let source = source_lines(&cell.source);
+            let id = Uuid::new_v4().to_string();
+            let metadata = if cell.title.is_empty() {
+                serde_json::json!({})
+            } else {
+                serde_json::json!({ "title": cell.title })
+            };
+            match cell.kind {
+                TextCellKind::Code => serde_json::json!({
+                    "cell_type": "code",
+                    "id": id,
+                    "metadata": metadata,
+                    "execution_count": null,
+                    "source": source,
+                    "outputs": [],
+                }),
+                TextCellKind::Markdown => serde_json::json!({
+                    "cell_type": "markdown",
+                    "id": id,
+                    "metadata": metadata,
+                    "source": source,
+                }),
+                TextCellKind::Raw => serde_json::json!({
+                    "cell_type": "raw",
+                    "id": id,
+                    "metadata": metadata,
+                    "source": source,
+                }),
+            }
+        })
+        .collect::<Vec<_>>();
+    let metadata = match language {
+        Some(language) => serde_json::json!({ "language_info": { "name": language } }),
+        None => serde_json::json!({}),
+    };
+    let notebook = serde_json::json!({
+        "nbformat": 4,
+        "nbformat_minor": 5,
+        "metadata": metadata,
+        "cells": cells,
+    });
+    parse_ipynb(&notebook.to_string())
+}
+
+fn source_lines(source: &str) -> Vec<String> {
+    let mut lines = source
+        .split_inclusive('\n')
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    // Like Jupyter, leave the newline off the last line
+    if let Some(last) = lines.last_mut()
+        && last.ends_with('\n')
+    {
+        last.pop();
+    }
+    lines
+}
+
+/// Takes the metadata of a `.ipynb` file and the ids and outputs of its code cells whose source
+/// didn't change in the text notebook it's paired with.
+fn merge_paired_notebook(notebook: &mut nbformat::v4::Notebook, paired: nbformat::v4::Notebook) {
+    fn code_source(cell: &nbformat::v4::Cell) -> Option<String> {
+        match cell {
+            nbformat::v4::Cell::Code { source, .. } => Some(source.concat()),
+            _ => None,
+        }
+    }
+
+    let sources = notebook
+        .cells
+        .iter()
+        .filter_map(code_source)
+        .collect::<Vec<_>>();
+    let mut paired_cells = paired
+        .cells
+        .into_iter()
+        .filter(|cell| matches!(cell, nbformat::v4::Cell::Code { .. }))
+        .map(Some)
+        .collect::<Vec<_>>();
+    let paired_sources = paired_cells
+        .iter()
+        .flatten()
+        .filter_map(code_source)
+        .collect::<Vec<_>>();
+    let matches = super::match_cells(
+        &sources.iter().map(String::as_str).collect::<Vec<_>>(),
+        &paired_sources
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>(),
+    );
+
+    let code_cells = notebook
+        .cells
+        .iter_mut()
+        .filter(|cell| matches!(cell, nbformat::v4::Cell::Code { .. }));
+    for (cell, paired_index) in code_cells.zip(matches) {
+        let Some(paired_cell) = paired_index.and_then(|index| paired_cells[index].take()) else {
+            continue;
+        };
+        if let (
+            nbformat::v4::Cell::Code {
+                id,
+                execution_count,
+                outputs,
+                ..
+            },
+            nbformat::v4::Cell::Code {
+                id: paired_id,
+                execution_count: paired_execution_count,
+                outputs: paired_outputs,
+                ..
+            },
+        ) = (cell, paired_cell)
+        {
+            *id = paired_id;
+            *execution_count = paired_execution_count;
+            *outputs = paired_outputs;
+        }
+    }
+    notebook.metadata = paired.metadata;
+}
+
+fn text_notebook_from(notebook: &nbformat::v4::Notebook, header: &str) -> TextNotebook {
+    let cells = notebook
+        .cells
+        .iter()
+        .map(|cell| {
+            let (kind, metadata, source) = match cell {
+                nbformat::v4::Cell::Code {
+                    metadata, source, ..
+                } => (TextCellKind::Code, metadata, source),
+                nbformat::v4::Cell::Markdown {
+                    metadata, source, ..
+                } => (TextCellKind::Markdown, metadata, source),
+                nbformat::v4::Cell::Raw {
+                    metadata, source, ..
+                } => (TextCellKind::Raw, metadata, source),
+            };
+            let title = serde_json::to_value(metadata)
+                .ok()
+                .and_then(|metadata| Some(metadata.get("title")?.as_str()?.to_string()))
+                .unwrap_or_default();
+            TextCell {
+                kind,
+                title,
+                source: source.concat().trim_end_matches('\n').to_string(),
+            }
+        })
+        .collect();
+    TextNotebook {
+        header: header.to_string(),
+        language: notebook_language_name(notebook),
+        cells,
+    }
+}
+
+fn notebook_contents(
+    notebook: &nbformat::v4::Notebook,
+    format: Option<TextNotebookFormat>,
+    header: &str,
+) -> Result<String> {
+    match format {
+        Some(format) => Ok(text_notebook_from(notebook, header).render(format, None)),
+        None => serde_json::to_string_pretty(notebook).context("Failed to serialize notebook"),
+    }
+}
+
+/// The `formats` a notebook declares it's paired with, in Jupytext's metadata: the front matter
+/// of a text notebook, or the notebook metadata of a `.ipynb` file.
+fn declared_formats(
+    notebook: &nbformat::v4::Notebook,
+    format: Option<TextNotebookFormat>,
+    header: &str,
+) -> Option<String> {
+    if format.is_some() {
+        return super::header_value(header, "formats").map(str::to_string);
+    }
+    let metadata = serde_json::to_value(&notebook.metadata).ok()?;
+    let formats = metadata.get("jupytext")?.get("formats")?.as_str()?;
+    Some(formats.to_string())
+}
+
+/// Writes `notebook` to `path`, and when pairing is enabled, to the notebooks it declares it's
+/// paired with. The files are written through their buffers, so that editors open on them see
+/// the new contents instead of overwriting them later.
+async fn write_notebook(
+    project: &Entity<Project>,
+    path: &Path,
+    format: Option<TextNotebookFormat>,
+    header: &str,
+    notebook: &nbformat::v4::Notebook,
+    pair_notebooks: bool,
+    cx: &mut gpui::AsyncApp,
+) -> Result<()> {
+    let contents = notebook_contents(notebook, format, header)?;
+    save_through_buffer(project, path, |_| Ok(contents), cx).await?;
+    if !pair_notebooks {
+        return Ok(());
+    }
+    let Some(formats) = declared_formats(notebook, format, header) else {
+        return Ok(());
+    };
+
+    for (extension, paired_format) in super::paired_formats(&formats) {
+        let paired_path = path.with_extension(extension);
+        if paired_path == path {
+            continue;
+        }
+        save_through_buffer(
+            project,
+            &paired_path,
+            |existing| {
+                let header = match paired_format {
+                    // Keep the script's own front matter, which Jupyter doesn't know about
+                    Some(paired_format) => {
+                        let header = TextNotebook::parse(existing, paired_format).header;
+                        if header.is_empty() {
+                            super::formats_header(paired_format, &formats)
+                        } else {
+                            header
+                        }
+                    }
+                    None => String::new(),
+                };
+                notebook_contents(notebook, paired_format, &header)
+            },
+            cx,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Replaces the text of the file at `abs_path` with the `contents` computed from its current text,
+/// and saves it. The file is created if it doesn't exist.
+async fn save_through_buffer(
+    project: &Entity<Project>,
+    abs_path: &Path,
+    contents: impl FnOnce(&str) -> Result<String>,
+    cx: &mut gpui::AsyncApp,
+) -> Result<()> {
+    let buffer = project
+        .update(cx, |project, cx| project.open_local_buffer(abs_path, cx))
+        .await?;
+    let contents = buffer.read_with(cx, |buffer, _| contents(&buffer.text()))?;
+    buffer.update(cx, |buffer, cx| {
+        buffer.set_text(contents, cx);
+    });
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer, cx))
+        .await
+}
+
 impl EventEmitter<()> for NotebookItem {}
 
 impl EventEmitter<()> for NotebookEditor {}
@@ -1742,16 +2132,25 @@ impl Item for NotebookEditor {
         cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
         let notebook = self.to_notebook(cx);
-        let path = self.notebook_item.read(cx).path.clone();
-        let fs = project.read(cx).fs().clone();
+        let notebook_item = self.notebook_item.read(cx);
+        let path = notebook_item.path.clone();
+        let format = notebook_item.format;
+        let header = notebook_item.text_header.clone();
+        let pair_notebooks = JupyterSettings::get_global(cx).pair_notebooks;
 
         self.mark_as_saved(cx);
 
-        cx.spawn(async move |_this, _cx| {
-            let json =
-                serde_json::to_string_pretty(&notebook).context("Failed to serialize notebook")?;
-            fs.atomic_write(path, json).await?;
-            Ok(())
+        cx.spawn(async move |_this, cx| {
+            write_notebook(
+                &project,
+                &path,
+                format,
+                &header,
+                &notebook,
+                pair_notebooks,
+                cx,
+            )
+            .await
         })
     }
 
@@ -1763,18 +2162,30 @@ impl Item for NotebookEditor {
         cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
         let notebook = self.to_notebook(cx);
-        let fs = project.read(cx).fs().clone();
 
         let abs_path = project.read(cx).absolute_path(&path, cx);
+        // Saving to a script or Markdown file writes a text notebook
+        let format = path
+            .path
+            .extension()
+            .and_then(TextNotebookFormat::for_extension);
+        let header = self.notebook_item.read(cx).text_header.clone();
+        let pair_notebooks = JupyterSettings::get_global(cx).pair_notebooks;
 
         self.mark_as_saved(cx);
 
-        cx.spawn(async move |_this, _cx| {
+        cx.spawn(async move |_this, cx| {
             let abs_path = abs_path.context("Failed to get absolute path")?;
-            let json =
-                serde_json::to_string_pretty(&notebook).context("Failed to serialize notebook")?;
-            fs.atomic_write(abs_path, json).await?;
-            Ok(())
+            write_notebook(
+                &project,
+                &abs_path,
+                format,
+                &header,
+                &notebook,
+                pair_notebooks,
+                cx,
+            )
+            .await
         })
     }
 
@@ -1784,45 +2195,21 @@ impl Item for NotebookEditor {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Task<Result<()>> {
-        let project_path = self.notebook_item.read(cx).project_path.clone();
+        let notebook_item = self.notebook_item.read(cx);
+        let project_path = notebook_item.project_path.clone();
+        let abs_path = notebook_item.path.clone();
+        let format = notebook_item.format;
         let languages = self.languages.clone();
         let notebook_language = self.notebook_language.clone();
 
         cx.spawn_in(window, async move |this, cx| {
-            let buffer = this
-                .update(cx, |this, cx| {
-                    this.project
-                        .update(cx, |project, cx| project.open_buffer(project_path, cx))
-                })?
-                .await?;
-
-            let file_content = buffer.read_with(cx, |buffer, _| buffer.text());
-
-            let mut json: serde_json::Value = serde_json::from_str(&file_content)?;
-            if let Some(cells) = json.get_mut("cells").and_then(|c| c.as_array_mut()) {
-                for cell in cells {
-                    if cell.get("id").is_none() {
-                        cell["id"] = serde_json::Value::String(Uuid::new_v4().to_string());
-                    }
-                }
-            }
-            let file_content = serde_json::to_string(&json)?;
-
-            let notebook = nbformat::parse_notebook(&file_content);
-            let notebook = match notebook {
-                Ok(nbformat::Notebook::V4(notebook)) => notebook,
-                Ok(nbformat::Notebook::Legacy(legacy_notebook)) => {
-                    nbformat::upgrade_legacy_notebook(legacy_notebook)?
-                }
-                Ok(nbformat::Notebook::V3(v3_notebook)) => {
-                    nbformat::upgrade_v3_notebook(v3_notebook)?
-                }
-                Err(e) => {
-                    anyhow::bail!("Failed to parse notebook: {:?}", e);
-                }
-            };
+            let (notebook, text_header) =
+                load_notebook(&project, &project_path, &abs_path, format, cx).await?;
 
             this.update_in(cx, |this, window, cx| {
+                this.notebook_item.update(cx, |notebook_item, _| {
+                    notebook_item.text_header = text_header;
+                });
                 let mut cell_order = vec![];
                 let mut cell_map = HashMap::default();
 
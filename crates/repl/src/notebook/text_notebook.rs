@@ -0,0 +1,667 @@
+//! Reads and writes notebooks stored as plain text, in the formats used by Jupytext: percent
+//! scripts, where cells are delimited by `# %%` comments, and Markdown notebooks, where code
+//! cells are fenced code blocks.
+
+/// How a notebook is stored as plain text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextNotebookFormat {
+    /// A Python, R or Julia script whose cells start with `# %%` comments.
+    Percent,
+    /// A Markdown file whose fenced code blocks are code cells. MyST notebooks mark their code
+    /// cells with `{code-cell}` directives and separate Markdown cells with `+++` lines.
+    Markdown { myst: bool },
+}
+
+impl TextNotebookFormat {
+    pub fn for_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "py" | "r" | "R" | "jl" => Some(Self::Percent),
+            "md" | "markdown" => Some(Self::Markdown { myst: false }),
+            "myst" => Some(Self::Markdown { myst: true }),
+            _ => None,
+        }
+    }
+}
+
+/// The files a notebook is paired with, from the `formats` metadata that Jupytext writes, such as
+/// `ipynb,py:percent`, by the extension of each file. `None` stands for the `.ipynb` file. Formats
+/// that can't be written here, such as light scripts or files in other directories, are left out.
+pub fn paired_formats(formats: &str) -> Vec<(&str, Option<TextNotebookFormat>)> {
+    formats
+        .split(',')
+        .filter_map(|entry| {
+            let entry = entry.trim();
+            let (extension, format_name) = entry.split_once(':').unwrap_or((entry, ""));
+            let extension = extension.trim_start_matches('.');
+            let format = match (extension, format_name) {
+                ("ipynb", "") => None,
+                ("py" | "R" | "r" | "jl", "percent") => Some(TextNotebookFormat::Percent),
+                ("md", "" | "markdown") => Some(TextNotebookFormat::Markdown { myst: false }),
+                ("md", "myst") | ("myst", "" | "myst") => {
+                    Some(TextNotebookFormat::Markdown { myst: true })
+                }
+                _ => return None,
+            };
+            Some((extension, format))
+        })
+        .collect()
+}
+
+/// The front matter of a new text notebook, declaring the `formats` it's paired with so that
+/// saving either file updates the other.
+pub fn formats_header(format: TextNotebookFormat, formats: &str) -> String {
+    let prefix = match format {
+        TextNotebookFormat::Percent => "# ",
+        TextNotebookFormat::Markdown { .. } => "",
+    };
+    let formats = format!("    formats: {formats}");
+    ["---", "jupyter:", "  jupytext:", &formats, "---"]
+        .iter()
+        .map(|line| format!("{prefix}{line}\n"))
+        .collect()
+}
+
+/// The language of a percent script's code cells, by its extension.
+pub fn language_for_extension(extension: &str) -> Option<&'static str> {
+    match extension {
+        "py" => Some("python"),
+        "r" | "R" => Some("R"),
+        "jl" => Some("julia"),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextCellKind {
+    Code,
+    Markdown,
+    Raw,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextCell {
+    pub kind: TextCellKind,
+    /// The title following a percent script's `# %%` marker.
+    pub title: String,
+    pub source: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextNotebook {
+    /// The front matter before the first cell, kept as is: a commented YAML block in percent
+    /// scripts, or a YAML block in Markdown notebooks.
+    pub header: String,
+    /// The language of the code cells, for Markdown notebooks whose code blocks name it.
+    pub language: Option<String>,
+    pub cells: Vec<TextCell>,
+}
+
+impl TextNotebook {
+    pub fn parse(text: &str, format: TextNotebookFormat) -> Self {
+        match format {
+            TextNotebookFormat::Percent => parse_percent(text),
+            TextNotebookFormat::Markdown { myst } => parse_markdown(text, myst),
+        }
+    }
+
+    pub fn render(&self, format: TextNotebookFormat, language: Option<&str>) -> String {
+        let cells = match format {
+            TextNotebookFormat::Percent => render_percent(&self.cells),
+            TextNotebookFormat::Markdown { myst } => {
+                let language = language.or(self.language.as_deref()).unwrap_or_default();
+                render_markdown(&self.cells, myst, language)
+            }
+        };
+        let mut text = self.header.clone();
+        if !text.is_empty() && !cells.is_empty() {
+            text.push('\n');
+        }
+        text.push_str(&cells);
+        text
+    }
+}
+
+/// The value of `key` in `header`, the front matter of a text notebook, such as the `language`
+/// of its kernel or the `formats` it's paired with. Only the first occurrence of the key counts.
+pub fn header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    header.lines().find_map(|line| {
+        let line = line.trim_start_matches('#').trim();
+        let value = line.strip_prefix(key)?.strip_prefix(':')?.trim();
+        let value = value.trim_matches(|c| c == '"' || c == '\'');
+        (!value.is_empty()).then_some(value)
+    })
+}
+
+/// Splits off the front matter delimited by `---` lines at the start of `text`, with
+/// `prefix` in front of each line.
+fn split_header<'a>(text: &'a str, prefix: &str) -> (&'a str, &'a str) {
+    let delimiter = format!("{prefix}---");
+    let mut end = 0;
+    for (index, line) in text.split_inclusive('\n').enumerate() {
+        end += line.len();
+        if index == 0 {
+            if line.trim_end() != delimiter {
+                break;
+            }
+        } else if line.trim_end() == delimiter {
+            return (&text[..end], text[end..].trim_start_matches('\n'));
+        } else if !line.starts_with(prefix.trim_end()) {
+            break;
+        }
+    }
+    ("", text)
+}
+
+fn parse_percent(text: &str) -> TextNotebook {
+    let (header, text) = split_header(text, "# ");
+    let mut notebook = TextNotebook {
+        header: header.to_string(),
+        ..Default::default()
+    };
+
+    let mut current: Option<(TextCellKind, String, Vec<&str>)> = None;
+    let mut leading_lines = Vec::new();
+    for line in text.lines() {
+        let marker = line
+            .strip_prefix("# %%")
+            .or_else(|| line.strip_prefix("#%%"));
+        if let Some(marker) = marker {
+            if let Some(cell) = current.take() {
+                push_percent_cell(&mut notebook.cells, cell);
+            } else if leading_lines
+                .iter()
+                .any(|line: &&str| !line.trim().is_empty())
+            {
+                // Code before the first marker is a cell of its own
+                push_percent_cell(
+                    &mut notebook.cells,
+                    (TextCellKind::Code, String::new(), leading_lines.clone()),
+                );
+            }
+            let marker = marker.trim();
+            let (kind, title) = if let Some(title) = marker.strip_suffix("[markdown]") {
+                (TextCellKind::Markdown, title)
+            } else if let Some(title) = marker.strip_suffix("[md]") {
+                (TextCellKind::Markdown, title)
+            } else if let Some(title) = marker.strip_suffix("[raw]") {
+                (TextCellKind::Raw, title)
+            } else {
+                (TextCellKind::Code, marker)
+            };
+            current = Some((kind, title.trim().to_string(), Vec::new()));
+        } else if let Some((_, _, lines)) = current.as_mut() {
+            lines.push(line);
+        } else {
+            leading_lines.push(line);
+        }
+    }
+    match current {
+        Some(cell) => push_percent_cell(&mut notebook.cells, cell),
+        None if leading_lines.iter().any(|line| !line.trim().is_empty()) => push_percent_cell(
+            &mut notebook.cells,
+            (TextCellKind::Code, String::new(), leading_lines),
+        ),
+        None => {}
+    }
+    notebook
+}
+
+fn push_percent_cell(
+    cells: &mut Vec<TextCell>,
+    (kind, title, lines): (TextCellKind, String, Vec<&str>),
+) {
+    let lines = lines.into_iter().map(|line| match kind {
+        TextCellKind::Code => line,
+        TextCellKind::Markdown | TextCellKind::Raw => line
+            .strip_prefix("# ")
+            .or_else(|| line.strip_prefix('#'))
+            .unwrap_or(line),
+    });
+    let source = lines.collect::<Vec<_>>().join("\n");
+    cells.push(TextCell {
+        kind,
+        title,
+        source: source.trim_matches('\n').to_string(),
+    });
+}
+
+fn render_percent(cells: &[TextCell]) -> String {
+    let mut text = String::new();
+    for cell in cells {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text.push_str("# %%");
+        if !cell.title.is_empty() {
+            text.push(' ');
+            text.push_str(&cell.title);
+        }
+        match cell.kind {
+            TextCellKind::Code => {}
+            TextCellKind::Markdown => text.push_str(" [markdown]"),
+            TextCellKind::Raw => text.push_str(" [raw]"),
+        }
+        text.push('\n');
+        for line in cell.source.lines() {
+            match cell.kind {
+                TextCellKind::Code => {}
+                TextCellKind::Markdown | TextCellKind::Raw if line.is_empty() => text.push('#'),
+                TextCellKind::Markdown | TextCellKind::Raw => text.push_str("# "),
+            }
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+    text
+}
+
+fn parse_markdown(text: &str, myst: bool) -> TextNotebook {
+    let (header, text) = split_header(text, "");
+    let myst = myst || text.lines().any(|line| line.starts_with("```{code-cell}"));
+    let mut notebook = TextNotebook {
+        header: header.to_string(),
+        // The kernel's language, so that code blocks in other languages stay in Markdown cells
+        language: header_value(header, "language").map(str::to_string),
+        ..Default::default()
+    };
+
+    let mut markdown = Vec::new();
+    let mut lines = text.lines();
+    while let Some(line) = lines.next() {
+        let fence_len = line.len() - line.trim_start_matches('`').len();
+        let info = line[fence_len..].trim();
+        let code_language = if fence_len < 3 {
+            None
+        } else if myst {
+            info.strip_prefix("{code-cell}")
+                .map(|language| Some(language.trim()))
+                .or_else(|| info.starts_with("{raw-cell}").then_some(None))
+        } else {
+            let is_code = !info.is_empty()
+                && !info.contains(char::is_whitespace)
+                && notebook
+                    .language
+                    .as_deref()
+                    .is_none_or(|language| language.eq_ignore_ascii_case(info));
+            is_code.then_some(Some(info))
+        };
+        let raw_end = (!myst && line.trim() == "<!-- #raw -->").then_some("<!-- #endraw -->");
+
+        if let Some(language) = code_language {
+            push_markdown_cell(&mut notebook.cells, &mut markdown);
+            let fence = &line[..fence_len];
+            let source = lines
+                .by_ref()
+                .take_while(|line| line.trim_end() != fence)
+                .collect::<Vec<_>>()
+                .join("\n");
+            let kind = match language {
+                Some(language) => {
+                    if notebook.language.is_none() && !language.is_empty() {
+                        notebook.language = Some(language.to_string());
+                    }
+                    TextCellKind::Code
+                }
+                None => TextCellKind::Raw,
+            };
+            notebook.cells.push(TextCell {
+                kind,
+                title: String::new(),
+                source,
+            });
+        } else if let Some(raw_end) = raw_end {
+            push_markdown_cell(&mut notebook.cells, &mut markdown);
+            let source = lines
+                .by_ref()
+                .take_while(|line| line.trim() != raw_end)
+                .collect::<Vec<_>>()
+                .join("\n");
+            notebook.cells.push(TextCell {
+                kind: TextCellKind::Raw,
+                title: String::new(),
+                source,
+            });
+        } else if fence_len >= 3 {
+            // Any other code block is part of the Markdown, even if it contains cell markers
+            let fence = &line[..fence_len];
+            markdown.push(line);
+            for line in lines.by_ref() {
+                markdown.push(line);
+                if line.trim_end() == fence {
+                    break;
+                }
+            }
+        } else if myst && line.trim() == "+++" {
+            push_markdown_cell(&mut notebook.cells, &mut markdown);
+        } else {
+            markdown.push(line);
+        }
+    }
+    push_markdown_cell(&mut notebook.cells, &mut markdown);
+    notebook
+}
+
+fn push_markdown_cell(cells: &mut Vec<TextCell>, lines: &mut Vec<&str>) {
+    let source = lines.join("\n");
+    lines.clear();
+    let source = source.trim_matches('\n');
+    if !source.trim().is_empty() {
+        cells.push(TextCell {
+            kind: TextCellKind::Markdown,
+            title: String::new(),
+            source: source.to_string(),
+        });
+    }
+}
+
+fn render_markdown(cells: &[TextCell], myst: bool, language: &str) -> String {
+    let mut text = String::new();
+    let mut previous_kind = None;
+    for cell in cells {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        // Consecutive Markdown cells would otherwise be read back as one
+        if myst && cell.kind == TextCellKind::Markdown && previous_kind == Some(cell.kind) {
+            text.push_str("+++\n\n");
+        }
+        match cell.kind {
+            TextCellKind::Markdown => {
+                text.push_str(&cell.source);
+                text.push('\n');
+            }
+            TextCellKind::Code | TextCellKind::Raw => {
+                let fence = code_fence(&cell.source);
+                let (start, end) = match (cell.kind, myst) {
+                    (TextCellKind::Code, true) => {
+                        (format!("{fence}{{code-cell}} {language}"), fence.clone())
+                    }
+                    (TextCellKind::Code, false) => (format!("{fence}{language}"), fence.clone()),
+                    (_, true) => (format!("{fence}{{raw-cell}}"), fence.clone()),
+                    (_, false) => ("<!-- #raw -->".to_string(), "<!-- #endraw -->".to_string()),
+                };
+                text.push_str(start.trim_end());
+                text.push('\n');
+                if !cell.source.is_empty() {
+                    text.push_str(&cell.source);
+                    text.push('\n');
+                }
+                text.push_str(&end);
+                text.push('\n');
+            }
+        }
+        previous_kind = Some(cell.kind);
+    }
+    text
+}
+
+/// A fence longer than any run of backticks in `source`, so that it can't be closed early.
+fn code_fence(source: &str) -> String {
+    let longest_run = source
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    "`".repeat(longest_run.max(2) + 1)
+}
+
+/// Pairs each code cell of a text notebook with a code cell of the notebook it's paired with,
+/// so that the outputs of unchanged cells can be kept. Cells are paired by their source, in
+/// order, and each paired cell is used at most once.
+pub fn match_cells(sources: &[&str], paired_sources: &[&str]) -> Vec<Option<usize>> {
+    let mut used = vec![false; paired_sources.len()];
+    let mut next = 0;
+    sources
+        .iter()
+        .map(|source| {
+            let source = source.trim_end();
+            // Prefer the next cell in order, so that repeated cells keep their own outputs
+            let index = (next..paired_sources.len())
+                .chain(0..next)
+                .find(|&index| !used[index] && paired_sources[index].trim_end() == source)?;
+            used[index] = true;
+            next = index + 1;
+            Some(index)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn cell(kind: TextCellKind, title: &str, source: &str) -> TextCell {
+        TextCell {
+            kind,
+            title: title.to_string(),
+            source: source.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_percent_format() {
+        let text = indoc! {"
+            # ---
+            # jupyter:
+            #   kernelspec:
+            #     name: python3
+            # ---
+
+            # %% [markdown]
+            # # Analysis
+            #
+            # Loads the data.
+
+            # %% Imports
+            import pandas as pd
+
+            # %%
+            df = pd.read_csv(\"data.csv\")
+            df.head()
+        "};
+        let notebook = TextNotebook::parse(text, TextNotebookFormat::Percent);
+        assert_eq!(
+            notebook.header,
+            "# ---\n# jupyter:\n#   kernelspec:\n#     name: python3\n# ---\n"
+        );
+        assert_eq!(
+            notebook.cells,
+            vec![
+                cell(TextCellKind::Markdown, "", "# Analysis\n\nLoads the data."),
+                cell(TextCellKind::Code, "Imports", "import pandas as pd"),
+                cell(
+                    TextCellKind::Code,
+                    "",
+                    "df = pd.read_csv(\"data.csv\")\ndf.head()"
+                ),
+            ]
+        );
+        assert_eq!(notebook.render(TextNotebookFormat::Percent, None), text);
+    }
+
+    #[test]
+    fn test_percent_format_without_markers() {
+        let notebook =
+            TextNotebook::parse("import os\n\n# %%\nprint(1)\n", TextNotebookFormat::Percent);
+        assert_eq!(
+            notebook.cells,
+            vec![
+                cell(TextCellKind::Code, "", "import os"),
+                cell(TextCellKind::Code, "", "print(1)"),
+            ]
+        );
+
+        let notebook = TextNotebook::parse("print(1)\n", TextNotebookFormat::Percent);
+        assert_eq!(
+            notebook.cells,
+            vec![cell(TextCellKind::Code, "", "print(1)")]
+        );
+    }
+
+    #[test]
+    fn test_markdown_format() {
+        let text = indoc! {"
+            ---
+            jupyter:
+              kernelspec:
+                name: python3
+            ---
+
+            # Analysis
+
+            ```python
+            import pandas as pd
+            ```
+
+            Some `inline code`.
+
+            <!-- #raw -->
+            raw text
+            <!-- #endraw -->
+        "};
+        let notebook = TextNotebook::parse(text, TextNotebookFormat::Markdown { myst: false });
+        assert_eq!(
+            notebook.header,
+            "---\njupyter:\n  kernelspec:\n    name: python3\n---\n"
+        );
+        assert_eq!(notebook.language.as_deref(), Some("python"));
+        assert_eq!(
+            notebook.cells,
+            vec![
+                cell(TextCellKind::Markdown, "", "# Analysis"),
+                cell(TextCellKind::Code, "", "import pandas as pd"),
+                cell(TextCellKind::Markdown, "", "Some `inline code`."),
+                cell(TextCellKind::Raw, "", "raw text"),
+            ]
+        );
+        assert_eq!(
+            notebook.render(TextNotebookFormat::Markdown { myst: false }, None),
+            text
+        );
+    }
+
+    #[test]
+    fn test_myst_format() {
+        let text = indoc! {"
+            # Analysis
+
+            +++
+
+            More notes.
+
+            ```{code-cell} ipython3
+            print(\"```\")
+            ```
+        "};
+        let notebook = TextNotebook::parse(text, TextNotebookFormat::Markdown { myst: false });
+        assert_eq!(notebook.language.as_deref(), Some("ipython3"));
+        assert_eq!(
+            notebook.cells,
+            vec![
+                cell(TextCellKind::Markdown, "", "# Analysis"),
+                cell(TextCellKind::Markdown, "", "More notes."),
+                cell(TextCellKind::Code, "", "print(\"```\")"),
+            ]
+        );
+        assert_eq!(
+            notebook.render(TextNotebookFormat::Markdown { myst: true }, None),
+            indoc! {"
+                # Analysis
+
+                +++
+
+                More notes.
+
+                ````{code-cell} ipython3
+                print(\"```\")
+                ````
+            "}
+        );
+    }
+
+    #[test]
+    fn test_markdown_format_keeps_other_code_blocks() {
+        let text = indoc! {"
+            ---
+            jupyter:
+              kernelspec:
+                language: python
+                name: python3
+            ---
+
+            Install it with:
+
+            ```bash
+            pip install pandas
+            ```
+
+            ```python
+            import pandas as pd
+            ```
+        "};
+        let notebook = TextNotebook::parse(text, TextNotebookFormat::Markdown { myst: false });
+        assert_eq!(notebook.language.as_deref(), Some("python"));
+        assert_eq!(
+            notebook.cells,
+            vec![
+                cell(
+                    TextCellKind::Markdown,
+                    "",
+                    "Install it with:\n\n```bash\npip install pandas\n```"
+                ),
+                cell(TextCellKind::Code, "", "import pandas as pd"),
+            ]
+        );
+        assert_eq!(
+            notebook.render(TextNotebookFormat::Markdown { myst: false }, None),
+            text
+        );
+
+        let notebook = TextNotebook::parse(
+            "```text\n+++\n```\n\n```{code-cell} python\n1\n```\n",
+            TextNotebookFormat::Markdown { myst: true },
+        );
+        assert_eq!(
+            notebook.cells,
+            vec![
+                cell(TextCellKind::Markdown, "", "```text\n+++\n```"),
+                cell(TextCellKind::Code, "", "1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_paired_formats() {
+        let header = formats_header(TextNotebookFormat::Percent, "ipynb,py:percent");
+        assert_eq!(
+            header,
+            "# ---\n# jupyter:\n#   jupytext:\n#     formats: ipynb,py:percent\n# ---\n"
+        );
+        let formats = header_value(&header, "formats").unwrap();
+        assert_eq!(
+            paired_formats(formats),
+            vec![("ipynb", None), ("py", Some(TextNotebookFormat::Percent))]
+        );
+        assert_eq!(
+            paired_formats("ipynb, md:myst, py:light, notebooks//ipynb"),
+            vec![
+                ("ipynb", None),
+                ("md", Some(TextNotebookFormat::Markdown { myst: true }))
+            ]
+        );
+        assert_eq!(
+            header_value("---\nlanguage: \"R\"\n---\n", "language"),
+            Some("R")
+        );
+    }
+
+    #[test]
+    fn test_match_cells() {
+        assert_eq!(
+            match_cells(&["a", "b", "a", "c"], &["a", "x", "a", "b"]),
+            vec![Some(0), Some(3), Some(2), None]
+        );
+        assert_eq!(match_cells(&["a\n"], &["a"]), vec![Some(0)]);
+    }
+}
@@ -47,7 +47,7 @@ mod markdown;
 use markdown::MarkdownView;
 
 mod table;
-use table::TableView;
+pub(crate) use table::TableView;
 
 mod json;
 use json::JsonView;
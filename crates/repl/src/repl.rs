@@ -8,6 +8,7 @@ mod repl_sessions_ui;
 mod repl_settings;
 mod repl_store;
 mod session;
+mod variable_explorer;
 
 use std::{sync::Arc, time::Duration};
 
@@ -25,12 +26,16 @@ pub use crate::repl_sessions_ui::{
 pub use crate::repl_settings::ReplSettings;
 pub use crate::repl_store::ReplStore;
 pub use crate::session::Session;
+pub use crate::variable_explorer::{
+    DataFrameView, RefreshVariables, ToggleVariableExplorer, VariableExplorer,
+};
 
 pub const KERNEL_DOCS_URL: &str = "https://zed.dev/docs/repl#changing-kernels";
 
 pub fn init(fs: Arc<dyn Fs>, cx: &mut App) {
     set_dispatcher(zed_dispatcher(cx));
     repl_sessions_ui::init(cx);
+    variable_explorer::init(cx);
     ReplStore::init(fs, cx);
 }
 
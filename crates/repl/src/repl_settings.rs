@@ -1,4 +1,4 @@
-use settings::{RegisterSetting, Settings};
+use settings::{DockSide, RegisterSetting, Settings};
 
 /// Settings for configuring REPL display and behavior.
 #[derive(Clone, Debug, RegisterSetting)]
@@ -27,6 +27,10 @@ pub struct ReplSettings {
     ///
     /// Default: 0
     pub output_max_height_lines: usize,
+    /// Where to dock the REPL variable explorer.
+    ///
+    /// Default: right
+    pub variable_explorer_dock: DockSide,
 }
 
 impl Settings for ReplSettings {
@@ -39,6 +43,7 @@ impl Settings for ReplSettings {
             inline_output: repl.inline_output.unwrap_or(true),
             inline_output_max_length: repl.inline_output_max_length.unwrap_or(50),
             output_max_height_lines: repl.output_max_height_lines.unwrap_or(0),
+            variable_explorer_dock: repl.variable_explorer_dock.unwrap_or(DockSide::Right),
         }
     }
 }
@@ -29,6 +29,7 @@ use project::InlayId;
 enum ReplExecutedRange {}
 
 use futures::FutureExt as _;
+use futures::channel::oneshot;
 use gpui::{
     Context, Entity, EventEmitter, Render, Subscription, Task, WeakEntity, Window, div, prelude::*,
 };
@@ -36,7 +37,7 @@ use language::Point;
 use project::Fs;
 use runtimelib::{
     ExecuteRequest, ExecutionState, InputReply, InterruptRequest, JupyterMessage,
-    JupyterMessageContent, KernelInfoRequest, ReplyStatus, ShutdownRequest,
+    JupyterMessageContent, KernelInfoRequest, MimeBundle, ReplyStatus, ShutdownRequest,
 };
 use settings::Settings as _;
 use std::{env::temp_dir, ops::Range, sync::Arc, time::Duration};
@@ -53,10 +54,29 @@ pub struct Session {
     blocks: HashMap<String, EditorBlock>,
     result_inlays: HashMap<String, (InlayId, Range<Anchor>, usize)>,
     next_inlay_id: usize,
+    pending_queries: HashMap<String, PendingQuery>,
 
     _subscriptions: Vec<Subscription>,
 }
 
+/// A request sent on behalf of Zed rather than the user, such as the variable explorer
+/// listing the kernel's variables. Its outputs are collected instead of being shown.
+struct PendingQuery {
+    outputs: Vec<MimeBundle>,
+    error: Option<String>,
+    tx: oneshot::Sender<anyhow::Result<Vec<MimeBundle>>>,
+}
+
+impl PendingQuery {
+    fn finish(self) {
+        let result = match self.error {
+            Some(error) => Err(anyhow::anyhow!(error)),
+            None => Ok(self.outputs),
+        };
+        self.tx.send(result).ok();
+    }
+}
+
 struct EditorBlock {
     code_range: Range<Anchor>,
     invalidation_anchor: Anchor,
@@ -257,6 +277,7 @@ impl Session {
             blocks: HashMap::default(),
             result_inlays: HashMap::default(),
             next_inlay_id: 0,
+            pending_queries: HashMap::default(),
             kernel_specification,
             _subscriptions: vec![subscription],
         };
@@ -616,6 +637,40 @@ impl Session {
         cx.notify();
     }
 
+    /// Runs `code` in the kernel without recording it in the kernel's history, and returns the
+    /// rich outputs it displayed.
+    pub fn query(
+        &mut self,
+        code: String,
+        cx: &mut Context<Self>,
+    ) -> Task<anyhow::Result<Vec<MimeBundle>>> {
+        if !matches!(self.kernel, Kernel::RunningKernel(_)) {
+            return Task::ready(Err(anyhow::anyhow!("kernel is not running")));
+        }
+
+        let execute_request = ExecuteRequest {
+            code,
+            store_history: false,
+            allow_stdin: false,
+            ..ExecuteRequest::default()
+        };
+        let message: JupyterMessage = execute_request.into();
+        let (tx, rx) = oneshot::channel();
+        self.pending_queries.insert(
+            message.header.msg_id.clone(),
+            PendingQuery {
+                outputs: Vec::new(),
+                error: None,
+                tx,
+            },
+        );
+        if let Err(error) = self.send(message, cx) {
+            return Task::ready(Err(error));
+        }
+
+        cx.background_spawn(async move { rx.await.context("kernel went away")? })
+    }
+
     pub fn execute(
         &mut self,
         code: String,
@@ -833,6 +888,11 @@ impl Session {
             repl_session_id = cx.entity_id().to_string(),
         );
 
+        if !matches!(kernel, Kernel::RunningKernel(_)) {
+            // Dropping the senders fails the queries waiting on them.
+            self.pending_queries.clear();
+        }
+
         self.kernel = kernel;
     }
 
@@ -915,6 +975,8 @@ impl Session {
 
 pub enum SessionEvent {
     Shutdown(WeakEntity<Editor>),
+    /// Code run from the editor finished executing, so the kernel's state may have changed.
+    ExecutionFinished,
 }
 
 impl EventEmitter<SessionEvent> for Session {}
@@ -1016,8 +1078,37 @@ impl KernelSession for Session {
             _ => {}
         }
 
+        if let Some(query) = self.pending_queries.get_mut(parent_message_id) {
+            match &message.content {
+                JupyterMessageContent::DisplayData(result) => {
+                    query.outputs.push(result.data.clone());
+                }
+                JupyterMessageContent::ExecuteResult(result) => {
+                    query.outputs.push(result.data.clone());
+                }
+                JupyterMessageContent::ErrorOutput(error) => {
+                    query.error = Some(format!("{}: {}", error.ename, error.evalue));
+                }
+                JupyterMessageContent::Status(status)
+                    if matches!(status.execution_state, ExecutionState::Idle) =>
+                {
+                    if let Some(query) = self.pending_queries.remove(parent_message_id) {
+                        query.finish();
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
+
         if let Some(block) = self.blocks.get_mut(parent_message_id) {
             block.handle_message(message, window, cx);
+
+            if let JupyterMessageContent::Status(status) = &message.content
+                && matches!(status.execution_state, ExecutionState::Idle)
+            {
+                cx.emit(SessionEvent::ExecutionFinished);
+            }
         }
     }
 
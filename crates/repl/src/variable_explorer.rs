@@ -0,0 +1,759 @@
+//! The variable explorer lists the variables defined in the Jupyter kernel attached to the
+//! active editor, and opens pandas and polars dataframes in a table.
+//!
+//! The kernel is queried by running small helper snippets that display their results as
+//! JSON, so only Python kernels are supported for now.
+
+use anyhow::{Context as _, Result};
+use editor::Editor;
+use gpui::{
+    Action, App, Entity, EventEmitter, FocusHandle, Focusable, Pixels, SharedString, Subscription,
+    Task, WeakEntity, Window, actions, px,
+};
+use runtimelib::{MimeBundle, MimeType};
+use serde::Deserialize;
+use settings::{DockSide, Settings as _};
+use ui::{ListItem, ListItemSpacing, Tab, Tooltip, prelude::*};
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+    item::{Item, ItemEvent},
+};
+
+use crate::kernels::Kernel;
+use crate::outputs::TableView;
+use crate::repl_settings::ReplSettings;
+use crate::repl_store::ReplStore;
+use crate::session::{Session, SessionEvent};
+
+actions!(
+    repl,
+    [
+        /// Toggles focus on the variable explorer for the active editor's kernel.
+        ToggleVariableExplorer,
+        /// Queries the kernel for its variables again.
+        RefreshVariables,
+    ]
+);
+
+const VARIABLE_EXPLORER_KEY: &str = "VariableExplorer";
+
+/// The number of rows fetched when opening a dataframe.
+const DATAFRAME_ROW_LIMIT: usize = 1000;
+
+/// Displays the user's variables as `application/json`, skipping modules, functions and classes.
+const LIST_VARIABLES_CODE: &str = r#"
+def _zed_list_variables():
+    import inspect
+    import reprlib
+    from IPython import get_ipython
+    from IPython.display import display
+
+    shell = get_ipython()
+    hidden = getattr(shell, "user_ns_hidden", {})
+    # Only the start of large containers and strings is formatted
+    limits = reprlib.Repr()
+    limits.maxstring = 200
+    limits.maxother = 200
+    variables = []
+    for name, value in list(shell.user_ns.items()):
+        if name.startswith("_") or name in hidden:
+            continue
+        if inspect.ismodule(value) or inspect.isroutine(value) or inspect.isclass(value):
+            continue
+        kind = type(value)
+        shape = getattr(value, "shape", None)
+        if isinstance(shape, tuple):
+            shape = " x ".join(str(size) for size in shape)
+        else:
+            try:
+                shape = str(len(value))
+            except Exception:
+                shape = None
+        try:
+            preview = " ".join(limits.repr(value).split())
+        except Exception:
+            preview = ""
+        variables.append({
+            "name": name,
+            "type": kind.__name__,
+            "shape": shape,
+            "preview": preview[:200],
+            "table": kind.__module__.split(".")[0] in ("pandas", "polars")
+                and kind.__name__ in ("DataFrame", "Series"),
+        })
+    display({"application/json": variables}, raw=True)
+
+_zed_list_variables()
+del _zed_list_variables
+"#;
+
+/// Displays the head of a dataframe as a Tabular Data Resource, which [`TableView`] renders.
+const SHOW_DATAFRAME_CODE: &str = r#"
+def _zed_show_dataframe(name, limit):
+    import json
+    from IPython import get_ipython
+    from IPython.display import display
+
+    value = get_ipython().user_ns[name]
+    if type(value).__name__ == "Series":
+        value = value.to_frame()
+    value = value.head(limit)
+    if type(value).__module__.split(".")[0] == "polars":
+        try:
+            value = value.to_pandas()
+        except Exception:
+            table = {
+                "schema": {
+                    "fields": [{"name": column, "type": "string"} for column in value.columns],
+                },
+                "data": [
+                    {column: str(cell) for column, cell in row.items()}
+                    for row in value.to_dicts()
+                ],
+            }
+            display({"application/vnd.dataresource+json": table}, raw=True)
+            return
+    table = json.loads(value.to_json(orient="table", default_handler=str))
+    display({"application/vnd.dataresource+json": table}, raw=True)
+"#;
+
+fn show_dataframe_code(name: &str) -> String {
+    // A JSON string is also a valid Python string literal.
+    let name = serde_json::Value::String(name.to_string());
+    format!(
+        "{SHOW_DATAFRAME_CODE}\n_zed_show_dataframe({name}, {DATAFRAME_ROW_LIMIT})\ndel _zed_show_dataframe\n"
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct Variable {
+    name: SharedString,
+    #[serde(rename = "type")]
+    type_name: SharedString,
+    shape: Option<SharedString>,
+    #[serde(default)]
+    preview: SharedString,
+    /// Whether the variable is a dataframe or series that can be opened in a table.
+    #[serde(default)]
+    table: bool,
+}
+
+fn parse_variables(outputs: &[MimeBundle]) -> Result<Vec<Variable>> {
+    let value = outputs
+        .iter()
+        .flat_map(|bundle| bundle.content.iter())
+        .find_map(|content| match content {
+            MimeType::Json(value) => Some(value.clone()),
+            _ => None,
+        })
+        .context("the kernel did not list its variables")?;
+    let mut variables: Vec<Variable> = serde_json::from_value(value)?;
+    variables.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(variables)
+}
+
+fn supports_variable_explorer(session: &Session) -> bool {
+    session
+        .kernel_specification
+        .language()
+        .eq_ignore_ascii_case("python")
+}
+
+/// Runs one of the Python helper snippets above in the session's kernel, refusing kernels of
+/// other languages. Jupyter has no kernel-agnostic request for listing variables or fetching a
+/// dataframe (`inspect_request` only returns a text description), so both the variable list
+/// and every per-variable query are limited to Python kernels.
+fn query_python(
+    session: &Entity<Session>,
+    code: String,
+    cx: &mut App,
+) -> Task<Result<Vec<MimeBundle>>> {
+    session.update(cx, |session, cx| {
+        if !supports_variable_explorer(session) {
+            return Task::ready(Err(anyhow::anyhow!(
+                "The variable explorer does not support {} kernels yet.",
+                session.kernel_specification.language()
+            )));
+        }
+        session.query(code, cx)
+    })
+}
+
+fn active_editor(workspace: &Workspace, cx: &App) -> Option<Entity<Editor>> {
+    workspace.active_item(cx)?.act_as::<Editor>(cx)
+}
+
+pub(crate) fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleVariableExplorer, window, cx| {
+            if workspace.panel::<VariableExplorer>(cx).is_none() {
+                let panel = VariableExplorer::new(workspace, window, cx);
+                workspace.add_panel(panel, window, cx);
+            }
+            workspace.toggle_panel_focus::<VariableExplorer>(window, cx);
+        });
+    })
+    .detach();
+}
+
+pub struct VariableExplorer {
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    active: bool,
+    session: Option<Entity<Session>>,
+    variables: Vec<Variable>,
+    error: Option<SharedString>,
+    loading: bool,
+    refresh_task: Task<()>,
+    session_subscription: Option<Subscription>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl VariableExplorer {
+    fn new(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let workspace_handle = cx.entity();
+        let active_editor = active_editor(workspace, cx);
+        cx.new(|cx| {
+            let subscriptions = vec![
+                cx.subscribe_in(
+                    &workspace_handle,
+                    window,
+                    |this, _, event: &workspace::Event, _, cx| {
+                        if let workspace::Event::ActiveItemChanged = event {
+                            this.update_session(cx);
+                        }
+                    },
+                ),
+                cx.observe(&ReplStore::global(cx), |this, _, cx| {
+                    this.update_session(cx)
+                }),
+            ];
+
+            let mut this = Self {
+                workspace: workspace.weak_handle(),
+                focus_handle: cx.focus_handle(),
+                active: false,
+                session: None,
+                variables: Vec::new(),
+                error: None,
+                loading: false,
+                refresh_task: Task::ready(()),
+                session_subscription: None,
+                _subscriptions: subscriptions,
+            };
+            this.follow_editor(active_editor, cx);
+            this
+        })
+    }
+
+    fn update_session(&mut self, cx: &mut Context<Self>) {
+        let active_editor = self
+            .workspace
+            .upgrade()
+            .and_then(|workspace| active_editor(workspace.read(cx), cx));
+        self.follow_editor(active_editor, cx);
+    }
+
+    /// Follows the session of the active editor. Other kinds of items, such as opened
+    /// dataframes, keep the current session.
+    fn follow_editor(&mut self, active_editor: Option<Entity<Editor>>, cx: &mut Context<Self>) {
+        let store = ReplStore::global(cx);
+        let session = match active_editor {
+            Some(editor) => store.read(cx).get_session(editor.entity_id()).cloned(),
+            None => self
+                .session
+                .clone()
+                .filter(|session| store.read(cx).sessions().any(|s| s == session)),
+        };
+        if session == self.session {
+            return;
+        }
+
+        self.session_subscription = session.as_ref().map(|session| {
+            cx.subscribe(session, |this, _, event, cx| {
+                if let SessionEvent::ExecutionFinished = event {
+                    this.refresh(cx);
+                }
+            })
+        });
+        self.session = session;
+        self.variables.clear();
+        self.refresh(cx);
+    }
+
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        self.error = None;
+        let Some(session) = self.session.clone().filter(|_| self.active) else {
+            self.loading = false;
+            self.refresh_task = Task::ready(());
+            cx.notify();
+            return;
+        };
+
+        let session_ref = session.read(cx);
+        if !supports_variable_explorer(session_ref) {
+            self.variables.clear();
+            self.error = Some(
+                format!(
+                    "The variable explorer does not support {} kernels yet.",
+                    session_ref.kernel_specification.language()
+                )
+                .into(),
+            );
+            cx.notify();
+            return;
+        }
+        if !matches!(session_ref.kernel, Kernel::RunningKernel(_)) {
+            self.variables.clear();
+            cx.notify();
+            return;
+        }
+
+        let query = query_python(&session, LIST_VARIABLES_CODE.to_string(), cx);
+        self.loading = true;
+        self.refresh_task = cx.spawn(async move |this, cx| {
+            let variables = query.await.and_then(|outputs| parse_variables(&outputs));
+            this.update(cx, |this, cx| {
+                this.loading = false;
+                match variables {
+                    Ok(variables) => this.variables = variables,
+                    Err(error) => this.error = Some(error.to_string().into()),
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+        cx.notify();
+    }
+
+    fn refresh_variables(&mut self, _: &RefreshVariables, _: &mut Window, cx: &mut Context<Self>) {
+        self.refresh(cx);
+    }
+
+    fn open_dataframe(&mut self, variable: &Variable, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(session), Some(workspace)) = (self.session.clone(), self.workspace.upgrade())
+        else {
+            return;
+        };
+
+        workspace.update(cx, |workspace, cx| {
+            let existing = workspace
+                .active_pane()
+                .read(cx)
+                .items()
+                .filter_map(|item| item.downcast::<DataFrameView>())
+                .find(|view| {
+                    let view = view.read(cx);
+                    view.name == variable.name && view.session == session.downgrade()
+                });
+
+            if let Some(existing) = existing {
+                existing.update(cx, |view, cx| {
+                    view.shape = variable.shape.clone();
+                    view.load(window, cx);
+                });
+                workspace.activate_item(&existing, true, true, window, cx);
+            } else {
+                let view = cx.new(|cx| {
+                    DataFrameView::new(
+                        variable.name.clone(),
+                        variable.shape.clone(),
+                        session.downgrade(),
+                        window,
+                        cx,
+                    )
+                });
+                workspace.add_item_to_active_pane(Box::new(view), None, true, window, cx);
+            }
+        });
+    }
+
+    fn render_variable(
+        &self,
+        ix: usize,
+        variable: &Variable,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let details = match &variable.shape {
+            Some(shape) => format!("{} ({shape})", variable.type_name),
+            None => variable.type_name.to_string(),
+        };
+
+        ListItem::new(("variable", ix))
+            .spacing(ListItemSpacing::Sparse)
+            .child(
+                v_flex()
+                    .w_full()
+                    .overflow_hidden()
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .justify_between()
+                            .child(Label::new(variable.name.clone()).buffer_font(cx).truncate())
+                            .child(
+                                Label::new(details)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .single_line(),
+                            ),
+                    )
+                    .child(
+                        Label::new(variable.preview.clone())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .buffer_font(cx)
+                            .truncate(),
+                    ),
+            )
+            .tooltip(Tooltip::text(variable.preview.clone()))
+            .when(variable.table, |item| {
+                let variable = variable.clone();
+                item.end_slot(
+                    IconButton::new(("open-dataframe", ix), IconName::Maximize)
+                        .icon_size(IconSize::Small)
+                        .tooltip(Tooltip::text("Open in Table")),
+                )
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.open_dataframe(&variable, window, cx)
+                }))
+            })
+    }
+
+    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let focus_handle = self.focus_handle.clone();
+        h_flex()
+            .px_2()
+            .h(Tab::container_height(cx))
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(Label::new("Variables").size(LabelSize::Small))
+            .child(
+                IconButton::new("refresh-variables", IconName::RotateCw)
+                    .icon_size(IconSize::Small)
+                    .disabled(self.session.is_none() || self.loading)
+                    .tooltip(move |_, cx| {
+                        Tooltip::for_action_in(
+                            "Refresh Variables",
+                            &RefreshVariables,
+                            &focus_handle,
+                            cx,
+                        )
+                    })
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        this.refresh_variables(&RefreshVariables, window, cx)
+                    })),
+            )
+    }
+
+    fn render_message(&self, message: SharedString, color: Color) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .p_4()
+            .items_center()
+            .justify_center()
+            .child(Label::new(message).color(color))
+    }
+}
+
+impl Panel for VariableExplorer {
+    fn persistent_name() -> &'static str {
+        "Variable Explorer"
+    }
+
+    fn panel_key() -> &'static str {
+        VARIABLE_EXPLORER_KEY
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        match ReplSettings::get_global(cx).variable_explorer_dock {
+            DockSide::Left => DockPosition::Left,
+            DockSide::Right => DockPosition::Right,
+        }
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        let fs = ReplStore::global(cx).read(cx).fs().clone();
+        settings::update_settings_file(fs, cx, move |settings, _| {
+            let dock = match position {
+                DockPosition::Left | DockPosition::Bottom => DockSide::Left,
+                DockPosition::Right => DockSide::Right,
+            };
+            settings.repl.get_or_insert_default().variable_explorer_dock = Some(dock);
+        });
+    }
+
+    fn default_size(&self, _: &Window, _: &App) -> Pixels {
+        px(320.)
+    }
+
+    fn icon(&self, _: &Window, _: &App) -> Option<IconName> {
+        Some(IconName::Code)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Variable Explorer")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleVariableExplorer)
+    }
+
+    fn set_active(&mut self, active: bool, _: &mut Window, cx: &mut Context<Self>) {
+        if self.active != active {
+            self.active = active;
+            self.refresh(cx);
+        }
+    }
+
+    fn activation_priority(&self) -> u32 {
+        9
+    }
+}
+
+impl Focusable for VariableExplorer {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for VariableExplorer {}
+
+impl Render for VariableExplorer {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = if let Some(error) = self.error.clone() {
+            self.render_message(error, Color::Error).into_any_element()
+        } else if self.session.is_none() {
+            self.render_message(
+                "Run code in an editor to start a kernel session.".into(),
+                Color::Muted,
+            )
+            .into_any_element()
+        } else if self.variables.is_empty() {
+            let message = if self.loading {
+                "Loading variables…"
+            } else {
+                "No variables defined"
+            };
+            self.render_message(message.into(), Color::Muted)
+                .into_any_element()
+        } else {
+            v_flex()
+                .id("variables")
+                .size_full()
+                .overflow_y_scroll()
+                .p_1()
+                .children(
+                    self.variables
+                        .iter()
+                        .enumerate()
+                        .map(|(ix, variable)| self.render_variable(ix, variable, cx)),
+                )
+                .into_any_element()
+        };
+
+        v_flex()
+            .id("variable-explorer")
+            .key_context("VariableExplorer")
+            .size_full()
+            .overflow_hidden()
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::refresh_variables))
+            .child(self.render_toolbar(cx))
+            .child(content)
+    }
+}
+
+/// The head of a dataframe or series from the kernel, shown in a table.
+pub struct DataFrameView {
+    name: SharedString,
+    shape: Option<SharedString>,
+    session: WeakEntity<Session>,
+    table: Option<Entity<TableView>>,
+    error: Option<SharedString>,
+    focus_handle: FocusHandle,
+    load_task: Task<()>,
+}
+
+impl DataFrameView {
+    fn new(
+        name: SharedString,
+        shape: Option<SharedString>,
+        session: WeakEntity<Session>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let mut this = Self {
+            name,
+            shape,
+            session,
+            table: None,
+            error: None,
+            focus_handle: cx.focus_handle(),
+            load_task: Task::ready(()),
+        };
+        this.load(window, cx);
+        this
+    }
+
+    fn load(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let code = show_dataframe_code(&self.name);
+        let query = match self.session.upgrade() {
+            Some(session) => query_python(&session, code, cx),
+            None => Task::ready(Err(anyhow::anyhow!("the kernel session has ended"))),
+        };
+
+        self.error = None;
+        self.load_task = cx.spawn_in(window, async move |this, cx| {
+            let outputs = query.await;
+            this.update_in(cx, |this, window, cx| {
+                let table = outputs.and_then(|outputs| {
+                    outputs
+                        .iter()
+                        .flat_map(|bundle| bundle.content.iter())
+                        .find_map(|content| match content {
+                            MimeType::DataTable(table) => Some(table.clone()),
+                            _ => None,
+                        })
+                        .context("the kernel did not return a table")
+                });
+                match table {
+                    Ok(table) => {
+                        this.table = Some(cx.new(|cx| TableView::new(&table, window, cx)));
+                    }
+                    Err(error) => this.error = Some(error.to_string().into()),
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+        cx.notify();
+    }
+}
+
+impl EventEmitter<ItemEvent> for DataFrameView {}
+
+impl Focusable for DataFrameView {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for DataFrameView {
+    type Event = ItemEvent;
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        self.name.clone()
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::Code))
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn to_item_events(event: &Self::Event, f: &mut dyn FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
+
+impl Render for DataFrameView {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let summary = match &self.shape {
+            Some(shape) => format!("{} ({shape}), first {DATAFRAME_ROW_LIMIT} rows", self.name),
+            None => format!("{}, first {DATAFRAME_ROW_LIMIT} rows", self.name),
+        };
+
+        v_flex()
+            .size_full()
+            .track_focus(&self.focus_handle)
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                h_flex()
+                    .px_2()
+                    .h(Tab::container_height(cx))
+                    .justify_between()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .child(Label::new(summary).size(LabelSize::Small))
+                    .child(
+                        IconButton::new("reload-dataframe", IconName::RotateCw)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Reload"))
+                            .on_click(cx.listener(|this, _, window, cx| this.load(window, cx))),
+                    ),
+            )
+            .child(
+                div()
+                    .id("dataframe")
+                    .size_full()
+                    .p_2()
+                    .overflow_y_scroll()
+                    .map(|this| match (&self.error, &self.table) {
+                        (Some(error), _) => {
+                            this.child(Label::new(error.clone()).color(Color::Error))
+                        }
+                        (None, Some(table)) => this.child(table.clone()),
+                        (None, None) => this.child(Label::new("Loading…").color(Color::Muted)),
+                    }),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_variables() {
+        let mut bundle = MimeBundle::default();
+        bundle.content.push(MimeType::Json(json!([
+            {"name": "df", "type": "DataFrame", "shape": "3 x 2", "preview": "a b", "table": true},
+            {"name": "count", "type": "int", "shape": null, "preview": "3", "table": false},
+        ])));
+
+        let variables = parse_variables(&[bundle]).unwrap();
+        assert_eq!(
+            variables,
+            vec![
+                Variable {
+                    name: "count".into(),
+                    type_name: "int".into(),
+                    shape: None,
+                    preview: "3".into(),
+                    table: false,
+                },
+                Variable {
+                    name: "df".into(),
+                    type_name: "DataFrame".into(),
+                    shape: Some("3 x 2".into()),
+                    preview: "a b".into(),
+                    table: true,
+                },
+            ]
+        );
+
+        assert!(parse_variables(&[]).is_err());
+    }
+
+    #[test]
+    fn test_show_dataframe_code_quotes_name() {
+        let code = show_dataframe_code("my \"frame\"");
+        assert!(code.ends_with(
+            "_zed_show_dataframe(\"my \\\"frame\\\"\", 1000)\ndel _zed_show_dataframe\n"
+        ));
+    }
+}
@@ -0,0 +1,612 @@
+//! Linux Landlock sandbox integration.
+//!
+//! This module confines a command using two kernel facilities that are
+//! available to unprivileged processes on modern Linux:
+//!
+//! - [Landlock](https://docs.kernel.org/userspace-api/landlock.html), which
+//!   restricts filesystem writes to a set of directory subtrees.
+//! - A fresh user + network namespace (`unshare(CLONE_NEWUSER |
+//!   CLONE_NEWNET)`), which leaves the command with nothing but an
+//!   unconfigured loopback device. On kernels where unprivileged user
+//!   namespaces are disabled we fall back to Landlock's TCP restrictions
+//!   (ABI v4+), and refuse to run the command if neither is available.
+//!
+//! Unlike Seatbelt there is no `sandbox-exec(1)` equivalent we can shell
+//! out to: the restrictions have to be applied by the process that is
+//! about to `exec` the command. The integration therefore wraps a shell
+//! invocation by:
+//!
+//! 1. Writing the requested policy (writable directories, permissions and
+//!    the original program/args) to a file in a private temporary
+//!    directory (a [`LandlockPolicyFile`], which cleans itself up when
+//!    dropped).
+//! 2. Returning the program/args needed to re-launch a caller-provided
+//!    launcher binary (Zed itself) as `<launcher> --linux-sandbox <path>`.
+//! 3. In that launcher process, [`exec_sandboxed`] reads the policy back,
+//!    applies the restrictions to itself and `exec`s the original command,
+//!    which inherits them.
+//!
+//! Reads are permitted everywhere; writes are restricted to a caller-
+//! provided list of directories; network access and unrestricted writes
+//! must be opted into per command — the same semantics as
+//! [`crate::macos_seatbelt`].
+
+use std::{
+    convert::Infallible,
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    os::{
+        fd::{AsRawFd, FromRawFd, OwnedFd},
+        unix::{
+            fs::{OpenOptionsExt, PermissionsExt},
+            process::CommandExt,
+        },
+    },
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
+use tempfile::TempDir;
+
+/// Command-line flag the launcher binary must recognize and forward to
+/// [`exec_sandboxed`], followed by the policy file path.
+pub const LAUNCHER_FLAG: &str = "--linux-sandbox";
+
+/// Per-command relaxations of the default Landlock sandbox.
+///
+/// All-false is the default, fully-sandboxed run. Setting any field
+/// requires user approval before the command is launched.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SandboxPermissions {
+    /// Allow network access for the command.
+    pub allow_network: bool,
+    /// Allow unrestricted filesystem writes.
+    pub allow_fs_write: bool,
+}
+
+/// Everything the launcher process needs to confine and run a command.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct LandlockPolicy {
+    writable_directories: Vec<PathBuf>,
+    permissions: SandboxPermissions,
+    program: String,
+    args: Vec<String>,
+}
+
+/// A sandbox policy written to a temporary path on disk, suitable for
+/// `<launcher> --linux-sandbox <path>`. The file is deleted when this is
+/// dropped.
+pub struct LandlockPolicyFile {
+    /// A directory only the current user can enter, holding the policy.
+    /// The shared temp directory is writable by every process on the host
+    /// (including sandboxed commands), so the policy must not live there
+    /// directly where it could be swapped out before the launcher reads it.
+    _dir: TempDir,
+    /// Path to the temporary policy file on disk.
+    path: PathBuf,
+}
+
+impl LandlockPolicyFile {
+    fn new(policy: &LandlockPolicy) -> Result<Self> {
+        let dir = tempfile::Builder::new()
+            .prefix("zed-landlock-")
+            .permissions(std::fs::Permissions::from_mode(0o700))
+            .tempdir()
+            .context("failed to create temporary Landlock policy directory")?;
+        let path = dir.path().join("policy.json");
+
+        let contents = serde_json::to_vec(policy).context("failed to serialize Landlock policy")?;
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&path)
+            .context("failed to create temporary Landlock policy file")?;
+        file.write_all(&contents)
+            .context("failed to write Landlock policy")?;
+        file.flush().context("failed to flush Landlock policy")?;
+
+        Ok(Self { _dir: dir, path })
+    }
+}
+
+/// Whether the running kernel supports Landlock, i.e. whether commands can
+/// be confined at all on this host.
+pub fn is_supported() -> bool {
+    landlock_abi_version().is_ok_and(|abi| abi >= 1)
+}
+
+/// Wrap a process invocation so it runs under Landlock with restrictions
+/// built from `permissions`.
+///
+/// Returns the new program and arguments to execute, along with a
+/// [`LandlockPolicyFile`] that **must** be kept alive for the duration of
+/// the command (the file is deleted when dropped, and the launcher reads
+/// it when the child process starts up).
+///
+/// # Arguments
+/// * `launcher` - A binary that handles [`LAUNCHER_FLAG`] by calling
+///   [`exec_sandboxed`] (typically the running Zed executable).
+/// * `program` - The program to invoke (typically a shell, e.g. `"/bin/sh"`,
+///   but anything that takes its arguments via `argv` works).
+/// * `args` - The full argument list that would have been passed to
+///   `program`.
+/// * `writable_directories` - Directory subtrees where the command is
+///   allowed to write when `permissions.allow_fs_write` is false. Pass
+///   the project's worktree paths here, not the working directory of the
+///   command (the working directory is model-controlled, and using it as
+///   the writable scope would let the model write outside the project).
+/// * `permissions` - Sandbox relaxations requested for this command.
+///
+/// # Returns
+/// A tuple of `(program, args, policy_file)` where `policy_file` must be
+/// kept alive.
+pub fn wrap_invocation(
+    launcher: &Path,
+    program: &str,
+    args: &[String],
+    writable_directories: &[&Path],
+    permissions: SandboxPermissions,
+) -> Result<(String, Vec<String>, LandlockPolicyFile)> {
+    let policy = LandlockPolicy {
+        writable_directories: writable_directories
+            .iter()
+            .map(|path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf()))
+            .collect(),
+        permissions,
+        program: program.to_string(),
+        args: args.to_vec(),
+    };
+    let policy_file = LandlockPolicyFile::new(&policy)?;
+
+    let launcher = launcher
+        .to_str()
+        .with_context(|| {
+            format!(
+                "sandbox launcher path contains invalid UTF-8: {}",
+                launcher.display()
+            )
+        })?
+        .to_string();
+    let policy_path = policy_file
+        .path
+        .to_str()
+        .with_context(|| {
+            format!(
+                "Landlock policy file path contains invalid UTF-8: {}",
+                policy_file.path.display()
+            )
+        })?
+        .to_string();
+
+    Ok((
+        launcher,
+        vec![LAUNCHER_FLAG.to_string(), policy_path],
+        policy_file,
+    ))
+}
+
+/// Entry point for the launcher process: read the policy at `policy_path`,
+/// confine the current process accordingly and `exec` the wrapped command.
+///
+/// Must be called while the process is still single-threaded, since
+/// `unshare(CLONE_NEWUSER)` refuses to run in multithreaded processes.
+/// Only returns on failure; the command is never run unconfined.
+pub fn exec_sandboxed(policy_path: &Path) -> Result<Infallible> {
+    let contents = std::fs::read(policy_path).with_context(|| {
+        format!(
+            "failed to read Landlock policy file: {}",
+            policy_path.display()
+        )
+    })?;
+    let policy: LandlockPolicy =
+        serde_json::from_slice(&contents).context("failed to parse Landlock policy")?;
+    let abi = landlock_abi_version().context("Landlock is not supported by this kernel")?;
+
+    let mut ruleset = Ruleset::default();
+    if !policy.permissions.allow_network {
+        match isolate_network() {
+            Ok(()) => {}
+            Err(_) if abi >= 4 => ruleset.handled_access_net = ACCESS_NET_ALL,
+            Err(error) => {
+                return Err(anyhow!(error).context(
+                    "network isolation requires unprivileged user namespaces or Landlock ABI v4",
+                ));
+            }
+        }
+    }
+    if !policy.permissions.allow_fs_write {
+        ruleset.handled_access_fs = access_fs_write_for_abi(abi);
+        ruleset.writable_directories = policy.writable_directories.clone();
+    }
+    ruleset.restrict_self()?;
+
+    let error = Command::new(&policy.program).args(&policy.args).exec();
+    Err(anyhow!(error).context(format!("failed to execute {}", policy.program)))
+}
+
+/// Move the current process into fresh user and network namespaces, keeping
+/// its uid/gid mapped to themselves so file ownership looks unchanged.
+fn isolate_network() -> io::Result<()> {
+    let uid = unsafe { libc::geteuid() };
+    let gid = unsafe { libc::getegid() };
+    if unsafe { libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // Writing gid_map requires denying setgroups first; older kernels don't
+    // have the file at all.
+    match std::fs::write("/proc/self/setgroups", "deny") {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error),
+        _ => {}
+    }
+    std::fs::write("/proc/self/uid_map", format!("{uid} {uid} 1"))?;
+    std::fs::write("/proc/self/gid_map", format!("{gid} {gid} 1"))?;
+    Ok(())
+}
+
+// Landlock UAPI definitions (linux/landlock.h), which `libc` doesn't expose.
+const LANDLOCK_CREATE_RULESET_VERSION: u32 = 1 << 0;
+const LANDLOCK_RULE_PATH_BENEATH: libc::c_int = 1;
+
+const ACCESS_FS_WRITE_FILE: u64 = 1 << 1;
+const ACCESS_FS_REMOVE_DIR: u64 = 1 << 4;
+const ACCESS_FS_REMOVE_FILE: u64 = 1 << 5;
+const ACCESS_FS_MAKE_CHAR: u64 = 1 << 6;
+const ACCESS_FS_MAKE_DIR: u64 = 1 << 7;
+const ACCESS_FS_MAKE_REG: u64 = 1 << 8;
+const ACCESS_FS_MAKE_SOCK: u64 = 1 << 9;
+const ACCESS_FS_MAKE_FIFO: u64 = 1 << 10;
+const ACCESS_FS_MAKE_BLOCK: u64 = 1 << 11;
+const ACCESS_FS_MAKE_SYM: u64 = 1 << 12;
+/// ABI v2: linking or renaming a file into a different directory.
+const ACCESS_FS_REFER: u64 = 1 << 13;
+/// ABI v3: truncating a file.
+const ACCESS_FS_TRUNCATE: u64 = 1 << 14;
+
+/// Rights that may be granted on a single (non-directory) file.
+const ACCESS_FS_FILE_WRITE: u64 = ACCESS_FS_WRITE_FILE | ACCESS_FS_TRUNCATE;
+
+const ACCESS_NET_BIND_TCP: u64 = 1 << 0;
+const ACCESS_NET_CONNECT_TCP: u64 = 1 << 1;
+const ACCESS_NET_ALL: u64 = ACCESS_NET_BIND_TCP | ACCESS_NET_CONNECT_TCP;
+
+/// Device files that stay writable even when writes are otherwise confined
+/// (needed for redirections like `2>/dev/null` and for talking to the pty).
+const ALWAYS_WRITABLE_DEVICES: &[&str] = &["/dev/null", "/dev/zero", "/dev/full", "/dev/tty"];
+/// Directories of pty devices, which `/dev/stdout` and friends resolve to.
+const ALWAYS_WRITABLE_DEVICE_DIRECTORIES: &[&str] = &["/dev/pts"];
+
+#[repr(C)]
+struct LandlockRulesetAttr {
+    handled_access_fs: u64,
+    handled_access_net: u64,
+}
+
+#[repr(C, packed)]
+struct LandlockPathBeneathAttr {
+    allowed_access: u64,
+    parent_fd: i32,
+}
+
+/// The filesystem write rights we can restrict for a given Landlock ABI.
+///
+/// Reads and executes are deliberately left unhandled, so they stay allowed
+/// everywhere. On ABI v1 cross-directory renames are always denied by the
+/// kernel, since it can't express a grant for them.
+fn access_fs_write_for_abi(abi: u32) -> u64 {
+    let mut access = ACCESS_FS_WRITE_FILE
+        | ACCESS_FS_REMOVE_DIR
+        | ACCESS_FS_REMOVE_FILE
+        | ACCESS_FS_MAKE_CHAR
+        | ACCESS_FS_MAKE_DIR
+        | ACCESS_FS_MAKE_REG
+        | ACCESS_FS_MAKE_SOCK
+        | ACCESS_FS_MAKE_FIFO
+        | ACCESS_FS_MAKE_BLOCK
+        | ACCESS_FS_MAKE_SYM;
+    if abi >= 2 {
+        access |= ACCESS_FS_REFER;
+    }
+    if abi >= 3 {
+        access |= ACCESS_FS_TRUNCATE;
+    }
+    access
+}
+
+fn landlock_abi_version() -> io::Result<u32> {
+    let abi = unsafe {
+        libc::syscall(
+            libc::SYS_landlock_create_ruleset,
+            std::ptr::null::<LandlockRulesetAttr>(),
+            0usize,
+            LANDLOCK_CREATE_RULESET_VERSION,
+        )
+    };
+    if abi < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(abi as u32)
+    }
+}
+
+/// The Landlock restrictions to apply to the current process.
+#[derive(Default)]
+struct Ruleset {
+    handled_access_fs: u64,
+    handled_access_net: u64,
+    writable_directories: Vec<PathBuf>,
+}
+
+impl Ruleset {
+    fn restrict_self(&self) -> Result<()> {
+        if self.handled_access_fs == 0 && self.handled_access_net == 0 {
+            return Ok(());
+        }
+
+        let attr = LandlockRulesetAttr {
+            handled_access_fs: self.handled_access_fs,
+            handled_access_net: self.handled_access_net,
+        };
+        let ruleset_fd = unsafe {
+            libc::syscall(
+                libc::SYS_landlock_create_ruleset,
+                &attr as *const LandlockRulesetAttr,
+                std::mem::size_of::<LandlockRulesetAttr>(),
+                0u32,
+            )
+        };
+        if ruleset_fd < 0 {
+            return Err(io::Error::last_os_error()).context("failed to create Landlock ruleset");
+        }
+        let ruleset_fd = unsafe { OwnedFd::from_raw_fd(ruleset_fd as i32) };
+
+        if self.handled_access_fs != 0 {
+            for directory in &self.writable_directories {
+                add_path_rule(&ruleset_fd, directory, self.handled_access_fs)?;
+            }
+            let device_access = self.handled_access_fs & ACCESS_FS_FILE_WRITE;
+            for path in ALWAYS_WRITABLE_DEVICES
+                .iter()
+                .chain(ALWAYS_WRITABLE_DEVICE_DIRECTORIES)
+            {
+                add_path_rule(&ruleset_fd, Path::new(path), device_access)?;
+            }
+        }
+
+        if unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) } != 0 {
+            return Err(io::Error::last_os_error()).context("failed to set no_new_privs");
+        }
+        let result = unsafe {
+            libc::syscall(
+                libc::SYS_landlock_restrict_self,
+                ruleset_fd.as_raw_fd(),
+                0u32,
+            )
+        };
+        if result != 0 {
+            return Err(io::Error::last_os_error()).context("failed to apply Landlock ruleset");
+        }
+        Ok(())
+    }
+}
+
+/// Allow `access` beneath `path`. Paths that don't exist are skipped, since
+/// there is nothing to write to there yet.
+fn add_path_rule(ruleset_fd: &OwnedFd, path: &Path, access: u64) -> Result<()> {
+    let file = match OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_PATH | libc::O_CLOEXEC)
+        .open(path)
+    {
+        Ok(file) => file,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(error) => {
+            return Err(error).with_context(|| format!("failed to open {}", path.display()));
+        }
+    };
+    let access = if is_directory(&file) {
+        access
+    } else {
+        access & ACCESS_FS_FILE_WRITE
+    };
+
+    let attr = LandlockPathBeneathAttr {
+        allowed_access: access,
+        parent_fd: file.as_raw_fd(),
+    };
+    let result = unsafe {
+        libc::syscall(
+            libc::SYS_landlock_add_rule,
+            ruleset_fd.as_raw_fd(),
+            LANDLOCK_RULE_PATH_BENEATH,
+            &attr as *const LandlockPathBeneathAttr,
+            0u32,
+        )
+    };
+    if result != 0 {
+        return Err(io::Error::last_os_error())
+            .with_context(|| format!("failed to add Landlock rule for {}", path.display()));
+    }
+    Ok(())
+}
+
+fn is_directory(file: &File) -> bool {
+    file.metadata().is_ok_and(|metadata| metadata.is_dir())
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::disallowed_methods,
+    reason = "tests run sandboxed commands synchronously to verify the applied ruleset"
+)]
+mod tests {
+    use super::*;
+
+    /// Run `script` under `sh -c` with the filesystem restrictions applied in
+    /// the child, mirroring what [`exec_sandboxed`] does before `exec`.
+    fn run_with_write_restrictions(
+        writable_directories: &[&Path],
+        script: &str,
+    ) -> std::process::Output {
+        let ruleset = Ruleset {
+            handled_access_fs: access_fs_write_for_abi(landlock_abi_version().unwrap()),
+            handled_access_net: 0,
+            writable_directories: writable_directories
+                .iter()
+                .map(|path| path.to_path_buf())
+                .collect(),
+        };
+        let mut command = Command::new("/bin/sh");
+        command.args(["-c", script]);
+        unsafe {
+            command.pre_exec(move || {
+                ruleset
+                    .restrict_self()
+                    .map_err(|error| io::Error::other(error.to_string()))
+            });
+        }
+        command.output().expect("failed to run sandboxed command")
+    }
+
+    #[test]
+    fn test_wrap_invocation_structure() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (program, args, policy_file) = wrap_invocation(
+            Path::new("/usr/libexec/zed-editor"),
+            "/bin/sh",
+            &["-c".to_string(), "echo hello".to_string()],
+            &[temp_dir.path()],
+            SandboxPermissions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(program, "/usr/libexec/zed-editor");
+        assert_eq!(args[0], LAUNCHER_FLAG);
+        assert_eq!(Path::new(&args[1]), policy_file.path);
+        assert_eq!(args.len(), 2);
+    }
+
+    #[test]
+    fn test_policy_file_round_trips_command_and_permissions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (_, args, _policy_file) = wrap_invocation(
+            Path::new("/usr/libexec/zed-editor"),
+            "/bin/sh",
+            &["-c".to_string(), "echo 'multi\nline'".to_string()],
+            &[temp_dir.path()],
+            SandboxPermissions {
+                allow_network: true,
+                allow_fs_write: false,
+            },
+        )
+        .unwrap();
+
+        let policy: LandlockPolicy =
+            serde_json::from_slice(&std::fs::read(&args[1]).unwrap()).unwrap();
+        assert_eq!(
+            policy,
+            LandlockPolicy {
+                writable_directories: vec![temp_dir.path().canonicalize().unwrap()],
+                permissions: SandboxPermissions {
+                    allow_network: true,
+                    allow_fs_write: false,
+                },
+                program: "/bin/sh".to_string(),
+                args: vec!["-c".to_string(), "echo 'multi\nline'".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_policy_file_is_deleted_when_dropped() {
+        let (_, args, policy_file) = wrap_invocation(
+            Path::new("/usr/libexec/zed-editor"),
+            "/bin/sh",
+            &[],
+            &[],
+            SandboxPermissions::default(),
+        )
+        .unwrap();
+
+        assert!(Path::new(&args[1]).exists());
+        drop(policy_file);
+        assert!(!Path::new(&args[1]).exists());
+        assert!(!Path::new(&args[1]).parent().unwrap().exists());
+    }
+
+    #[test]
+    fn test_policy_file_is_private_to_the_current_user() {
+        let (_, args, _policy_file) = wrap_invocation(
+            Path::new("/usr/libexec/zed-editor"),
+            "/bin/sh",
+            &[],
+            &[],
+            SandboxPermissions::default(),
+        )
+        .unwrap();
+
+        let policy_path = Path::new(&args[1]);
+        let mode = |path: &Path| std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(policy_path.parent().unwrap()), 0o700);
+        assert_eq!(mode(policy_path), 0o600);
+    }
+
+    #[test]
+    fn test_access_fs_write_for_abi_only_includes_supported_rights() {
+        assert_eq!(access_fs_write_for_abi(1) & ACCESS_FS_REFER, 0);
+        assert_eq!(access_fs_write_for_abi(1) & ACCESS_FS_TRUNCATE, 0);
+        assert_ne!(access_fs_write_for_abi(2) & ACCESS_FS_REFER, 0);
+        assert_eq!(access_fs_write_for_abi(2) & ACCESS_FS_TRUNCATE, 0);
+        assert_ne!(access_fs_write_for_abi(3) & ACCESS_FS_TRUNCATE, 0);
+    }
+
+    #[test]
+    fn test_sandbox_allows_write_to_writable_directory_and_dev_null() {
+        if !is_supported() {
+            return;
+        }
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let test_file = temp_dir.path().join("test_write.txt");
+        let output = run_with_write_restrictions(
+            &[temp_dir.path()],
+            &format!("echo 'hello' > '{}' 2>/dev/null", test_file.display()),
+        );
+
+        assert!(
+            output.status.success(),
+            "sandbox should allow writing to a writable directory: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        assert!(test_file.exists(), "file should have been created");
+    }
+
+    #[test]
+    fn test_sandbox_allows_reads_and_denies_write_outside_writable_directories() {
+        if !is_supported() {
+            return;
+        }
+
+        let project_dir = tempfile::tempdir().unwrap();
+        let other_dir = tempfile::tempdir().unwrap();
+        let forbidden_file = other_dir.path().join("forbidden.txt");
+        let output = run_with_write_restrictions(
+            &[project_dir.path()],
+            &format!(
+                "cat /etc/hostname > /dev/null; echo 'hello' > '{}'",
+                forbidden_file.display()
+            ),
+        );
+
+        assert!(
+            !output.status.success(),
+            "sandbox should deny writing outside the writable directories"
+        );
+        assert!(
+            !forbidden_file.exists(),
+            "file should not have been created"
+        );
+    }
+}
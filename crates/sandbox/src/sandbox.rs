@@ -5,8 +5,11 @@
 //! its `target_os` cfg so callers reach for the right one explicitly and
 //! non-host targets don't carry dead code.
 //!
-//! Today only macOS has an integration ([`macos_seatbelt`]), wrapping
-//! Apple's Seatbelt / `sandbox-exec` framework.
+//! Today macOS ([`macos_seatbelt`], wrapping Apple's Seatbelt /
+//! `sandbox-exec` framework) and Linux ([`linux_landlock`], using Landlock
+//! plus a private network namespace) have integrations.
 
+#[cfg(target_os = "linux")]
+pub mod linux_landlock;
 #[cfg(target_os = "macos")]
 pub mod macos_seatbelt;
@@ -956,6 +956,13 @@ pub struct JupyterContent {
     ///
     /// Default: `{}`
     pub kernel_selections: Option<HashMap<String, String>>,
+
+    /// Whether to keep percent-format scripts and Markdown notebooks in sync with the `.ipynb`
+    /// file of the same name when either is saved from the notebook editor. The script holds
+    /// the source of the cells and the `.ipynb` holds their outputs.
+    ///
+    /// Default: false
+    pub pair_notebooks: Option<bool>,
 }
 
 /// Whether to allow drag and drop text selection in buffer.
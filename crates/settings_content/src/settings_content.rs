@@ -204,6 +204,8 @@ pub struct SettingsContent {
 
     pub language_models: Option<AllLanguageModelSettingsContent>,
 
+    pub bookmarks_panel: Option<BookmarksPanelSettingsContent>,
+
     pub outline_panel: Option<OutlinePanelSettingsContent>,
 
     pub project_panel: Option<ProjectPanelSettingsContent>,
@@ -570,6 +572,15 @@ pub struct DebuggerSettingsContent {
     ///
     /// Default: true
     pub format_dap_log_messages: Option<bool>,
+    /// Whether inline values evaluate member access chains, such as `config.port` or `ptr->len`,
+    /// in addition to plain variables.
+    ///
+    /// Default: true
+    pub inline_value_expressions: Option<bool>,
+    /// The length at which a single inline value is truncated.
+    ///
+    /// Default: 100
+    pub inline_value_max_length: Option<usize>,
     /// The dock position of the debug panel
     ///
     /// Default: Bottom
@@ -977,6 +988,21 @@ pub struct JournalSettingsContent {
     ///
     /// Default: hour12
     pub hour_format: Option<HourFormat>,
+    /// The text inserted for each new journal entry. `{date}`, `{time}` and
+    /// `{weekday}` are replaced by the entry's date, time and day of the week.
+    ///
+    /// Default: `# {time}`
+    pub entry_template: Option<String>,
+    /// The text a new day's page starts with, using the same placeholders as
+    /// `entry_template`.
+    ///
+    /// Default: ""
+    pub day_template: Option<String>,
+    /// Whether to link each new day's page to the pages of the previous and
+    /// next days in the journal.
+    ///
+    /// Default: true
+    pub link_days: Option<bool>,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, MergeFrom, PartialEq)]
@@ -987,6 +1013,28 @@ pub enum HourFormat {
     Hour24,
 }
 
+#[with_fallible_options]
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug, PartialEq)]
+pub struct BookmarksPanelSettingsContent {
+    /// Whether to show the bookmarks panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Customize default width (in pixels) taken by bookmarks panel
+    ///
+    /// Default: 300
+    #[serde(serialize_with = "crate::serialize_optional_f32_with_two_decimal_places")]
+    pub default_width: Option<f32>,
+    /// The position of bookmarks panel
+    ///
+    /// Default: right
+    pub dock: Option<DockSide>,
+    /// Whether to show file icons in the bookmarks panel.
+    ///
+    /// Default: true
+    pub file_icons: Option<bool>,
+}
+
 #[with_fallible_options]
 #[derive(Clone, Default, Serialize, Deserialize, JsonSchema, MergeFrom, Debug, PartialEq)]
 pub struct OutlinePanelSettingsContent {
@@ -1232,6 +1280,10 @@ pub struct ReplSettingsContent {
     ///
     /// Default: 0
     pub output_max_height_lines: Option<usize>,
+    /// Where to dock the REPL variable explorer.
+    ///
+    /// Default: right
+    pub variable_explorer_dock: Option<DockSide>,
 }
 
 /// Settings for configuring the which-key popup behaviour.
@@ -619,6 +619,42 @@ pub fn append_top_level_array_value_in_json_text(
     }
 }
 
+/// Returns the byte range of the value at `key_path`, where `#N` keys index into arrays.
+pub fn value_range_in_json_text(text: &str, key_path: &[impl AsRef<str>]) -> Option<Range<usize>> {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_json::LANGUAGE.into())
+        .unwrap();
+    let syntax_tree = parser.parse(text, None)?;
+
+    let mut cursor = syntax_tree.walk();
+    let mut node = syntax_tree
+        .root_node()
+        .named_children(&mut cursor)
+        .find(|node| node.kind() != TS_COMMENT_KIND)?;
+    for key in key_path {
+        let key = key.as_ref();
+        node = if let Some(index) = parse_index_key(key) {
+            if node.kind() != TS_ARRAY_KIND {
+                return None;
+            }
+            node.named_children(&mut cursor)
+                .filter(|child| child.kind() != TS_COMMENT_KIND)
+                .nth(index)?
+        } else {
+            let key = serde_json::to_string(key).ok()?;
+            node.named_children(&mut cursor)
+                .filter(|child| child.kind() == "pair")
+                .find(|pair| {
+                    pair.child_by_field_name("key")
+                        .is_some_and(|pair_key| text.get(pair_key.byte_range()) == Some(&key))
+                })?
+                .child_by_field_name("value")?
+        };
+    }
+    Some(node.byte_range())
+}
+
 /// Infers the indentation size used in JSON text by analyzing the tree structure.
 /// Returns the detected indent size, or a default of 2 if no indentation is found.
 pub fn infer_json_indent_size(text: &str) -> usize {
@@ -2632,4 +2668,27 @@ mod tests {
 }"#;
         assert_eq!(infer_json_indent_size(json_mixed), 2);
     }
+
+    #[test]
+    fn test_value_range_in_json_text() {
+        let text = r#"// Comment
+{
+    "a": [
+        // Skipped
+        1,
+        {"b": "c"}
+    ],
+    "d": {"e": true}
+}"#;
+        let value_at =
+            |key_path: &[&str]| value_range_in_json_text(text, key_path).map(|range| &text[range]);
+
+        assert_eq!(value_at(&["a", "#0"]), Some("1"));
+        assert_eq!(value_at(&["a", "#1"]), Some(r#"{"b": "c"}"#));
+        assert_eq!(value_at(&["a", "#1", "b"]), Some(r#""c""#));
+        assert_eq!(value_at(&["d", "e"]), Some("true"));
+        assert_eq!(value_at(&["a", "#2"]), None);
+        assert_eq!(value_at(&["d", "#0"]), None);
+        assert_eq!(value_at(&["f"]), None);
+    }
 }
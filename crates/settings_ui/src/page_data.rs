@@ -5307,6 +5307,92 @@ fn panels_page() -> SettingsPage {
         ]
     }
 
+    fn bookmarks_panel_section() -> [SettingsPageItem; 5] {
+        [
+            SettingsPageItem::SectionHeader("Bookmarks Panel"),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Bookmarks Panel Button",
+                description: "Show the bookmarks panel button in the status bar.",
+                field: Box::new(SettingField {
+                    json_path: Some("bookmarks_panel.button"),
+                    pick: |settings_content| {
+                        settings_content.bookmarks_panel.as_ref()?.button.as_ref()
+                    },
+                    write: |settings_content, value, _| {
+                        settings_content
+                            .bookmarks_panel
+                            .get_or_insert_default()
+                            .button = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Bookmarks Panel Dock",
+                description: "Where to dock the bookmarks panel.",
+                field: Box::new(SettingField {
+                    json_path: Some("bookmarks_panel.dock"),
+                    pick: |settings_content| {
+                        settings_content.bookmarks_panel.as_ref()?.dock.as_ref()
+                    },
+                    write: |settings_content, value, _| {
+                        settings_content
+                            .bookmarks_panel
+                            .get_or_insert_default()
+                            .dock = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Bookmarks Panel Default Width",
+                description: "Default width of the bookmarks panel in pixels.",
+                field: Box::new(SettingField {
+                    json_path: Some("bookmarks_panel.default_width"),
+                    pick: |settings_content| {
+                        settings_content
+                            .bookmarks_panel
+                            .as_ref()?
+                            .default_width
+                            .as_ref()
+                    },
+                    write: |settings_content, value, _| {
+                        settings_content
+                            .bookmarks_panel
+                            .get_or_insert_default()
+                            .default_width = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "File Icons",
+                description: "Show file icons in the bookmarks panel.",
+                field: Box::new(SettingField {
+                    json_path: Some("bookmarks_panel.file_icons"),
+                    pick: |settings_content| {
+                        settings_content
+                            .bookmarks_panel
+                            .as_ref()?
+                            .file_icons
+                            .as_ref()
+                    },
+                    write: |settings_content, value, _| {
+                        settings_content
+                            .bookmarks_panel
+                            .get_or_insert_default()
+                            .file_icons = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+        ]
+    }
+
     fn outline_panel_section() -> [SettingsPageItem; 11] {
         [
             SettingsPageItem::SectionHeader("Outline Panel"),
@@ -6011,6 +6097,7 @@ fn panels_page() -> SettingsPage {
             project_panel_section(),
             terminal_panel_section(),
             outline_panel_section(),
+            bookmarks_panel_section(),
             git_panel_section(),
             debugger_panel_section(),
             collaboration_panel_section(),
@@ -6020,7 +6107,7 @@ fn panels_page() -> SettingsPage {
 }
 
 fn debugger_page() -> SettingsPage {
-    fn general_section() -> [SettingsPageItem; 6] {
+    fn general_section() -> [SettingsPageItem; 8] {
         [
             SettingsPageItem::SectionHeader("General"),
             SettingsPageItem::SettingItem(SettingItem {
@@ -6124,6 +6211,50 @@ fn debugger_page() -> SettingsPage {
                 metadata: None,
                 files: USER,
             }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Inline Value Expressions",
+                description: "Whether inline values evaluate member access chains, such as `config.port` or `ptr->len`.",
+                field: Box::new(SettingField {
+                    json_path: Some("debugger.inline_value_expressions"),
+                    pick: |settings_content| {
+                        settings_content
+                            .debugger
+                            .as_ref()?
+                            .inline_value_expressions
+                            .as_ref()
+                    },
+                    write: |settings_content, value, _| {
+                        settings_content
+                            .debugger
+                            .get_or_insert_default()
+                            .inline_value_expressions = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
+            SettingsPageItem::SettingItem(SettingItem {
+                title: "Inline Value Max Length",
+                description: "The length at which a single inline value is truncated.",
+                field: Box::new(SettingField {
+                    json_path: Some("debugger.inline_value_max_length"),
+                    pick: |settings_content| {
+                        settings_content
+                            .debugger
+                            .as_ref()?
+                            .inline_value_max_length
+                            .as_ref()
+                    },
+                    write: |settings_content, value, _| {
+                        settings_content
+                            .debugger
+                            .get_or_insert_default()
+                            .inline_value_max_length = value;
+                    },
+                }),
+                metadata: None,
+                files: USER,
+            }),
         ]
     }
 
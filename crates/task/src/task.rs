@@ -6,6 +6,7 @@ mod serde_helpers;
 pub mod static_source;
 mod task_template;
 mod vscode_debug_format;
+mod vscode_export;
 mod vscode_format;
 
 use anyhow::Context as _;
@@ -400,6 +401,23 @@ type VsCodeEnvVariable = String;
 type VsCodeCommand = String;
 type ZedEnvVariable = String;
 
+/// VS Code's predefined variables that have a Zed counterpart.
+const VSCODE_VARIABLES: &[(&str, VariableName)] = &[
+    ("workspaceFolder", VariableName::WorktreeRoot),
+    ("file", VariableName::File),
+    ("relativeFile", VariableName::RelativeFile),
+    ("relativeFileDirname", VariableName::RelativeDir),
+    ("fileBasename", VariableName::Filename),
+    ("fileBasenameNoExtension", VariableName::Stem),
+    ("fileDirname", VariableName::Dirname),
+    ("lineNumber", VariableName::Row),
+    ("columnNumber", VariableName::Column),
+    ("selectedText", VariableName::SelectedText),
+];
+
+/// VS Code's `${command:...}` variables that have a Zed counterpart.
+const VSCODE_COMMANDS: &[(&str, VariableName)] = &[("pickMyProcess", VariableName::PickProcessId)];
+
 struct EnvVariableReplacer {
     variables: HashMap<VsCodeEnvVariable, ZedEnvVariable>,
     commands: HashMap<VsCodeCommand, ZedEnvVariable>,
@@ -413,6 +431,21 @@ impl EnvVariableReplacer {
         }
     }
 
+    /// A replacer for all of VS Code's variables that Zed supports.
+    fn vscode() -> Self {
+        Self::new(
+            VSCODE_VARIABLES
+                .iter()
+                .map(|(vscode_name, zed_name)| (vscode_name.to_string(), zed_name.to_string()))
+                .collect(),
+        )
+        .with_commands(
+            VSCODE_COMMANDS
+                .iter()
+                .map(|(vscode_name, zed_name)| (vscode_name.to_string(), zed_name.to_string())),
+        )
+    }
+
     fn with_commands(
         mut self,
         commands: impl IntoIterator<Item = (VsCodeCommand, ZedEnvVariable)>,
@@ -476,3 +509,49 @@ impl EnvVariableReplacer {
         .into_owned()
     }
 }
+
+/// Replaces Zed variables with their VS Code equivalents, the reverse of [`EnvVariableReplacer::vscode`].
+/// Other environment variables are written as `${env:NAME}`, while Zed variables without a
+/// VS Code equivalent and variables with defaults are left as is.
+fn to_vscode_variables(input: &str) -> String {
+    shellexpand::env_with_context_no_errors(input, |var: &str| {
+        if var.contains(':') {
+            return Some(format!("${{{var}}}"));
+        }
+        // Positional shell parameters like `$1` are not variables
+        if var.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        let replacement = if let Some((command, _)) = VSCODE_COMMANDS
+            .iter()
+            .find(|(_, zed_name)| zed_name.to_string() == var)
+        {
+            format!("command:{command}")
+        } else if var.starts_with(ZED_VARIABLE_NAME_PREFIX) {
+            let variable = VariableName::from_str(var).ok()?;
+            let (vscode_name, _) = VSCODE_VARIABLES
+                .iter()
+                .find(|(_, zed_name)| *zed_name == variable)?;
+            vscode_name.to_string()
+        } else {
+            format!("env:{var}")
+        };
+        Some(format!("${{{replacement}}}"))
+    })
+    .into_owned()
+}
+
+fn to_vscode_variables_in_value(input: serde_json::Value) -> serde_json::Value {
+    match input {
+        serde_json::Value::String(s) => serde_json::Value::String(to_vscode_variables(&s)),
+        serde_json::Value::Array(arr) => {
+            serde_json::Value::Array(arr.into_iter().map(to_vscode_variables_in_value).collect())
+        }
+        serde_json::Value::Object(obj) => serde_json::Value::Object(
+            obj.into_iter()
+                .map(|(k, v)| (k, to_vscode_variables_in_value(v)))
+                .collect(),
+        ),
+        _ => input,
+    }
+}
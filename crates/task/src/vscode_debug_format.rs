@@ -1,9 +1,9 @@
-use collections::HashMap;
 use serde::Deserialize;
 use util::ResultExt as _;
 
 use crate::{
-    DebugScenario, DebugTaskFile, EnvVariableReplacer, TcpArgumentsTemplate, VariableName,
+    BuildTaskDefinition, DebugScenario, DebugTaskFile, EnvVariableReplacer, TcpArgumentsTemplate,
+    to_vscode_variables_in_value, vscode_export,
 };
 
 // TODO support preLaunchTask linkage with other tasks
@@ -58,21 +58,7 @@ impl TryFrom<VsCodeDebugTaskFile> for DebugTaskFile {
     type Error = anyhow::Error;
 
     fn try_from(file: VsCodeDebugTaskFile) -> Result<Self, Self::Error> {
-        let replacer = EnvVariableReplacer::new(HashMap::from_iter([
-            (
-                "workspaceFolder".to_owned(),
-                VariableName::WorktreeRoot.to_string(),
-            ),
-            (
-                "relativeFile".to_owned(),
-                VariableName::RelativeFile.to_string(),
-            ),
-            ("file".to_owned(), VariableName::File.to_string()),
-        ]))
-        .with_commands([(
-            "pickMyProcess".to_owned(),
-            VariableName::PickProcessId.to_string(),
-        )]);
+        let replacer = EnvVariableReplacer::vscode();
         let templates = file
             .configurations
             .into_iter()
@@ -82,6 +68,79 @@ impl TryFrom<VsCodeDebugTaskFile> for DebugTaskFile {
     }
 }
 
+impl VsCodeDebugTaskFile {
+    /// Writes Zed debug scenarios into the text of a `.vscode/launch.json` file. Configurations
+    /// with the same name are replaced, and the other configurations and comments in the file
+    /// are left untouched.
+    pub fn merge_scenarios(
+        vscode_launch: &str,
+        scenarios: &DebugTaskFile,
+    ) -> anyhow::Result<String> {
+        let configurations = scenarios.0.iter().map(scenario_to_vscode).collect();
+        vscode_export::merge_entries(
+            vscode_launch,
+            "0.2.0",
+            "configurations",
+            "name",
+            configurations,
+            |_, exported| exported,
+        )
+    }
+}
+
+fn scenario_to_vscode(scenario: &DebugScenario) -> serde_json::Value {
+    let mut config = to_vscode_variables_in_value(scenario.config.clone());
+    let config = config
+        .as_object_mut()
+        .map(std::mem::take)
+        .unwrap_or_default();
+    let mut configuration = serde_json::Map::new();
+    configuration.insert(
+        "type".to_owned(),
+        adapter_name_to_task_type(&scenario.adapter, &config).into(),
+    );
+    configuration.insert("name".to_owned(), scenario.label.to_string().into());
+    configuration.extend(config.into_iter().filter(|(key, _)| key != "type"));
+    if let Some(port) = scenario
+        .tcp_connection
+        .as_ref()
+        .and_then(|connection| connection.port)
+    {
+        configuration.insert("port".to_owned(), port.into());
+    }
+    let pre_launch_task = match &scenario.build {
+        Some(BuildTaskDefinition::ByName(label)) => Some(label.to_string()),
+        Some(BuildTaskDefinition::Template { task_template, .. }) => {
+            Some(task_template.label.clone())
+        }
+        None => None,
+    };
+    if let Some(pre_launch_task) = pre_launch_task {
+        configuration.insert("preLaunchTask".to_owned(), pre_launch_task.into());
+    }
+    configuration.into()
+}
+
+/// The reverse of [`task_type_to_adapter_name`]. JavaScript configurations keep the type they
+/// were imported with in their config.
+fn adapter_name_to_task_type(
+    adapter: &str,
+    config: &serde_json::Map<String, serde_json::Value>,
+) -> String {
+    match adapter {
+        "JavaScript" => config
+            .get("type")
+            .and_then(|task_type| task_type.as_str())
+            .unwrap_or("pwa-node"),
+        "Delve" => "go",
+        "Xdebug" => "php",
+        "CodeLLDB" => "lldb",
+        "Debugpy" => "debugpy",
+        _ => adapter,
+    }
+    .to_owned()
+}
+
 fn task_type_to_adapter_name(task_type: &str) -> String {
     match task_type {
         "pwa-node" | "node" | "node-terminal" | "chrome" | "pwa-chrome" | "edge" | "pwa-edge"
@@ -100,7 +159,7 @@ fn task_type_to_adapter_name(task_type: &str) -> String {
 mod tests {
     use serde_json::json;
 
-    use crate::{DebugScenario, DebugTaskFile, VariableName};
+    use crate::{BuildTaskDefinition, DebugScenario, DebugTaskFile, VariableName};
 
     use super::VsCodeDebugTaskFile;
 
@@ -191,4 +250,103 @@ mod tests {
             }])
         );
     }
+
+    #[test]
+    fn test_merging_scenarios_into_launch_json() {
+        let existing = r#"{
+    "version": "0.2.0",
+    "configurations": [
+        // Attach to a running server
+        {
+            "type": "node",
+            "request": "attach",
+            "name": "Attach",
+            "port": 9229
+        },
+        {
+            "type": "lldb",
+            "request": "launch",
+            "name": "Debug app",
+            "program": "${workspaceFolder}/target/debug/old"
+        }
+    ]
+}"#;
+        let scenarios = DebugTaskFile(vec![
+            DebugScenario {
+                label: "Debug app".into(),
+                adapter: "CodeLLDB".into(),
+                config: json!({
+                    "request": "launch",
+                    "program": "$ZED_WORKTREE_ROOT/target/debug/app",
+                    "args": ["$ZED_FILE"],
+                }),
+                tcp_connection: None,
+                build: Some(BuildTaskDefinition::ByName("build".into())),
+            },
+            DebugScenario {
+                label: "Run script".into(),
+                adapter: "JavaScript".into(),
+                config: json!({
+                    "request": "launch",
+                    "type": "pwa-node",
+                    "program": "${ZED_FILE}",
+                }),
+                tcp_connection: None,
+                build: None,
+            },
+            DebugScenario {
+                label: "Attach to process".into(),
+                adapter: "Debugpy".into(),
+                config: json!({
+                    "request": "attach",
+                    "processId": format!("${{{}}}", VariableName::PickProcessId),
+                }),
+                tcp_connection: None,
+                build: None,
+            },
+        ]);
+
+        let merged = VsCodeDebugTaskFile::merge_scenarios(existing, &scenarios).unwrap();
+        pretty_assertions::assert_eq!(
+            merged,
+            r#"{
+    "version": "0.2.0",
+    "configurations": [
+        // Attach to a running server
+        {
+            "type": "node",
+            "request": "attach",
+            "name": "Attach",
+            "port": 9229
+        },
+        {
+            "type": "lldb",
+            "name": "Debug app",
+            "request": "launch",
+            "program": "${workspaceFolder}/target/debug/app",
+            "args": [
+                "${file}"
+            ],
+            "preLaunchTask": "build"
+        },
+        {
+            "type": "pwa-node",
+            "name": "Run script",
+            "request": "launch",
+            "program": "${file}"
+        },
+        {
+            "type": "debugpy",
+            "name": "Attach to process",
+            "request": "attach",
+            "processId": "${command:pickMyProcess}"
+        }
+    ]
+}"#
+        );
+
+        let parsed: VsCodeDebugTaskFile = serde_json_lenient::from_str(&merged).unwrap();
+        let imported = DebugTaskFile::try_from(parsed).unwrap();
+        pretty_assertions::assert_eq!(imported.0[3].config, scenarios.0[2].config);
+    }
 }
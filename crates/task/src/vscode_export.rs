@@ -0,0 +1,107 @@
+//! Writes Zed's tasks and debug scenarios into VS Code's `tasks.json` and `launch.json`.
+
+use anyhow::Context as _;
+use serde_json::Value;
+use settings_json::{
+    infer_json_indent_size, parse_json_with_comments, replace_value_in_json_text, to_pretty_json,
+    value_range_in_json_text,
+};
+
+/// Merges `entries` into the array under `array_key` of a VS Code JSON file, matching existing
+/// entries by their `name_key` field. Matching entries are replaced with the result of `merge`
+/// and the rest are appended, leaving the other entries and comments untouched.
+pub(crate) fn merge_entries(
+    text: &str,
+    version: &str,
+    array_key: &str,
+    name_key: &str,
+    entries: Vec<Value>,
+    merge: impl Fn(&Value, Value) -> Value,
+) -> anyhow::Result<String> {
+    let mut text = if text.trim().is_empty() {
+        let document = serde_json::json!({ "version": version, array_key: [] });
+        to_pretty_json(&document, 4, 0) + "\n"
+    } else {
+        text.to_owned()
+    };
+    let tab_size = infer_json_indent_size(&text);
+    let document: Value =
+        parse_json_with_comments(&text).context("parsing the existing VS Code file")?;
+    let mut existing = document
+        .get(array_key)
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    let kept_count = existing.len();
+    for entry in entries {
+        let index = entry.get(name_key).and_then(|name| {
+            existing
+                .iter()
+                .position(|existing| existing.get(name_key) == Some(name))
+        });
+        let Some(index) = index else {
+            existing.push(entry);
+            continue;
+        };
+        let entry = merge(&existing[index], entry);
+        if index < kept_count {
+            let range = value_range_in_json_text(&text, &[array_key, &format!("#{index}")])
+                .with_context(|| format!("locating entry {index} of `{array_key}`"))?;
+            let indent = column(&text, range.start);
+            text.replace_range(range, &to_pretty_json(&entry, tab_size, indent));
+        }
+        existing[index] = entry;
+    }
+
+    if existing.len() == kept_count {
+        return Ok(text);
+    }
+    if kept_count == 0 {
+        // There's nothing to keep in the array, so write it out whole
+        let (range, replacement) = replace_value_in_json_text(
+            &text,
+            &[array_key],
+            tab_size,
+            Some(&Value::Array(existing)),
+            None,
+        );
+        text.replace_range(range, &replacement);
+    } else {
+        let last_index = kept_count - 1;
+        let last_range =
+            value_range_in_json_text(&text, &[array_key, &format!("#{last_index}")])
+                .with_context(|| format!("locating entry {last_index} of `{array_key}`"))?;
+        let indent = column(&text, last_range.start);
+        let mut insertion = String::new();
+        for entry in &existing[kept_count..] {
+            insertion.push_str(",\n");
+            insertion.push_str(&" ".repeat(indent));
+            insertion.push_str(&to_pretty_json(entry, tab_size, indent));
+        }
+        text.insert_str(last_range.end, &insertion);
+    }
+    Ok(text)
+}
+
+fn column(text: &str, offset: usize) -> usize {
+    text[..offset]
+        .rfind('\n')
+        .map_or(offset, |newline| offset - newline - 1)
+}
+
+/// Replaces `managed_keys` of an existing entry with those of the exported one, keeping the
+/// fields that Zed doesn't know about.
+pub(crate) fn merge_fields(existing: &Value, exported: Value, managed_keys: &[&str]) -> Value {
+    let Some(existing) = existing.as_object() else {
+        return exported;
+    };
+    let mut merged = existing.clone();
+    for key in managed_keys {
+        match exported.get(*key) {
+            Some(value) => merged.insert(key.to_string(), value.clone()),
+            None => merged.remove(*key),
+        };
+    }
+    Value::Object(merged)
+}
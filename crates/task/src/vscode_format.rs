@@ -3,7 +3,7 @@ use collections::HashMap;
 use serde::Deserialize;
 use util::ResultExt;
 
-use crate::{EnvVariableReplacer, TaskTemplate, TaskTemplates, VariableName};
+use crate::{EnvVariableReplacer, TaskTemplate, TaskTemplates, to_vscode_variables, vscode_export};
 
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -137,18 +137,7 @@ impl TryFrom<VsCodeTaskFile> for TaskTemplates {
     type Error = anyhow::Error;
 
     fn try_from(value: VsCodeTaskFile) -> Result<Self, Self::Error> {
-        let replacer = EnvVariableReplacer::new(HashMap::from_iter([
-            (
-                "workspaceFolder".to_owned(),
-                VariableName::WorktreeRoot.to_string(),
-            ),
-            ("file".to_owned(), VariableName::File.to_string()),
-            ("lineNumber".to_owned(), VariableName::Row.to_string()),
-            (
-                "selectedText".to_owned(),
-                VariableName::SelectedText.to_string(),
-            ),
-        ]));
+        let replacer = EnvVariableReplacer::vscode();
         let templates = value
             .tasks
             .into_iter()
@@ -163,6 +152,90 @@ impl TryFrom<VsCodeTaskFile> for TaskTemplates {
     }
 }
 
+/// The fields of a VS Code task that are exported from a [`TaskTemplate`]. `script` and `task`
+/// belong to npm and gulp tasks, which become shell tasks when exported.
+const EXPORTED_TASK_FIELDS: &[&str] = &["type", "command", "args", "script", "task"];
+const EXPORTED_TASK_OPTIONS: &[&str] = &["cwd", "env"];
+
+impl VsCodeTaskFile {
+    /// Writes Zed task templates into the text of a `.vscode/tasks.json` file as shell tasks.
+    /// Tasks with the same label are updated in place, keeping the fields that Zed doesn't know
+    /// about, and the other tasks and comments in the file are left untouched.
+    pub fn merge_templates(
+        vscode_tasks: &str,
+        templates: &TaskTemplates,
+    ) -> anyhow::Result<String> {
+        let tasks = templates.0.iter().map(template_to_vscode_task).collect();
+        vscode_export::merge_entries(
+            vscode_tasks,
+            "2.0.0",
+            "tasks",
+            "label",
+            tasks,
+            |existing, exported| {
+                let options = match (existing.get("options"), exported.get("options")) {
+                    (Some(existing_options), exported_options) => {
+                        Some(vscode_export::merge_fields(
+                            existing_options,
+                            exported_options.cloned().unwrap_or_default(),
+                            EXPORTED_TASK_OPTIONS,
+                        ))
+                    }
+                    (None, exported_options) => exported_options.cloned(),
+                };
+                let mut task =
+                    vscode_export::merge_fields(existing, exported, EXPORTED_TASK_FIELDS);
+                if let Some(task) = task.as_object_mut() {
+                    match options {
+                        Some(options) if options.as_object().is_some_and(|o| !o.is_empty()) => {
+                            task.insert("options".to_owned(), options);
+                        }
+                        _ => {
+                            task.remove("options");
+                        }
+                    }
+                }
+                task
+            },
+        )
+    }
+}
+
+fn template_to_vscode_task(template: &TaskTemplate) -> serde_json::Value {
+    let mut task = serde_json::Map::new();
+    task.insert("label".to_owned(), template.label.clone().into());
+    task.insert("type".to_owned(), "shell".into());
+    task.insert(
+        "command".to_owned(),
+        to_vscode_variables(&template.command).into(),
+    );
+    if !template.args.is_empty() {
+        let args = template
+            .args
+            .iter()
+            .map(|arg| to_vscode_variables(arg))
+            .collect::<Vec<_>>();
+        task.insert("args".to_owned(), args.into());
+    }
+    let mut options = serde_json::Map::new();
+    if let Some(cwd) = &template.cwd {
+        options.insert("cwd".to_owned(), to_vscode_variables(cwd).into());
+    }
+    if !template.env.is_empty() {
+        let mut env = template.env.iter().collect::<Vec<_>>();
+        env.sort();
+        let env = env
+            .into_iter()
+            .map(|(name, value)| (name.clone(), to_vscode_variables(value).into()))
+            .collect::<serde_json::Map<_, _>>();
+        options.insert("env".to_owned(), env.into());
+    }
+    if !options.is_empty() {
+        task.insert("options".to_owned(), options.into());
+    }
+    task.into()
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -172,7 +245,7 @@ mod tests {
         vscode_format::{Command, VsCodeTaskDefinition},
     };
 
-    use super::{EnvVariableReplacer, generate_label};
+    use super::{EnvVariableReplacer, generate_label, to_vscode_variables};
 
     fn compare_without_other_attributes(lhs: VsCodeTaskDefinition, rhs: VsCodeTaskDefinition) {
         assert_eq!(
@@ -460,4 +533,136 @@ mod tests {
         );
         assert_eq!(generate_label(&None), "Untitled Task");
     }
+
+    #[test]
+    fn test_vscode_variable_export() {
+        assert_eq!(
+            to_vscode_variables("$ZED_FILE:$ZED_ROW"),
+            "${file}:${lineNumber}"
+        );
+        assert_eq!(
+            to_vscode_variables("${ZED_WORKTREE_ROOT}/src"),
+            "${workspaceFolder}/src"
+        );
+        assert_eq!(
+            to_vscode_variables("$HOME/${PATH}"),
+            "${env:HOME}/${env:PATH}"
+        );
+        assert_eq!(
+            to_vscode_variables("${ZED_PICK_PID}"),
+            "${command:pickMyProcess}"
+        );
+        // Variables without a VS Code counterpart, with defaults, or positional are left as is.
+        assert_eq!(to_vscode_variables("$ZED_SYMBOL"), "$ZED_SYMBOL");
+        assert_eq!(
+            to_vscode_variables("${ZED_FILE:default}"),
+            "${ZED_FILE:default}"
+        );
+        assert_eq!(to_vscode_variables("awk '{print $1}'"), "awk '{print $1}'");
+
+        let replacer = EnvVariableReplacer::vscode();
+        for vscode in [
+            "${file}:${lineNumber}",
+            "${workspaceFolder}/src",
+            "${env:HOME}",
+        ] {
+            assert_eq!(to_vscode_variables(&replacer.replace(vscode)), vscode);
+        }
+    }
+
+    #[test]
+    fn test_merging_templates_into_vscode_tasks() {
+        let existing = r#"{
+  // Shared with Zed
+  "version": "2.0.0",
+  "tasks": [
+    {
+      "label": "build",
+      "type": "shell",
+      "command": "make",
+      "group": "build",
+      "problemMatcher": ["$gcc"]
+    },
+    // Only used in VS Code
+    {
+      "label": "lint",
+      "type": "npm",
+      "script": "lint"
+    }
+  ]
+}
+"#;
+        let templates = TaskTemplates(vec![
+            TaskTemplate {
+                label: "build".to_string(),
+                command: "cargo".to_string(),
+                args: vec![
+                    "build".to_string(),
+                    "--manifest-path".to_string(),
+                    "$ZED_WORKTREE_ROOT/Cargo.toml".to_string(),
+                ],
+                ..TaskTemplate::default()
+            },
+            TaskTemplate {
+                label: "test file".to_string(),
+                command: "cargo test -- $ZED_STEM".to_string(),
+                cwd: Some("${ZED_DIRNAME}".to_string()),
+                env: HashMap::from_iter([("RUST_LOG".to_string(), "$RUST_LOG".to_string())]),
+                ..TaskTemplate::default()
+            },
+        ]);
+
+        let merged = VsCodeTaskFile::merge_templates(existing, &templates).unwrap();
+        pretty_assertions::assert_eq!(
+            merged,
+            r#"{
+  // Shared with Zed
+  "version": "2.0.0",
+  "tasks": [
+    {
+      "label": "build",
+      "type": "shell",
+      "command": "cargo",
+      "group": "build",
+      "problemMatcher": [
+        "$gcc"
+      ],
+      "args": [
+        "build",
+        "--manifest-path",
+        "${workspaceFolder}/Cargo.toml"
+      ]
+    },
+    // Only used in VS Code
+    {
+      "label": "lint",
+      "type": "npm",
+      "script": "lint"
+    },
+    {
+      "label": "test file",
+      "type": "shell",
+      "command": "cargo test -- ${fileBasenameNoExtension}",
+      "options": {
+        "cwd": "${fileDirname}",
+        "env": {
+          "RUST_LOG": "${env:RUST_LOG}"
+        }
+      }
+    }
+  ]
+}
+"#
+        );
+
+        let parsed: VsCodeTaskFile = serde_json_lenient::from_str(&merged).unwrap();
+        let imported = TaskTemplates::try_from(parsed).unwrap();
+        assert_eq!(imported.0[0].args[2], "${ZED_WORKTREE_ROOT}/Cargo.toml");
+        assert_eq!(imported.0[2].cwd.as_deref(), Some("${ZED_DIRNAME}"));
+
+        // Merging into a missing file creates it.
+        let created = VsCodeTaskFile::merge_templates("", &templates).unwrap();
+        let parsed: VsCodeTaskFile = serde_json_lenient::from_str(&created).unwrap();
+        assert_eq!(TaskTemplates::try_from(parsed).unwrap().0.len(), 2);
+    }
 }
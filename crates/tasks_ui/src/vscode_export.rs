@@ -0,0 +1,148 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use fs::Fs;
+use gpui::{Context, Window, actions};
+use paths::{
+    local_debug_file_relative_path, local_tasks_file_relative_path,
+    local_vscode_launch_file_relative_path, local_vscode_tasks_file_relative_path,
+};
+use serde::de::DeserializeOwned;
+use task::{
+    BuildTaskDefinition, DebugTaskFile, TaskTemplates, VsCodeDebugTaskFile, VsCodeTaskFile,
+};
+use workspace::{
+    Toast, Workspace,
+    notifications::{DetachAndPromptErr, NotificationId},
+};
+
+actions!(
+    task,
+    [
+        /// Writes the project's tasks and debug scenarios into `.vscode/tasks.json` and
+        /// `.vscode/launch.json`, so that VS Code can run them too.
+        ExportToVsCode
+    ]
+);
+
+pub(crate) fn export_to_vscode(
+    workspace: &mut Workspace,
+    _: &ExportToVsCode,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let project = workspace.project().read(cx);
+    if !project.is_local() {
+        workspace.show_error(
+            &anyhow::anyhow!("Exporting tasks to VS Code is only supported in local projects"),
+            cx,
+        );
+        return;
+    }
+    let fs = project.fs().clone();
+    let worktree_roots = project
+        .visible_worktrees(cx)
+        .filter(|worktree| {
+            worktree
+                .read(cx)
+                .root_entry()
+                .is_some_and(|entry| entry.is_dir())
+        })
+        .map(|worktree| worktree.read(cx).abs_path())
+        .collect::<Vec<_>>();
+
+    cx.spawn_in(window, async move |workspace, cx| {
+        let mut exported_count = 0;
+        for root in worktree_roots {
+            exported_count += export_worktree(fs.as_ref(), &root).await?;
+        }
+        let message = if exported_count == 0 {
+            "There are no Zed tasks or debug scenarios to export".to_string()
+        } else {
+            format!("Exported {exported_count} tasks and debug scenarios to .vscode")
+        };
+        workspace.update(cx, |workspace, cx| {
+            workspace.show_toast(
+                Toast::new(NotificationId::unique::<ExportToVsCode>(), message).autohide(),
+                cx,
+            )
+        })
+    })
+    .detach_and_prompt_err("Failed to export tasks", window, cx, |_, _, _| None);
+}
+
+/// Exports the tasks and debug scenarios of the worktree at `root`, returning how many there were.
+async fn export_worktree(fs: &dyn Fs, root: &Path) -> anyhow::Result<usize> {
+    let mut templates: TaskTemplates = load_zed_file(
+        fs,
+        &root.join(local_tasks_file_relative_path().as_std_path()),
+    )
+    .await?
+    .unwrap_or_default();
+    let scenarios: DebugTaskFile = load_zed_file(
+        fs,
+        &root.join(local_debug_file_relative_path().as_std_path()),
+    )
+    .await?
+    .unwrap_or_default();
+
+    // Build tasks defined inline in a scenario become VS Code tasks, for `preLaunchTask` to refer to
+    for scenario in &scenarios.0 {
+        if let Some(BuildTaskDefinition::Template { task_template, .. }) = &scenario.build
+            && !templates
+                .0
+                .iter()
+                .any(|template| template.label == task_template.label)
+        {
+            templates.0.push(task_template.clone());
+        }
+    }
+
+    if !templates.0.is_empty() {
+        update_vscode_file(
+            fs,
+            root.join(local_vscode_tasks_file_relative_path().as_std_path()),
+            |text| VsCodeTaskFile::merge_templates(text, &templates),
+        )
+        .await?;
+    }
+    if !scenarios.0.is_empty() {
+        update_vscode_file(
+            fs,
+            root.join(local_vscode_launch_file_relative_path().as_std_path()),
+            |text| VsCodeDebugTaskFile::merge_scenarios(text, &scenarios),
+        )
+        .await?;
+    }
+    Ok(templates.0.len() + scenarios.0.len())
+}
+
+async fn load_zed_file<T: DeserializeOwned>(fs: &dyn Fs, path: &Path) -> anyhow::Result<Option<T>> {
+    if !fs.is_file(path).await {
+        return Ok(None);
+    }
+    let text = fs.load(path).await?;
+    serde_json_lenient::from_str(&text)
+        .with_context(|| format!("parsing {path:?}"))
+        .map(Some)
+}
+
+async fn update_vscode_file(
+    fs: &dyn Fs,
+    path: PathBuf,
+    merge: impl FnOnce(&str) -> anyhow::Result<String>,
+) -> anyhow::Result<()> {
+    let existing = if fs.is_file(&path).await {
+        fs.load(&path).await?
+    } else {
+        String::new()
+    };
+    let merged = merge(&existing).with_context(|| format!("updating {path:?}"))?;
+    if merged != existing {
+        if let Some(parent) = path.parent() {
+            fs.create_dir(parent).await?;
+        }
+        fs.atomic_write(path, merged).await?;
+    }
+    Ok(())
+}
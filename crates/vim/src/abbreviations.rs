@@ -0,0 +1,356 @@
+//! Abbreviations, defined with `:abbreviate` and friends.
+//!
+//! Insert mode abbreviations are expanded when a non-keyword character is typed
+//! after them, command-line abbreviations when the command is run.
+
+use editor::Editor;
+use gpui::{Action, App, Context, Window};
+use itertools::Itertools;
+use language::Point;
+
+use crate::{
+    Vim,
+    command::show_command_error,
+    state::{Mode, VimGlobals},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AbbreviationMode {
+    Insert,
+    CommandLine,
+}
+
+impl AbbreviationMode {
+    /// The modes affected by a command, where `None` means all of them.
+    fn matches(self, mode: Option<AbbreviationMode>) -> bool {
+        mode.is_none_or(|mode| mode == self)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Abbreviation {
+    pub mode: AbbreviationMode,
+    pub lhs: String,
+    pub rhs: String,
+}
+
+#[derive(Default)]
+pub(crate) struct Abbreviations(Vec<Abbreviation>);
+
+impl Abbreviations {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn define(&mut self, mode: Option<AbbreviationMode>, lhs: &str, rhs: &str) {
+        for abbreviation_mode in [AbbreviationMode::Insert, AbbreviationMode::CommandLine] {
+            if !abbreviation_mode.matches(mode) {
+                continue;
+            }
+            self.0.retain(|abbreviation| {
+                abbreviation.mode != abbreviation_mode || abbreviation.lhs != lhs
+            });
+            self.0.push(Abbreviation {
+                mode: abbreviation_mode,
+                lhs: lhs.to_string(),
+                rhs: rhs.to_string(),
+            });
+        }
+    }
+
+    pub fn remove(&mut self, mode: Option<AbbreviationMode>, lhs: &str) -> Result<(), String> {
+        let len = self.0.len();
+        self.0
+            .retain(|abbreviation| !abbreviation.mode.matches(mode) || abbreviation.lhs != lhs);
+        if self.0.len() == len {
+            return Err(format!("E24: No such abbreviation: {lhs}"));
+        }
+        Ok(())
+    }
+
+    pub fn clear(&mut self, mode: Option<AbbreviationMode>) {
+        self.0
+            .retain(|abbreviation| !abbreviation.mode.matches(mode));
+    }
+
+    /// Abbreviations for the given modes whose left-hand side starts with `prefix`.
+    pub fn list<'a>(
+        &'a self,
+        mode: Option<AbbreviationMode>,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = &'a Abbreviation> {
+        self.0.iter().filter(move |abbreviation| {
+            abbreviation.mode.matches(mode) && abbreviation.lhs.starts_with(prefix)
+        })
+    }
+
+    /// Finds the insert mode abbreviation at the end of `text`, the line
+    /// content before the character that was just typed. Returns the length of
+    /// the abbreviation and its expansion.
+    ///
+    /// As in Vim, an abbreviation made of keyword characters only must follow a
+    /// non-keyword character, and any other must follow whitespace. Either can
+    /// also start the line.
+    pub fn find_insert_expansion(
+        &self,
+        text: &str,
+        is_word_char: impl Fn(char) -> bool,
+    ) -> Option<(usize, &str)> {
+        self.list(Some(AbbreviationMode::Insert), "")
+            .filter(|abbreviation| {
+                let Some(before) = text.strip_suffix(abbreviation.lhs.as_str()) else {
+                    return false;
+                };
+                let Some(previous) = before.chars().next_back() else {
+                    return true;
+                };
+                if abbreviation.lhs.chars().all(&is_word_char) {
+                    !is_word_char(previous)
+                } else {
+                    previous.is_whitespace()
+                }
+            })
+            .max_by_key(|abbreviation| abbreviation.lhs.len())
+            .map(|abbreviation| (abbreviation.lhs.len(), abbreviation.rhs.as_str()))
+    }
+
+    /// Expands a command-line abbreviation used as the command name of `query`.
+    pub fn expand_command_line(&self, query: &str) -> Option<String> {
+        let name_end = query
+            .find(|c: char| c.is_whitespace() || c == '!')
+            .unwrap_or(query.len());
+        let (name, rest) = query.split_at(name_end);
+        self.list(Some(AbbreviationMode::CommandLine), name)
+            .find(|abbreviation| abbreviation.lhs == name)
+            .map(|abbreviation| format!("{}{rest}", abbreviation.rhs))
+    }
+}
+
+/// Defines, removes or lists abbreviations. A `mode` of `None` applies to both
+/// insert mode and the command-line.
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) enum VimAbbreviate {
+    List {
+        mode: Option<AbbreviationMode>,
+        prefix: String,
+    },
+    Define {
+        mode: Option<AbbreviationMode>,
+        lhs: String,
+        rhs: String,
+    },
+    Remove {
+        mode: Option<AbbreviationMode>,
+        lhs: String,
+    },
+    Clear {
+        mode: Option<AbbreviationMode>,
+    },
+}
+
+impl VimAbbreviate {
+    /// Parses the arguments of `:abbreviate`: lists abbreviations when given at
+    /// most one word, and defines one otherwise.
+    pub(crate) fn parse(mode: Option<AbbreviationMode>, args: &str) -> Self {
+        let args = args.trim();
+        match args.split_once(char::is_whitespace) {
+            Some((lhs, rhs)) => Self::Define {
+                mode,
+                lhs: lhs.to_string(),
+                rhs: rhs.trim_start().to_string(),
+            },
+            None => Self::List {
+                mode,
+                prefix: args.to_string(),
+            },
+        }
+    }
+
+    /// Updates the abbreviations. Listing them requires a window, so it's left to the caller.
+    pub(crate) fn apply(&self, cx: &mut App) -> Result<(), String> {
+        let abbreviations = &mut Vim::globals(cx).abbreviations;
+        match self {
+            Self::List { .. } => Ok(()),
+            Self::Define { mode, lhs, rhs } => {
+                abbreviations.define(*mode, lhs, rhs);
+                Ok(())
+            }
+            Self::Remove { mode, lhs } => abbreviations.remove(*mode, lhs),
+            Self::Clear { mode } => {
+                abbreviations.clear(*mode);
+                Ok(())
+            }
+        }
+    }
+}
+
+pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
+    Vim::action(editor, cx, |_, action: &VimAbbreviate, window, cx| {
+        if let VimAbbreviate::List { mode, prefix } = action {
+            list_abbreviations(*mode, prefix, window, cx);
+        } else if let Err(error) = action.apply(cx) {
+            show_command_error(&error, window, cx);
+        }
+    });
+}
+
+fn list_abbreviations(
+    mode: Option<AbbreviationMode>,
+    prefix: &str,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let detail = Vim::globals(cx)
+        .abbreviations
+        .list(mode, prefix)
+        .map(|abbreviation| {
+            let mode = match abbreviation.mode {
+                AbbreviationMode::Insert => "i",
+                AbbreviationMode::CommandLine => "c",
+            };
+            format!("{mode}  {:<12} {}", abbreviation.lhs, abbreviation.rhs)
+        })
+        .join("\n");
+    let detail = if detail.is_empty() {
+        "No abbreviation found".to_string()
+    } else {
+        detail
+    };
+    let _ = window.prompt(
+        gpui::PromptLevel::Info,
+        "Abbreviations",
+        Some(&detail),
+        &["OK"],
+        cx,
+    );
+}
+
+impl Vim {
+    /// Expands the insert mode abbreviations ending right before `text`, which
+    /// was just typed.
+    pub(crate) fn expand_abbreviation(&mut self, text: &str, cx: &mut Context<Self>) {
+        if self.mode != Mode::Insert || Vim::globals(cx).abbreviations.is_empty() {
+            return;
+        }
+        let mut chars = text.chars();
+        let (Some(trigger), None) = (chars.next(), chars.next()) else {
+            return;
+        };
+
+        self.update_editor(cx, |_, editor, cx| {
+            let selections = editor.selections.all::<Point>(&editor.display_snapshot(cx));
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let abbreviations = &cx.global::<VimGlobals>().abbreviations;
+            let mut edits = Vec::new();
+            for selection in selections {
+                let cursor = selection.head();
+                let Some(trigger_column) = cursor.column.checked_sub(trigger.len_utf8() as u32)
+                else {
+                    continue;
+                };
+                let trigger_start = Point::new(cursor.row, trigger_column);
+                let classifier = snapshot.char_classifier_at(trigger_start);
+                if !selection.is_empty() || classifier.is_word(trigger) {
+                    continue;
+                }
+
+                let line = snapshot
+                    .text_for_range(Point::new(cursor.row, 0)..trigger_start)
+                    .collect::<String>();
+                if let Some((lhs_len, rhs)) =
+                    abbreviations.find_insert_expansion(&line, |c| classifier.is_word(c))
+                {
+                    let start = Point::new(cursor.row, trigger_column - lhs_len as u32);
+                    edits.push((start..trigger_start, rhs.to_string()));
+                }
+            }
+            if !edits.is_empty() {
+                editor.edit(edits, cx);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gpui::TestAppContext;
+
+    use super::{AbbreviationMode, Abbreviations};
+    use crate::{state::Mode, test::VimTestContext};
+
+    fn is_word_char(c: char) -> bool {
+        c.is_alphanumeric() || c == '_'
+    }
+
+    #[test]
+    fn test_find_insert_expansion() {
+        let mut abbreviations = Abbreviations::default();
+        abbreviations.define(None, "teh", "the");
+        abbreviations.define(Some(AbbreviationMode::Insert), "#i", "#include");
+        abbreviations.define(Some(AbbreviationMode::CommandLine), "W", "w");
+
+        assert_eq!(
+            abbreviations.find_insert_expansion("see teh", is_word_char),
+            Some((3, "the"))
+        );
+        assert_eq!(
+            abbreviations.find_insert_expansion("(teh", is_word_char),
+            Some((3, "the"))
+        );
+        assert_eq!(
+            abbreviations.find_insert_expansion("steh", is_word_char),
+            None
+        );
+        assert_eq!(
+            abbreviations.find_insert_expansion("#i", is_word_char),
+            Some((2, "#include"))
+        );
+        assert_eq!(
+            abbreviations.find_insert_expansion("x#i", is_word_char),
+            None
+        );
+        assert_eq!(abbreviations.find_insert_expansion("W", is_word_char), None);
+
+        abbreviations.define(Some(AbbreviationMode::Insert), "teh", "THE");
+        assert_eq!(
+            abbreviations.find_insert_expansion("teh", is_word_char),
+            Some((3, "THE"))
+        );
+        assert_eq!(
+            abbreviations.expand_command_line("teh! args"),
+            Some("the! args".to_string())
+        );
+
+        assert!(
+            abbreviations
+                .remove(Some(AbbreviationMode::Insert), "W")
+                .is_err()
+        );
+        abbreviations.clear(Some(AbbreviationMode::Insert));
+        assert_eq!(
+            abbreviations.find_insert_expansion("teh", is_word_char),
+            None
+        );
+        assert_eq!(
+            abbreviations.expand_command_line("W"),
+            Some("w".to_string())
+        );
+    }
+
+    #[gpui::test]
+    async fn test_insert_abbreviations(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇ", Mode::Normal);
+        cx.simulate_keystrokes(": i a b space t e h space t h e enter");
+        cx.simulate_keystrokes("i t e h space s t e h , t e h");
+        cx.assert_state("the steh, tehˇ", Mode::Insert);
+        cx.simulate_keystrokes(".");
+        cx.assert_state("the steh, the.ˇ", Mode::Insert);
+
+        cx.simulate_keystrokes("escape : i u n a space t e h enter");
+        cx.simulate_keystrokes("a space t e h space");
+        cx.assert_state("the steh, the. teh ˇ", Mode::Insert);
+    }
+}
@@ -42,6 +42,7 @@ use zed_actions::{OpenDocs, RevealTarget};
 
 use crate::{
     ToggleMarksView, ToggleRegistersView, Vim, VimSettings,
+    abbreviations::{AbbreviationMode, VimAbbreviate},
     motion::{EndOfDocument, Motion, MotionKind, StartOfDocument},
     normal::{
         JoinLines,
@@ -49,7 +50,9 @@ use crate::{
     },
     object::Object,
     rewrap::Rewrap,
-    state::{Mark, Mode},
+    state::{Mark, Mode, VimGlobals},
+    user_commands::{ClearUserCommands, DefineUserCommand, DeleteUserCommand, RunUserCommand},
+    vimrc::VimSource,
     visual::VisualDeleteLine,
 };
 
@@ -196,7 +199,7 @@ impl VimOption {
 #[derive(Clone, PartialEq, Action)]
 #[action(namespace = vim, no_json, no_register)]
 pub struct VimSet {
-    options: Vec<VimOption>,
+    pub(crate) options: Vec<VimOption>,
 }
 
 /// Saves the current file with optional save intent.
@@ -284,10 +287,10 @@ impl Deref for WrappedAction {
     }
 }
 
-pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
-    Vim::action(editor, cx, |vim, action: &VimSet, _, cx| {
-        for option in action.options.iter() {
-            vim.update_editor(cx, |_, editor, cx| match option {
+impl Vim {
+    pub(crate) fn set_options(&mut self, options: &[VimOption], cx: &mut Context<Self>) {
+        for option in options {
+            self.update_editor(cx, |_, editor, cx| match option {
                 VimOption::Wrap(true) => {
                     editor
                         .set_soft_wrap_mode(language::language_settings::SoftWrap::EditorWidth, cx);
@@ -318,6 +321,58 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
                 }
             });
         }
+    }
+
+    /// Runs an ex command as if it was typed into the command palette. `depth`
+    /// is the number of user commands and sourced files it's run from.
+    pub(crate) fn execute_ex_command(
+        &self,
+        input: &str,
+        depth: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if depth > MAX_EX_COMMAND_DEPTH {
+            return Task::ready(Err(anyhow!("E169: Command too recursive")));
+        }
+        let workspace = self
+            .workspace(window, cx)
+            .map_or_else(WeakEntity::new_invalid, |workspace| workspace.downgrade());
+        let input = input.to_string();
+        let results = command_interceptor(&input, workspace, cx);
+        cx.spawn_in(window, async move |_, cx| {
+            let Some(item) = results.await.results.into_iter().next() else {
+                anyhow::bail!("E492: Not an editor command: {input}");
+            };
+            let mut action = item.action;
+            if let Some(command) = action.as_any().downcast_ref::<RunUserCommand>() {
+                action = RunUserCommand {
+                    depth,
+                    ..command.clone()
+                }
+                .boxed_clone();
+            } else if let Some(source) = action.as_any().downcast_ref::<VimSource>() {
+                action = VimSource {
+                    depth,
+                    ..source.clone()
+                }
+                .boxed_clone();
+            }
+            cx.update(|window, cx| window.dispatch_action(action, cx))
+        })
+    }
+}
+
+/// Maximum nesting of user commands and sourced files.
+pub(crate) const MAX_EX_COMMAND_DEPTH: usize = 100;
+
+pub(crate) fn show_command_error(message: &str, window: &mut Window, cx: &mut App) {
+    let _ = window.prompt(gpui::PromptLevel::Critical, message, None, &["Cancel"], cx);
+}
+
+pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
+    Vim::action(editor, cx, |vim, action: &VimSet, _, cx| {
+        vim.set_options(&action.options, cx);
     });
     Vim::action(editor, cx, |vim, _: &VisualCommand, window, cx| {
         let Some(workspace) = vim.workspace(window, cx) else {
@@ -338,13 +393,7 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     });
 
     Vim::action(editor, cx, |_, _: &ArgumentRequired, window, cx| {
-        let _ = window.prompt(
-            gpui::PromptLevel::Critical,
-            "Argument required",
-            None,
-            &["Cancel"],
-            cx,
-        );
+        show_command_error("Argument required", window, cx);
     });
 
     Vim::action(editor, cx, |vim, _: &ShellCommand, window, cx| {
@@ -1439,13 +1488,26 @@ impl CommandRange {
         }
     }
 
+    /// Number of line numbers the range was written with.
+    pub(crate) fn line_count(&self) -> usize {
+        if self.end.is_some() { 2 } else { 1 }
+    }
+
     /// The `CommandRange` representing the entire buffer.
-    fn buffer() -> Self {
+    pub(crate) fn buffer() -> Self {
         Self {
             start: Position::Line { row: 1, offset: 0 },
             end: Some(Position::LastLine { offset: 0 }),
         }
     }
+
+    /// The `CommandRange` representing the line of the newest cursor.
+    pub(crate) fn current_line() -> Self {
+        Self {
+            start: Position::CurrentLine { offset: 0 },
+            end: None,
+        }
+    }
 }
 
 fn generate_commands(_: &App) -> Vec<VimCommand> {
@@ -1769,6 +1831,71 @@ fn generate_commands(_: &App) -> Vec<VimCommand> {
         VimCommand::new(("delm", "arks"), ArgumentRequired)
             .bang(DeleteMarks::AllLocal)
             .args(|_, args| Some(DeleteMarks::Marks(args).boxed_clone())),
+        VimCommand::new(
+            ("com", "mand"),
+            DefineUserCommand {
+                args: String::new(),
+                overwrite: false,
+            },
+        )
+        .bang(DefineUserCommand {
+            args: String::new(),
+            overwrite: true,
+        })
+        .args(|action, args| {
+            let overwrite = action
+                .as_any()
+                .downcast_ref::<DefineUserCommand>()?
+                .overwrite;
+            Some(DefineUserCommand { args, overwrite }.boxed_clone())
+        }),
+        VimCommand::new(("delc", "ommand"), ArgumentRequired)
+            .args(|_, name| Some(DeleteUserCommand { name }.boxed_clone())),
+        VimCommand::new(("comc", "lear"), ClearUserCommands),
+        abbreviate_command(("ab", "breviate"), None),
+        abbreviate_command(("iab", "brev"), Some(AbbreviationMode::Insert)),
+        abbreviate_command(("ca", "bbrev"), Some(AbbreviationMode::CommandLine)),
+        VimCommand::new(("una", "bbreviate"), ArgumentRequired)
+            .args(|_, lhs| Some(VimAbbreviate::Remove { mode: None, lhs }.boxed_clone())),
+        VimCommand::new(("iuna", "bbrev"), ArgumentRequired).args(|_, lhs| {
+            Some(
+                VimAbbreviate::Remove {
+                    mode: Some(AbbreviationMode::Insert),
+                    lhs,
+                }
+                .boxed_clone(),
+            )
+        }),
+        VimCommand::new(("cuna", "bbrev"), ArgumentRequired).args(|_, lhs| {
+            Some(
+                VimAbbreviate::Remove {
+                    mode: Some(AbbreviationMode::CommandLine),
+                    lhs,
+                }
+                .boxed_clone(),
+            )
+        }),
+        VimCommand::new(("abc", "lear"), VimAbbreviate::Clear { mode: None }),
+        VimCommand::new(
+            ("iabc", "lear"),
+            VimAbbreviate::Clear {
+                mode: Some(AbbreviationMode::Insert),
+            },
+        ),
+        VimCommand::new(
+            ("cabc", "lear"),
+            VimAbbreviate::Clear {
+                mode: Some(AbbreviationMode::CommandLine),
+            },
+        ),
+        VimCommand::new(
+            ("so", "urce"),
+            VimSource {
+                filename: String::new(),
+                depth: 0,
+            },
+        )
+        .filename(|_, filename| Some(VimSource { filename, depth: 0 }.boxed_clone())),
         VimCommand::new(("sor", "t"), SortLinesCaseSensitive)
             .range(select_range)
             .default_range(CommandRange::buffer()),
@@ -1811,6 +1938,20 @@ fn commands(cx: &App) -> &Vec<VimCommand> {
         .0
 }
 
+fn abbreviate_command(
+    name: (&'static str, &'static str),
+    mode: Option<AbbreviationMode>,
+) -> VimCommand {
+    VimCommand::new(
+        name,
+        VimAbbreviate::List {
+            mode,
+            prefix: String::new(),
+        },
+    )
+    .args(move |_, args| Some(VimAbbreviate::parse(mode, &args).boxed_clone()))
+}
+
 fn act_on_range(action: Box<dyn Action>, range: &CommandRange) -> Option<Box<dyn Action>> {
     Some(
         WithRange {
@@ -1855,6 +1996,14 @@ pub fn command_interceptor(
     let (range, query) = VimCommand::parse_range(input);
     let range_prefix = input[0..(input.len() - query.len())].to_string();
     let has_trailing_space = query.ends_with(" ");
+    let query = cx
+        .try_global::<VimGlobals>()
+        .and_then(|globals| {
+            globals
+                .abbreviations
+                .expand_command_line(query.trim_start())
+        })
+        .unwrap_or(query);
     let mut query = query.as_str().trim_start();
 
     let on_matching_lines = (query.starts_with('g') || query.starts_with('v'))
@@ -1873,6 +2022,13 @@ pub fn command_interceptor(
             }
             .boxed_clone(),
         )
+    } else if let Some(command) = RunUserCommand::parse(
+        query,
+        range.clone().filter(|_| on_matching_lines.is_none()),
+        false,
+        cx,
+    ) {
+        Some(command.boxed_clone())
     } else if query.starts_with('/') || query.starts_with('?') {
         Some(
             FindCommand {
@@ -1905,10 +2061,7 @@ pub fn command_interceptor(
             query.next();
         }
         if let Some(replacement) = Replacement::parse(query) {
-            let range = range.clone().unwrap_or(CommandRange {
-                start: Position::CurrentLine { offset: 0 },
-                end: None,
-            });
+            let range = range.clone().unwrap_or_else(CommandRange::current_line);
             Some(ReplaceCommand { replacement, range }.boxed_clone())
         } else {
             None
@@ -1986,7 +2139,20 @@ pub fn command_interceptor(
             ))
         })
     else {
-        return Task::ready(CommandInterceptResult::default());
+        // Abbreviated user commands, which only run when no builtin command matches.
+        let Some(command) = RunUserCommand::parse(query, range, true, cx) else {
+            return Task::ready(CommandInterceptResult::default());
+        };
+        let string = command.display_string(&range_prefix);
+        let positions = generate_positions(&string, &(range_prefix + query));
+        return Task::ready(CommandInterceptResult {
+            results: vec![CommandInterceptItem {
+                action: command.boxed_clone(),
+                string,
+                positions,
+            }],
+            exclusive: false,
+        });
     };
 
     if let Some((cmd_idx, parsed_query, display_string, no_args_positions)) = filenames {
@@ -0,0 +1,220 @@
+//! Converts between keystrokes and Vim's key notation, like `<Esc>` or `<C-w>`.
+//!
+//! Macros are stored in their register as text in this notation, so that they
+//! can be pasted into a buffer, edited, and yanked back.
+
+use gpui::{Keystroke, Modifiers};
+
+/// Keys that are written as `<Name>`, with their names in GPUI.
+const NAMED_KEYS: &[(&str, &str)] = &[
+    ("Esc", "escape"),
+    ("CR", "enter"),
+    ("BS", "backspace"),
+    ("Tab", "tab"),
+    ("Del", "delete"),
+    ("Space", "space"),
+    ("Up", "up"),
+    ("Down", "down"),
+    ("Left", "left"),
+    ("Right", "right"),
+    ("Home", "home"),
+    ("End", "end"),
+    ("PageUp", "pageup"),
+    ("PageDown", "pagedown"),
+    ("Insert", "insert"),
+];
+
+/// Writes a keystroke in key notation. Characters typed without modifiers are
+/// written as is, except for `<` which is written as `<lt>`.
+pub(crate) fn keystroke_to_notation(keystroke: &Keystroke) -> String {
+    let keystroke = keystroke.clone().with_simulated_ime();
+    let modifiers = keystroke.modifiers;
+    let has_command_modifier =
+        modifiers.control || modifiers.alt || modifiers.platform || modifiers.function;
+
+    if !has_command_modifier && keystroke.key != "space" {
+        match keystroke.key_char.as_deref() {
+            Some("<") => return "<lt>".to_string(),
+            Some(text) if !text.chars().any(char::is_control) => return text.to_string(),
+            _ => {}
+        }
+    } else if keystroke.key == "space" && modifiers == Modifiers::none() {
+        return " ".to_string();
+    }
+
+    let mut notation = String::from("<");
+    if modifiers.control {
+        notation.push_str("C-");
+    }
+    if modifiers.alt {
+        notation.push_str("M-");
+    }
+    if modifiers.platform {
+        notation.push_str("D-");
+    }
+    if modifiers.shift {
+        notation.push_str("S-");
+    }
+    if let Some((name, _)) = NAMED_KEYS.iter().find(|(_, key)| *key == keystroke.key) {
+        notation.push_str(name);
+    } else if let Some(number) = keystroke
+        .key
+        .strip_prefix('f')
+        .filter(|number| number.parse::<u8>().is_ok())
+    {
+        notation.push('F');
+        notation.push_str(number);
+    } else if keystroke.key == "<" {
+        notation.push_str("lt");
+    } else {
+        notation.push_str(&keystroke.key);
+    }
+    notation.push('>');
+    notation
+}
+
+/// Parses text in key notation into the keystrokes that type it. A `<` that
+/// doesn't start a key name is typed literally.
+pub(crate) fn parse_notation(text: &str) -> Vec<Keystroke> {
+    let mut keystrokes = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c == '<'
+            && let Some(end) = rest.find('>')
+            && let Some(keystroke) = parse_key_name(&rest[1..end])
+        {
+            keystrokes.push(keystroke);
+            rest = &rest[end + 1..];
+            continue;
+        }
+        keystrokes.push(char_to_keystroke(c));
+        rest = &rest[c.len_utf8()..];
+    }
+    keystrokes
+}
+
+/// Parses the contents of `<...>`, like `C-w` or `S-Tab`.
+fn parse_key_name(mut name: &str) -> Option<Keystroke> {
+    let mut modifiers = Modifiers::none();
+    while let Some((modifier, rest)) = name.split_once('-')
+        && !rest.is_empty()
+    {
+        match modifier {
+            "C" | "c" => modifiers.control = true,
+            "M" | "m" | "A" | "a" => modifiers.alt = true,
+            "D" | "d" => modifiers.platform = true,
+            "S" | "s" => modifiers.shift = true,
+            _ => return None,
+        }
+        name = rest;
+    }
+
+    let key = if name.eq_ignore_ascii_case("lt") {
+        "<".to_string()
+    } else if let Some((_, key)) = NAMED_KEYS
+        .iter()
+        .find(|(named, _)| named.eq_ignore_ascii_case(name))
+    {
+        key.to_string()
+    } else if let Some(number) = name
+        .strip_prefix(['F', 'f'])
+        .filter(|number| number.parse::<u8>().is_ok_and(|number| number > 0))
+    {
+        format!("f{number}")
+    } else {
+        let mut chars = name.chars();
+        match (chars.next(), chars.next()) {
+            // Without modifiers, `<x>` is just text
+            (Some(c), None) if modifiers != Modifiers::none() => c.to_lowercase().to_string(),
+            _ => return None,
+        }
+    };
+
+    Some(Keystroke {
+        modifiers,
+        key,
+        key_char: None,
+    })
+}
+
+/// The keystroke that types `c`. Control characters, as stored in Vim macros,
+/// are typed with the control key.
+fn char_to_keystroke(c: char) -> Keystroke {
+    let mut modifiers = Modifiers::none();
+    let key = match c {
+        '\n' | '\r' => "enter".to_string(),
+        '\t' => "tab".to_string(),
+        ' ' => "space".to_string(),
+        '\u{1b}' => "escape".to_string(),
+        '\u{7f}' => "backspace".to_string(),
+        '\u{1}'..='\u{1a}' => {
+            modifiers.control = true;
+            char::from(b'a' + c as u8 - 1).to_string()
+        }
+        c if c.is_ascii_uppercase() => {
+            modifiers.shift = true;
+            c.to_ascii_lowercase().to_string()
+        }
+        c => c.to_string(),
+    };
+    Keystroke {
+        modifiers,
+        key,
+        key_char: None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gpui::Keystroke;
+
+    use super::{keystroke_to_notation, parse_notation};
+
+    fn notation(keystrokes: &str) -> String {
+        keystrokes
+            .split(' ')
+            .map(|keystroke| keystroke_to_notation(&Keystroke::parse(keystroke).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_keystroke_to_notation() {
+        assert_eq!(notation("d w"), "dw");
+        assert_eq!(notation("shift-a x escape"), "Ax<Esc>");
+        assert_eq!(notation("i < space > enter"), "i<lt> ><CR>");
+        assert_eq!(notation("ctrl-w ctrl-shift-a alt-x"), "<C-w><C-S-a><M-x>");
+        assert_eq!(notation("shift-tab f5 backspace"), "<S-Tab><F5><BS>");
+    }
+
+    #[test]
+    fn test_parse_notation() {
+        let parsed = |text: &str| {
+            parse_notation(text)
+                .iter()
+                .map(|keystroke| keystroke.unparse())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        assert_eq!(parsed("dw"), "d w");
+        assert_eq!(parsed("Ax<Esc>"), "shift-a x escape");
+        assert_eq!(parsed("i<lt> <cr>\n"), "i < space enter enter");
+        assert_eq!(parsed("<C-w><c-S-A><M-x>"), "ctrl-w ctrl-shift-a alt-x");
+        assert_eq!(parsed("<S-Tab><F5><BS>"), "shift-tab f5 backspace");
+        assert_eq!(parsed("a<b>c<"), "a < b > c <");
+        assert_eq!(parsed("\u{1b}\u{17}"), "escape ctrl-w");
+
+        for text in [
+            "dw",
+            "Ax<Esc>",
+            "i<lt> <CR>",
+            "<C-w><C-S-a><M-x>",
+            "<S-Tab><F5>",
+        ] {
+            let round_trip = parse_notation(text)
+                .iter()
+                .map(keystroke_to_notation)
+                .collect::<String>();
+            assert_eq!(round_trip, text);
+        }
+    }
+}
@@ -3,6 +3,7 @@ use std::{cell::RefCell, rc::Rc};
 use crate::{
     Vim,
     insert::NormalBefore,
+    key_notation,
     motion::Motion,
     normal::InsertBefore,
     state::{Mode, Operator, RecordedSelection, ReplayableAction, VimGlobals},
@@ -52,7 +53,7 @@ fn repeatable_insert(action: &ReplayableAction) -> Option<Box<dyn Action>> {
                 None
             }
         }
-        ReplayableAction::Insertion { .. } => None,
+        ReplayableAction::Insertion { .. } | ReplayableAction::Keystroke(_) => None,
     }
 }
 
@@ -67,9 +68,14 @@ pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     });
 
     Vim::action(editor, cx, |vim, _: &ToggleRecord, window, cx| {
-        let globals = Vim::globals(cx);
-        if let Some(char) = globals.recording_register.take() {
-            globals.last_recorded_register = Some(char)
+        if let Some(char) = Vim::globals(cx).recording_register.take() {
+            let workspace = vim
+                .workspace(window, cx)
+                .map(|workspace| workspace.downgrade());
+            Vim::update_globals(cx, |globals, cx| {
+                globals.last_recorded_register = Some(char);
+                globals.finish_macro_recording(char, workspace, cx);
+            });
         } else {
             vim.push_operator(Operator::RecordRegister, window, cx);
         }
@@ -179,6 +185,15 @@ impl Replayer {
                     editor.replay_insert_event(&text, utf16_range_to_replace.clone(), window, cx)
                 })
             }
+            ReplayableAction::Keystroke(keystroke) => {
+                let focused = window.focused(cx);
+                window.dispatch_keystroke(keystroke, cx);
+                if window.focused(cx) != focused {
+                    // Keys are dispatched using the last drawn frame, so draw
+                    // to send the following ones to the newly focused view.
+                    window.draw(cx).clear();
+                }
+            }
         }
         window.defer(cx, move |window, cx| self.next(window, cx));
     }
@@ -215,8 +230,35 @@ impl Vim {
             };
             register = last;
         }
-        let Some(actions) = globals.recordings.get(&register) else {
-            return;
+        register = register.to_lowercase().next().unwrap_or(register);
+
+        // Macros that were edited, or restored from a previous session, are
+        // replayed by typing the register's contents.
+        let recorded_actions = globals
+            .recordings
+            .get(&register)
+            .filter(|_| {
+                globals.macro_keys.get(&register)
+                    == globals
+                        .registers
+                        .get(&register)
+                        .map(|register| &register.text)
+            })
+            .cloned();
+        let actions = match recorded_actions {
+            Some(actions) => actions,
+            None => {
+                let Some(text) = Vim::update_globals(cx, |globals, cx| {
+                    globals.read_register(Some(register), None, cx)
+                })
+                .map(|register| register.text) else {
+                    return;
+                };
+                key_notation::parse_notation(&text)
+                    .into_iter()
+                    .map(ReplayableAction::Keystroke)
+                    .collect()
+            }
         };
 
         let mut repeated_actions = vec![];
@@ -225,6 +267,7 @@ impl Vim {
             count -= 1
         }
 
+        let globals = Vim::globals(cx);
         globals.last_replayed_register = Some(register);
         let mut replayer = globals.replayer.get_or_insert_with(Replayer::new).clone();
         replayer.replay(repeated_actions, window, cx);
@@ -1204,4 +1247,69 @@ mod test {
             the lazy dog"
         });
     }
+
+    #[gpui::test]
+    async fn test_edit_macro_as_text(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state("ˇone two three four", Mode::Normal);
+        cx.simulate_keystrokes("q q d w q");
+        cx.assert_state("ˇtwo three four", Mode::Normal);
+
+        // Paste the macro, change it and yank it back into its register
+        cx.simulate_keystrokes("o escape \" q p");
+        cx.assert_state("two three four\ndˇw", Mode::Normal);
+        cx.simulate_keystrokes("shift-a x escape 0 \" q y $ d d");
+        cx.assert_state("ˇtwo three four", Mode::Normal);
+        cx.update(|_, cx| {
+            assert_eq!(
+                cx.global::<VimGlobals>().registers[&'q'].text.as_ref(),
+                "dwx"
+            );
+        });
+
+        cx.simulate_keystrokes("@ q");
+        cx.run_until_parked();
+        cx.assert_state("ˇhree four", Mode::Normal);
+        cx.simulate_keystrokes("@ @");
+        cx.run_until_parked();
+        cx.assert_state("ˇour", Mode::Normal);
+    }
+
+    #[gpui::test]
+    async fn test_replay_register_text(cx: &mut gpui::TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        // A register restored from a previous session has no recorded actions
+        cx.update(|_, cx| {
+            cx.global_mut::<VimGlobals>()
+                .registers
+                .insert('a', "A!<Esc>j0".to_string().into());
+        });
+        cx.set_state(
+            indoc! {"
+            ˇone
+            two
+            three"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes("2 @ a");
+        cx.run_until_parked();
+        cx.assert_state(
+            indoc! {"
+            one!
+            two!
+            ˇthree"},
+            Mode::Normal,
+        );
+
+        // Recording into an uppercase register appends to its text
+        cx.simulate_keystrokes("q shift-a x q");
+        cx.update(|_, cx| {
+            assert_eq!(
+                cx.global::<VimGlobals>().registers[&'a'].text.as_ref(),
+                "A!<Esc>j0x"
+            );
+        });
+    }
 }
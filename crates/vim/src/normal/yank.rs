@@ -207,6 +207,7 @@ impl Vim {
         }
 
         let selected_register = self.selected_register.take();
+        let workspace = editor.workspace().map(|workspace| workspace.downgrade());
         Vim::update_globals(cx, |globals, cx| {
             globals.write_registers(
                 Register {
@@ -216,6 +217,7 @@ impl Vim {
                 selected_register,
                 is_yank,
                 kind,
+                workspace,
                 cx,
             )
         });
@@ -1,13 +1,17 @@
-use crate::command::command_interceptor;
+use crate::abbreviations::Abbreviations;
+use crate::command::{VimOption, command_interceptor};
+use crate::key_notation;
 use crate::motion::MotionKind;
 use crate::normal::repeat::Replayer;
 use crate::surrounds::SurroundsType;
+use crate::user_commands::UserCommand;
 use crate::{ToggleMarksView, ToggleRegistersView, UseSystemClipboard, Vim, VimAddon, VimSettings};
 use crate::{motion::Motion, object::Object};
 use anyhow::Result;
 use collections::HashMap;
 use command_palette_hooks::{CommandPaletteFilter, GlobalCommandPaletteInterceptor};
 use db::{
+    kvp::KeyValueStore,
     sqlez::{domain::Domain, thread_safe_connection::ThreadSafeConnection},
     sqlez_macros::sql,
 };
@@ -15,8 +19,8 @@ use editor::display_map::{is_invisible, replacement};
 use editor::{Anchor, ClipboardSelection, Editor, MultiBuffer, ToPoint as EditorToPoint};
 use gpui::{
     Action, App, AppContext, BorrowAppContext, ClipboardEntry, ClipboardItem, DismissEvent, Entity,
-    EntityId, Global, HighlightStyle, StyledText, Subscription, Task, TaskExt, TextStyle,
-    WeakEntity,
+    EntityId, Global, HighlightStyle, Keystroke, StyledText, Subscription, Task, TaskExt,
+    TextStyle, WeakEntity,
 };
 use language::{Buffer, BufferEvent, BufferId, Chunk, LanguageAwareStyling, Point};
 
@@ -26,9 +30,9 @@ use project::{Project, ProjectItem, ProjectPath};
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsStore};
 use std::borrow::BorrowMut;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::path::Path;
-use std::{fmt::Display, ops::Range, sync::Arc};
+use std::{fmt::Display, ops::Range, sync::Arc, time::Duration};
 use text::{Bias, ToPoint};
 use theme_settings::ThemeSettings;
 use ui::{
@@ -206,7 +210,7 @@ pub enum RecordedSelection {
     },
 }
 
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
 pub struct Register {
     pub(crate) text: SharedString,
     pub(crate) clipboard_selections: Option<Vec<ClipboardSelection>>,
@@ -282,10 +286,27 @@ pub struct VimGlobals {
     pub last_yank: Option<SharedString>,
     pub registers: HashMap<char, Register>,
     pub recordings: HashMap<char, Vec<ReplayableAction>>,
+    /// The keys typed during the active macro recording, in key notation.
+    pub(crate) recording_keys: Vec<String>,
+    /// The text stored in each register by its last macro recording. When the
+    /// register no longer contains it, the macro was edited and is replayed
+    /// from its text instead of the recorded actions.
+    pub(crate) macro_keys: HashMap<char, SharedString>,
+    /// Pending writes of the registers to the database, by workspace.
+    save_registers_tasks: HashMap<EntityId, Task<()>>,
 
     pub focused_vim: Option<WeakEntity<Vim>>,
 
     pub marks: HashMap<EntityId, Entity<MarksState>>,
+
+    /// Commands defined with `:command`.
+    pub(crate) user_commands: BTreeMap<String, UserCommand>,
+    pub(crate) abbreviations: Abbreviations,
+    /// Options set by the vimrc, applied to each editor when it's focused.
+    pub(crate) vimrc_options: Vec<VimOption>,
+    /// Incremented whenever the vimrc is reloaded.
+    pub(crate) vimrc_generation: usize,
+    pub(crate) vimrc_watcher: Option<Task<()>>,
 }
 
 pub struct MarksState {
@@ -752,10 +773,21 @@ impl MarksState {
 
 impl Global for VimGlobals {}
 
+/// The key-value store namespace of the registers saved for each workspace.
+const REGISTERS_NAMESPACE: &str = "vim_registers";
+/// How long to wait for further register writes before saving them, so that a burst of deletes
+/// is saved once.
+const SAVE_REGISTERS_DEBOUNCE: Duration = Duration::from_millis(500);
+
 impl VimGlobals {
     pub(crate) fn register(cx: &mut App) {
         cx.set_global(VimGlobals::default());
 
+        cx.intercept_keystrokes(|event, _, cx| {
+            Vim::globals(cx).observe_keystroke(&event.keystroke)
+        })
+        .detach();
+
         cx.observe_keystrokes(|event, _, cx| {
             let Some(action) = event.action.as_ref().map(|action| action.boxed_clone()) else {
                 return;
@@ -820,6 +852,10 @@ impl VimGlobals {
     }
 
     fn register_workspace(&mut self, workspace: &Workspace, cx: &mut Context<Workspace>) {
+        if Vim::enabled(cx) {
+            self.watch_vimrc(workspace, cx);
+        }
+        Self::load_registers(cx);
         let entity_id = cx.entity_id();
         self.marks.insert(entity_id, MarksState::new(workspace, cx));
         cx.observe_release(&cx.entity(), move |_, _, cx| {
@@ -836,6 +872,7 @@ impl VimGlobals {
         register: Option<char>,
         is_yank: bool,
         kind: MotionKind,
+        workspace: Option<WeakEntity<Workspace>>,
         cx: &mut Context<Editor>,
     ) {
         if let Some(register) = register {
@@ -903,6 +940,9 @@ impl VimGlobals {
                 }
             }
         }
+        if let Some(workspace) = workspace {
+            self.save_registers(workspace, cx);
+        }
     }
 
     pub(crate) fn read_register(
@@ -965,6 +1005,132 @@ impl VimGlobals {
         })
     }
 
+    /// Registers that are saved to the database, and restored when the workspace is reopened.
+    fn is_persisted_register(register: char) -> bool {
+        register.is_ascii_alphanumeric() || register == '-' || register == '"'
+    }
+
+    fn load_registers(cx: &mut Context<Workspace>) {
+        cx.spawn(async move |workspace, cx| {
+            let workspace_id = workspace
+                .read_with(cx, |workspace, _| workspace.database_id())
+                .ok()
+                .flatten()?;
+            let kvp = cx.update(|cx| KeyValueStore::global(cx));
+            let registers = cx
+                .background_spawn(async move {
+                    kvp.scoped(REGISTERS_NAMESPACE)
+                        .read(&i64::from(workspace_id).to_string())
+                })
+                .await
+                .log_err()
+                .flatten()?;
+            let registers: BTreeMap<char, Register> = serde_json::from_str(&registers).log_err()?;
+            cx.update(|cx| {
+                // Registers written in this session are newer than the saved ones
+                let globals = Vim::globals(cx);
+                for (name, register) in registers {
+                    if Self::is_persisted_register(name) {
+                        globals.registers.entry(name).or_insert(register);
+                    }
+                }
+            });
+            Some(())
+        })
+        .detach();
+    }
+
+    /// Saves the registers for the given workspace once no register was written for a moment.
+    fn save_registers(&mut self, workspace: WeakEntity<Workspace>, cx: &mut App) {
+        let kvp = KeyValueStore::global(cx);
+        let task = cx.spawn({
+            let workspace = workspace.clone();
+            async move |cx| {
+                cx.background_executor()
+                    .timer(SAVE_REGISTERS_DEBOUNCE)
+                    .await;
+                let Some(workspace_id) = workspace
+                    .read_with(cx, |workspace, _| workspace.database_id())
+                    .ok()
+                    .flatten()
+                else {
+                    return;
+                };
+                let registers = cx.update(|cx| {
+                    Vim::globals(cx)
+                        .registers
+                        .iter()
+                        .filter(|(name, _)| Self::is_persisted_register(**name))
+                        .map(|(name, register)| (*name, register.clone()))
+                        .collect::<BTreeMap<_, _>>()
+                });
+                let Some(registers) = serde_json::to_string(&registers).log_err() else {
+                    return;
+                };
+                kvp.scoped(REGISTERS_NAMESPACE)
+                    .write(i64::from(workspace_id).to_string(), registers)
+                    .await
+                    .log_err();
+            }
+        });
+        self.save_registers_tasks
+            .insert(workspace.entity_id(), task);
+    }
+
+    /// Records the keys typed during a macro recording, so that the macro can
+    /// be stored in its register as text.
+    fn observe_keystroke(&mut self, keystroke: &Keystroke) {
+        if self.replayer.is_none() && self.recording_register.is_some() {
+            self.recording_keys
+                .push(key_notation::keystroke_to_notation(keystroke));
+        }
+    }
+
+    /// Ends the recording of a macro into `register`, and stores its keys in
+    /// the register. Recording into an uppercase register appends to it.
+    pub(crate) fn finish_macro_recording(
+        &mut self,
+        register: char,
+        workspace: Option<WeakEntity<Workspace>>,
+        cx: &mut App,
+    ) {
+        // The last key is the `q` that ended the recording
+        if self.recording_keys.last().is_some_and(|key| key == "q") {
+            self.recording_keys.pop();
+        }
+        let keys = std::mem::take(&mut self.recording_keys).concat();
+        let lower = register.to_lowercase().next().unwrap_or(register);
+        if lower == register {
+            self.macro_keys.insert(register, keys.clone().into());
+            self.registers.insert(register, keys.into());
+            if let Some(workspace) = workspace {
+                self.save_registers(workspace, cx);
+            }
+            return;
+        }
+
+        let actions = self.recordings.remove(&register).unwrap_or_default();
+        let previous = self.registers.remove(&lower).unwrap_or_default();
+        let text = SharedString::from(format!("{}{keys}", previous.text));
+        if self.macro_keys.get(&lower) == Some(&previous.text) {
+            self.recordings.entry(lower).or_default().extend(actions);
+            self.macro_keys.insert(lower, text.clone());
+        } else {
+            self.recordings.remove(&lower);
+            self.macro_keys.remove(&lower);
+        }
+        self.registers.insert(
+            lower,
+            Register {
+                text,
+                clipboard_selections: None,
+            },
+        );
+        if let Some(workspace) = workspace {
+            self.save_registers(workspace, cx);
+        }
+    }
+
     pub fn observe_action(&mut self, action: Box<dyn Action>) {
         if self.dot_recording {
             self.recording_actions
@@ -1041,6 +1207,8 @@ pub enum ReplayableAction {
         text: Arc<str>,
         utf16_range_to_replace: Option<Range<isize>>,
     },
+    /// A key from a macro that is replayed from its text.
+    Keystroke(Keystroke),
 }
 
 impl Clone for ReplayableAction {
@@ -1054,6 +1222,7 @@ impl Clone for ReplayableAction {
                 text: text.clone(),
                 utf16_range_to_replace: utf16_range_to_replace.clone(),
             },
+            Self::Keystroke(keystroke) => Self::Keystroke(keystroke.clone()),
         }
     }
 }
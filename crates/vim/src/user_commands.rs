@@ -0,0 +1,526 @@
+//! User-defined ex commands, created with `:command`.
+//!
+//! A user command expands to another ex command when it's run, replacing
+//! `<args>`, `<line1>`, `<bang>` etc. in its definition with the arguments and
+//! range it was invoked with.
+
+use editor::Editor;
+use gpui::{Action, App, Context, Window};
+use itertools::Itertools;
+
+use crate::{
+    Vim,
+    command::{CommandRange, show_command_error},
+    state::VimGlobals,
+};
+
+/// A command defined with `:command`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct UserCommand {
+    pub name: String,
+    /// The ex command run in place of this one, before expansion.
+    pub replacement: String,
+    pub nargs: Nargs,
+    /// Set when the command accepts a range (`-range`).
+    pub range: Option<DefaultRange>,
+    /// Whether the command accepts a `!` (`-bang`).
+    pub bang: bool,
+}
+
+/// Number of arguments a user command accepts, set with `-nargs`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum Nargs {
+    #[default]
+    Zero,
+    /// Exactly one argument, which may contain spaces.
+    One,
+    Any,
+    ZeroOrOne,
+    AtLeastOne,
+}
+
+impl Nargs {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "0" => Some(Self::Zero),
+            "1" => Some(Self::One),
+            "*" => Some(Self::Any),
+            "?" => Some(Self::ZeroOrOne),
+            "+" => Some(Self::AtLeastOne),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Zero => "0",
+            Self::One => "1",
+            Self::Any => "*",
+            Self::ZeroOrOne => "?",
+            Self::AtLeastOne => "+",
+        }
+    }
+}
+
+/// Range used when a user command defined with `-range` is run without one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DefaultRange {
+    /// `-range`
+    CurrentLine,
+    /// `-range=%`
+    Buffer,
+}
+
+impl UserCommand {
+    /// Parses the arguments of `:command`, e.g. `-nargs=1 -range Name replacement`.
+    pub(crate) fn parse(input: &str) -> Result<Self, String> {
+        let mut nargs = Nargs::default();
+        let mut range = None;
+        let mut bang = false;
+
+        let mut rest = input.trim_start();
+        while let Some(attribute) = rest.strip_prefix('-') {
+            let end = attribute
+                .find(char::is_whitespace)
+                .unwrap_or(attribute.len());
+            let (attribute, remainder) = attribute.split_at(end);
+            let (key, value) = match attribute.split_once('=') {
+                Some((key, value)) => (key, Some(value)),
+                None => (attribute, None),
+            };
+            match (key, value) {
+                ("nargs", Some(value)) => {
+                    nargs = Nargs::parse(value)
+                        .ok_or_else(|| format!("E176: Invalid number of arguments: {value}"))?;
+                }
+                ("range", None) => range = Some(DefaultRange::CurrentLine),
+                ("range", Some("%")) => range = Some(DefaultRange::Buffer),
+                ("bang", None) => bang = true,
+                // Only affect completion and argument parsing details Zed doesn't implement
+                ("bar" | "keepscript", None) | ("complete", Some(_)) => {}
+                _ => return Err(format!("E181: Invalid attribute: -{attribute}")),
+            }
+            rest = remainder.trim_start();
+        }
+
+        let name_end = rest
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(rest.len());
+        let (name, replacement) = rest.split_at(name_end);
+        if name.is_empty()
+            || (!replacement.is_empty() && !replacement.starts_with(char::is_whitespace))
+        {
+            return Err("E182: Invalid command name".into());
+        }
+        if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return Err("E183: User defined commands must start with an uppercase letter".into());
+        }
+        let replacement = replacement.trim();
+        if replacement.is_empty() {
+            return Err("E471: Argument required".into());
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            replacement: replacement.to_string(),
+            nargs,
+            range,
+            bang,
+        })
+    }
+
+    /// Checks that the command can be run with the given arguments, `!` and range.
+    fn check_invocation(&self, args: &str, bang: bool, has_range: bool) -> Result<(), String> {
+        if bang && !self.bang {
+            return Err("E477: No ! allowed".into());
+        }
+        if has_range && self.range.is_none() {
+            return Err("E481: No range allowed".into());
+        }
+        let arg_count = args.split_whitespace().count();
+        match self.nargs {
+            Nargs::Zero if arg_count > 0 => Err(format!("E488: Trailing characters: {args}")),
+            Nargs::One | Nargs::AtLeastOne if arg_count == 0 => {
+                Err("E471: Argument required".into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Expands the escape sequences in the replacement text. `lines` is the
+    /// 1-based range of lines the command was run on, and `range_count` the
+    /// number of line numbers given with the command (0, 1 or 2).
+    fn expand(&self, args: &str, bang: bool, lines: (u32, u32), range_count: usize) -> String {
+        let mut result = String::new();
+        let mut rest = self.replacement.as_str();
+        while let Some(start) = rest.find('<') {
+            result.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let Some(end) = rest.find('>') else {
+                break;
+            };
+
+            let expansion = match rest[1..end].to_ascii_lowercase().as_str() {
+                "args" => Some(args.to_string()),
+                "q-args" => Some(quote(args)),
+                "f-args" if self.nargs == Nargs::One => Some(quote(args)),
+                "f-args" => Some(args.split_whitespace().map(quote).join(",")),
+                "bang" => Some(if bang { "!" } else { "" }.to_string()),
+                "line1" => Some(lines.0.to_string()),
+                "line2" => Some(lines.1.to_string()),
+                "range" => Some(range_count.to_string()),
+                "lt" => Some("<".to_string()),
+                // Unknown sequences are left as they are, like in Vim
+                _ => None,
+            };
+            match expansion {
+                Some(expansion) => {
+                    result.push_str(&expansion);
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    result.push('<');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Defines a user command, or lists them all when no arguments are given.
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct DefineUserCommand {
+    /// The attributes, name and replacement text of the command.
+    pub args: String,
+    /// Replace an existing command with the same name (`:command!`).
+    pub overwrite: bool,
+}
+
+/// Deletes a user command.
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct DeleteUserCommand {
+    pub name: String,
+}
+
+/// Deletes all user commands.
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct ClearUserCommands;
+
+/// Runs a user command.
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct RunUserCommand {
+    pub name: String,
+    pub args: String,
+    pub bang: bool,
+    pub range: Option<CommandRange>,
+    /// Number of user commands and sourced files this is run from.
+    pub depth: usize,
+}
+
+impl DefineUserCommand {
+    /// Defines the command. Listing commands requires a window, so it's left to the caller.
+    pub(crate) fn apply(&self, cx: &mut App) -> Result<(), String> {
+        if self.args.is_empty() {
+            return Ok(());
+        }
+        let definition = UserCommand::parse(&self.args)?;
+        let commands = &mut Vim::globals(cx).user_commands;
+        if !self.overwrite && commands.contains_key(&definition.name) {
+            return Err(format!(
+                "E174: Command already exists: add ! to replace it: {}",
+                definition.name
+            ));
+        }
+        commands.insert(definition.name.clone(), definition);
+        Ok(())
+    }
+}
+
+impl DeleteUserCommand {
+    pub(crate) fn apply(&self, cx: &mut App) -> Result<(), String> {
+        match Vim::globals(cx).user_commands.remove(&self.name) {
+            Some(_) => Ok(()),
+            None => Err(format!("E184: No such user-defined command: {}", self.name)),
+        }
+    }
+}
+
+impl RunUserCommand {
+    /// Parses a query such as `Name! args` invoking a user command. With
+    /// `allow_prefix`, the name can be abbreviated as long as it's unambiguous.
+    pub(crate) fn parse(
+        query: &str,
+        range: Option<CommandRange>,
+        allow_prefix: bool,
+        cx: &App,
+    ) -> Option<Self> {
+        let name_end = query
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(query.len());
+        let (name, rest) = query.split_at(name_end);
+        if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return None;
+        }
+        let (bang, args) = match rest.strip_prefix('!') {
+            Some(args) => (true, args),
+            None => (false, rest),
+        };
+        if !args.is_empty() && !args.starts_with(char::is_whitespace) {
+            return None;
+        }
+
+        let commands = &cx.try_global::<VimGlobals>()?.user_commands;
+        let name = if commands.contains_key(name) {
+            name.to_string()
+        } else if allow_prefix {
+            commands
+                .keys()
+                .filter(|command| command.starts_with(name))
+                .exactly_one()
+                .ok()?
+                .clone()
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            name,
+            args: args.trim().to_string(),
+            bang,
+            range,
+            depth: 0,
+        })
+    }
+
+    /// How the command is shown in the command palette.
+    pub(crate) fn display_string(&self, range_prefix: &str) -> String {
+        let bang = if self.bang { "!" } else { "" };
+        let space = if self.args.is_empty() { "" } else { " " };
+        format!(":{range_prefix}{}{bang}{space}{}", self.name, self.args)
+    }
+
+    fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) {
+        let Some(command) = Vim::globals(cx).user_commands.get(&self.name).cloned() else {
+            show_command_error(
+                &format!("E492: Not an editor command: {}", self.name),
+                window,
+                cx,
+            );
+            return;
+        };
+        if let Err(error) = command.check_invocation(&self.args, self.bang, self.range.is_some()) {
+            show_command_error(&error, window, cx);
+            return;
+        }
+
+        let range = self.range.clone().unwrap_or_else(|| match command.range {
+            Some(DefaultRange::Buffer) => CommandRange::buffer(),
+            Some(DefaultRange::CurrentLine) | None => CommandRange::current_line(),
+        });
+        let lines = vim.update_editor(cx, |vim, editor, cx| {
+            range.buffer_range(vim, editor, window, cx)
+        });
+        let lines = match lines {
+            Some(Ok(lines)) => (lines.start.0 + 1, lines.end.0 + 1),
+            Some(Err(error)) => {
+                show_command_error(&error.to_string(), window, cx);
+                return;
+            }
+            None => return,
+        };
+        let range_count = self.range.as_ref().map_or(0, CommandRange::line_count);
+
+        let expanded = command.expand(&self.args, self.bang, lines, range_count);
+        let task = vim.execute_ex_command(&expanded, self.depth + 1, window, cx);
+        cx.spawn_in(window, async move |_, cx| {
+            if let Err(error) = task.await {
+                cx.update(|window, cx| show_command_error(&error.to_string(), window, cx))
+                    .ok();
+            }
+        })
+        .detach();
+    }
+}
+
+pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
+    Vim::action(editor, cx, |_, action: &DefineUserCommand, window, cx| {
+        if action.args.is_empty() {
+            list_user_commands(window, cx);
+        } else if let Err(error) = action.apply(cx) {
+            show_command_error(&error, window, cx);
+        }
+    });
+
+    Vim::action(editor, cx, |_, action: &DeleteUserCommand, window, cx| {
+        if let Err(error) = action.apply(cx) {
+            show_command_error(&error, window, cx);
+        }
+    });
+
+    Vim::action(editor, cx, |_, _: &ClearUserCommands, _, cx| {
+        Vim::globals(cx).user_commands.clear();
+    });
+
+    Vim::action(editor, cx, |vim, action: &RunUserCommand, window, cx| {
+        action.run(vim, window, cx)
+    });
+}
+
+fn list_user_commands(window: &mut Window, cx: &mut App) {
+    let commands = &Vim::globals(cx).user_commands;
+    let detail = if commands.is_empty() {
+        "No user-defined commands found".to_string()
+    } else {
+        commands
+            .values()
+            .map(|command| {
+                let bang = if command.bang { "!" } else { " " };
+                let range = match command.range {
+                    Some(DefaultRange::CurrentLine) => ".",
+                    Some(DefaultRange::Buffer) => "%",
+                    None => " ",
+                };
+                format!(
+                    "{bang} {:<12} {} {range} {}",
+                    command.name,
+                    command.nargs.as_str(),
+                    command.replacement
+                )
+            })
+            .join("\n")
+    };
+    let _ = window.prompt(
+        gpui::PromptLevel::Info,
+        "User commands",
+        Some(&detail),
+        &["OK"],
+        cx,
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use gpui::TestAppContext;
+    use indoc::indoc;
+
+    use super::{DefaultRange, Nargs, UserCommand};
+    use crate::{
+        state::{Mode, VimGlobals},
+        test::VimTestContext,
+    };
+
+    #[test]
+    fn test_parse_user_command() {
+        assert_eq!(
+            UserCommand::parse("-nargs=1 -range=% -bang Replace %s/foo/<args>/g"),
+            Ok(UserCommand {
+                name: "Replace".into(),
+                replacement: "%s/foo/<args>/g".into(),
+                nargs: Nargs::One,
+                range: Some(DefaultRange::Buffer),
+                bang: true,
+            })
+        );
+        assert_eq!(
+            UserCommand::parse("W w").map(|command| (command.nargs, command.range)),
+            Ok((Nargs::Zero, None))
+        );
+        assert!(UserCommand::parse("lower w").is_err());
+        assert!(UserCommand::parse("-nargs=2 Foo w").is_err());
+        assert!(UserCommand::parse("-unknown Foo w").is_err());
+        assert!(UserCommand::parse("Foo").is_err());
+    }
+
+    #[test]
+    fn test_expand_user_command() {
+        let command = UserCommand::parse(
+            "-nargs=* -range -bang Foo <line1>,<line2>s/<args>/<q-args><bang>/ <lt>x> <unknown>",
+        )
+        .unwrap();
+        assert_eq!(
+            command.expand(r#"a "b""#, true, (2, 4), 2),
+            r#"2,4s/a "b"/"a \"b\""!/ <x> <unknown>"#
+        );
+        assert_eq!(
+            command.expand("a b", false, (1, 1), 0),
+            r#"1,1s/a b/"a b"/ <x> <unknown>"#
+        );
+
+        assert_eq!(command.check_invocation("", false, false), Ok(()));
+        let command = UserCommand::parse("-nargs=1 Foo e <args>").unwrap();
+        assert!(command.check_invocation("", false, false).is_err());
+        assert!(command.check_invocation("file", true, false).is_err());
+        assert!(command.check_invocation("file", false, true).is_err());
+        assert_eq!(command.check_invocation("some file", false, false), Ok(()));
+    }
+
+    #[gpui::test]
+    async fn test_user_commands(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+            ˇb b b
+            b b b
+            b b b"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes(": c o m m a n d space - n a r g s = 1 space - r a n g e space");
+        cx.simulate_keystrokes(
+            "S w a p space < l i n e 1 > , < l i n e 2 > s / b / < a r g s > / enter",
+        );
+        cx.simulate_keystrokes(": S w a p space x enter");
+        cx.assert_state(
+            indoc! {"
+            ˇx b b
+            b b b
+            b b b"},
+            Mode::Normal,
+        );
+
+        cx.simulate_keystrokes(": 2 , 3 S w a p space y enter");
+        cx.assert_state(
+            indoc! {"
+            x b b
+            y b b
+            ˇy b b"},
+            Mode::Normal,
+        );
+
+        // Unambiguous prefixes run the command
+        cx.simulate_keystrokes(": % S w space z enter");
+        cx.assert_state(
+            indoc! {"
+            x z b
+            y z b
+            ˇy z b"},
+            Mode::Normal,
+        );
+
+        // Without `!`, existing commands can't be redefined
+        cx.simulate_keystrokes(": c o m m a n d space S w a p space s / b / c / enter");
+        cx.simulate_prompt_answer("Cancel");
+        cx.simulate_keystrokes(": c o m m a n d ! space S w a p space s / b / c / enter");
+        cx.simulate_keystrokes(": S w a p enter");
+        cx.assert_state(
+            indoc! {"
+            x z b
+            y z b
+            ˇy z c"},
+            Mode::Normal,
+        );
+
+        cx.simulate_keystrokes(": d e l c space S w a p enter");
+        cx.read(|cx| assert!(cx.global::<VimGlobals>().user_commands.is_empty()));
+    }
+}
@@ -3,12 +3,14 @@
 #[cfg(test)]
 mod test;
 
+mod abbreviations;
 mod change_list;
 mod command;
 mod digraph;
 mod helix;
 mod indent;
 mod insert;
+mod key_notation;
 mod mode_indicator;
 mod motion;
 mod normal;
@@ -17,6 +19,8 @@ mod replace;
 mod rewrap;
 mod state;
 mod surrounds;
+mod user_commands;
+mod vimrc;
 mod visual;
 
 use crate::normal::paste::Paste as VimPaste;
@@ -538,6 +542,8 @@ pub(crate) struct Vim {
 
     last_command: Option<String>,
     running_command: Option<Task<()>>,
+    /// The version of the vimrc whose options were applied to this editor.
+    vimrc_generation: usize,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -597,6 +603,7 @@ impl Vim {
 
             last_command: None,
             running_command: None,
+            vimrc_generation: 0,
 
             editor: editor.downgrade(),
             _subscriptions: vec![
@@ -994,6 +1001,9 @@ impl Vim {
             visual::register(editor, cx);
             change_list::register(editor, cx);
             digraph::register(editor, cx);
+            user_commands::register(editor, cx);
+            abbreviations::register(editor, cx);
+            vimrc::register(editor, cx);
 
             if editor.is_focused(window) {
                 cx.defer_in(window, |vim, window, cx| {
@@ -1134,7 +1144,12 @@ impl Vim {
             EditorEvent::InputHandled {
                 text,
                 utf16_range_to_replace: range_to_replace,
-            } => Vim::globals(cx).observe_insertion(text, range_to_replace.clone()),
+            } => {
+                Vim::globals(cx).observe_insertion(text, range_to_replace.clone());
+                if range_to_replace.is_none() {
+                    self.expand_abbreviation(text, cx);
+                }
+            }
             EditorEvent::TransactionBegun { transaction_id } => {
                 self.transaction_begun(*transaction_id, window, cx)
             }
@@ -1540,6 +1555,17 @@ impl Vim {
         cx.emit(VimEvent::Focused);
         self.sync_vim_settings(window, cx);
 
+        let vimrc_generation = Vim::globals(cx).vimrc_generation;
+        if self.vimrc_generation != vimrc_generation
+            && self
+                .editor()
+                .is_some_and(|editor| editor.read(cx).mode().is_full())
+        {
+            self.vimrc_generation = vimrc_generation;
+            let options = Vim::globals(cx).vimrc_options.clone();
+            self.set_options(&options, cx);
+        }
+
         if VimSettings::get_global(cx).toggle_relative_line_numbers {
             if let Some(old_vim) = Vim::globals(cx).focused_vim() {
                 if old_vim.entity_id() != cx.entity().entity_id() {
@@ -0,0 +1,331 @@
+//! `:source` and the vimrc file.
+//!
+//! The vimrc (`~/.config/zed/vimrc`) is loaded on startup and whenever it
+//! changes. It can define user commands and abbreviations, and `:set` options
+//! that are applied to every editor; other commands are rejected since there's
+//! no editor to run them in. `:source` runs any ex commands from a file in the
+//! current editor.
+
+use std::{path::PathBuf, sync::Arc};
+
+use editor::Editor;
+use futures::StreamExt as _;
+use gpui::{Action, App, AsyncApp, Context, Task, WeakEntity, Window};
+use project::Fs;
+use util::paths::home_dir;
+use workspace::Workspace;
+
+use crate::{
+    Vim,
+    abbreviations::VimAbbreviate,
+    command::{MAX_EX_COMMAND_DEPTH, VimSet, command_interceptor, show_command_error},
+    state::VimGlobals,
+    user_commands::{ClearUserCommands, DefineUserCommand, DeleteUserCommand},
+};
+
+/// Runs the ex commands in a file, or in the current buffer when no file is given.
+#[derive(Clone, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct VimSource {
+    pub filename: String,
+    /// Number of user commands and sourced files this is run from.
+    pub depth: usize,
+}
+
+/// Splits a script into ex commands, with their 1-based line numbers. Blank
+/// lines and `"` comments are skipped, lines starting with `\` continue the
+/// previous one, and leading colons are removed.
+pub(crate) fn ex_command_lines(contents: &str) -> Vec<(usize, String)> {
+    let mut commands: Vec<(usize, String)> = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim_start();
+        if let Some(continuation) = line.strip_prefix('\\') {
+            if let Some((_, command)) = commands.last_mut() {
+                command.push_str(continuation);
+            }
+            continue;
+        }
+        let line = line.trim_start_matches([':', ' ', '\t']);
+        if line.is_empty() || line.starts_with('"') {
+            continue;
+        }
+        commands.push((index + 1, line.to_string()));
+    }
+    for (_, command) in &mut commands {
+        command.truncate(command.trim_end().len());
+    }
+    commands
+}
+
+/// Resolves a path given to `:source`. Relative paths are resolved against `directory`.
+fn resolve_path(filename: &str, directory: Option<PathBuf>) -> PathBuf {
+    if let Some(path) = filename.strip_prefix("~/") {
+        return home_dir().join(path);
+    }
+    match directory {
+        Some(directory) => directory.join(filename),
+        None => PathBuf::from(filename),
+    }
+}
+
+pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
+    Vim::action(editor, cx, |vim, action: &VimSource, window, cx| {
+        vim.source(action, window, cx)
+    });
+}
+
+impl Vim {
+    fn source(&mut self, action: &VimSource, window: &mut Window, cx: &mut Context<Self>) {
+        let (path, contents) = if action.filename.is_empty() {
+            let Some(contents) = self.update_editor(cx, |_, editor, cx| {
+                editor.buffer().read(cx).snapshot(cx).text()
+            }) else {
+                return;
+            };
+            (None, Task::ready(Ok(contents)))
+        } else {
+            let Some(workspace) = self.workspace(window, cx) else {
+                return;
+            };
+            let workspace = workspace.read(cx);
+            let directory = workspace
+                .project()
+                .read(cx)
+                .visible_worktrees(cx)
+                .next()
+                .map(|worktree| worktree.read(cx).abs_path().to_path_buf());
+            let path = resolve_path(&action.filename, directory);
+            let fs = workspace.app_state().fs.clone();
+            let load_path = path.clone();
+            let contents = cx.background_spawn(async move { fs.load(&load_path).await });
+            (Some(path), contents)
+        };
+
+        let depth = action.depth;
+        cx.spawn_in(window, async move |vim, cx| {
+            let source_name = path.map_or_else(
+                || "the current buffer".to_string(),
+                |path| path.display().to_string(),
+            );
+            let contents = match contents.await {
+                Ok(contents) => contents,
+                Err(error) => {
+                    let message = format!("E484: Can't open file {source_name}: {error}");
+                    cx.update(|window, cx| show_command_error(&message, window, cx))
+                        .ok();
+                    return;
+                }
+            };
+
+            let mut errors = Vec::new();
+            for (line_number, line) in ex_command_lines(&contents) {
+                let Ok(task) = vim.update_in(cx, |vim, window, cx| {
+                    vim.execute_ex_command(&line, depth + 1, window, cx)
+                }) else {
+                    return;
+                };
+                if let Err(error) = task.await {
+                    errors.push(format!("line {line_number}: {error}"));
+                }
+            }
+            if !errors.is_empty() {
+                let message = format!(
+                    "Error detected while processing {source_name}:\n{}",
+                    errors.join("\n")
+                );
+                cx.update(|window, cx| show_command_error(&message, window, cx))
+                    .ok();
+            }
+        })
+        .detach();
+    }
+}
+
+impl VimGlobals {
+    /// Loads the vimrc, and reloads it whenever it changes. Errors are shown in `workspace`.
+    pub(crate) fn watch_vimrc(&mut self, workspace: &Workspace, cx: &mut Context<Workspace>) {
+        if self.vimrc_watcher.is_some() {
+            return;
+        }
+        let fs = workspace.app_state().fs.clone();
+        let (mut contents, watcher) = settings::watch_config_file(
+            cx.background_executor(),
+            fs.clone(),
+            paths::vimrc_file().clone(),
+        );
+        self.vimrc_watcher = Some(cx.spawn(async move |workspace, cx| {
+            let _watcher = watcher;
+            let mut loaded = false;
+            while let Some(contents) = contents.next().await {
+                // Nothing to load or unload, keep what was defined interactively
+                if !loaded && contents.trim().is_empty() {
+                    continue;
+                }
+                loaded = true;
+                let errors = load_vimrc(&fs, &contents, cx).await;
+                if errors.is_empty() || !cx.update(Vim::enabled) {
+                    continue;
+                }
+                let message = format!("Failed to load vimrc:\n{}", errors.join("\n"));
+                workspace
+                    .update(cx, |workspace, cx| workspace.show_error(&message, cx))
+                    .ok();
+            }
+        }));
+    }
+}
+
+/// Replaces the user commands, abbreviations and options defined by the
+/// previous vimrc with the ones in `contents`. Returns the errors encountered.
+async fn load_vimrc(fs: &Arc<dyn Fs>, contents: &str, cx: &mut AsyncApp) -> Vec<String> {
+    cx.update(|cx| {
+        let globals = Vim::globals(cx);
+        globals.user_commands.clear();
+        globals.abbreviations = Default::default();
+        globals.vimrc_options.clear();
+        globals.vimrc_generation += 1;
+    });
+
+    let mut errors = Vec::new();
+    let mut scripts = vec![(
+        paths::vimrc_file().clone(),
+        ex_command_lines(contents).into_iter(),
+    )];
+    while let Some((path, lines)) = scripts.last_mut() {
+        let Some((line_number, line)) = lines.next() else {
+            scripts.pop();
+            continue;
+        };
+        let path = path.clone();
+        let result = cx
+            .update(|cx| command_interceptor(&line, WeakEntity::new_invalid(), cx))
+            .await;
+        let Some(item) = result.results.into_iter().next() else {
+            errors.push(format!(
+                "{}: line {line_number}: E492: Not an editor command: {line}",
+                path.display()
+            ));
+            continue;
+        };
+
+        if let Some(source) = item.action.as_any().downcast_ref::<VimSource>() {
+            let sourced_path = resolve_path(
+                &source.filename,
+                path.parent().map(|directory| directory.to_path_buf()),
+            );
+            if scripts.len() > MAX_EX_COMMAND_DEPTH {
+                errors.push(format!(
+                    "{}: line {line_number}: E169: Command too recursive",
+                    path.display()
+                ));
+            } else if source.filename.is_empty() {
+                errors.push(format!(
+                    "{}: line {line_number}: E471: Argument required",
+                    path.display()
+                ));
+            } else {
+                match fs.load(&sourced_path).await {
+                    Ok(contents) => {
+                        scripts.push((sourced_path, ex_command_lines(&contents).into_iter()))
+                    }
+                    Err(error) => errors.push(format!(
+                        "{}: line {line_number}: E484: Can't open file {}: {error}",
+                        path.display(),
+                        sourced_path.display()
+                    )),
+                }
+            }
+            continue;
+        }
+
+        if let Err(error) = cx.update(|cx| apply_vimrc_command(&line, item.action.as_ref(), cx)) {
+            errors.push(format!("{}: line {line_number}: {error}", path.display()));
+        }
+    }
+    errors
+}
+
+fn apply_vimrc_command(line: &str, action: &dyn Action, cx: &mut App) -> Result<(), String> {
+    let action = action.as_any();
+    if let Some(definition) = action.downcast_ref::<DefineUserCommand>() {
+        definition.apply(cx)
+    } else if let Some(deletion) = action.downcast_ref::<DeleteUserCommand>() {
+        deletion.apply(cx)
+    } else if action.is::<ClearUserCommands>() {
+        Vim::globals(cx).user_commands.clear();
+        Ok(())
+    } else if let Some(abbreviate) = action.downcast_ref::<VimAbbreviate>() {
+        abbreviate.apply(cx)
+    } else if let Some(set) = action.downcast_ref::<VimSet>() {
+        Vim::globals(cx)
+            .vimrc_options
+            .extend(set.options.iter().cloned());
+        Ok(())
+    } else {
+        Err(format!("Not supported in the vimrc: {line}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gpui::TestAppContext;
+    use indoc::indoc;
+    use util::path;
+
+    use super::ex_command_lines;
+    use crate::{state::Mode, test::VimTestContext};
+
+    #[test]
+    fn test_ex_command_lines() {
+        let script = indoc! {r#"
+            " comment
+            set nowrap
+
+            :command -nargs=1 Greet
+                \ s/^/<args>/
+            iabbrev teh the
+        "#};
+        assert_eq!(
+            ex_command_lines(script),
+            vec![
+                (2, "set nowrap".to_string()),
+                (4, "command -nargs=1 Greet s/^/<args>/".to_string()),
+                (6, "iabbrev teh the".to_string()),
+            ]
+        );
+    }
+
+    #[gpui::test]
+    async fn test_source(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        let fs = cx.workspace(|workspace, _, cx| workspace.project().read(cx).fs().clone());
+        fs.as_fake()
+            .insert_file(
+                path!("/root/dir/commands.vim"),
+                indoc! {r#"
+                    " Defines Upper
+                    command -range Upper <line1>,<line2>s/a/A/
+                "#}
+                .as_bytes()
+                .to_vec(),
+            )
+            .await;
+
+        cx.set_state(
+            indoc! {"
+            ˇa
+            a"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes(": s o space d i r / c o m m a n d s . v i m enter");
+        cx.run_until_parked();
+        cx.simulate_keystrokes(": % U p p e r enter");
+        cx.assert_state(
+            indoc! {"
+            A
+            ˇA"},
+            Mode::Normal,
+        );
+    }
+}
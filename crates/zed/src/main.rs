@@ -207,6 +207,16 @@ fn main() {
 
     let args = Args::parse();
 
+    // `zed --linux-sandbox` Makes zed apply the agent terminal sandbox policy
+    // to itself and exec the sandboxed command. This has to happen before any
+    // threads are spawned.
+    #[cfg(target_os = "linux")]
+    if let Some(policy_path) = &args.linux_sandbox {
+        let Err(error) = sandbox::linux_landlock::exec_sandboxed(policy_path);
+        eprintln!("zed: failed to run sandboxed command: {error:#}");
+        process::exit(1);
+    }
+
     // `zed --askpass` Makes zed operate in nc/netcat mode for use with askpass
     #[cfg(not(target_os = "windows"))]
     if let Some(socket) = &args.askpass {
@@ -740,6 +750,7 @@ fn main() {
         project_symbols::init(cx);
         project_panel::init(cx);
         outline_panel::init(cx);
+        bookmarks_panel::init(cx);
         tasks_ui::init(cx);
         snippets_ui::init(cx);
         channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
@@ -1843,6 +1854,12 @@ struct Args {
     #[arg(hide = true)]
     askpass: Option<String>,
 
+    /// Used for sandboxing agent terminal commands on Linux, by having Zed
+    /// confine itself with the policy in the given file and exec the command.
+    #[arg(long, hide = true)]
+    #[cfg(target_os = "linux")]
+    linux_sandbox: Option<PathBuf>,
+
     #[arg(long, hide = true)]
     dump_all_actions: bool,
 
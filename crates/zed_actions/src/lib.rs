@@ -862,6 +862,8 @@ pub mod notebook {
         [
             /// Opens a Jupyter notebook file.
             OpenNotebook,
+            /// Opens the active percent-format script or Markdown file as a notebook.
+            OpenAsNotebook,
             /// Runs all cells in the notebook.
             RunAll,
             /// Runs the current cell and stays on it.